@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AfkConfig {
+    /// Seconds without moving, chatting or interacting until a player is marked as AFK.
+    /// `0` disables marking players as AFK automatically
+    pub timeout_seconds: u64,
+    /// Seconds without any action until a player is kicked. `0` disables kicking
+    pub kick_timeout_seconds: u64,
+    /// Show the names of AFK players gray in the tab list
+    pub gray_in_tab_list: bool,
+    /// Leave AFK players out when counting how many players have to sleep to skip the night
+    pub ignore_for_sleeping: bool,
+}
+
+impl Default for AfkConfig {
+    fn default() -> Self {
+        Self {
+            timeout_seconds: 300,
+            kick_timeout_seconds: 0,
+            gray_in_tab_list: true,
+            ignore_for_sleeping: true,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// The messages may use `{reason}`, `{source}`, who banned the player, and `{expires}`
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct BansConfig {
+    /// Shown to banned players trying to join. Empty for the translated message of the client
+    pub message: String,
+    /// Shown to players joining from a banned IP. Empty for the translated message of the client
+    pub ip_message: String,
+    /// Used for bans without a reason
+    pub default_reason: String,
+    /// What `{expires}` becomes for permanent bans
+    pub never_expires: String,
+}
+
+impl Default for BansConfig {
+    fn default() -> Self {
+        Self {
+            message: String::new(),
+            ip_message: String::new(),
+            default_reason: "Banned by an operator.".to_string(),
+            never_expires: "never".to_string(),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct BlockUpdatesConfig {
+    /// How deep neighbor updates may cause further updates, deeper ones are dropped
+    pub max_depth: u32,
+    /// How many neighbor updates may run per tick in each world, the rest of the tick's updates
+    /// are dropped
+    pub max_per_tick: u32,
+}
+
+impl Default for BlockUpdatesConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 512,
+            // Like `max-chained-neighbor-updates` of vanilla
+            max_per_tick: 1_000_000,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use pumpkin_util::PermissionLvl;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChatConfig {
+    /// Checked against every chat message, in order
+    pub filters: Vec<ChatFilterConfig>,
+    /// Seconds a player has to wait between chat messages on startup, `0` disables slow chat.
+    /// Can be changed at runtime with `/slowchat`
+    pub slow_chat_seconds: u64,
+    /// Players with at least this permission level are not slowed down
+    pub slow_chat_bypass_level: PermissionLvl,
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            filters: Vec::new(),
+            slow_chat_seconds: 0,
+            slow_chat_bypass_level: PermissionLvl::Two,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct ChatFilterConfig {
+    /// A word (matched as a whole word, ignoring case) or a regular expression
+    pub pattern: String,
+    /// Whether `pattern` is a regular expression
+    #[serde(default)]
+    pub regex: bool,
+    pub action: ChatFilterAction,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatFilterAction {
+    /// Tell the player to watch their language, but send the message
+    Warn,
+    /// Replace the match with `*`
+    Censor,
+    /// Don't send the message at all
+    Block,
+}
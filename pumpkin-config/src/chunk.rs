@@ -2,18 +2,68 @@ use std::str;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Default, Clone)]
+#[derive(Deserialize, Serialize, Clone)]
 #[serde(default)]
 pub struct ChunkConfig {
     pub compression: ChunkCompression,
     pub format: ChunkFormat,
     pub write_in_place: bool,
+    /// Rewrites region files without the space left unused by chunks that shrank or moved when
+    /// saving them. Only matters with `write_in_place`, whole files are always written compactly
+    pub defragment_on_save: bool,
+    /// The maximum amount of chunks that can be forced loaded (e.g. by plugins) at the same time
+    pub max_forced_chunks: usize,
+    /// Threads which serialize and compress chunks when saving, 0 uses half of the cores
+    pub serialization_threads: usize,
+    /// How many chunks can wait for or be in serialization at once. Saving more waits until
+    /// there is room again
+    pub serialization_queue: usize,
+    /// Reading or writing a region file taking longer than this many milliseconds logs a
+    /// warning, 0 disables the warning
+    pub slow_io_warning_ms: u64,
+    /// When the estimated memory of loaded chunks, entities and cached region files goes above
+    /// this many MiB, chunks no player is in are unloaded right away. 0 disables the cap
+    pub memory_soft_cap_mb: u64,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            compression: ChunkCompression::default(),
+            format: ChunkFormat::default(),
+            write_in_place: false,
+            defragment_on_save: false,
+            max_forced_chunks: 256,
+            serialization_threads: 0,
+            serialization_queue: 64,
+            slow_io_warning_ms: 500,
+            memory_soft_cap_mb: 0,
+        }
+    }
 }
 
+/// The id of the zstd codec built into pumpkin-world
+const ZSTD_CODEC: &str = "pumpkin:zstd";
+
 #[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
 pub struct ChunkCompression {
     pub algorithm: Compression,
     pub level: u32,
+    /// The namespaced id of the codec to use with the `Custom` algorithm. Besides `pumpkin:zstd`
+    /// codecs have to be registered by plugins
+    pub custom_algorithm: String,
+}
+
+impl ChunkCompression {
+    /// The namespaced id of the codec chunks are written with when the algorithm is saved as
+    /// custom compression
+    pub fn custom_codec(&self) -> &str {
+        match self.algorithm {
+            Compression::Zstd => ZSTD_CODEC,
+            _ => &self.custom_algorithm,
+        }
+    }
 }
 
 impl Default for ChunkCompression {
@@ -21,6 +71,7 @@ impl Default for ChunkCompression {
         Self {
             algorithm: Compression::LZ4,
             level: 6,
+            custom_algorithm: ZSTD_CODEC.to_string(),
         }
     }
 }
@@ -35,9 +86,12 @@ pub enum Compression {
     LZ4,
     /// Custom compression algorithm (since 24w05a)
     Custom,
+    /// Zstd Compression, saved as the custom compression with the `pumpkin:zstd` codec. Vanilla
+    /// can't read chunks written with it
+    Zstd,
 }
 
-#[derive(Deserialize, Serialize, Clone, Default)]
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
 pub enum ChunkFormat {
     #[default]
     Anvil,
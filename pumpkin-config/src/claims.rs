@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ClaimsConfig {
+    /// Let players protect regions of the world from others with `/claim`
+    pub enabled: bool,
+    /// How many regions a single player may own in each world, `0` for no limit
+    pub max_claims_per_player: u32,
+    /// The most blocks a single region may contain, `0` for no limit
+    pub max_volume: u64,
+}
+
+impl Default for ClaimsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_claims_per_player: 3,
+            max_volume: 1_048_576,
+        }
+    }
+}
@@ -10,6 +10,8 @@ pub struct CommandsConfig {
     pub log_console: bool, // TODO: commands...
     /// The op permission level of everyone that is not in the ops file
     pub default_op_level: PermissionLvl,
+    /// Should commands and operator actions be written to `data/audit-log.jsonl`?
+    pub audit_log: bool,
 }
 
 impl Default for CommandsConfig {
@@ -18,6 +20,7 @@ impl Default for CommandsConfig {
             use_console: true,
             log_console: true,
             default_op_level: PermissionLvl::Zero,
+            audit_log: true,
         }
     }
 }
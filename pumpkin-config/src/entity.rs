@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityConfig {
+    /// How many workers tick entities in parallel, entities are split between them by region.
+    /// With `1` every entity is ticked on the game loop.
+    ///
+    /// Damage, spawns, removals and block changes entities cause are applied after all of them
+    /// ticked, in the same order for any amount of workers. Entities still read the position
+    /// and state of others while those are ticked on another worker, so more than `1` is not
+    /// safe yet and can make ticks turn out differently
+    pub tick_workers: usize,
+}
+
+impl Default for EntityConfig {
+    fn default() -> Self {
+        Self { tick_workers: 1 }
+    }
+}
@@ -0,0 +1,15 @@
+use std::{io, path::PathBuf};
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to create the config folder {path:?}: {source}")]
+    CreateFolder { path: PathBuf, source: io::Error },
+    #[error("Couldn't read configuration file at {path:?}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("Couldn't parse config at {path:?}: {message}")]
+    Parse { path: PathBuf, message: String },
+    #[error("Invalid config: {0}")]
+    Invalid(String),
+}
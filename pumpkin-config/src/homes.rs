@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct HomesConfig {
+    /// Let players save their own places with `/sethome` and go back with `/home`
+    pub enabled: bool,
+    /// Let operators set server-wide places everyone can go to with `/warp`
+    pub warps_enabled: bool,
+    /// How many homes players may have, the entry with the highest permission level the
+    /// player has counts
+    pub limits: Vec<HomeLimit>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+pub struct HomeLimit {
+    /// The operator permission level from 0 to 4
+    pub permission_level: u8,
+    /// 0 means unlimited
+    pub max_homes: u32,
+}
+
+impl Default for HomesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            warps_enabled: true,
+            limits: vec![
+                HomeLimit {
+                    permission_level: 0,
+                    max_homes: 3,
+                },
+                HomeLimit {
+                    permission_level: 2,
+                    max_homes: 10,
+                },
+                HomeLimit {
+                    permission_level: 4,
+                    max_homes: 0,
+                },
+            ],
+        }
+    }
+}
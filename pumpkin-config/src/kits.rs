@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Kits are off by default, like in vanilla. A starter kit handed out on the first join and a kit
+/// of healing potions look like this in `features.toml`:
+///
+/// ```toml
+/// [kits]
+/// enabled = true
+/// starter_kit = "starter"
+///
+/// [[kits.kits]]
+/// name = "starter"
+/// permission_level = 4
+/// items = [
+///     { id = "minecraft:stone_sword" },
+///     { id = "minecraft:stone_pickaxe" },
+///     { id = "minecraft:bread", count = 16 },
+/// ]
+///
+/// [[kits.kits]]
+/// name = "healer"
+/// cooldown_seconds = 600
+/// permission_level = 2
+/// items = [
+///     { id = "minecraft:splash_potion", count = 3, components = { "minecraft:potion_contents" = { potion = "minecraft:healing" } } },
+/// ]
+/// ```
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct KitsConfig {
+    /// Let players claim kits with `/kit`
+    pub enabled: bool,
+    /// The kit players get when they join for the first time, empty for none
+    pub starter_kit: String,
+    pub kits: Vec<Kit>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Kit {
+    pub name: String,
+    pub items: Vec<KitItem>,
+    /// Seconds a player has to wait until they may claim the kit again, 0 for no cooldown
+    #[serde(default)]
+    pub cooldown_seconds: u64,
+    /// The operator permission level from 0 to 4 players need to claim the kit
+    #[serde(default)]
+    pub permission_level: u8,
+}
+
+/// An item the way vanilla saves it, like `{ id = "minecraft:potion", count = 1, components =
+/// { "minecraft:potion_contents" = { potion = "minecraft:healing" } } }`
+#[derive(Deserialize, Serialize, Clone)]
+pub struct KitItem {
+    pub id: String,
+    #[serde(default = "default_count")]
+    pub count: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Map<String, Value>>,
+}
+
+const fn default_count() -> u8 {
+    1
+}
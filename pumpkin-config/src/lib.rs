@@ -1,35 +1,76 @@
 use chunk::ChunkConfig;
-use log::warn;
 use logging::LoggingConfig;
 use pumpkin_util::{Difficulty, GameMode, PermissionLvl};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
 use std::{
+    collections::BTreeMap,
     env, fs,
     net::{Ipv4Addr, SocketAddr},
     num::NonZeroU8,
     path::Path,
-    sync::LazyLock,
+    sync::{LazyLock, Mutex},
 };
 pub mod logging;
 pub mod networking;
 
 pub mod resource_pack;
 
+pub use afk::AfkConfig;
+pub use bans::BansConfig;
+pub use block_updates::BlockUpdatesConfig;
+pub use chat::{ChatConfig, ChatFilterAction, ChatFilterConfig};
+pub use claims::ClaimsConfig;
 pub use commands::CommandsConfig;
+pub use entity::EntityConfig;
+pub use error::ConfigError;
+pub use homes::{HomeLimit, HomesConfig};
+pub use kits::{Kit, KitItem, KitsConfig};
+pub use manager::ConfigManager;
+pub use motd::MotdConfig;
+pub use music::{MusicConfig, WorldMusic};
 pub use networking::auth::AuthenticationConfig;
 pub use networking::compression::CompressionConfig;
+pub use networking::http_api::HttpApiConfig;
 pub use networking::lan_broadcast::LANBroadcastConfig;
 pub use networking::rcon::RCONConfig;
 pub use pvp::PVPConfig;
-pub use server_links::ServerLinksConfig;
+pub use runtime::RuntimeConfig;
+pub use seed::SeedConfig;
+pub use server_links::{ConditionalLink, ServerLinksConfig};
+pub use slots::{JoinQueueConfig, SlotsConfig};
+pub use tpa::TpaConfig;
+pub use transfer::{TransferConfig, TransferServer};
+pub use virtual_host::{VirtualHostConfig, normalize_hostname};
+pub use webhooks::{WebhookConfig, WebhookEvent, WebhookFormat, WebhooksConfig};
+pub use whitelist::WhitelistConfig;
 
+mod afk;
+mod bans;
+mod block_updates;
+mod chat;
+mod claims;
 mod commands;
+mod entity;
+mod error;
+mod homes;
+mod kits;
+mod manager;
+mod motd;
+mod music;
 
 pub mod chunk;
 pub mod op;
 mod pvp;
+mod runtime;
+mod seed;
 mod server_links;
+mod slots;
+mod tpa;
+mod transfer;
+mod virtual_host;
+mod webhooks;
+mod whitelist;
 
 use networking::NetworkingConfig;
 use resource_pack::ResourcePackConfig;
@@ -38,18 +79,27 @@ const CONFIG_ROOT_FOLDER: &str = "config/";
 
 pub static BASIC_CONFIG: LazyLock<BasicConfiguration> = LazyLock::new(|| {
     let exec_dir = env::current_dir().unwrap();
-    BasicConfiguration::load(&exec_dir)
+    BasicConfiguration::load(&exec_dir).unwrap_or_else(|err| {
+        load_warning(format!("{err}, using the defaults"));
+        BasicConfiguration::default()
+    })
 });
 
-#[cfg(not(feature = "test_helper"))]
-static ADVANCED_CONFIG: LazyLock<AdvancedConfiguration> = LazyLock::new(|| {
+/// The advanced configuration, which can be reloaded at runtime
+pub static CONFIG_MANAGER: LazyLock<ConfigManager> = LazyLock::new(|| {
     let exec_dir = env::current_dir().unwrap();
-    AdvancedConfiguration::load(&exec_dir)
+    ConfigManager::new(
+        AdvancedConfiguration::load(&exec_dir).unwrap_or_else(|err| {
+            load_warning(format!("{err}, using the defaults"));
+            AdvancedConfiguration::default()
+        }),
+    )
 });
 
+/// The current advanced configuration. Don't keep it around for long, so reloads are picked up
 #[cfg(not(feature = "test_helper"))]
 pub fn advanced_config() -> &'static AdvancedConfiguration {
-    &ADVANCED_CONFIG
+    CONFIG_MANAGER.get()
 }
 
 // This is pretty jank but it works :(
@@ -92,6 +142,23 @@ pub struct AdvancedConfiguration {
     pub commands: CommandsConfig,
     pub pvp: PVPConfig,
     pub server_links: ServerLinksConfig,
+    pub entity: EntityConfig,
+    pub afk: AfkConfig,
+    pub chat: ChatConfig,
+    pub claims: ClaimsConfig,
+    pub tpa: TpaConfig,
+    pub homes: HomesConfig,
+    pub kits: KitsConfig,
+    pub motd: MotdConfig,
+    pub block_updates: BlockUpdatesConfig,
+    pub bans: BansConfig,
+    pub music: MusicConfig,
+    pub transfer: TransferConfig,
+    pub runtime: RuntimeConfig,
+    pub whitelist: WhitelistConfig,
+    pub webhooks: WebhooksConfig,
+    pub slots: SlotsConfig,
+    pub seed: SeedConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -113,13 +180,15 @@ pub struct BasicConfiguration {
     pub op_permission_level: PermissionLvl,
     /// Whether the Nether dimension is enabled.
     pub allow_nether: bool,
+    /// Whether the End dimension is enabled.
+    pub allow_end: bool,
     /// Whether the server is in hardcore mode.
     pub hardcore: bool,
     /// Whether online mode is enabled. Requires valid Minecraft accounts.
     pub online_mode: bool,
     /// Whether packet encryption is enabled. Required when online mode is enabled.
     pub encryption: bool,
-    /// The server's description displayed on the status screen.
+    /// The server's description displayed on the status screen, see `MotdConfig` for placeholders.
     pub motd: String,
     /// The server's ticks per second.
     pub tps: f32,
@@ -133,6 +202,20 @@ pub struct BasicConfiguration {
     pub use_favicon: bool,
     /// Path to server favicon
     pub favicon_path: String,
+    /// Overrides by the hostname clients connect with, like `[virtual_hosts."play.example.com"]`
+    pub virtual_hosts: BTreeMap<String, VirtualHostConfig>,
+}
+
+impl BasicConfiguration {
+    /// The virtual host matching the server address from a client's handshake
+    #[must_use]
+    pub fn virtual_host(&self, address: &str) -> Option<&VirtualHostConfig> {
+        let hostname = normalize_hostname(address);
+        self.virtual_hosts
+            .iter()
+            .find(|(name, _)| normalize_hostname(name) == hostname)
+            .map(|(_, host)| host)
+    }
 }
 
 impl Default for BasicConfiguration {
@@ -146,6 +229,7 @@ impl Default for BasicConfiguration {
             default_difficulty: Difficulty::Normal,
             op_permission_level: PermissionLvl::Four,
             allow_nether: true,
+            allow_end: true,
             hardcore: false,
             online_mode: true,
             encryption: true,
@@ -156,53 +240,234 @@ impl Default for BasicConfiguration {
             scrub_ips: true,
             use_favicon: true,
             favicon_path: "icon.png".to_string(),
+            virtual_hosts: BTreeMap::new(),
         }
     }
 }
 
 trait LoadConfiguration {
-    fn load(exec_dir: &Path) -> Self
+    /// Loads the configuration, creating it with the defaults if it doesn't exist. A file which
+    /// can't be parsed or is invalid doesn't stop the server, it is recovered, see `recover`.
+    /// Only a config folder or file which can't be accessed at all is an error
+    fn load(exec_dir: &Path) -> Result<Self, ConfigError>
+    where
+        Self: Sized + Default + Serialize + DeserializeOwned,
+    {
+        match Self::try_load(exec_dir) {
+            Ok(config) => Ok(config),
+            Err(ConfigError::Parse { path, message }) => {
+                load_warning(format!("Couldn't parse config at {path:?}: {message}"));
+                Ok(Self::recover(&path))
+            }
+            Err(ConfigError::Invalid(message)) => {
+                let path = exec_dir.join(CONFIG_ROOT_FOLDER).join(Self::get_path());
+                load_warning(format!("Invalid config at {path:?}: {message}"));
+                Ok(Self::recover(&path))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Like `load`, but fails on a file which can't be parsed or is invalid instead of
+    /// recovering from it
+    fn try_load(exec_dir: &Path) -> Result<Self, ConfigError>
     where
         Self: Sized + Default + Serialize + DeserializeOwned,
     {
         let config_dir = exec_dir.join(CONFIG_ROOT_FOLDER);
         if !config_dir.exists() {
             log::debug!("creating new config root folder");
-            fs::create_dir(&config_dir).expect("Failed to create Config root folder");
+            fs::create_dir(&config_dir).map_err(|source| ConfigError::CreateFolder {
+                path: config_dir.clone(),
+                source,
+            })?;
         }
         let path = config_dir.join(Self::get_path());
 
         let config = if path.exists() {
-            let file_content = fs::read_to_string(&path)
-                .unwrap_or_else(|_| panic!("Couldn't read configuration file at {:?}", &path));
-
-            toml::from_str(&file_content).unwrap_or_else(|err| {
-                panic!(
-                    "Couldn't parse config at {:?}. Reason: {}. This is is probably caused by an Config update, Just delete the old Config and start Pumpkin again",
-                    &path,
-                    err.message()
-                )
-            })
+            let file_content = fs::read_to_string(&path).map_err(|source| ConfigError::Read {
+                path: path.clone(),
+                source,
+            })?;
+
+            toml::from_str(&file_content).map_err(|err| ConfigError::Parse {
+                path: path.clone(),
+                message: err.message().to_string(),
+            })?
         } else {
             let content = Self::default();
-
-            if let Err(err) = fs::write(&path, toml::to_string(&content).unwrap()) {
-                warn!(
-                    "Couldn't write default config to {:?}. Reason: {}. This is is probably caused by an Config update, Just delete the old Config and start Pumpkin again",
-                    &path, err
-                );
-            }
-
+            write_config(&path, &content);
             content
         };
 
-        config.validate();
+        config.validate().map_err(ConfigError::Invalid)?;
+        Ok(config)
+    }
+
+    /// Backs the file up with a `.bak` extension and takes every field from it which can be read
+    /// and is valid, the rest keeps its default. The recovered configuration replaces the file
+    fn recover(path: &Path) -> Self
+    where
+        Self: Sized + Default + Serialize + DeserializeOwned,
+    {
+        let backup = path.with_extension("toml.bak");
+        match fs::copy(path, &backup) {
+            Ok(_) => load_warning(format!("Backed up the broken config to {backup:?}")),
+            Err(err) => load_warning(format!("Couldn't back up the config to {backup:?}: {err}")),
+        }
+
+        let default =
+            toml::Value::try_from(Self::default()).expect("The default config can be serialized");
+        let mut config = default.clone();
+        // Syntax errors make the whole file unreadable, wrong types and values only their field
+        match fs::read_to_string(path).map(|content| content.parse::<toml::Table>()) {
+            Ok(Ok(file)) => {
+                let mut fields = Vec::new();
+                merge_valid::<Self>(&mut config, &mut Vec::new(), &mut fields, file);
+                reset_invalid::<Self>(&mut config, &default, &mut fields);
+            }
+            _ => load_warning(format!(
+                "Couldn't read any field of {path:?}, using the defaults"
+            )),
+        }
+        let config: Self = config.try_into().unwrap_or_default();
+        write_config(path, &config);
         config
     }
 
     fn get_path() -> &'static Path;
 
-    fn validate(&self);
+    fn validate(&self) -> Result<(), String>;
+}
+
+/// Writes the configuration to the file, a failure only leaves the file as it was
+fn write_config<T: Serialize>(path: &Path, config: &T) {
+    let result = toml::to_string(config)
+        .map_err(|err| err.to_string())
+        .and_then(|content| fs::write(path, content).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        load_warning(format!("Couldn't write the config to {path:?}: {err}"));
+    }
+}
+
+static LOAD_WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Configurations are loaded before the logger exists, so their warnings are kept until then
+fn load_warning(message: String) {
+    LOAD_WARNINGS
+        .lock()
+        .expect("The config warnings were poisoned")
+        .push(message);
+}
+
+/// The warnings of loading the configurations at startup, like fields which were invalid and
+/// left at their default
+pub fn take_load_warnings() -> Vec<String> {
+    std::mem::take(
+        &mut LOAD_WARNINGS
+            .lock()
+            .expect("The config warnings were poisoned"),
+    )
+}
+
+/// Copies the fields of `file` into `config`, except for the ones `T` can't be read with.
+/// `keys` is the path of the table `file` is at, the paths of the copied fields are added to
+/// `fields`
+fn merge_valid<T: DeserializeOwned>(
+    config: &mut toml::Value,
+    keys: &mut Vec<String>,
+    fields: &mut Vec<Vec<String>>,
+    file: toml::Table,
+) {
+    for (key, value) in file {
+        keys.push(key);
+        let is_table = matches!(
+            table_at(config, keys).get(keys.last().unwrap()),
+            Some(toml::Value::Table(_))
+        );
+        match value {
+            toml::Value::Table(table) if is_table => merge_valid::<T>(config, keys, fields, table),
+            value => {
+                let default = set_field(config, keys, Some(value));
+                if config.clone().try_into::<T>().is_err() {
+                    set_field(config, keys, default);
+                    load_warning(format!(
+                        "Invalid value for {}, using the default",
+                        keys.join(".")
+                    ));
+                } else {
+                    fields.push(keys.clone());
+                }
+            }
+        }
+        keys.pop();
+    }
+}
+
+/// Resets the `fields` copied from the file which make the configuration fail
+/// `LoadConfiguration::validate`, one at a time, until it is valid
+fn reset_invalid<T: DeserializeOwned + LoadConfiguration>(
+    config: &mut toml::Value,
+    default: &toml::Value,
+    fields: &mut Vec<Vec<String>>,
+) {
+    let validate = |config: &toml::Value| match config.clone().try_into::<T>() {
+        Ok(config) => config.validate(),
+        Err(err) => Err(err.to_string()),
+    };
+    while let Err(error) = validate(config) {
+        // A field is the culprit if resetting it gets rid of the error
+        let culprit = (0..fields.len()).find(|&index| {
+            let keys = &fields[index];
+            let value = set_field(config, keys, field_at(default, keys).cloned());
+            let fixed = validate(config).err().as_ref() != Some(&error);
+            if !fixed {
+                set_field(config, keys, value);
+            }
+            fixed
+        });
+        let Some(culprit) = culprit else {
+            load_warning(format!("{error}, using the defaults"));
+            *config = default.clone();
+            return;
+        };
+        load_warning(format!(
+            "{error}, using the default for {}",
+            fields.remove(culprit).join(".")
+        ));
+    }
+}
+
+/// Sets the field at the path of `keys` to the value, removing it for `None`. Returns the value
+/// it had before
+fn set_field(
+    config: &mut toml::Value,
+    keys: &[String],
+    value: Option<toml::Value>,
+) -> Option<toml::Value> {
+    let table = table_at(config, keys);
+    let key = keys.last().unwrap().clone();
+    match value {
+        Some(value) => table.insert(key, value),
+        None => table.remove(&key),
+    }
+}
+
+/// The field at the path of `keys`
+fn field_at<'a>(config: &'a toml::Value, keys: &[String]) -> Option<&'a toml::Value> {
+    keys.iter().try_fold(config, |value, key| value.get(key))
+}
+
+/// The table holding the last of the keys
+fn table_at<'a>(config: &'a mut toml::Value, keys: &[String]) -> &'a mut toml::Table {
+    let mut table = config.as_table_mut().expect("Configurations are tables");
+    for key in &keys[..keys.len() - 1] {
+        table = table
+            .get_mut(key)
+            .and_then(toml::Value::as_table_mut)
+            .expect("Only tables of the default config are merged into");
+    }
+    table
 }
 
 impl LoadConfiguration for AdvancedConfiguration {
@@ -210,8 +475,9 @@ impl LoadConfiguration for AdvancedConfiguration {
         Path::new("features.toml")
     }
 
-    fn validate(&self) {
-        self.resource_pack.validate()
+    fn validate(&self) -> Result<(), String> {
+        self.resource_pack.validate()?;
+        self.networking.http_api.validate()
     }
 }
 
@@ -220,23 +486,140 @@ impl LoadConfiguration for BasicConfiguration {
         Path::new("configuration.toml")
     }
 
-    fn validate(&self) {
-        let min = unsafe { NonZeroU8::new_unchecked(2) };
-        let max = unsafe { NonZeroU8::new_unchecked(32) };
+    fn validate(&self) -> Result<(), String> {
+        if self.view_distance.get() < 2 {
+            return Err("View distance must be at least 2".to_string());
+        }
+        if self.view_distance.get() > 32 {
+            return Err("View distance must be less than 32".to_string());
+        }
+        if self.online_mode && !self.encryption {
+            return Err("When Online Mode is enabled, Encryption must be enabled".to_string());
+        }
+        for host in self.virtual_hosts.values() {
+            if let Some(resource_pack) = &host.resource_pack {
+                resource_pack.validate()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use temp_dir::TempDir;
 
-        assert!(
-            self.view_distance.ge(&min),
-            "View distance must be at least 2"
+    use super::{
+        AdvancedConfiguration, BasicConfiguration, CONFIG_ROOT_FOLDER, LoadConfiguration,
+        merge_valid, reset_invalid, table_at,
+    };
+
+    /// The configuration `T` recovered from the file, like `LoadConfiguration::recover` does
+    fn recover<T: Default + serde::Serialize + serde::de::DeserializeOwned + LoadConfiguration>(
+        file: &str,
+    ) -> T {
+        let default = toml::Value::try_from(T::default()).unwrap();
+        let mut config = default.clone();
+        let mut fields = Vec::new();
+        merge_valid::<T>(
+            &mut config,
+            &mut Vec::new(),
+            &mut fields,
+            file.parse().unwrap(),
         );
-        assert!(
-            self.view_distance.le(&max),
-            "View distance must be less than 32"
+        reset_invalid::<T>(&mut config, &default, &mut fields);
+        config.try_into().unwrap()
+    }
+
+    /// Writes the file into a new config folder and loads it
+    fn load_file<
+        T: Default + serde::Serialize + serde::de::DeserializeOwned + LoadConfiguration,
+    >(
+        content: &str,
+    ) -> (TempDir, T) {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join(CONFIG_ROOT_FOLDER)).unwrap();
+        fs::write(
+            dir.path().join(CONFIG_ROOT_FOLDER).join(T::get_path()),
+            content,
+        )
+        .unwrap();
+        let config = T::load(dir.path()).unwrap();
+        (dir, config)
+    }
+
+    #[test]
+    fn wrong_typed_field_keeps_the_others() {
+        let config: BasicConfiguration = recover("max_players = \"many\"\nmotd = \"Hello\"");
+        assert_eq!(
+            config.max_players,
+            BasicConfiguration::default().max_players
         );
-        if self.online_mode {
-            assert!(
-                self.encryption,
-                "When Online Mode is enabled, Encryption must be enabled"
-            )
-        }
+        assert_eq!(config.motd, "Hello");
+    }
+
+    #[test]
+    fn unknown_key_is_ignored() {
+        let config: BasicConfiguration = recover("not_a_field = 3\nhardcore = true");
+        assert!(config.hardcore);
+    }
+
+    #[test]
+    fn nested_tables_are_merged_field_by_field() {
+        let config: AdvancedConfiguration = recover(
+            "[networking.rcon]\nenabled = true\nmax_connections = -1\n\n[networking.rcon.logging]\ncommands = false",
+        );
+        let rcon = &config.networking.rcon;
+        assert!(rcon.enabled);
+        assert_eq!(rcon.max_connections, 0);
+        assert!(!rcon.logging.commands);
+    }
+
+    #[test]
+    fn table_at_nested_keys() {
+        let mut config = toml::Value::try_from(AdvancedConfiguration::default()).unwrap();
+        let keys = ["networking", "rcon", "enabled"].map(String::from);
+        let table = table_at(&mut config, &keys);
+        assert_eq!(table.get("enabled"), Some(&toml::Value::Boolean(false)));
+        assert!(table.contains_key("logging"));
+    }
+
+    #[test]
+    fn invalid_value_keeps_the_others() {
+        let (_dir, config): (_, BasicConfiguration) =
+            load_file("view_distance = 40\nonline_mode = false\nmax_players = 5");
+        assert_eq!(config.view_distance.get(), 10);
+        assert!(!config.online_mode);
+        assert_eq!(config.max_players, 5);
+    }
+
+    #[test]
+    fn every_invalid_value_is_reset() {
+        let config: AdvancedConfiguration = recover(
+            "[resource_pack]\nenabled = true\nurl = \"https://example.com/pack.zip\"\nsha1 = \"abc\"\n\n[networking.http_api]\nenabled = true\n\n[pvp]\nenabled = false",
+        );
+        assert!(!config.resource_pack.enabled);
+        assert_eq!(config.resource_pack.url, "https://example.com/pack.zip");
+        assert!(!config.networking.http_api.enabled);
+        assert!(!config.pvp.enabled);
+    }
+
+    #[test]
+    fn syntax_error_is_backed_up() {
+        let content = "max_players = = 5";
+        let (dir, config): (_, BasicConfiguration) = load_file(content);
+        assert_eq!(
+            config.max_players,
+            BasicConfiguration::default().max_players
+        );
+
+        let folder = dir.path().join(CONFIG_ROOT_FOLDER);
+        let backup = fs::read_to_string(folder.join("configuration.toml.bak")).unwrap();
+        assert_eq!(backup, content);
+        // The file is replaced with the recovered configuration
+        let file = fs::read_to_string(folder.join("configuration.toml")).unwrap();
+        assert!(toml::from_str::<BasicConfiguration>(&file).is_ok());
     }
 }
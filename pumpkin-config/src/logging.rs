@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize)]
@@ -7,6 +9,11 @@ pub struct LoggingConfig {
     pub threads: bool,
     pub color: bool,
     pub timestamp: bool,
+    /// Sends game events and their listeners to the clients, for their game event debug renderer
+    pub game_event_debug: bool,
+    /// Log levels of single modules and everything inside them, like
+    /// `"pumpkin_world::chunk" = "debug"`, the rest logs at the level of `RUST_LOG`
+    pub modules: BTreeMap<String, String>,
 }
 
 impl Default for LoggingConfig {
@@ -16,6 +23,8 @@ impl Default for LoggingConfig {
             threads: true,
             color: true,
             timestamp: true,
+            game_event_debug: false,
+            modules: BTreeMap::new(),
         }
     }
 }
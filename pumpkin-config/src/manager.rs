@@ -0,0 +1,65 @@
+use std::{
+    path::Path,
+    sync::{
+        Mutex,
+        atomic::{AtomicPtr, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
+};
+
+use crate::{AdvancedConfiguration, ConfigError, LoadConfiguration};
+
+/// Holds the current advanced configuration and swaps it for a new one on reload. Everything
+/// reading `advanced_config()` sees the new values from then on, so e.g. PVP, chat or chunk
+/// compression pick them up without a restart.
+///
+/// References to a configuration are `'static`, so replaced configurations are leaked. Reloads
+/// are rare and configurations are small, so this is fine.
+pub struct ConfigManager {
+    current: AtomicPtr<AdvancedConfiguration>,
+    subscribers: Mutex<Vec<Sender<&'static AdvancedConfiguration>>>,
+}
+
+impl ConfigManager {
+    pub(crate) fn new(config: AdvancedConfiguration) -> Self {
+        Self {
+            current: AtomicPtr::new(Box::into_raw(Box::new(config))),
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self) -> &'static AdvancedConfiguration {
+        // SAFETY: The pointer always comes from a leaked box which is never freed
+        unsafe { &*self.current.load(Ordering::Acquire) }
+    }
+
+    /// Reads `features.toml` in the config folder again. An unreadable or invalid file keeps
+    /// the current configuration
+    pub fn reload(&self, exec_dir: &Path) -> Result<&'static AdvancedConfiguration, ConfigError> {
+        let config = AdvancedConfiguration::try_load(exec_dir)?;
+        Ok(self.replace(config))
+    }
+
+    /// Makes the configuration the current one and sends it to every subscriber
+    pub fn replace(&self, config: AdvancedConfiguration) -> &'static AdvancedConfiguration {
+        let config: &'static AdvancedConfiguration = Box::leak(Box::new(config));
+        self.current
+            .store(std::ptr::from_ref(config).cast_mut(), Ordering::Release);
+        self.subscribers
+            .lock()
+            .expect("The config subscribers were poisoned")
+            .retain(|subscriber| subscriber.send(config).is_ok());
+        config
+    }
+
+    /// Receives every configuration loaded from now on, dropping the receiver unsubscribes
+    pub fn subscribe(&self) -> Receiver<&'static AdvancedConfiguration> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers
+            .lock()
+            .expect("The config subscribers were poisoned")
+            .push(sender);
+        receiver
+    }
+}
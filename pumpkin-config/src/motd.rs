@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// The MOTDs may use `{online}`, `{max}`, `{tip}` and `{hostname}`, the address the client
+/// pinged
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct MotdConfig {
+    /// MOTDs shown one after another, a new one on every ping. Empty to always show the `motd`
+    /// of the basic config
+    pub motds: Vec<String>,
+    /// Random tips for the `{tip}` placeholder
+    pub tips: Vec<String>,
+    /// MOTDs shown instead while `max_players` are online. Empty to keep showing the others
+    pub full_motds: Vec<String>,
+}
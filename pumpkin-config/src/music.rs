@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Lets themed servers play their own music instead of the one of the biomes
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct MusicConfig {
+    /// The music of every biome in a world, by dimension like `minecraft:overworld`
+    pub worlds: BTreeMap<String, WorldMusic>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct WorldMusic {
+    /// The sound event of the music, like `minecraft:music.creative`
+    pub sound: String,
+    /// The shortest the client waits between songs, in ticks
+    pub min_delay: i32,
+    /// The longest the client waits between songs, in ticks
+    pub max_delay: i32,
+    /// Stop the music already playing when entering the world
+    pub replace_current_music: bool,
+}
+
+impl Default for WorldMusic {
+    fn default() -> Self {
+        Self {
+            sound: "minecraft:music.game".to_string(),
+            min_delay: 12000,
+            max_delay: 24000,
+            replace_current_music: true,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, SocketAddr};
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct HttpApiConfig {
+    /// Whether the HTTP admin API is enabled
+    pub enabled: bool,
+    /// The network address and port where the API listens. Only local by default, put it
+    /// behind a reverse proxy with TLS to reach it from elsewhere
+    pub address: SocketAddr,
+    /// The token clients have to send as `Authorization: Bearer <token>`
+    pub token: String,
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 25580),
+            token: String::new(),
+        }
+    }
+}
+
+impl HttpApiConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.enabled && self.token.is_empty() {
+            return Err("The HTTP API needs a token when it is enabled".to_string());
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use auth::AuthenticationConfig;
+use http_api::HttpApiConfig;
 use proxy::ProxyConfig;
 use query::QueryConfig;
 use rcon::RCONConfig;
@@ -8,6 +9,7 @@ use crate::{CompressionConfig, LANBroadcastConfig};
 
 pub mod auth;
 pub mod compression;
+pub mod http_api;
 pub mod lan_broadcast;
 pub mod proxy;
 pub mod query;
@@ -21,4 +23,5 @@ pub struct NetworkingConfig {
     pub proxy: ProxyConfig,
     pub packet_compression: CompressionConfig,
     pub lan_broadcast: LANBroadcastConfig,
+    pub http_api: HttpApiConfig,
 }
@@ -15,22 +15,21 @@ pub struct ResourcePackConfig {
 }
 
 impl ResourcePackConfig {
-    pub fn validate(&self) {
+    pub fn validate(&self) -> Result<(), String> {
         if !self.enabled {
-            return;
+            return Ok(());
         }
 
-        assert_eq!(
-            !self.url.is_empty(),
-            !self.sha1.is_empty(),
-            "Resource Pack path or Sha1 hash is missing"
-        );
+        if self.url.is_empty() != self.sha1.is_empty() {
+            return Err("Resource Pack path or Sha1 hash is missing".to_string());
+        }
 
         let hash_len = self.sha1.len();
-        assert!(
-            hash_len == 40,
-            "Resource pack sha1 hash is the wrong length (should be 40, is {})",
-            hash_len
-        )
+        if hash_len != 40 {
+            return Err(format!(
+                "Resource pack sha1 hash is the wrong length (should be 40, is {hash_len})"
+            ));
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// The threads Pumpkin runs on, read once at startup
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct RuntimeConfig {
+    /// Threads of the async runtime which runs the game loop and the connections, 0 uses one per
+    /// core
+    pub worker_threads: usize,
+    /// The most threads the async runtime starts for blocking work like file IO
+    pub max_blocking_threads: usize,
+    /// Threads which read and decompress chunks and generate new ones, 0 uses one per core
+    pub rayon_threads: usize,
+    /// How many region files may be read or written at the same time, 0 doesn't limit it
+    pub io_concurrency: usize,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            worker_threads: 0,
+            // The default of tokio
+            max_blocking_threads: 512,
+            rayon_threads: 0,
+            io_concurrency: 0,
+        }
+    }
+}
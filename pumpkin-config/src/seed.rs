@@ -0,0 +1,24 @@
+use pumpkin_util::PermissionLvl;
+use serde::{Deserialize, Serialize};
+
+/// Keeps the world seed from players, who could find structures and ores with it
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct SeedConfig {
+    /// Clients are told a fake seed, including in `/seed` for players below `reveal_level`
+    pub hide: bool,
+    /// The seed shown instead of the real one, a random one is picked on every start if unset
+    pub fake_seed: Option<i64>,
+    /// The permission level needed to see the real seed with `/seed` while it's hidden
+    pub reveal_level: PermissionLvl,
+}
+
+impl Default for SeedConfig {
+    fn default() -> Self {
+        Self {
+            hide: false,
+            fake_seed: None,
+            reveal_level: PermissionLvl::Four,
+        }
+    }
+}
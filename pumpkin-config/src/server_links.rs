@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[derive(Deserialize, Serialize)]
 #[serde(default)]
 pub struct ServerLinksConfig {
     pub enabled: bool,
     pub bug_report: String,
+    pub community_guidelines: String,
     pub support: String,
     pub status: String,
     pub feedback: String,
@@ -14,7 +15,19 @@ pub struct ServerLinksConfig {
     pub forums: String,
     pub news: String,
     pub announcements: String,
-    pub custom: HashMap<String, String>,
+    /// Links with their own label, sorted by the label
+    pub custom: BTreeMap<String, String>,
+    /// Links with their own label only some players see
+    pub conditional: Vec<ConditionalLink>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct ConditionalLink {
+    pub label: String,
+    pub url: String,
+    /// The operator permission level from 0 to 4 players need to see the link
+    #[serde(default)]
+    pub permission_level: u8,
 }
 
 impl Default for ServerLinksConfig {
@@ -22,6 +35,7 @@ impl Default for ServerLinksConfig {
         Self {
             enabled: true,
             bug_report: "https://github.com/Pumpkin-MC/Pumpkin/issues".to_string(),
+            community_guidelines: "".to_string(),
             support: "".to_string(),
             status: "".to_string(),
             feedback: "".to_string(),
@@ -31,6 +45,7 @@ impl Default for ServerLinksConfig {
             news: "".to_string(),
             announcements: "".to_string(),
             custom: Default::default(),
+            conditional: Vec::new(),
         }
     }
 }
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Who may join while `max_players` are online. Operators with `bypasses_player_limit` in
+/// `ops.json` always have a reserved slot
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct SlotsConfig {
+    /// How many players with a reserved slot may join past `max_players`, 0 for no limit
+    pub reserved_slots: u32,
+    /// The names or UUIDs of the players with a reserved slot
+    pub reserved_players: Vec<String>,
+    /// Shows at most `max_players` online in the server list, so players in reserved slots
+    /// don't show up. Otherwise the reserved slots are added to the max players shown
+    pub hide_reserved: bool,
+    pub queue: JoinQueueConfig,
+}
+
+impl Default for SlotsConfig {
+    fn default() -> Self {
+        Self {
+            reserved_slots: 0,
+            reserved_players: Vec::new(),
+            hide_reserved: true,
+            queue: JoinQueueConfig::default(),
+        }
+    }
+}
+
+/// Lets players wait for a slot while the server is full, instead of turning them away
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct JoinQueueConfig {
+    pub enabled: bool,
+    /// How many players may wait at once, 0 for no limit
+    pub max_size: u32,
+}
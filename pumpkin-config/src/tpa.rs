@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct TpaConfig {
+    /// Let players ask others to teleport to them with `/tpa`
+    pub enabled: bool,
+    /// Seconds until a request nobody answered runs out
+    pub expire_seconds: u64,
+    /// Seconds a player has to wait between sending two requests
+    pub cooldown_seconds: u64,
+}
+
+impl Default for TpaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            expire_seconds: 120,
+            cooldown_seconds: 30,
+        }
+    }
+}
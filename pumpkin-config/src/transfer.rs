@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// Other servers players can be sent to with the transfer packet. They only let the players in
+/// if they accept transfers
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct TransferConfig {
+    /// Let players switch to the servers with `/server`
+    pub enabled: bool,
+    /// The server players are sent to when this one stops, empty to kick them instead
+    pub fallback: String,
+    pub servers: Vec<TransferServer>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct TransferServer {
+    pub name: String,
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+}
+
+const fn default_port() -> u16 {
+    25565
+}
+
+impl TransferConfig {
+    #[must_use]
+    pub fn server(&self, name: &str) -> Option<&TransferServer> {
+        self.servers.iter().find(|server| server.name == name)
+    }
+
+    /// The server players are sent to when this one stops, if there is one configured
+    #[must_use]
+    pub fn fallback_server(&self) -> Option<&TransferServer> {
+        if self.fallback.is_empty() {
+            return None;
+        }
+        self.server(&self.fallback)
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::resource_pack::ResourcePackConfig;
+
+/// What clients see depending on the address they connected with, so one server can front
+/// several communities. Anything left out falls back to the server-wide setting
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct VirtualHostConfig {
+    /// MOTDs rotated on every ping like the ones in `MotdConfig`, empty for the server's
+    pub motds: Vec<String>,
+    /// Path to the favicon shown to clients pinging this host
+    pub favicon_path: Option<String>,
+    /// The resource pack sent to players joining through this host
+    pub resource_pack: Option<ResourcePackConfig>,
+}
+
+/// The hostname from the handshake, without the marker Forge appends after a null byte, the
+/// trailing dot of a fully qualified name and in lowercase
+#[must_use]
+pub fn normalize_hostname(address: &str) -> String {
+    let hostname = address.split('\0').next().unwrap_or_default();
+    hostname.trim_end_matches('.').to_ascii_lowercase()
+}
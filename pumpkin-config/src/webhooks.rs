@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct WebhooksConfig {
+    pub enabled: bool,
+    /// How long to wait for a webhook to answer, in milliseconds
+    pub timeout: u64,
+    pub hooks: Vec<WebhookConfig>,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout: 5000,
+            hooks: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// The events which are posted to the webhook, all of them if empty
+    pub events: Vec<WebhookEvent>,
+    pub format: WebhookFormat,
+}
+
+impl WebhookConfig {
+    #[must_use]
+    pub fn wants(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    ServerStart,
+    ServerStop,
+    PlayerJoin,
+    PlayerLeave,
+    Crash,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// The event, a message and the player, if there is one
+    #[default]
+    Json,
+    /// Only the message, as the content of a Discord webhook
+    Discord,
+}
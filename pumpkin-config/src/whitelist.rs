@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct WhitelistConfig {
+    /// Only players in `data/whitelist.json` and operators can join
+    pub enabled: bool,
+    /// Kicks online players who aren't whitelisted (anymore) when the whitelist changes or is
+    /// reloaded
+    pub enforce: bool,
+}
@@ -8,19 +8,66 @@ pub(crate) fn build() -> TokenStream {
     let biomes: Vec<String> = serde_json::from_str(include_str!("../../assets/biome.json"))
         .expect("Failed to parse biome.json");
     let mut variants = TokenStream::new();
+    let mut type_from_name = TokenStream::new();
+    let mut type_to_name = TokenStream::new();
+    let mut type_from_id = TokenStream::new();
 
-    for status in biomes.iter() {
+    for (id, status) in biomes.iter().enumerate() {
         let full_name = format!("minecraft:{status}");
         let name = format_ident!("{}", status.to_pascal_case());
+        let id = id as u8;
         variants.extend([quote! {
             #[serde(rename = #full_name)]
             #name,
         }]);
+        type_from_name.extend(quote! {
+            #status => Some(Self::#name),
+        });
+        type_to_name.extend(quote! {
+            Self::#name => #status,
+        });
+        type_from_id.extend(quote! {
+            #id => Some(Self::#name),
+        });
     }
+    let count = biomes.len();
     quote! {
-        #[derive(Clone, Deserialize, Copy, Hash, PartialEq, Eq)]
+        #[derive(Clone, Deserialize, Copy, Hash, PartialEq, Eq, Debug)]
+        #[repr(u8)]
         pub enum Biome {
             #variants
         }
+
+        impl Biome {
+            #[doc = r" How many biomes there are in the biome registry"]
+            pub const COUNT: usize = #count;
+
+            #[doc = r" Try to parse a Biome from a resource location string"]
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #type_from_name
+                    _ => None
+                }
+            }
+
+            pub const fn to_name(&self) -> &'static str {
+                match self {
+                    #type_to_name
+                }
+            }
+
+            #[doc = r" Try to parse a Biome from its id in the biome registry"]
+            pub const fn from_id(id: u8) -> Option<Self> {
+                match id {
+                    #type_from_id
+                    _ => None
+                }
+            }
+
+            #[doc = r" The id of the biome in the biome registry, the registry is sent to the client in this order"]
+            pub const fn id(&self) -> u8 {
+                *self as u8
+            }
+        }
     }
 }
@@ -6,6 +6,8 @@ use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use syn::{Ident, LitBool, LitInt, LitStr};
 
+use crate::loot::LootTableStruct;
+
 fn const_block_name_from_block_name(block: &str) -> String {
     block.to_shouty_snake_case()
 }
@@ -391,288 +393,6 @@ impl ToTokens for BlockStateRef {
     }
 }
 
-/// These are required to be defined twice, cause serde can't deseraliz into static context for obvious reasons
-#[derive(Deserialize, Clone, Debug)]
-pub struct LootTableStruct {
-    r#type: LootTableTypeStruct,
-    random_sequence: Option<String>,
-    pools: Option<Vec<LootPoolStruct>>,
-}
-
-impl ToTokens for LootTableStruct {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let loot_table_type = self.r#type.to_token_stream();
-        let random_sequence = match &self.random_sequence {
-            Some(seq) => quote! { Some(#seq) },
-            None => quote! { None },
-        };
-        let pools = match &self.pools {
-            Some(pools) => {
-                let pool_tokens: Vec<_> = pools.iter().map(|pool| pool.to_token_stream()).collect();
-                quote! { Some(&[#(#pool_tokens),*]) }
-            }
-            None => quote! { None },
-        };
-
-        tokens.extend(quote! {
-            LootTable {
-                r#type: #loot_table_type,
-                random_sequence: #random_sequence,
-                pools: #pools,
-            }
-        });
-    }
-}
-
-#[derive(Deserialize, Clone, Debug)]
-pub struct LootPoolStruct {
-    entries: Vec<LootPoolEntryStruct>,
-    rolls: f32, // TODO
-    bonus_rolls: f32,
-}
-
-impl ToTokens for LootPoolStruct {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let entries_tokens: Vec<_> = self
-            .entries
-            .iter()
-            .map(|entry| entry.to_token_stream())
-            .collect();
-        let rolls = &self.rolls;
-        let bonus_rolls = &self.bonus_rolls;
-
-        tokens.extend(quote! {
-            LootPool {
-                entries: &[#(#entries_tokens),*],
-                rolls: #rolls,
-                bonus_rolls: #bonus_rolls,
-            }
-        });
-    }
-}
-
-#[derive(Deserialize, Clone, Debug)]
-pub struct ItemEntryStruct {
-    name: String,
-}
-
-impl ToTokens for ItemEntryStruct {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let name = LitStr::new(&self.name, Span::call_site());
-
-        tokens.extend(quote! {
-            ItemEntry {
-                name: #name,
-            }
-        });
-    }
-}
-
-#[derive(Deserialize, Clone, Debug)]
-pub struct AlternativeEntryStruct {
-    children: Vec<LootPoolEntryStruct>,
-}
-
-impl ToTokens for AlternativeEntryStruct {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let children = self.children.iter().map(|entry| entry.to_token_stream());
-
-        tokens.extend(quote! {
-            AlternativeEntry {
-                children: &[#(#children),*],
-            }
-        });
-    }
-}
-
-#[derive(Deserialize, Clone, Debug)]
-#[serde(tag = "type")]
-pub enum LootPoolEntryTypesStruct {
-    #[serde(rename = "minecraft:empty")]
-    Empty,
-    #[serde(rename = "minecraft:item")]
-    Item(ItemEntryStruct),
-    #[serde(rename = "minecraft:loot_table")]
-    LootTable,
-    #[serde(rename = "minecraft:dynamic")]
-    Dynamic,
-    #[serde(rename = "minecraft:tag")]
-    Tag,
-    #[serde(rename = "minecraft:alternatives")]
-    Alternatives(AlternativeEntryStruct),
-    #[serde(rename = "minecraft:sequence")]
-    Sequence,
-    #[serde(rename = "minecraft:group")]
-    Group,
-}
-
-impl ToTokens for LootPoolEntryTypesStruct {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        match self {
-            LootPoolEntryTypesStruct::Empty => {
-                tokens.extend(quote! { LootPoolEntryTypes::Empty });
-            }
-            LootPoolEntryTypesStruct::Item(item) => {
-                tokens.extend(quote! { LootPoolEntryTypes::Item(#item) });
-            }
-            LootPoolEntryTypesStruct::LootTable => {
-                tokens.extend(quote! { LootPoolEntryTypes::LootTable });
-            }
-            LootPoolEntryTypesStruct::Dynamic => {
-                tokens.extend(quote! { LootPoolEntryTypes::Dynamic });
-            }
-            LootPoolEntryTypesStruct::Tag => {
-                tokens.extend(quote! { LootPoolEntryTypes::Tag });
-            }
-            LootPoolEntryTypesStruct::Alternatives(alt) => {
-                tokens.extend(quote! { LootPoolEntryTypes::Alternatives(#alt) });
-            }
-            LootPoolEntryTypesStruct::Sequence => {
-                tokens.extend(quote! { LootPoolEntryTypes::Sequence });
-            }
-            LootPoolEntryTypesStruct::Group => {
-                tokens.extend(quote! { LootPoolEntryTypes::Group });
-            }
-        }
-    }
-}
-
-#[derive(Deserialize, Clone, Debug)]
-#[serde(tag = "condition")]
-pub enum LootConditionStruct {
-    #[serde(rename = "minecraft:inverted")]
-    Inverted,
-    #[serde(rename = "minecraft:any_of")]
-    AnyOf,
-    #[serde(rename = "minecraft:all_of")]
-    AllOf,
-    #[serde(rename = "minecraft:random_chance")]
-    RandomChance,
-    #[serde(rename = "minecraft:random_chance_with_enchanted_bonus")]
-    RandomChanceWithEnchantedBonus,
-    #[serde(rename = "minecraft:entity_properties")]
-    EntityProperties,
-    #[serde(rename = "minecraft:killed_by_player")]
-    KilledByPlayer,
-    #[serde(rename = "minecraft:entity_scores")]
-    EntityScores,
-    #[serde(rename = "minecraft:block_state_property")]
-    BlockStateProperty { properties: HashMap<String, String> },
-    #[serde(rename = "minecraft:match_tool")]
-    MatchTool,
-    #[serde(rename = "minecraft:table_bonus")]
-    TableBonus,
-    #[serde(rename = "minecraft:survives_explosion")]
-    SurvivesExplosion,
-    #[serde(rename = "minecraft:damage_source_properties")]
-    DamageSourceProperties,
-    #[serde(rename = "minecraft:location_check")]
-    LocationCheck,
-    #[serde(rename = "minecraft:weather_check")]
-    WeatherCheck,
-    #[serde(rename = "minecraft:reference")]
-    Reference,
-    #[serde(rename = "minecraft:time_check")]
-    TimeCheck,
-    #[serde(rename = "minecraft:value_check")]
-    ValueCheck,
-    #[serde(rename = "minecraft:enchantment_active_check")]
-    EnchantmentActiveCheck,
-}
-
-impl ToTokens for LootConditionStruct {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let name = match self {
-            LootConditionStruct::Inverted => quote! { LootCondition::Inverted },
-            LootConditionStruct::AnyOf => quote! { LootCondition::AnyOf },
-            LootConditionStruct::AllOf => quote! { LootCondition::AllOf },
-            LootConditionStruct::RandomChance => quote! { LootCondition::RandomChance },
-            LootConditionStruct::RandomChanceWithEnchantedBonus => {
-                quote! { LootCondition::RandomChanceWithEnchantedBonus }
-            }
-            LootConditionStruct::EntityProperties => quote! { LootCondition::EntityProperties },
-            LootConditionStruct::KilledByPlayer => quote! { LootCondition::KilledByPlayer },
-            LootConditionStruct::EntityScores => quote! { LootCondition::EntityScores },
-            LootConditionStruct::BlockStateProperty { properties } => {
-                let properties: Vec<_> = properties
-                    .iter()
-                    .map(|(k, v)| quote! { (#k, #v) })
-                    .collect();
-                quote! { LootCondition::BlockStateProperty { properties: &[#(#properties),*] } }
-            }
-            LootConditionStruct::MatchTool => quote! { LootCondition::MatchTool },
-            LootConditionStruct::TableBonus => quote! { LootCondition::TableBonus },
-            LootConditionStruct::SurvivesExplosion => quote! { LootCondition::SurvivesExplosion },
-            LootConditionStruct::DamageSourceProperties => {
-                quote! { LootCondition::DamageSourceProperties }
-            }
-            LootConditionStruct::LocationCheck => quote! { LootCondition::LocationCheck },
-            LootConditionStruct::WeatherCheck => quote! { LootCondition::WeatherCheck },
-            LootConditionStruct::Reference => quote! { LootCondition::Reference },
-            LootConditionStruct::TimeCheck => quote! { LootCondition::TimeCheck },
-            LootConditionStruct::ValueCheck => quote! { LootCondition::ValueCheck },
-            LootConditionStruct::EnchantmentActiveCheck => {
-                quote! { LootCondition::EnchantmentActiveCheck }
-            }
-        };
-
-        tokens.extend(name);
-    }
-}
-
-#[derive(Deserialize, Clone, Debug)]
-pub struct LootPoolEntryStruct {
-    #[serde(flatten)]
-    content: LootPoolEntryTypesStruct,
-    conditions: Option<Vec<LootConditionStruct>>,
-}
-
-impl ToTokens for LootPoolEntryStruct {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let content = &self.content;
-        let conditions_tokens = match &self.conditions {
-            Some(conds) => {
-                let cond_tokens: Vec<_> = conds.iter().map(|c| c.to_token_stream()).collect();
-                quote! { Some(&[#(#cond_tokens),*]) }
-            }
-            None => quote! { None },
-        };
-
-        tokens.extend(quote! {
-            LootPoolEntry {
-                content: #content,
-                conditions: #conditions_tokens,
-            }
-        });
-    }
-}
-
-#[derive(Deserialize, Clone, Debug)]
-#[serde(rename = "snake_case")]
-pub enum LootTableTypeStruct {
-    #[serde(rename = "minecraft:empty")]
-    /// Nothing will be dropped
-    Empty,
-    #[serde(rename = "minecraft:block")]
-    /// A Block will be dropped
-    Block,
-    #[serde(rename = "minecraft:chest")]
-    /// A Item will be dropped
-    Chest,
-}
-
-impl ToTokens for LootTableTypeStruct {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let name = match self {
-            LootTableTypeStruct::Empty => quote! { LootTableType::Empty },
-            LootTableTypeStruct::Block => quote! { LootTableType::Block },
-            LootTableTypeStruct::Chest => quote! { LootTableType::Chest },
-        };
-
-        tokens.extend(name);
-    }
-}
-
 #[derive(Deserialize, Clone, Debug)]
 pub struct Block {
     pub id: u16,
@@ -904,6 +624,7 @@ pub(crate) fn build() -> TokenStream {
                                 && s.sided_transparency == state.sided_transparency
                                 && s.replaceable == state.replaceable
                                 && s.collision_shapes == state.collision_shapes
+                                && s.is_liquid == state.is_liquid
                         })
                         .unwrap() as u16;
 
@@ -1013,6 +734,14 @@ pub(crate) fn build() -> TokenStream {
         .map(|entity_type| LitStr::new(entity_type, Span::call_site()));
 
     // Generate constants and match arms for each block
+    // State ids are contiguous, so the partial state of every state can be looked up by index
+    let mut state_indices = vec![0u16; blocks_assets.blocks.iter().map(|b| b.states.len()).sum()];
+    for (_, block) in &optimized_blocks {
+        for state in &block.states {
+            state_indices[state.id as usize] = state.state_idx;
+        }
+    }
+
     for (name, block) in optimized_blocks {
         let const_ident = format_ident!("{}", const_block_name_from_block_name(&name));
         let block_tokens = block.to_token_stream();
@@ -1124,6 +853,12 @@ pub(crate) fn build() -> TokenStream {
             pub max: [f64; 3],
         }
 
+        impl CollisionShape {
+            pub fn is_full_cube(&self) -> bool {
+                self.min == [0.0; 3] && self.max == [1.0; 3]
+            }
+        }
+
         #[derive(Clone, Copy, Debug)]
         pub struct BlockStateData {
             pub air: bool,
@@ -1178,6 +913,11 @@ pub(crate) fn build() -> TokenStream {
             #(#unique_states),*
         ];
 
+        #[doc = r" The index into `BLOCK_STATES` of every state id"]
+        pub static STATE_INDICES: &[u16] = &[
+            #(#state_indices),*
+        ];
+
         pub static BLOCK_ENTITY_TYPES: &[&str] = &[
             #(#block_entity_types),*
         ];
@@ -1241,6 +981,19 @@ pub(crate) fn build() -> TokenStream {
 
         #(#block_props)*
 
+        impl PartialBlockState {
+            #[doc = r" Gets the shared data of a state id without looking up its block"]
+            pub fn from_state_id(id: u16) -> Option<&'static Self> {
+                let index = *STATE_INDICES.get(id as usize)?;
+                Some(&BLOCK_STATES[index as usize])
+            }
+
+            #[doc = r" The collision boxes of the state, relative to its block position"]
+            pub fn collision_shapes(&self) -> impl Iterator<Item = &'static CollisionShape> {
+                self.collision_shapes.iter().map(|&shape| &COLLISION_SHAPES[shape as usize])
+            }
+        }
+
         impl BlockStateRef {
             pub fn get_state(&self) -> BlockState {
                 let partial_state = &BLOCK_STATES[self.state_idx as usize];
@@ -14,10 +14,12 @@ mod entity_type;
 mod fluid;
 mod game_event;
 mod item;
+mod loot;
 mod message_type;
 mod noise_parameter;
 mod packet;
 mod particle;
+mod potion;
 mod scoreboard_slot;
 mod screen;
 mod sound;
@@ -47,6 +49,7 @@ pub fn main() {
     write_generated_file(item::build(), "item.rs");
     write_generated_file(fluid::build(), "fluid.rs");
     write_generated_file(status_effect::build(), "status_effect.rs");
+    write_generated_file(potion::build(), "potion.rs");
     write_generated_file(entity_status::build(), "entity_status.rs");
     write_generated_file(block::build(), "block.rs");
     write_generated_file(tag::build(), "tag.rs");
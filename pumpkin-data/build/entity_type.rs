@@ -5,6 +5,8 @@ use quote::{ToTokens, format_ident, quote};
 use serde::Deserialize;
 use syn::LitInt;
 
+use crate::loot::LootTableStruct;
+
 #[derive(Deserialize)]
 pub struct EntityType {
     pub id: u16,
@@ -14,6 +16,7 @@ pub struct EntityType {
     pub fire_immune: bool,
     pub dimension: [f32; 2],
     pub eye_height: f32,
+    pub loot_table: Option<LootTableStruct>,
 }
 
 pub struct NamedEntityType<'a>(&'a str, &'a EntityType);
@@ -66,6 +69,7 @@ pub(crate) fn build() -> TokenStream {
     let mut consts = TokenStream::new();
     let mut type_from_raw_id_arms = TokenStream::new();
     let mut type_from_name = TokenStream::new();
+    let mut loot_table_arms = TokenStream::new();
 
     for (name, entity) in json.iter() {
         let id = entity.id as u8;
@@ -85,8 +89,16 @@ pub(crate) fn build() -> TokenStream {
         type_from_name.extend(quote! {
             #name => Some(Self::#upper_name),
         });
+
+        if let Some(loot_table) = &entity.loot_table {
+            loot_table_arms.extend(quote! {
+                #id_lit => Some(&#loot_table),
+            });
+        }
     }
     quote! {
+        use pumpkin_util::loot_table::*;
+
         #[derive(Clone, Copy, Debug, PartialEq)]
         pub struct EntityType {
             pub id: u16,
@@ -115,6 +127,14 @@ pub(crate) fn build() -> TokenStream {
                     _ => None
                 }
             }
+
+            /// What the entity drops once it dies
+            pub fn loot_table(&self) -> Option<&'static LootTable> {
+                match self.id {
+                    #loot_table_arms
+                    _ => None
+                }
+            }
         }
     }
 }
@@ -1,5 +1,6 @@
+use heck::ToPascalCase;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 
 use crate::array_to_tokenstream;
 
@@ -10,10 +11,47 @@ pub(crate) fn build() -> TokenStream {
         serde_json::from_str(include_str!("../../assets/game_event.json"))
             .expect("Failed to parse game_event.json");
     let variants = array_to_tokenstream(&game_events);
+    let type_from_name = &game_events
+        .iter()
+        .map(|event| {
+            let name = format_ident!("{}", event.to_pascal_case());
+
+            quote! {
+                #event => Some(Self::#name),
+            }
+        })
+        .collect::<TokenStream>();
+
+    let type_to_name = &game_events
+        .iter()
+        .map(|event| {
+            let name = format_ident!("{}", event.to_pascal_case());
+
+            quote! {
+                Self::#name => #event,
+            }
+        })
+        .collect::<TokenStream>();
 
     quote! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
         pub enum GameEvent {
             #variants
         }
+
+        impl GameEvent {
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #type_from_name
+                    _ => None
+                }
+            }
+
+            pub const fn to_name(&self) -> &'static str {
+                match self {
+                    #type_to_name
+                }
+            }
+        }
     }
 }
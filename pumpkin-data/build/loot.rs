@@ -0,0 +1,613 @@
+use std::collections::HashMap;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use serde::Deserialize;
+use syn::LitStr;
+
+fn optional_slice<T: ToTokens>(items: &Option<Vec<T>>) -> TokenStream {
+    match items {
+        Some(items) => quote! { Some(&[#(#items),*]) },
+        None => quote! { None },
+    }
+}
+
+fn optional_float(value: Option<f32>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+/// These are required to be defined twice, cause serde can't deseraliz into static context for obvious reasons
+#[derive(Deserialize, Clone, Debug)]
+pub struct LootTableStruct {
+    r#type: LootTableTypeStruct,
+    random_sequence: Option<String>,
+    pools: Option<Vec<LootPoolStruct>>,
+    functions: Option<Vec<LootFunctionStruct>>,
+}
+
+impl ToTokens for LootTableStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let loot_table_type = self.r#type.to_token_stream();
+        let random_sequence = match &self.random_sequence {
+            Some(seq) => quote! { Some(#seq) },
+            None => quote! { None },
+        };
+        let pools = optional_slice(&self.pools);
+        let functions = optional_slice(&self.functions);
+
+        tokens.extend(quote! {
+            LootTable {
+                r#type: #loot_table_type,
+                random_sequence: #random_sequence,
+                pools: #pools,
+                functions: #functions,
+            }
+        });
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LootPoolStruct {
+    entries: Vec<LootPoolEntryStruct>,
+    conditions: Option<Vec<LootConditionStruct>>,
+    functions: Option<Vec<LootFunctionStruct>>,
+    rolls: LootNumberProviderStruct,
+    bonus_rolls: f32,
+}
+
+impl ToTokens for LootPoolStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let entries_tokens: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| entry.to_token_stream())
+            .collect();
+        let conditions = optional_slice(&self.conditions);
+        let functions = optional_slice(&self.functions);
+        let rolls = &self.rolls;
+        let bonus_rolls = &self.bonus_rolls;
+
+        tokens.extend(quote! {
+            LootPool {
+                entries: &[#(#entries_tokens),*],
+                conditions: #conditions,
+                functions: #functions,
+                rolls: #rolls,
+                bonus_rolls: #bonus_rolls,
+            }
+        });
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemEntryStruct {
+    name: String,
+}
+
+impl ToTokens for ItemEntryStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = LitStr::new(&self.name, Span::call_site());
+
+        tokens.extend(quote! {
+            ItemEntry {
+                name: #name,
+            }
+        });
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AlternativeEntryStruct {
+    children: Vec<LootPoolEntryStruct>,
+}
+
+impl ToTokens for AlternativeEntryStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let children = self.children.iter().map(|entry| entry.to_token_stream());
+
+        tokens.extend(quote! {
+            AlternativeEntry {
+                children: &[#(#children),*],
+            }
+        });
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TagEntryStruct {
+    name: String,
+    #[serde(default)]
+    expand: bool,
+}
+
+impl ToTokens for TagEntryStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = LitStr::new(&self.name, Span::call_site());
+        let expand = self.expand;
+
+        tokens.extend(quote! {
+            TagEntry {
+                name: #name,
+                expand: #expand,
+            }
+        });
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum LootPoolEntryTypesStruct {
+    #[serde(rename = "minecraft:empty")]
+    Empty,
+    #[serde(rename = "minecraft:item")]
+    Item(ItemEntryStruct),
+    #[serde(rename = "minecraft:loot_table")]
+    LootTable,
+    #[serde(rename = "minecraft:dynamic")]
+    Dynamic,
+    #[serde(rename = "minecraft:tag")]
+    Tag(TagEntryStruct),
+    #[serde(rename = "minecraft:alternatives")]
+    Alternatives(AlternativeEntryStruct),
+    #[serde(rename = "minecraft:sequence")]
+    Sequence,
+    #[serde(rename = "minecraft:group")]
+    Group,
+}
+
+impl ToTokens for LootPoolEntryTypesStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            LootPoolEntryTypesStruct::Empty => {
+                tokens.extend(quote! { LootPoolEntryTypes::Empty });
+            }
+            LootPoolEntryTypesStruct::Item(item) => {
+                tokens.extend(quote! { LootPoolEntryTypes::Item(#item) });
+            }
+            LootPoolEntryTypesStruct::LootTable => {
+                tokens.extend(quote! { LootPoolEntryTypes::LootTable });
+            }
+            LootPoolEntryTypesStruct::Dynamic => {
+                tokens.extend(quote! { LootPoolEntryTypes::Dynamic });
+            }
+            LootPoolEntryTypesStruct::Tag(tag) => {
+                tokens.extend(quote! { LootPoolEntryTypes::Tag(#tag) });
+            }
+            LootPoolEntryTypesStruct::Alternatives(alt) => {
+                tokens.extend(quote! { LootPoolEntryTypes::Alternatives(#alt) });
+            }
+            LootPoolEntryTypesStruct::Sequence => {
+                tokens.extend(quote! { LootPoolEntryTypes::Sequence });
+            }
+            LootPoolEntryTypesStruct::Group => {
+                tokens.extend(quote! { LootPoolEntryTypes::Group });
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemPredicateStruct {
+    items: Option<String>,
+    #[serde(default)]
+    predicates: ItemSubPredicatesStruct,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ItemSubPredicatesStruct {
+    #[serde(rename = "minecraft:enchantments", default)]
+    enchantments: Vec<EnchantmentPredicateStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnchantmentPredicateStruct {
+    enchantments: String,
+    levels: Option<LevelsStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LevelsStruct {
+    min: Option<i32>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum EnchantedChanceStruct {
+    Constant(f32),
+    Linear {
+        base: f32,
+        per_level_above_first: f32,
+    },
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "condition")]
+pub enum LootConditionStruct {
+    #[serde(rename = "minecraft:inverted")]
+    Inverted { term: Box<LootConditionStruct> },
+    #[serde(rename = "minecraft:any_of")]
+    AnyOf { terms: Vec<LootConditionStruct> },
+    #[serde(rename = "minecraft:all_of")]
+    AllOf { terms: Vec<LootConditionStruct> },
+    #[serde(rename = "minecraft:random_chance")]
+    RandomChance { chance: f32 },
+    #[serde(rename = "minecraft:random_chance_with_enchanted_bonus")]
+    RandomChanceWithEnchantedBonus {
+        enchantment: String,
+        unenchanted_chance: f32,
+        enchanted_chance: EnchantedChanceStruct,
+    },
+    #[serde(rename = "minecraft:entity_properties")]
+    EntityProperties,
+    #[serde(rename = "minecraft:killed_by_player")]
+    KilledByPlayer,
+    #[serde(rename = "minecraft:entity_scores")]
+    EntityScores,
+    #[serde(rename = "minecraft:block_state_property")]
+    BlockStateProperty { properties: HashMap<String, String> },
+    #[serde(rename = "minecraft:match_tool")]
+    MatchTool { predicate: ItemPredicateStruct },
+    #[serde(rename = "minecraft:table_bonus")]
+    TableBonus {
+        enchantment: String,
+        chances: Vec<f32>,
+    },
+    #[serde(rename = "minecraft:survives_explosion")]
+    SurvivesExplosion,
+    #[serde(rename = "minecraft:damage_source_properties")]
+    DamageSourceProperties,
+    #[serde(rename = "minecraft:location_check")]
+    LocationCheck,
+    #[serde(rename = "minecraft:weather_check")]
+    WeatherCheck,
+    #[serde(rename = "minecraft:reference")]
+    Reference,
+    #[serde(rename = "minecraft:time_check")]
+    TimeCheck,
+    #[serde(rename = "minecraft:value_check")]
+    ValueCheck,
+    #[serde(rename = "minecraft:enchantment_active_check")]
+    EnchantmentActiveCheck,
+}
+
+impl ToTokens for LootConditionStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = match self {
+            LootConditionStruct::Inverted { term } => quote! { LootCondition::Inverted(&#term) },
+            LootConditionStruct::AnyOf { terms } => {
+                quote! { LootCondition::AnyOf(&[#(#terms),*]) }
+            }
+            LootConditionStruct::AllOf { terms } => {
+                quote! { LootCondition::AllOf(&[#(#terms),*]) }
+            }
+            LootConditionStruct::RandomChance { chance } => {
+                quote! { LootCondition::RandomChance { chance: #chance } }
+            }
+            LootConditionStruct::RandomChanceWithEnchantedBonus {
+                enchantment,
+                unenchanted_chance,
+                enchanted_chance,
+            } => {
+                let (base, per_level_above_first) = match enchanted_chance {
+                    EnchantedChanceStruct::Constant(chance) => (*chance, 0.0),
+                    EnchantedChanceStruct::Linear {
+                        base,
+                        per_level_above_first,
+                    } => (*base, *per_level_above_first),
+                };
+                quote! {
+                    LootCondition::RandomChanceWithEnchantedBonus {
+                        enchantment: #enchantment,
+                        unenchanted_chance: #unenchanted_chance,
+                        base: #base,
+                        per_level_above_first: #per_level_above_first,
+                    }
+                }
+            }
+            LootConditionStruct::EntityProperties => quote! { LootCondition::EntityProperties },
+            LootConditionStruct::KilledByPlayer => quote! { LootCondition::KilledByPlayer },
+            LootConditionStruct::EntityScores => quote! { LootCondition::EntityScores },
+            LootConditionStruct::BlockStateProperty { properties } => {
+                let properties: Vec<_> = properties
+                    .iter()
+                    .map(|(k, v)| quote! { (#k, #v) })
+                    .collect();
+                quote! { LootCondition::BlockStateProperty { properties: &[#(#properties),*] } }
+            }
+            LootConditionStruct::MatchTool { predicate } => {
+                let items = match &predicate.items {
+                    Some(items) => quote! { Some(#items) },
+                    None => quote! { None },
+                };
+                let enchantments: Vec<_> = predicate
+                    .predicates
+                    .enchantments
+                    .iter()
+                    .map(|enchantment| {
+                        let name = &enchantment.enchantments;
+                        let min = enchantment
+                            .levels
+                            .as_ref()
+                            .and_then(|levels| levels.min)
+                            .unwrap_or(1);
+                        quote! { (#name, #min) }
+                    })
+                    .collect();
+                quote! {
+                    LootCondition::MatchTool {
+                        items: #items,
+                        enchantments: &[#(#enchantments),*],
+                    }
+                }
+            }
+            LootConditionStruct::TableBonus {
+                enchantment,
+                chances,
+            } => {
+                quote! {
+                    LootCondition::TableBonus {
+                        enchantment: #enchantment,
+                        chances: &[#(#chances),*],
+                    }
+                }
+            }
+            LootConditionStruct::SurvivesExplosion => quote! { LootCondition::SurvivesExplosion },
+            LootConditionStruct::DamageSourceProperties => {
+                quote! { LootCondition::DamageSourceProperties }
+            }
+            LootConditionStruct::LocationCheck => quote! { LootCondition::LocationCheck },
+            LootConditionStruct::WeatherCheck => quote! { LootCondition::WeatherCheck },
+            LootConditionStruct::Reference => quote! { LootCondition::Reference },
+            LootConditionStruct::TimeCheck => quote! { LootCondition::TimeCheck },
+            LootConditionStruct::ValueCheck => quote! { LootCondition::ValueCheck },
+            LootConditionStruct::EnchantmentActiveCheck => {
+                quote! { LootCondition::EnchantmentActiveCheck }
+            }
+        };
+
+        tokens.extend(name);
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LootPoolEntryStruct {
+    #[serde(flatten)]
+    content: LootPoolEntryTypesStruct,
+    conditions: Option<Vec<LootConditionStruct>>,
+    functions: Option<Vec<LootFunctionStruct>>,
+    weight: Option<i32>,
+}
+
+impl ToTokens for LootPoolEntryStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let content = &self.content;
+        let conditions_tokens = optional_slice(&self.conditions);
+        let functions_tokens = optional_slice(&self.functions);
+        let weight = self.weight.unwrap_or(1);
+
+        tokens.extend(quote! {
+            LootPoolEntry {
+                content: #content,
+                conditions: #conditions_tokens,
+                functions: #functions_tokens,
+                weight: #weight,
+            }
+        });
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LimitStruct {
+    min: Option<f32>,
+    max: Option<f32>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BonusParametersStruct {
+    #[serde(rename = "bonusMultiplier")]
+    bonus_multiplier: Option<i32>,
+    extra: Option<i32>,
+    probability: Option<f32>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "function")]
+pub enum LootFunctionTypesStruct {
+    #[serde(rename = "minecraft:set_count")]
+    SetCount {
+        count: LootNumberProviderStruct,
+        #[serde(default)]
+        add: bool,
+    },
+    #[serde(rename = "minecraft:limit_count")]
+    LimitCount { limit: LimitStruct },
+    #[serde(rename = "minecraft:apply_bonus")]
+    ApplyBonus {
+        enchantment: String,
+        formula: String,
+        parameters: Option<BonusParametersStruct>,
+    },
+    #[serde(rename = "minecraft:enchanted_count_increase")]
+    EnchantedCountIncrease {
+        enchantment: String,
+        count: LootNumberProviderStruct,
+        #[serde(default)]
+        limit: i32,
+    },
+    #[serde(rename = "minecraft:explosion_decay")]
+    ExplosionDecay,
+    #[serde(rename = "minecraft:furnace_smelt")]
+    FurnaceSmelt,
+    #[serde(rename = "minecraft:copy_components")]
+    CopyComponents,
+    #[serde(rename = "minecraft:copy_state")]
+    CopyState,
+    #[serde(rename = "minecraft:set_potion")]
+    SetPotion,
+    #[serde(rename = "minecraft:set_ominous_bottle_amplifier")]
+    SetOminousBottleAmplifier,
+}
+
+impl ToTokens for LootFunctionTypesStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = match self {
+            LootFunctionTypesStruct::SetCount { count, add } => {
+                quote! { LootFunctionTypes::SetCount { count: #count, add: #add } }
+            }
+            LootFunctionTypesStruct::LimitCount { limit } => {
+                let min = optional_float(limit.min);
+                let max = optional_float(limit.max);
+                quote! { LootFunctionTypes::LimitCount { min: #min, max: #max } }
+            }
+            LootFunctionTypesStruct::ApplyBonus {
+                enchantment,
+                formula,
+                parameters,
+            } => {
+                let parameters = parameters.as_ref();
+                let formula = match formula.as_str() {
+                    "minecraft:ore_drops" => quote! { BonusFormula::OreDrops },
+                    "minecraft:uniform_bonus_count" => {
+                        let bonus_multiplier = parameters
+                            .and_then(|parameters| parameters.bonus_multiplier)
+                            .unwrap_or(1);
+                        quote! { BonusFormula::UniformBonusCount { bonus_multiplier: #bonus_multiplier } }
+                    }
+                    "minecraft:binomial_with_bonus_count" => {
+                        let extra = parameters
+                            .and_then(|parameters| parameters.extra)
+                            .unwrap_or(0);
+                        let probability = parameters
+                            .and_then(|parameters| parameters.probability)
+                            .unwrap_or(0.5);
+                        quote! {
+                            BonusFormula::BinomialWithBonusCount {
+                                extra: #extra,
+                                probability: #probability,
+                            }
+                        }
+                    }
+                    formula => panic!("Unknown bonus formula {formula}"),
+                };
+                quote! {
+                    LootFunctionTypes::ApplyBonus {
+                        enchantment: #enchantment,
+                        formula: #formula,
+                    }
+                }
+            }
+            LootFunctionTypesStruct::EnchantedCountIncrease {
+                enchantment,
+                count,
+                limit,
+            } => {
+                quote! {
+                    LootFunctionTypes::EnchantedCountIncrease {
+                        enchantment: #enchantment,
+                        count: #count,
+                        limit: #limit,
+                    }
+                }
+            }
+            LootFunctionTypesStruct::ExplosionDecay => quote! { LootFunctionTypes::ExplosionDecay },
+            LootFunctionTypesStruct::FurnaceSmelt => quote! { LootFunctionTypes::FurnaceSmelt },
+            LootFunctionTypesStruct::CopyComponents => quote! { LootFunctionTypes::CopyComponents },
+            LootFunctionTypesStruct::CopyState => quote! { LootFunctionTypes::CopyState },
+            LootFunctionTypesStruct::SetPotion => quote! { LootFunctionTypes::SetPotion },
+            LootFunctionTypesStruct::SetOminousBottleAmplifier => {
+                quote! { LootFunctionTypes::SetOminousBottleAmplifier }
+            }
+        };
+
+        tokens.extend(name);
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LootFunctionStruct {
+    #[serde(flatten)]
+    content: LootFunctionTypesStruct,
+    conditions: Option<Vec<LootConditionStruct>>,
+}
+
+impl ToTokens for LootFunctionStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let content = &self.content;
+        let conditions = optional_slice(&self.conditions);
+
+        tokens.extend(quote! {
+            LootFunction {
+                content: #content,
+                conditions: #conditions,
+            }
+        });
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum LootNumberProviderObjectStruct {
+    #[serde(rename = "minecraft:uniform")]
+    Uniform { min: f32, max: f32 },
+    #[serde(rename = "minecraft:binomial")]
+    Binomial { n: f32, p: f32 },
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum LootNumberProviderStruct {
+    Constant(f32),
+    Object(LootNumberProviderObjectStruct),
+}
+
+impl ToTokens for LootNumberProviderStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let provider = match self {
+            LootNumberProviderStruct::Constant(value) => {
+                quote! { LootNumberProvider::Constant(#value) }
+            }
+            LootNumberProviderStruct::Object(LootNumberProviderObjectStruct::Uniform {
+                min,
+                max,
+            }) => quote! { LootNumberProvider::Uniform { min: #min, max: #max } },
+            LootNumberProviderStruct::Object(LootNumberProviderObjectStruct::Binomial { n, p }) => {
+                quote! { LootNumberProvider::Binomial { n: #n, p: #p } }
+            }
+        };
+
+        tokens.extend(provider);
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename = "snake_case")]
+pub enum LootTableTypeStruct {
+    #[serde(rename = "minecraft:empty")]
+    /// Nothing will be dropped
+    Empty,
+    #[serde(rename = "minecraft:block")]
+    /// A Block will be dropped
+    Block,
+    #[serde(rename = "minecraft:chest")]
+    /// A Item will be dropped
+    Chest,
+    #[serde(rename = "minecraft:entity")]
+    /// An Entity died
+    Entity,
+}
+
+impl ToTokens for LootTableTypeStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = match self {
+            LootTableTypeStruct::Empty => quote! { LootTableType::Empty },
+            LootTableTypeStruct::Block => quote! { LootTableType::Block },
+            LootTableTypeStruct::Chest => quote! { LootTableType::Chest },
+            LootTableTypeStruct::Entity => quote! { LootTableType::Entity },
+        };
+
+        tokens.extend(name);
+    }
+}
@@ -0,0 +1,69 @@
+use heck::ToPascalCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=../assets/potions.json");
+
+    let potions: Vec<String> = serde_json::from_str(include_str!("../../assets/potions.json"))
+        .expect("Failed to parse potions.json");
+    let mut variants = TokenStream::new();
+    let mut type_from_name = TokenStream::new();
+    let mut type_to_name = TokenStream::new();
+    let mut type_from_id = TokenStream::new();
+
+    for (id, potion) in potions.iter().enumerate() {
+        let const_ident = format_ident!("{}", potion.to_pascal_case());
+        let resource_name = potion.to_lowercase();
+        let id = id as u16;
+
+        variants.extend([quote! {
+            #const_ident,
+        }]);
+        type_from_name.extend(quote! {
+            #resource_name => Some(Self::#const_ident),
+        });
+        type_to_name.extend(quote! {
+            Self::#const_ident => #resource_name,
+        });
+        type_from_id.extend(quote! {
+            #id => Some(Self::#const_ident),
+        });
+    }
+    quote! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[repr(u16)]
+        pub enum Potion {
+            #variants
+        }
+
+        impl Potion {
+            #[doc = r" Try to parse a Potion from a resource location string"]
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #type_from_name
+                    _ => None
+                }
+            }
+
+            pub const fn to_name(&self) -> &'static str {
+                match self {
+                    #type_to_name
+                }
+            }
+
+            #[doc = r" Try to parse a Potion from its id in the potion registry"]
+            pub const fn from_id(id: u16) -> Option<Self> {
+                match id {
+                    #type_from_id
+                    _ => None
+                }
+            }
+
+            #[doc = r" The id of the potion in the potion registry"]
+            pub const fn id(&self) -> u16 {
+                *self as u16
+            }
+        }
+    }
+}
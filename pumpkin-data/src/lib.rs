@@ -16,6 +16,10 @@ pub mod particle {
     include!(concat!(env!("OUT_DIR"), "/particle.rs"));
 }
 
+pub mod potion {
+    include!(concat!(env!("OUT_DIR"), "/potion.rs"));
+}
+
 pub mod sound {
     include!(concat!(env!("OUT_DIR"), "/sound.rs"));
     include!(concat!(env!("OUT_DIR"), "/sound_category.rs"));
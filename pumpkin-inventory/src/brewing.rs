@@ -0,0 +1,251 @@
+use pumpkin_data::item::Item;
+use pumpkin_data::potion::Potion;
+use pumpkin_data::screen::WindowType;
+use pumpkin_world::item::ItemStack;
+
+use crate::Container;
+use crate::window_property::{BrewingStand as BrewingStandProperty, WindowProperty};
+
+/// Ticks it takes to brew a batch of potions
+const BREW_TIME: u16 = 400;
+/// How many batches a single blaze powder brews
+const FUEL_PER_BLAZE_POWDER: u16 = 20;
+
+/// What brewing the potion with the ingredient turns it into, like an awkward potion with sugar
+/// into a potion of swiftness
+fn potion_mix(potion: Potion, ingredient: &str) -> Option<Potion> {
+    use Potion::*;
+
+    // Most effects are brewed from an awkward potion, their ingredient makes water mundane
+    let start_mix = match ingredient {
+        "breeze_rod" => Some(WindCharged),
+        "slime_block" => Some(Oozing),
+        "stone" => Some(Infested),
+        "cobweb" => Some(Weaving),
+        "magma_cream" => Some(FireResistance),
+        "rabbit_foot" => Some(Leaping),
+        "sugar" => Some(Swiftness),
+        "glistering_melon_slice" => Some(Healing),
+        "spider_eye" => Some(Poison),
+        "ghast_tear" => Some(Regeneration),
+        "blaze_powder" => Some(Strength),
+        _ => None,
+    };
+    if let Some(effect) = start_mix {
+        return match potion {
+            Water => Some(Mundane),
+            Awkward => Some(effect),
+            _ => None,
+        };
+    }
+
+    Some(match (potion, ingredient) {
+        (Water, "glowstone_dust") => Thick,
+        (Water, "redstone") => Mundane,
+        (Water, "nether_wart") => Awkward,
+        (Water, "fermented_spider_eye") => Weakness,
+        (Awkward, "golden_carrot") => NightVision,
+        (Awkward, "turtle_helmet") => TurtleMaster,
+        (Awkward, "pufferfish") => WaterBreathing,
+        (Awkward, "phantom_membrane") => SlowFalling,
+        (NightVision, "redstone") => LongNightVision,
+        (NightVision, "fermented_spider_eye") => Invisibility,
+        (LongNightVision, "fermented_spider_eye") => LongInvisibility,
+        (Invisibility, "redstone") => LongInvisibility,
+        (FireResistance, "redstone") => LongFireResistance,
+        (Leaping, "redstone") => LongLeaping,
+        (Leaping, "glowstone_dust") => StrongLeaping,
+        (Leaping | Swiftness, "fermented_spider_eye") => Slowness,
+        (LongLeaping | LongSwiftness, "fermented_spider_eye") => LongSlowness,
+        (Slowness, "redstone") => LongSlowness,
+        (Slowness, "glowstone_dust") => StrongSlowness,
+        (TurtleMaster, "redstone") => LongTurtleMaster,
+        (TurtleMaster, "glowstone_dust") => StrongTurtleMaster,
+        (Swiftness, "redstone") => LongSwiftness,
+        (Swiftness, "glowstone_dust") => StrongSwiftness,
+        (WaterBreathing, "redstone") => LongWaterBreathing,
+        (Healing, "glowstone_dust") => StrongHealing,
+        (Healing | Poison | LongPoison, "fermented_spider_eye") => Harming,
+        (StrongHealing | StrongPoison, "fermented_spider_eye") => StrongHarming,
+        (Harming, "glowstone_dust") => StrongHarming,
+        (Poison, "redstone") => LongPoison,
+        (Poison, "glowstone_dust") => StrongPoison,
+        (Regeneration, "redstone") => LongRegeneration,
+        (Regeneration, "glowstone_dust") => StrongRegeneration,
+        (Strength, "redstone") => LongStrength,
+        (Strength, "glowstone_dust") => StrongStrength,
+        (Weakness, "redstone") => LongWeakness,
+        (SlowFalling, "redstone") => LongSlowFalling,
+        _ => return None,
+    })
+}
+
+/// What brewing the bottle with the ingredient turns it into, `None` if the ingredient does
+/// nothing to it
+#[must_use]
+pub fn brew(bottle: &ItemStack, ingredient: &Item) -> Option<ItemStack> {
+    let potion = bottle.components.potion?;
+    // Gunpowder and dragon's breath change the bottle but keep the potion
+    let item = match (bottle.item.registry_key, ingredient.registry_key) {
+        ("potion", "gunpowder") => Item::SPLASH_POTION,
+        ("splash_potion", "dragon_breath") => Item::LINGERING_POTION,
+        ("potion" | "splash_potion" | "lingering_potion", _) => {
+            let potion = potion_mix(potion, ingredient.registry_key)?;
+            return Some(ItemStack::with_potion(
+                bottle.item_count,
+                bottle.item.clone(),
+                potion,
+            ));
+        }
+        _ => return None,
+    };
+    Some(ItemStack::with_potion(bottle.item_count, item, potion))
+}
+
+#[derive(Default)]
+pub struct BrewingStand {
+    bottles: [Option<ItemStack>; 3],
+    ingredient: Option<ItemStack>,
+    fuel: Option<ItemStack>,
+    /// Ticks until the current batch is done, 0 if nothing is brewing
+    brew_time: u16,
+    /// Batches left until another blaze powder is needed
+    fuel_left: u16,
+    /// The ingredient being brewed, the brewing stops when it gets swapped
+    brewing: Option<u16>,
+}
+
+impl BrewingStand {
+    /// Whether the ingredient does something to any of the bottles
+    fn can_brew(&self) -> bool {
+        let Some(ingredient) = &self.ingredient else {
+            return false;
+        };
+        self.bottles
+            .iter()
+            .flatten()
+            .any(|bottle| brew(bottle, &ingredient.item).is_some())
+    }
+
+    fn finish_brewing(&mut self) {
+        let Some(ingredient) = &mut self.ingredient else {
+            return;
+        };
+        for bottle in self.bottles.iter_mut().flatten() {
+            if let Some(brewed) = brew(bottle, &ingredient.item) {
+                *bottle = brewed;
+            }
+        }
+        if ingredient.item_count > 1 {
+            ingredient.item_count -= 1;
+        } else if ingredient.item.id == Item::DRAGON_BREATH.id {
+            // The bottle the breath was kept in is left behind
+            *ingredient = ItemStack::new(1, Item::GLASS_BOTTLE);
+        } else {
+            self.ingredient = None;
+        }
+    }
+}
+
+impl Container for BrewingStand {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::BrewingStand
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Brewing Stand"
+    }
+
+    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        let [first, second, third] = &mut self.bottles;
+        Box::new([first, second, third, &mut self.ingredient, &mut self.fuel])
+    }
+
+    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
+        let [first, second, third] = &self.bottles;
+        Box::new([
+            first.as_ref(),
+            second.as_ref(),
+            third.as_ref(),
+            self.ingredient.as_ref(),
+            self.fuel.as_ref(),
+        ])
+    }
+
+    fn tick(&mut self) -> bool {
+        let mut changed = false;
+        if self.fuel_left == 0 {
+            if let Some(fuel) = &mut self.fuel {
+                if fuel.item.id == Item::BLAZE_POWDER.id {
+                    self.fuel_left = FUEL_PER_BLAZE_POWDER;
+                    if fuel.item_count > 1 {
+                        fuel.item_count -= 1;
+                    } else {
+                        self.fuel = None;
+                    }
+                    changed = true;
+                }
+            }
+        }
+
+        let can_brew = self.can_brew();
+        let ingredient = self
+            .ingredient
+            .as_ref()
+            .map(|ingredient| ingredient.item.id);
+        if self.brew_time > 0 {
+            self.brew_time -= 1;
+            if self.brew_time == 0 && can_brew && ingredient == self.brewing {
+                self.finish_brewing();
+                changed = true;
+            } else if !can_brew || ingredient != self.brewing {
+                self.brew_time = 0;
+            }
+        } else if can_brew && self.fuel_left > 0 {
+            self.fuel_left -= 1;
+            self.brew_time = BREW_TIME;
+            self.brewing = ingredient;
+        }
+        changed
+    }
+
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        [
+            (BrewingStandProperty::BrewTime, self.brew_time),
+            (BrewingStandProperty::FuelTime, self.fuel_left),
+        ]
+        .into_iter()
+        .map(|(property, value)| WindowProperty::new(property, value as i16).into_tuple())
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::item::Item;
+    use pumpkin_data::potion::Potion;
+    use pumpkin_world::item::ItemStack;
+
+    use super::brew;
+
+    #[test]
+    fn brewing_chain() {
+        let water = ItemStack::with_potion(1, Item::POTION, Potion::Water);
+        let awkward = brew(&water, &Item::NETHER_WART).unwrap();
+        assert_eq!(awkward.components.potion, Some(Potion::Awkward));
+        // Start mixes make water mundane instead of giving the effect
+        let mundane = brew(&water, &Item::SUGAR).unwrap();
+        assert_eq!(mundane.components.potion, Some(Potion::Mundane));
+
+        let swiftness = brew(&awkward, &Item::SUGAR).unwrap();
+        assert_eq!(swiftness.components.potion, Some(Potion::Swiftness));
+        let splash = brew(&swiftness, &Item::GUNPOWDER).unwrap();
+        assert_eq!(splash.item.id, Item::SPLASH_POTION.id);
+        assert_eq!(splash.components.potion, Some(Potion::Swiftness));
+        let lingering = brew(&splash, &Item::DRAGON_BREATH).unwrap();
+        assert_eq!(lingering.item.id, Item::LINGERING_POTION.id);
+
+        assert!(brew(&swiftness, &Item::NETHER_WART).is_none());
+        assert!(brew(&ItemStack::new(1, Item::GLASS_BOTTLE), &Item::NETHER_WART).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+use pumpkin_world::item::ItemStack;
+
+use crate::container_click::MouseClick;
+
+/// Weights are counted in 1/64 of a bundle so every item takes up a whole number, a full bundle
+/// holds 64 of an item stacking to 64
+const MAX_WEIGHT: u32 = 64;
+/// A bundle inside of a bundle takes up a 1/16 on top of its contents
+const NESTED_BUNDLE_WEIGHT: u32 = 4;
+
+/// How much of a bundle a single item of the stack takes up
+fn item_weight(stack: &ItemStack) -> u32 {
+    if stack.is_bundle() {
+        let contents = stack
+            .components
+            .bundle_contents
+            .as_deref()
+            .unwrap_or_default();
+        return NESTED_BUNDLE_WEIGHT + weight(contents);
+    }
+    MAX_WEIGHT / u32::from(stack.item.components.max_stack_size.max(1))
+}
+
+/// How much of a bundle the items take up
+#[must_use]
+pub fn weight(contents: &[ItemStack]) -> u32 {
+    contents
+        .iter()
+        .map(|stack| item_weight(stack) * u32::from(stack.item_count))
+        .sum()
+}
+
+/// Puts as much of the stack into the bundle as fits, newest items go first. Returns whether
+/// anything was put in
+pub fn try_insert(bundle: &mut ItemStack, slot: &mut Option<ItemStack>) -> bool {
+    let Some(stack) = slot else {
+        return false;
+    };
+    if !stack.can_fit_inside_container_items() {
+        return false;
+    }
+    let per_item = item_weight(stack);
+    let contents = bundle.components.bundle_contents.get_or_insert_default();
+    let space = MAX_WEIGHT.saturating_sub(weight(contents));
+    let amount = (space / per_item).min(u32::from(stack.item_count)) as u8;
+    if amount == 0 {
+        return false;
+    }
+
+    let mut added = ItemStack {
+        item_count: amount,
+        ..stack.clone()
+    };
+    if let Some(index) = contents
+        .iter()
+        .position(|other| other.item.id == stack.item.id && other.components == stack.components)
+    {
+        added.item_count += contents.remove(index).item_count;
+    }
+    contents.insert(0, added);
+
+    stack.item_count -= amount;
+    if stack.item_count == 0 {
+        *slot = None;
+    }
+    true
+}
+
+/// Takes the most recently added stack out of the bundle
+pub fn remove_one(bundle: &mut ItemStack) -> Option<ItemStack> {
+    let contents = bundle.components.bundle_contents.as_mut()?;
+    if contents.is_empty() {
+        return None;
+    }
+    let removed = contents.remove(0);
+    if contents.is_empty() {
+        bundle.components.bundle_contents = None;
+    }
+    Some(removed)
+}
+
+/// Left clicking with a bundle puts the clicked stack into it and right clicking an empty slot
+/// takes one stack out, the same goes the other way around for a bundle in a slot. Returns
+/// whether the click was handled, otherwise the stacks get swapped or combined as usual
+pub fn handle_click(
+    carried_slot: &mut Option<ItemStack>,
+    current_slot: &mut Option<ItemStack>,
+    mouse_click: &MouseClick,
+) -> bool {
+    if let Some(bundle) = carried_slot.as_mut().filter(|stack| stack.is_bundle()) {
+        match mouse_click {
+            MouseClick::Left if current_slot.is_some() => {
+                if try_insert(bundle, current_slot) {
+                    return true;
+                }
+            }
+            MouseClick::Right if current_slot.is_none() => {
+                if let Some(removed) = remove_one(bundle) {
+                    *current_slot = Some(removed);
+                    return true;
+                }
+            }
+            _ => (),
+        }
+    }
+    if let Some(bundle) = current_slot.as_mut().filter(|stack| stack.is_bundle()) {
+        match mouse_click {
+            MouseClick::Left if carried_slot.is_some() => return try_insert(bundle, carried_slot),
+            MouseClick::Right if carried_slot.is_none() => {
+                if let Some(removed) = remove_one(bundle) {
+                    *carried_slot = Some(removed);
+                    return true;
+                }
+            }
+            _ => (),
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::item::Item;
+    use pumpkin_world::item::ItemStack;
+
+    use super::{remove_one, try_insert, weight};
+
+    #[test]
+    fn bundle_weights() {
+        let mut bundle = ItemStack::new(1, Item::BUNDLE);
+        let mut pearls = Some(ItemStack::new(10, Item::ENDER_PEARL));
+        assert!(try_insert(&mut bundle, &mut pearls));
+        assert!(pearls.is_none());
+
+        // Ender pearls stack to 16, so ten of them leave room for 24 dirt
+        let mut dirt = Some(ItemStack::new(30, Item::DIRT));
+        assert!(try_insert(&mut bundle, &mut dirt));
+        assert_eq!(dirt.as_ref().unwrap().item_count, 6);
+        assert!(!try_insert(&mut bundle, &mut dirt));
+
+        let contents = bundle.components.bundle_contents.as_ref().unwrap();
+        assert_eq!(weight(contents), 64);
+        assert_eq!(contents[0].item.id, Item::DIRT.id);
+
+        // A bundle in a bundle weighs 4 on top of what is inside
+        let mut outer = ItemStack::new(1, Item::BUNDLE);
+        assert!(try_insert(
+            &mut outer,
+            &mut Some(ItemStack::new(1, Item::BUNDLE))
+        ));
+        assert_eq!(
+            weight(outer.components.bundle_contents.as_ref().unwrap()),
+            4
+        );
+        assert!(!try_insert(&mut outer, &mut Some(bundle.clone())));
+
+        let mut shulker_box = Some(ItemStack::new(1, Item::SHULKER_BOX));
+        assert!(!try_insert(&mut outer, &mut shulker_box));
+
+        let removed = remove_one(&mut bundle).unwrap();
+        assert_eq!((removed.item.id, removed.item_count), (Item::DIRT.id, 24));
+        assert_eq!(remove_one(&mut bundle).unwrap().item_count, 10);
+        assert!(remove_one(&mut bundle).is_none());
+        assert!(bundle.components.bundle_contents.is_none());
+    }
+}
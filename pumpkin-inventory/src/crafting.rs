@@ -1,73 +1,159 @@
+use std::{collections::BTreeSet, sync::LazyLock};
+
 use pumpkin_data::{
     item::Item,
-    tag::{RegistryKey, get_tag_values},
+    tag::{RegistryKey, Tagable, get_tag_values},
+};
+use pumpkin_protocol::{
+    client::play::{IdSet, RecipePropertySet, StonecutterRecipe},
+    codec::{slot::Slot, var_int::VarInt},
 };
-use pumpkin_registry::{RECIPES, RecipeResult, flatten_3x3};
+use pumpkin_registry::{RECIPES, RecipeResult, RecipeType, flatten_3x3};
 use pumpkin_util::registry::{RegistryEntryList, TagType};
 use pumpkin_world::item::ItemStack;
 use rayon::prelude::*;
 
-#[inline(always)]
-fn check_ingredient_type(ingredient_type: &TagType, input: &ItemStack) -> bool {
-    match ingredient_type {
-        TagType::Tag(tag) => {
-            let _items = match get_tag_values(RegistryKey::Item, tag) {
-                Some(items) => items,
-                None => return false,
+/// The input slots the client restricts to the ingredients of a recipe type
+const PROPERTY_SETS: [(&str, RecipeType); 4] = [
+    ("minecraft:furnace_input", RecipeType::Smelting),
+    ("minecraft:blast_furnace_input", RecipeType::Blasting),
+    ("minecraft:smoker_input", RecipeType::Smoking),
+    ("minecraft:campfire_input", RecipeType::CampfireCooking),
+];
+
+static PROPERTY_SET_DATA: LazyLock<Vec<RecipePropertySet>> = LazyLock::new(|| {
+    PROPERTY_SETS
+        .iter()
+        .map(|(id, recipe_type)| {
+            let items: BTreeSet<u16> = RECIPES
+                .iter()
+                .filter(|recipe| recipe.recipe_type == *recipe_type)
+                .filter_map(|recipe| recipe.cooking())
+                .flat_map(|cooking| ingredient_items(&cooking.ingredient))
+                .collect();
+            RecipePropertySet {
+                id: (*id).to_string(),
+                items: items.into_iter().map(|id| VarInt(i32::from(id))).collect(),
+            }
+        })
+        .collect()
+});
+
+static STONECUTTER_RECIPES: LazyLock<Vec<StonecutterRecipe>> = LazyLock::new(|| {
+    RECIPES
+        .iter()
+        .filter(|recipe| recipe.recipe_type == RecipeType::StoneCutting)
+        .filter_map(|recipe| {
+            let ingredient = match recipe.ingredient()? {
+                RegistryEntryList::Single(TagType::Tag(tag)) => IdSet::Tag(tag.clone()),
+                ingredient => IdSet::Ids(
+                    ingredient_items(ingredient)
+                        .into_iter()
+                        .map(|id| VarInt(i32::from(id)))
+                        .collect(),
+                ),
             };
-            // TODO
-            false
-            // items.iter().any(|tag| check_ingredient_type(&tag, input))
-        }
-        TagType::Item(item) => {
-            Item::from_registry_key(item).is_some_and(|item| item.id == input.item.id)
-        }
+            let result = result_stack(recipe.result())?;
+            Some(StonecutterRecipe {
+                ingredient,
+                result: Slot::from(&result),
+            })
+        })
+        .collect()
+});
+
+/// Finds what the recipes of `RECIPES` turn items into
+pub struct RecipeManager;
+
+impl RecipeManager {
+    /// The result of crafting the items in a crafting table grid. The 2x2 grid of the inventory
+    /// is the top left corner, the items may be anywhere in the grid
+    #[must_use]
+    pub fn match_crafting(grid: [[Option<&ItemStack>; 3]; 3]) -> Option<ItemStack> {
+        let input = flatten_3x3(grid);
+        RECIPES
+            .par_iter()
+            .find_any(|recipe| {
+                let patterns = recipe.pattern();
+                if patterns
+                    .iter()
+                    .flatten()
+                    .flatten()
+                    .all(|slot| slot.is_none())
+                {
+                    false
+                } else if recipe.recipe_type.is_shapeless() {
+                    shapeless_crafting_match(input, recipe.pattern())
+                } else {
+                    patterns.par_iter().any(|pattern| {
+                        pattern.iter().enumerate().all(|(i, row)| {
+                            row.iter()
+                                .enumerate()
+                                .all(|(j, item)| match (item, input[i][j]) {
+                                    (Some(item), Some(input)) => ingredient_slot_check(item, input),
+                                    (None, None) => true,
+                                    (Some(_), None) | (None, Some(_)) => false,
+                                })
+                        })
+                    })
+                }
+            })
+            .and_then(|recipe| result_stack(recipe.result()))
+    }
+
+    /// The items which fit into the input slots of furnaces, smokers, blast furnaces and
+    /// campfires, sent to the client on join
+    #[must_use]
+    pub fn property_sets() -> &'static [RecipePropertySet] {
+        &PROPERTY_SET_DATA
+    }
+
+    /// The recipes the stonecutter shows, sent to the client on join
+    #[must_use]
+    pub fn stonecutter_recipes() -> &'static [StonecutterRecipe] {
+        &STONECUTTER_RECIPES
     }
 }
 
-pub fn check_if_matches_crafting(input: [[Option<&ItemStack>; 3]; 3]) -> Option<ItemStack> {
-    let input = flatten_3x3(input);
-    RECIPES
-        .par_iter()
-        .find_any(|recipe| {
-            let patterns = recipe.pattern();
-            if patterns
+/// The ids of every item the ingredient accepts
+fn ingredient_items(ingredient: &RegistryEntryList) -> Vec<u16> {
+    ingredient
+        .get_values()
+        .iter()
+        .flat_map(|ingredient| match ingredient {
+            TagType::Item(item) => item_from_id(item).map(|item| item.id).into_iter().collect(),
+            TagType::Tag(tag) => get_tag_values(RegistryKey::Item, tag)
+                .unwrap_or_default()
                 .iter()
-                .flatten()
-                .flatten()
-                .all(|slot| slot.is_none())
-            {
-                false
-            } else if recipe.recipe_type.is_shapeless() {
-                shapeless_crafting_match(input, recipe.pattern())
-            } else {
-                patterns.par_iter().any(|pattern| {
-                    pattern.iter().enumerate().all(|(i, row)| {
-                        row.iter()
-                            .enumerate()
-                            .all(|(j, item)| match (item, input[i][j]) {
-                                (Some(item), Some(input)) => ingredient_slot_check(item, input),
-                                (None, None) => true,
-                                (Some(_), None) | (None, Some(_)) => false,
-                            })
-                    })
-                })
-            }
+                .filter_map(|item| item_from_id(item))
+                .map(|item| item.id)
+                .collect::<Vec<_>>(),
         })
-        .map(|recipe| match recipe.result() {
-            RecipeResult::Single { id, .. } => Some(ItemStack {
-                item: Item::from_registry_key(id).unwrap(),
-                item_count: 1,
-            }),
-            RecipeResult::Many { id, count, .. } => Some(ItemStack {
-                item: Item::from_registry_key(id).unwrap(),
-                item_count: *count,
-            }),
-            RecipeResult::Special => None,
-        })?
+        .collect()
 }
 
-fn ingredient_slot_check(recipe_item: &RegistryEntryList, input: &ItemStack) -> bool {
+#[inline(always)]
+fn check_ingredient_type(ingredient_type: &TagType, input: &ItemStack) -> bool {
+    match ingredient_type {
+        TagType::Tag(tag) => input.item.is_tagged_with(tag).unwrap_or_default(),
+        TagType::Item(item) => item_from_id(item).is_some_and(|item| item.id == input.item.id),
+    }
+}
+
+/// Recipes name items with their namespace, the item registry does not
+fn item_from_id(id: &str) -> Option<Item> {
+    Item::from_registry_key(id.strip_prefix("minecraft:").unwrap_or(id))
+}
+
+pub(crate) fn result_stack(result: &RecipeResult) -> Option<ItemStack> {
+    match result {
+        RecipeResult::Single { id, .. } => Some(ItemStack::new(1, item_from_id(id)?)),
+        RecipeResult::Many { id, count, .. } => Some(ItemStack::new(*count, item_from_id(id)?)),
+        RecipeResult::Special => None,
+    }
+}
+
+pub(crate) fn ingredient_slot_check(recipe_item: &RegistryEntryList, input: &ItemStack) -> bool {
     match recipe_item {
         RegistryEntryList::Single(ingredient) => check_ingredient_type(ingredient, input),
         RegistryEntryList::Many(ingredients) => ingredients
@@ -101,3 +187,56 @@ fn shapeless_crafting_match(
     }
     pattern.is_empty()
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::item::Item;
+    use pumpkin_protocol::codec::var_int::VarInt;
+    use pumpkin_world::item::ItemStack;
+
+    use super::RecipeManager;
+
+    #[test]
+    fn crafting_anywhere_in_the_grid() {
+        let planks = ItemStack::new(1, Item::OAK_PLANKS);
+        let table = RecipeManager::match_crafting([
+            [None, None, None],
+            [None, Some(&planks), Some(&planks)],
+            [None, Some(&planks), Some(&planks)],
+        ])
+        .unwrap();
+        assert_eq!(table.item.id, Item::CRAFTING_TABLE.id);
+
+        let log = ItemStack::new(1, Item::OAK_LOG);
+        let planks = RecipeManager::match_crafting([
+            [None, None, Some(&log)],
+            [None, None, None],
+            [None, None, None],
+        ])
+        .unwrap();
+        assert_eq!(planks.item.id, Item::OAK_PLANKS.id);
+        assert_eq!(planks.item_count, 4);
+
+        assert!(
+            RecipeManager::match_crafting([[Some(&log), Some(&log), None], [None; 3], [None; 3]])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn declared_recipes() {
+        let furnace_input = &RecipeManager::property_sets()[0];
+        assert_eq!(furnace_input.id, "minecraft:furnace_input");
+        // Logs are in the charcoal recipe through a tag
+        for item in [Item::POTATO, Item::OAK_LOG, Item::IRON_ORE] {
+            assert!(furnace_input.items.contains(&VarInt(i32::from(item.id))));
+        }
+        assert!(
+            !furnace_input
+                .items
+                .contains(&VarInt(i32::from(Item::STICK.id)))
+        );
+
+        assert!(!RecipeManager::stonecutter_recipes().is_empty());
+    }
+}
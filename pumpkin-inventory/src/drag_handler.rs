@@ -97,8 +97,8 @@ impl DragHandler {
                             }
                         } else {
                             *slots[slot] = Some(ItemStack {
-                                item: carried_item.item.clone(),
                                 item_count: 1,
+                                ..carried_item.clone()
                             })
                         }
                     }
@@ -0,0 +1,224 @@
+use std::marker::PhantomData;
+
+use pumpkin_data::item::Item;
+use pumpkin_data::screen::WindowType;
+use pumpkin_registry::RecipeType;
+use pumpkin_world::item::ItemStack;
+
+use crate::Container;
+use crate::smelting::{find_cooking_recipe, fuel_burn_time};
+use crate::window_property::{Furnace as FurnaceProperty, WindowProperty};
+
+/// What sets a furnace, smoker and blast furnace apart
+pub trait FurnaceKind: Send + Sync {
+    const WINDOW_TYPE: &'static WindowType;
+    const NAME: &'static str;
+    const RECIPE_TYPE: RecipeType;
+    /// Smokers and blast furnaces cook twice as fast, but also burn their fuel twice as fast
+    const FUEL_DIVISOR: u16;
+}
+
+pub struct Smelting;
+pub struct Smoking;
+pub struct Blasting;
+
+impl FurnaceKind for Smelting {
+    const WINDOW_TYPE: &'static WindowType = &WindowType::Furnace;
+    const NAME: &'static str = "Furnace";
+    const RECIPE_TYPE: RecipeType = RecipeType::Smelting;
+    const FUEL_DIVISOR: u16 = 1;
+}
+
+impl FurnaceKind for Smoking {
+    const WINDOW_TYPE: &'static WindowType = &WindowType::Smoker;
+    const NAME: &'static str = "Smoker";
+    const RECIPE_TYPE: RecipeType = RecipeType::Smoking;
+    const FUEL_DIVISOR: u16 = 2;
+}
+
+impl FurnaceKind for Blasting {
+    const WINDOW_TYPE: &'static WindowType = &WindowType::BlastFurnace;
+    const NAME: &'static str = "Blast Furnace";
+    const RECIPE_TYPE: RecipeType = RecipeType::Blasting;
+    const FUEL_DIVISOR: u16 = 2;
+}
+
+pub type Furnace = AbstractFurnace<Smelting>;
+pub type Smoker = AbstractFurnace<Smoking>;
+pub type BlastFurnace = AbstractFurnace<Blasting>;
+
+pub struct AbstractFurnace<K: FurnaceKind> {
+    cook: Option<ItemStack>,
+    fuel: Option<ItemStack>,
+    output: Option<ItemStack>,
+    /// Ticks until the current fuel is used up
+    burn_time: u16,
+    /// Ticks the current fuel burns in total
+    fuel_time: u16,
+    cook_time: u16,
+    cook_time_total: u16,
+    /// The item which is being cooked, progress is lost when it gets swapped
+    cooking: Option<u16>,
+    /// Collected from all cooked items until someone takes the output
+    experience: f32,
+    kind: PhantomData<K>,
+}
+
+impl<K: FurnaceKind> Default for AbstractFurnace<K> {
+    fn default() -> Self {
+        Self {
+            cook: None,
+            fuel: None,
+            output: None,
+            burn_time: 0,
+            fuel_time: 0,
+            cook_time: 0,
+            cook_time_total: 0,
+            cooking: None,
+            experience: 0.0,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<K: FurnaceKind> AbstractFurnace<K> {
+    const SLOT_OUTPUT: usize = 2;
+
+    const fn is_burning(&self) -> bool {
+        self.burn_time > 0
+    }
+
+    /// Whether the result fits into the output slot
+    fn can_output(&self, result: &ItemStack) -> bool {
+        self.output.as_ref().is_none_or(|output| {
+            output.item.id == result.item.id
+                && output.item_count + result.item_count <= output.item.components.max_stack_size
+        })
+    }
+
+    /// Uses up one fuel item, buckets are left behind
+    fn consume_fuel(&mut self) {
+        let Some(fuel) = &mut self.fuel else {
+            return;
+        };
+        if fuel.item.id == Item::LAVA_BUCKET.id {
+            *fuel = ItemStack::new(1, Item::BUCKET);
+        } else if fuel.item_count > 1 {
+            fuel.item_count -= 1;
+        } else {
+            self.fuel = None;
+        }
+    }
+
+    /// Moves the cooked item into the output
+    fn finish_cooking(&mut self, result: ItemStack) {
+        match &mut self.output {
+            Some(output) => output.item_count += result.item_count,
+            None => self.output = Some(result),
+        }
+        if let Some(cook) = &mut self.cook {
+            if cook.item_count > 1 {
+                cook.item_count -= 1;
+            } else {
+                self.cook = None;
+            }
+        }
+    }
+}
+
+impl<K: FurnaceKind> Container for AbstractFurnace<K> {
+    fn window_type(&self) -> &'static WindowType {
+        K::WINDOW_TYPE
+    }
+
+    fn window_name(&self) -> &'static str {
+        K::NAME
+    }
+
+    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        Box::new([&mut self.cook, &mut self.fuel, &mut self.output])
+    }
+
+    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
+        Box::new([self.cook.as_ref(), self.fuel.as_ref(), self.output.as_ref()])
+    }
+
+    // Nothing can be put into the output, only taken out
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(Self::SLOT_OUTPUT)
+    }
+
+    fn tick(&mut self) -> bool {
+        if self.is_burning() {
+            self.burn_time -= 1;
+        }
+
+        let cooking = self.cook.as_ref().map(|cook| cook.item.id);
+        if cooking != self.cooking {
+            self.cooking = cooking;
+            self.cook_time = 0;
+        }
+
+        let recipe = self
+            .cook
+            .as_ref()
+            .and_then(|cook| find_cooking_recipe(K::RECIPE_TYPE, cook))
+            .filter(|(_, result)| self.can_output(result));
+        let Some((recipe, result)) = recipe else {
+            self.cook_time = if self.is_burning() {
+                0
+            } else {
+                self.cook_time.saturating_sub(2)
+            };
+            return false;
+        };
+
+        let mut changed = false;
+        if !self.is_burning() {
+            if let Some(burn_time) = self
+                .fuel
+                .as_ref()
+                .and_then(|fuel| fuel_burn_time(&fuel.item))
+            {
+                self.burn_time = burn_time / K::FUEL_DIVISOR;
+                self.fuel_time = self.burn_time;
+                self.consume_fuel();
+                changed = true;
+            }
+        }
+
+        if self.is_burning() {
+            self.cook_time_total = recipe.cooking_time;
+            self.cook_time += 1;
+            if self.cook_time >= self.cook_time_total {
+                self.cook_time = 0;
+                self.experience += recipe.experience;
+                self.finish_cooking(result);
+                changed = true;
+            }
+        } else {
+            self.cook_time = self.cook_time.saturating_sub(2);
+        }
+        changed
+    }
+
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        [
+            (FurnaceProperty::FireIcon, self.burn_time),
+            (FurnaceProperty::MaximumFuelBurnTime, self.fuel_time),
+            (FurnaceProperty::ProgressArrow, self.cook_time),
+            (FurnaceProperty::MaximumProgress, self.cook_time_total),
+        ]
+        .into_iter()
+        .map(|(property, value)| WindowProperty::new(property, value as i16).into_tuple())
+        .collect()
+    }
+
+    fn is_lit(&self) -> bool {
+        self.is_burning()
+    }
+
+    fn take_experience(&mut self) -> f32 {
+        std::mem::take(&mut self.experience)
+    }
+}
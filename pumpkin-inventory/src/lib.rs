@@ -3,15 +3,24 @@ use crate::player::PlayerInventory;
 use pumpkin_data::screen::WindowType;
 use pumpkin_world::item::ItemStack;
 
+pub mod brewing;
+pub mod bundle;
 pub mod container_click;
 mod crafting;
 pub mod drag_handler;
 mod error;
+mod furnace;
 mod open_container;
 pub mod player;
+pub mod smelting;
 pub mod window_property;
 
+pub use brewing::BrewingStand;
+pub use crafting::RecipeManager;
 pub use error::InventoryError;
+pub use furnace::{
+    AbstractFurnace, BlastFurnace, Blasting, Furnace, FurnaceKind, Smelting, Smoker, Smoking,
+};
 pub use open_container::*;
 
 pub struct ContainerStruct<const SLOTS: usize>([Option<ItemStack>; SLOTS]);
@@ -33,6 +42,14 @@ pub trait Container: Sync + Send {
         if slot > all_slots.len() {
             Err(InventoryError::InvalidSlot)?
         }
+        drop(all_slots);
+        if carried_item
+            .as_ref()
+            .is_some_and(|carried| !self.can_place(slot, carried))
+        {
+            Err(InventoryError::InvalidSlot)?
+        }
+        let all_slots = self.all_slots();
         if taking_crafted {
             match (all_slots[slot].as_mut(), carried_item.as_mut()) {
                 (Some(s1), Some(s2)) => {
@@ -52,6 +69,12 @@ pub trait Container: Sync + Send {
 
     fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]>;
 
+    /// Whether the stack may be put into the slot, like shulker boxes which can't hold other
+    /// shulker boxes
+    fn can_place(&self, _slot: usize, _stack: &ItemStack) -> bool {
+        true
+    }
+
     fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]>;
 
     fn clear_all_slots(&mut self) {
@@ -90,6 +113,27 @@ pub trait Container: Sync + Send {
     }
 
     fn recipe_used(&mut self) {}
+
+    /// Runs one tick for containers which work on their own, like furnaces. Returns whether
+    /// the slots changed
+    fn tick(&mut self) -> bool {
+        false
+    }
+
+    /// The current window properties as `(id, value)`, like the progress of a furnace
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        Vec::new()
+    }
+
+    /// Whether the block of the container should be lit, like a burning furnace
+    fn is_lit(&self) -> bool {
+        false
+    }
+
+    /// Takes the experience stored in the container, granted when taking out the output
+    fn take_experience(&mut self) -> f32 {
+        0.0
+    }
 }
 
 pub struct EmptyContainer;
@@ -150,10 +194,13 @@ pub fn handle_item_change(
     current_slot: &mut Option<ItemStack>,
     mouse_click: MouseClick,
 ) {
+    if bundle::handle_click(carried_slot, current_slot, &mouse_click) {
+        return;
+    }
     match (current_slot.as_mut(), carried_slot.as_mut()) {
         // Swap or combine current and carried
         (Some(current), Some(carried)) => {
-            if current.item.id == carried.item.id {
+            if current.item.id == carried.item.id && current.components == carried.components {
                 combine_stacks(carried_slot, current, mouse_click);
             } else if mouse_click == MouseClick::Left {
                 std::mem::swap(carried_slot, current_slot);
@@ -166,8 +213,8 @@ pub fn handle_item_change(
             }
             MouseClick::Right => {
                 let new_stack = ItemStack {
-                    item: carried_item_stack.item.clone(),
                     item_count: 1,
+                    ..carried_item_stack.clone()
                 };
                 *current_slot = Some(new_stack);
                 carried_item_stack.item_count -= 1;
@@ -261,6 +308,15 @@ impl<'a> Container for OptionallyCombinedContainer<'a, 'a> {
         slots
     }
 
+    fn can_place(&self, slot: usize, stack: &ItemStack) -> bool {
+        match &self.container {
+            Some(container) if slot < container.all_slots_ref().len() => {
+                container.can_place(slot, stack)
+            }
+            _ => true,
+        }
+    }
+
     fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
         match &self.container {
             Some(container) => {
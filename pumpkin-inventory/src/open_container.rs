@@ -1,5 +1,5 @@
 use crate::Container;
-use crate::crafting::check_if_matches_crafting;
+use crate::crafting::RecipeManager;
 use pumpkin_data::block::Block;
 use pumpkin_data::screen::WindowType;
 use pumpkin_util::math::position::BlockPos;
@@ -26,6 +26,11 @@ impl OpenContainer {
         Some(container)
     }
 
+    /// The container itself, no matter who has it open
+    pub fn container(&self) -> &Arc<Mutex<Box<dyn Container>>> {
+        &self.container
+    }
+
     pub fn add_player(&mut self, player_id: i32) {
         if !self.players.contains(&player_id) {
             self.players.push(player_id);
@@ -53,6 +58,40 @@ impl OpenContainer {
         }
     }
 
+    /// Opens a container with items already in it, like a shulker box which was placed down
+    pub fn with_container<C: Container + 'static>(
+        container: C,
+        location: Option<BlockPos>,
+        block: Option<Block>,
+    ) -> Self {
+        Self {
+            players: vec![],
+            container: Arc::new(Mutex::new(Box::new(container))),
+            location,
+            block,
+        }
+    }
+
+    /// Opens a container which lives somewhere else, like the ender chest of the player
+    pub fn with_shared_container(
+        player_id: i32,
+        container: Arc<Mutex<Box<dyn Container>>>,
+        location: Option<BlockPos>,
+        block: Option<Block>,
+    ) -> Self {
+        Self {
+            players: vec![player_id],
+            container,
+            location,
+            block,
+        }
+    }
+
+    /// Whether this opens the given container
+    pub fn is_container(&self, container: &Arc<Mutex<Box<dyn Container>>>) -> bool {
+        Arc::ptr_eq(&self.container, container)
+    }
+
     pub fn is_location(&self, try_position: BlockPos) -> bool {
         if let Some(location) = self.location {
             location == try_position
@@ -114,6 +153,86 @@ impl Container for Chest {
     }
 }
 
+// TODO: move items into the container above and below
+#[derive(Default)]
+pub struct Hopper([Option<ItemStack>; 5]);
+
+impl Container for Hopper {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Hopper
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Item Hopper"
+    }
+
+    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+/// The items every player has in their own ender chest, the same in every ender chest block
+#[derive(Default)]
+pub struct EnderChest([Option<ItemStack>; 27]);
+
+impl Container for EnderChest {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Generic9x3
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Ender Chest"
+    }
+
+    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct ShulkerBox([Option<ItemStack>; 27]);
+
+impl ShulkerBox {
+    /// A shulker box with the items kept in its item
+    pub fn from_contents(contents: Vec<Option<ItemStack>>) -> Self {
+        let mut shulker_box = Self::default();
+        for (slot, stack) in shulker_box.0.iter_mut().zip(contents) {
+            *slot = stack;
+        }
+        shulker_box
+    }
+}
+
+impl Container for ShulkerBox {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::ShulkerBox
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Shulker Box"
+    }
+
+    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+
+    fn can_place(&self, _slot: usize, stack: &ItemStack) -> bool {
+        stack.can_fit_inside_container_items()
+    }
+}
+
 #[derive(Default)]
 pub struct CraftingTable {
     input: [[Option<ItemStack>; 3]; 3],
@@ -180,7 +299,7 @@ impl Container for CraftingTable {
             ],
         ];
 
-        let new_output = check_if_matches_crafting(check);
+        let new_output = RecipeManager::match_crafting(check);
         let result = new_output != self.output
             || self.input.iter().flatten().any(|s| s.is_some())
             || new_output.is_some();
@@ -208,27 +327,3 @@ impl Container for CraftingTable {
         })
     }
 }
-
-#[derive(Default)]
-pub struct Furnace {
-    cook: Option<ItemStack>,
-    fuel: Option<ItemStack>,
-    output: Option<ItemStack>,
-}
-
-impl Container for Furnace {
-    fn window_type(&self) -> &'static WindowType {
-        &WindowType::Furnace
-    }
-
-    fn window_name(&self) -> &'static str {
-        "Furnace"
-    }
-    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
-        Box::new([&mut self.cook, &mut self.fuel, &mut self.output])
-    }
-
-    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
-        Box::new([self.cook.as_ref(), self.fuel.as_ref(), self.output.as_ref()])
-    }
-}
@@ -1,5 +1,5 @@
 use crate::container_click::MouseClick;
-use crate::crafting::check_if_matches_crafting;
+use crate::crafting::RecipeManager;
 use crate::{Container, InventoryError, WindowType, handle_item_change};
 use pumpkin_data::item::Item;
 use pumpkin_world::item::ItemStack;
@@ -350,7 +350,7 @@ impl Container for PlayerInventory {
         let v3 = [const { None }; 3];
         let together = [v1, v2, v3];
 
-        self.crafting_output = check_if_matches_crafting(together);
+        self.crafting_output = RecipeManager::match_crafting(together);
         self.crafting.iter().any(|s| s.is_some())
     }
 
@@ -0,0 +1,126 @@
+use pumpkin_data::item::Item;
+use pumpkin_data::tag::Tagable;
+use pumpkin_registry::{CookingRecipe, RECIPES, RecipeType};
+use pumpkin_world::item::ItemStack;
+
+use crate::crafting::{ingredient_slot_check, result_stack};
+
+/// Finds the recipe of the given type cooking the item, along with what it turns into
+pub fn find_cooking_recipe(
+    recipe_type: RecipeType,
+    input: &ItemStack,
+) -> Option<(&'static CookingRecipe, ItemStack)> {
+    RECIPES
+        .iter()
+        .filter(|recipe| recipe.recipe_type == recipe_type)
+        .find_map(|recipe| {
+            let cooking = recipe.cooking()?;
+            if !ingredient_slot_check(&cooking.ingredient, input) {
+                return None;
+            }
+            Some((cooking, result_stack(recipe.result())?))
+        })
+}
+
+/// How many ticks the item burns in a furnace, `None` if it is no fuel
+#[must_use]
+pub fn fuel_burn_time(item: &Item) -> Option<u16> {
+    let tagged = |tag: &str| item.is_tagged_with(tag).unwrap_or_default();
+    // Crimson and warped wood is in the wood tags as well but does not burn
+    if tagged("minecraft:non_flammable_wood") {
+        return None;
+    }
+
+    let ticks = match item.registry_key {
+        "lava_bucket" => 20000,
+        "coal_block" => 16000,
+        "dried_kelp_block" => 4001,
+        "blaze_rod" => 2400,
+        "coal" | "charcoal" => 1600,
+        "note_block"
+        | "bookshelf"
+        | "chiseled_bookshelf"
+        | "lectern"
+        | "jukebox"
+        | "chest"
+        | "trapped_chest"
+        | "crafting_table"
+        | "daylight_detector"
+        | "bow"
+        | "fishing_rod"
+        | "ladder"
+        | "crossbow"
+        | "barrel"
+        | "composter"
+        | "cartography_table"
+        | "fletching_table"
+        | "smithing_table"
+        | "loom"
+        | "mangrove_roots"
+        | "bamboo_mosaic"
+        | "bamboo_mosaic_stairs" => 300,
+        "wooden_shovel" | "wooden_sword" | "wooden_hoe" | "wooden_axe" | "wooden_pickaxe" => 200,
+        "bamboo_mosaic_slab" => 150,
+        "stick" | "bowl" | "dead_bush" | "azalea" | "flowering_azalea" => 100,
+        "bamboo" | "scaffolding" => 50,
+        _ if tagged("minecraft:boats") || tagged("minecraft:chest_boats") => 1200,
+        _ if tagged("minecraft:hanging_signs") => 800,
+        _ if tagged("minecraft:logs")
+            || tagged("minecraft:planks")
+            || tagged("minecraft:bamboo_blocks")
+            || tagged("minecraft:wooden_stairs")
+            || tagged("minecraft:wooden_trapdoors")
+            || tagged("minecraft:wooden_pressure_plates")
+            || tagged("minecraft:wooden_fences")
+            || tagged("minecraft:fence_gates")
+            || tagged("minecraft:banners") =>
+        {
+            300
+        }
+        _ if tagged("minecraft:signs") || tagged("minecraft:wooden_doors") => 200,
+        _ if tagged("minecraft:wooden_slabs") => 150,
+        _ if tagged("minecraft:wool")
+            || tagged("minecraft:wooden_buttons")
+            || tagged("minecraft:saplings") =>
+        {
+            100
+        }
+        _ if tagged("minecraft:wool_carpets") => 67,
+        _ => return None,
+    };
+    Some(ticks)
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::item::Item;
+    use pumpkin_registry::RecipeType;
+    use pumpkin_world::item::ItemStack;
+
+    use super::{find_cooking_recipe, fuel_burn_time};
+
+    #[test]
+    fn recipes_depend_on_the_block() {
+        let potato = ItemStack::new(1, Item::POTATO);
+        let (smelting, result) = find_cooking_recipe(RecipeType::Smelting, &potato).unwrap();
+        assert_eq!(result.item.id, Item::BAKED_POTATO.id);
+        assert_eq!(smelting.cooking_time, 200);
+        let (campfire, _) = find_cooking_recipe(RecipeType::CampfireCooking, &potato).unwrap();
+        assert_eq!(campfire.cooking_time, 600);
+        // Blast furnaces only do ores and armor
+        assert!(find_cooking_recipe(RecipeType::Blasting, &potato).is_none());
+
+        // Charcoal is made from any log that burns, which is a tag
+        let log = ItemStack::new(1, Item::OAK_LOG);
+        let (_, charcoal) = find_cooking_recipe(RecipeType::Smelting, &log).unwrap();
+        assert_eq!(charcoal.item.id, Item::CHARCOAL.id);
+    }
+
+    #[test]
+    fn fuels() {
+        assert_eq!(fuel_burn_time(&Item::COAL), Some(1600));
+        assert_eq!(fuel_burn_time(&Item::OAK_PLANKS), Some(300));
+        assert_eq!(fuel_burn_time(&Item::CRIMSON_PLANKS), None);
+        assert_eq!(fuel_burn_time(&Item::STONE), None);
+    }
+}
@@ -26,6 +26,12 @@ pub enum Furnace {
     MaximumProgress,
 }
 
+impl WindowPropertyTrait for Furnace {
+    fn to_id(self) -> i16 {
+        self as i16
+    }
+}
+
 pub enum EnchantmentTable {
     LevelRequirement { slot: u8 },
     EnchantmentSeed,
@@ -61,6 +67,12 @@ pub enum BrewingStand {
     FuelTime,
 }
 
+impl WindowPropertyTrait for BrewingStand {
+    fn to_id(self) -> i16 {
+        self as i16
+    }
+}
+
 pub enum Stonecutter {
     SelectedRecipe,
 }
@@ -2,6 +2,10 @@ use crate::deserializer::ReadAdaptor;
 use crate::serializer::WriteAdaptor;
 use crate::tag::NbtTag;
 use crate::{END_ID, Error, Nbt, get_nbt_string};
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::io::{ErrorKind, Read, Write};
 use std::vec::IntoIter;
 
@@ -241,3 +245,39 @@ impl AsRef<NbtCompound> for NbtCompound {
         self
     }
 }
+
+impl Serialize for NbtCompound {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.child_tags.len()))?;
+        for (name, tag) in &self.child_tags {
+            map.serialize_entry(name, tag)?;
+        }
+        map.end()
+    }
+}
+
+pub(crate) struct NbtCompoundVisitor;
+
+impl<'de> Visitor<'de> for NbtCompoundVisitor {
+    type Value = NbtCompound;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an NBT compound")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<NbtCompound, A::Error> {
+        let mut compound = NbtCompound::new();
+        while let Some((name, tag)) = map.next_entry::<String, NbtTag>()? {
+            compound.child_tags.push((name, tag));
+        }
+        Ok(compound)
+    }
+}
+
+/// Reads any compound, like data of a format which isn't fully known. See the deserialization
+/// of [`NbtTag`] for what is lost
+impl<'de> Deserialize<'de> for NbtCompound {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(NbtCompoundVisitor)
+    }
+}
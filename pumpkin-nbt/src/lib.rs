@@ -211,12 +211,14 @@ impl_array!(nbt_byte_array, NBT_BYTE_ARRAY_TAG);
 mod test {
 
     use crate::Error;
+    use crate::compound::NbtCompound;
     use crate::deserializer::from_bytes;
     use crate::nbt_byte_array;
     use crate::nbt_int_array;
     use crate::nbt_long_array;
     use crate::serializer::to_bytes;
     use crate::serializer::to_bytes_named;
+    use crate::tag::NbtTag;
     use crate::{deserializer::from_bytes_unnamed, serializer::to_bytes_unnamed};
     use serde::{Deserialize, Serialize};
 
@@ -520,5 +522,37 @@ mod test {
         assert_eq!(value, reconstructed);
     }
 
+    #[test]
+    fn compound_roundtrip() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Holder {
+            data: Vec<NbtCompound>,
+        }
+
+        let mut item = NbtCompound::new();
+        item.put_byte("Slot", 3);
+        item.put_string("id", "minecraft:stone".to_string());
+        let mut chest = NbtCompound::new();
+        chest.put_string("id", "minecraft:chest".to_string());
+        chest.put_list("Items", vec![NbtTag::Compound(item)].into_boxed_slice());
+        chest.put_list("Empty", Box::new([]));
+        chest.put("Position", NbtTag::IntArray(Box::new([1, 2, 3])));
+        let value = Holder { data: vec![chest] };
+
+        let mut bytes = Vec::new();
+        to_bytes(&value, &mut bytes).unwrap();
+        let reconstructed: Holder = from_bytes(&bytes[..]).unwrap();
+
+        // Arrays come back as lists, everything else is the same
+        let data = &reconstructed.data[0];
+        assert_eq!(data.get_string("id").unwrap(), "minecraft:chest");
+        assert_eq!(data.get_list("Items"), value.data[0].get_list("Items"));
+        assert_eq!(data.get_list("Empty").unwrap().len(), 0);
+        assert_eq!(
+            data.get_list("Position").unwrap(),
+            [NbtTag::Int(1), NbtTag::Int(2), NbtTag::Int(3)]
+        );
+    }
+
     // TODO: More robust tests
 }
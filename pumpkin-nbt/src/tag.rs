@@ -1,7 +1,10 @@
-use compound::NbtCompound;
+use compound::{NbtCompound, NbtCompoundVisitor};
 use deserializer::ReadAdaptor;
 use io::Read;
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
 use serializer::WriteAdaptor;
+use std::fmt;
 
 use crate::*;
 
@@ -389,3 +392,93 @@ impl From<bool> for NbtTag {
         NbtTag::Byte(value as i8)
     }
 }
+
+/// Lists are written as NBT lists and arrays as NBT arrays, nested compounds keep their order
+impl Serialize for NbtTag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            NbtTag::End => Err(serde::ser::Error::custom("an end tag has no value")),
+            NbtTag::Byte(value) => serializer.serialize_i8(*value),
+            NbtTag::Short(value) => serializer.serialize_i16(*value),
+            NbtTag::Int(value) => serializer.serialize_i32(*value),
+            NbtTag::Long(value) => serializer.serialize_i64(*value),
+            NbtTag::Float(value) => serializer.serialize_f32(*value),
+            NbtTag::Double(value) => serializer.serialize_f64(*value),
+            NbtTag::ByteArray(value) => nbt_byte_array(value, serializer),
+            NbtTag::String(value) => serializer.serialize_str(value),
+            NbtTag::List(value) => value.serialize(serializer),
+            NbtTag::Compound(value) => value.serialize(serializer),
+            NbtTag::IntArray(value) => nbt_int_array(value, serializer),
+            NbtTag::LongArray(value) => nbt_long_array(value, serializer),
+        }
+    }
+}
+
+struct NbtTagVisitor;
+
+impl<'de> Visitor<'de> for NbtTagVisitor {
+    type Value = NbtTag;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an NBT tag")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, value: bool) -> Result<NbtTag, E> {
+        Ok(NbtTag::from(value))
+    }
+
+    fn visit_i8<E: serde::de::Error>(self, value: i8) -> Result<NbtTag, E> {
+        Ok(NbtTag::Byte(value))
+    }
+
+    fn visit_u8<E: serde::de::Error>(self, value: u8) -> Result<NbtTag, E> {
+        Ok(NbtTag::Byte(value as i8))
+    }
+
+    fn visit_i16<E: serde::de::Error>(self, value: i16) -> Result<NbtTag, E> {
+        Ok(NbtTag::Short(value))
+    }
+
+    fn visit_i32<E: serde::de::Error>(self, value: i32) -> Result<NbtTag, E> {
+        Ok(NbtTag::Int(value))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<NbtTag, E> {
+        Ok(NbtTag::Long(value))
+    }
+
+    fn visit_f32<E: serde::de::Error>(self, value: f32) -> Result<NbtTag, E> {
+        Ok(NbtTag::Float(value))
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<NbtTag, E> {
+        Ok(NbtTag::Double(value))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<NbtTag, E> {
+        Ok(NbtTag::String(value.to_string()))
+    }
+
+    fn visit_string<E: serde::de::Error>(self, value: String) -> Result<NbtTag, E> {
+        Ok(NbtTag::String(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<NbtTag, A::Error> {
+        let mut list = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(tag) = seq.next_element()? {
+            list.push(tag);
+        }
+        Ok(NbtTag::List(list.into_boxed_slice()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<NbtTag, A::Error> {
+        NbtCompoundVisitor.visit_map(map).map(NbtTag::Compound)
+    }
+}
+
+/// The deserializer doesn't tell arrays apart from lists, so arrays are read back as lists
+impl<'de> Deserialize<'de> for NbtTag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(NbtTagVisitor)
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::{
+    Mutex,
+    atomic::{AtomicU64, Ordering},
+};
+
+use bytes::{Bytes, BytesMut};
+
+/// The most buffers kept around for reuse
+const MAX_POOLED_BUFFERS: usize = 1024;
+/// Buffers which grew larger than this, like for a burst of chunks, are freed instead of pooled
+const MAX_POOLED_CAPACITY: usize = 64 * 1024;
+
+/// The buffers packet encoders write into. Once the connection wrote them, they are given back
+/// here so the next packets don't need a new allocation
+pub static BUFFER_POOL: BufferPool = BufferPool::new(MAX_POOLED_BUFFERS, MAX_POOLED_CAPACITY);
+
+pub struct BufferPool {
+    buffers: Mutex<Vec<BytesMut>>,
+    max_buffers: usize,
+    max_capacity: usize,
+    reused: AtomicU64,
+    allocated: AtomicU64,
+}
+
+impl BufferPool {
+    pub const fn new(max_buffers: usize, max_capacity: usize) -> Self {
+        Self {
+            buffers: Mutex::new(Vec::new()),
+            max_buffers,
+            max_capacity,
+            reused: AtomicU64::new(0),
+            allocated: AtomicU64::new(0),
+        }
+    }
+
+    /// An empty buffer, one given back before if there is any
+    pub fn take(&self) -> BytesMut {
+        let pooled = self
+            .buffers
+            .lock()
+            .expect("Buffer pool lock is poisoned")
+            .pop();
+        if let Some(buf) = pooled {
+            self.reused.fetch_add(1, Ordering::Relaxed);
+            return buf;
+        }
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+        BytesMut::new()
+    }
+
+    /// Gives the buffers of written parts back. Parts which are still used elsewhere, like the
+    /// frames of shared packets, are only dropped
+    pub fn recycle(&self, parts: Vec<Bytes>) {
+        let mut buffers = self.buffers.lock().expect("Buffer pool lock is poisoned");
+        for part in parts {
+            if buffers.len() >= self.max_buffers {
+                break;
+            }
+            let Ok(mut buf) = part.try_into_mut() else {
+                continue;
+            };
+            if buf.capacity() == 0 || buf.capacity() > self.max_capacity {
+                continue;
+            }
+            buf.clear();
+            buffers.push(buf);
+        }
+    }
+
+    /// How many buffers were handed out again instead of allocating new ones
+    pub fn reused(&self) -> u64 {
+        self.reused.load(Ordering::Relaxed)
+    }
+
+    pub fn allocated(&self) -> u64 {
+        self.allocated.load(Ordering::Relaxed)
+    }
+
+    pub fn pooled(&self) -> usize {
+        self.buffers
+            .lock()
+            .expect("Buffer pool lock is poisoned")
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, Bytes};
+
+    use super::BufferPool;
+
+    fn written(pool: &BufferPool, len: usize) -> Bytes {
+        let mut buf = pool.take();
+        buf.put_bytes(1, len);
+        buf.freeze()
+    }
+
+    #[test]
+    fn buffers_are_reused() {
+        let pool = BufferPool::new(4, 1024);
+        let part = written(&pool, 100);
+        let ptr = part.as_ptr();
+        pool.recycle(vec![part]);
+
+        let buf = pool.take();
+        assert!(buf.is_empty());
+        assert_eq!(buf.as_ptr(), ptr);
+        assert_eq!((pool.allocated(), pool.reused()), (1, 1));
+    }
+
+    #[test]
+    fn shared_and_large_buffers_are_not_pooled() {
+        let pool = BufferPool::new(4, 1024);
+        let shared = written(&pool, 100);
+        let _frame = shared.clone();
+        pool.recycle(vec![shared, written(&pool, 4096), Bytes::new()]);
+        assert_eq!(pool.pooled(), 0);
+    }
+
+    #[test]
+    fn pool_is_capped() {
+        let pool = BufferPool::new(2, 1024);
+        let parts = (0..5).map(|_| written(&pool, 10)).collect();
+        pool.recycle(parts);
+        assert_eq!(pool.pooled(), 2);
+    }
+}
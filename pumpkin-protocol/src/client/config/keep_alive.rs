@@ -0,0 +1,15 @@
+use pumpkin_data::packet::clientbound::CONFIG_KEEP_ALIVE;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[packet(CONFIG_KEEP_ALIVE)]
+pub struct CConfigKeepAlive {
+    pub keep_alive_id: i64,
+}
+
+impl CConfigKeepAlive {
+    pub fn new(keep_alive_id: i64) -> Self {
+        Self { keep_alive_id }
+    }
+}
@@ -2,6 +2,7 @@ mod add_resource_pack;
 mod config_disconnect;
 mod cookie_request;
 mod finish_config;
+mod keep_alive;
 mod known_packs;
 mod plugin_message;
 mod registry_data;
@@ -14,6 +15,7 @@ pub use add_resource_pack::*;
 pub use config_disconnect::*;
 pub use cookie_request::*;
 pub use finish_config::*;
+pub use keep_alive::*;
 pub use known_packs::*;
 pub use plugin_message::*;
 pub use registry_data::*;
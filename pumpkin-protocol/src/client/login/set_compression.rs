@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::VarInt;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(LOGIN_LOGIN_COMPRESSION)]
 pub struct CSetCompression {
     pub threshold: VarInt,
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::VarInt;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(PLAY_BLOCK_CHANGED_ACK)]
 pub struct CAcknowledgeBlockChange {
     pub sequence_id: VarInt,
@@ -0,0 +1,45 @@
+use std::ops::Range;
+
+use crate::{ClientPacket, VarInt, bytebuf::ByteBufMut};
+
+use bytes::{BufMut, BytesMut};
+use pumpkin_data::packet::clientbound::PLAY_CHUNKS_BIOMES;
+use pumpkin_macros::packet;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_world::chunk::ChunkBiomes;
+
+use super::chunk_data::{dimension_sections, put_biome_section};
+
+/// Resends the biomes of loaded chunks, e.g. after `/fillbiome`. Like `CChunkData`, only the
+/// sections within the height of the dimension are written
+#[packet(PLAY_CHUNKS_BIOMES)]
+pub struct CChunkBiomes<'a> {
+    chunks: &'a [(Vector2<i32>, ChunkBiomes)],
+    sections: Range<usize>,
+}
+
+impl<'a> CChunkBiomes<'a> {
+    pub fn new(chunks: &'a [(Vector2<i32>, ChunkBiomes)], min_y: i32, height: i32) -> Self {
+        Self {
+            chunks,
+            sections: dimension_sections(min_y, height),
+        }
+    }
+}
+
+impl ClientPacket for CChunkBiomes<'_> {
+    fn write(&self, buf: &mut impl BufMut) {
+        buf.put_var_int(&VarInt(self.chunks.len() as i32));
+        for (position, biomes) in self.chunks {
+            // The chunk position packed into a long, z in the upper half
+            buf.put_i64((i64::from(position.z) << 32) | (i64::from(position.x) & 0xFFFF_FFFF));
+
+            let mut data_buf = BytesMut::new();
+            for i in self.sections.clone() {
+                put_biome_section(&mut data_buf, &biomes.section(i));
+            }
+            buf.put_var_int(&VarInt(data_buf.len() as i32));
+            buf.put_slice(&data_buf);
+        }
+    }
+}
@@ -1,30 +1,67 @@
+use std::ops::Range;
+
 use crate::{ClientPacket, VarInt, bytebuf::ByteBufMut, codec::bit_set::BitSet};
 
 use bytes::{BufMut, BytesMut};
+use pumpkin_data::chunk::Biome;
 use pumpkin_data::packet::clientbound::PLAY_LEVEL_CHUNK_WITH_LIGHT;
 use pumpkin_macros::packet;
+use pumpkin_util::math::ceil_log2;
 use pumpkin_world::{
-    DIRECT_PALETTE_BITS,
-    chunk::{ChunkData, SUBCHUNKS_COUNT},
+    DIRECT_PALETTE_BITS, WORLD_LOWEST_Y,
+    chunk::{BIOME_SECTION_VOLUME, ChunkData, SUBCHUNK_VOLUME, SUBCHUNKS_COUNT},
 };
 
+/// The stored sections of a chunk within a dimension starting at `min_y`, which is `height`
+/// blocks high
+pub fn dimension_sections(min_y: i32, height: i32) -> Range<usize> {
+    let first = ((min_y - i32::from(WORLD_LOWEST_Y)) / 16).clamp(0, SUBCHUNKS_COUNT as i32);
+    let last = (first + height / 16).clamp(first, SUBCHUNKS_COUNT as i32);
+    first as usize..last as usize
+}
+
+/// Chunks are stored as high as the overworld in every dimension. Only the sections within the
+/// height of the dimension the chunk is sent in are written, as many as the client expects
 #[packet(PLAY_LEVEL_CHUNK_WITH_LIGHT)]
-pub struct CChunkData<'a>(pub &'a ChunkData);
+pub struct CChunkData<'a> {
+    chunk: &'a ChunkData,
+    sections: Range<usize>,
+}
+
+impl<'a> CChunkData<'a> {
+    /// The chunk as seen in a dimension starting at `min_y`, which is `height` blocks high
+    pub fn new(chunk: &'a ChunkData, min_y: i32, height: i32) -> Self {
+        Self {
+            chunk,
+            sections: dimension_sections(min_y, height),
+        }
+    }
+
+    fn subchunks(&self) -> impl Iterator<Item = Box<[u16; SUBCHUNK_VOLUME]>> + '_ {
+        self.chunk
+            .subchunks
+            .array_iter()
+            .skip(self.sections.start)
+            .take(self.sections.len())
+    }
+}
 
 impl ClientPacket for CChunkData<'_> {
     fn write(&self, buf: &mut impl BufMut) {
         // Chunk X
-        buf.put_i32(self.0.position.x);
+        buf.put_i32(self.chunk.position.x);
         // Chunk Z
-        buf.put_i32(self.0.position.z);
+        buf.put_i32(self.chunk.position.z);
 
         let mut heightmap_nbt = Vec::new();
-        pumpkin_nbt::serializer::to_bytes_unnamed(&self.0.heightmap, &mut heightmap_nbt).unwrap();
+        pumpkin_nbt::serializer::to_bytes_unnamed(&self.chunk.heightmap, &mut heightmap_nbt)
+            .unwrap();
         // Heightmaps
         buf.put_slice(&heightmap_nbt);
 
         let mut data_buf = BytesMut::new();
-        self.0.subchunks.array_iter().for_each(|subchunk| {
+        let mut section = self.sections.start;
+        self.subchunks().for_each(|subchunk| {
             let block_count = subchunk.len() as i16;
             // Block count
             data_buf.put_i16(block_count);
@@ -99,11 +136,8 @@ impl ClientPacket for CChunkData<'_> {
             }
 
             //// Biomes
-            // TODO: make biomes work
-            data_buf.put_u8(0);
-            // This seems to be the biome
-            data_buf.put_var_int(&VarInt(10));
-            data_buf.put_var_int(&VarInt(0));
+            put_biome_section(&mut data_buf, &self.chunk.biomes.section(section));
+            section += 1;
         });
 
         // Size
@@ -117,7 +151,11 @@ impl ClientPacket for CChunkData<'_> {
         // Sky Light Mask
         // All of the chunks, this is not optimal and uses way more data than needed but will be
         // overhauled with full lighting system.
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0b01111111111111111111111110]));
+        // The light sections go from one below the lowest section to one above the highest
+        buf.put_bit_set(&BitSet(
+            VarInt(1),
+            vec![((1i64 << self.sections.len()) - 1) << 1],
+        ));
         // Block Light Mask
         buf.put_bit_set(&BitSet(VarInt(1), vec![0]));
         // Empty Sky Light Mask
@@ -125,8 +163,8 @@ impl ClientPacket for CChunkData<'_> {
         // Empty Block Light Mask
         buf.put_bit_set(&BitSet(VarInt(1), vec![0]));
 
-        buf.put_var_int(&VarInt(SUBCHUNKS_COUNT as i32));
-        self.0.subchunks.array_iter().for_each(|chunk| {
+        buf.put_var_int(&VarInt(self.sections.len() as i32));
+        self.subchunks().for_each(|chunk| {
             let mut chunk_light = [0u8; 2048];
             for (i, _) in chunk.iter().enumerate() {
                 // if !block .is_air() {
@@ -145,3 +183,91 @@ impl ClientPacket for CChunkData<'_> {
         buf.put_var_int(&VarInt(0));
     }
 }
+
+/// Writes the biomes of a subchunk as a paletted container
+pub(crate) fn put_biome_section(buf: &mut impl BufMut, biomes: &[Biome; BIOME_SECTION_VOLUME]) {
+    let mut palette: Vec<Biome> = Vec::new();
+    for biome in biomes {
+        if !palette.contains(biome) {
+            palette.push(*biome);
+        }
+    }
+    if let [biome] = palette[..] {
+        // Bits per entry, a single value has no data
+        buf.put_u8(0);
+        buf.put_var_int(&VarInt(biome.id().into()));
+        // Data array length
+        buf.put_var_int(&VarInt(0));
+        return;
+    }
+
+    // The client only uses an indirect palette for up to 3 bits, otherwise the registry ids
+    let palette_bits = u32::from(ceil_log2(palette.len() as u32));
+    let direct = palette_bits > 3;
+    let bits = if direct {
+        u32::from(ceil_log2(Biome::COUNT as u32))
+    } else {
+        palette_bits
+    };
+    // Bits per entry
+    buf.put_u8(bits as u8);
+    if !direct {
+        // Palette length
+        buf.put_var_int(&VarInt(palette.len() as i32));
+        palette.iter().for_each(|biome| {
+            // Palette
+            buf.put_var_int(&VarInt(biome.id().into()));
+        });
+    }
+
+    let biomes_per_long = (64 / bits) as usize;
+    // Data array length
+    buf.put_var_int(&VarInt(biomes.len().div_ceil(biomes_per_long) as i32));
+    for clump in biomes.chunks(biomes_per_long) {
+        let mut out_long: i64 = 0;
+        for (i, biome) in clump.iter().enumerate() {
+            let value = if direct {
+                i64::from(biome.id())
+            } else {
+                palette.iter().position(|entry| entry == biome).unwrap() as i64
+            };
+            out_long |= value << (i as u32 * bits);
+        }
+        buf.put_i64(out_long);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bytes::BytesMut;
+    use pumpkin_world::chunk::{ChunkData, ChunkHeightmaps, Subchunks};
+
+    use super::CChunkData;
+    use crate::ClientPacket;
+
+    #[test]
+    fn sections_fit_the_dimension() {
+        let chunk = ChunkData {
+            subchunks: Subchunks::Single(1),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: Default::default(),
+            light: Default::default(),
+            block_entities: HashMap::new(),
+            position: (0, 0).into(),
+            dirty: false,
+        };
+        // The overworld, the nether and the end
+        let overworld = CChunkData::new(&chunk, -64, 384);
+        assert_eq!(overworld.sections, 0..24);
+        let nether = CChunkData::new(&chunk, 0, 256);
+        assert_eq!(nether.sections, 4..20);
+        assert_eq!(nether.subchunks().count(), 16);
+
+        let (mut overworld_buf, mut nether_buf) = (BytesMut::new(), BytesMut::new());
+        overworld.write(&mut overworld_buf);
+        nether.write(&mut nether_buf);
+        assert!(nether_buf.len() < overworld_buf.len());
+    }
+}
@@ -0,0 +1,70 @@
+use bytes::BufMut;
+use pumpkin_data::packet::clientbound::PLAY_CUSTOM_PAYLOAD;
+use pumpkin_macros::packet;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+use crate::{ClientPacket, VarInt, bytebuf::ByteBufMut, codec::identifier::Identifier};
+
+/// A game event for the game event debug renderer of the client, sent as a custom payload
+#[packet(PLAY_CUSTOM_PAYLOAD)]
+pub struct CDebugGameEvent {
+    event: Identifier,
+    position: Vector3<f64>,
+}
+
+impl CDebugGameEvent {
+    pub fn new(event: Identifier, position: Vector3<f64>) -> Self {
+        Self { event, position }
+    }
+}
+
+impl ClientPacket for CDebugGameEvent {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_identifier(&Identifier::vanilla("debug/game_event"));
+        bytebuf.put_identifier(&self.event);
+        bytebuf.put_f64(self.position.x);
+        bytebuf.put_f64(self.position.y);
+        bytebuf.put_f64(self.position.z);
+    }
+}
+
+/// Where a game event listener is
+pub enum PositionSource {
+    Block(BlockPos),
+    Entity { entity_id: VarInt, y_offset: f32 },
+}
+
+/// A game event listener and how far it hears, for the game event debug renderer of the client
+#[packet(PLAY_CUSTOM_PAYLOAD)]
+pub struct CDebugGameEventListener {
+    source: PositionSource,
+    range: VarInt,
+}
+
+impl CDebugGameEventListener {
+    pub fn new(source: PositionSource, range: VarInt) -> Self {
+        Self { source, range }
+    }
+}
+
+impl ClientPacket for CDebugGameEventListener {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_identifier(&Identifier::vanilla("debug/game_event_listeners"));
+        // The ids of the position source types in their registry
+        match &self.source {
+            PositionSource::Block(position) => {
+                bytebuf.put_var_int(&VarInt(0));
+                bytebuf.put_i64(position.as_long());
+            }
+            PositionSource::Entity {
+                entity_id,
+                y_offset,
+            } => {
+                bytebuf.put_var_int(&VarInt(1));
+                bytebuf.put_var_int(entity_id);
+                bytebuf.put_f32(*y_offset);
+            }
+        }
+        bytebuf.put_var_int(&self.range);
+    }
+}
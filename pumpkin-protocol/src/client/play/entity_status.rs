@@ -2,7 +2,7 @@ use pumpkin_data::packet::clientbound::PLAY_ENTITY_EVENT;
 use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(PLAY_ENTITY_EVENT)]
 pub struct CEntityStatus {
     entity_id: i32,
@@ -2,7 +2,7 @@ use pumpkin_data::packet::clientbound::PLAY_GAME_EVENT;
 use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(PLAY_GAME_EVENT)]
 pub struct CGameEvent {
     event: u8,
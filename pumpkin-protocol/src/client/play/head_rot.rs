@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::VarInt;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(PLAY_ROTATE_HEAD)]
 pub struct CHeadRot {
     entity_id: VarInt,
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::VarInt;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(PLAY_HURT_ANIMATION)]
 pub struct CHurtAnimation {
     entity_id: VarInt,
@@ -2,7 +2,7 @@ use pumpkin_data::packet::clientbound::PLAY_KEEP_ALIVE;
 use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(PLAY_KEEP_ALIVE)]
 pub struct CKeepAlive {
     pub keep_alive_id: i64,
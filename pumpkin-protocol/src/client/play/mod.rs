@@ -10,6 +10,7 @@ mod center_chunk;
 mod change_difficulty;
 mod chunk_batch_end;
 mod chunk_batch_start;
+mod chunk_biomes;
 mod chunk_data;
 mod clear_title;
 mod close_container;
@@ -18,6 +19,7 @@ mod command_suggestions;
 mod commands;
 mod cookie_request;
 mod damage_event;
+mod debug_game_event;
 mod disconnect;
 mod disguised_chat_message;
 mod display_objective;
@@ -45,6 +47,7 @@ mod player_info_update;
 mod player_position;
 mod player_remove;
 mod remove_entities;
+mod remove_mob_effect;
 mod reset_score;
 mod respawn;
 mod server_links;
@@ -70,14 +73,19 @@ mod subtitle;
 mod system_chat_message;
 mod take_item;
 mod teleport_entity;
+mod ticking_state;
+mod ticking_step;
 mod transfer;
 mod unload_chunk;
+mod update_advancements;
 mod update_entity_pos;
 mod update_entity_pos_rot;
 mod update_entity_rot;
 mod update_mob_effect;
 mod update_objectives;
+mod update_recipes;
 mod update_score;
+mod update_teams;
 mod worldevent;
 
 pub use acknowledge_block::*;
@@ -92,6 +100,7 @@ pub use center_chunk::*;
 pub use change_difficulty::*;
 pub use chunk_batch_end::*;
 pub use chunk_batch_start::*;
+pub use chunk_biomes::*;
 pub use chunk_data::*;
 pub use clear_title::*;
 pub use close_container::*;
@@ -100,6 +109,7 @@ pub use command_suggestions::*;
 pub use commands::*;
 pub use cookie_request::*;
 pub use damage_event::*;
+pub use debug_game_event::*;
 pub use disconnect::*;
 pub use disguised_chat_message::*;
 pub use display_objective::*;
@@ -127,6 +137,7 @@ pub use player_info_update::*;
 pub use player_position::*;
 pub use player_remove::*;
 pub use remove_entities::*;
+pub use remove_mob_effect::*;
 pub use reset_score::*;
 pub use respawn::*;
 pub use server_links::*;
@@ -152,12 +163,17 @@ pub use subtitle::*;
 pub use system_chat_message::*;
 pub use take_item::*;
 pub use teleport_entity::*;
+pub use ticking_state::*;
+pub use ticking_step::*;
 pub use transfer::*;
 pub use unload_chunk::*;
+pub use update_advancements::*;
 pub use update_entity_pos::*;
 pub use update_entity_pos_rot::*;
 pub use update_entity_rot::*;
 pub use update_mob_effect::*;
 pub use update_objectives::*;
+pub use update_recipes::*;
 pub use update_score::*;
+pub use update_teams::*;
 pub use worldevent::*;
@@ -2,7 +2,7 @@ use pumpkin_data::packet::clientbound::PLAY_PONG_RESPONSE;
 use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(PLAY_PONG_RESPONSE)]
 pub struct CPingResponse {
     pub payload: i64,
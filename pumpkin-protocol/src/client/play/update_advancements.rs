@@ -0,0 +1,121 @@
+use bytes::{BufMut, BytesMut};
+use pumpkin_data::packet::clientbound::PLAY_UPDATE_ADVANCEMENTS;
+use pumpkin_macros::packet;
+use pumpkin_util::text::TextComponent;
+use serde::Serialize;
+
+use crate::{
+    ClientPacket,
+    bytebuf::{ByteBufMut, serializer::Serializer},
+    codec::{slot::Slot, var_int::VarInt},
+};
+
+/// Adds advancements to the advancement screen of the client and updates its progress
+#[packet(PLAY_UPDATE_ADVANCEMENTS)]
+pub struct CUpdateAdvancements {
+    /// Whether the client forgets every advancement it knew before
+    reset: bool,
+    added: Vec<AdvancementEntry>,
+    removed: Vec<String>,
+    progress: Vec<AdvancementProgressEntry>,
+}
+
+impl CUpdateAdvancements {
+    pub fn new(
+        reset: bool,
+        added: Vec<AdvancementEntry>,
+        removed: Vec<String>,
+        progress: Vec<AdvancementProgressEntry>,
+    ) -> Self {
+        Self {
+            reset,
+            added,
+            removed,
+            progress,
+        }
+    }
+}
+
+pub struct AdvancementEntry {
+    pub id: String,
+    pub parent: Option<String>,
+    pub display: Option<AdvancementEntryDisplay>,
+    /// Every list needs one of its criteria done
+    pub requirements: Vec<Vec<String>>,
+    pub sends_telemetry_event: bool,
+}
+
+pub struct AdvancementEntryDisplay {
+    pub title: TextComponent,
+    pub description: TextComponent,
+    pub icon: Slot,
+    /// 0 for a task, 1 for a challenge and 2 for a goal
+    pub frame: VarInt,
+    pub background: Option<String>,
+    pub show_toast: bool,
+    pub hidden: bool,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// When each criterion of the advancement was done, in milliseconds since the epoch, `None` if
+/// it isn't yet
+pub struct AdvancementProgressEntry {
+    pub id: String,
+    pub criteria: Vec<(String, Option<i64>)>,
+}
+
+impl ClientPacket for CUpdateAdvancements {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_bool(self.reset);
+        bytebuf.put_list(&self.added, |bytebuf, advancement| {
+            bytebuf.put_string(&advancement.id);
+            bytebuf.put_option(&advancement.parent, |bytebuf, parent| {
+                bytebuf.put_string(parent);
+            });
+            bytebuf.put_option(&advancement.display, |bytebuf, display| {
+                write_display(bytebuf, display);
+            });
+            bytebuf.put_list(&advancement.requirements, |bytebuf, any| {
+                bytebuf.put_list(any, |bytebuf, criterion| bytebuf.put_string(criterion));
+            });
+            bytebuf.put_bool(advancement.sends_telemetry_event);
+        });
+        bytebuf.put_list(&self.removed, |bytebuf, id| bytebuf.put_string(id));
+        bytebuf.put_list(&self.progress, |bytebuf, progress| {
+            bytebuf.put_string(&progress.id);
+            bytebuf.put_list(&progress.criteria, |bytebuf, (criterion, done)| {
+                bytebuf.put_string(criterion);
+                bytebuf.put_option(done, |bytebuf, date| bytebuf.put_i64(*date));
+            });
+        });
+    }
+}
+
+fn write_display(bytebuf: &mut impl BufMut, display: &AdvancementEntryDisplay) {
+    bytebuf.put_slice(&display.title.encode());
+    bytebuf.put_slice(&display.description.encode());
+    let mut serializer = Serializer::new(BytesMut::new());
+    display
+        .icon
+        .serialize(&mut serializer)
+        .expect("Could not serialize the advancement icon");
+    bytebuf.put(serializer.output);
+    bytebuf.put_var_int(&display.frame);
+    let mut flags = 0;
+    if display.background.is_some() {
+        flags |= 0x01;
+    }
+    if display.show_toast {
+        flags |= 0x02;
+    }
+    if display.hidden {
+        flags |= 0x04;
+    }
+    bytebuf.put_i32(flags);
+    if let Some(background) = &display.background {
+        bytebuf.put_string(background);
+    }
+    bytebuf.put_f32(display.x);
+    bytebuf.put_f32(display.y);
+}
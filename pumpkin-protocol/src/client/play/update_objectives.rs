@@ -61,12 +61,14 @@ impl ClientPacket for CUpdateObjectives<'_> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum Mode {
     Add,
     Remove,
     Update,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum RenderType {
     Integer,
     Hearts,
@@ -0,0 +1,80 @@
+use bytes::{BufMut, BytesMut};
+use pumpkin_data::packet::clientbound::PLAY_UPDATE_RECIPES;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::{
+    ClientPacket,
+    bytebuf::{ByteBufMut, serializer::Serializer},
+    codec::{slot::Slot, var_int::VarInt},
+};
+
+/// The slot display showing a whole item stack
+const ITEM_STACK_DISPLAY: i32 = 3;
+
+/// Tells the client which items go into the input slots of furnaces and smithing tables, and
+/// which recipes the stonecutter offers
+#[packet(PLAY_UPDATE_RECIPES)]
+pub struct CUpdateRecipes<'a> {
+    property_sets: &'a [RecipePropertySet],
+    stonecutter_recipes: &'a [StonecutterRecipe],
+}
+
+impl<'a> CUpdateRecipes<'a> {
+    pub fn new(
+        property_sets: &'a [RecipePropertySet],
+        stonecutter_recipes: &'a [StonecutterRecipe],
+    ) -> Self {
+        Self {
+            property_sets,
+            stonecutter_recipes,
+        }
+    }
+}
+
+/// The items allowed in an input slot, e.g. `minecraft:furnace_input`
+pub struct RecipePropertySet {
+    pub id: String,
+    pub items: Vec<VarInt>,
+}
+
+pub struct StonecutterRecipe {
+    pub ingredient: IdSet,
+    pub result: Slot,
+}
+
+/// Either a tag or a list of registry ids
+pub enum IdSet {
+    Tag(String),
+    Ids(Vec<VarInt>),
+}
+
+impl ClientPacket for CUpdateRecipes<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_list(self.property_sets, |bytebuf, set| {
+            bytebuf.put_string(&set.id);
+            bytebuf.put_list(&set.items, |bytebuf, item| bytebuf.put_var_int(item));
+        });
+        bytebuf.put_list(self.stonecutter_recipes, |bytebuf, recipe| {
+            match &recipe.ingredient {
+                IdSet::Tag(tag) => {
+                    bytebuf.put_var_int(&VarInt(0));
+                    bytebuf.put_string(tag);
+                }
+                IdSet::Ids(ids) => {
+                    bytebuf.put_var_int(&VarInt(ids.len() as i32 + 1));
+                    for id in ids {
+                        bytebuf.put_var_int(id);
+                    }
+                }
+            }
+            bytebuf.put_var_int(&VarInt(ITEM_STACK_DISPLAY));
+            let mut serializer = Serializer::new(BytesMut::new());
+            recipe
+                .result
+                .serialize(&mut serializer)
+                .expect("Could not serialize the stonecutter result");
+            bytebuf.put(serializer.output);
+        });
+    }
+}
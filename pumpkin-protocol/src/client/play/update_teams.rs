@@ -0,0 +1,85 @@
+use bytes::BufMut;
+use pumpkin_data::packet::clientbound::PLAY_SET_PLAYER_TEAM;
+use pumpkin_macros::packet;
+use pumpkin_util::text::TextComponent;
+
+use crate::{ClientPacket, VarInt, bytebuf::ByteBufMut};
+
+#[packet(PLAY_SET_PLAYER_TEAM)]
+pub struct CUpdateTeams<'a> {
+    team_name: &'a str,
+    method: TeamMethod<'a>,
+}
+
+impl<'a> CUpdateTeams<'a> {
+    pub fn new(team_name: &'a str, method: TeamMethod<'a>) -> Self {
+        Self { team_name, method }
+    }
+}
+
+impl ClientPacket for CUpdateTeams<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_string(self.team_name);
+        match &self.method {
+            TeamMethod::Create(info, entities) => {
+                bytebuf.put_u8(0);
+                info.write(bytebuf);
+                bytebuf.put_list(entities, |p, v| p.put_string(v));
+            }
+            TeamMethod::Remove => bytebuf.put_u8(1),
+            TeamMethod::Update(info) => {
+                bytebuf.put_u8(2);
+                info.write(bytebuf);
+            }
+            TeamMethod::AddEntities(entities) => {
+                bytebuf.put_u8(3);
+                bytebuf.put_list(entities, |p, v| p.put_string(v));
+            }
+            TeamMethod::RemoveEntities(entities) => {
+                bytebuf.put_u8(4);
+                bytebuf.put_list(entities, |p, v| p.put_string(v));
+            }
+        }
+    }
+}
+
+pub enum TeamMethod<'a> {
+    /// Creates the team together with its members
+    Create(TeamInfo<'a>, Vec<&'a str>),
+    Remove,
+    Update(TeamInfo<'a>),
+    AddEntities(Vec<&'a str>),
+    RemoveEntities(Vec<&'a str>),
+}
+
+pub struct TeamInfo<'a> {
+    pub display_name: &'a TextComponent,
+    pub friendly_fire: bool,
+    /// Whether members see invisible members of their team as translucent
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: &'a str,
+    pub collision_rule: &'a str,
+    /// The id of a named color, 21 is the reset formatting
+    pub color: VarInt,
+    pub prefix: &'a TextComponent,
+    pub suffix: &'a TextComponent,
+}
+
+impl TeamInfo<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_slice(&self.display_name.encode());
+        let mut flags = 0;
+        if self.friendly_fire {
+            flags |= 0x01;
+        }
+        if self.see_friendly_invisibles {
+            flags |= 0x02;
+        }
+        bytebuf.put_u8(flags);
+        bytebuf.put_string(self.name_tag_visibility);
+        bytebuf.put_string(self.collision_rule);
+        bytebuf.put_var_int(&self.color);
+        bytebuf.put_slice(&self.prefix.encode());
+        bytebuf.put_slice(&self.suffix.encode());
+    }
+}
@@ -2,7 +2,7 @@ use pumpkin_data::packet::clientbound::STATUS_PONG_RESPONSE;
 use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[packet(STATUS_PONG_RESPONSE)]
 pub struct CPingResponse {
     payload: i64, // must respond with the same as in `SPingRequest`
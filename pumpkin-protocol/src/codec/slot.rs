@@ -1,20 +1,274 @@
 use crate::VarInt;
 use pumpkin_data::item::Item;
-use pumpkin_world::item::ItemStack;
+use pumpkin_data::potion::Potion;
+use pumpkin_nbt::{Nbt, compound::NbtCompound};
+use pumpkin_util::math::position::{BlockPos, GlobalPos};
+use pumpkin_world::item::{AdventurePredicate, BlockSet, ItemStack, LodestoneTracker};
 use serde::ser::SerializeSeq;
 use serde::{
     Deserialize, Serialize, Serializer,
-    de::{self, SeqAccess},
+    de::{self, DeserializeSeed, SeqAccess},
 };
 
+/// The id of the `can_place_on` data component
+const CAN_PLACE_ON: i32 = 11;
+/// The id of the `can_break` data component
+const CAN_BREAK: i32 = 12;
+/// The id of the `bundle_contents` data component
+const BUNDLE_CONTENTS: i32 = 40;
+/// The id of the `potion_contents` data component
+const POTION_CONTENTS: i32 = 41;
+/// The id of the `bucket_entity_data` data component
+const BUCKET_ENTITY_DATA: i32 = 48;
+/// The id of the `lodestone_tracker` data component
+const LODESTONE_TRACKER: i32 = 54;
+/// The id of the `container` data component
+const CONTAINER: i32 = 62;
+
+/// Most slots a container component may have
+const MAX_CONTAINER_SLOTS: i32 = 256;
+/// Most block predicates `can_place_on` and `can_break` may have
+const MAX_BLOCK_PREDICATES: i32 = 256;
+/// Most stacks a bundle can hold, every item takes up at least 1/64 of it
+const MAX_BUNDLE_STACKS: i32 = 64;
+/// Items holding items holding items, like bundles in bundles, are refused when nested deeper
+/// than this
+const MAX_NESTING: u8 = 16;
+
 #[derive(Debug, Clone)]
 pub struct Slot {
     pub item_count: VarInt,
     item_id: Option<VarInt>,
-    num_components_to_add: Option<VarInt>,
-    num_components_to_remove: Option<VarInt>,
-    components_to_add: Option<Vec<(VarInt, ())>>, // The second type depends on the varint
-    components_to_remove: Option<Vec<VarInt>>,
+    components_to_add: Vec<SlotComponent>,
+    components_to_remove: Vec<VarInt>,
+}
+
+/// The data components we know how to send and receive
+#[derive(Debug, Clone)]
+enum SlotComponent {
+    BundleContents(Vec<Slot>),
+    PotionContents(Potion),
+    Container(Vec<Slot>),
+    LodestoneTracker(LodestoneTracker),
+    /// Only sent, reading network NBT from a slot isn't supported yet
+    BucketEntityData(NbtCompound),
+    CanPlaceOn(AdventurePredicate),
+    CanBreak(AdventurePredicate),
+}
+
+impl Serialize for SlotComponent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::BundleContents(slots) | Self::Container(slots) => {
+                let id = if matches!(self, Self::BundleContents(_)) {
+                    BUNDLE_CONTENTS
+                } else {
+                    CONTAINER
+                };
+                let mut s = serializer.serialize_seq(None)?;
+                s.serialize_element(&VarInt(id))?;
+                s.serialize_element(&VarInt(slots.len() as i32))?;
+                for slot in slots {
+                    s.serialize_element(slot)?;
+                }
+                s.end()
+            }
+            Self::PotionContents(potion) => {
+                let mut s = serializer.serialize_seq(None)?;
+                s.serialize_element(&VarInt(POTION_CONTENTS))?;
+                s.serialize_element(&Some(VarInt(i32::from(potion.id()))))?;
+                // Custom color
+                s.serialize_element(&None::<i32>)?;
+                // Custom effects
+                s.serialize_element(&VarInt(0))?;
+                // Custom name
+                s.serialize_element(&None::<String>)?;
+                s.end()
+            }
+            Self::LodestoneTracker(tracker) => {
+                let mut s = serializer.serialize_seq(None)?;
+                s.serialize_element(&VarInt(LODESTONE_TRACKER))?;
+                s.serialize_element(&tracker.target.is_some())?;
+                if let Some(target) = &tracker.target {
+                    s.serialize_element(&target.dimension)?;
+                    s.serialize_element(&target.pos)?;
+                }
+                s.serialize_element(&tracker.tracked)?;
+                s.end()
+            }
+            Self::CanPlaceOn(predicate) | Self::CanBreak(predicate) => {
+                let id = if matches!(self, Self::CanPlaceOn(_)) {
+                    CAN_PLACE_ON
+                } else {
+                    CAN_BREAK
+                };
+                let mut s = serializer.serialize_seq(None)?;
+                s.serialize_element(&VarInt(id))?;
+                s.serialize_element(&VarInt(predicate.blocks.len() as i32))?;
+                for set in &predicate.blocks {
+                    // The blocks are optional, then come the optional state and NBT predicates
+                    s.serialize_element(&true)?;
+                    match set {
+                        // Tags are sent as 0, direct blocks as their count plus 1
+                        BlockSet::Tag(tag) => {
+                            s.serialize_element(&VarInt(0))?;
+                            s.serialize_element(tag)?;
+                        }
+                        BlockSet::Blocks(ids) => {
+                            s.serialize_element(&VarInt(ids.len() as i32 + 1))?;
+                            for id in ids {
+                                s.serialize_element(&VarInt(i32::from(*id)))?;
+                            }
+                        }
+                    }
+                    s.serialize_element(&false)?;
+                    s.serialize_element(&false)?;
+                }
+                s.serialize_element(&predicate.show_in_tooltip)?;
+                s.end()
+            }
+            Self::BucketEntityData(data) => {
+                let mut s = serializer.serialize_seq(None)?;
+                s.serialize_element(&VarInt(BUCKET_ENTITY_DATA))?;
+                let nbt = Nbt::new(String::new(), data.clone()).write_unnamed();
+                s.serialize_element(nbt.as_ref())?;
+                s.end()
+            }
+        }
+    }
+}
+
+fn next<'de, T: Deserialize<'de>, A: SeqAccess<'de>>(seq: &mut A) -> Result<T, A::Error> {
+    seq.next_element::<T>()?
+        .ok_or(de::Error::custom("Unexpected end of slot data"))
+}
+
+/// Reads the slots of a component holding items, one level deeper than the slot holding them
+fn read_slots<'de, A: SeqAccess<'de>>(
+    seq: &mut A,
+    max_len: i32,
+    depth: u8,
+) -> Result<Vec<Slot>, A::Error> {
+    if depth >= MAX_NESTING {
+        return Err(de::Error::custom("Slot components nested too deep"));
+    }
+    let len = next::<VarInt, A>(seq)?.0;
+    if !(0..=max_len).contains(&len) {
+        return Err(de::Error::custom("Too many slots in slot component"));
+    }
+    let mut slots = Vec::new();
+    for _ in 0..len {
+        let slot = seq
+            .next_element_seed(SlotSeed { depth: depth + 1 })?
+            .ok_or(de::Error::custom("Unexpected end of slot data"))?;
+        slots.push(slot);
+    }
+    Ok(slots)
+}
+
+/// Reads the block predicates of `can_place_on` or `can_break`
+fn read_adventure_predicate<'de, A: SeqAccess<'de>>(
+    seq: &mut A,
+) -> Result<AdventurePredicate, A::Error> {
+    let len = next::<VarInt, A>(seq)?.0;
+    if !(0..=MAX_BLOCK_PREDICATES).contains(&len) {
+        return Err(de::Error::custom("Too many block predicates"));
+    }
+    let mut blocks = Vec::new();
+    for _ in 0..len {
+        if !next::<bool, A>(seq)? {
+            return Err(de::Error::custom(
+                "Block predicates without blocks are currently unsupported",
+            ));
+        }
+        let set = match next::<VarInt, A>(seq)?.0 {
+            0 => BlockSet::Tag(next::<String, A>(seq)?),
+            count if (1..=MAX_BLOCK_PREDICATES).contains(&count) => {
+                let mut ids = Vec::new();
+                for _ in 1..count {
+                    let id = next::<VarInt, A>(seq)?;
+                    ids.push(
+                        u16::try_from(id.0).map_err(|_| de::Error::custom("Invalid block id"))?,
+                    );
+                }
+                BlockSet::Blocks(ids)
+            }
+            _ => return Err(de::Error::custom("Too many blocks in block predicate")),
+        };
+        if next::<bool, A>(seq)? || next::<bool, A>(seq)? {
+            return Err(de::Error::custom(
+                "Block state and NBT predicates are currently unsupported",
+            ));
+        }
+        blocks.push(set);
+    }
+    let show_in_tooltip = next::<bool, A>(seq)?;
+    Ok(AdventurePredicate {
+        blocks,
+        show_in_tooltip,
+    })
+}
+
+impl SlotComponent {
+    fn read<'de, A: SeqAccess<'de>>(id: VarInt, seq: &mut A, depth: u8) -> Result<Self, A::Error> {
+        match id.0 {
+            BUNDLE_CONTENTS => Ok(Self::BundleContents(read_slots(
+                seq,
+                MAX_BUNDLE_STACKS,
+                depth,
+            )?)),
+            CONTAINER => Ok(Self::Container(read_slots(
+                seq,
+                MAX_CONTAINER_SLOTS,
+                depth,
+            )?)),
+            POTION_CONTENTS => {
+                let potion = if next::<bool, A>(seq)? {
+                    let id = next::<VarInt, A>(seq)?;
+                    Some(
+                        u16::try_from(id.0)
+                            .ok()
+                            .and_then(Potion::from_id)
+                            .ok_or(de::Error::custom("Invalid potion id"))?,
+                    )
+                } else {
+                    None
+                };
+                if next::<bool, A>(seq)? {
+                    next::<i32, A>(seq)?;
+                }
+                if next::<VarInt, A>(seq)?.0 != 0 {
+                    return Err(de::Error::custom(
+                        "Custom potion effects are currently unsupported",
+                    ));
+                }
+                if next::<bool, A>(seq)? {
+                    next::<String, A>(seq)?;
+                }
+                // Potions without a base potion only make sense with custom effects
+                potion
+                    .map(Self::PotionContents)
+                    .ok_or(de::Error::custom("Potion contents without a potion"))
+            }
+            CAN_PLACE_ON => Ok(Self::CanPlaceOn(read_adventure_predicate(seq)?)),
+            CAN_BREAK => Ok(Self::CanBreak(read_adventure_predicate(seq)?)),
+            LODESTONE_TRACKER => {
+                let target = if next::<bool, A>(seq)? {
+                    let dimension = next::<String, A>(seq)?;
+                    let pos = next::<BlockPos, A>(seq)?;
+                    Some(GlobalPos::new(dimension, pos))
+                } else {
+                    None
+                };
+                let tracked = next::<bool, A>(seq)?;
+                Ok(Self::LodestoneTracker(LodestoneTracker { target, tracked }))
+            }
+            _ => Err(de::Error::custom("Slot component is currently unsupported")),
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for Slot {
@@ -22,7 +276,25 @@ impl<'de> Deserialize<'de> for Slot {
     where
         D: de::Deserializer<'de>,
     {
-        struct Visitor;
+        SlotSeed { depth: 0 }.deserialize(deserializer)
+    }
+}
+
+/// Reads a slot knowing how deep it is nested inside of the items of other slots
+struct SlotSeed {
+    depth: u8,
+}
+
+impl<'de> DeserializeSeed<'de> for SlotSeed {
+    type Value = Slot;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor {
+            depth: u8,
+        }
         impl<'de> de::Visitor<'de> for Visitor {
             type Value = Slot;
 
@@ -38,14 +310,7 @@ impl<'de> Deserialize<'de> for Slot {
                     .next_element::<VarInt>()?
                     .ok_or(de::Error::custom("Failed to decode VarInt"))?;
                 if item_count.0 == 0 {
-                    return Ok(Slot {
-                        item_count: 0.into(),
-                        item_id: None,
-                        num_components_to_add: None,
-                        num_components_to_remove: None,
-                        components_to_add: None,
-                        components_to_remove: None,
-                    });
+                    return Ok(Slot::empty());
                 }
                 let item_id = seq
                     .next_element::<VarInt>()?
@@ -56,24 +321,27 @@ impl<'de> Deserialize<'de> for Slot {
                 let num_components_to_remove = seq
                     .next_element::<VarInt>()?
                     .ok_or(de::Error::custom("Failed to decode VarInt"))?;
-                if num_components_to_add.0 != 0 || num_components_to_remove.0 != 0 {
-                    return Err(de::Error::custom(
-                        "Slot components are currently unsupported",
-                    ));
+
+                let mut components_to_add = Vec::new();
+                for _ in 0..num_components_to_add.0 {
+                    let id = next::<VarInt, A>(&mut seq)?;
+                    components_to_add.push(SlotComponent::read(id, &mut seq, self.depth)?);
+                }
+                let mut components_to_remove = Vec::new();
+                for _ in 0..num_components_to_remove.0 {
+                    components_to_remove.push(next::<VarInt, A>(&mut seq)?);
                 }
 
                 Ok(Slot {
                     item_count,
                     item_id: Some(item_id),
-                    num_components_to_add: Some(num_components_to_add),
-                    num_components_to_remove: Some(num_components_to_remove),
-                    components_to_add: None,
-                    components_to_remove: None,
+                    components_to_add,
+                    components_to_remove,
                 })
             }
         }
 
-        deserializer.deserialize_seq(Visitor)
+        deserializer.deserialize_seq(Visitor { depth: self.depth })
     }
 }
 
@@ -82,50 +350,21 @@ impl Serialize for Slot {
     where
         S: Serializer,
     {
+        let mut s = serializer.serialize_seq(None)?;
+        s.serialize_element(&self.item_count)?;
         if self.item_count == 0.into() {
-            let mut s = serializer.serialize_seq(Some(1))?;
-            s.serialize_element(&self.item_count)?;
-            s.end()
-        } else {
-            match (&self.num_components_to_add, &self.num_components_to_remove) {
-                (Some(to_add), Some(to_remove)) => {
-                    let mut s = serializer.serialize_seq(Some(6))?;
-                    s.serialize_element(&self.item_count)?;
-                    s.serialize_element(self.item_id.as_ref().unwrap())?;
-                    s.serialize_element(to_add)?;
-                    s.serialize_element(to_remove)?;
-                    s.serialize_element(self.components_to_add.as_ref().unwrap())?;
-                    s.serialize_element(self.components_to_remove.as_ref().unwrap())?;
-                    s.end()
-                }
-                (None, Some(to_remove)) => {
-                    let mut s = serializer.serialize_seq(Some(5))?;
-                    s.serialize_element(&self.item_count)?;
-                    s.serialize_element(self.item_id.as_ref().unwrap())?;
-                    s.serialize_element(&VarInt(0))?;
-                    s.serialize_element(to_remove)?;
-                    s.serialize_element(self.components_to_remove.as_ref().unwrap())?;
-                    s.end()
-                }
-                (Some(to_add), None) => {
-                    let mut s = serializer.serialize_seq(Some(5))?;
-                    s.serialize_element(&self.item_count)?;
-                    s.serialize_element(self.item_id.as_ref().unwrap())?;
-                    s.serialize_element(to_add)?;
-                    s.serialize_element(&VarInt(0))?;
-                    s.serialize_element(self.components_to_add.as_ref().unwrap())?;
-                    s.end()
-                }
-                (None, None) => {
-                    let mut s = serializer.serialize_seq(Some(4))?;
-                    s.serialize_element(&self.item_count)?;
-                    s.serialize_element(&self.item_id.as_ref().unwrap())?;
-                    s.serialize_element(&VarInt(0))?;
-                    s.serialize_element(&VarInt(0))?;
-                    s.end()
-                }
-            }
+            return s.end();
+        }
+        s.serialize_element(self.item_id.as_ref().unwrap())?;
+        s.serialize_element(&VarInt(self.components_to_add.len() as i32))?;
+        s.serialize_element(&VarInt(self.components_to_remove.len() as i32))?;
+        for component in &self.components_to_add {
+            s.serialize_element(component)?;
         }
+        for component in &self.components_to_remove {
+            s.serialize_element(component)?;
+        }
+        s.end()
     }
 }
 
@@ -134,11 +373,8 @@ impl Slot {
         Slot {
             item_count: count.into(),
             item_id: Some((item_id as i32).into()),
-            // TODO: add these
-            num_components_to_add: None,
-            num_components_to_remove: None,
-            components_to_add: None,
-            components_to_remove: None,
+            components_to_add: Vec::new(),
+            components_to_remove: Vec::new(),
         }
     }
 
@@ -152,14 +388,46 @@ impl Slot {
         if self.item_count.0 > item.components.max_stack_size as i32 {
             Err("Over sized stack")
         } else {
-            let stack = ItemStack {
-                item,
-                item_count: self
-                    .item_count
+            let mut stack = ItemStack::new(
+                self.item_count
                     .0
                     .try_into()
                     .map_err(|_| "Stack count too large")?,
-            };
+                item,
+            );
+            for component in self.components_to_add {
+                match component {
+                    SlotComponent::PotionContents(potion) => {
+                        stack.components.potion = Some(potion);
+                    }
+                    SlotComponent::Container(slots) => {
+                        let contents = slots
+                            .into_iter()
+                            .map(Slot::to_stack)
+                            .collect::<Result<_, _>>()?;
+                        stack.components.container = Some(contents);
+                    }
+                    SlotComponent::BundleContents(slots) => {
+                        let contents = slots
+                            .into_iter()
+                            .map(|slot| slot.to_stack()?.ok_or("Empty stack in bundle"))
+                            .collect::<Result<_, _>>()?;
+                        stack.components.bundle_contents = Some(contents);
+                    }
+                    SlotComponent::LodestoneTracker(tracker) => {
+                        stack.components.lodestone_tracker = Some(tracker);
+                    }
+                    SlotComponent::BucketEntityData(data) => {
+                        stack.components.bucket_entity_data = Some(data);
+                    }
+                    SlotComponent::CanPlaceOn(predicate) => {
+                        stack.components.can_place_on = Some(predicate);
+                    }
+                    SlotComponent::CanBreak(predicate) => {
+                        stack.components.can_break = Some(predicate);
+                    }
+                }
+            }
             Ok(Some(stack))
         }
     }
@@ -168,17 +436,53 @@ impl Slot {
         Slot {
             item_count: VarInt(0),
             item_id: None,
-            num_components_to_add: None,
-            num_components_to_remove: None,
-            components_to_add: None,
-            components_to_remove: None,
+            components_to_add: Vec::new(),
+            components_to_remove: Vec::new(),
         }
     }
 }
 
 impl From<&ItemStack> for Slot {
     fn from(item: &ItemStack) -> Self {
-        Slot::new(item.item.id, item.item_count as u32)
+        let mut slot = Slot::new(item.item.id, item.item_count as u32);
+        if let Some(potion) = item.components.potion {
+            slot.components_to_add
+                .push(SlotComponent::PotionContents(potion));
+        }
+        if let Some(contents) = &item.components.container {
+            // Empty slots at the end are left out like the client does
+            let len = contents
+                .iter()
+                .rposition(Option::is_some)
+                .map_or(0, |last| last + 1);
+            let slots = contents[..len]
+                .iter()
+                .map(|stack| Slot::from(stack.as_ref()))
+                .collect();
+            slot.components_to_add.push(SlotComponent::Container(slots));
+        }
+        if let Some(contents) = &item.components.bundle_contents {
+            let slots = contents.iter().map(Slot::from).collect();
+            slot.components_to_add
+                .push(SlotComponent::BundleContents(slots));
+        }
+        if let Some(tracker) = &item.components.lodestone_tracker {
+            slot.components_to_add
+                .push(SlotComponent::LodestoneTracker(tracker.clone()));
+        }
+        if let Some(predicate) = &item.components.can_place_on {
+            slot.components_to_add
+                .push(SlotComponent::CanPlaceOn(predicate.clone()));
+        }
+        if let Some(predicate) = &item.components.can_break {
+            slot.components_to_add
+                .push(SlotComponent::CanBreak(predicate.clone()));
+        }
+        if let Some(data) = &item.components.bucket_entity_data {
+            slot.components_to_add
+                .push(SlotComponent::BucketEntityData(data.clone()));
+        }
+        slot
     }
 }
 
@@ -29,7 +29,8 @@ impl Codec<Self> for VarInt {
     }
 
     fn encode(&self, write: &mut impl BufMut) {
-        let mut val = self.0;
+        // Shifting the unsigned value, negative values would never reach 0
+        let mut val = self.0 as u32;
         for _ in 0..Self::MAX_SIZE.get() {
             let b: u8 = val as u8 & 0b01111111;
             val >>= 7;
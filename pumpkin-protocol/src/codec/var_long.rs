@@ -24,12 +24,13 @@ impl Codec<Self> for VarLong {
     fn written_size(&self) -> usize {
         match self.0 {
             0 => 1,
-            n => (31 - n.leading_zeros() as usize) / 7 + 1,
+            n => (63 - n.leading_zeros() as usize) / 7 + 1,
         }
     }
 
     fn encode(&self, write: &mut impl BufMut) {
-        let mut x = self.0;
+        // Shifting the unsigned value, negative values would never reach 0
+        let mut x = self.0 as u64;
         for _ in 0..Self::MAX_SIZE.get() {
             let byte = (x & 0x7F) as u8;
             x >>= 7;
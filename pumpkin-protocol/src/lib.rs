@@ -6,6 +6,7 @@ use codec::{identifier::Identifier, var_int::VarInt};
 use pumpkin_util::text::{TextComponent, style::Style};
 use serde::{Deserialize, Serialize, Serializer};
 
+pub mod buffer_pool;
 pub mod bytebuf;
 #[cfg(feature = "clientbound")]
 pub mod client;
@@ -16,6 +17,8 @@ pub mod packet_encoder;
 pub mod query;
 #[cfg(feature = "serverbound")]
 pub mod server;
+#[cfg(all(test, feature = "packets"))]
+mod tests;
 
 /// To current Minecraft protocol
 /// Don't forget to change this when porting
@@ -112,7 +115,7 @@ pub trait ServerPacket: Packet + Sized {
     fn read(bytebuf: &mut impl Buf) -> Result<Self, ReadingError>;
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct StatusResponse {
     /// The version on which the Server is running. Optional
     pub version: Option<Version>,
@@ -125,7 +128,7 @@ pub struct StatusResponse {
     /// Players are forced to use Secure chat
     pub enforce_secure_chat: bool,
 }
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct Version {
     /// The current name of the Version (e.g. 1.21.4)
     pub name: String,
@@ -133,7 +136,7 @@ pub struct Version {
     pub protocol: u32,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct Players {
     /// The maximum Player count the server allows
     pub max: u32,
@@ -144,7 +147,7 @@ pub struct Players {
     pub sample: Vec<Sample>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct Sample {
     /// Players Name
     pub name: String,
@@ -168,7 +171,7 @@ pub struct KnownPack<'a> {
     pub version: &'a str,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub enum NumberFormat {
     /// Show nothing
     Blank,
@@ -211,6 +214,7 @@ impl PositionFlag {
     }
 }
 
+#[derive(Clone)]
 pub enum Label {
     BuiltIn(LinkType),
     TextComponent(Box<TextComponent>),
@@ -248,6 +252,7 @@ impl<'a> Link<'a> {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum LinkType {
     BugReport,
     CommunityGuidelines,
@@ -1,11 +1,14 @@
+use std::sync::{Arc, OnceLock};
+
 use aes::cipher::{BlockEncryptMut, BlockSizeUser, KeyIvInit, generic_array::GenericArray};
-use bytes::{BufMut, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use thiserror::Error;
 
 use libdeflater::{CompressionLvl, Compressor};
 
 use crate::{
-    ClientPacket, CompressionLevel, CompressionThreshold, MAX_PACKET_SIZE, VarInt, codec::Codec,
+    ClientPacket, CompressionLevel, CompressionThreshold, MAX_PACKET_SIZE, VarInt,
+    buffer_pool::BUFFER_POOL, codec::Codec,
 };
 
 type Cipher = cfb8::Encryptor<aes::Aes128>;
@@ -16,12 +19,37 @@ type Cipher = cfb8::Encryptor<aes::Aes128>;
 #[derive(Default)]
 pub struct PacketEncoder {
     buf: BytesMut,
+    /// Parts of the output which are done, frames of shared packets are kept here as they are
+    /// instead of being copied into `buf`
+    queued: Vec<Bytes>,
     compress_buf: Vec<u8>,
     cipher: Option<Cipher>,
     // compression and compression threshold
     compression: Option<(Compressor, CompressionThreshold)>,
 }
 
+/// A clientbound packet serialized once so it can be sent to many connections, like when
+/// broadcasting. The first encoder appending it builds the frame, which every encoder with the
+/// same compression threshold then copies as is. Only the encryption is done per connection.
+pub struct EncodedPacket {
+    /// The packet id followed by the data
+    payload: Arc<[u8]>,
+    /// The frame and the threshold it was compressed with, `None` without compression
+    frame: OnceLock<(Option<CompressionThreshold>, Bytes)>,
+}
+
+impl EncodedPacket {
+    pub fn new<P: ClientPacket>(packet: &P) -> Self {
+        let mut buf = BytesMut::new();
+        VarInt(P::PACKET_ID).encode(&mut buf);
+        packet.write(&mut buf);
+        Self {
+            payload: Arc::from(&buf[..]),
+            frame: OnceLock::new(),
+        }
+    }
+}
+
 impl PacketEncoder {
     /// Appends a Clientbound `ClientPacket` to the internal buffer and applies compression when needed.
     ///
@@ -59,6 +87,39 @@ impl PacketEncoder {
         VarInt(P::PACKET_ID).encode(&mut self.buf);
         // Now write the packet into an empty buffer
         packet.write(&mut self.buf);
+        self.frame(start_len)
+    }
+
+    /// Appends a packet shared with other connections, see `EncodedPacket`. Compression only
+    /// happens if no encoder with the same threshold appended the packet before
+    pub fn append_encoded(&mut self, packet: &EncodedPacket) -> Result<(), PacketEncodeError> {
+        let threshold = self.compression.as_ref().map(|(_, threshold)| *threshold);
+        if let Some((frame_threshold, frame)) = packet.frame.get() {
+            if *frame_threshold == threshold {
+                if self.cipher.is_some() {
+                    // Encryption has to happen on a copy of this connection
+                    self.buf.extend_from_slice(frame);
+                } else {
+                    self.queue_buf();
+                    self.queued.push(frame.clone());
+                }
+                return Ok(());
+            }
+        }
+
+        let start_len = self.buf.len();
+        self.buf.extend_from_slice(&packet.payload);
+        self.frame(start_len)?;
+        // Another encoder may have been faster, its frame is just as good
+        let _ = packet
+            .frame
+            .set((threshold, Bytes::copy_from_slice(&self.buf[start_len..])));
+        Ok(())
+    }
+
+    /// Puts the length prefix in front of the packet starting at `start_len` and compresses it
+    /// when needed
+    fn frame(&mut self, start_len: usize) -> Result<(), PacketEncodeError> {
         let data_len = self.buf.len() - start_len;
 
         if let Some((compressor, compression_threshold)) = &mut self.compression {
@@ -178,7 +239,17 @@ impl PacketEncoder {
         Ok(())
     }
 
-    /// Encrypts the data in the internal buffer and returns it as a `BytesMut`.
+    /// Moves what was written into `buf` so far to the queued parts, continuing in a buffer from
+    /// the pool
+    fn queue_buf(&mut self) {
+        if !self.buf.is_empty() {
+            let buf = std::mem::replace(&mut self.buf, BUFFER_POOL.take());
+            self.queued.push(buf.freeze());
+        }
+    }
+
+    /// Encrypts the data in the internal buffer and returns everything appended so far as parts
+    /// to write one after another, e.g. with a single vectored write.
     ///
     /// If a cipher is set, the data is encrypted in-place using block cipher encryption.
     /// The buffer is processed in chunks of the cipher's block size. If the buffer's
@@ -186,8 +257,8 @@ impl PacketEncoder {
     /// It's important to ensure that the data being encrypted is padded appropriately
     /// beforehand if necessary.
     ///
-    /// If no cipher is set, the buffer is returned as is.
-    pub fn take(&mut self) -> BytesMut {
+    /// Give the parts to `BUFFER_POOL` once they are written, so their buffers are reused.
+    pub fn take_parts(&mut self) -> Vec<Bytes> {
         if let Some(cipher) = &mut self.cipher {
             for chunk in self.buf.chunks_mut(Cipher::block_size()) {
                 let gen_arr = GenericArray::from_mut_slice(chunk);
@@ -195,7 +266,19 @@ impl PacketEncoder {
             }
         }
 
-        self.buf.split()
+        self.queue_buf();
+        std::mem::take(&mut self.queued)
+    }
+
+    /// Like `take_parts`, but returns everything in one buffer
+    pub fn take(&mut self) -> BytesMut {
+        let parts = self.take_parts();
+        let mut buf = BytesMut::with_capacity(parts.iter().map(Bytes::len).sum());
+        for part in &parts {
+            buf.extend_from_slice(part);
+        }
+        BUFFER_POOL.recycle(parts);
+        buf
     }
 }
 
@@ -594,4 +677,61 @@ mod tests {
 
         assert_eq!(buffer, expected_payload);
     }
+
+    /// Shared packets come out the same as packets encoded for each connection
+    #[test]
+    fn test_encode_shared() {
+        let description = "A Minecraft Server ".repeat(20);
+        let packet = CStatusResponse::new(&description);
+        let shared = EncodedPacket::new(&packet);
+        let key = [0x42; 16];
+
+        for (compression, key) in [
+            (
+                Some((CompressionThreshold(64), CompressionLevel(6))),
+                Some(&key),
+            ),
+            (Some((CompressionThreshold(64), CompressionLevel(6))), None),
+            (None, Some(&key)),
+            (
+                Some((CompressionThreshold(1024), CompressionLevel(6))),
+                None,
+            ),
+        ] {
+            let mut encoder = PacketEncoder::default();
+            encoder.set_compression(compression).unwrap();
+            if let Some(key) = key {
+                encoder.set_encryption(Some(key));
+            }
+            encoder.append_encoded(&shared).unwrap();
+
+            assert_eq!(
+                encoder.take(),
+                build_packet_with_encoder(&packet, compression, key)
+            );
+        }
+        // Only the first encoder built the frame
+        assert_eq!(
+            shared.frame.get().unwrap().0,
+            Some(CompressionThreshold(64))
+        );
+    }
+
+    #[test]
+    fn test_shared_frames_are_not_copied() {
+        let packet = CStatusResponse::new("A Minecraft Server");
+        let shared = EncodedPacket::new(&packet);
+        PacketEncoder::default().append_encoded(&shared).unwrap();
+
+        let mut encoder = PacketEncoder::default();
+        encoder.append_packet(&packet).unwrap();
+        encoder.append_encoded(&shared).unwrap();
+        encoder.append_packet(&packet).unwrap();
+        let parts = encoder.take_parts();
+
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[1].as_ptr(), shared.frame.get().unwrap().1.as_ptr());
+        assert!(parts.iter().all(|part| part[..] == parts[0][..]));
+        assert!(encoder.take_parts().is_empty());
+    }
 }
@@ -4,7 +4,7 @@ use serde::Serialize;
 
 use crate::VarInt;
 
-#[derive(serde::Deserialize, Serialize)]
+#[derive(serde::Deserialize, Serialize, Debug, PartialEq)]
 #[packet(CONFIG_SELECT_KNOWN_PACKS)]
 pub struct SKnownPacks {
     pub known_pack_count: VarInt,
@@ -1,6 +1,7 @@
 mod acknowledge_finish_config;
 mod client_information;
 mod cookie_response;
+mod keep_alive;
 mod known_packs;
 mod plugin_message;
 mod resource_pack_response;
@@ -8,6 +9,7 @@ mod resource_pack_response;
 pub use acknowledge_finish_config::*;
 pub use client_information::*;
 pub use cookie_response::*;
+pub use keep_alive::*;
 pub use known_packs::*;
 pub use plugin_message::*;
 pub use resource_pack_response::*;
@@ -7,6 +7,7 @@ use bytes::Buf;
 use pumpkin_data::packet::serverbound::HANDSHAKE_INTENTION;
 use pumpkin_macros::packet;
 
+#[derive(Debug, PartialEq)]
 #[packet(HANDSHAKE_INTENTION)]
 pub struct SHandShake {
     pub protocol_version: VarInt,
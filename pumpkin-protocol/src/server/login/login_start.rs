@@ -8,6 +8,7 @@ use crate::{
     bytebuf::{ByteBuf, ReadingError},
 };
 
+#[derive(Debug, PartialEq)]
 #[packet(LOGIN_HELLO)]
 pub struct SLoginStart {
     pub name: String, // 16
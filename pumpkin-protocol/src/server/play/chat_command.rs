@@ -2,7 +2,7 @@ use pumpkin_data::packet::serverbound::PLAY_CHAT_COMMAND;
 use pumpkin_macros::packet;
 use serde::Serialize;
 
-#[derive(serde::Deserialize, Serialize)]
+#[derive(serde::Deserialize, Serialize, Debug, PartialEq)]
 #[packet(PLAY_CHAT_COMMAND)]
 pub struct SChatCommand {
     pub command: String,
@@ -1,14 +1,13 @@
-use bytes::Buf;
+use bytes::{Buf, BufMut};
 use pumpkin_data::packet::serverbound::PLAY_CHAT;
 use pumpkin_macros::packet;
-use serde::Serialize;
 
 use crate::{
-    ServerPacket, VarInt,
-    bytebuf::{ByteBuf, ReadingError},
+    ClientPacket, ServerPacket, VarInt,
+    bytebuf::{ByteBuf, ByteBufMut, ReadingError},
 };
 
-#[derive(Serialize)]
+#[derive(Debug, PartialEq)]
 #[packet(PLAY_CHAT)]
 pub struct SChatMessage {
     pub message: String,
@@ -39,6 +38,17 @@ impl SChatMessage {
     }
 }
 
+impl ClientPacket for SChatMessage {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_string_len(&self.message, 256);
+        bytebuf.put_i64(self.timestamp);
+        bytebuf.put_i64(self.salt);
+        bytebuf.put_option(&self.signature, |p, v| p.put_slice(v));
+        bytebuf.put_var_int(&self.message_count);
+        bytebuf.put_slice(&self.acknowledged);
+    }
+}
+
 // TODO
 impl ServerPacket for SChatMessage {
     fn read(bytebuf: &mut impl Buf) -> Result<Self, ReadingError> {
@@ -1,8 +1,8 @@
 use pumpkin_data::packet::serverbound::PLAY_CHUNK_BATCH_RECEIVED;
 use pumpkin_macros::packet;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[packet(PLAY_CHUNK_BATCH_RECEIVED)]
 pub struct SChunkBatch {
     pub chunks_per_tick: f32,
@@ -4,7 +4,7 @@ use serde::Serialize;
 
 use crate::VarInt;
 
-#[derive(serde::Deserialize, Serialize)]
+#[derive(serde::Deserialize, Serialize, Debug, PartialEq)]
 #[packet(PLAY_CLIENT_INFORMATION)]
 pub struct SClientInformationPlay {
     pub locale: String, // 16
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::VarInt;
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[packet(PLAY_CONTAINER_CLOSE)]
 pub struct SCloseContainer {
     pub window_id: VarInt,
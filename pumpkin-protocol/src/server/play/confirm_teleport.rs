@@ -4,7 +4,7 @@ use serde::Serialize;
 
 use crate::VarInt;
 
-#[derive(serde::Deserialize, Serialize)]
+#[derive(serde::Deserialize, Serialize, Debug, PartialEq)]
 #[packet(PLAY_ACCEPT_TELEPORTATION)]
 pub struct SConfirmTeleport {
     pub teleport_id: VarInt,
@@ -2,7 +2,7 @@ use pumpkin_data::packet::serverbound::PLAY_MOVE_PLAYER_STATUS_ONLY;
 use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[packet(PLAY_MOVE_PLAYER_STATUS_ONLY)]
 pub struct SSetPlayerGround {
     pub on_ground: bool,
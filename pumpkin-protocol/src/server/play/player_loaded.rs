@@ -1,5 +1,7 @@
 use pumpkin_data::packet::serverbound::PLAY_PLAYER_LOADED;
 use pumpkin_macros::packet;
+use serde::Serialize;
 
+#[derive(Serialize)]
 #[packet(PLAY_PLAYER_LOADED)]
 pub struct SPlayerLoaded;
@@ -2,7 +2,7 @@ use pumpkin_data::packet::serverbound::PLAY_SET_CARRIED_ITEM;
 use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[packet(PLAY_SET_CARRIED_ITEM)]
 pub struct SSetHeldItem {
     pub slot: i16,
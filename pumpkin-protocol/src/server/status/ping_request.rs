@@ -2,7 +2,7 @@ use pumpkin_data::packet::serverbound::STATUS_PING_REQUEST;
 use pumpkin_macros::packet;
 use serde::Serialize;
 
-#[derive(serde::Deserialize, Serialize)]
+#[derive(serde::Deserialize, Serialize, Debug, PartialEq)]
 #[packet(STATUS_PING_REQUEST)]
 pub struct SStatusPingRequest {
     pub payload: i64,
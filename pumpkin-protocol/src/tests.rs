@@ -0,0 +1,278 @@
+//! Round trips for every packet that can be both written and read, and golden bytes for a few
+//! packets, so changing how a packet is encoded doesn't go unnoticed.
+
+use std::fmt::Debug;
+
+use bytes::{Bytes, BytesMut};
+use proptest::prelude::*;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use uuid::Uuid;
+
+use crate::{
+    ClientPacket, CompressionLevel, CompressionThreshold, ConnectionState, ServerPacket,
+    bytebuf::packet::Packet,
+    client::{
+        login::CSetCompression,
+        play::{
+            CAcknowledgeBlockChange, CEntityStatus, CGameEvent, CHeadRot, CHurtAnimation,
+            CKeepAlive, CLevelEvent, CPingResponse, GameEvent,
+        },
+        status,
+    },
+    codec::var_int::VarInt,
+    packet_decoder::PacketDecoder,
+    packet_encoder::PacketEncoder,
+    server::{
+        config::{SClientInformationConfig, SKnownPacks},
+        handshake::SHandShake,
+        login::SLoginStart,
+        play::{
+            SChatCommand, SChatMessage, SChunkBatch, SClientInformationPlay, SCloseContainer,
+            SConfirmTeleport, SKeepAlive, SPlayPingRequest, SPlayerPosition, SSetHeldItem,
+            SSetPlayerGround, SSwingArm,
+        },
+        status::SStatusPingRequest,
+    },
+};
+
+fn write<P: ClientPacket>(packet: &P) -> Bytes {
+    let mut buf = BytesMut::new();
+    packet.write(&mut buf);
+    buf.freeze()
+}
+
+/// Writes the packet, reads it back and checks nothing was lost or left over
+fn round_trip<P: ClientPacket + ServerPacket + PartialEq + Debug>(
+    packet: &P,
+) -> Result<(), TestCaseError> {
+    let mut bytes = write(packet);
+    let read = P::read(&mut bytes).map_err(|err| TestCaseError::fail(err.to_string()))?;
+    prop_assert_eq!(&read, packet);
+    prop_assert!(bytes.is_empty(), "{} bytes were not read", bytes.len());
+    Ok(())
+}
+
+fn var_int() -> impl Strategy<Value = VarInt> {
+    any::<i32>().prop_map(VarInt)
+}
+
+/// Floats without NaN, which never equals itself
+fn float() -> impl Strategy<Value = f32> {
+    prop::num::f32::NORMAL | prop::num::f32::ZERO | prop::num::f32::INFINITE
+}
+
+proptest! {
+    #[test]
+    fn handshake(
+        protocol_version in var_int(),
+        server_address in "[a-z0-9.-]{0,255}",
+        server_port: u16,
+        next_state in prop_oneof![
+            Just(ConnectionState::Status),
+            Just(ConnectionState::Login),
+            Just(ConnectionState::Transfer),
+        ],
+    ) {
+        round_trip(&SHandShake { protocol_version, server_address, server_port, next_state })?;
+    }
+
+    #[test]
+    fn login_start(name in "[A-Za-z0-9_]{1,16}", uuid: u128) {
+        round_trip(&SLoginStart { name, uuid: Uuid::from_u128(uuid) })?;
+    }
+
+    #[test]
+    fn client_information(
+        locale in "[a-z_]{0,16}",
+        view_distance: i8,
+        chat_mode in var_int(),
+        chat_colors: bool,
+        skin_parts: u8,
+        main_hand in var_int(),
+        text_filtering: bool,
+        server_listing: bool,
+    ) {
+        round_trip(&SClientInformationConfig {
+            locale: locale.clone(),
+            view_distance,
+            chat_mode,
+            chat_colors,
+            skin_parts,
+            main_hand,
+            text_filtering,
+            server_listing,
+        })?;
+        round_trip(&SClientInformationPlay {
+            locale,
+            view_distance,
+            chat_mode,
+            chat_colors,
+            skin_parts,
+            main_hand,
+            text_filtering,
+            server_listing,
+        })?;
+    }
+
+    #[test]
+    fn chat(
+        message in "[ -~]{0,256}",
+        command in "[ -~]{0,256}",
+        timestamp: i64,
+        salt: i64,
+        signature in prop::option::of(prop::collection::vec(any::<u8>(), 256)),
+        message_count in var_int(),
+        acknowledged in prop::collection::vec(any::<u8>(), 3),
+    ) {
+        round_trip(&SChatMessage::new(
+            message,
+            timestamp,
+            salt,
+            signature,
+            message_count,
+            acknowledged,
+        ))?;
+        round_trip(&SChatCommand { command })?;
+    }
+
+    #[test]
+    fn serverbound_play(
+        int in var_int(),
+        long: i64,
+        slot: i16,
+        on_ground: bool,
+        chunks_per_tick in float(),
+    ) {
+        round_trip(&SKnownPacks { known_pack_count: int })?;
+        round_trip(&SConfirmTeleport { teleport_id: int })?;
+        round_trip(&SCloseContainer { window_id: int })?;
+        round_trip(&SSwingArm { hand: int })?;
+        round_trip(&SKeepAlive { keep_alive_id: long })?;
+        round_trip(&SPlayPingRequest { payload: long })?;
+        round_trip(&SStatusPingRequest { payload: long })?;
+        round_trip(&SSetHeldItem { slot })?;
+        round_trip(&SSetPlayerGround { on_ground })?;
+        round_trip(&SChunkBatch { chunks_per_tick })?;
+    }
+
+    #[test]
+    fn clientbound(
+        int in var_int(),
+        entity_id: i32,
+        entity_status: i8,
+        long: i64,
+        head_yaw: u8,
+        value in float(),
+    ) {
+        round_trip(&CEntityStatus::new(entity_id, entity_status))?;
+        round_trip(&CAcknowledgeBlockChange::new(int))?;
+        round_trip(&CHurtAnimation::new(int, value))?;
+        round_trip(&CGameEvent::new(GameEvent::ChangeGameMode, value))?;
+        round_trip(&CHeadRot::new(int, head_yaw))?;
+        round_trip(&CKeepAlive::new(long))?;
+        round_trip(&CPingResponse::new(long))?;
+        round_trip(&status::CPingResponse::new(long))?;
+        round_trip(&CSetCompression::new(int))?;
+    }
+
+    /// The whole way through the codec, with packets both above and below the threshold
+    #[test]
+    fn compressed_frames(commands in prop::collection::vec("[ -~]{0,600}", 1..8)) {
+        let mut encoder = PacketEncoder::default();
+        encoder
+            .set_compression(Some((CompressionThreshold(256), CompressionLevel(4))))
+            .unwrap();
+        let mut decoder = PacketDecoder::default();
+        decoder.set_compression(true);
+
+        for command in &commands {
+            encoder.append_packet(&SChatCommand { command: command.clone() }).unwrap();
+        }
+        decoder.queue_bytes(encoder.take());
+
+        for command in commands {
+            let mut packet = decoder.decode().unwrap().expect("a whole packet");
+            prop_assert_eq!(packet.id.0, SChatCommand::PACKET_ID);
+            let read = SChatCommand::read(&mut packet.bytebuf)
+                .map_err(|err| TestCaseError::fail(err.to_string()))?;
+            prop_assert_eq!(read, SChatCommand { command });
+        }
+        prop_assert!(decoder.decode().unwrap().is_none());
+    }
+}
+
+#[test]
+fn golden_handshake() {
+    let packet = SHandShake {
+        protocol_version: VarInt(769),
+        server_address: "localhost".to_string(),
+        server_port: 25565,
+        next_state: ConnectionState::Login,
+    };
+    assert_eq!(
+        write(&packet)[..],
+        [
+            0x81, 0x06, 0x09, b'l', b'o', b'c', b'a', b'l', b'h', b'o', b's', b't', 0x63, 0xdd,
+            0x02
+        ]
+    );
+}
+
+#[test]
+fn golden_login_start() {
+    let packet = SLoginStart {
+        name: "Steve".to_string(),
+        uuid: Uuid::from_u128(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210),
+    };
+    assert_eq!(
+        write(&packet)[..],
+        [
+            0x05, b'S', b't', b'e', b'v', b'e', 0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+            0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10
+        ]
+    );
+}
+
+#[test]
+fn golden_chat_message() {
+    let packet = SChatMessage::new("hi".to_string(), 0, 0, None, VarInt(0), vec![0; 3]);
+    let mut expected = vec![0x02, b'h', b'i'];
+    // The timestamp and salt, no signature, no acknowledged messages
+    expected.extend([0; 16]);
+    expected.extend([0x00, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(write(&packet)[..], expected);
+}
+
+#[test]
+fn golden_clientbound() {
+    assert_eq!(write(&CSetCompression::new(VarInt(256)))[..], [0x80, 0x02]);
+    assert_eq!(
+        write(&CKeepAlive::new(0x0102_0304_0506_0708))[..],
+        [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]
+    );
+    assert_eq!(
+        write(&CEntityStatus::new(1, 3))[..],
+        [0x00, 0x00, 0x00, 0x01, 0x03]
+    );
+    // Block positions are packed into a long as x, z, y
+    let event = CLevelEvent::new(2001, BlockPos(Vector3::new(1, 2, 3)), 1, false);
+    assert_eq!(
+        write(&event)[..],
+        [
+            0x00, 0x00, 0x07, 0xd1, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x30, 0x02, 0x00, 0x00,
+            0x00, 0x01, 0x00
+        ]
+    );
+}
+
+#[test]
+fn golden_player_position() {
+    let mut bytes = Bytes::from_static(&[
+        0x3f, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x50, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xc0, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+    ]);
+    let packet = SPlayerPosition::read(&mut bytes).unwrap();
+    assert_eq!(packet.position, Vector3::new(1.0, 64.0, -2.5));
+    assert!(packet.ground);
+    assert!(bytes.is_empty());
+}
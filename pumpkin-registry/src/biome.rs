@@ -1,6 +1,24 @@
 use pumpkin_protocol::codec::var_int::VarInt;
 use serde::{Deserialize, Serialize};
 
+use crate::DataPool;
+
+/// The biomes of the Nether, every other biome outside the End is in the overworld
+const NETHER_BIOMES: [&str; 5] = [
+    "basalt_deltas",
+    "crimson_forest",
+    "nether_wastes",
+    "soul_sand_valley",
+    "warped_forest",
+];
+const END_BIOMES: [&str; 5] = [
+    "end_barrens",
+    "end_highlands",
+    "end_midlands",
+    "small_end_islands",
+    "the_end",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Biome {
     has_precipitation: bool,
@@ -30,8 +48,8 @@ struct BiomeEffects {
     mood_sound: Option<MoodSound>,
     #[serde(skip_serializing_if = "Option::is_none")]
     additions_sound: Option<AdditionsSound>,
-    //   #[serde(skip_serializing_if = "Option::is_none")]
-    //   music: Option<Vec<DataPool<Music>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    music: Option<Vec<DataPool<Music>>>,
     music_volume: f32,
 }
 
@@ -61,10 +79,51 @@ struct AdditionsSound {
     tick_chance: f64,
 }
 
+/// Music the client plays while in a biome, picked after a random delay between the two, in ticks
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct Music {
-    sound: String,
-    min_delay: i32,
-    max_delay: i32,
-    replace_current_music: bool,
+pub struct Music {
+    pub sound: String,
+    pub min_delay: i32,
+    pub max_delay: i32,
+    /// Whether the music stops the one playing when entering the biome
+    pub replace_current_music: bool,
+}
+
+impl Biome {
+    /// Replaces the music of the biome
+    pub fn set_music(&mut self, music: Music) {
+        self.effects.music = Some(vec![DataPool {
+            data: music,
+            weight: 1,
+        }]);
+    }
+}
+
+/// The dimension a biome generates in, like `minecraft:the_nether`
+#[must_use]
+pub fn dimension_of(biome: &str) -> &'static str {
+    let biome = biome.strip_prefix("minecraft:").unwrap_or(biome);
+    if NETHER_BIOMES.contains(&biome) {
+        "minecraft:the_nether"
+    } else if END_BIOMES.contains(&biome) {
+        "minecraft:the_end"
+    } else {
+        "minecraft:overworld"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dimension_of;
+
+    #[test]
+    fn dimensions() {
+        assert_eq!(dimension_of("minecraft:deep_ocean"), "minecraft:overworld");
+        assert_eq!(dimension_of("lush_caves"), "minecraft:overworld");
+        assert_eq!(
+            dimension_of("minecraft:soul_sand_valley"),
+            "minecraft:the_nether"
+        );
+        assert_eq!(dimension_of("minecraft:end_highlands"), "minecraft:the_end");
+    }
 }
@@ -23,6 +23,18 @@ pub struct Dimension {
     ultrawarm: bool,
 }
 
+impl Dimension {
+    /// The lowest block the dimension has
+    pub fn min_y(&self) -> i32 {
+        self.min_y
+    }
+
+    /// How many blocks high the dimension is, from `min_y` up
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default, Debug)]
 pub enum DimensionEffects {
     #[serde(rename = "minecraft:overworld")]
@@ -1,17 +1,20 @@
-use std::sync::LazyLock;
+use std::{collections::BTreeMap, sync::LazyLock};
 
 use banner_pattern::BannerPattern;
-use biome::Biome;
+pub use biome::Music;
+use biome::{Biome, dimension_of};
 use chat_type::ChatType;
 use damage_type::DamageType;
-use dimension::Dimension;
+pub use dimension::Dimension;
 use enchantment::Enchantment;
 use indexmap::IndexMap;
 use instrument::Instrument;
 use jukebox_song::JukeboxSong;
 use paint::Painting;
 use pumpkin_protocol::{client::config::RegistryEntry, codec::identifier::Identifier};
-pub use recipe::{RECIPES, Recipe, RecipeResult, RecipeType, flatten_3x3};
+pub use recipe::{
+    CookingRecipe, RECIPES, Recipe, RecipeBookCategory, RecipeResult, RecipeType, flatten_3x3,
+};
 use serde::{Deserialize, Serialize};
 use trim_material::TrimMaterial;
 use trim_pattern::TrimPattern;
@@ -81,14 +84,28 @@ impl DimensionType {
             Self::TheNether => Identifier::vanilla("the_nether"),
         }
     }
+
+    /// The dimension type as synced to clients, which decides how many sections their chunks have
+    pub fn dimension(&self) -> &'static Dimension {
+        &SYNCED_REGISTRIES.dimension_type[self.name().path.as_str()]
+    }
 }
 
 impl Registry {
-    pub fn get_synced() -> Vec<Self> {
+    /// The registries sent to clients, with the music of every biome in a dimension replaced by
+    /// `music`, which is keyed by dimension like `minecraft:overworld`
+    pub fn get_synced(music: &BTreeMap<String, Music>) -> Vec<Self> {
         let registry_entries = SYNCED_REGISTRIES
             .biome
             .iter()
-            .map(|(name, nbt)| RegistryEntry::from_nbt(name, nbt))
+            .map(|(name, biome)| match music.get(dimension_of(name)) {
+                Some(music) => {
+                    let mut biome = biome.clone();
+                    biome.set_music(music.clone());
+                    RegistryEntry::from_nbt(name, &biome)
+                }
+                None => RegistryEntry::from_nbt(name, biome),
+            })
             .collect();
         let biome = Registry {
             registry_id: Identifier::vanilla("worldgen/biome"),
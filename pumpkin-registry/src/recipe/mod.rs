@@ -1,7 +1,7 @@
 mod read;
 mod recipe_formats;
 
-pub use read::{Recipe, RecipeResult, RecipeType};
+pub use read::{CookingRecipe, Recipe, RecipeBookCategory, RecipeResult, RecipeType};
 use std::sync::LazyLock;
 pub fn flatten_3x3<T: Clone>(input: [[Option<T>; 3]; 3]) -> [[Option<T>; 3]; 3] {
     let mut final_output = [const { [const { None }; 3] }; 3];
@@ -44,7 +44,8 @@ pub static RECIPES: LazyLock<Vec<Recipe>> =
 #[cfg(test)]
 mod test {
     use super::flatten_3x3;
-    use crate::RECIPES;
+    use crate::{RECIPES, RecipeBookCategory, RecipeType};
+    use pumpkin_util::registry::TagType;
 
     #[test]
     fn row_flatten() {
@@ -87,4 +88,49 @@ mod test {
     fn check_parsing() {
         assert!(!RECIPES.is_empty())
     }
+
+    #[test]
+    fn cooking_recipes_keep_their_time() {
+        let baked_potato = |recipe_type| {
+            RECIPES
+                .iter()
+                .find(|recipe| {
+                    recipe.recipe_type == recipe_type
+                        && recipe.result().id() == "minecraft:baked_potato"
+                })
+                .and_then(|recipe| recipe.cooking())
+                .unwrap()
+        };
+        let campfire = baked_potato(RecipeType::CampfireCooking);
+        assert_eq!(campfire.cooking_time, 600);
+        assert_eq!(campfire.book_category, RecipeBookCategory::Campfire);
+        let smoker = baked_potato(RecipeType::Smoking);
+        assert_eq!(smoker.cooking_time, 100);
+        assert_eq!(
+            baked_potato(RecipeType::Smelting).book_category,
+            RecipeBookCategory::FurnaceFood
+        );
+    }
+
+    #[test]
+    fn stonecutting_keeps_its_ingredient() {
+        let slab = RECIPES
+            .iter()
+            .find(|recipe| {
+                recipe.recipe_type == RecipeType::StoneCutting
+                    && recipe.result().id() == "minecraft:andesite_slab"
+            })
+            .unwrap();
+        assert_eq!(
+            slab.ingredient().unwrap().get_values(),
+            vec![TagType::Item("minecraft:andesite".to_string())]
+        );
+        assert!(
+            slab.pattern()
+                .iter()
+                .flatten()
+                .flatten()
+                .all(Option::is_none)
+        );
+    }
 }
@@ -4,7 +4,7 @@ use crate::recipe::read::SpecialCraftingType::{
     ShulkerboxColoring, SuspiciousStew, TippedArrow,
 };
 use crate::recipe::read::ingredients::Ingredients;
-use crate::recipe::recipe_formats::{ShapedCrafting, ShapelessCrafting};
+use crate::recipe::recipe_formats::{Cooking, ShapedCrafting, ShapelessCrafting, StoneCutting};
 use pumpkin_util::registry::RegistryEntryList;
 use serde::de::{Error, MapAccess, Visitor};
 use serde::{Deserialize, Deserializer, de};
@@ -28,6 +28,52 @@ impl RecipeType {
         // I have not checked exactly which ones require shape and which don't!
         !matches!(self, Self::Crafting(CraftingType::Shaped))
     }
+
+    /// The cooking time in ticks used when a cooking recipe does not set one
+    const fn default_cooking_time(&self) -> u16 {
+        match self {
+            Self::Smelting => 200,
+            _ => 100,
+        }
+    }
+}
+
+/// The tab of the recipe book a recipe is shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipeBookCategory {
+    FurnaceFood,
+    FurnaceBlocks,
+    FurnaceMisc,
+    BlastFurnaceBlocks,
+    BlastFurnaceMisc,
+    SmokerFood,
+    Campfire,
+}
+
+impl RecipeBookCategory {
+    /// The tab of a cooking recipe, `category` is the one of the recipe file
+    fn for_cooking(recipe_type: RecipeType, category: Option<&str>) -> Self {
+        match (recipe_type, category) {
+            (RecipeType::Smelting, Some("food")) => Self::FurnaceFood,
+            (RecipeType::Smelting, Some("blocks")) => Self::FurnaceBlocks,
+            (RecipeType::Blasting, Some("blocks")) => Self::BlastFurnaceBlocks,
+            (RecipeType::Blasting, _) => Self::BlastFurnaceMisc,
+            (RecipeType::Smoking, _) => Self::SmokerFood,
+            (RecipeType::CampfireCooking, _) => Self::Campfire,
+            _ => Self::FurnaceMisc,
+        }
+    }
+}
+
+/// Everything a furnace, smoker, blast furnace or campfire needs to cook an item
+#[derive(Debug, Clone)]
+pub struct CookingRecipe {
+    pub ingredient: RegistryEntryList,
+    /// In ticks
+    pub cooking_time: u16,
+    /// Granted when taking the result out of a furnace
+    pub experience: f32,
+    pub book_category: RecipeBookCategory,
 }
 
 impl FromStr for RecipeType {
@@ -379,10 +425,26 @@ impl<'de> Deserialize<'de> for Recipe {
                         recipe_type,
                         result: RecipeResult::Special,
                     })),
-                    _ => Ok(Recipe::from(Test {
-                        recipe_type,
-                        result,
-                    })),
+                    RecipeType::Blasting
+                    | RecipeType::CampfireCooking
+                    | RecipeType::Smelting
+                    | RecipeType::Smoking => {
+                        let ingredient =
+                            ingredient.ok_or_else(|| de::Error::missing_field("ingredient"))?;
+                        let cooking = CookingRecipe {
+                            ingredient,
+                            cooking_time: cookingtime
+                                .unwrap_or_else(|| recipe_type.default_cooking_time()),
+                            experience: experience.unwrap_or_default(),
+                            book_category: RecipeBookCategory::for_cooking(recipe_type, category),
+                        };
+                        Ok(Recipe::from(Cooking::new(recipe_type, cooking, result)))
+                    }
+                    RecipeType::StoneCutting => {
+                        let ingredient =
+                            ingredient.ok_or_else(|| de::Error::missing_field("ingredient"))?;
+                        Ok(Recipe::from(StoneCutting::new(ingredient, result)))
+                    }
                 }
             }
         }
@@ -428,6 +490,8 @@ fn visit_option<'de, T: Deserialize<'de>, Map: MapAccess<'de>>(
 pub struct Recipe {
     pub recipe_type: RecipeType,
     pattern: Vec<[[Option<RegistryEntryList>; 3]; 3]>,
+    cooking: Option<CookingRecipe>,
+    ingredient: Option<RegistryEntryList>,
     result: RecipeResult,
 }
 
@@ -436,6 +500,16 @@ impl Recipe {
         &self.pattern
     }
 
+    /// The ingredient, time and experience of cooking recipes
+    pub fn cooking(&self) -> Option<&CookingRecipe> {
+        self.cooking.as_ref()
+    }
+
+    /// The ingredient of stonecutting recipes
+    pub fn ingredient(&self) -> Option<&RegistryEntryList> {
+        self.ingredient.as_ref()
+    }
+
     pub fn result(&self) -> &RecipeResult {
         &self.result
     }
@@ -481,12 +555,22 @@ pub trait RecipeTrait: Sized {
 
     fn pattern(&self) -> Vec<[[Option<RegistryEntryList>; 3]; 3]>;
 
+    fn cooking(&self) -> Option<CookingRecipe> {
+        None
+    }
+
+    fn ingredient(&self) -> Option<RegistryEntryList> {
+        None
+    }
+
     fn result(self) -> RecipeResult;
 
     fn to_recipe(self) -> Recipe {
         Recipe {
             recipe_type: self.recipe_type(),
             pattern: self.pattern().into_iter().map(flatten_3x3).collect(),
+            cooking: self.cooking(),
+            ingredient: self.ingredient(),
             result: self.result(),
         }
     }
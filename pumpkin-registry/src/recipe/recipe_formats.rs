@@ -1,7 +1,7 @@
 use pumpkin_util::registry::RegistryEntryList;
 
 use super::super::recipe::RecipeType;
-use super::read::{CraftingType, RecipeKeys, RecipeResult, RecipeTrait};
+use super::read::{CookingRecipe, CraftingType, RecipeKeys, RecipeResult, RecipeTrait};
 pub struct ShapedCrafting {
     keys: RecipeKeys,
     pattern: [[Option<char>; 3]; 3],
@@ -90,3 +90,74 @@ impl RecipeTrait for ShapelessCrafting {
         self.output
     }
 }
+
+/// Smelting, smoking, blasting and campfire cooking, which all turn a single item into another
+pub struct Cooking {
+    recipe_type: RecipeType,
+    cooking: CookingRecipe,
+    output: RecipeResult,
+}
+
+impl Cooking {
+    pub(crate) fn new(
+        recipe_type: RecipeType,
+        cooking: CookingRecipe,
+        output: RecipeResult,
+    ) -> Self {
+        Self {
+            recipe_type,
+            cooking,
+            output,
+        }
+    }
+}
+
+impl RecipeTrait for Cooking {
+    fn recipe_type(&self) -> RecipeType {
+        self.recipe_type
+    }
+
+    // Cooking is not done in a crafting grid, an empty pattern keeps the crafting matcher away
+    fn pattern(&self) -> Vec<[[Option<RegistryEntryList>; 3]; 3]> {
+        vec![[const { [const { None }; 3] }; 3]]
+    }
+
+    fn cooking(&self) -> Option<CookingRecipe> {
+        Some(self.cooking.clone())
+    }
+
+    fn result(self) -> RecipeResult {
+        self.output
+    }
+}
+
+/// Stonecutting, where the player picks which of the recipes of the ingredient to use
+pub struct StoneCutting {
+    ingredient: RegistryEntryList,
+    output: RecipeResult,
+}
+
+impl StoneCutting {
+    pub(crate) fn new(ingredient: RegistryEntryList, output: RecipeResult) -> Self {
+        Self { ingredient, output }
+    }
+}
+
+impl RecipeTrait for StoneCutting {
+    fn recipe_type(&self) -> RecipeType {
+        RecipeType::StoneCutting
+    }
+
+    // Stonecutting is not done in a crafting grid either
+    fn pattern(&self) -> Vec<[[Option<RegistryEntryList>; 3]; 3]> {
+        vec![[const { [const { None }; 3] }; 3]]
+    }
+
+    fn ingredient(&self) -> Option<RegistryEntryList> {
+        Some(self.ingredient.clone())
+    }
+
+    fn result(self) -> RecipeResult {
+        self.output
+    }
+}
@@ -0,0 +1,290 @@
+use std::{
+    collections::VecDeque,
+    io,
+    net::SocketAddr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Buf;
+use pumpkin_data::packet::clientbound::{
+    CONFIG_DISCONNECT, CONFIG_FINISH_CONFIGURATION, CONFIG_SELECT_KNOWN_PACKS,
+    LOGIN_LOGIN_COMPRESSION, LOGIN_LOGIN_DISCONNECT, LOGIN_LOGIN_FINISHED,
+    PLAY_CHUNK_BATCH_FINISHED, PLAY_DISCONNECT, PLAY_KEEP_ALIVE, PLAY_LOGIN, PLAY_PLAYER_CHAT,
+    PLAY_PLAYER_POSITION, PLAY_SYSTEM_CHAT,
+};
+use pumpkin_protocol::{
+    CURRENT_MC_PROTOCOL, ClientPacket, CompressionLevel, CompressionThreshold, ConnectionState,
+    RawPacket,
+    bytebuf::{ByteBuf, ReadingError},
+    codec::var_int::VarInt,
+    packet_decoder::{PacketDecodeError, PacketDecoder},
+    packet_encoder::{PacketEncodeError, PacketEncoder},
+    server::{
+        config::{SAcknowledgeFinishConfig, SKnownPacks},
+        handshake::SHandShake,
+        login::{SLoginAcknowledged, SLoginStart},
+        play::{
+            SChatCommand, SChatMessage, SChunkBatch, SConfirmTeleport, SKeepAlive, SPlayerLoaded,
+        },
+    },
+};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::timeout,
+};
+use uuid::Uuid;
+
+/// How long to wait for the server before failing the test
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Error, Debug)]
+pub enum TestClientError {
+    #[error("connection error: {0}")]
+    Io(#[from] io::Error),
+    #[error("failed to encode packet: {0}")]
+    Encode(#[from] PacketEncodeError),
+    #[error("failed to decode packet: {0}")]
+    Decode(#[from] PacketDecodeError),
+    #[error("failed to read packet: {0}")]
+    Reading(#[from] ReadingError),
+    #[error("the server closed the connection")]
+    Closed,
+    #[error("the server disconnected the client during {0:?}")]
+    Disconnected(ConnectionState),
+    #[error("timed out waiting for the server")]
+    Timeout,
+    #[error("unexpected packet {id} during {state:?}")]
+    UnexpectedPacket { id: i32, state: ConnectionState },
+}
+
+/// A headless client connected to a server, see [`TestClient::join`] for getting into the game
+pub struct TestClient {
+    stream: TcpStream,
+    encoder: PacketEncoder,
+    decoder: PacketDecoder,
+    state: ConnectionState,
+    /// Play packets received while joining, handed out before reading new ones
+    pending: VecDeque<RawPacket>,
+}
+
+impl TestClient {
+    pub async fn connect(address: SocketAddr) -> Result<Self, TestClientError> {
+        let stream = TcpStream::connect(address).await?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            encoder: PacketEncoder::default(),
+            decoder: PacketDecoder::default(),
+            state: ConnectionState::HandShake,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Connects, logs in offline and goes through the configuration phase, returning once the
+    /// player spawned in the world
+    pub async fn join(address: SocketAddr, name: &str) -> Result<Self, TestClientError> {
+        let mut client = Self::connect(address).await?;
+        client
+            .send(&SHandShake {
+                protocol_version: VarInt(i32::from(CURRENT_MC_PROTOCOL.get())),
+                server_address: address.ip().to_string(),
+                server_port: address.port(),
+                next_state: ConnectionState::Login,
+            })
+            .await?;
+        client.state = ConnectionState::Login;
+        client.login(name).await?;
+        client.configure().await?;
+        client.spawn().await?;
+        Ok(client)
+    }
+
+    async fn login(&mut self, name: &str) -> Result<(), TestClientError> {
+        self.send(&SLoginStart {
+            name: name.to_string(),
+            // The server makes up its own uuid in offline mode
+            uuid: Uuid::nil(),
+        })
+        .await?;
+        loop {
+            let mut packet = self.receive().await?;
+            match packet.id.0 {
+                LOGIN_LOGIN_COMPRESSION => {
+                    let threshold = packet.bytebuf.try_get_var_int()?;
+                    self.decoder.set_compression(true);
+                    self.encoder
+                        .set_compression(Some((
+                            CompressionThreshold(threshold.0.unsigned_abs()),
+                            CompressionLevel(6),
+                        )))
+                        .expect("6 is a valid compression level");
+                }
+                LOGIN_LOGIN_FINISHED => {
+                    self.send(&SLoginAcknowledged).await?;
+                    self.state = ConnectionState::Config;
+                    return Ok(());
+                }
+                LOGIN_LOGIN_DISCONNECT => return Err(self.disconnected()),
+                id => {
+                    return Err(TestClientError::UnexpectedPacket {
+                        id,
+                        state: self.state,
+                    });
+                }
+            }
+        }
+    }
+
+    async fn configure(&mut self) -> Result<(), TestClientError> {
+        loop {
+            let packet = self.receive().await?;
+            match packet.id.0 {
+                CONFIG_SELECT_KNOWN_PACKS => {
+                    self.send(&SKnownPacks {
+                        known_pack_count: VarInt(0),
+                    })
+                    .await?;
+                }
+                CONFIG_FINISH_CONFIGURATION => {
+                    self.send(&SAcknowledgeFinishConfig).await?;
+                    self.state = ConnectionState::Play;
+                    return Ok(());
+                }
+                CONFIG_DISCONNECT => return Err(self.disconnected()),
+                // Registries, tags, brand and so on
+                _ => {}
+            }
+        }
+    }
+
+    /// Waits for the login and the first position of the player, everything else is kept for
+    /// the test to look at
+    async fn spawn(&mut self) -> Result<(), TestClientError> {
+        let mut logged_in = false;
+        let mut skipped = VecDeque::new();
+        loop {
+            let mut packet = self.receive().await?;
+            match packet.id.0 {
+                PLAY_LOGIN => logged_in = true,
+                PLAY_PLAYER_POSITION if logged_in => {
+                    let teleport_id = packet.bytebuf.try_get_var_int()?;
+                    self.send(&SConfirmTeleport { teleport_id }).await?;
+                    self.send(&SPlayerLoaded).await?;
+                    self.pending = skipped;
+                    return Ok(());
+                }
+                PLAY_DISCONNECT => return Err(self.disconnected()),
+                _ => skipped.push_back(packet),
+            }
+        }
+    }
+
+    fn disconnected(&self) -> TestClientError {
+        TestClientError::Disconnected(self.state)
+    }
+
+    pub async fn send<P: ClientPacket>(&mut self, packet: &P) -> Result<(), TestClientError> {
+        self.encoder.append_packet(packet)?;
+        self.stream.write_all(&self.encoder.take()).await?;
+        Ok(())
+    }
+
+    /// Runs a command like the chat box does, without the leading slash
+    pub async fn command(&mut self, command: &str) -> Result<(), TestClientError> {
+        self.send(&SChatCommand {
+            command: command.to_string(),
+        })
+        .await
+    }
+
+    /// Sends an unsigned chat message
+    pub async fn chat(&mut self, message: &str) -> Result<(), TestClientError> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis());
+        self.send(&SChatMessage::new(
+            message.to_string(),
+            i64::try_from(timestamp).unwrap_or_default(),
+            0,
+            None,
+            VarInt(0),
+            vec![0; 3],
+        ))
+        .await
+    }
+
+    /// The next packet from the server. Keep alives and chunk batches get answered like a real
+    /// client would, so the server keeps the connection alive and keeps sending chunks
+    pub async fn receive(&mut self) -> Result<RawPacket, TestClientError> {
+        if let Some(packet) = self.pending.pop_front() {
+            return Ok(packet);
+        }
+        let packet = timeout(TIMEOUT, self.read_packet())
+            .await
+            .map_err(|_| TestClientError::Timeout)??;
+        if self.state == ConnectionState::Play {
+            match packet.id.0 {
+                PLAY_KEEP_ALIVE => {
+                    let keep_alive_id = packet
+                        .bytebuf
+                        .clone()
+                        .try_get_i64()
+                        .map_err(ReadingError::from)?;
+                    self.send(&SKeepAlive { keep_alive_id }).await?;
+                }
+                PLAY_CHUNK_BATCH_FINISHED => {
+                    self.send(&SChunkBatch {
+                        chunks_per_tick: 64.0,
+                    })
+                    .await?;
+                }
+                _ => {}
+            }
+        }
+        Ok(packet)
+    }
+
+    /// Skips packets until one with the id arrives
+    pub async fn expect(&mut self, id: i32) -> Result<RawPacket, TestClientError> {
+        loop {
+            let packet = self.receive().await?;
+            if packet.id.0 == id {
+                return Ok(packet);
+            }
+        }
+    }
+
+    /// Skips packets until a chat message containing the text arrives. This only looks at the
+    /// raw bytes, so it matches plain text and translation keys alike
+    pub async fn expect_message(&mut self, text: &str) -> Result<RawPacket, TestClientError> {
+        loop {
+            let packet = self.receive().await?;
+            let chat = matches!(packet.id.0, PLAY_SYSTEM_CHAT | PLAY_PLAYER_CHAT);
+            if chat
+                && packet
+                    .bytebuf
+                    .chunk()
+                    .windows(text.len())
+                    .any(|window| window == text.as_bytes())
+            {
+                return Ok(packet);
+            }
+        }
+    }
+
+    async fn read_packet(&mut self) -> Result<RawPacket, TestClientError> {
+        loop {
+            if let Some(packet) = self.decoder.decode()? {
+                return Ok(packet);
+            }
+            self.decoder.reserve(4096);
+            let mut buf = self.decoder.take_capacity();
+            if self.stream.read_buf(&mut buf).await? == 0 {
+                return Err(TestClientError::Closed);
+            }
+            self.decoder.queue_bytes(buf);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! A minimal headless client for testing the server end-to-end.
+//!
+//! It speaks just enough of the protocol to get through the handshake, an offline login and the
+//! configuration phase, then hands out the raw play packets so tests can check what the server
+//! sent. [`test_server`] starts one shared server in a temporary folder for the tests to connect
+//! to.
+
+mod client;
+mod server;
+
+pub use client::{TestClient, TestClientError};
+pub use server::test_server;
@@ -0,0 +1,60 @@
+use std::{
+    env, fs,
+    net::SocketAddr,
+    sync::{OnceLock, mpsc},
+    thread,
+};
+
+use pumpkin::PumpkinServer;
+
+/// Offline, unencrypted and on a random port, without reading from the console
+const CONFIGURATION: &str = r#"
+server_address = "127.0.0.1:0"
+online_mode = false
+encryption = false
+view_distance = 2
+simulation_distance = 2
+use_favicon = false
+"#;
+
+const FEATURES: &str = r"
+[logging]
+enabled = false
+
+[commands]
+use_console = false
+";
+
+static ADDRESS: OnceLock<SocketAddr> = OnceLock::new();
+
+/// Starts a server shared by all tests of the binary and returns where it listens. It runs in a
+/// fresh folder in the temp directory, which becomes the working directory of the process as the
+/// config and worlds are read from there
+pub fn test_server() -> SocketAddr {
+    *ADDRESS.get_or_init(|| {
+        let folder = env::temp_dir().join(format!("pumpkin-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(folder.join("config")).expect("Failed to create the test folder");
+        fs::write(folder.join("config/configuration.toml"), CONFIGURATION)
+            .expect("Failed to write the test configuration");
+        fs::write(folder.join("config/features.toml"), FEATURES)
+            .expect("Failed to write the test features");
+        env::set_current_dir(&folder).expect("Failed to enter the test folder");
+
+        // Every test gets its own runtime, so the server needs one that outlives them
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .expect("Failed to build the server runtime");
+            runtime.block_on(async {
+                let server = PumpkinServer::new().await;
+                server.init_plugins().await;
+                sender.send(server.server_addr).unwrap();
+                server.start().await;
+            });
+        });
+        receiver.recv().expect("The test server failed to start")
+    })
+}
@@ -0,0 +1,35 @@
+use pumpkin_data::packet::clientbound::{PLAY_CHUNK_BATCH_FINISHED, PLAY_LEVEL_CHUNK_WITH_LIGHT};
+use pumpkin_test_client::{TestClient, test_server};
+
+#[tokio::test]
+async fn join_and_receive_chunks() {
+    let mut client = TestClient::join(test_server(), "Chunky").await.unwrap();
+    client.expect(PLAY_LEVEL_CHUNK_WITH_LIGHT).await.unwrap();
+    client.expect(PLAY_CHUNK_BATCH_FINISHED).await.unwrap();
+}
+
+#[tokio::test]
+async fn chat() {
+    let mut client = TestClient::join(test_server(), "Chatty").await.unwrap();
+    client.chat("hello from the test client").await.unwrap();
+    client
+        .expect_message("hello from the test client")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn command_responses() {
+    let mut client = TestClient::join(test_server(), "Commander").await.unwrap();
+    client.command("list").await.unwrap();
+    client
+        .expect_message("commands.list.players")
+        .await
+        .unwrap();
+
+    client.command("not_a_command").await.unwrap();
+    client
+        .expect_message("command.unknown.command")
+        .await
+        .unwrap();
+}
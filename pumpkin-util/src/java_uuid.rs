@@ -0,0 +1,83 @@
+//! UUIDs the way Java and vanilla create and store them.
+
+use pumpkin_nbt::tag::NbtTag;
+use uuid::{Builder, Uuid};
+
+/// The UUID of a player on a server in offline mode, which Java derives from the name like
+/// `UUID.nameUUIDFromBytes` (version 3, without a namespace)
+pub fn offline_uuid(name: &str) -> Uuid {
+    name_uuid_from_bytes(format!("OfflinePlayer:{name}").as_bytes())
+}
+
+/// A version 3 UUID of the bytes, like `UUID.nameUUIDFromBytes` in Java
+pub fn name_uuid_from_bytes(bytes: &[u8]) -> Uuid {
+    Builder::from_md5_bytes(md5::compute(bytes).0).into_uuid()
+}
+
+/// Splits the UUID into the four ints vanilla stores it as in NBT, most significant first
+pub fn uuid_to_int_array(uuid: Uuid) -> [i32; 4] {
+    let bits = uuid.as_u128();
+    [
+        (bits >> 96) as i32,
+        (bits >> 64) as i32,
+        (bits >> 32) as i32,
+        bits as i32,
+    ]
+}
+
+/// Puts a UUID back together from its four ints, `None` if there aren't exactly four
+pub fn uuid_from_int_array(ints: &[i32]) -> Option<Uuid> {
+    let [a, b, c, d] = ints.try_into().ok()?;
+    Some(Uuid::from_u128(
+        (u128::from(a as u32) << 96)
+            | (u128::from(b as u32) << 64)
+            | (u128::from(c as u32) << 32)
+            | u128::from(d as u32),
+    ))
+}
+
+/// The UUID as the int array tag vanilla writes, like the `UUID` of entities
+pub fn uuid_to_nbt(uuid: Uuid) -> NbtTag {
+    NbtTag::IntArray(Box::new(uuid_to_int_array(uuid)))
+}
+
+/// Reads a UUID written by [`uuid_to_nbt`]
+pub fn uuid_from_nbt(tag: &NbtTag) -> Option<Uuid> {
+    match tag {
+        NbtTag::IntArray(ints) => uuid_from_int_array(ints),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{offline_uuid, uuid_from_int_array, uuid_from_nbt, uuid_to_int_array, uuid_to_nbt};
+
+    // Values checked against results from the equivalent Java source
+
+    #[test]
+    fn offline_uuids() {
+        let values = [
+            ("Notch", "b50ad385-829d-3141-a216-7e7d7539ba7f"),
+            ("jeb_", "a762f560-4fce-3236-812a-b80efff0b62b"),
+            ("Steve", "5627dd98-e6be-3c21-b8a8-e92344183641"),
+        ];
+        for (name, uuid) in values {
+            let expected = Uuid::parse_str(uuid).unwrap();
+            assert_eq!(offline_uuid(name), expected);
+            assert_eq!(offline_uuid(name).get_version_num(), 3);
+        }
+    }
+
+    #[test]
+    fn int_arrays() {
+        let uuid = Uuid::parse_str("b50ad385-829d-3141-a216-7e7d7539ba7f").unwrap();
+        let ints = [-1257581691, -2103627455, -1575584131, 1966717567];
+        assert_eq!(uuid_to_int_array(uuid), ints);
+        assert_eq!(uuid_from_int_array(&ints), Some(uuid));
+        assert_eq!(uuid_from_int_array(&ints[..3]), None);
+        assert_eq!(uuid_from_nbt(&uuid_to_nbt(uuid)), Some(uuid));
+    }
+}
@@ -1,7 +1,9 @@
 pub mod gamemode;
+pub mod java_uuid;
 pub mod loot_table;
 pub mod math;
 pub mod permission;
+pub mod player_name;
 pub mod random;
 pub mod registry;
 pub mod text;
@@ -1,54 +1,85 @@
-#[derive(Clone, Debug)]
+use crate::random::weighted::Weighted;
+
+#[derive(Clone, Copy, Debug)]
 pub struct LootTable {
     pub r#type: LootTableType,
     pub random_sequence: Option<&'static str>,
     pub pools: Option<&'static [LootPool]>,
+    /// Applied to every item the table drops
+    pub functions: Option<&'static [LootFunction]>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct LootPool {
     pub entries: &'static [LootPoolEntry],
-    pub rolls: f32,
+    pub conditions: Option<&'static [LootCondition]>,
+    /// Applied to every item the pool drops
+    pub functions: Option<&'static [LootFunction]>,
+    pub rolls: LootNumberProvider,
     pub bonus_rolls: f32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct ItemEntry {
     pub name: &'static str,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct AlternativeEntry {
     pub children: &'static [LootPoolEntry],
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
+pub struct TagEntry {
+    pub name: &'static str,
+    /// Whether one item of the tag drops instead of all of them
+    pub expand: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
 pub enum LootPoolEntryTypes {
     Empty,
     Item(ItemEntry),
     LootTable,
     Dynamic,
-    Tag,
+    Tag(TagEntry),
     Alternatives(AlternativeEntry),
     Sequence,
     Group,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum LootCondition {
-    Inverted,
-    AnyOf,
-    AllOf,
-    RandomChance,
-    RandomChanceWithEnchantedBonus,
+    Inverted(&'static LootCondition),
+    AnyOf(&'static [LootCondition]),
+    AllOf(&'static [LootCondition]),
+    RandomChance {
+        chance: f32,
+    },
+    RandomChanceWithEnchantedBonus {
+        enchantment: &'static str,
+        unenchanted_chance: f32,
+        /// The chance at the first level
+        base: f32,
+        per_level_above_first: f32,
+    },
     EntityProperties,
     KilledByPlayer,
     EntityScores,
     BlockStateProperty {
         properties: &'static [(&'static str, &'static str)],
     },
-    MatchTool,
-    TableBonus,
+    MatchTool {
+        /// An item like `minecraft:shears` or an item tag like `#minecraft:axes`
+        items: Option<&'static str>,
+        /// The enchantments the tool needs and their minimum level
+        enchantments: &'static [(&'static str, i32)],
+    },
+    TableBonus {
+        enchantment: &'static str,
+        /// The chance for each level of the enchantment, starting at 0
+        chances: &'static [f32],
+    },
     SurvivesExplosion,
     DamageSourceProperties,
     LocationCheck,
@@ -59,13 +90,75 @@ pub enum LootCondition {
     EnchantmentActiveCheck,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct LootPoolEntry {
     pub content: LootPoolEntryTypes,
     pub conditions: Option<&'static [LootCondition]>,
+    pub functions: Option<&'static [LootFunction]>,
+    /// How likely the entry is chosen compared to the other ones of the pool
+    pub weight: i32,
+}
+
+impl Weighted for LootPoolEntry {
+    fn weight(&self) -> i32 {
+        self.weight
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LootFunction {
+    pub content: LootFunctionTypes,
+    pub conditions: Option<&'static [LootCondition]>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum LootFunctionTypes {
+    SetCount {
+        count: LootNumberProvider,
+        /// Whether the count is added to the current one instead of replacing it
+        add: bool,
+    },
+    LimitCount {
+        min: Option<f32>,
+        max: Option<f32>,
+    },
+    ApplyBonus {
+        enchantment: &'static str,
+        formula: BonusFormula,
+    },
+    EnchantedCountIncrease {
+        enchantment: &'static str,
+        /// Added for each level of the enchantment
+        count: LootNumberProvider,
+        /// The highest the count can get, 0 if there is no limit
+        limit: i32,
+    },
+    ExplosionDecay,
+    FurnaceSmelt,
+    CopyComponents,
+    CopyState,
+    SetPotion,
+    SetOminousBottleAmplifier,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BonusFormula {
+    /// Multiplies the count by a random number from 1 up to the level plus one, like fortune on ores
+    OreDrops,
+    /// Adds a random number from 0 up to the level times the multiplier
+    UniformBonusCount { bonus_multiplier: i32 },
+    /// Adds one for each of `level + extra` tries succeeding with the probability
+    BinomialWithBonusCount { extra: i32, probability: f32 },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum LootNumberProvider {
+    Constant(f32),
+    Uniform { min: f32, max: f32 },
+    Binomial { n: f32, p: f32 },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum LootTableType {
     /// Nothing will be dropped
     Empty,
@@ -73,4 +166,6 @@ pub enum LootTableType {
     Block,
     /// A Item will be dropped
     Chest,
+    /// An Entity died
+    Entity,
 }
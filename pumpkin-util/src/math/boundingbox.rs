@@ -73,6 +73,66 @@ impl BoundingBox {
             && self.max.z > other.min.z
     }
 
+    /// Moves the box by the given amounts
+    pub fn shift(&self, x: f64, y: f64, z: f64) -> Self {
+        Self {
+            min: self.min.add_raw(x, y, z),
+            max: self.max.add_raw(x, y, z),
+        }
+    }
+
+    /// Grows the box in the direction of the movement, covering everything it passes through
+    pub fn stretch(&self, movement: Vector3<f64>) -> Self {
+        let mut min = self.min;
+        let mut max = self.max;
+        if movement.x < 0.0 {
+            min.x += movement.x;
+        } else {
+            max.x += movement.x;
+        }
+        if movement.y < 0.0 {
+            min.y += movement.y;
+        } else {
+            max.y += movement.y;
+        }
+        if movement.z < 0.0 {
+            min.z += movement.z;
+        } else {
+            max.z += movement.z;
+        }
+        Self { min, max }
+    }
+
+    /// How far the box can move by `offset` along the x axis before running into `other`
+    pub fn max_offset_x(&self, other: &Self, offset: f64) -> f64 {
+        if !overlaps(self.min.y, self.max.y, other.min.y, other.max.y)
+            || !overlaps(self.min.z, self.max.z, other.min.z, other.max.z)
+        {
+            return offset;
+        }
+        max_offset(self.min.x, self.max.x, other.min.x, other.max.x, offset)
+    }
+
+    /// How far the box can move by `offset` along the y axis before running into `other`
+    pub fn max_offset_y(&self, other: &Self, offset: f64) -> f64 {
+        if !overlaps(self.min.x, self.max.x, other.min.x, other.max.x)
+            || !overlaps(self.min.z, self.max.z, other.min.z, other.max.z)
+        {
+            return offset;
+        }
+        max_offset(self.min.y, self.max.y, other.min.y, other.max.y, offset)
+    }
+
+    /// How far the box can move by `offset` along the z axis before running into `other`
+    pub fn max_offset_z(&self, other: &Self, offset: f64) -> f64 {
+        if !overlaps(self.min.x, self.max.x, other.min.x, other.max.x)
+            || !overlaps(self.min.y, self.max.y, other.min.y, other.max.y)
+        {
+            return offset;
+        }
+        max_offset(self.min.z, self.max.z, other.min.z, other.max.z, offset)
+    }
+
     pub fn squared_magnitude(&self, pos: Vector3<f64>) -> f64 {
         let d = f64::max(f64::max(self.min.x - pos.x, pos.x - self.max.x), 0.0);
         let e = f64::max(f64::max(self.min.y - pos.y, pos.y - self.max.y), 0.0);
@@ -81,8 +141,42 @@ impl BoundingBox {
     }
 }
 
+/// Whether the ranges `min..max` and `other_min..other_max` overlap, touching isn't enough
+fn overlaps(min: f64, max: f64, other_min: f64, other_max: f64) -> bool {
+    min < other_max && max > other_min
+}
+
+/// Shortens `offset` along a single axis so that the range doesn't move into the other range
+fn max_offset(min: f64, max: f64, other_min: f64, other_max: f64, offset: f64) -> f64 {
+    if offset > 0.0 && other_min >= max {
+        offset.min(other_min - max)
+    } else if offset < 0.0 && other_max <= min {
+        offset.max(other_max - min)
+    } else {
+        offset
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct EntityDimensions {
     pub width: f32,
     pub height: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::BoundingBox;
+    use crate::math::vector3::Vector3;
+
+    #[test]
+    fn max_offset_stops_at_block() {
+        let entity = BoundingBox::new(Vector3::new(0.2, 1.5, 0.2), Vector3::new(0.8, 2.0, 0.8));
+        let block = BoundingBox::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(entity.max_offset_y(&block, -2.0), -0.5);
+        assert_eq!(entity.max_offset_y(&block, 0.3), 0.3);
+        // Not above the block, so nothing is in the way
+        let beside = entity.shift(2.0, 0.0, 0.0);
+        assert_eq!(beside.max_offset_y(&block, -2.0), -2.0);
+        assert_eq!(beside.max_offset_x(&block, -3.0), -3.0);
+    }
+}
@@ -3,9 +3,10 @@ use std::fmt;
 
 use crate::math::vector2::Vector2;
 use num_traits::Euclid;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 /// Aka Block Position
 pub struct BlockPos(pub Vector3<i32>);
 
@@ -54,16 +55,20 @@ impl BlockPos {
     pub fn offset(&self, offset: Vector3<i32>) -> Self {
         BlockPos(self.0 + offset)
     }
+
+    /// The position packed into a long, like it's sent over the network
+    pub fn as_long(&self) -> i64 {
+        ((self.0.x as i64 & 0x3FFFFFF) << 38)
+            | ((self.0.z as i64 & 0x3FFFFFF) << 12)
+            | (self.0.y as i64 & 0xFFF)
+    }
 }
 impl Serialize for BlockPos {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let long = ((self.0.x as i64 & 0x3FFFFFF) << 38)
-            | ((self.0.z as i64 & 0x3FFFFFF) << 12)
-            | (self.0.y as i64 & 0xFFF);
-        serializer.serialize_i64(long)
+        serializer.serialize_i64(self.as_long())
     }
 }
 
@@ -98,3 +103,36 @@ impl fmt::Display for BlockPos {
         write!(f, "{}, {}, {}", self.0.x, self.0.y, self.0.z)
     }
 }
+
+/// A block position in a dimension, like the lodestone a compass points to
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GlobalPos {
+    /// The identifier of the dimension, like `minecraft:overworld`
+    pub dimension: String,
+    pub pos: BlockPos,
+}
+
+impl GlobalPos {
+    pub fn new(dimension: String, pos: BlockPos) -> Self {
+        Self { dimension, pos }
+    }
+
+    /// Writes the position like vanilla, `{dimension: "minecraft:overworld", pos: [I; 0, 64, 0]}`
+    pub fn write_nbt(&self) -> NbtCompound {
+        let mut compound = NbtCompound::new();
+        compound.put_string("dimension", self.dimension.clone());
+        compound.put(
+            "pos",
+            NbtTag::IntArray(Box::new([self.pos.0.x, self.pos.0.y, self.pos.0.z])),
+        );
+        compound
+    }
+
+    pub fn read_nbt(compound: &NbtCompound) -> Option<Self> {
+        let dimension = compound.get_string("dimension")?.clone();
+        let [x, y, z] = compound.get_int_array("pos")? else {
+            return None;
+        };
+        Some(Self::new(dimension, BlockPos(Vector3::new(*x, *y, *z))))
+    }
+}
@@ -0,0 +1,35 @@
+//! The rules for the names of player profiles.
+
+/// Names of accounts can't be longer than this
+pub const MAX_NAME_LENGTH: usize = 16;
+
+/// Whether the name could belong to an account: 1 to 16 ASCII letters, digits or underscores.
+///
+/// Vanilla itself only rejects control characters, spaces and names longer than 16 characters,
+/// but no account can have such a name, so it's most likely a broken or malicious client
+pub fn is_valid_player_name(name: &str) -> bool {
+    (1..=MAX_NAME_LENGTH).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_valid_player_name;
+
+    #[test]
+    fn player_names() {
+        for name in ["Notch", "jeb_", "a", "_0123456789abcde"] {
+            assert!(is_valid_player_name(name), "{name}");
+        }
+        for name in [
+            "",
+            "0123456789abcdefg",
+            "two words",
+            "Notch!",
+            "§cRed",
+            "Ünïcode",
+        ] {
+            assert!(!is_valid_player_name(name), "{name}");
+        }
+    }
+}
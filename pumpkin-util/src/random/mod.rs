@@ -8,6 +8,8 @@ use xoroshiro128::{Xoroshiro, XoroshiroSplitter};
 
 mod gaussian;
 pub mod legacy_rand;
+pub mod seed;
+pub mod weighted;
 pub mod xoroshiro128;
 
 static SEED_UNIQUIFIER: AtomicU64 = AtomicU64::new(8682522807148012u64);
@@ -0,0 +1,124 @@
+//! Derives the seeds vanilla uses for the random generators of a single chunk or region from the
+//! world seed, so features placed with them end up where they do in vanilla.
+
+use super::{RandomImpl, legacy_rand::LegacyRand};
+
+/// The scrambler vanilla mixes into the seed of slime chunks
+const SLIME_SCRAMBLER: u64 = 987234911;
+
+/// The seed features of the chunk starting at `block_x`, `block_z` get placed with. `R` is the
+/// random the world generates with
+pub fn population_seed<R: RandomImpl>(world_seed: u64, block_x: i32, block_z: i32) -> u64 {
+    let mut random = R::from_seed(world_seed);
+    let l = random.next_i64() | 1;
+    let m = random.next_i64() | 1;
+    (i64::from(block_x)
+        .wrapping_mul(l)
+        .wrapping_add(i64::from(block_z).wrapping_mul(m)) as u64)
+        ^ world_seed
+}
+
+/// The seed of a single feature, `index` being the feature within its generation `step`
+pub fn decorator_seed(population_seed: u64, index: i32, step: i32) -> u64 {
+    population_seed
+        .wrapping_add(index as u64)
+        .wrapping_add(step.wrapping_mul(10000) as u64)
+}
+
+/// The seed the carvers of the chunk at `chunk_x`, `chunk_z` run with
+pub fn carver_seed<R: RandomImpl>(world_seed: u64, chunk_x: i32, chunk_z: i32) -> u64 {
+    let mut random = R::from_seed(world_seed);
+    let l = random.next_i64();
+    let m = random.next_i64();
+    (i64::from(chunk_x).wrapping_mul(l) ^ i64::from(chunk_z).wrapping_mul(m)) as u64 ^ world_seed
+}
+
+/// The seed structures pick their position within a region of their spacing with
+pub fn region_seed(world_seed: u64, region_x: i32, region_z: i32, salt: i32) -> u64 {
+    (i64::from(region_x).wrapping_mul(341873128712) as u64)
+        .wrapping_add(i64::from(region_z).wrapping_mul(132897987541) as u64)
+        .wrapping_add(world_seed)
+        .wrapping_add(salt as u64)
+}
+
+/// Whether slimes spawn deep below the surface of the chunk
+pub fn is_slime_chunk(world_seed: u64, chunk_x: i32, chunk_z: i32) -> bool {
+    // The overflows match the int arithmetic of vanilla
+    let seed = world_seed
+        .wrapping_add(chunk_x.wrapping_mul(chunk_x).wrapping_mul(4987142) as u64)
+        .wrapping_add(chunk_x.wrapping_mul(5947611) as u64)
+        .wrapping_add(i64::from(chunk_z.wrapping_mul(chunk_z)).wrapping_mul(4392871) as u64)
+        .wrapping_add(chunk_z.wrapping_mul(389711) as u64)
+        ^ SLIME_SCRAMBLER;
+    LegacyRand::from_seed(seed).next_bounded_i32(10) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::{legacy_rand::LegacyRand, xoroshiro128::Xoroshiro};
+
+    use super::{carver_seed, decorator_seed, is_slime_chunk, population_seed, region_seed};
+
+    // Values checked against results from the equivalent Java source
+
+    /// A chunk with its legacy and xoroshiro population and carver seeds
+    type ChunkSeeds = ((i32, i32), i64, i64, i64, i64);
+
+    #[test]
+    fn chunk_seeds() {
+        let values: [ChunkSeeds; 3] = [
+            ((0, 0), 12345, 12345, 12345, 12345),
+            (
+                (100, -200),
+                -7717849351365322587,
+                -6743114951537654035,
+                5229539980855207261,
+                6360105962991781797,
+            ),
+            (
+                (-16, 32),
+                1234855896218438697,
+                6243986732884693001,
+                -1362102927935401015,
+                4708185919077216169,
+            ),
+        ];
+        for ((x, z), legacy_population, population, legacy_carver, carver) in values {
+            assert_eq!(
+                population_seed::<LegacyRand>(12345, x, z),
+                legacy_population as u64
+            );
+            assert_eq!(population_seed::<Xoroshiro>(12345, x, z), population as u64);
+            assert_eq!(carver_seed::<LegacyRand>(12345, x, z), legacy_carver as u64);
+            assert_eq!(carver_seed::<Xoroshiro>(12345, x, z), carver as u64);
+        }
+
+        let population = population_seed::<Xoroshiro>(12345, 16, 16);
+        assert_eq!(decorator_seed(population, 2, 3), 1969159549260270795);
+        assert_eq!(region_seed(12345, 3, -7, 14357620), 95347843314);
+    }
+
+    #[test]
+    fn slime_chunks() {
+        let slime_chunks = [
+            (-4, 0),
+            (-2, -4),
+            (-2, 1),
+            (-2, 4),
+            (-1, 2),
+            (0, -2),
+            (3, 0),
+            (4, -3),
+            (4, 1),
+        ];
+        for x in -4..=4 {
+            for z in -4..=4 {
+                assert_eq!(
+                    is_slime_chunk(12345, x, z),
+                    slime_chunks.contains(&(x, z)),
+                    "chunk {x}, {z}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Picks entries with a chance relative to their weight, like the entries of loot pools or the
+//! mobs that can spawn in a biome.
+
+use super::RandomImpl;
+
+/// Something that can be picked out of a list with a chance relative to its weight
+pub trait Weighted {
+    fn weight(&self) -> i32;
+}
+
+impl<T: Weighted> Weighted for &T {
+    fn weight(&self) -> i32 {
+        (*self).weight()
+    }
+}
+
+/// The sum of all weights, nothing can be picked when it isn't positive
+pub fn total_weight<T: Weighted>(entries: &[T]) -> i32 {
+    entries.iter().map(Weighted::weight).sum()
+}
+
+/// The entry that a roll between 0 and the total weight lands on
+pub fn get_at<T: Weighted>(entries: &[T], roll: i32) -> Option<&T> {
+    let mut roll = roll;
+    entries.iter().find(|entry| {
+        roll -= entry.weight();
+        roll < 0
+    })
+}
+
+/// Picks one of the entries the same way vanilla does, `None` when there are no weights
+pub fn get_random<'a, T: Weighted>(
+    random: &mut impl RandomImpl,
+    entries: &'a [T],
+) -> Option<&'a T> {
+    let total_weight = total_weight(entries);
+    if total_weight <= 0 {
+        return None;
+    }
+    get_at(entries, random.next_bounded_i32(total_weight))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::{RandomImpl, legacy_rand::LegacyRand};
+
+    use super::{Weighted, get_at, get_random, total_weight};
+
+    struct Entry(i32);
+
+    impl Weighted for Entry {
+        fn weight(&self) -> i32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn rolls_land_on_weights() {
+        let entries = [Entry(1), Entry(0), Entry(3), Entry(6)];
+        assert_eq!(total_weight(&entries), 10);
+        let picked: Vec<i32> = (0..10)
+            .map(|roll| get_at(&entries, roll).unwrap().0)
+            .collect();
+        assert_eq!(picked, [1, 3, 3, 3, 6, 6, 6, 6, 6, 6]);
+        assert!(get_at(&entries, 10).is_none());
+    }
+
+    #[test]
+    fn random_matches_vanilla() {
+        // Java's `new Random(42)` rolls 0, 3, 8, 4, 0 when bounded to 10
+        let entries = [Entry(1), Entry(3), Entry(6)];
+        let mut random = LegacyRand::from_seed(42);
+        let picked: Vec<i32> = (0..5)
+            .map(|_| get_random(&mut random, &entries).unwrap().0)
+            .collect();
+        assert_eq!(picked, [1, 3, 6, 6, 1]);
+
+        let empty: [Entry; 0] = [];
+        assert!(get_random(&mut random, &empty).is_none());
+    }
+}
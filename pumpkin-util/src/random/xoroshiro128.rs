@@ -31,6 +31,16 @@ impl Xoroshiro {
         Self::new(lo, hi)
     }
 
+    /// The random of a sequence like the one a loot table rolls with, which vanilla derives from
+    /// the world seed and the identifier of the sequence
+    pub fn from_sequence(world_seed: u64, id: &str) -> Self {
+        let (lo, hi) = Self::mix_u64(world_seed);
+        let bytes = md5::compute(id.as_bytes());
+        let l = u64::from_be_bytes(bytes[0..8].try_into().expect("incorrect length"));
+        let m = u64::from_be_bytes(bytes[8..16].try_into().expect("incorrect length"));
+        Self::new(mix_stafford_13(lo ^ l), mix_stafford_13(hi ^ m))
+    }
+
     fn next(&mut self, bits: u64) -> u64 {
         self.next_random() >> (64 - bits)
     }
@@ -198,6 +208,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_sequence() {
+        let mut rand = Xoroshiro::from_sequence(12345, "minecraft:blocks/stone");
+        for value in [4615165662812329105, 663010234322599802, 3287341084594599814] {
+            assert_eq!(rand.next_i64(), value);
+        }
+
+        let mut rand = Xoroshiro::from_sequence(0, "minecraft:chests/simple_dungeon");
+        for value in [-980891774, -2113264652, 1695643152] {
+            assert_eq!(rand.next_i32(), value);
+        }
+    }
+
     #[test]
     fn test_next_i32() {
         let values = [
@@ -2,8 +2,7 @@ use colored::{ColoredString, Colorize};
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// Text color
-#[derive(Default, Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
-#[serde(untagged)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
     /// The default color for the text will be used, which varies by context
     /// (in some cases, it's white; in others, it's black; in still others, it
@@ -16,6 +15,19 @@ pub enum Color {
     Named(NamedColor),
 }
 
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Color::Reset => serializer.serialize_str("reset"),
+            Color::Rgb(color) => color.serialize(serializer),
+            Color::Named(color) => color.serialize(serializer),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for Color {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -101,7 +113,7 @@ impl Serialize for RGBColor {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 pub struct ARGBColor {
     alpha: u8,
     red: u8,
@@ -121,11 +133,24 @@ impl ARGBColor {
 }
 
 impl Serialize for ARGBColor {
+    /// Written as a single int like vanilla does, alpha in the highest byte
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_bytes([self.alpha, self.red, self.green, self.blue].as_ref())
+        serializer.serialize_i32(i32::from_be_bytes([
+            self.alpha, self.red, self.green, self.blue,
+        ]))
+    }
+}
+
+impl<'de> Deserialize<'de> for ARGBColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let [alpha, red, green, blue] = i32::deserialize(deserializer)?.to_be_bytes();
+        Ok(Self::new(alpha, red, green, blue))
     }
 }
 
@@ -151,6 +176,31 @@ pub enum NamedColor {
     White,
 }
 
+impl NamedColor {
+    /// The name commands and text components use for the color
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Black => "black",
+            Self::DarkBlue => "dark_blue",
+            Self::DarkGreen => "dark_green",
+            Self::DarkAqua => "dark_aqua",
+            Self::DarkRed => "dark_red",
+            Self::DarkPurple => "dark_purple",
+            Self::Gold => "gold",
+            Self::Gray => "gray",
+            Self::DarkGray => "dark_gray",
+            Self::Blue => "blue",
+            Self::Green => "green",
+            Self::Aqua => "aqua",
+            Self::Red => "red",
+            Self::LightPurple => "light_purple",
+            Self::Yellow => "yellow",
+            Self::White => "white",
+        }
+    }
+}
+
 impl TryFrom<&str> for NamedColor {
     type Error = ();
 
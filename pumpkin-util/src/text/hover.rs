@@ -16,9 +16,6 @@ pub enum HoverEvent {
         /// Number of the items in the stack
         #[serde(default, skip_serializing_if = "Option::is_none")]
         count: Option<i32>,
-        /// NBT information about the item (sNBT format)
-        #[serde(default, skip_serializing_if = "Option::is_none")]
-        tag: Option<Cow<'static, str>>,
     },
     /// Shows an entity.
     ShowEntity {
@@ -38,6 +35,15 @@ impl HoverEvent {
     pub fn show_text(text: TextComponent) -> Self {
         Self::ShowText(vec![text.0])
     }
+    pub fn show_item<P>(id: P, count: i32) -> Self
+    where
+        P: Into<Cow<'static, str>>,
+    {
+        Self::ShowItem {
+            id: id.into(),
+            count: Some(count),
+        }
+    }
     pub fn show_entity<P>(id: P, kind: P, name: Option<TextComponent>) -> Self
     where
         P: Into<Cow<'static, str>>,
@@ -48,6 +48,8 @@ impl TextComponentBase {
                 separator: _,
             } => selector.into_owned(),
             TextContent::Keybind { keybind } => keybind.into_owned(),
+            // TODO: Show the value of the score once scoreboards can be looked up from here
+            TextContent::Score { score } => score.name.into_owned(),
         };
         let style = self.style;
         let color = style.color;
@@ -106,11 +108,56 @@ impl TextComponent {
         })
     }
 
+    /// Displays the key the client has bound to `key`, e.g. `key.jump`
+    pub fn keybind<K>(key: K) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        Self::from_content(TextContent::Keybind {
+            keybind: key.into(),
+        })
+    }
+
+    /// Displays the score `name` has in `objective`, `name` may be a selector like `@s`
+    pub fn score<N, O>(name: N, objective: O) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        O: Into<Cow<'static, str>>,
+    {
+        Self::from_content(TextContent::Score {
+            score: ScoreContent {
+                name: name.into(),
+                objective: objective.into(),
+            },
+        })
+    }
+
+    /// Displays the names of the entities found by `selector`, joined by `separator` or `, `
+    pub fn selector<S>(selector: S, separator: Option<TextComponent>) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::from_content(TextContent::EntityNames {
+            selector: selector.into(),
+            separator: separator.map(|separator| Box::new(separator.0)),
+        })
+    }
+
     pub fn add_child(mut self, child: TextComponent) -> Self {
         self.0.extra.push(child.0);
         self
     }
 
+    pub fn add_children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator<Item = TextComponent>,
+    {
+        self.0
+            .extra
+            .extend(children.into_iter().map(|child| child.0));
+        self
+    }
+
     pub fn from_content(content: TextContent) -> Self {
         Self(TextComponentBase {
             content,
@@ -145,6 +192,8 @@ impl TextComponent {
                 separator: _,
             } => selector.into_owned(),
             TextContent::Keybind { keybind } => keybind.into_owned(),
+            // TODO: Show the value of the score once scoreboards can be looked up from here
+            TextContent::Score { score } => score.name.into_owned(),
         }
     }
 
@@ -163,6 +212,21 @@ impl TextComponent {
         buf.into_boxed_slice()
     }
 
+    /// The JSON form of the component, used where the protocol still expects a JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.0).expect("Failed to serialize text component JSON")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self)
+    }
+
+    /// Replaces the whole style of the component
+    pub fn style(mut self, style: Style) -> Self {
+        self.0.style = style;
+        self
+    }
+
     pub fn color(mut self, color: Color) -> Self {
         self.0.style.color = Some(color);
         self
@@ -255,18 +319,33 @@ pub enum TextContent {
     EntityNames {
         selector: Cow<'static, str>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
-        separator: Option<Cow<'static, str>>,
+        separator: Option<Box<TextComponentBase>>,
     },
     /// A keybind identifier
     /// https://minecraft.wiki/w/Controls#Configurable_controls
     Keybind { keybind: Cow<'static, str> },
+    /// The score an entity or player has in an objective
+    Score { score: ScoreContent },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ScoreContent {
+    /// Name of the score holder or a selector matching a single entity
+    pub name: Cow<'static, str>,
+    /// The objective to show the score of
+    pub objective: Cow<'static, str>,
 }
 
 #[cfg(test)]
 mod test {
     use pumpkin_nbt::serializer::to_bytes_unnamed;
 
-    use crate::text::{TextComponent, color::NamedColor};
+    use crate::text::{
+        TextComponent,
+        click::ClickEvent,
+        color::{ARGBColor, Color, NamedColor, RGBColor},
+        hover::HoverEvent,
+    };
 
     #[test]
     fn test_serialize_text_component() {
@@ -290,4 +369,34 @@ mod test {
 
         assert_eq!(bytes, expected_bytes);
     }
+
+    #[test]
+    fn test_serialize_text_component_json() {
+        let msg_comp = TextComponent::text("Hello ")
+            .color_named(NamedColor::Gold)
+            .bold()
+            .click_event(ClickEvent::RunCommand("/help".into()))
+            .hover_event(HoverEvent::show_item("minecraft:diamond", 3))
+            .add_children([
+                TextComponent::keybind("key.jump").color_rgb(RGBColor::new(255, 0, 0)),
+                TextComponent::score("@s", "kills"),
+            ]);
+
+        let json = msg_comp.to_json();
+        assert_eq!(
+            json,
+            r##"{"text":"Hello ","color":"gold","bold":true,"clickEvent":{"action":"run_command","value":"/help"},"hoverEvent":{"action":"show_item","contents":{"id":"minecraft:diamond","count":3}},"extra":[{"keybind":"key.jump","color":"#FF0000"},{"score":{"name":"@s","objective":"kills"}}]}"##
+        );
+        assert_eq!(TextComponent::from_json(&json).unwrap(), msg_comp);
+
+        let msg_comp = TextComponent::selector("@a", Some(TextComponent::text(" and ")))
+            .color(Color::Reset)
+            .shadow_color(ARGBColor::new(255, 0, 0, 0));
+        let json = msg_comp.to_json();
+        assert_eq!(
+            json,
+            r#"{"selector":"@a","separator":{"text":" and "},"color":"reset","shadow_color":-16777216}"#
+        );
+        assert_eq!(TextComponent::from_json(&json).unwrap(), msg_comp);
+    }
 }
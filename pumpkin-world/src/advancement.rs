@@ -0,0 +1,407 @@
+//! Advancements in the vanilla datapack format, loaded from
+//! `<world>/datapacks/<pack>/data/<namespace>/advancement/<path>.json`. Which of them a player
+//! completed is tracked by the server, this only knows what they are and how they are laid out
+//! on the advancement screen.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+
+use pumpkin_util::text::TextComponent;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The folder of a namespace advancements are loaded from
+const ADVANCEMENT_FOLDER: &str = "advancement";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Advancement {
+    /// The id of the parent, advancements without one are the roots of their own tab
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Advancements without a display are tracked but not shown, like the ones unlocking recipes
+    #[serde(default)]
+    pub display: Option<AdvancementDisplay>,
+    pub criteria: BTreeMap<String, Criterion>,
+    /// Every list needs one of its criteria done, `None` if every criterion is needed
+    #[serde(default)]
+    pub requirements: Option<Vec<Vec<String>>>,
+    #[serde(default)]
+    pub rewards: AdvancementRewards,
+    #[serde(default)]
+    pub sends_telemetry_event: bool,
+}
+
+impl Advancement {
+    /// The requirements, every criterion on its own if none were given
+    #[must_use]
+    pub fn requirements(&self) -> Vec<Vec<String>> {
+        self.requirements.clone().unwrap_or_else(|| {
+            self.criteria
+                .keys()
+                .map(|criterion| vec![criterion.clone()])
+                .collect()
+        })
+    }
+
+    /// Whether the advancement is done once the criteria for which `done` returns true are
+    #[must_use]
+    pub fn is_done(&self, done: impl Fn(&str) -> bool) -> bool {
+        self.requirements()
+            .iter()
+            .all(|any| any.iter().any(|criterion| done(criterion)))
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AdvancementDisplay {
+    pub icon: AdvancementIcon,
+    /// A text component, either a plain string or a component object
+    pub title: Value,
+    pub description: Value,
+    #[serde(default)]
+    pub frame: AdvancementFrame,
+    /// The texture behind the tab, only used by roots
+    #[serde(default)]
+    pub background: Option<String>,
+    #[serde(default = "default_true")]
+    pub show_toast: bool,
+    #[serde(default = "default_true")]
+    pub announce_to_chat: bool,
+    /// Hidden advancements are only shown once they are done
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl AdvancementDisplay {
+    #[must_use]
+    pub fn title(&self) -> TextComponent {
+        text_component(&self.title)
+    }
+
+    #[must_use]
+    pub fn description(&self) -> TextComponent {
+        text_component(&self.description)
+    }
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+const fn default_count() -> u8 {
+    1
+}
+
+fn text_component(value: &Value) -> TextComponent {
+    match value {
+        Value::String(text) => TextComponent::text(text.clone()),
+        value => serde_json::from_value(value.clone())
+            .unwrap_or_else(|_| TextComponent::text(value.to_string())),
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AdvancementIcon {
+    /// The item, like `minecraft:grass_block`
+    pub id: String,
+    #[serde(default = "default_count")]
+    pub count: u8,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvancementFrame {
+    #[default]
+    Task,
+    Challenge,
+    Goal,
+}
+
+impl AdvancementFrame {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Task => "task",
+            Self::Challenge => "challenge",
+            Self::Goal => "goal",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Criterion {
+    /// What has to happen, like `minecraft:inventory_changed`
+    pub trigger: String,
+    /// What the trigger has to match, their format depends on the trigger
+    #[serde(default)]
+    pub conditions: Option<Value>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct AdvancementRewards {
+    #[serde(default)]
+    pub experience: i32,
+}
+
+/// Every loaded advancement by its id, like `minecraft:story/root`
+#[derive(Default)]
+pub struct Advancements {
+    advancements: BTreeMap<String, Advancement>,
+    /// Where each shown advancement is in its tab
+    positions: HashMap<String, (f32, f32)>,
+    /// The advancements and criteria waiting for each trigger
+    by_trigger: HashMap<String, Vec<(String, String)>>,
+}
+
+impl Advancements {
+    /// Loads the advancements of every datapack in the folder
+    #[must_use]
+    pub fn load(datapacks: &Path) -> Self {
+        let mut advancements = BTreeMap::new();
+        let Ok(packs) = fs::read_dir(datapacks) else {
+            return Self::default();
+        };
+        for pack in packs.flatten() {
+            let Ok(namespaces) = fs::read_dir(pack.path().join("data")) else {
+                continue;
+            };
+            for namespace in namespaces.flatten() {
+                let name = namespace.file_name().to_string_lossy().into_owned();
+                let folder = namespace.path().join(ADVANCEMENT_FOLDER);
+                load_folder(&folder, &name, "", &mut advancements);
+            }
+        }
+        let advancements = Self::new(advancements);
+        log::info!("Loaded {} advancements", advancements.advancements.len());
+        advancements
+    }
+
+    /// Drops the advancements with a missing parent or broken requirements, and lays out the rest
+    #[must_use]
+    pub fn new(mut advancements: BTreeMap<String, Advancement>) -> Self {
+        for advancement in advancements.values_mut() {
+            if let Some(parent) = &mut advancement.parent {
+                *parent = namespaced(parent);
+            }
+        }
+        advancements.retain(|id, advancement| {
+            let valid = advancement
+                .requirements()
+                .iter()
+                .flatten()
+                .all(|criterion| advancement.criteria.contains_key(criterion));
+            if !valid {
+                log::warn!("Advancement {id} requires a criterion it doesn't have, skipping it");
+            }
+            valid && !advancement.criteria.is_empty()
+        });
+        // Removing an advancement can orphan its children in turn
+        loop {
+            let orphans: Vec<String> = advancements
+                .iter()
+                .filter(|(_, advancement)| {
+                    advancement
+                        .parent
+                        .as_ref()
+                        .is_some_and(|parent| !advancements.contains_key(parent))
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+            if orphans.is_empty() {
+                break;
+            }
+            for id in orphans {
+                log::warn!("The parent of advancement {id} doesn't exist, skipping it");
+                advancements.remove(&id);
+            }
+        }
+
+        let mut by_trigger: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        for (id, advancement) in &advancements {
+            for (name, criterion) in &advancement.criteria {
+                by_trigger
+                    .entry(namespaced(&criterion.trigger))
+                    .or_default()
+                    .push((id.clone(), name.clone()));
+            }
+        }
+        let positions = layout(&advancements);
+        Self {
+            advancements,
+            positions,
+            by_trigger,
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&Advancement> {
+        self.advancements.get(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Advancement)> {
+        self.advancements.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.advancements.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.advancements.is_empty()
+    }
+
+    /// Where the advancement is shown in its tab, in columns and rows
+    #[must_use]
+    pub fn position(&self, id: &str) -> (f32, f32) {
+        self.positions.get(id).copied().unwrap_or_default()
+    }
+
+    /// The advancements and their criteria which use the trigger, like `minecraft:tick`
+    #[must_use]
+    pub fn with_trigger(&self, trigger: &str) -> &[(String, String)] {
+        self.by_trigger.get(trigger).map_or(&[], Vec::as_slice)
+    }
+}
+
+fn load_folder(
+    folder: &Path,
+    namespace: &str,
+    prefix: &str,
+    advancements: &mut BTreeMap<String, Advancement>,
+) {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if path.is_dir() {
+            load_folder(&path, namespace, &format!("{prefix}{name}/"), advancements);
+            continue;
+        }
+        let Some(name) = name.strip_suffix(".json") else {
+            continue;
+        };
+        let id = format!("{namespace}:{prefix}{name}");
+        let parsed = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|err| err.to_string()));
+        match parsed {
+            Ok(advancement) => {
+                advancements.insert(id, advancement);
+            }
+            Err(err) => log::warn!("Failed to load advancement {id}: {err}"),
+        }
+    }
+}
+
+/// Adds the `minecraft` namespace to ids without one
+#[must_use]
+pub fn namespaced(id: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("minecraft:{id}")
+    }
+}
+
+/// Lays out every tab as a tree growing to the right: children are one column further than their
+/// parent, every leaf gets a row of its own and parents line up with their first child
+fn layout(advancements: &BTreeMap<String, Advancement>) -> HashMap<String, (f32, f32)> {
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (id, advancement) in advancements {
+        match &advancement.parent {
+            Some(parent) => children.entry(parent).or_default().push(id),
+            None => roots.push(id.as_str()),
+        }
+    }
+
+    let mut positions = HashMap::new();
+    for root in roots {
+        let mut row = 0;
+        place(root, 0, &mut row, &children, &mut positions);
+    }
+    positions
+}
+
+fn place(
+    id: &str,
+    column: u32,
+    row: &mut u32,
+    children: &HashMap<&str, Vec<&str>>,
+    positions: &mut HashMap<String, (f32, f32)>,
+) {
+    let first_row = *row;
+    match children.get(id) {
+        Some(children_of) => {
+            for child in children_of {
+                place(child, column + 1, row, children, positions);
+            }
+        }
+        None => *row += 1,
+    }
+    positions.insert(id.to_string(), (column as f32, first_row as f32));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{Advancement, Advancements};
+
+    fn advancement(json: &str) -> Advancement {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn requirements() {
+        let any = advancement(
+            r#"{
+                "criteria": {
+                    "a": {"trigger": "minecraft:tick"},
+                    "b": {"trigger": "minecraft:impossible"}
+                },
+                "requirements": [["a", "b"]]
+            }"#,
+        );
+        assert!(any.is_done(|criterion| criterion == "a"));
+        assert!(!any.is_done(|_| false));
+
+        let all =
+            advancement(r#"{"criteria": {"a": {"trigger": "tick"}, "b": {"trigger": "tick"}}}"#);
+        assert_eq!(all.requirements(), vec![vec!["a"], vec!["b"]]);
+        assert!(!all.is_done(|criterion| criterion == "a"));
+        assert!(all.is_done(|_| true));
+    }
+
+    #[test]
+    fn tree() {
+        let criteria = r#""criteria": {"a": {"trigger": "minecraft:tick"}}"#;
+        let mut loaded = BTreeMap::new();
+        loaded.insert("test:root".into(), advancement(&format!("{{{criteria}}}")));
+        for (id, parent) in [
+            ("test:a", "test:root"),
+            ("test:b", "test:root"),
+            ("test:a/child", "test:a"),
+            ("test:orphan", "test:missing"),
+            ("test:orphan/child", "test:orphan"),
+        ] {
+            let json = format!(r#"{{"parent": "{parent}", {criteria}}}"#);
+            loaded.insert(id.into(), advancement(&json));
+        }
+        let advancements = Advancements::new(loaded);
+
+        assert_eq!(advancements.len(), 4);
+        assert!(advancements.get("test:orphan/child").is_none());
+        assert_eq!(advancements.position("test:root"), (0.0, 0.0));
+        assert_eq!(advancements.position("test:a/child"), (2.0, 0.0));
+        assert_eq!(advancements.position("test:b"), (1.0, 1.0));
+        assert_eq!(advancements.with_trigger("minecraft:tick").len(), 4);
+    }
+}
@@ -0,0 +1,36 @@
+//! Prints what differs between two region files, like a backup and the corrupted copy
+//!
+//! `cargo run -p pumpkin-world --features chunk-diff --bin chunk-diff -- <left.mca> <right.mca>`
+
+use std::{env, fs, process::ExitCode};
+
+use pumpkin_world::chunk::diff::diff_regions;
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let [left, right] = args.as_slice() else {
+        eprintln!("Usage: chunk-diff <left region> <right region>");
+        return ExitCode::FAILURE;
+    };
+
+    let read =
+        |path: &String| fs::read(path).map_err(|err| eprintln!("Failed to read {path}: {err}"));
+    let (Ok(left), Ok(right)) = (read(left), read(right)) else {
+        return ExitCode::FAILURE;
+    };
+
+    match diff_regions(left.into(), right.into()) {
+        Ok(diff) => {
+            print!("{diff}");
+            if diff.chunks.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(1)
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::from(2)
+        }
+    }
+}
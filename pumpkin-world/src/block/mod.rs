@@ -8,7 +8,7 @@ use pumpkin_util::math::vector3::Vector3;
 
 pub use state::ChunkBlockState;
 
-#[derive(FromPrimitive, PartialEq, Clone, Copy)]
+#[derive(FromPrimitive, PartialEq, Clone, Copy, Debug)]
 pub enum BlockDirection {
     Down = 0,
     Up,
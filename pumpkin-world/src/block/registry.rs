@@ -1,8 +1,10 @@
 use pumpkin_data::block::Block;
 use pumpkin_data::block::BlockState;
 use pumpkin_data::block::BlockStateRef;
-use pumpkin_data::block::COLLISION_SHAPES;
 use pumpkin_data::block::CollisionShape;
+use pumpkin_data::block::PartialBlockState;
+use pumpkin_util::math::boundingbox::BoundingBox;
+use pumpkin_util::math::position::BlockPos;
 
 pub fn get_block(registry_id: &str) -> Option<Block> {
     let key = registry_id.replace("minecraft:", "");
@@ -40,11 +42,64 @@ pub fn get_block_by_item(item_id: u16) -> Option<Block> {
 }
 
 pub fn get_block_collision_shapes(state_id: u16) -> Option<Vec<CollisionShape>> {
-    let state = get_state_by_state_id(state_id)?;
-    let mut shapes: Vec<CollisionShape> = vec![];
-    for i in 0..state.collision_shapes.len() {
-        let shape = &COLLISION_SHAPES[state.collision_shapes[i] as usize];
-        shapes.push(*shape);
+    let state = PartialBlockState::from_state_id(state_id)?;
+    Some(state.collision_shapes().copied().collect())
+}
+
+/// The collision boxes of a block state placed at `position`, empty for blocks without collision
+pub fn get_block_collision_boxes(state_id: u16, position: &BlockPos) -> Vec<BoundingBox> {
+    let Some(state) = PartialBlockState::from_state_id(state_id) else {
+        return Vec::new();
+    };
+    state
+        .collision_shapes()
+        .map(|shape| {
+            BoundingBox::from_block_raw(position)
+                .offset(BoundingBox::new_array(shape.min, shape.max))
+        })
+        .collect()
+}
+
+/// Whether the block state fills its whole block, like stone but unlike slabs or fences
+pub fn is_full_cube(state_id: u16) -> bool {
+    PartialBlockState::from_state_id(state_id).is_some_and(|state| {
+        let mut shapes = state.collision_shapes();
+        shapes.next().is_some_and(CollisionShape::is_full_cube) && shapes.next().is_none()
+    })
+}
+
+/// The height of the top of the collision shapes of a block state, `None` without collision
+pub fn get_collision_height(state_id: u16) -> Option<f64> {
+    PartialBlockState::from_state_id(state_id)?
+        .collision_shapes()
+        .map(|shape| shape.max[1])
+        .reduce(f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_block, get_block_collision_boxes, get_collision_height, is_full_cube};
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    fn default_state(name: &str) -> u16 {
+        get_block(name).unwrap().default_state_id
+    }
+
+    #[test]
+    fn shapes_per_state() {
+        assert!(is_full_cube(default_state("stone")));
+        assert!(!is_full_cube(default_state("oak_slab")));
+        assert!(!is_full_cube(default_state("oak_fence")));
+        assert!(!is_full_cube(default_state("air")));
+
+        assert_eq!(get_collision_height(default_state("oak_slab")), Some(0.5));
+        assert_eq!(get_collision_height(default_state("oak_fence")), Some(1.5));
+        assert_eq!(get_collision_height(default_state("short_grass")), None);
+
+        let boxes =
+            get_block_collision_boxes(default_state("stone"), &BlockPos(Vector3::new(2, -3, 4)));
+        assert_eq!(boxes.len(), 1);
+        assert_eq!(boxes[0].min, Vector3::new(2.0, -3.0, 4.0));
+        assert_eq!(boxes[0].max, Vector3::new(3.0, -2.0, 5.0));
     }
-    Some(shapes)
 }
@@ -0,0 +1,573 @@
+//! Compares chunks or whole region files to find out what changed between two saves, meant for
+//! tracking down save corruption. Blocks are compared after decoding, so two sections that store
+//! the same blocks with a differently ordered palette are still equal.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use bytes::Bytes;
+use pumpkin_data::block::Block;
+use pumpkin_nbt::{Nbt, compound::NbtCompound, deserializer::ReadAdaptor, tag::NbtTag};
+use pumpkin_util::math::vector2::Vector2;
+use thiserror::Error;
+
+use super::{
+    CHUNK_AREA, ChunkData, ChunkReadingError, CompressionError,
+    format::anvil::{AnvilChunkFile, CHUNK_COUNT, REGION_SIZE},
+    io::ChunkSerializer,
+};
+use crate::WORLD_LOWEST_Y;
+
+/// How many differing blocks are listed per chunk, the rest is only counted
+const MAX_LISTED_BLOCKS: usize = 64;
+
+#[derive(Error, Debug)]
+pub enum ChunkDiffError {
+    #[error("Failed to read the chunk NBT: {0}")]
+    Nbt(#[from] pumpkin_nbt::Error),
+    #[error("Failed to read the region: {0}")]
+    Region(#[from] ChunkReadingError),
+    #[error("Failed to decompress the chunk: {0}")]
+    Compression(#[from] CompressionError),
+}
+
+pub struct BlockDifference {
+    /// The position of the block in the world
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub left: u16,
+    pub right: u16,
+}
+
+/// Palette entries, written like `minecraft:oak_stairs[facing=east,half=top]`, only one side of
+/// a section has
+pub struct PaletteDifference {
+    pub section_y: i8,
+    pub only_left: Vec<String>,
+    pub only_right: Vec<String>,
+}
+
+/// A field of the chunk NBT which only one side has, or which differs. The path looks like
+/// `sections[3].biomes.palette[0]`
+pub struct NbtDifference {
+    pub path: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+pub struct ChunkDiff {
+    pub position: Vector2<i32>,
+    /// The first differing blocks, see `differing_blocks` for how many there are
+    pub blocks: Vec<BlockDifference>,
+    pub differing_blocks: usize,
+    pub palettes: Vec<PaletteDifference>,
+    /// Every other field, block states are covered by the blocks and palettes
+    pub nbt: Vec<NbtDifference>,
+    /// Why blocks couldn't be compared, like a side failing to parse
+    pub errors: Vec<String>,
+}
+
+impl ChunkDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.differing_blocks == 0
+            && self.palettes.is_empty()
+            && self.nbt.is_empty()
+            && self.errors.is_empty()
+    }
+}
+
+pub enum RegionChunkDiff {
+    /// Only the left region has the chunk, by its index in the region
+    OnlyLeft(usize),
+    OnlyRight(usize),
+    /// The chunk couldn't be decompressed or read as NBT on one of the sides
+    Unreadable {
+        index: usize,
+        error: String,
+    },
+    Different(ChunkDiff),
+}
+
+pub struct RegionDiff {
+    pub chunks: Vec<RegionChunkDiff>,
+}
+
+/// Compares two chunks given as their uncompressed NBT
+pub fn diff_chunks(left: &[u8], right: &[u8]) -> Result<ChunkDiff, ChunkDiffError> {
+    let left_nbt = read_nbt(left)?;
+    let right_nbt = read_nbt(right)?;
+    let position = Vector2::new(
+        left_nbt.get_int("xPos").unwrap_or_default(),
+        left_nbt.get_int("zPos").unwrap_or_default(),
+    );
+
+    let mut diff = ChunkDiff {
+        position,
+        blocks: Vec::new(),
+        differing_blocks: 0,
+        palettes: diff_palettes(&left_nbt, &right_nbt),
+        nbt: Vec::new(),
+        errors: Vec::new(),
+    };
+    diff_compounds("", &left_nbt, &right_nbt, &mut diff.nbt);
+
+    let right_position = Vector2::new(
+        right_nbt.get_int("xPos").unwrap_or_default(),
+        right_nbt.get_int("zPos").unwrap_or_default(),
+    );
+    match (
+        ChunkData::from_bytes(left, position),
+        ChunkData::from_bytes(right, right_position),
+    ) {
+        (Ok(left), Ok(right)) => diff_blocks(&left, &right, &mut diff),
+        (left, right) => {
+            if let Err(err) = left {
+                diff.errors
+                    .push(format!("Failed to parse the left chunk: {err}"));
+            }
+            if let Err(err) = right {
+                diff.errors
+                    .push(format!("Failed to parse the right chunk: {err}"));
+            }
+        }
+    }
+    Ok(diff)
+}
+
+/// Compares every chunk of two Anvil region files, identical chunks are left out
+pub fn diff_regions(left: Bytes, right: Bytes) -> Result<RegionDiff, ChunkDiffError> {
+    let left = <AnvilChunkFile>::read(left)?;
+    let right = <AnvilChunkFile>::read(right)?;
+
+    let mut chunks = Vec::new();
+    for index in 0..CHUNK_COUNT {
+        let diff = match (left.raw_chunk(index), right.raw_chunk(index)) {
+            (None, None) => continue,
+            (Some(_), None) => RegionChunkDiff::OnlyLeft(index),
+            (None, Some(_)) => RegionChunkDiff::OnlyRight(index),
+            (Some(left), Some(right)) => {
+                let diff = left
+                    .map_err(ChunkDiffError::from)
+                    .and_then(|left| diff_chunks(&left, &right?));
+                match diff {
+                    Ok(diff) if diff.is_empty() => continue,
+                    Ok(diff) => RegionChunkDiff::Different(diff),
+                    Err(err) => RegionChunkDiff::Unreadable {
+                        index,
+                        error: err.to_string(),
+                    },
+                }
+            }
+        };
+        chunks.push(diff);
+    }
+    Ok(RegionDiff { chunks })
+}
+
+fn read_nbt(bytes: &[u8]) -> Result<NbtCompound, pumpkin_nbt::Error> {
+    Nbt::read(&mut ReadAdaptor::new(bytes)).map(|nbt| nbt.root_tag)
+}
+
+fn diff_blocks(left: &ChunkData, right: &ChunkData, diff: &mut ChunkDiff) {
+    let sections = left
+        .subchunks
+        .array_iter()
+        .zip(right.subchunks.array_iter());
+    for (section, (left, right)) in sections.enumerate() {
+        for (index, (&left, &right)) in left.iter().zip(right.iter()).enumerate() {
+            if left == right {
+                continue;
+            }
+            diff.differing_blocks += 1;
+            if diff.blocks.len() < MAX_LISTED_BLOCKS {
+                diff.blocks.push(BlockDifference {
+                    x: diff.position.x * 16 + (index % 16) as i32,
+                    y: (section * 16 + index / CHUNK_AREA) as i32 + i32::from(WORLD_LOWEST_Y),
+                    z: diff.position.z * 16 + (index % CHUNK_AREA / 16) as i32,
+                    left,
+                    right,
+                });
+            }
+        }
+    }
+}
+
+/// The palette entries of every section, by the section's y
+fn palettes(chunk: &NbtCompound) -> BTreeMap<i8, BTreeSet<String>> {
+    let mut palettes = BTreeMap::new();
+    for section in chunk.get_list("sections").unwrap_or_default() {
+        let NbtTag::Compound(section) = section else {
+            continue;
+        };
+        let entries = section
+            .get_compound("block_states")
+            .and_then(|states| states.get_list("palette"))
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| match entry {
+                NbtTag::Compound(entry) => Some(palette_entry(entry)),
+                _ => None,
+            })
+            .collect();
+        palettes.insert(section.get_byte("Y").unwrap_or_default(), entries);
+    }
+    palettes
+}
+
+fn palette_entry(entry: &NbtCompound) -> String {
+    let name = entry.get_string("Name").cloned().unwrap_or_default();
+    let Some(properties) = entry.get_compound("Properties") else {
+        return name;
+    };
+    let mut properties = properties
+        .child_tags
+        .iter()
+        .map(|(key, value)| format!("{key}={}", describe(value)))
+        .collect::<Vec<_>>();
+    properties.sort();
+    format!("{name}[{}]", properties.join(","))
+}
+
+fn diff_palettes(left: &NbtCompound, right: &NbtCompound) -> Vec<PaletteDifference> {
+    let left = palettes(left);
+    let mut right = palettes(right);
+    let empty = BTreeSet::new();
+
+    let mut differences = Vec::new();
+    for (section_y, left) in left {
+        let right = right.remove(&section_y);
+        let right = right.as_ref().unwrap_or(&empty);
+        if &left != right {
+            differences.push(PaletteDifference {
+                section_y,
+                only_left: left.difference(right).cloned().collect(),
+                only_right: right.difference(&left).cloned().collect(),
+            });
+        }
+    }
+    for (section_y, right) in right {
+        differences.push(PaletteDifference {
+            section_y,
+            only_left: Vec::new(),
+            only_right: right.into_iter().collect(),
+        });
+    }
+    differences.sort_by_key(|difference| difference.section_y);
+    differences
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+fn diff_compounds(
+    path: &str,
+    left: &NbtCompound,
+    right: &NbtCompound,
+    differences: &mut Vec<NbtDifference>,
+) {
+    for (name, left_tag) in &left.child_tags {
+        // Compared block by block instead
+        if name == "block_states" {
+            continue;
+        }
+        let path = join(path, name);
+        match right.get(name) {
+            Some(right_tag) => diff_tags(path, left_tag, right_tag, differences),
+            None => differences.push(NbtDifference {
+                path,
+                left: Some(describe(left_tag)),
+                right: None,
+            }),
+        }
+    }
+    for (name, right_tag) in &right.child_tags {
+        if name != "block_states" && left.get(name).is_none() {
+            differences.push(NbtDifference {
+                path: join(path, name),
+                left: None,
+                right: Some(describe(right_tag)),
+            });
+        }
+    }
+}
+
+fn diff_tags(path: String, left: &NbtTag, right: &NbtTag, differences: &mut Vec<NbtDifference>) {
+    match (left, right) {
+        (NbtTag::Compound(left), NbtTag::Compound(right)) => {
+            diff_compounds(&path, left, right, differences);
+        }
+        (NbtTag::List(left), NbtTag::List(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                let path = format!("{path}[{index}]");
+                match (left.get(index), right.get(index)) {
+                    (Some(left), Some(right)) => diff_tags(path, left, right, differences),
+                    (left, right) => differences.push(NbtDifference {
+                        path,
+                        left: left.map(describe),
+                        right: right.map(describe),
+                    }),
+                }
+            }
+        }
+        (left, right) if left != right => differences.push(NbtDifference {
+            path: first_array_difference(left, right)
+                .map_or(path.clone(), |index| format!("{path}[{index}]")),
+            left: Some(describe(left)),
+            right: Some(describe(right)),
+        }),
+        _ => {}
+    }
+}
+
+/// Where two arrays of the same type start to differ, as listing every element of a heightmap
+/// would bury the useful part
+fn first_array_difference(left: &NbtTag, right: &NbtTag) -> Option<usize> {
+    fn first<T: PartialEq>(left: &[T], right: &[T]) -> usize {
+        left.iter()
+            .zip(right)
+            .position(|(left, right)| left != right)
+            .unwrap_or(left.len().min(right.len()))
+    }
+    match (left, right) {
+        (NbtTag::ByteArray(left), NbtTag::ByteArray(right)) => Some(first(left, right)),
+        (NbtTag::IntArray(left), NbtTag::IntArray(right)) => Some(first(left, right)),
+        (NbtTag::LongArray(left), NbtTag::LongArray(right)) => Some(first(left, right)),
+        _ => None,
+    }
+}
+
+/// A short description of the value, arrays and nested tags are summarized
+fn describe(tag: &NbtTag) -> String {
+    match tag {
+        NbtTag::End => "end".to_string(),
+        NbtTag::Byte(value) => format!("{value}b"),
+        NbtTag::Short(value) => format!("{value}s"),
+        NbtTag::Int(value) => value.to_string(),
+        NbtTag::Long(value) => format!("{value}L"),
+        NbtTag::Float(value) => format!("{value}f"),
+        NbtTag::Double(value) => format!("{value}d"),
+        NbtTag::String(value) => value.clone(),
+        NbtTag::ByteArray(values) => format!("[{} bytes]", values.len()),
+        NbtTag::IntArray(values) => format!("[{} ints]", values.len()),
+        NbtTag::LongArray(values) => format!("[{} longs]", values.len()),
+        NbtTag::List(values) => format!("[{} entries]", values.len()),
+        NbtTag::Compound(compound) => format!("{{{} fields}}", compound.child_tags.len()),
+    }
+}
+
+fn block_name(state_id: u16) -> String {
+    Block::from_state_id(state_id).map_or_else(
+        || format!("unknown state {state_id}"),
+        |block| format!("{} ({state_id})", block.name),
+    )
+}
+
+fn value(value: Option<&String>) -> &str {
+    value.map_or("<missing>", String::as_str)
+}
+
+impl fmt::Display for ChunkDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Chunk {} {}:", self.position.x, self.position.z)?;
+        if self.is_empty() {
+            return writeln!(f, "  identical");
+        }
+        for error in &self.errors {
+            writeln!(f, "  error: {error}")?;
+        }
+        if self.differing_blocks > 0 {
+            writeln!(f, "  {} blocks differ:", self.differing_blocks)?;
+            for block in &self.blocks {
+                writeln!(
+                    f,
+                    "    {} {} {}: {} -> {}",
+                    block.x,
+                    block.y,
+                    block.z,
+                    block_name(block.left),
+                    block_name(block.right)
+                )?;
+            }
+            if self.differing_blocks > self.blocks.len() {
+                writeln!(
+                    f,
+                    "    ... and {} more",
+                    self.differing_blocks - self.blocks.len()
+                )?;
+            }
+        }
+        for palette in &self.palettes {
+            writeln!(f, "  palette of section {}:", palette.section_y)?;
+            for entry in &palette.only_left {
+                writeln!(f, "    - {entry}")?;
+            }
+            for entry in &palette.only_right {
+                writeln!(f, "    + {entry}")?;
+            }
+        }
+        for field in &self.nbt {
+            writeln!(
+                f,
+                "  {}: {} -> {}",
+                field.path,
+                value(field.left.as_ref()),
+                value(field.right.as_ref())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RegionDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.chunks.is_empty() {
+            return writeln!(f, "The regions are identical");
+        }
+        let local = |index: usize| (index % REGION_SIZE, index / REGION_SIZE);
+        for chunk in &self.chunks {
+            match chunk {
+                RegionChunkDiff::OnlyLeft(index) => {
+                    let (x, z) = local(*index);
+                    writeln!(f, "Chunk {x} {z} (in region): only in the left region")?;
+                }
+                RegionChunkDiff::OnlyRight(index) => {
+                    let (x, z) = local(*index);
+                    writeln!(f, "Chunk {x} {z} (in region): only in the right region")?;
+                }
+                RegionChunkDiff::Unreadable { index, error } => {
+                    let (x, z) = local(*index);
+                    writeln!(f, "Chunk {x} {z} (in region): unreadable, {error}")?;
+                }
+                RegionChunkDiff::Different(diff) => write!(f, "{diff}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(y: i8, palette: &[&str], biome: &str) -> NbtTag {
+        let mut block_states = NbtCompound::new();
+        block_states.put_list(
+            "palette",
+            palette
+                .iter()
+                .map(|name| {
+                    let mut entry = NbtCompound::new();
+                    entry.put_string("Name", (*name).to_string());
+                    NbtTag::Compound(entry)
+                })
+                .collect(),
+        );
+        let mut biomes = NbtCompound::new();
+        biomes.put_list("palette", Box::new([NbtTag::String(biome.to_string())]));
+
+        let mut section = NbtCompound::new();
+        section.put_byte("Y", y);
+        section.put_component("block_states", block_states);
+        section.put_component("biomes", biomes);
+        NbtTag::Compound(section)
+    }
+
+    fn chunk(sections: Vec<NbtTag>, heightmap: &[i64]) -> Vec<u8> {
+        let mut heightmaps = NbtCompound::new();
+        heightmaps.put("WORLD_SURFACE", NbtTag::LongArray(heightmap.into()));
+
+        let mut chunk = NbtCompound::new();
+        chunk.put_int("xPos", 2);
+        chunk.put_int("zPos", -1);
+        chunk.put_list("sections", sections.into_boxed_slice());
+        chunk.put_component("Heightmaps", heightmaps);
+        Nbt::new(String::new(), chunk).write().to_vec()
+    }
+
+    #[test]
+    fn nbt_and_palettes() {
+        let left = chunk(
+            vec![section(
+                0,
+                &["minecraft:air", "minecraft:stone"],
+                "minecraft:plains",
+            )],
+            &[1, 2, 3],
+        );
+        let right = chunk(
+            vec![
+                section(
+                    0,
+                    &["minecraft:stone", "minecraft:dirt"],
+                    "minecraft:desert",
+                ),
+                section(1, &["minecraft:air"], "minecraft:desert"),
+            ],
+            &[1, 2, 4],
+        );
+        let diff = diff_chunks(&left, &right).unwrap();
+        assert_eq!(diff.position, Vector2::new(2, -1));
+
+        let palettes = diff
+            .palettes
+            .iter()
+            .map(|palette| {
+                (
+                    palette.section_y,
+                    palette.only_left.clone(),
+                    palette.only_right.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            palettes,
+            [
+                (
+                    0,
+                    vec!["minecraft:air".into()],
+                    vec!["minecraft:dirt".into()]
+                ),
+                (1, vec![], vec!["minecraft:air".into()])
+            ]
+        );
+
+        let fields = diff
+            .nbt
+            .iter()
+            .map(|field| {
+                (
+                    field.path.as_str(),
+                    field.left.as_deref(),
+                    field.right.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                (
+                    "sections[0].biomes.palette[0]",
+                    Some("minecraft:plains"),
+                    Some("minecraft:desert")
+                ),
+                ("sections[1]", None, Some("{3 fields}")),
+                (
+                    "Heightmaps.WORLD_SURFACE[2]",
+                    Some("[3 longs]"),
+                    Some("[3 longs]")
+                ),
+            ]
+        );
+        // Neither is a full chunk, so blocks can't be compared
+        assert_eq!(diff.errors.len(), 2);
+    }
+}
@@ -0,0 +1,120 @@
+use std::{path::Path, sync::Arc};
+
+use pumpkin_nbt::{compound::NbtCompound, from_bytes, nbt_int_array, serializer::to_bytes};
+use pumpkin_util::math::vector2::Vector2;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::level::LevelFolder;
+
+use super::{
+    ChunkParsingError, ChunkSerializingError, format::anvil::WORLD_DATA_VERSION, io::RegionData,
+};
+
+pub type SyncEntityChunk = Arc<RwLock<ChunkEntityData>>;
+
+/// The entities saved in a chunk, they are kept in the region files of the `entities` folder
+#[derive(Clone)]
+pub struct ChunkEntityData {
+    pub position: Vector2<i32>,
+    /// Every entity as it was saved, including its `id`
+    pub entities: Vec<NbtCompound>,
+    pub dirty: bool,
+}
+
+impl ChunkEntityData {
+    pub fn new(position: Vector2<i32>, entities: Vec<NbtCompound>) -> Self {
+        Self {
+            position,
+            entities,
+            dirty: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EntityChunkNbt {
+    data_version: i32,
+    #[serde(serialize_with = "nbt_int_array")]
+    position: Vec<i32>,
+    #[serde(default)]
+    entities: Vec<NbtCompound>,
+}
+
+impl RegionData for ChunkEntityData {
+    fn region_folder(folder: &LevelFolder) -> &Path {
+        &folder.entities_folder
+    }
+
+    fn position(&self) -> Vector2<i32> {
+        self.position
+    }
+
+    fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, ChunkSerializingError> {
+        let nbt = EntityChunkNbt {
+            data_version: WORLD_DATA_VERSION,
+            position: vec![self.position.x, self.position.z],
+            entities: self.entities.clone(),
+        };
+
+        let mut result = Vec::new();
+        to_bytes(&nbt, &mut result).map_err(ChunkSerializingError::ErrorSerializingChunk)?;
+        Ok(result)
+    }
+
+    fn from_bytes(bytes: &[u8], position: Vector2<i32>) -> Result<Self, ChunkParsingError> {
+        let nbt = from_bytes::<EntityChunkNbt>(bytes)
+            .map_err(|e| ChunkParsingError::ErrorDeserializingChunk(e.to_string()))?;
+
+        if nbt.position[..] != [position.x, position.z] {
+            return Err(ChunkParsingError::ErrorDeserializingChunk(format!(
+                "Expected entities for chunk {},{} but got them for {:?}!",
+                position.x, position.z, nbt.position,
+            )));
+        }
+
+        Ok(Self {
+            position,
+            entities: nbt.entities,
+            dirty: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::ChunkEntityData;
+    use crate::chunk::io::RegionData;
+
+    #[test]
+    fn entities_roundtrip() {
+        let mut zombie = NbtCompound::new();
+        zombie.put_string("id", "minecraft:zombie".to_string());
+        zombie.put_float("Health", 12.0);
+        let data = ChunkEntityData::new(Vector2::new(-4, 7), vec![zombie]);
+
+        let bytes = data.to_bytes().unwrap();
+        let read = ChunkEntityData::from_bytes(&bytes, data.position).unwrap();
+        assert_eq!(read.entities.len(), 1);
+        assert_eq!(
+            read.entities[0].get_string("id").map(String::as_str),
+            Some("minecraft:zombie")
+        );
+        assert_eq!(read.entities[0].get_float("Health"), Some(12.0));
+        assert!(!read.dirty);
+
+        assert!(ChunkEntityData::from_bytes(&bytes, Vector2::new(0, 0)).is_err());
+    }
+}
@@ -11,6 +11,7 @@ use pumpkin_util::math::vector2::Vector2;
 use std::{
     collections::{HashMap, HashSet},
     io::{Read, SeekFrom, Write},
+    marker::PhantomData,
     path::{Path, PathBuf},
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -19,12 +20,16 @@ use tokio::{
     sync::Mutex,
 };
 
+use crate::WORLD_LOWEST_Y;
 use crate::chunk::{
     ChunkData, ChunkReadingError, ChunkSerializingError, ChunkWritingError, CompressionError,
-    io::{ChunkSerializer, LoadedData},
+    LIGHT_SECTIONS_COUNT,
+    io::{ChunkSerializer, LoadedData, RegionData, serialization_pool::serialization_pool},
 };
 
-use super::{ChunkNbt, ChunkSection, ChunkSectionBlockStates, PaletteEntry};
+use super::{
+    ChunkNbt, ChunkSection, ChunkSectionBiomes, ChunkSectionBlockStates, PaletteEntry, codec,
+};
 
 /// The side size of a region in chunks (one region is 32x32 chunks)
 pub const REGION_SIZE: usize = 32;
@@ -41,7 +46,7 @@ pub const CHUNK_COUNT: usize = REGION_SIZE * REGION_SIZE;
 const SECTOR_BYTES: usize = 4096;
 
 // 1.21.4
-const WORLD_DATA_VERSION: i32 = 4189;
+pub(crate) const WORLD_DATA_VERSION: i32 = 4189;
 
 #[derive(Clone, Default)]
 pub struct AnvilChunkFormat;
@@ -114,10 +119,13 @@ struct AnvilChunkMetadata {
     file_sector_offset: u32,
 }
 
-pub struct AnvilChunkFile {
+/// A region file in the anvil format. Besides the chunks themselves, vanilla keeps other data
+/// of chunks in region files like these, like their entities
+pub struct AnvilChunkFile<D = ChunkData> {
     chunks_data: [Option<AnvilChunkMetadata>; CHUNK_COUNT],
     end_sector: u32,
     write_action: Mutex<WriteAction>,
+    data: PhantomData<D>,
 }
 
 impl Compression {
@@ -154,7 +162,7 @@ impl Compression {
                     .map_err(CompressionError::LZ4Error)?;
                 Ok(decompressed_data.into_boxed_slice())
             }
-            Compression::Custom => todo!(),
+            Compression::Custom => codec::decompress(compressed_data),
         }
     }
 
@@ -201,7 +209,11 @@ impl Compression {
                 }
                 Ok(compressed_data)
             }
-            Compression::Custom => todo!(),
+            Compression::Custom => codec::compress(
+                advanced_config().chunk.compression.custom_codec(),
+                uncompressed_data,
+                compression_level,
+            ),
         }
     }
 
@@ -228,7 +240,8 @@ impl From<pumpkin_config::chunk::Compression> for Compression {
             pumpkin_config::chunk::Compression::GZip => Self::GZip,
             pumpkin_config::chunk::Compression::ZLib => Self::ZLib,
             pumpkin_config::chunk::Compression::LZ4 => Self::LZ4,
-            pumpkin_config::chunk::Compression::Custom => Self::Custom,
+            pumpkin_config::chunk::Compression::Custom
+            | pumpkin_config::chunk::Compression::Zstd => Self::Custom,
         }
     }
 }
@@ -289,26 +302,27 @@ impl AnvilChunkData {
         Ok(())
     }
 
-    fn to_chunk(&self, pos: Vector2<i32>) -> Result<ChunkData, ChunkReadingError> {
+    fn to_chunk<D: RegionData>(&self, pos: Vector2<i32>) -> Result<D, ChunkReadingError> {
         let chunk = if let Some(compression) = self.compression {
             let decompress_bytes = compression
                 .decompress_data(&self.compressed_data)
                 .map_err(ChunkReadingError::Compression)?;
 
-            ChunkData::from_bytes(&decompress_bytes, pos)
+            D::from_bytes(&decompress_bytes, pos)
         } else {
-            ChunkData::from_bytes(&self.compressed_data, pos)
+            D::from_bytes(&self.compressed_data, pos)
         }
         .map_err(ChunkReadingError::ParsingError)?;
 
         Ok(chunk)
     }
 
-    fn from_chunk(
-        chunk: &ChunkData,
+    fn from_chunk<D: RegionData>(
+        chunk: &D,
         compression: Option<Compression>,
     ) -> Result<Self, ChunkWritingError> {
-        let raw_bytes = chunk_to_bytes(chunk)
+        let raw_bytes = chunk
+            .to_bytes()
             .map_err(|err| ChunkWritingError::ChunkSerializingError(err.to_string()))?;
 
         let compression = compression
@@ -326,7 +340,7 @@ impl AnvilChunkData {
     }
 }
 
-impl AnvilChunkFile {
+impl<D: RegionData> AnvilChunkFile<D> {
     pub const fn get_region_coords(at: &Vector2<i32>) -> (i32, i32) {
         // Divide by 32 for the region coordinates
         (at.x >> SUBREGION_BITS, at.z >> SUBREGION_BITS)
@@ -489,22 +503,76 @@ impl AnvilChunkFile {
         log::trace!("Wrote file to Disk: {:?}", path);
         Ok(())
     }
+
+    /// The NBT of the chunk at the index as stored in the file, decompressed
+    pub fn raw_chunk(&self, index: usize) -> Option<Result<Box<[u8]>, CompressionError>> {
+        let chunk = &self.chunks_data[index].as_ref()?.serialized_data;
+        Some(match chunk.compression {
+            Some(compression) => compression.decompress_data(&chunk.compressed_data),
+            None => Ok(chunk.compressed_data.to_vec().into_boxed_slice()),
+        })
+    }
+
+    /// Sectors between the chunks which no chunk uses anymore, left behind when chunks shrink
+    /// or move while writing in place
+    pub fn unused_sectors(&self) -> u32 {
+        let used_sectors: u32 = self
+            .chunks_data
+            .iter()
+            .flatten()
+            .map(|chunk| chunk.serialized_data.sector_count())
+            .sum();
+        self.end_sector.saturating_sub(2 + used_sectors)
+    }
+
+    /// Lays the chunks out back to back in the order `write_all` writes them and marks the whole
+    /// file to be rewritten. Returns how many sectors are no longer needed
+    fn compact_layout(&mut self) -> u32 {
+        let mut current_sector: u32 = 2;
+        for chunk in self.chunks_data.iter_mut().flatten() {
+            chunk.file_sector_offset = current_sector;
+            current_sector += chunk.serialized_data.sector_count();
+        }
+
+        let reclaimed_sectors = self.end_sector.saturating_sub(current_sector);
+        self.end_sector = current_sector;
+        *self.write_action.get_mut() = WriteAction::All;
+        reclaimed_sectors
+    }
+
+    /// Rewrites the region file at `path` with the chunks stored contiguously, returning by how
+    /// many bytes the file shrank
+    pub async fn defragment(&mut self, path: &Path) -> Result<u64, std::io::Error> {
+        let old_size = tokio::fs::metadata(path)
+            .await
+            .map_or(0, |metadata| metadata.len());
+
+        self.compact_layout();
+        self.write_all(path).await?;
+        *self.write_action.get_mut() = WriteAction::Pass;
+
+        let new_size = tokio::fs::metadata(path).await?.len();
+        let reclaimed = old_size.saturating_sub(new_size);
+        log::debug!("Defragmented {:?}, reclaimed {} bytes", path, reclaimed);
+        Ok(reclaimed)
+    }
 }
 
-impl Default for AnvilChunkFile {
+impl<D> Default for AnvilChunkFile<D> {
     fn default() -> Self {
         Self {
             chunks_data: [const { None }; CHUNK_COUNT],
             write_action: Mutex::new(WriteAction::Pass),
             // Two sectors for offset + timestamp
             end_sector: 2,
+            data: PhantomData,
         }
     }
 }
 
 #[async_trait]
-impl ChunkSerializer for AnvilChunkFile {
-    type Data = ChunkData;
+impl<D: RegionData> ChunkSerializer for AnvilChunkFile<D> {
+    type Data = D;
     type WriteBackend = PathBuf;
 
     fn should_write(&self, is_watched: bool) -> bool {
@@ -548,7 +616,7 @@ impl ChunkSerializer for AnvilChunkFile {
         let headers = raw_file_bytes.split_to(SECTOR_BYTES * 2);
         let (mut location_bytes, mut timestamp_bytes) = headers.split_at(SECTOR_BYTES);
 
-        let mut chunk_file = AnvilChunkFile::default();
+        let mut chunk_file = Self::default();
 
         let mut last_offset = 2;
         for i in 0..CHUNK_COUNT {
@@ -588,18 +656,21 @@ impl ChunkSerializer for AnvilChunkFile {
         Ok(chunk_file)
     }
 
-    async fn update_chunk(&mut self, chunk: &ChunkData) -> Result<(), ChunkWritingError> {
+    async fn update_chunk(&mut self, chunk: &D) -> Result<(), ChunkWritingError> {
         let epoch = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs() as u32;
 
-        let index = AnvilChunkFile::get_chunk_index(&chunk.position);
+        let index = Self::get_chunk_index(&chunk.position());
         // Default to the compression type read from the file
         let compression_type = self.chunks_data[index]
             .as_ref()
             .and_then(|chunk_data| chunk_data.serialized_data.compression);
-        let new_chunk_data = AnvilChunkData::from_chunk(chunk, compression_type)?;
+        let chunk = chunk.snapshot();
+        let new_chunk_data = serialization_pool()
+            .run(move || AnvilChunkData::from_chunk(&chunk, compression_type))
+            .await?;
 
         let mut write_action = self.write_action.lock().await;
         if !advanced_config().chunk.write_in_place {
@@ -760,13 +831,25 @@ impl ChunkSerializer for AnvilChunkFile {
             }
         }
 
+        // Writing the whole file always lays the chunks out contiguously
+        let compact = advanced_config().chunk.defragment_on_save
+            && !matches!(*write_action, WriteAction::All);
+        drop(write_action);
+        if compact && self.unused_sectors() > 0 {
+            let reclaimed_sectors = self.compact_layout();
+            log::debug!(
+                "Compacting region on save, reclaiming {} bytes",
+                reclaimed_sectors as usize * SECTOR_BYTES
+            );
+        }
+
         Ok(())
     }
 
     async fn get_chunks(
         &self,
         chunks: &[Vector2<i32>],
-        stream: tokio::sync::mpsc::Sender<LoadedData<ChunkData, ChunkReadingError>>,
+        stream: tokio::sync::mpsc::Sender<LoadedData<D, ChunkReadingError>>,
     ) {
         // Create an unbounded buffer so we don't block the rayon thread pool
         let (bridge_send, mut bridge_recv) = tokio::sync::mpsc::unbounded_channel();
@@ -774,7 +857,7 @@ impl ChunkSerializer for AnvilChunkFile {
         // Don't par iter here so we can prevent backpressure with the await in the async
         // runtime
         for chunk in chunks.iter().cloned() {
-            let index = AnvilChunkFile::get_chunk_index(&chunk);
+            let index = Self::get_chunk_index(&chunk);
             match &self.chunks_data[index] {
                 None => stream
                     .send(LoadedData::Missing(chunk))
@@ -808,11 +891,45 @@ impl ChunkSerializer for AnvilChunkFile {
                 .expect("Failed to send anvil chunks from bridge");
         }
     }
+
+    fn estimated_memory(&self) -> usize {
+        size_of::<Self>()
+            + self
+                .chunks_data
+                .iter()
+                .flatten()
+                .map(|chunk| chunk.serialized_data.compressed_data.len())
+                .sum::<usize>()
+    }
 }
 
 pub fn chunk_to_bytes(chunk_data: &ChunkData) -> Result<Vec<u8>, ChunkSerializingError> {
     let mut sections = Vec::new();
 
+    let light = &chunk_data.light;
+    let block_light = |index: usize| {
+        light.block_light[index]
+            .as_ref()
+            .map(|light| Box::from(&light[..]))
+    };
+    let sky_light = |index: usize| {
+        light.sky_light[index]
+            .as_ref()
+            .map(|light| Box::from(&light[..]))
+    };
+    // The sections below and above the world only hold light
+    let light_only = |index: usize| {
+        let section = ChunkSection {
+            y: index as i8 - 5,
+            block_states: None,
+            biomes: None,
+            block_light: block_light(index),
+            sky_light: sky_light(index),
+        };
+        (section.block_light.is_some() || section.sky_light.is_some()).then_some(section)
+    };
+    sections.extend(light_only(0));
+
     for (i, blocks) in chunk_data.subchunks.array_iter().enumerate() {
         // get unique blocks
         let unique_blocks: HashSet<_> = blocks.iter().collect();
@@ -873,7 +990,7 @@ pub fn chunk_to_bytes(chunk_data: &ChunkData) -> Result<Vec<u8>, ChunkSerializin
                 palette: palette
                     .into_iter()
                     .map(|entry| PaletteEntry {
-                        name: entry.1.0.to_string(),
+                        name: format!("minecraft:{}", entry.1.0),
                         properties: {
                             let block = Block::from_state_id(*entry.0).unwrap();
                             if let Some(properties) = block.properties(*entry.0) {
@@ -890,16 +1007,24 @@ pub fn chunk_to_bytes(chunk_data: &ChunkData) -> Result<Vec<u8>, ChunkSerializin
                     })
                     .collect(),
             }),
+            biomes: Some(ChunkSectionBiomes::from_biomes(
+                &chunk_data.biomes.section(i),
+            )),
+            block_light: block_light(i + 1),
+            sky_light: sky_light(i + 1),
         });
     }
+    sections.extend(light_only(LIGHT_SECTIONS_COUNT - 1));
 
     let nbt = ChunkNbt {
         data_version: WORLD_DATA_VERSION,
         x_pos: chunk_data.position.x,
+        y_pos: i32::from(WORLD_LOWEST_Y) / 16,
         z_pos: chunk_data.position.z,
         status: ChunkStatus::Full,
         heightmaps: chunk_data.heightmap.clone(),
         sections,
+        block_entities: chunk_data.block_entities.values().cloned().collect(),
     };
 
     let mut result = Vec::new();
@@ -910,17 +1035,32 @@ pub fn chunk_to_bytes(chunk_data: &ChunkData) -> Result<Vec<u8>, ChunkSerializin
 #[cfg(test)]
 mod tests {
     use pumpkin_config::{AdvancedConfiguration, advanced_config, override_config_for_testing};
-    use pumpkin_util::math::vector2::Vector2;
+    use pumpkin_data::chunk::Biome;
+    use pumpkin_nbt::{Nbt, compound::NbtCompound, deserializer::ReadAdaptor, tag::NbtTag};
+    use pumpkin_util::math::ceil_log2;
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+    use std::collections::HashMap;
     use std::fs;
     use std::path::PathBuf;
     use std::sync::Arc;
     use temp_dir::TempDir;
     use tokio::sync::RwLock;
 
-    use crate::chunk::format::anvil::AnvilChunkFile;
+    use crate::block::registry::get_block_by_state_id;
+    use crate::block::state::ChunkBlockState;
+    use crate::chunk::format::PaletteEntry;
+    use crate::chunk::format::anvil::{
+        AnvilChunkData, AnvilChunkFile, AnvilChunkMetadata, Compression, SECTOR_BYTES,
+        WORLD_DATA_VERSION, chunk_to_bytes,
+    };
     use crate::chunk::io::chunk_file_manager::ChunkFileManager;
-    use crate::chunk::io::{ChunkIO, LoadedData};
+    use crate::chunk::io::{ChunkIO, ChunkSerializer, LoadedData};
+    use crate::chunk::{
+        BIOME_SECTION_VOLUME, CHUNK_AREA, ChunkBiomes, ChunkData, ChunkHeightmaps, ChunkLight,
+        LIGHT_SECTION_SIZE, LIGHT_SECTIONS_COUNT, SUBCHUNK_VOLUME, SUBCHUNKS_COUNT, Subchunks,
+    };
     use crate::coordinates::ChunkRelativeBlockCoordinates;
+    use crate::dimension::Dimension;
     use crate::generation::{Seed, get_world_gen};
     use crate::level::{LevelFolder, SyncChunk};
 
@@ -969,6 +1109,7 @@ mod tests {
                 &LevelFolder {
                     root_folder: PathBuf::from(""),
                     region_folder: region_path,
+                    entities_folder: PathBuf::from(""),
                 },
                 &[Vector2::new(0, 0)],
                 send,
@@ -991,12 +1132,13 @@ mod tests {
 
         let _ = env_logger::try_init();
 
-        let generator = get_world_gen(Seed(0));
+        let generator = get_world_gen(Seed(0), Dimension::OverWorld);
 
         let temp_dir = TempDir::new().unwrap();
         let level_folder = LevelFolder {
             root_folder: temp_dir.path().to_path_buf(),
             region_folder: temp_dir.path().join("region"),
+            entities_folder: temp_dir.path().join("entities"),
         };
         fs::create_dir(&level_folder.region_folder).expect("couldn't create region folder");
         let chunk_saver = ChunkFileManager::<AnvilChunkFile>::default();
@@ -1196,12 +1338,13 @@ mod tests {
 
         let _ = env_logger::try_init();
 
-        let generator = get_world_gen(Seed(0));
+        let generator = get_world_gen(Seed(0), Dimension::OverWorld);
 
         let temp_dir = TempDir::new().unwrap();
         let level_folder = LevelFolder {
             root_folder: temp_dir.path().to_path_buf(),
             region_folder: temp_dir.path().join("region"),
+            entities_folder: temp_dir.path().join("entities"),
         };
         fs::create_dir(&level_folder.region_folder).expect("couldn't create region folder");
         let chunk_saver = ChunkFileManager::<AnvilChunkFile>::default();
@@ -1245,46 +1388,599 @@ mod tests {
         }
     }
 
-    // TODO
-    /*
-    #[test]
-    fn test_load_java_chunk() {
-        let temp_dir = TempDir::new().unwrap();
-        let level_folder = LevelFolder {
-            root_folder: temp_dir.path().to_path_buf(),
-            region_folder: temp_dir.path().join("region"),
-        };
+    /// Data versions of releases which save chunks in the layout from 1.18 on
+    const VANILLA_VERSIONS: [(&str, i32); 4] = [
+        ("1.18.2", 2975),
+        ("1.20.1", 3465),
+        ("1.21", 3953),
+        ("1.21.4", 4189),
+    ];
+
+    fn palette_entry(name: &str, properties: &[(&str, &str)]) -> NbtTag {
+        let mut entry = NbtCompound::new();
+        entry.put_string("Name", name.to_string());
+        if !properties.is_empty() {
+            let mut values = NbtCompound::new();
+            for (key, value) in properties {
+                values.put_string(key, (*value).to_string());
+            }
+            entry.put_component("Properties", values);
+        }
+        NbtTag::Compound(entry)
+    }
 
-        fs::create_dir(&level_folder.region_folder).unwrap();
-        fs::copy(
-            Path::new(env!("CARGO_MANIFEST_DIR"))
-                .parent()
-                .unwrap()
-                .join(file!())
-                .parent()
-                .unwrap()
-                .join("../../assets/r.0.0.mca"),
-            level_folder.region_folder.join("r.0.0.mca"),
-        )
-        .unwrap();
-
-        let mut actually_tested = false;
-        for x in 0..(1 << 5) {
-            for z in 0..(1 << 5) {
-                let result = AnvilChunkFormat {}.read_chunk(&level_folder, &Vector2 { x, z });
-
-                match result {
-                    Ok(_) => actually_tested = true,
-                    Err(ChunkReadingError::ParsingError(ChunkParsingError::ChunkNotGenerated)) => {}
-                    Err(ChunkReadingError::ChunkNotExist) => {}
-                    Err(e) => panic!("{:?}", e),
+    /// Packs palette indices the way vanilla does since 1.16, an index never spans two longs
+    fn pack(indices: &[usize], bits: usize) -> NbtTag {
+        let longs = indices
+            .chunks(64 / bits)
+            .map(|long| {
+                long.iter().enumerate().fold(0u64, |packed, (i, index)| {
+                    packed | (*index as u64) << (i * bits)
+                }) as i64
+            })
+            .collect::<Vec<_>>();
+        NbtTag::LongArray(longs.into_boxed_slice())
+    }
+
+    fn unpack(data: &[i64], bits: usize, len: usize) -> Vec<usize> {
+        let mask = (1u64 << bits) - 1;
+        data.iter()
+            .flat_map(|long| {
+                (0..64 / bits).map(move |i| ((*long as u64) >> (i * bits) & mask) as usize)
+            })
+            .take(len)
+            .collect()
+    }
+
+    fn container(palette: Vec<NbtTag>, data: Option<NbtTag>) -> NbtCompound {
+        let mut container = NbtCompound::new();
+        container.put_list("palette", palette.into_boxed_slice());
+        if let Some(data) = data {
+            container.put("data", data);
+        }
+        container
+    }
+
+    fn section(
+        y: i8,
+        block_states: Vec<NbtTag>,
+        data: Option<NbtTag>,
+        biomes: NbtCompound,
+    ) -> NbtTag {
+        let mut section = NbtCompound::new();
+        section.put_byte("Y", y);
+        section.put_component("block_states", container(block_states, data));
+        section.put_component("biomes", biomes);
+        NbtTag::Compound(section)
+    }
+
+    /// A chunk laid out like vanilla saves it: bedrock at the bottom, stone sections which are
+    /// saved without block data, grass with a chest on top and forest above the plains
+    fn vanilla_chunk(data_version: i32, position: Vector2<i32>) -> NbtCompound {
+        let plains = || container(vec![NbtTag::String("minecraft:plains".into())], None);
+
+        // Below the world only light is saved
+        let mut below = NbtCompound::new();
+        below.put_byte("Y", -5);
+        below.put(
+            "SkyLight",
+            NbtTag::ByteArray(vec![0; LIGHT_SECTION_SIZE].into_boxed_slice()),
+        );
+        let mut sections = vec![NbtTag::Compound(below)];
+
+        let bottom = (0..SUBCHUNK_VOLUME)
+            .map(|i| usize::from(i >= CHUNK_AREA))
+            .collect::<Vec<_>>();
+        sections.push(section(
+            -4,
+            vec![
+                palette_entry("minecraft:bedrock", &[]),
+                palette_entry("minecraft:stone", &[]),
+            ],
+            Some(pack(&bottom, 4)),
+            plains(),
+        ));
+        for y in -3..4 {
+            sections.push(section(
+                y,
+                vec![palette_entry("minecraft:stone", &[])],
+                None,
+                plains(),
+            ));
+        }
+        let surface = (0..SUBCHUNK_VOLUME)
+            .map(|i| match (i % 16, i / CHUNK_AREA, i / 16 % 16) {
+                (_, 0, _) => 0,
+                (_, 1, _) => 2,
+                (5, 2, 7) => 3,
+                _ => 1,
+            })
+            .collect::<Vec<_>>();
+        let forest_above = (0..BIOME_SECTION_VOLUME)
+            .map(|i| usize::from(i >= 32))
+            .collect::<Vec<_>>();
+        sections.push(section(
+            4,
+            vec![
+                palette_entry("minecraft:stone", &[]),
+                palette_entry("minecraft:air", &[]),
+                palette_entry("minecraft:grass_block", &[("snowy", "false")]),
+                palette_entry(
+                    "minecraft:chest",
+                    &[
+                        ("facing", "north"),
+                        ("type", "single"),
+                        ("waterlogged", "false"),
+                    ],
+                ),
+            ],
+            Some(pack(&surface, 4)),
+            container(
+                vec![
+                    NbtTag::String("minecraft:plains".into()),
+                    NbtTag::String("minecraft:forest".into()),
+                ],
+                Some(pack(&forest_above, 1)),
+            ),
+        ));
+        for y in 5..20 {
+            sections.push(section(
+                y,
+                vec![palette_entry("minecraft:air", &[])],
+                None,
+                plains(),
+            ));
+        }
+
+        let mut heightmaps = NbtCompound::new();
+        for name in [
+            "MOTION_BLOCKING",
+            "MOTION_BLOCKING_NO_LEAVES",
+            "OCEAN_FLOOR",
+            "WORLD_SURFACE",
+        ] {
+            heightmaps.put(name, NbtTag::LongArray(vec![0; 37].into_boxed_slice()));
+        }
+
+        let mut chest = NbtCompound::new();
+        chest.put_string("id", "minecraft:chest".to_string());
+        chest.put_int("x", position.x * 16 + 5);
+        chest.put_int("y", 66);
+        chest.put_int("z", position.z * 16 + 7);
+        chest.put_bool("keepPacked", false);
+        chest.put_list("Items", Box::new([]));
+
+        let mut structures = NbtCompound::new();
+        structures.put_component("References", NbtCompound::new());
+        structures.put_component("starts", NbtCompound::new());
+
+        let mut chunk = NbtCompound::new();
+        chunk.put_int("DataVersion", data_version);
+        chunk.put_int("xPos", position.x);
+        chunk.put_int("yPos", -4);
+        chunk.put_int("zPos", position.z);
+        chunk.put_string("Status", "minecraft:full".to_string());
+        chunk.put_long("LastUpdate", 52_000);
+        chunk.put_long("InhabitedTime", 0);
+        chunk.put_bool("isLightOn", true);
+        chunk.put_list("sections", sections.into_boxed_slice());
+        chunk.put_component("Heightmaps", heightmaps);
+        chunk.put_list(
+            "block_entities",
+            vec![NbtTag::Compound(chest)].into_boxed_slice(),
+        );
+        chunk.put_list("block_ticks", Box::new([]));
+        chunk.put_list("fluid_ticks", Box::new([]));
+        chunk.put_list(
+            "PostProcessing",
+            vec![NbtTag::List(Box::new([])); SUBCHUNKS_COUNT].into_boxed_slice(),
+        );
+        chunk.put_component("structures", structures);
+        chunk
+    }
+
+    fn read_nbt(bytes: &[u8]) -> NbtCompound {
+        Nbt::read(&mut ReadAdaptor::new(bytes)).unwrap().root_tag
+    }
+
+    /// Checks the parts of a saved chunk vanilla relies on when reading it
+    fn assert_vanilla_layout(nbt: &NbtCompound, position: Vector2<i32>) {
+        assert_eq!(nbt.get_int("DataVersion"), Some(WORLD_DATA_VERSION));
+        assert_eq!(nbt.get_int("xPos"), Some(position.x));
+        assert_eq!(nbt.get_int("yPos"), Some(-4));
+        assert_eq!(nbt.get_int("zPos"), Some(position.z));
+        assert_eq!(
+            nbt.get_string("Status").map(String::as_str),
+            Some("minecraft:full")
+        );
+
+        let heightmaps = nbt.get_compound("Heightmaps").unwrap();
+        for name in ["MOTION_BLOCKING", "WORLD_SURFACE"] {
+            assert_eq!(
+                heightmaps.get_long_array(name).map(<[i64]>::len),
+                Some(37),
+                "heightmap {name}"
+            );
+        }
+
+        let mut last_y = None;
+        for section in nbt.get_list("sections").unwrap().iter() {
+            let NbtTag::Compound(section) = section else {
+                panic!("sections have to be compounds");
+            };
+            let y = section.get_byte("Y").unwrap();
+            assert!(
+                (-5..=20).contains(&y),
+                "section {y} is outside of the world"
+            );
+            assert!(last_y < Some(y), "sections have to be sorted by Y");
+            last_y = Some(y);
+
+            if let Some(states) = section.get_compound("block_states") {
+                assert_container(states, SUBCHUNK_VOLUME, |len| ceil_log2(len).max(4));
+            }
+            if let Some(biomes) = section.get_compound("biomes") {
+                assert_container(biomes, BIOME_SECTION_VOLUME, ceil_log2);
+            }
+            for light in ["BlockLight", "SkyLight"] {
+                if let Some(NbtTag::ByteArray(light)) = section.get(light) {
+                    assert_eq!(light.len(), LIGHT_SECTION_SIZE);
                 }
+            }
+        }
 
-                println!("=========== OK ===========");
+        for block_entity in nbt.get_list("block_entities").unwrap().iter() {
+            let NbtTag::Compound(block_entity) = block_entity else {
+                panic!("block entities have to be compounds");
+            };
+            assert!(block_entity.get_string("id").is_some());
+            for axis in ["x", "y", "z"] {
+                assert!(block_entity.get_int(axis).is_some(), "block entity {axis}");
             }
         }
+    }
+
+    /// Palettes are never empty and the data of palettes with more than one entry fills the
+    /// longs vanilla expects with indices into the palette
+    fn assert_container(container: &NbtCompound, entries: usize, bits: impl Fn(u32) -> u8) {
+        let palette_len = container.get_list("palette").unwrap().len();
+        assert!(palette_len > 0, "empty palette");
+        let Some(data) = container.get_long_array("data") else {
+            assert_eq!(palette_len, 1, "data is only left out for a single entry");
+            return;
+        };
+        if palette_len == 1 {
+            // Vanilla ignores it
+            return;
+        }
+        let bits = bits(palette_len as u32) as usize;
+        assert_eq!(data.len(), entries.div_ceil(64 / bits));
+        assert!(
+            unpack(data, bits, entries)
+                .into_iter()
+                .all(|index| index < palette_len)
+        );
+    }
+
+    #[test]
+    fn load_vanilla_chunks() {
+        let position = Vector2::new(-3, 12);
+        let expected_chest = ChunkBlockState::from_palette(&PaletteEntry {
+            name: "minecraft:chest".to_string(),
+            properties: Some(HashMap::from(
+                [
+                    ("facing", "north"),
+                    ("type", "single"),
+                    ("waterlogged", "false"),
+                ]
+                .map(|(key, value)| (key.to_string(), value.to_string())),
+            )),
+        });
+
+        for (version, data_version) in VANILLA_VERSIONS {
+            let bytes = Nbt::new(String::new(), vanilla_chunk(data_version, position)).write();
+            let chunk = ChunkData::from_bytes(&bytes, position)
+                .unwrap_or_else(|err| panic!("{version}: {err}"));
+
+            let block = |x, y, z| {
+                chunk
+                    .get_block(Vector3::new(x, y, z).into())
+                    .and_then(get_block_by_state_id)
+                    .map(|block| block.name)
+            };
+            assert_eq!(block(0, -64, 0), Some("bedrock"), "{version}");
+            assert_eq!(block(0, -63, 0), Some("stone"), "{version}");
+            // Sections which are all stone come without block data
+            assert_eq!(block(3, -30, 3), Some("stone"), "{version}");
+            assert_eq!(block(15, 63, 15), Some("stone"), "{version}");
+            assert_eq!(block(1, 65, 1), Some("grass_block"), "{version}");
+            assert_eq!(block(5, 67, 7), Some("air"), "{version}");
+            assert_eq!(block(8, 200, 8), Some("air"), "{version}");
+            assert_eq!(
+                chunk.get_block(Vector3::new(5, 66, 7).into()),
+                Some(expected_chest.state_id),
+                "{version}"
+            );
+
+            assert_eq!(
+                chunk.biomes.get_biome(Vector3::new(0, 64, 0).into()),
+                Biome::Plains
+            );
+            assert_eq!(
+                chunk.biomes.get_biome(Vector3::new(0, 72, 0).into()),
+                Biome::Forest
+            );
+            assert_eq!(
+                chunk.biomes.get_biome(Vector3::new(0, -64, 0).into()),
+                Biome::Plains
+            );
+
+            let chest = BlockPos(Vector3::new(position.x * 16 + 5, 66, position.z * 16 + 7));
+            assert!(chunk.get_block_entity(&chest).is_some(), "{version}");
+        }
+    }
+
+    #[test]
+    fn resave_vanilla_chunks() {
+        let position = Vector2::new(7, -1);
+        for (version, data_version) in VANILLA_VERSIONS {
+            let original = vanilla_chunk(data_version, position);
+            let bytes = Nbt::new(String::new(), original.clone()).write();
+            let chunk = ChunkData::from_bytes(&bytes, position).unwrap();
+
+            let saved = chunk_to_bytes(&chunk).unwrap();
+            let saved_nbt = read_nbt(&saved);
+            assert_vanilla_layout(&saved_nbt, position);
+            assert_same_structure(&original, &saved_nbt, position);
+
+            let read = ChunkData::from_bytes(&saved, position).unwrap();
+            assert_eq!(read.subchunks, chunk.subchunks, "{version}");
+            assert_eq!(read.biomes, chunk.biomes, "{version}");
+            assert_eq!(read.block_entities, chunk.block_entities, "{version}");
+        }
+    }
+
+    #[test]
+    fn reject_chunks_from_before_1_18() {
+        let mut level = NbtCompound::new();
+        level.put_int("xPos", 0);
+        level.put_int("zPos", 0);
+        level.put_string("Status", "full".to_string());
+        let mut chunk = NbtCompound::new();
+        chunk.put_int("DataVersion", 2586);
+        chunk.put_component("Level", level);
+
+        let bytes = Nbt::new(String::new(), chunk).write();
+        assert!(ChunkData::from_bytes(&bytes, Vector2::new(0, 0)).is_err());
+    }
+
+    /// The block entities of a chunk by their id and position
+    fn block_entities(nbt: &NbtCompound) -> Vec<(String, i32, i32, i32)> {
+        let mut block_entities = nbt
+            .get_list("block_entities")
+            .into_iter()
+            .flat_map(|list| list.iter())
+            .filter_map(|block_entity| match block_entity {
+                NbtTag::Compound(block_entity) => Some((
+                    block_entity.get_string("id")?.clone(),
+                    block_entity.get_int("x")?,
+                    block_entity.get_int("y")?,
+                    block_entity.get_int("z")?,
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        block_entities.sort();
+        block_entities
+    }
+
+    /// The names in the palettes of the sections, by the Y of the section
+    fn palette_names(nbt: &NbtCompound, container: &str) -> HashMap<i8, Vec<String>> {
+        nbt.get_list("sections")
+            .into_iter()
+            .flat_map(|sections| sections.iter())
+            .filter_map(|section| {
+                let NbtTag::Compound(section) = section else {
+                    return None;
+                };
+                let palette = section.get_compound(container)?.get_list("palette")?;
+                let names = palette
+                    .iter()
+                    .filter_map(|entry| match entry {
+                        NbtTag::Compound(entry) => entry.get_string("Name").cloned(),
+                        NbtTag::String(name) => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                Some((section.get_byte("Y")?, names))
+            })
+            .collect()
+    }
+
+    /// Checks that saving a chunk read from vanilla kept what vanilla saved: the same sections
+    /// with only blocks and biomes from the original palettes, and the same block entities
+    fn assert_same_structure(original: &NbtCompound, saved: &NbtCompound, position: Vector2<i32>) {
+        assert_eq!(
+            saved.get_int("yPos"),
+            original.get_int("yPos"),
+            "{position:?}"
+        );
+        for container in ["block_states", "biomes"] {
+            let original = palette_names(original, container);
+            let saved = palette_names(saved, container);
+            let mut original_sections = original.keys().collect::<Vec<_>>();
+            let mut saved_sections = saved.keys().collect::<Vec<_>>();
+            original_sections.sort();
+            saved_sections.sort();
+            assert_eq!(
+                saved_sections, original_sections,
+                "{position:?}: sections with {container}"
+            );
+            for (y, names) in &saved {
+                // Entries no block uses may be left out, but nothing may be added
+                assert!(
+                    names.iter().all(|name| original[y].contains(name)),
+                    "{position:?}: {container} of section {y}"
+                );
+            }
+        }
+        assert_eq!(
+            block_entities(saved),
+            block_entities(original),
+            "{position:?}: block entities"
+        );
+    }
+
+    #[tokio::test]
+    async fn defragment() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("r.0.0.mca");
+
+        let chunk = |byte: u8, len: usize, file_sector_offset: u32| {
+            Some(AnvilChunkMetadata {
+                serialized_data: AnvilChunkData {
+                    compression: Some(Compression::ZLib),
+                    compressed_data: vec![byte; len].into(),
+                },
+                timestamp: 0,
+                file_sector_offset,
+            })
+        };
+        // Gaps at sectors 3 and 5 to 9
+        let mut region = <AnvilChunkFile>::default();
+        region.chunks_data[0] = chunk(1, 100, 2);
+        region.chunks_data[1] = chunk(2, 5000, 10);
+        region.chunks_data[5] = chunk(3, 100, 4);
+        region.end_sector = 12;
+        region.write_indices(&path, &[0, 1, 5]).await.unwrap();
+
+        let mut region = <AnvilChunkFile>::read(fs::read(&path).unwrap().into()).unwrap();
+        assert_eq!(region.unused_sectors(), 6);
+        assert_eq!(
+            region.defragment(&path).await.unwrap(),
+            6 * SECTOR_BYTES as u64
+        );
+
+        let region = <AnvilChunkFile>::read(fs::read(&path).unwrap().into()).unwrap();
+        assert_eq!(region.unused_sectors(), 0);
+        let offsets = [0, 1, 5].map(|index| {
+            let chunk = region.chunks_data[index].as_ref().unwrap();
+            (
+                chunk.file_sector_offset,
+                chunk.serialized_data.compressed_data[0],
+            )
+        });
+        assert_eq!(offsets, [(2, 1), (3, 2), (5, 3)]);
+    }
+
+    #[test]
+    fn zstd_roundtrip() {
+        let mut config = AdvancedConfiguration::default();
+        config.chunk.compression.algorithm = pumpkin_config::chunk::Compression::Zstd;
+        // Only used by the `Custom` algorithm
+        config.chunk.compression.custom_algorithm = "test:missing".to_string();
+        override_config_for_testing(config);
+
+        let mut chunk = ChunkData {
+            subchunks: Subchunks::Single(0),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: ChunkBiomes::default(),
+            light: ChunkLight::default(),
+            block_entities: HashMap::new(),
+            position: Vector2::new(7, -5),
+            dirty: false,
+        };
+        let block = Vector3::new(1, 80, 2).into();
+        chunk.set_block_light(block, 9);
+
+        let data = AnvilChunkData::from_chunk(&chunk, None).unwrap();
+        assert_eq!(data.compression, Some(Compression::Custom));
+        assert_eq!(&data.compressed_data[..14], b"\x00\x0cpumpkin:zstd");
+
+        let read: ChunkData = data.to_chunk(chunk.position).unwrap();
+        assert_eq!(read.light, chunk.light);
+        assert_eq!(read.get_block_light(block), Some(9));
+    }
+
+    #[test]
+    fn light_roundtrip() {
+        let mut chunk = ChunkData {
+            subchunks: Subchunks::Single(0),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: ChunkBiomes::default(),
+            light: ChunkLight::default(),
+            block_entities: HashMap::new(),
+            position: Vector2::new(3, -2),
+            dirty: false,
+        };
+        let block = Vector3::new(4, 70, 9).into();
+        let neighbour = Vector3::new(5, 70, 9).into();
+        chunk.set_block_light(block, 14);
+        chunk.set_sky_light(block, 7);
+        chunk.set_sky_light(neighbour, 15);
+        // The section above the world
+        chunk.light.sky_light[LIGHT_SECTIONS_COUNT - 1] =
+            Some(Arc::new([0xFF; LIGHT_SECTION_SIZE]));
+
+        let bytes = chunk_to_bytes(&chunk).unwrap();
+        let read = ChunkData::from_bytes(&bytes, chunk.position).unwrap();
+        assert_eq!(read.light, chunk.light);
+        assert_eq!(read.get_block_light(block), Some(14));
+        assert_eq!(read.get_block_light(neighbour), Some(0));
+        assert_eq!(read.get_sky_light(block), Some(7));
+        assert_eq!(read.get_sky_light(neighbour), Some(15));
+        assert_eq!(read.get_sky_light(Vector3::new(4, -64, 9).into()), None);
+    }
+
+    #[test]
+    fn block_entity_roundtrip() {
+        let mut chunk = ChunkData {
+            subchunks: Subchunks::Single(0),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: ChunkBiomes::default(),
+            light: ChunkLight::default(),
+            block_entities: HashMap::new(),
+            position: Vector2::new(-1, 4),
+            dirty: false,
+        };
+        let position = BlockPos(Vector3::new(-3, 64, 70));
+        let mut item = NbtCompound::new();
+        item.put_byte("Slot", 13);
+        item.put_string("id", "minecraft:diamond".to_string());
+        item.put_int("count", 5);
+        let mut nbt = NbtCompound::new();
+        nbt.put_list("Items", vec![NbtTag::Compound(item)].into_boxed_slice());
+        chunk.set_block_entity("minecraft:chest", position, nbt);
+
+        let bytes = chunk_to_bytes(&chunk).unwrap();
+        let read = ChunkData::from_bytes(&bytes, chunk.position).unwrap();
+        assert_eq!(read.block_entities, chunk.block_entities);
+        let chest = read.get_block_entity(&position).unwrap();
+        assert_eq!(chest.get_string("id").unwrap(), "minecraft:chest");
+        assert_eq!(chest.get_int("x"), Some(-3));
+    }
+
+    #[test]
+    fn drop_block_entities_of_other_chunks() {
+        let mut chunk = ChunkData {
+            subchunks: Subchunks::Single(0),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: ChunkBiomes::default(),
+            light: ChunkLight::default(),
+            block_entities: HashMap::new(),
+            position: Vector2::new(2, 2),
+            dirty: false,
+        };
+        let inside = BlockPos(Vector3::new(40, 10, 47));
+        chunk.set_block_entity("minecraft:furnace", inside, NbtCompound::new());
+        chunk.set_block_entity(
+            "minecraft:furnace",
+            BlockPos(Vector3::new(48, 10, 47)),
+            NbtCompound::new(),
+        );
 
-        assert!(actually_tested);
+        let bytes = chunk_to_bytes(&chunk).unwrap();
+        let read = ChunkData::from_bytes(&bytes, chunk.position).unwrap();
+        assert_eq!(read.block_entities.keys().collect::<Vec<_>>(), [&inside]);
     }
-    */
 }
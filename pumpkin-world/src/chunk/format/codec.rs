@@ -0,0 +1,152 @@
+//! Codecs for chunks saved with the custom compression of Anvil (since 24w05a). The data of such
+//! a chunk starts with the namespaced id of the algorithm, prefixed by its length as an unsigned
+//! short, followed by whatever that algorithm produced.
+//!
+//! Only `pumpkin:zstd` is known out of the box, plugins can add others with [`register_codec`].
+
+use std::{
+    collections::HashMap,
+    io::{self, ErrorKind},
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use bytes::{Buf, BufMut};
+
+use crate::chunk::CompressionError;
+
+/// The id of the zstd codec which is always registered
+pub const ZSTD_CODEC: &str = "pumpkin:zstd";
+
+/// A compression algorithm for the custom compression
+pub trait CompressionCodec: Send + Sync {
+    /// `level` is the one from the chunk config, codecs without levels can ignore it
+    fn compress(&self, data: &[u8], level: u32) -> io::Result<Vec<u8>>;
+
+    fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>>;
+}
+
+struct Zstd;
+
+impl CompressionCodec for Zstd {
+    fn compress(&self, data: &[u8], level: u32) -> io::Result<Vec<u8>> {
+        zstd::encode_all(data, level as i32)
+    }
+
+    fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        zstd::decode_all(data)
+    }
+}
+
+type Codecs = HashMap<String, Arc<dyn CompressionCodec>>;
+
+static CODECS: LazyLock<RwLock<Codecs>> = LazyLock::new(|| {
+    let mut codecs: Codecs = HashMap::new();
+    codecs.insert(ZSTD_CODEC.to_string(), Arc::new(Zstd));
+    RwLock::new(codecs)
+});
+
+/// Makes chunks using the id readable and lets the chunk config pick it, replacing any codec
+/// registered with the same id before
+pub fn register_codec(id: impl Into<String>, codec: Arc<dyn CompressionCodec>) {
+    CODECS
+        .write()
+        .expect("The codec registry was poisoned")
+        .insert(id.into(), codec);
+}
+
+pub fn get_codec(id: &str) -> Option<Arc<dyn CompressionCodec>> {
+    CODECS
+        .read()
+        .expect("The codec registry was poisoned")
+        .get(id)
+        .cloned()
+}
+
+pub(crate) fn compress(id: &str, data: &[u8], level: u32) -> Result<Vec<u8>, CompressionError> {
+    let codec = get_codec(id).ok_or_else(|| CompressionError::UnknownCodec(id.to_string()))?;
+    let id_length = u16::try_from(id.len()).map_err(|_| {
+        CompressionError::CustomError(io::Error::new(
+            ErrorKind::InvalidInput,
+            "The codec id is too long",
+        ))
+    })?;
+
+    let compressed = codec
+        .compress(data, level)
+        .map_err(CompressionError::CustomError)?;
+    let mut result = Vec::with_capacity(2 + id.len() + compressed.len());
+    result.put_u16(id_length);
+    result.put_slice(id.as_bytes());
+    result.extend_from_slice(&compressed);
+    Ok(result)
+}
+
+pub(crate) fn decompress(mut data: &[u8]) -> Result<Box<[u8]>, CompressionError> {
+    let invalid =
+        |message| CompressionError::CustomError(io::Error::new(ErrorKind::InvalidData, message));
+
+    let id_length = data
+        .try_get_u16()
+        .map_err(|_| invalid("Missing the length of the codec id"))?;
+    let Some((id, data)) = data.split_at_checked(id_length as usize) else {
+        return Err(invalid("The codec id is cut off"));
+    };
+    let id = std::str::from_utf8(id).map_err(|_| invalid("The codec id is not valid UTF-8"))?;
+
+    let codec = get_codec(id).ok_or_else(|| CompressionError::UnknownCodec(id.to_string()))?;
+    codec
+        .decompress(data)
+        .map(Vec::into_boxed_slice)
+        .map_err(CompressionError::CustomError)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc};
+
+    use super::{CompressionCodec, ZSTD_CODEC, compress, decompress, register_codec};
+    use crate::chunk::CompressionError;
+
+    /// Stores the data reversed, so it's clear the codec ran
+    struct Reversed;
+
+    impl CompressionCodec for Reversed {
+        fn compress(&self, data: &[u8], _level: u32) -> io::Result<Vec<u8>> {
+            Ok(data.iter().rev().copied().collect())
+        }
+
+        fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+            Ok(data.iter().rev().copied().collect())
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let data = b"some chunk data, some chunk data, some chunk data";
+
+        let compressed = compress(ZSTD_CODEC, data, 3).unwrap();
+        assert_eq!(&compressed[2..2 + ZSTD_CODEC.len()], ZSTD_CODEC.as_bytes());
+        assert_eq!(&*decompress(&compressed).unwrap(), data);
+
+        register_codec("test:reversed", Arc::new(Reversed));
+        let compressed = compress("test:reversed", b"abc", 0).unwrap();
+        assert_eq!(compressed, b"\x00\x0dtest:reversedcba");
+        assert_eq!(&*decompress(&compressed).unwrap(), b"abc");
+    }
+
+    #[test]
+    fn unknown_codec() {
+        assert!(matches!(
+            compress("test:missing", b"abc", 0),
+            Err(CompressionError::UnknownCodec(id)) if id == "test:missing"
+        ));
+        assert!(matches!(
+            decompress(b"\x00\x0ctest:missingabc"),
+            Err(CompressionError::UnknownCodec(_))
+        ));
+        assert!(matches!(
+            decompress(b"\x00\x20test"),
+            Err(CompressionError::CustomError(_))
+        ));
+    }
+}
@@ -1,9 +1,10 @@
 use std::io::ErrorKind;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::chunk::format::anvil::AnvilChunkFile;
-use crate::chunk::io::{ChunkSerializer, LoadedData};
+use crate::chunk::io::{ChunkSerializer, LoadedData, serialization_pool::serialization_pool};
 use crate::chunk::{ChunkData, ChunkReadingError, ChunkWritingError};
 use async_trait::async_trait;
 use bytes::{Buf, BufMut, Bytes};
@@ -55,6 +56,9 @@ struct LinearFileHeader {
 pub struct LinearFile {
     chunks_headers: [LinearChunkHeader; CHUNK_COUNT],
     chunks_data: [Option<Bytes>; CHUNK_COUNT],
+    /// Whether a chunk changed since the file was read or last written. The whole file is
+    /// compressed at once, so unlike Anvil there are no parts to write
+    dirty: AtomicBool,
 }
 
 impl LinearChunkHeader {
@@ -136,7 +140,7 @@ impl LinearFileHeader {
 
 impl LinearFile {
     const fn get_chunk_index(at: &Vector2<i32>) -> usize {
-        AnvilChunkFile::get_chunk_index(at)
+        <AnvilChunkFile>::get_chunk_index(at)
     }
 
     fn check_signature(bytes: &[u8]) -> Result<(), ChunkReadingError> {
@@ -154,6 +158,7 @@ impl Default for LinearFile {
         LinearFile {
             chunks_headers: [LinearChunkHeader::default(); CHUNK_COUNT],
             chunks_data: [const { None }; CHUNK_COUNT],
+            dirty: AtomicBool::new(false),
         }
     }
 }
@@ -168,11 +173,16 @@ impl ChunkSerializer for LinearFile {
     }
 
     fn get_chunk_key(chunk: &Vector2<i32>) -> String {
-        let (region_x, region_z) = AnvilChunkFile::get_region_coords(chunk);
+        let (region_x, region_z) = <AnvilChunkFile>::get_region_coords(chunk);
         format!("./r.{}.{}.linear", region_x, region_z)
     }
 
     async fn write(&self, path: PathBuf) -> Result<(), std::io::Error> {
+        if !self.dirty.load(Ordering::Relaxed) {
+            log::trace!("Skipping unchanged file: {:?}", path);
+            return Ok(());
+        }
+
         let temp_path = path.with_extension("tmp");
         log::trace!("Writing tmp file to disk: {:?}", temp_path);
 
@@ -201,8 +211,7 @@ impl ChunkSerializer for LinearFile {
         let compressed_buffer = zstd::bulk::compress(
             data_buffer.as_slice(),
             advanced_config().chunk.compression.level as i32,
-        )
-        .expect("Failed to compress the data buffer")
+        )?
         .into_boxed_slice();
 
         let file_header = LinearFileHeader {
@@ -234,6 +243,7 @@ impl ChunkSerializer for LinearFile {
         // The rename of the file works like an atomic operation ensuring
         // that the data is not corrupted before the rename is completed
         tokio::fs::rename(temp_path, &path).await?;
+        self.dirty.store(false, Ordering::Relaxed);
 
         log::trace!("Wrote file to Disk: {:?}", path);
         Ok(())
@@ -303,12 +313,16 @@ impl ChunkSerializer for LinearFile {
         Ok(LinearFile {
             chunks_headers: chunk_headers,
             chunks_data: chunks,
+            dirty: AtomicBool::new(false),
         })
     }
 
     async fn update_chunk(&mut self, chunk: &ChunkData) -> Result<(), ChunkWritingError> {
         let index = LinearFile::get_chunk_index(&chunk.position);
-        let chunk_raw: Bytes = chunk_to_bytes(chunk)
+        let chunk = chunk.snapshot();
+        let chunk_raw: Bytes = serialization_pool()
+            .run(move || chunk_to_bytes(&chunk))
+            .await
             .map_err(|err| ChunkWritingError::ChunkSerializingError(err.to_string()))?
             .into();
 
@@ -321,6 +335,7 @@ impl ChunkSerializer for LinearFile {
 
         // We update the data buffer
         self.chunks_data[index] = Some(chunk_raw);
+        *self.dirty.get_mut() = true;
 
         Ok(())
     }
@@ -369,6 +384,16 @@ impl ChunkSerializer for LinearFile {
                 .expect("Failed to send anvil chunks from bridge");
         }
     }
+
+    fn estimated_memory(&self) -> usize {
+        size_of::<Self>()
+            + self
+                .chunks_data
+                .iter()
+                .flatten()
+                .map(Bytes::len)
+                .sum::<usize>()
+    }
 }
 
 #[cfg(test)]
@@ -383,7 +408,8 @@ mod tests {
 
     use crate::chunk::format::linear::LinearFile;
     use crate::chunk::io::chunk_file_manager::ChunkFileManager;
-    use crate::chunk::io::{ChunkIO, LoadedData};
+    use crate::chunk::io::{ChunkIO, ChunkSerializer, LoadedData};
+    use crate::dimension::Dimension;
     use crate::generation::{Seed, get_world_gen};
     use crate::level::LevelFolder;
 
@@ -400,6 +426,7 @@ mod tests {
                 &LevelFolder {
                     root_folder: PathBuf::from(""),
                     region_folder: region_path,
+                    entities_folder: PathBuf::from(""),
                 },
                 &[Vector2::new(0, 0)],
                 send,
@@ -413,16 +440,36 @@ mod tests {
         assert!(chunks.len() == 1 && matches!(chunks[0], LoadedData::Missing(_)));
     }
 
+    #[tokio::test]
+    async fn skips_unchanged_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("r.0.0.linear");
+
+        let mut file = LinearFile::default();
+        file.write(path.clone()).await.unwrap();
+        assert!(!path.exists());
+
+        let chunk = get_world_gen(Seed(0), Dimension::OverWorld).generate_chunk(Vector2::new(0, 0));
+        file.update_chunk(&chunk).await.unwrap();
+        file.write(path.clone()).await.unwrap();
+        let written = fs::metadata(&path).unwrap().modified().unwrap();
+
+        let file = LinearFile::read(fs::read(&path).unwrap().into()).unwrap();
+        file.write(path.clone()).await.unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), written);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_writing() {
         let _ = env_logger::try_init();
 
-        let generator = get_world_gen(Seed(0));
+        let generator = get_world_gen(Seed(0), Dimension::OverWorld);
 
         let temp_dir = TempDir::new().unwrap();
         let level_folder = LevelFolder {
             root_folder: temp_dir.path().to_path_buf(),
             region_folder: temp_dir.path().join("region"),
+            entities_folder: temp_dir.path().join("entities"),
         };
         fs::create_dir(&level_folder.region_folder).expect("couldn't create region folder");
         let chunk_saver = ChunkFileManager::<LinearFile>::default();
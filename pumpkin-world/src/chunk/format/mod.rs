@@ -1,21 +1,25 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path, sync::Arc};
 
-use pumpkin_data::chunk::ChunkStatus;
-use pumpkin_nbt::{from_bytes, nbt_long_array};
+use pumpkin_data::chunk::{Biome, ChunkStatus};
+use pumpkin_nbt::{compound::NbtCompound, from_bytes, nbt_byte_array, nbt_long_array};
 
-use pumpkin_util::math::{ceil_log2, vector2::Vector2};
+use pumpkin_util::math::{ceil_log2, position::BlockPos, vector2::Vector2, vector3::Vector3};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     block::ChunkBlockState,
     coordinates::{ChunkRelativeBlockCoordinates, Height},
+    level::LevelFolder,
 };
 
 use super::{
-    CHUNK_AREA, ChunkData, ChunkHeightmaps, ChunkParsingError, SUBCHUNK_VOLUME, Subchunks,
+    BIOME_SECTION_VOLUME, CHUNK_AREA, ChunkBiomes, ChunkData, ChunkHeightmaps, ChunkLight,
+    ChunkParsingError, ChunkSerializingError, LIGHT_SECTION_SIZE, LIGHT_SECTIONS_COUNT,
+    SUBCHUNK_VOLUME, SUBCHUNKS_COUNT, Subchunks, io::RegionData,
 };
 
 pub mod anvil;
+pub mod codec;
 pub mod linear;
 
 // I can't use an tag because it will break ChunkNBT, but status need to have a big S, so "Status"
@@ -51,13 +55,34 @@ impl ChunkData {
 
         // this needs to be boxed, otherwise it will cause a stack-overflow
         let mut subchunks = Subchunks::Single(0);
-        let mut block_index = 0; // which block we're currently at
+        let mut biomes = ChunkBiomes::default();
+        let mut light = ChunkLight::default();
 
         for section in chunk_data.sections.into_iter() {
+            // The lowest section is at y -4
+            let section_index = (i32::from(section.y) + 4) as usize;
+            if let Some(section_biomes) = &section.biomes {
+                if section_index < SUBCHUNKS_COUNT {
+                    biomes.set_section(section_index, &section_biomes.to_biomes());
+                }
+            }
+            // Light also has a section below the world
+            let light_index = section_index.wrapping_add(1);
+            if light_index < LIGHT_SECTIONS_COUNT {
+                light.block_light[light_index] =
+                    section.block_light.as_deref().and_then(light_section);
+                light.sky_light[light_index] = section.sky_light.as_deref().and_then(light_section);
+            }
+
             let block_states = match section.block_states {
                 Some(states) => states,
-                None => continue, // TODO @lukas0008 this should instead fill all blocks with the only element of the palette
+                None => continue,
             };
+            // Blocks only exist in the sections of the world, not in the light ones around it
+            if section_index >= SUBCHUNKS_COUNT {
+                continue;
+            }
+            let mut block_index = section_index * SUBCHUNK_VOLUME; // which block we're currently at
 
             let palette = block_states
                 .palette
@@ -67,9 +92,10 @@ impl ChunkData {
 
             let block_data = match block_states.data {
                 None => {
-                    // We skipped placing an empty subchunk.
-                    // We need to increase the y coordinate of the next subchunk being placed.
-                    block_index += SUBCHUNK_VOLUME;
+                    // Vanilla leaves out the data of sections which are a single block
+                    if let Some(block) = palette.first() {
+                        subchunks.fill_subchunk(section_index, block.get_id());
+                    }
                     continue;
                 }
                 Some(d) => d,
@@ -113,9 +139,27 @@ impl ChunkData {
             }
         }
 
+        let block_entities = chunk_data
+            .block_entities
+            .into_iter()
+            .filter_map(|nbt| Some((block_entity_position(&nbt)?, nbt)))
+            .filter(|(block_position, _)| {
+                let in_chunk = block_position.chunk_and_chunk_relative_position().0 == position;
+                if !in_chunk {
+                    log::warn!(
+                        "Dropping block entity at {block_position} which isn't in chunk {position:?}"
+                    );
+                }
+                in_chunk
+            })
+            .collect();
+
         Ok(ChunkData {
             subchunks,
             heightmap: chunk_data.heightmaps,
+            biomes,
+            light,
+            block_entities,
             position,
             // This chunk is read from disk, so it has not been modified
             dirty: false,
@@ -123,6 +167,46 @@ impl ChunkData {
     }
 }
 
+impl RegionData for ChunkData {
+    fn region_folder(folder: &LevelFolder) -> &Path {
+        &folder.region_folder
+    }
+
+    fn position(&self) -> Vector2<i32> {
+        self.position
+    }
+
+    fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, ChunkSerializingError> {
+        anvil::chunk_to_bytes(self)
+    }
+
+    fn from_bytes(bytes: &[u8], position: Vector2<i32>) -> Result<Self, ChunkParsingError> {
+        ChunkData::from_bytes(bytes, position)
+    }
+}
+
+/// Block entities without a position are dropped, they can't belong to any block
+fn block_entity_position(nbt: &NbtCompound) -> Option<BlockPos> {
+    Some(BlockPos(Vector3::new(
+        nbt.get_int("x")?,
+        nbt.get_int("y")?,
+        nbt.get_int("z")?,
+    )))
+}
+
+/// Light arrays of another size are dropped, the section is then treated as having no light data
+fn light_section(light: &[u8]) -> Option<Arc<[u8; LIGHT_SECTION_SIZE]>> {
+    light.try_into().ok().map(Arc::new)
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct PaletteEntry {
@@ -138,6 +222,20 @@ struct ChunkSection {
     y: i8,
     #[serde(skip_serializing_if = "Option::is_none")]
     block_states: Option<ChunkSectionBlockStates>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    biomes: Option<ChunkSectionBiomes>,
+    #[serde(
+        rename = "BlockLight",
+        serialize_with = "nbt_byte_array",
+        skip_serializing_if = "Option::is_none"
+    )]
+    block_light: Option<Box<[u8]>>,
+    #[serde(
+        rename = "SkyLight",
+        serialize_with = "nbt_byte_array",
+        skip_serializing_if = "Option::is_none"
+    )]
+    sky_light: Option<Box<[u8]>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -150,18 +248,127 @@ struct ChunkSectionBlockStates {
     palette: Vec<PaletteEntry>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct ChunkSectionBiomes {
+    #[serde(
+        serialize_with = "nbt_long_array",
+        skip_serializing_if = "Option::is_none"
+    )]
+    data: Option<Box<[i64]>>,
+    palette: Vec<String>,
+}
+
+impl ChunkSectionBiomes {
+    /// How many bits each biome takes in the data, unlike the network format this always is an
+    /// index into the palette
+    fn bits(palette_len: usize) -> u32 {
+        u32::from(ceil_log2(palette_len as u32))
+    }
+
+    fn from_biomes(biomes: &[Biome; BIOME_SECTION_VOLUME]) -> Self {
+        let mut palette: Vec<Biome> = Vec::new();
+        for biome in biomes {
+            if !palette.contains(biome) {
+                palette.push(*biome);
+            }
+        }
+        let names = palette
+            .iter()
+            .map(|biome| format!("minecraft:{}", biome.to_name()))
+            .collect();
+        if palette.len() == 1 {
+            return Self {
+                data: None,
+                palette: names,
+            };
+        }
+
+        let bits = Self::bits(palette.len());
+        let data = biomes
+            .chunks((64 / bits) as usize)
+            .map(|cells| {
+                cells.iter().enumerate().fold(0, |long, (i, biome)| {
+                    let index = palette.iter().position(|entry| entry == biome).unwrap();
+                    long | ((index as i64) << (i as u32 * bits))
+                })
+            })
+            .collect();
+        Self {
+            data: Some(data),
+            palette: names,
+        }
+    }
+
+    fn to_biomes(&self) -> [Biome; BIOME_SECTION_VOLUME] {
+        // Biomes which do not exist here, e.g. from a datapack, become plains
+        let palette: Vec<Biome> = self
+            .palette
+            .iter()
+            .map(|name| {
+                Biome::from_name(name.trim_start_matches("minecraft:")).unwrap_or(Biome::Plains)
+            })
+            .collect();
+        let first = palette.first().copied().unwrap_or(Biome::Plains);
+        let mut biomes = [first; BIOME_SECTION_VOLUME];
+        let Some(data) = self.data.as_ref().filter(|_| palette.len() > 1) else {
+            return biomes;
+        };
+
+        let bits = Self::bits(palette.len());
+        let per_long = (64 / bits) as usize;
+        let mask = (1 << bits) - 1;
+        for (i, biome) in biomes.iter_mut().enumerate() {
+            let Some(long) = data.get(i / per_long) else {
+                break;
+            };
+            let index = (long >> ((i % per_long) as u32 * bits)) & mask;
+            if let Some(entry) = palette.get(index as usize) {
+                *biome = *entry;
+            }
+        }
+        biomes
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct ChunkNbt {
     data_version: i32,
     #[serde(rename = "xPos")]
     x_pos: i32,
-    // #[serde(rename = "yPos")]
-    //y_pos: i32,
+    /// The lowest section of the chunk, vanilla always saves it
+    #[serde(rename = "yPos", default)]
+    y_pos: i32,
     #[serde(rename = "zPos")]
     z_pos: i32,
     status: ChunkStatus,
     #[serde(rename = "sections")]
     sections: Vec<ChunkSection>,
     heightmaps: ChunkHeightmaps,
+    #[serde(rename = "block_entities", default)]
+    block_entities: Vec<NbtCompound>,
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::chunk::Biome;
+
+    use super::ChunkSectionBiomes;
+    use crate::chunk::BIOME_SECTION_VOLUME;
+
+    #[test]
+    fn biome_section_roundtrip() {
+        let single = [Biome::Forest; BIOME_SECTION_VOLUME];
+        let section = ChunkSectionBiomes::from_biomes(&single);
+        assert!(section.data.is_none());
+        assert_eq!(section.to_biomes(), single);
+
+        for kinds in [2, 5, 9] {
+            let mut mixed = single;
+            for (i, biome) in mixed.iter_mut().enumerate() {
+                *biome = Biome::from_id((i % kinds) as u8).unwrap();
+            }
+            assert_eq!(ChunkSectionBiomes::from_biomes(&mixed).to_biomes(), mixed);
+        }
+    }
 }
@@ -3,26 +3,32 @@ use std::{
     io::ErrorKind,
     ops::{AddAssign, SubAssign},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, LazyLock},
+    time::Instant,
 };
 
 use async_trait::async_trait;
 use futures::future::join_all;
 use log::{error, trace};
 use num_traits::Zero;
+use pumpkin_config::advanced_config;
 use pumpkin_util::math::vector2::Vector2;
 use tokio::{
     io::AsyncReadExt,
     join,
-    sync::{OnceCell, RwLock, mpsc},
+    sync::{OnceCell, RwLock, Semaphore, mpsc},
 };
 
 use crate::{
-    chunk::{ChunkData, ChunkReadingError, ChunkWritingError},
-    level::{LevelFolder, SyncChunk},
+    chunk::{ChunkReadingError, ChunkWritingError},
+    level::LevelFolder,
 };
 
-use super::{ChunkIO, ChunkSerializer, LoadedData};
+use super::{
+    ChunkIO, ChunkSerializer, LoadedData, RegionData,
+    io_metrics::{ChunkIoMetrics, IoCounters},
+    serialization_pool::serialization_pool,
+};
 
 /// A simple implementation of the ChunkSerializer trait
 /// that load and save the data from a file in the disk
@@ -34,22 +40,31 @@ pub struct ChunkFileManager<S: ChunkSerializer<WriteBackend = PathBuf>> {
     // Dashmap has rw-locks on shards, but we want per-serializer
     file_locks: RwLock<BTreeMap<PathBuf, SerializerCacheEntry<S>>>,
     watchers: RwLock<BTreeMap<PathBuf, usize>>,
+    counters: IoCounters,
 }
 //to avoid clippy warnings we extract the type alias
 type SerializerCacheEntry<S> = OnceCell<Arc<RwLock<S>>>;
 
+/// Limits how many region files are read or written at once, shared by all levels
+static IO_PERMITS: LazyLock<Semaphore> =
+    LazyLock::new(|| match advanced_config().runtime.io_concurrency {
+        0 => Semaphore::new(Semaphore::MAX_PERMITS),
+        limit => Semaphore::new(limit),
+    });
+
 impl<S: ChunkSerializer<WriteBackend = PathBuf>> Default for ChunkFileManager<S> {
     fn default() -> Self {
         Self {
             file_locks: RwLock::new(BTreeMap::new()),
             watchers: RwLock::new(BTreeMap::new()),
+            counters: IoCounters::default(),
         }
     }
 }
 
 impl<S: ChunkSerializer<WriteBackend = PathBuf>> ChunkFileManager<S> {
     fn map_key(folder: &LevelFolder, file_name: &str) -> PathBuf {
-        folder.region_folder.join(file_name)
+        S::Data::region_folder(folder).join(file_name)
     }
 
     async fn read_file(&self, path: &Path) -> Result<Arc<RwLock<S>>, ChunkReadingError> {
@@ -58,8 +73,14 @@ impl<S: ChunkSerializer<WriteBackend = PathBuf>> ChunkFileManager<S> {
 
         async fn read_from_disk<S: ChunkSerializer>(
             path: &Path,
+            counters: &IoCounters,
         ) -> Result<Arc<RwLock<S>>, ChunkReadingError> {
             trace!("Opening file from Disk: {:?}", path);
+            let _permit = IO_PERMITS
+                .acquire()
+                .await
+                .expect("IO permits are never closed");
+            let start = Instant::now();
             let file = tokio::fs::OpenOptions::new()
                 .read(true)
                 .write(false)
@@ -83,6 +104,7 @@ impl<S: ChunkSerializer<WriteBackend = PathBuf>> ChunkFileManager<S> {
                     file.read_to_end(&mut file_bytes)
                         .await
                         .map_err(|err| ChunkReadingError::IoError(err.kind()))?;
+                    counters.record_read(path, file_bytes.len() as u64, start.elapsed());
                     S::read(file_bytes.into())?
                 }
                 Err(ChunkReadingError::ChunkNotExist) => S::default(),
@@ -97,8 +119,11 @@ impl<S: ChunkSerializer<WriteBackend = PathBuf>> ChunkFileManager<S> {
         // lock for too long starving other threads
         let serializer = if let Some(once_cell) = self.file_locks.read().await.get(path) {
             log::trace!("Loading file lock from cache: {:?}", path);
+            if once_cell.initialized() {
+                self.counters.record_cache_hit();
+            }
             once_cell
-                .get_or_try_init(|| read_from_disk(path))
+                .get_or_try_init(|| read_from_disk(path, &self.counters))
                 .await?
                 .clone()
         } else {
@@ -110,7 +135,7 @@ impl<S: ChunkSerializer<WriteBackend = PathBuf>> ChunkFileManager<S> {
             let file_locks = file_locks.downgrade();
             let once_cell = file_locks.get(path).expect("We just inserted this!");
             once_cell
-                .get_or_try_init(|| read_from_disk(path))
+                .get_or_try_init(|| read_from_disk(path, &self.counters))
                 .await?
                 .clone()
         };
@@ -122,9 +147,9 @@ impl<S: ChunkSerializer<WriteBackend = PathBuf>> ChunkFileManager<S> {
 #[async_trait]
 impl<S> ChunkIO for ChunkFileManager<S>
 where
-    S: ChunkSerializer<Data = ChunkData, WriteBackend = PathBuf>,
+    S: ChunkSerializer<WriteBackend = PathBuf>,
 {
-    type Data = SyncChunk;
+    type Data = Arc<RwLock<S::Data>>;
 
     async fn watch_chunks(&self, folder: &LevelFolder, chunks: &[Vector2<i32>]) {
         // It is intentional that regions are watched multiple times (once per chunk)
@@ -164,11 +189,25 @@ where
         self.watchers.write().await.clear();
     }
 
+    async fn release_unwatched(&self, folder: &LevelFolder, chunks: &[Vector2<i32>]) {
+        // Same lock order as `save_chunks`
+        let mut file_locks = self.file_locks.write().await;
+        let watchers = self.watchers.read().await;
+        for chunk in chunks {
+            let path = Self::map_key(folder, &S::get_chunk_key(chunk));
+            if watchers.get(&path).is_none_or(|count| count.is_zero())
+                && file_locks.remove(&path).is_some()
+            {
+                log::trace!("Released unwatched file from cache {:?}", path);
+            }
+        }
+    }
+
     async fn fetch_chunks(
         &self,
         folder: &LevelFolder,
         chunk_coords: &[Vector2<i32>],
-        stream: tokio::sync::mpsc::Sender<LoadedData<SyncChunk, ChunkReadingError>>,
+        stream: tokio::sync::mpsc::Sender<LoadedData<Self::Data, ChunkReadingError>>,
     ) {
         let mut regions_chunks: BTreeMap<String, Vec<Vector2<i32>>> = BTreeMap::new();
 
@@ -199,7 +238,7 @@ where
             };
 
             // Intermediate channel for wrapping the data with the Arc<RwLock>
-            let (send, mut recv) = mpsc::channel::<LoadedData<ChunkData, ChunkReadingError>>(1);
+            let (send, mut recv) = mpsc::channel::<LoadedData<S::Data, ChunkReadingError>>(1);
 
             let intermediary = async {
                 while let Some(data) = recv.recv().await {
@@ -224,9 +263,9 @@ where
     async fn save_chunks(
         &self,
         folder: &LevelFolder,
-        chunks_data: Vec<(Vector2<i32>, SyncChunk)>,
+        chunks_data: Vec<(Vector2<i32>, Self::Data)>,
     ) -> Result<(), ChunkWritingError> {
-        let mut regions_chunks: BTreeMap<String, Vec<SyncChunk>> = BTreeMap::new();
+        let mut regions_chunks: BTreeMap<String, Vec<Self::Data>> = BTreeMap::new();
 
         for (at, chunk) in chunks_data {
             let key = S::get_chunk_key(&at);
@@ -264,22 +303,23 @@ where
                     }
                 }?;
 
-                let mut serializer = chunk_serializer.write().await;
+                // Take a snapshot of every dirty chunk first, so the chunk locks are only held
+                // for a moment and gameplay can keep reading and modifying the chunks while they
+                // are serialized. A chunk modified after its snapshot is marked dirty again and
+                // picked up by the next save.
+                let mut snapshots = Vec::with_capacity(chunk_locks.len());
                 for chunk_lock in chunk_locks {
                     let mut chunk = chunk_lock.write().await;
-                    let chunk_is_dirty = chunk.dirty;
-                    // Edge case: this chunk is loaded while we were saving, mark it as cleaned since we are
-                    // updating what we will write here
-                    chunk.dirty = false;
-                    // It is important that we keep the lock after we mark the chunk as clean so no one else
-                    // can modify it
-                    let chunk = chunk.downgrade();
-
                     // We only need to update the chunk if it is dirty
-                    if chunk_is_dirty {
-                        serializer.update_chunk(&*chunk).await?;
+                    if chunk.take_dirty() {
+                        snapshots.push(chunk.snapshot());
                     }
                 }
+
+                let mut serializer = chunk_serializer.write().await;
+                for chunk in &snapshots {
+                    serializer.update_chunk(chunk).await?;
+                }
                 log::trace!("Updated data for file {:?}", path);
 
                 let is_watched = self
@@ -295,11 +335,22 @@ where
                     let serializer = serializer.downgrade();
 
                     log::debug!("Writing file for {:?}", path);
+                    let permit = IO_PERMITS
+                        .acquire()
+                        .await
+                        .expect("IO permits are never closed");
+                    let start = Instant::now();
                     serializer
                         .write(path.clone())
                         .await
                         .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
+                    drop(permit);
                     drop(serializer);
+                    let elapsed = start.elapsed();
+                    let bytes = tokio::fs::metadata(&path)
+                        .await
+                        .map_or(0, |metadata| metadata.len());
+                    self.counters.record_write(&path, bytes, elapsed);
 
                     // If there are still no watchers, drop from the locks
                     let mut locks = self.file_locks.write().await;
@@ -329,6 +380,27 @@ where
     async fn clean_up_log(&self) {
         let locks = self.file_locks.read().await;
         log::debug!("{} File locks remain in cache", locks.len());
+        log::debug!("Chunk serialization: {:?}", serialization_pool().metrics());
+        log::debug!("Chunk IO: {:?}", self.counters.metrics());
+    }
+
+    fn metrics(&self) -> ChunkIoMetrics {
+        self.counters.metrics()
+    }
+
+    async fn cache_memory(&self) -> usize {
+        let files: Vec<_> = self
+            .file_locks
+            .read()
+            .await
+            .values()
+            .filter_map(|file| file.get().cloned())
+            .collect();
+        let mut memory = 0;
+        for file in files {
+            memory += file.read().await.estimated_memory();
+        }
+        memory
     }
 
     async fn block_and_await_ongoing_tasks(&self) {
@@ -0,0 +1,120 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use pumpkin_config::advanced_config;
+
+/// Counts the region file reads and writes of a chunk IO and warns about the ones that took
+/// longer than `slow_io_warning_ms`, which usually means a failing or network disk
+#[derive(Default)]
+pub struct IoCounters {
+    reads: AtomicU64,
+    writes: AtomicU64,
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    cache_hits: AtomicU64,
+    slow_reads: AtomicU64,
+    slow_writes: AtomicU64,
+}
+
+/// A snapshot of the region file IO of a level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChunkIoMetrics {
+    pub reads: u64,
+    pub writes: u64,
+    pub bytes_read: u64,
+    /// The size of the region files after writing them
+    pub bytes_written: u64,
+    /// How often a region file was already cached and didn't have to be read
+    pub cache_hits: u64,
+    pub slow_reads: u64,
+    pub slow_writes: u64,
+}
+
+/// Whether IO that took `elapsed` is slow enough to warn about, a threshold of 0 never warns
+fn is_slow(elapsed: Duration, threshold_ms: u64) -> bool {
+    threshold_ms != 0 && elapsed >= Duration::from_millis(threshold_ms)
+}
+
+impl IoCounters {
+    pub fn record_read(&self, path: &Path, bytes: u64, elapsed: Duration) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+        self.bytes_read.fetch_add(bytes, Ordering::Relaxed);
+        log::trace!("Read {bytes} bytes from {} in {elapsed:?}", path.display());
+        if is_slow(elapsed, advanced_config().chunk.slow_io_warning_ms) {
+            self.slow_reads.fetch_add(1, Ordering::Relaxed);
+            log::warn!(
+                "Reading {} took {elapsed:?}, the disk may be failing or overloaded",
+                path.display()
+            );
+        }
+    }
+
+    pub fn record_write(&self, path: &Path, bytes: u64, elapsed: Duration) {
+        self.writes.fetch_add(1, Ordering::Relaxed);
+        self.bytes_written.fetch_add(bytes, Ordering::Relaxed);
+        log::trace!("Wrote {bytes} bytes to {} in {elapsed:?}", path.display());
+        if is_slow(elapsed, advanced_config().chunk.slow_io_warning_ms) {
+            self.slow_writes.fetch_add(1, Ordering::Relaxed);
+            log::warn!(
+                "Writing {} took {elapsed:?}, the disk may be failing or overloaded",
+                path.display()
+            );
+        }
+    }
+
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn metrics(&self) -> ChunkIoMetrics {
+        ChunkIoMetrics {
+            reads: self.reads.load(Ordering::Relaxed),
+            writes: self.writes.load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            slow_reads: self.slow_reads.load(Ordering::Relaxed),
+            slow_writes: self.slow_writes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, time::Duration};
+
+    use super::{ChunkIoMetrics, IoCounters, is_slow};
+
+    #[test]
+    fn slow_threshold() {
+        assert!(!is_slow(Duration::from_millis(499), 500));
+        assert!(is_slow(Duration::from_millis(500), 500));
+        assert!(!is_slow(Duration::from_secs(60), 0));
+    }
+
+    #[test]
+    fn counts_io() {
+        let counters = IoCounters::default();
+        let path = Path::new("r.0.0.mca");
+        counters.record_read(path, 4096, Duration::ZERO);
+        counters.record_read(path, 8192, Duration::ZERO);
+        counters.record_write(path, 1024, Duration::ZERO);
+        counters.record_cache_hit();
+
+        assert_eq!(
+            counters.metrics(),
+            ChunkIoMetrics {
+                reads: 2,
+                writes: 1,
+                bytes_read: 12288,
+                bytes_written: 1024,
+                cache_hits: 1,
+                slow_reads: 0,
+                slow_writes: 0,
+            }
+        );
+    }
+}
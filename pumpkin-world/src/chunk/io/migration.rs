@@ -0,0 +1,233 @@
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use pumpkin_config::chunk::ChunkFormat;
+use pumpkin_util::math::vector2::Vector2;
+use thiserror::Error;
+
+use crate::{
+    chunk::{
+        ChunkWritingError,
+        format::anvil::{REGION_SIZE, SUBREGION_BITS},
+    },
+    level::LevelFolder,
+};
+
+#[derive(Error, Debug)]
+pub enum ChunkMigrationError {
+    #[error("The world is already stored in the {0:?} format")]
+    SameFormat(ChunkFormat),
+    #[error("A migration is already running for this world")]
+    AlreadyRunning,
+    #[error("Io error: {0}")]
+    IoError(std::io::ErrorKind),
+    #[error("Failed to write migrated chunks: {0}")]
+    Writing(ChunkWritingError),
+}
+
+/// The file in the level folder that records the format of the last migration
+pub const FORMAT_MARKER_FILE_NAME: &str = "chunk_format";
+/// Where the region files of the old format are moved to after a migration
+pub const OLD_REGION_FOLDER: &str = "region.old";
+
+/// The outcome of a finished migration
+#[derive(Default, Debug, Clone, Copy)]
+pub struct MigrationReport {
+    /// The amount of regions copied to the new format
+    pub regions: usize,
+    /// The amount of chunks copied to the new format
+    pub chunks: usize,
+    /// The amount of regions that changed while migrating and had to be copied again
+    pub remigrated_regions: usize,
+}
+
+/// The extension of the region files of a format
+pub const fn region_file_extension(format: ChunkFormat) -> &'static str {
+    match format {
+        ChunkFormat::Anvil => "mca",
+        ChunkFormat::Linear => "linear",
+    }
+}
+
+/// Parses region coordinates out of a region file name (`r.<x>.<z>.<extension>`)
+pub fn parse_region_file_name(file_name: &str, extension: &str) -> Option<(i32, i32)> {
+    let coords = file_name
+        .strip_prefix("r.")?
+        .strip_suffix(extension)?
+        .strip_suffix('.')?;
+    let (x, z) = coords.split_once('.')?;
+    Some((x.parse().ok()?, z.parse().ok()?))
+}
+
+/// The path of a region file with its region coordinates
+pub type RegionFile = (PathBuf, (i32, i32));
+
+/// Lists all the region files of a format in the level, with their region coordinates
+pub fn list_region_files(
+    folder: &LevelFolder,
+    format: ChunkFormat,
+) -> Result<Vec<RegionFile>, ChunkMigrationError> {
+    let extension = region_file_extension(format);
+    let entries = std::fs::read_dir(&folder.region_folder)
+        .map_err(|err| ChunkMigrationError::IoError(err.kind()))?;
+
+    let mut regions = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let region = parse_region_file_name(path.file_name()?.to_str()?, extension)?;
+            Some((path, region))
+        })
+        .collect::<Vec<_>>();
+    // Migrate in a stable order to make the progress logs meaningful
+    regions.sort_by_key(|(_, region)| *region);
+    Ok(regions)
+}
+
+/// All the chunk positions of a region
+pub fn region_chunks((region_x, region_z): (i32, i32)) -> impl Iterator<Item = Vector2<i32>> {
+    let size = REGION_SIZE as i32;
+    (0..size).flat_map(move |z| {
+        (0..size).map(move |x| {
+            Vector2::new(
+                (region_x << SUBREGION_BITS) + x,
+                (region_z << SUBREGION_BITS) + z,
+            )
+        })
+    })
+}
+
+pub async fn last_modified(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The format a migration cut the level over to, read on startup in place of `chunk.format`
+pub fn read_format_marker(folder: &LevelFolder) -> Option<ChunkFormat> {
+    let marker = std::fs::read_to_string(folder.root_folder.join(FORMAT_MARKER_FILE_NAME)).ok()?;
+    match marker.trim() {
+        "anvil" => Some(ChunkFormat::Anvil),
+        "linear" => Some(ChunkFormat::Linear),
+        unknown => {
+            log::warn!(
+                "Unknown chunk format {unknown:?} in {FORMAT_MARKER_FILE_NAME}, ignoring it"
+            );
+            None
+        }
+    }
+}
+
+pub async fn write_format_marker(
+    folder: &LevelFolder,
+    format: ChunkFormat,
+) -> Result<(), ChunkMigrationError> {
+    let name = match format {
+        ChunkFormat::Anvil => "anvil",
+        ChunkFormat::Linear => "linear",
+    };
+    tokio::fs::write(folder.root_folder.join(FORMAT_MARKER_FILE_NAME), name)
+        .await
+        .map_err(|err| ChunkMigrationError::IoError(err.kind()))
+}
+
+/// Moves the region files of the old format out of the region folder, into `region.old`
+pub async fn move_old_regions(
+    folder: &LevelFolder,
+    regions: &[PathBuf],
+) -> Result<(), ChunkMigrationError> {
+    let old_folder = folder.root_folder.join(OLD_REGION_FOLDER);
+    tokio::fs::create_dir_all(&old_folder)
+        .await
+        .map_err(|err| ChunkMigrationError::IoError(err.kind()))?;
+    for path in regions {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        tokio::fs::rename(path, old_folder.join(name))
+            .await
+            .map_err(|err| ChunkMigrationError::IoError(err.kind()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use pumpkin_config::chunk::ChunkFormat;
+    use temp_dir::TempDir;
+
+    use super::{
+        OLD_REGION_FOLDER, list_region_files, move_old_regions, parse_region_file_name,
+        read_format_marker, region_chunks, write_format_marker,
+    };
+    use crate::level::LevelFolder;
+
+    #[test]
+    fn parse_region_names() {
+        assert_eq!(parse_region_file_name("r.0.0.mca", "mca"), Some((0, 0)));
+        assert_eq!(parse_region_file_name("r.-3.12.mca", "mca"), Some((-3, 12)));
+        assert_eq!(
+            parse_region_file_name("r.1.-1.linear", "linear"),
+            Some((1, -1))
+        );
+        assert_eq!(parse_region_file_name("r.1.-1.linear", "mca"), None);
+        assert_eq!(parse_region_file_name("r.0.0.tmp", "mca"), None);
+        assert_eq!(parse_region_file_name("level.dat", "mca"), None);
+    }
+
+    #[test]
+    fn region_chunk_positions() {
+        let chunks = region_chunks((-1, 2)).collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 32 * 32);
+        assert_eq!(chunks[0], Vector2::new(-32, 64));
+        assert_eq!(chunks[chunks.len() - 1], Vector2::new(-1, 95));
+    }
+
+    #[tokio::test]
+    async fn cutover_is_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+            entities_folder: temp_dir.path().join("entities"),
+        };
+        std::fs::create_dir(&folder.region_folder).unwrap();
+        std::fs::write(folder.region_folder.join("r.0.0.mca"), []).unwrap();
+        std::fs::write(folder.region_folder.join("r.0.0.linear"), []).unwrap();
+        assert_eq!(read_format_marker(&folder), None);
+
+        write_format_marker(&folder, ChunkFormat::Linear)
+            .await
+            .unwrap();
+        assert_eq!(read_format_marker(&folder), Some(ChunkFormat::Linear));
+
+        let old = list_region_files(&folder, ChunkFormat::Anvil)
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        move_old_regions(&folder, &old).await.unwrap();
+        assert!(
+            list_region_files(&folder, ChunkFormat::Anvil)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            list_region_files(&folder, ChunkFormat::Linear)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(
+            temp_dir
+                .path()
+                .join(OLD_REGION_FOLDER)
+                .join("r.0.0.mca")
+                .exists()
+        );
+    }
+}
@@ -1,13 +1,17 @@
-use std::error;
+use std::{error, path::Path};
 
 use async_trait::async_trait;
 use bytes::Bytes;
 use pumpkin_util::math::vector2::Vector2;
 
-use super::{ChunkReadingError, ChunkWritingError};
+use super::{ChunkParsingError, ChunkReadingError, ChunkSerializingError, ChunkWritingError};
 use crate::level::LevelFolder;
+use io_metrics::ChunkIoMetrics;
 
 pub mod chunk_file_manager;
+pub mod io_metrics;
+pub mod migration;
+pub mod serialization_pool;
 
 /// The result of loading a chunk data.
 ///
@@ -75,19 +79,52 @@ where
     /// Tells the `ChunkIO` that no more chunks are loaded in memory
     async fn clear_watched_chunks(&self);
 
+    /// Drops the cached data of the given chunks if they are not watched
+    async fn release_unwatched(&self, folder: &LevelFolder, chunks: &[Vector2<i32>]);
+
     async fn clean_up_log(&self);
 
+    /// How many region files were read and written so far
+    fn metrics(&self) -> ChunkIoMetrics {
+        ChunkIoMetrics::default()
+    }
+
+    /// Roughly how many bytes the cached region files take up
+    async fn cache_memory(&self) -> usize {
+        0
+    }
+
     /// Ensure that all ongoing operations are finished
     async fn block_and_await_ongoing_tasks(&self);
 }
 
+/// The data of a chunk which is kept in region files, like its blocks or the entities in it.
+/// Every kind of data has region files of its own
+pub trait RegionData: Send + Sync + Sized + 'static {
+    /// The folder of the level the region files are in
+    fn region_folder(folder: &LevelFolder) -> &Path;
+
+    fn position(&self) -> Vector2<i32>;
+
+    /// Whether the data changed since it was saved last, it counts as saved afterwards
+    fn take_dirty(&mut self) -> bool;
+
+    /// A copy to serialize, so the data can keep being used in the meantime
+    fn snapshot(&self) -> Self;
+
+    /// The NBT of the chunk inside of a region file
+    fn to_bytes(&self) -> Result<Vec<u8>, ChunkSerializingError>;
+
+    fn from_bytes(bytes: &[u8], position: Vector2<i32>) -> Result<Self, ChunkParsingError>;
+}
+
 /// Trait to serialize and deserialize the chunk data to and from bytes.
 ///
 /// The `Data` type is the type of the data that will be updated or serialized/deserialized
 /// like ChunkData or EntityData
 #[async_trait]
 pub trait ChunkSerializer: Send + Sync + Default {
-    type Data: Send + Sync + Sized;
+    type Data: RegionData;
     type WriteBackend;
 
     /// Get the key for the chunk (like the file name)
@@ -110,4 +147,9 @@ pub trait ChunkSerializer: Send + Sync + Default {
         chunks: &[Vector2<i32>],
         stream: tokio::sync::mpsc::Sender<LoadedData<Self::Data, ChunkReadingError>>,
     );
+
+    /// Roughly how many bytes the file takes up while it is cached
+    fn estimated_memory(&self) -> usize {
+        size_of::<Self>()
+    }
 }
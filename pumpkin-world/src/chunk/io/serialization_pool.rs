@@ -0,0 +1,182 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use pumpkin_config::advanced_config;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use tokio::sync::{Semaphore, oneshot};
+
+static POOL: LazyLock<SerializationPool> = LazyLock::new(|| {
+    let config = &advanced_config().chunk;
+    SerializationPool::new(config.serialization_threads, config.serialization_queue)
+});
+
+/// The pool chunks are serialized and compressed on when saving
+pub fn serialization_pool() -> &'static SerializationPool {
+    &POOL
+}
+
+/// Runs the CPU heavy part of saving chunks on its own threads, so a burst of saves can't take
+/// over the threads of the async runtime or the rayon pool world generation uses.
+///
+/// The queue is bounded: once it is full, submitting waits until a job finished, which slows
+/// down whoever is saving instead of piling up chunk snapshots in memory.
+pub struct SerializationPool {
+    pool: ThreadPool,
+    slots: Arc<Semaphore>,
+    capacity: usize,
+    queued: Arc<AtomicUsize>,
+    running: Arc<AtomicUsize>,
+    completed: Arc<AtomicU64>,
+    /// How often submitting had to wait for a slot
+    waited: AtomicU64,
+}
+
+/// A snapshot of how busy the pool is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerializationMetrics {
+    pub threads: usize,
+    pub capacity: usize,
+    pub queued: usize,
+    pub running: usize,
+    pub completed: u64,
+    pub waited: u64,
+}
+
+impl SerializationPool {
+    /// `threads` of 0 uses half of the available cores, `queue` is how many jobs can be queued or
+    /// running at the same time
+    pub fn new(threads: usize, queue: usize) -> Self {
+        let threads = if threads == 0 {
+            thread::available_parallelism().map_or(1, |cores| (cores.get() / 2).max(1))
+        } else {
+            threads
+        };
+        let capacity = queue.max(1);
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("chunk-serializer-{index}"))
+            .build()
+            .expect("Failed to start the chunk serialization threads");
+
+        Self {
+            pool,
+            slots: Arc::new(Semaphore::new(capacity)),
+            capacity,
+            queued: Arc::new(AtomicUsize::new(0)),
+            running: Arc::new(AtomicUsize::new(0)),
+            completed: Arc::new(AtomicU64::new(0)),
+            waited: AtomicU64::new(0),
+        }
+    }
+
+    /// Runs the job on the pool, waiting for a free slot first if the queue is full. A panic in
+    /// the job is resumed in the caller
+    pub async fn run<T, F>(&self, job: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let permit = match self.slots.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                self.waited.fetch_add(1, Ordering::Relaxed);
+                log::debug!("Chunk serialization queue is full, waiting for a free slot");
+                self.slots
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("The serialization slots are never closed")
+            }
+        };
+
+        let (send, recv) = oneshot::channel();
+        let queued = self.queued.clone();
+        let running = self.running.clone();
+        let completed = self.completed.clone();
+        queued.fetch_add(1, Ordering::Relaxed);
+        self.pool.spawn(move || {
+            queued.fetch_sub(1, Ordering::Relaxed);
+            running.fetch_add(1, Ordering::Relaxed);
+            let result = panic::catch_unwind(AssertUnwindSafe(job));
+            running.fetch_sub(1, Ordering::Relaxed);
+            completed.fetch_add(1, Ordering::Relaxed);
+            drop(permit);
+            // The caller may have been cancelled, nobody needs the result then
+            let _ = send.send(result);
+        });
+
+        match recv.await.expect("Serialization jobs always send a result") {
+            Ok(result) => result,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+
+    pub fn metrics(&self) -> SerializationMetrics {
+        SerializationMetrics {
+            threads: self.pool.current_num_threads(),
+            capacity: self.capacity,
+            queued: self.queued.load(Ordering::Relaxed),
+            running: self.running.load(Ordering::Relaxed),
+            completed: self.completed.load(Ordering::Relaxed),
+            waited: self.waited.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        thread,
+        time::Duration,
+    };
+
+    use futures::future::join_all;
+
+    use super::SerializationPool;
+
+    #[tokio::test]
+    async fn bounded_queue() {
+        let pool = SerializationPool::new(2, 3);
+        let running = Arc::new(AtomicUsize::new(0));
+        let most_running = Arc::new(AtomicUsize::new(0));
+
+        let jobs = (0..12).map(|i| {
+            let running = running.clone();
+            let most_running = most_running.clone();
+            pool.run(move || {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                most_running.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(5));
+                running.fetch_sub(1, Ordering::SeqCst);
+                i * 2
+            })
+        });
+        let results = join_all(jobs).await;
+
+        assert_eq!(results, (0..12).map(|i| i * 2).collect::<Vec<_>>());
+        assert!(most_running.load(Ordering::SeqCst) <= 2);
+        let metrics = pool.metrics();
+        assert_eq!(metrics.completed, 12);
+        assert_eq!((metrics.queued, metrics.running), (0, 0));
+        // Only 3 jobs fit at once, the others had to wait
+        assert!(metrics.waited >= 9);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "broken chunk")]
+    async fn panics_reach_the_caller() {
+        SerializationPool::new(1, 1)
+            .run(|| panic!("broken chunk"))
+            .await;
+    }
+}
@@ -1,11 +1,18 @@
-use pumpkin_nbt::nbt_long_array;
-use pumpkin_util::math::vector2::Vector2;
+use pumpkin_data::chunk::Biome;
+use pumpkin_nbt::{compound::NbtCompound, nbt_long_array};
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use serde::{Deserialize, Serialize};
-use std::iter::repeat_with;
+use std::{collections::HashMap, iter::repeat_with, sync::Arc};
 use thiserror::Error;
 
-use crate::{WORLD_HEIGHT, coordinates::ChunkRelativeBlockCoordinates};
+use crate::{
+    WORLD_HEIGHT,
+    coordinates::{ChunkRelativeBlockCoordinates, Height},
+};
 
+#[cfg(feature = "chunk-diff")]
+pub mod diff;
+pub mod entities;
 pub mod format;
 pub mod io;
 
@@ -13,6 +20,15 @@ pub const CHUNK_AREA: usize = 16 * 16;
 pub const SUBCHUNK_VOLUME: usize = CHUNK_AREA * 16;
 pub const SUBCHUNKS_COUNT: usize = WORLD_HEIGHT / 16;
 pub const CHUNK_VOLUME: usize = CHUNK_AREA * WORLD_HEIGHT;
+/// Biomes are stored for cells of 4x4x4 blocks, so a subchunk has 64 of them
+pub const BIOME_SECTION_VOLUME: usize = 4 * 4 * 4;
+pub const BIOMES_COUNT: usize = BIOME_SECTION_VOLUME * SUBCHUNKS_COUNT;
+/// Light levels are 4 bits, so a byte holds two blocks
+pub const LIGHT_SECTION_SIZE: usize = SUBCHUNK_VOLUME / 2;
+/// Light is also kept for the section below and the section above the world
+pub const LIGHT_SECTIONS_COUNT: usize = SUBCHUNKS_COUNT + 2;
+/// What a block entity is guessed to take up in memory, their NBT differs a lot
+const BLOCK_ENTITY_MEMORY: usize = 512;
 
 #[derive(Error, Debug)]
 pub enum ChunkReadingError {
@@ -52,14 +68,25 @@ pub enum CompressionError {
     LZ4Error(std::io::Error),
     #[error("Error while working with zstd compression: {0}")]
     ZstdError(std::io::Error),
+    #[error("No codec is registered for the custom compression {0}")]
+    UnknownCodec(String),
+    #[error("Error while working with custom compression: {0}")]
+    CustomError(std::io::Error),
 }
 
+/// Cloning a `ChunkData` is cheap: the block data of the subchunks is shared with the clone and
+/// only copied once either of them is modified, see `ChunkData::snapshot`.
 #[derive(Clone)]
 pub struct ChunkData {
     /// See description in `Subchunks`
     pub subchunks: Subchunks,
     /// See `https://minecraft.wiki/w/Heightmap` for more info
     pub heightmap: ChunkHeightmaps,
+    pub biomes: ChunkBiomes,
+    pub light: ChunkLight,
+    /// The extra data of blocks like chests, keyed by their absolute position. It is kept the
+    /// way it is saved, see `https://minecraft.wiki/w/Chunk_format#Block_entity_format`
+    pub block_entities: HashMap<BlockPos, NbtCompound>,
     pub position: Vector2<i32>,
     pub dirty: bool,
 }
@@ -89,12 +116,38 @@ pub enum Subchunks {
 /// subchunk, what filled only air or only water.
 ///
 /// Multi means a normal subchunk, what contains 4096 blocks.
+///
+/// The blocks of a multi subchunk are copy-on-write, so clones share them until one is modified.
 #[derive(Clone, PartialEq, Debug)]
 pub enum Subchunk {
     Single(u16),
     // The packet relies on this ordering -> leave it like this for performance
     /// Ordering: yzx (y being the most significant)
-    Multi(Box<[u16; SUBCHUNK_VOLUME]>),
+    Multi(Arc<[u16; SUBCHUNK_VOLUME]>),
+}
+
+/// # Biomes
+/// The biomes of a chunk, one for every 4x4x4 cell of blocks.
+///
+/// Like the subchunks they are single if the whole chunk is the same biome.
+#[derive(PartialEq, Debug, Clone)]
+pub enum ChunkBiomes {
+    Single(Biome),
+    /// Ordering: yzx of the cells (y being the most significant), like the blocks
+    Multi(Box<[Biome; BIOMES_COUNT]>),
+}
+
+/// # Light
+/// The block light and sky light of a chunk, one array per section with 4 bits per block in the
+/// same order as the blocks. The first array is the section below the world.
+///
+/// Sections without an array have no light data, either because they were never lit or because
+/// the chunk was saved without light. Like the blocks, the arrays are shared between clones until
+/// one of them is modified.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ChunkLight {
+    pub block_light: [Option<Arc<[u8; LIGHT_SECTION_SIZE]>>; LIGHT_SECTIONS_COUNT],
+    pub sky_light: [Option<Arc<[u8; LIGHT_SECTION_SIZE]>>; LIGHT_SECTIONS_COUNT],
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -117,6 +170,12 @@ impl Default for ChunkHeightmaps {
     }
 }
 
+impl ChunkHeightmaps {
+    fn estimated_memory(&self) -> usize {
+        (self.motion_blocking.len() + self.world_surface.len()) * size_of::<i64>()
+    }
+}
+
 impl Subchunk {
     /// Gets the given block in the chunk
     pub fn get_block(&self, position: ChunkRelativeBlockCoordinates) -> Option<u16> {
@@ -148,10 +207,12 @@ impl Subchunk {
                     let mut blocks = Box::new([*block; SUBCHUNK_VOLUME]);
                     blocks[convert_index(position)] = new_block;
 
-                    *self = Self::Multi(blocks)
+                    *self = Self::Multi(Arc::from(blocks))
                 }
             }
             Self::Multi(blocks) => {
+                // Copies the blocks if they are still shared with a snapshot
+                let blocks = Arc::make_mut(blocks);
                 blocks[convert_index(position)] = new_block;
 
                 if blocks.iter().all(|b| *b == new_block) {
@@ -164,7 +225,7 @@ impl Subchunk {
     pub fn clone_as_array(&self) -> Box<[u16; SUBCHUNK_VOLUME]> {
         match &self {
             Self::Single(block) => Box::new([*block; SUBCHUNK_VOLUME]),
-            Self::Multi(blocks) => blocks.clone(),
+            Self::Multi(blocks) => Box::new(**blocks),
         }
     }
 }
@@ -180,6 +241,36 @@ impl Subchunks {
         }
     }
 
+    /// The positions of all blocks with the state, only looking through the subchunks which can
+    /// have it
+    pub fn find_block(&self, state_id: u16) -> Vec<ChunkRelativeBlockCoordinates> {
+        let whole_subchunk = |section: usize| {
+            (0..SUBCHUNK_VOLUME).map(move |index| convert_back_index(section, index))
+        };
+        match self {
+            Self::Single(block) if *block == state_id => {
+                (0..SUBCHUNKS_COUNT).flat_map(whole_subchunk).collect()
+            }
+            Self::Single(_) => Vec::new(),
+            Self::Multi(subchunks) => subchunks
+                .iter()
+                .enumerate()
+                .flat_map(|(section, subchunk)| match subchunk {
+                    Subchunk::Single(block) if *block == state_id => {
+                        whole_subchunk(section).collect()
+                    }
+                    Subchunk::Single(_) => Vec::new(),
+                    Subchunk::Multi(blocks) => blocks
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, block)| **block == state_id)
+                        .map(|(index, _)| convert_back_index(section, index))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
     /// Sets the given block in the chunk, returning the old block
     pub fn set_block(&mut self, position: ChunkRelativeBlockCoordinates, block_id: u16) {
         // TODO @LUK_ESC? update the heightmap
@@ -220,6 +311,38 @@ impl Subchunks {
         }
     }
 
+    /// Roughly how many bytes the blocks take up besides the enum itself
+    pub fn estimated_memory(&self) -> usize {
+        match self {
+            Self::Single(_) => 0,
+            Self::Multi(subchunks) => {
+                size_of::<[Subchunk; SUBCHUNKS_COUNT]>()
+                    + subchunks
+                        .iter()
+                        .map(|subchunk| match subchunk {
+                            Subchunk::Single(_) => 0,
+                            Subchunk::Multi(_) => size_of::<[u16; SUBCHUNK_VOLUME]>(),
+                        })
+                        .sum::<usize>()
+            }
+        }
+    }
+
+    /// Fills the whole subchunk with one block
+    pub fn fill_subchunk(&mut self, section: usize, block: u16) {
+        if let Self::Single(current) = *self {
+            if current == block {
+                return;
+            }
+            *self = Self::Multi(Box::new(std::array::from_fn(|_| Subchunk::Single(current))));
+        }
+        if let Self::Multi(subchunks) = self {
+            if let Some(subchunk) = subchunks.get_mut(section) {
+                *subchunk = Subchunk::Single(block);
+            }
+        }
+    }
+
     //TODO: Needs optimizations
     pub fn array_iter(&self) -> Box<dyn Iterator<Item = Box<[u16; SUBCHUNK_VOLUME]>> + '_> {
         match self {
@@ -233,12 +356,134 @@ impl Subchunks {
     }
 }
 
+impl Default for ChunkBiomes {
+    fn default() -> Self {
+        Self::Single(Biome::Plains)
+    }
+}
+
+impl ChunkBiomes {
+    /// Gets the biome of the cell the block is in
+    pub fn get_biome(&self, position: ChunkRelativeBlockCoordinates) -> Biome {
+        match self {
+            Self::Single(biome) => *biome,
+            Self::Multi(biomes) => biomes[convert_biome_index(position)],
+        }
+    }
+
+    /// Sets the biome of the whole cell the block is in
+    pub fn set_biome(&mut self, position: ChunkRelativeBlockCoordinates, new_biome: Biome) {
+        match self {
+            Self::Single(biome) => {
+                if *biome != new_biome {
+                    let mut biomes = Box::new([*biome; BIOMES_COUNT]);
+                    biomes[convert_biome_index(position)] = new_biome;
+                    *self = Self::Multi(biomes);
+                }
+            }
+            Self::Multi(biomes) => {
+                biomes[convert_biome_index(position)] = new_biome;
+                if biomes.iter().all(|biome| *biome == new_biome) {
+                    *self = Self::Single(new_biome);
+                }
+            }
+        }
+    }
+
+    /// The biomes of the subchunk with the given index, in the same order as they are stored
+    pub fn section(&self, index: usize) -> [Biome; BIOME_SECTION_VOLUME] {
+        match self {
+            Self::Single(biome) => [*biome; BIOME_SECTION_VOLUME],
+            Self::Multi(biomes) => {
+                let start = index * BIOME_SECTION_VOLUME;
+                biomes[start..start + BIOME_SECTION_VOLUME]
+                    .try_into()
+                    .expect("a section always has the same size")
+            }
+        }
+    }
+
+    /// Replaces the biomes of the subchunk with the given index
+    pub fn set_section(&mut self, index: usize, section: &[Biome; BIOME_SECTION_VOLUME]) {
+        if let Self::Single(biome) = self {
+            if section.iter().all(|new_biome| new_biome == biome) {
+                return;
+            }
+            *self = Self::Multi(Box::new([*biome; BIOMES_COUNT]));
+        }
+        if let Self::Multi(biomes) = self {
+            let start = index * BIOME_SECTION_VOLUME;
+            biomes[start..start + BIOME_SECTION_VOLUME].copy_from_slice(section);
+            if biomes.iter().all(|biome| *biome == section[0]) {
+                *self = Self::Single(section[0]);
+            }
+        }
+    }
+}
+
+impl ChunkLight {
+    fn get(
+        sections: &[Option<Arc<[u8; LIGHT_SECTION_SIZE]>>; LIGHT_SECTIONS_COUNT],
+        position: ChunkRelativeBlockCoordinates,
+    ) -> Option<u8> {
+        let (section, index) = convert_light_index(position);
+        let light = sections.get(section)?.as_ref()?;
+        Some((light[index / 2] >> (index % 2 * 4)) & 0xF)
+    }
+
+    fn set(
+        sections: &mut [Option<Arc<[u8; LIGHT_SECTION_SIZE]>>; LIGHT_SECTIONS_COUNT],
+        position: ChunkRelativeBlockCoordinates,
+        level: u8,
+    ) {
+        let (section, index) = convert_light_index(position);
+        let Some(light) = sections.get_mut(section) else {
+            return;
+        };
+        // Copies the light if it is still shared with a snapshot
+        let light = Arc::make_mut(light.get_or_insert_with(|| Arc::new([0; LIGHT_SECTION_SIZE])));
+        let shift = index % 2 * 4;
+        light[index / 2] = (light[index / 2] & !(0xF << shift)) | ((level & 0xF) << shift);
+    }
+}
+
 impl ChunkData {
+    /// Takes an immutable copy of the chunk in its current state, e.g. to serialize it without
+    /// holding the chunk lock.
+    ///
+    /// This only clones the subchunk handles, block data is copied lazily by the next write to
+    /// either the chunk or the snapshot.
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
     /// Gets the given block in the chunk
     pub fn get_block(&self, position: ChunkRelativeBlockCoordinates) -> Option<u16> {
         self.subchunks.get_block(position)
     }
 
+    /// Roughly how many bytes the chunk takes up in memory. Data shared with snapshots is
+    /// counted for every one of them
+    pub fn estimated_memory(&self) -> usize {
+        let biomes = match &self.biomes {
+            ChunkBiomes::Single(_) => 0,
+            ChunkBiomes::Multi(_) => size_of::<[Biome; BIOMES_COUNT]>(),
+        };
+        let light_sections = self
+            .light
+            .block_light
+            .iter()
+            .chain(&self.light.sky_light)
+            .filter(|section| section.is_some())
+            .count();
+        size_of::<Self>()
+            + self.subchunks.estimated_memory()
+            + biomes
+            + light_sections * LIGHT_SECTION_SIZE
+            + self.heightmap.estimated_memory()
+            + self.block_entities.len() * BLOCK_ENTITY_MEMORY
+    }
+
     /// Sets the given block in the chunk, returning the old block
     pub fn set_block(&mut self, position: ChunkRelativeBlockCoordinates, block_id: u16) {
         // TODO @LUK_ESC? update the heightmap
@@ -259,6 +504,48 @@ impl ChunkData {
             .set_block_no_heightmap_update(position, block);
     }
 
+    /// The block light at the position, `None` if its section has no light data
+    pub fn get_block_light(&self, position: ChunkRelativeBlockCoordinates) -> Option<u8> {
+        ChunkLight::get(&self.light.block_light, position)
+    }
+
+    /// Sets the block light at the position, levels above 15 are cut off
+    pub fn set_block_light(&mut self, position: ChunkRelativeBlockCoordinates, level: u8) {
+        ChunkLight::set(&mut self.light.block_light, position, level);
+    }
+
+    /// The sky light at the position, `None` if its section has no light data
+    pub fn get_sky_light(&self, position: ChunkRelativeBlockCoordinates) -> Option<u8> {
+        ChunkLight::get(&self.light.sky_light, position)
+    }
+
+    /// Sets the sky light at the position, levels above 15 are cut off
+    pub fn set_sky_light(&mut self, position: ChunkRelativeBlockCoordinates, level: u8) {
+        ChunkLight::set(&mut self.light.sky_light, position, level);
+    }
+
+    /// The block entity of the block at the absolute position
+    pub fn get_block_entity(&self, position: &BlockPos) -> Option<&NbtCompound> {
+        self.block_entities.get(position)
+    }
+
+    /// Sets the block entity of a block, the id like `minecraft:chest` and the position are
+    /// written into the data
+    pub fn set_block_entity(&mut self, id: &str, position: BlockPos, mut nbt: NbtCompound) {
+        nbt.child_tags
+            .retain(|(name, _)| !matches!(name.as_str(), "id" | "x" | "y" | "z"));
+        nbt.put_string("id", id.to_string());
+        nbt.put_int("x", position.0.x);
+        nbt.put_int("y", position.0.y);
+        nbt.put_int("z", position.0.z);
+        self.block_entities.insert(position, nbt);
+    }
+
+    /// Removes the block entity of a block, returning it
+    pub fn remove_block_entity(&mut self, position: &BlockPos) -> Option<NbtCompound> {
+        self.block_entities.remove(position)
+    }
+
     #[expect(dead_code)]
     fn calculate_heightmap(&self) -> ChunkHeightmaps {
         // figure out how LongArray is formatted
@@ -281,8 +568,130 @@ fn convert_index(index: ChunkRelativeBlockCoordinates) -> usize {
     // % works for negative numbers as intended.
     (index.y.get_absolute() % 16) as usize * CHUNK_AREA + *index.z as usize * 16 + *index.x as usize
 }
+/// The position of the block at `index` in the subchunk `section`, the opposite of `convert_index`
+fn convert_back_index(section: usize, index: usize) -> ChunkRelativeBlockCoordinates {
+    ChunkRelativeBlockCoordinates {
+        x: (index % 16).into(),
+        y: Height::from_absolute((section * 16 + index / CHUNK_AREA) as u16),
+        z: (index / 16 % 16).into(),
+    }
+}
+/// The light section of the block and the index of its 4 bits in there
+fn convert_light_index(index: ChunkRelativeBlockCoordinates) -> (usize, usize) {
+    // Skip the section below the world
+    let section = (index.y.get_absolute() / 16) as usize + 1;
+    (section, convert_index(index))
+}
+fn convert_biome_index(index: ChunkRelativeBlockCoordinates) -> usize {
+    (index.y.get_absolute() / 4) as usize * 16
+        + (*index.z / 4) as usize * 4
+        + (*index.x / 4) as usize
+}
+
 #[derive(Error, Debug)]
 pub enum ChunkSerializingError {
     #[error("Error serializing chunk: {0}")]
     ErrorSerializingChunk(pumpkin_nbt::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+    use std::collections::HashMap;
+
+    use pumpkin_data::chunk::Biome;
+
+    use super::{ChunkBiomes, ChunkData, ChunkHeightmaps, ChunkLight, SUBCHUNK_VOLUME, Subchunks};
+    use crate::coordinates::ChunkRelativeBlockCoordinates;
+
+    #[test]
+    fn find_block_positions() {
+        let mut subchunks = Subchunks::Single(0);
+        assert!(subchunks.find_block(7).is_empty());
+
+        let positions = [
+            ChunkRelativeBlockCoordinates::from(Vector3::new(3, -64, 15)),
+            ChunkRelativeBlockCoordinates::from(Vector3::new(0, 70, 2)),
+            ChunkRelativeBlockCoordinates::from(Vector3::new(15, 319, 9)),
+        ];
+        for position in positions {
+            subchunks.set_block(position, 7);
+        }
+        assert_eq!(subchunks.find_block(7), positions);
+    }
+
+    #[test]
+    fn estimated_memory_grows_with_sections() {
+        let mut chunk = ChunkData {
+            subchunks: Subchunks::Single(0),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: ChunkBiomes::default(),
+            light: ChunkLight::default(),
+            block_entities: HashMap::new(),
+            position: Vector2::new(0, 0),
+            dirty: false,
+        };
+        chunk.set_block(Vector3::new(1, 0, 1).into(), 1);
+        let one_section = chunk.estimated_memory();
+        chunk.set_block(Vector3::new(1, 100, 1).into(), 1);
+        assert_eq!(
+            chunk.estimated_memory() - one_section,
+            SUBCHUNK_VOLUME * size_of::<u16>()
+        );
+        // Setting a block in a section which has blocks already costs nothing
+        chunk.set_block(Vector3::new(2, 100, 1).into(), 1);
+        assert_eq!(
+            chunk.estimated_memory() - one_section,
+            SUBCHUNK_VOLUME * size_of::<u16>()
+        );
+    }
+
+    #[test]
+    fn snapshot_is_isolated_from_later_writes() {
+        let mut chunk = ChunkData {
+            subchunks: Subchunks::Single(0),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: ChunkBiomes::default(),
+            light: ChunkLight::default(),
+            block_entities: HashMap::new(),
+            position: Vector2::new(0, 0),
+            dirty: false,
+        };
+        let first = Vector3::new(1, 0, 1).into();
+        let second = Vector3::new(2, 0, 2).into();
+        chunk.set_block(first, 1);
+
+        let snapshot = chunk.snapshot();
+        chunk.set_block(first, 2);
+        chunk.set_block(second, 3);
+
+        assert_eq!(snapshot.get_block(first), Some(1));
+        assert_eq!(snapshot.get_block(second), Some(0));
+        assert_eq!(chunk.get_block(first), Some(2));
+        assert_eq!(chunk.get_block(second), Some(3));
+    }
+
+    #[test]
+    fn biomes_are_set_per_cell() {
+        let mut biomes = ChunkBiomes::default();
+        let position = Vector3::new(5, 70, 2).into();
+        biomes.set_biome(position, Biome::Desert);
+
+        // The other blocks of the 4x4x4 cell share the biome
+        assert_eq!(
+            biomes.get_biome(Vector3::new(7, 68, 0).into()),
+            Biome::Desert
+        );
+        assert_eq!(
+            biomes.get_biome(Vector3::new(8, 70, 2).into()),
+            Biome::Plains
+        );
+        assert_eq!(
+            biomes.get_biome(Vector3::new(5, 72, 2).into()),
+            Biome::Plains
+        );
+
+        biomes.set_biome(position, Biome::Plains);
+        assert_eq!(biomes, ChunkBiomes::Single(Biome::Plains));
+    }
+}
@@ -1,10 +1,10 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
 use crate::level::Level;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Dimension {
     OverWorld,
@@ -13,12 +13,29 @@ pub enum Dimension {
 }
 
 impl Dimension {
-    pub fn into_level(&self, mut base_directory: PathBuf) -> Level {
+    pub fn into_level(&self, base_directory: PathBuf) -> Level {
+        Level::from_dimension(base_directory, *self)
+    }
+
+    /// How many blocks in the overworld one block in this dimension stands for, horizontally
+    pub const fn coordinate_scale(&self) -> f64 {
+        match self {
+            Dimension::Nether => 8.0,
+            Dimension::OverWorld | Dimension::End => 1.0,
+        }
+    }
+
+    /// Scales a horizontal coordinate in this dimension to the same place in `to`
+    pub fn scale_to(&self, coordinate: f64, to: Dimension) -> f64 {
+        coordinate * self.coordinate_scale() / to.coordinate_scale()
+    }
+
+    /// The folder of the dimension inside the world folder, like vanilla has them
+    pub fn folder(&self, world_folder: &Path) -> PathBuf {
         match self {
-            Dimension::OverWorld => {}
-            Dimension::Nether => base_directory.push("DIM-1"),
-            Dimension::End => base_directory.push("DIM1"),
+            Dimension::OverWorld => world_folder.to_path_buf(),
+            Dimension::Nether => world_folder.join("DIM-1"),
+            Dimension::End => world_folder.join("DIM1"),
         }
-        Level::from_root_folder(base_directory)
     }
 }
@@ -27,6 +27,8 @@ use super::{
 };
 
 pub const STONE_BLOCK: ChunkBlockState = block_state!("stone");
+pub const END_STONE_BLOCK: ChunkBlockState = block_state!("end_stone");
+pub const NETHERRACK_BLOCK: ChunkBlockState = block_state!("netherrack");
 pub const LAVA_BLOCK: ChunkBlockState = block_state!("lava");
 pub const WATER_BLOCK: ChunkBlockState = block_state!("water");
 
@@ -205,6 +207,7 @@ pub struct ChunkNoiseGenerator<'a> {
 
     start_cell_pos: Vector2<i32>,
 
+    horizontal_cell_count: u8,
     vertical_cell_count: usize,
     minimum_cell_y: i32,
 
@@ -305,6 +308,7 @@ impl<'a> ChunkNoiseGenerator<'a> {
 
             start_cell_pos,
 
+            horizontal_cell_count,
             vertical_cell_count,
             minimum_cell_y,
 
@@ -330,7 +334,8 @@ impl<'a> ChunkNoiseGenerator<'a> {
     fn sample_density(&mut self, start: bool, current_x: i32) {
         let x = current_x * self.horizontal_cell_block_count() as i32;
 
-        for cell_z in 0..=self.horizontal_cell_block_count() {
+        // The corners of the cells, so one more than there are cells
+        for cell_z in 0..=self.horizontal_cell_count {
             let current_cell_z_pos = self.start_cell_pos.z + cell_z as i32;
             let z = current_cell_z_pos * self.horizontal_cell_block_count() as i32;
             self.cache_fill_unique_id += 1;
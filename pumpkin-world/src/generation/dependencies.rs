@@ -0,0 +1,341 @@
+//! Makes sure the features of a chunk, like trees, only run once the chunks they can spill into
+//! exist, and that a chunk is only handed out once every feature which can reach into it ran.
+//!
+//! A chunk first gets its terrain (`ChunkStatus::Noise`), then its features
+//! (`ChunkStatus::Features`), which may place blocks up to `WorldGenerator::feature_reach` chunks
+//! away. Before a chunk becomes `ChunkStatus::Full` all chunks within that reach have to reach
+//! `ChunkStatus::Features`, which in turn needs the terrain of all chunks within twice the reach.
+//!
+//! Chunks which did not reach `ChunkStatus::Full` only live in memory, they are generated again
+//! after a restart. Once the level unloads a chunk it is forgotten here as well, together with the
+//! unfinished chunks around it which no loaded chunk can need anymore, so only the area around the
+//! loaded chunks is kept. Light is not part of the pipeline yet.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::chunk::ChunkData;
+
+use super::WorldGenerator;
+
+/// How far a chunk got in generation, in order
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ChunkStatus {
+    /// The terrain is generated
+    Noise,
+    /// The features of the chunk itself ran, features of its neighbors may still change it
+    Features,
+    /// Nothing changes the chunk anymore, it can be loaded into the world
+    Full,
+}
+
+/// The chunks a feature may change, the chunk being populated and its neighbors within the
+/// feature reach of the generator
+pub struct GenerationRegion {
+    center: Vector2<i32>,
+    chunks: HashMap<Vector2<i32>, ChunkData>,
+}
+
+impl GenerationRegion {
+    /// The chunk whose features are generated
+    pub fn center(&self) -> Vector2<i32> {
+        self.center
+    }
+
+    pub fn get_block(&self, position: &BlockPos) -> Option<u16> {
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        self.chunks.get(&chunk)?.get_block(relative.into())
+    }
+
+    /// Sets the block if it is in the region. Returns false for chunks which are already full or
+    /// out of reach, the block is dropped then
+    pub fn set_block(&mut self, position: &BlockPos, block_id: u16) -> bool {
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let Some(chunk) = self.chunks.get_mut(&chunk) else {
+            return false;
+        };
+        chunk.set_block(relative.into(), block_id);
+        true
+    }
+}
+
+struct ProtoChunk {
+    status: ChunkStatus,
+    chunk: ChunkData,
+}
+
+/// Generates chunks with their neighbors up to the status the features need, see the module
+/// documentation
+pub struct ChunkDependencies {
+    world_gen: Arc<dyn WorldGenerator>,
+    /// Chunks which are not full yet
+    pending: Mutex<HashMap<Vector2<i32>, ProtoChunk>>,
+    /// Chunks which are full or were loaded from disk and are still loaded in the level, features
+    /// no longer change them
+    done: Mutex<HashSet<Vector2<i32>>>,
+}
+
+impl ChunkDependencies {
+    pub fn new(world_gen: Arc<dyn WorldGenerator>) -> Self {
+        Self {
+            world_gen,
+            pending: Mutex::new(HashMap::new()),
+            done: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Generates the chunk up to `ChunkStatus::Full`, together with whatever its neighbors need.
+    /// This blocks, so it should be run on the rayon pool
+    pub fn generate(&self, at: Vector2<i32>) -> ChunkData {
+        let reach = self.world_gen.feature_reach();
+        if reach <= 0 {
+            return self.generate_alone(at);
+        }
+
+        // The terrain is generated in parallel without holding the lock, two threads asking for
+        // the same neighbor may generate it twice, only the first one is kept
+        let missing: Vec<_> = {
+            let pending = self.pending.lock().unwrap();
+            let done = self.done.lock().unwrap();
+            square(at, reach * 2)
+                .filter(|pos| !pending.contains_key(pos) && !done.contains(pos))
+                .collect()
+        };
+        let generated: Vec<_> = missing
+            .into_par_iter()
+            .map(|pos| (pos, self.world_gen.generate_chunk(pos)))
+            .collect();
+
+        let mut pending = self.pending.lock().unwrap();
+        for (pos, chunk) in generated {
+            pending.entry(pos).or_insert(ProtoChunk {
+                status: ChunkStatus::Noise,
+                chunk,
+            });
+        }
+
+        for pos in square(at, reach) {
+            if pending
+                .get(&pos)
+                .is_none_or(|proto| proto.status != ChunkStatus::Noise)
+            {
+                continue;
+            }
+            let mut statuses = HashMap::new();
+            let mut chunks = HashMap::new();
+            for neighbor in square(pos, reach) {
+                if let Some(proto) = pending.remove(&neighbor) {
+                    statuses.insert(neighbor, proto.status);
+                    chunks.insert(neighbor, proto.chunk);
+                }
+            }
+            let mut region = GenerationRegion {
+                center: pos,
+                chunks,
+            };
+            self.world_gen.populate(&mut region);
+            for (neighbor, chunk) in region.chunks {
+                let status = if neighbor == pos {
+                    ChunkStatus::Features
+                } else {
+                    statuses[&neighbor]
+                };
+                pending.insert(neighbor, ProtoChunk { status, chunk });
+            }
+        }
+
+        let proto = pending.remove(&at);
+        drop(pending);
+        self.done.lock().unwrap().insert(at);
+        match proto {
+            Some(proto) => proto.chunk,
+            // It was already handed out once and got requested again before it was saved
+            None => self.generate_alone(at),
+        }
+    }
+
+    /// Marks a chunk which was loaded from disk, so features of its neighbors leave it alone
+    pub fn loaded(&self, at: Vector2<i32>) {
+        if self.world_gen.feature_reach() <= 0 {
+            return;
+        }
+        self.pending.lock().unwrap().remove(&at);
+        self.done.lock().unwrap().insert(at);
+    }
+
+    /// Forgets chunks the level unloaded, together with the unfinished chunks which were only
+    /// kept for them. If a forgotten chunk is needed again it is loaded from disk and marked again
+    pub fn unloaded(&self, chunks: &[Vector2<i32>]) {
+        let reach = self.world_gen.feature_reach();
+        if reach <= 0 {
+            return;
+        }
+        // Always `pending` before `done`, like in `generate`
+        let mut pending = self.pending.lock().unwrap();
+        let mut done = self.done.lock().unwrap();
+        for pos in chunks {
+            done.remove(pos);
+        }
+        for pos in chunks {
+            for neighbor in square(*pos, reach * 2) {
+                if pending.contains_key(&neighbor)
+                    && !square(neighbor, reach * 2).any(|other| done.contains(&other))
+                {
+                    pending.remove(&neighbor);
+                }
+            }
+        }
+    }
+
+    /// Generates the chunk with the features which stay inside of it
+    fn generate_alone(&self, at: Vector2<i32>) -> ChunkData {
+        let mut region = GenerationRegion {
+            center: at,
+            chunks: HashMap::from([(at, self.world_gen.generate_chunk(at))]),
+        };
+        self.world_gen.populate(&mut region);
+        region
+            .chunks
+            .remove(&at)
+            .expect("The center is in the region")
+    }
+}
+
+/// The chunks at most `radius` chunks away from `center` on both axes
+fn square(center: Vector2<i32>, radius: i32) -> impl Iterator<Item = Vector2<i32>> {
+    (-radius..=radius).flat_map(move |x| {
+        (-radius..=radius).map(move |z| Vector2::new(center.x + x, center.z + z))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{Arc, mpsc},
+        thread,
+        time::Duration,
+    };
+
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+
+    use crate::{
+        chunk::{ChunkData, Subchunks},
+        coordinates::ChunkRelativeBlockCoordinates,
+        generation::WorldGenerator,
+    };
+
+    use super::{ChunkDependencies, GenerationRegion};
+
+    /// Places a block in the corner of every chunk and one in the chunk to the west of it
+    struct BorderGenerator;
+
+    impl WorldGenerator for BorderGenerator {
+        fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+            ChunkData {
+                subchunks: Subchunks::Single(0),
+                heightmap: Default::default(),
+                biomes: Default::default(),
+                light: Default::default(),
+                block_entities: Default::default(),
+                position: at,
+                dirty: true,
+            }
+        }
+
+        fn feature_reach(&self) -> i32 {
+            1
+        }
+
+        fn populate(&self, region: &mut GenerationRegion) {
+            let corner = region.center() * 16;
+            for x in [corner.x - 1, corner.x] {
+                region.set_block(&BlockPos(Vector3::new(x, 64, corner.z)), 1);
+            }
+        }
+    }
+
+    fn block(chunk: &ChunkData, x: u8) -> Option<u16> {
+        chunk.get_block(ChunkRelativeBlockCoordinates {
+            x: x.into(),
+            y: 64.into(),
+            z: 0u8.into(),
+        })
+    }
+
+    #[test]
+    fn features_cross_chunk_borders() {
+        let dependencies = ChunkDependencies::new(Arc::new(BorderGenerator));
+        for x in [0, 1] {
+            let chunk = dependencies.generate(Vector2::new(x, 0));
+            // Its own block and the one of the chunk to the east
+            assert_eq!(block(&chunk, 0), Some(1));
+            assert_eq!(block(&chunk, 15), Some(1));
+        }
+    }
+
+    #[test]
+    fn loaded_chunks_are_left_alone() {
+        let dependencies = ChunkDependencies::new(Arc::new(BorderGenerator));
+        dependencies.loaded(Vector2::new(-1, 0));
+        dependencies.loaded(Vector2::new(1, 0));
+        // The block for the chunk to the west is dropped, the features of the chunk to the east
+        // ran before it was saved
+        let chunk = dependencies.generate(Vector2::new(0, 0));
+        assert_eq!(block(&chunk, 0), Some(1));
+        assert_eq!(block(&chunk, 15), Some(0));
+    }
+
+    #[test]
+    fn unloaded_chunks_are_forgotten() {
+        let dependencies = ChunkDependencies::new(Arc::new(BorderGenerator));
+        dependencies.generate(Vector2::new(0, 0));
+        dependencies.generate(Vector2::new(1, 0));
+        assert!(!dependencies.pending.lock().unwrap().is_empty());
+
+        dependencies.unloaded(&[Vector2::new(0, 0)]);
+        // Still kept for the chunk which is loaded
+        assert!(
+            dependencies
+                .pending
+                .lock()
+                .unwrap()
+                .contains_key(&Vector2::new(2, 0))
+        );
+
+        dependencies.unloaded(&[Vector2::new(1, 0)]);
+        assert!(dependencies.pending.lock().unwrap().is_empty());
+        assert!(dependencies.done.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unloading_while_generating() {
+        const THREADS: usize = 4;
+        let dependencies = Arc::new(ChunkDependencies::new(Arc::new(BorderGenerator)));
+        let (sender, receiver) = mpsc::channel();
+        for thread in 0..THREADS * 2 {
+            let dependencies = dependencies.clone();
+            let sender = sender.clone();
+            thread::spawn(move || {
+                for x in 0..10_000 {
+                    let at = Vector2::new(x % 8, 0);
+                    if thread % 2 == 0 {
+                        dependencies.unloaded(&[at]);
+                    } else {
+                        dependencies.generate(at);
+                    }
+                }
+                sender.send(()).unwrap();
+            });
+        }
+        for _ in 0..THREADS * 2 {
+            receiver
+                .recv_timeout(Duration::from_secs(30))
+                .expect("Generating and unloading block each other");
+        }
+    }
+}
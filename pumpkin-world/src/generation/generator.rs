@@ -8,12 +8,24 @@ use crate::chunk::{ChunkData, Subchunks};
 use crate::coordinates::{BlockCoordinates, ChunkRelativeBlockCoordinates, XZBlockCoordinates};
 use crate::generation::Seed;
 
+use super::dependencies::GenerationRegion;
+
 pub trait GeneratorInit {
     fn new(seed: Seed) -> Self;
 }
 
 pub trait WorldGenerator: Sync + Send {
+    /// Generates the terrain of the chunk
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData;
+
+    /// How many chunks away from their chunk the features may place blocks, see
+    /// `GenerationRegion`
+    fn feature_reach(&self) -> i32 {
+        0
+    }
+
+    /// Places the features of the chunk in the middle of the region, like trees
+    fn populate(&self, _region: &mut GenerationRegion) {}
 }
 
 pub(crate) trait BiomeGenerator: Sync + Send {
@@ -2,8 +2,8 @@ use noise::{NoiseFn, Perlin};
 use pumpkin_util::math::vector2::Vector2;
 
 use crate::{
-    WORLD_LOWEST_Y,
-    chunk::{ChunkData, Subchunks},
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+    chunk::{ChunkBiomes, ChunkData, Subchunks},
     coordinates::{ChunkRelativeBlockCoordinates, ChunkRelativeXZBlockCoordinates},
 };
 
@@ -35,6 +35,7 @@ impl<B: BiomeGenerator + GeneratorInit, T: PerlinTerrainGenerator + GeneratorIni
 impl<B: BiomeGenerator, T: PerlinTerrainGenerator> WorldGenerator for GenericGenerator<B, T> {
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
         let mut subchunks = Subchunks::Single(0);
+        let mut biomes = ChunkBiomes::default();
         self.terrain_generator.prepare_chunk(&at, &self.perlin);
         let noise_value = self.perlin.get([at.x as f64 / 16.0, at.z as f64 / 16.0]);
 
@@ -52,6 +53,18 @@ impl<B: BiomeGenerator, T: PerlinTerrainGenerator> WorldGenerator for GenericGen
                     .with_chunk_coordinates(at),
                 );
 
+                // Biomes are stored per 4x4x4 cell
+                if x % 4 == 0 && z % 4 == 0 {
+                    for y in (WORLD_LOWEST_Y..WORLD_MAX_Y).step_by(4) {
+                        let coordinates = ChunkRelativeBlockCoordinates {
+                            x: x.into(),
+                            y: y.into(),
+                            z: z.into(),
+                        };
+                        biomes.set_biome(coordinates, biome);
+                    }
+                }
+
                 // Iterate from the highest block to the lowest, in order to minimize the heightmap updates
                 for y in (WORLD_LOWEST_Y..chunk_height).rev() {
                     let coordinates = ChunkRelativeBlockCoordinates {
@@ -75,6 +88,9 @@ impl<B: BiomeGenerator, T: PerlinTerrainGenerator> WorldGenerator for GenericGen
         ChunkData {
             subchunks,
             heightmap: Default::default(),
+            biomes,
+            light: Default::default(),
+            block_entities: Default::default(),
             position: at,
             // We just generated this chunk! Mark it as dirty
             dirty: true,
@@ -0,0 +1,265 @@
+use std::f64::consts::PI;
+
+use pumpkin_data::chunk::Biome;
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    random::{RandomImpl, legacy_rand::LegacyRand},
+};
+
+use super::noise_chunk_data;
+use crate::{
+    block::ChunkBlockState,
+    chunk::ChunkData,
+    generation::{
+        GlobalRandomConfig, Seed, WorldGenerator, dependencies::GenerationRegion,
+        generation_shapes::GenerationShape, generator::GeneratorInit,
+        noise_router::proto_noise_router::GlobalProtoNoiseRouter, proto_chunk::ProtoChunk,
+    },
+    noise_router::NOISE_ROUTER_ASTS,
+};
+
+/// Chunks further away from the center than this belong to the outer islands
+const MAIN_ISLAND_CHUNK_RADIUS: i64 = 64;
+
+/// How far the obsidian pillars stand from the center of the End
+const SPIKE_DISTANCE: f64 = 42.0;
+/// Below this the space around a pillar is not cleared, so it doesn't cut into the island
+const SPIKE_CLEAR_FROM_Y: i32 = 66;
+
+/// One of the obsidian pillars around the exit portal, an end crystal stands on top of each
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EndSpike {
+    pub center_x: i32,
+    pub center_z: i32,
+    pub radius: i32,
+    /// The y of the bedrock block on top, the crystal stands above it
+    pub height: i32,
+    /// Whether iron bars surround the crystal
+    pub guarded: bool,
+}
+
+impl EndSpike {
+    /// Where the end crystal of the pillar stands
+    pub fn crystal_position(&self) -> Vector3<f64> {
+        Vector3::new(
+            f64::from(self.center_x) + 0.5,
+            f64::from(self.height + 1),
+            f64::from(self.center_z) + 0.5,
+        )
+    }
+
+    fn place(&self, region: &mut GenerationRegion) {
+        let id = |name| ChunkBlockState::new(name).unwrap().state_id;
+        let (obsidian, air) = (id("obsidian"), id("air"));
+        let radius_squared = self.radius * self.radius + 1;
+        for x in self.center_x - self.radius..=self.center_x + self.radius {
+            for z in self.center_z - self.radius..=self.center_z + self.radius {
+                let (dx, dz) = (x - self.center_x, z - self.center_z);
+                let inside = dx * dx + dz * dz <= radius_squared;
+                let from_y = if inside {
+                    i32::from(GenerationShape::END.min_y())
+                } else {
+                    SPIKE_CLEAR_FROM_Y
+                };
+                for y in from_y..self.height + 10 {
+                    let block = if inside && y < self.height {
+                        obsidian
+                    } else {
+                        air
+                    };
+                    region.set_block(&BlockPos(Vector3::new(x, y, z)), block);
+                }
+            }
+        }
+
+        if self.guarded {
+            let iron_bars = id("iron_bars");
+            for dx in -2..=2i32 {
+                for dz in -2..=2i32 {
+                    for dy in 0..=3 {
+                        if dx.abs() == 2 || dz.abs() == 2 || dy == 3 {
+                            let position = Vector3::new(
+                                self.center_x + dx,
+                                self.height + dy,
+                                self.center_z + dz,
+                            );
+                            region.set_block(&BlockPos(position), iron_bars);
+                        }
+                    }
+                }
+            }
+        }
+
+        let top = Vector3::new(self.center_x, self.height, self.center_z);
+        region.set_block(&BlockPos(top), id("bedrock"));
+    }
+}
+
+/// The ten pillars of the End like vanilla places them for the seed, evenly spread on a circle
+/// with their size shuffled
+pub fn end_spikes(seed: u64) -> Vec<EndSpike> {
+    let mut random = LegacyRand::from_seed(seed);
+    let mut random = LegacyRand::from_seed((random.next_i64() & 0xFFFF) as u64);
+    let mut sizes: Vec<i32> = (0..10).collect();
+    for i in (2..=sizes.len()).rev() {
+        let j = random.next_bounded_i32(i as i32) as usize;
+        sizes.swap(i - 1, j);
+    }
+
+    sizes
+        .into_iter()
+        .enumerate()
+        .map(|(i, size)| {
+            let angle = 2.0 * (-PI + PI / 10.0 * i as f64);
+            EndSpike {
+                center_x: (SPIKE_DISTANCE * angle.cos()).floor() as i32,
+                center_z: (SPIKE_DISTANCE * angle.sin()).floor() as i32,
+                radius: 2 + size / 3,
+                height: 76 + size * 3,
+                guarded: size == 1 || size == 2,
+            }
+        })
+        .collect()
+}
+
+/// Generates the central island of the End and the islands around it
+pub struct EndGenerator {
+    random_config: GlobalRandomConfig,
+    base_router: GlobalProtoNoiseRouter,
+    spikes: Vec<EndSpike>,
+}
+
+impl GeneratorInit for EndGenerator {
+    fn new(seed: Seed) -> Self {
+        let random_config = GlobalRandomConfig::new(seed.0);
+        let base_router = GlobalProtoNoiseRouter::generate(&NOISE_ROUTER_ASTS.end, &random_config);
+        Self {
+            random_config,
+            base_router,
+            spikes: end_spikes(seed.0),
+        }
+    }
+}
+
+// TODO: The outer islands have their highlands, midlands and barrens depending on the erosion
+fn biome_at(chunk: Vector2<i32>) -> Biome {
+    let (x, z) = (i64::from(chunk.x), i64::from(chunk.z));
+    if x * x + z * z <= MAIN_ISLAND_CHUNK_RADIUS * MAIN_ISLAND_CHUNK_RADIUS {
+        Biome::TheEnd
+    } else {
+        Biome::SmallEndIslands
+    }
+}
+
+impl WorldGenerator for EndGenerator {
+    fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+        let mut proto_chunk = ProtoChunk::new_end(at, &self.base_router, &self.random_config);
+        proto_chunk.populate_noise();
+        noise_chunk_data(&proto_chunk, at, &GenerationShape::END, biome_at(at))
+    }
+
+    fn feature_reach(&self) -> i32 {
+        // The widest pillar is 5 blocks wide around its center
+        1
+    }
+
+    fn populate(&self, region: &mut GenerationRegion) {
+        // Each pillar is placed with the chunk its center is in
+        let center = region.center();
+        for spike in &self.spikes {
+            if spike.center_x >> 4 == center.x && spike.center_z >> 4 == center.z {
+                spike.place(region);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::chunk::Biome;
+    use pumpkin_util::math::vector2::Vector2;
+
+    use crate::{
+        block::ChunkBlockState,
+        coordinates::ChunkRelativeBlockCoordinates,
+        generation::{
+            Seed, WorldGenerator, dependencies::ChunkDependencies, generator::GeneratorInit,
+        },
+    };
+
+    use super::{EndGenerator, biome_at, end_spikes};
+
+    #[test]
+    fn central_island_and_void() {
+        let generator = EndGenerator::new(Seed(0));
+        let end_stone = ChunkBlockState::new("end_stone").unwrap().state_id;
+        let column = |chunk: Vector2<i32>| {
+            let chunk = generator.generate_chunk(chunk);
+            (0..128i16)
+                .filter(|&y| {
+                    chunk.subchunks.get_block(ChunkRelativeBlockCoordinates {
+                        x: 8u8.into(),
+                        y: y.into(),
+                        z: 8u8.into(),
+                    }) == Some(end_stone)
+                })
+                .count()
+        };
+
+        // The middle of the central island is solid end stone, while the void around it is empty
+        assert!(column(Vector2::new(0, 0)) > 10);
+        assert_eq!(column(Vector2::new(50, 0)), 0);
+        // The outer islands start about a thousand blocks out
+        assert!(column(Vector2::new(100, 0)) > 0);
+
+        assert_eq!(biome_at(Vector2::new(0, 0)), Biome::TheEnd);
+        assert_eq!(biome_at(Vector2::new(100, 0)), Biome::SmallEndIslands);
+    }
+
+    #[test]
+    fn spikes_around_the_center() {
+        let spikes = end_spikes(0);
+        assert_eq!(spikes.len(), 10);
+        // Every size is used once
+        let mut heights: Vec<_> = spikes.iter().map(|spike| spike.height).collect();
+        heights.sort_unstable();
+        assert_eq!(
+            heights,
+            (0..10).map(|size| 76 + size * 3).collect::<Vec<_>>()
+        );
+        assert_eq!(spikes.iter().filter(|spike| spike.guarded).count(), 2);
+        assert_eq!(spikes, end_spikes(0));
+    }
+
+    #[test]
+    fn spikes_cross_chunk_borders() {
+        let generator = Arc::new(EndGenerator::new(Seed(0)));
+        let spike = generator.spikes[0];
+        let dependencies = ChunkDependencies::new(generator);
+        let obsidian = ChunkBlockState::new("obsidian").unwrap().state_id;
+        let bedrock = ChunkBlockState::new("bedrock").unwrap().state_id;
+        let block = |x: i32, y: i32, z: i32| {
+            let chunk = dependencies.generate(Vector2::new(x >> 4, z >> 4));
+            chunk.subchunks.get_block(ChunkRelativeBlockCoordinates {
+                x: ((x & 15) as u8).into(),
+                y: (y as i16).into(),
+                z: ((z & 15) as u8).into(),
+            })
+        };
+
+        assert_eq!(
+            block(spike.center_x, spike.height, spike.center_z),
+            Some(bedrock)
+        );
+        for (x, z) in [
+            (spike.center_x - spike.radius, spike.center_z),
+            (spike.center_x + spike.radius, spike.center_z),
+            (spike.center_x, spike.center_z - spike.radius),
+            (spike.center_x, spike.center_z + spike.radius),
+        ] {
+            assert_eq!(block(x, spike.height - 1, z), Some(obsidian));
+        }
+    }
+}
@@ -1,3 +1,58 @@
+use pumpkin_data::chunk::Biome;
+use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+
+use crate::{
+    chunk::{ChunkBiomes, ChunkData, Subchunks},
+    coordinates::ChunkRelativeBlockCoordinates,
+    generation::{generation_shapes::GenerationShape, proto_chunk::ProtoChunk},
+};
+
+pub mod end;
+pub mod nether;
 pub mod overworld;
 pub mod superflat;
 pub mod test;
+
+/// Turns the populated noise of a chunk, which is within `shape`, into a chunk of a single biome
+fn noise_chunk_data(
+    proto_chunk: &ProtoChunk,
+    at: Vector2<i32>,
+    shape: &GenerationShape,
+    biome: Biome,
+) -> ChunkData {
+    let mut subchunks = Subchunks::Single(0);
+    let mut biomes = ChunkBiomes::default();
+    let min_y = i16::from(shape.min_y());
+    let max_y = min_y + shape.height() as i16;
+    for x in 0..16u8 {
+        for z in 0..16u8 {
+            for y in (min_y..max_y).rev() {
+                let coordinates = ChunkRelativeBlockCoordinates {
+                    x: x.into(),
+                    y: y.into(),
+                    z: z.into(),
+                };
+                if x % 4 == 0 && z % 4 == 0 && y % 4 == 0 {
+                    biomes.set_biome(coordinates, biome);
+                }
+
+                let block =
+                    proto_chunk.get_block_state(&Vector3::new(x.into(), y.into(), z.into()));
+                if !block.is_air() {
+                    subchunks.set_block(coordinates, block.state_id);
+                }
+            }
+        }
+    }
+
+    ChunkData {
+        subchunks,
+        heightmap: Default::default(),
+        biomes,
+        light: Default::default(),
+        block_entities: Default::default(),
+        position: at,
+        // This chunk was just created! We want to say its been changed
+        dirty: true,
+    }
+}
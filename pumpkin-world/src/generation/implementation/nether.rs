@@ -0,0 +1,94 @@
+use pumpkin_data::chunk::Biome;
+use pumpkin_util::math::vector2::Vector2;
+
+use super::noise_chunk_data;
+use crate::{
+    block::ChunkBlockState,
+    chunk::ChunkData,
+    coordinates::ChunkRelativeBlockCoordinates,
+    generation::{
+        GlobalRandomConfig, Seed, WorldGenerator, generation_shapes::GenerationShape,
+        generator::GeneratorInit, noise_router::proto_noise_router::GlobalProtoNoiseRouter,
+        proto_chunk::ProtoChunk,
+    },
+    noise_router::NOISE_ROUTER_ASTS,
+};
+
+/// Generates the netherrack caverns of the Nether, closed by bedrock at the bottom and the top
+pub struct NetherGenerator {
+    random_config: GlobalRandomConfig,
+    base_router: GlobalProtoNoiseRouter,
+}
+
+impl GeneratorInit for NetherGenerator {
+    fn new(seed: Seed) -> Self {
+        let random_config = GlobalRandomConfig::new(seed.0);
+        let base_router =
+            GlobalProtoNoiseRouter::generate(&NOISE_ROUTER_ASTS.nether, &random_config);
+        Self {
+            random_config,
+            base_router,
+        }
+    }
+}
+
+// TODO: The biomes, surface rules and the bedrock gradients of vanilla
+impl WorldGenerator for NetherGenerator {
+    fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+        let mut proto_chunk = ProtoChunk::new_nether(at, &self.base_router, &self.random_config);
+        proto_chunk.populate_noise();
+        let shape = GenerationShape::NETHER;
+        let mut chunk = noise_chunk_data(&proto_chunk, at, &shape, Biome::NetherWastes);
+
+        let bedrock = ChunkBlockState::new("bedrock").unwrap().state_id;
+        let floor = i16::from(shape.min_y());
+        let ceiling = floor + shape.height() as i16 - 1;
+        for x in 0..16u8 {
+            for z in 0..16u8 {
+                for y in [floor, ceiling] {
+                    chunk.subchunks.set_block(
+                        ChunkRelativeBlockCoordinates {
+                            x: x.into(),
+                            y: y.into(),
+                            z: z.into(),
+                        },
+                        bedrock,
+                    );
+                }
+            }
+        }
+        chunk
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use crate::{
+        block::ChunkBlockState,
+        coordinates::ChunkRelativeBlockCoordinates,
+        generation::{Seed, WorldGenerator, generator::GeneratorInit},
+    };
+
+    use super::NetherGenerator;
+
+    #[test]
+    fn caverns_between_bedrock() {
+        let chunk = NetherGenerator::new(Seed(0)).generate_chunk(Vector2::new(0, 0));
+        let block = |y: i16| {
+            chunk.subchunks.get_block(ChunkRelativeBlockCoordinates {
+                x: 8u8.into(),
+                y: y.into(),
+                z: 8u8.into(),
+            })
+        };
+        let id = |name| ChunkBlockState::new(name).map(|state| state.state_id);
+
+        assert_eq!(block(0), id("bedrock"));
+        assert_eq!(block(127), id("bedrock"));
+        assert_eq!(block(128), Some(0));
+        assert!((1..127).any(|y| block(y) == id("netherrack")));
+        assert!((1..127).any(|y| block(y) == Some(0)));
+    }
+}
@@ -56,6 +56,9 @@ impl WorldGenerator for TestGenerator {
         ChunkData {
             subchunks,
             heightmap: Default::default(),
+            biomes: Default::default(),
+            light: Default::default(),
+            block_entities: Default::default(),
             position: at,
             // This chunk was just created! We want to say its been changed
             dirty: true,
@@ -3,6 +3,7 @@
 pub mod aquifer_sampler;
 mod blender;
 pub mod chunk_noise;
+pub mod dependencies;
 pub mod generation_shapes;
 mod generator;
 mod generic_generator;
@@ -17,19 +18,28 @@ mod seed;
 
 use derive_getters::Getters;
 pub use generator::WorldGenerator;
+pub use implementation::end::{EndSpike, end_spikes};
 use implementation::{
+    end::EndGenerator,
+    nether::NetherGenerator,
     //overworld::biome::plains::PlainsGenerator,
     test::TestGenerator,
 };
 use pumpkin_util::random::{RandomDeriver, RandomImpl, xoroshiro128::Xoroshiro};
 pub use seed::Seed;
 
+use crate::dimension::Dimension;
+
 use generator::GeneratorInit;
 
-pub fn get_world_gen(seed: Seed) -> Box<dyn WorldGenerator> {
+pub fn get_world_gen(seed: Seed, dimension: Dimension) -> Box<dyn WorldGenerator> {
     // TODO decide which WorldGenerator to pick based on config.
     //Box::new(PlainsGenerator::new(seed))
-    Box::new(TestGenerator::new(seed))
+    match dimension {
+        Dimension::OverWorld => Box::new(TestGenerator::new(seed)),
+        Dimension::Nether => Box::new(NetherGenerator::new(seed)),
+        Dimension::End => Box::new(EndGenerator::new(seed)),
+    }
 }
 
 #[derive(Getters)]
@@ -9,8 +9,13 @@ use crate::{
 
 use super::{
     GlobalRandomConfig,
-    aquifer_sampler::{FluidLevel, FluidLevelSampler, FluidLevelSamplerImpl},
-    chunk_noise::{ChunkNoiseGenerator, LAVA_BLOCK, STONE_BLOCK, WATER_BLOCK},
+    aquifer_sampler::{
+        FluidLevel, FluidLevelSampler, FluidLevelSamplerImpl, StaticFluidLevelSampler,
+    },
+    chunk_noise::{
+        ChunkNoiseGenerator, END_STONE_BLOCK, LAVA_BLOCK, NETHERRACK_BLOCK, STONE_BLOCK,
+        WATER_BLOCK,
+    },
     noise_router::proto_noise_router::GlobalProtoNoiseRouter,
     positions::chunk_pos::{start_block_x, start_block_z},
 };
@@ -47,6 +52,8 @@ impl FluidLevelSamplerImpl for StandardChunkFluidLevelSampler {
 pub struct ProtoChunk<'a> {
     chunk_pos: Vector2<i32>,
     sampler: ChunkNoiseGenerator<'a>,
+    /// What the terrain is made of where the density is positive
+    default_block: ChunkBlockState,
     // These are local positions
     flat_block_map: Vec<ChunkBlockState>,
     // may want to use chunk status
@@ -58,15 +65,71 @@ impl<'a> ProtoChunk<'a> {
         base_router: &'a GlobalProtoNoiseRouter,
         random_config: &'a GlobalRandomConfig,
     ) -> Self {
-        let generation_shape = GenerationShape::SURFACE;
-
-        let horizontal_cell_count = CHUNK_DIM / generation_shape.horizontal_cell_block_count();
-
         // TODO: Customize these
         let sampler = FluidLevelSampler::Chunk(StandardChunkFluidLevelSampler::new(
             FluidLevel::new(63, WATER_BLOCK),
             FluidLevel::new(-54, LAVA_BLOCK),
         ));
+        Self::with_shape(
+            chunk_pos,
+            base_router,
+            random_config,
+            GenerationShape::SURFACE,
+            sampler,
+            STONE_BLOCK,
+            true,
+        )
+    }
+
+    /// A chunk of the Nether, netherrack caverns with a lava sea. The router has to be the one of
+    /// the Nether
+    pub fn new_nether(
+        chunk_pos: Vector2<i32>,
+        base_router: &'a GlobalProtoNoiseRouter,
+        random_config: &'a GlobalRandomConfig,
+    ) -> Self {
+        let sampler = FluidLevelSampler::Static(StaticFluidLevelSampler::new(32, LAVA_BLOCK));
+        Self::with_shape(
+            chunk_pos,
+            base_router,
+            random_config,
+            GenerationShape::NETHER,
+            sampler,
+            NETHERRACK_BLOCK,
+            false,
+        )
+    }
+
+    /// A chunk of the End, end stone islands floating in the void without any fluids. The
+    /// router has to be the one of the End
+    pub fn new_end(
+        chunk_pos: Vector2<i32>,
+        base_router: &'a GlobalProtoNoiseRouter,
+        random_config: &'a GlobalRandomConfig,
+    ) -> Self {
+        let sampler =
+            FluidLevelSampler::Static(StaticFluidLevelSampler::new(0, ChunkBlockState::AIR));
+        Self::with_shape(
+            chunk_pos,
+            base_router,
+            random_config,
+            GenerationShape::END,
+            sampler,
+            END_STONE_BLOCK,
+            false,
+        )
+    }
+
+    fn with_shape(
+        chunk_pos: Vector2<i32>,
+        base_router: &'a GlobalProtoNoiseRouter,
+        random_config: &'a GlobalRandomConfig,
+        generation_shape: GenerationShape,
+        fluid_sampler: FluidLevelSampler,
+        default_block: ChunkBlockState,
+        aquifers_and_ore_veins: bool,
+    ) -> Self {
+        let horizontal_cell_count = CHUNK_DIM / generation_shape.horizontal_cell_block_count();
 
         let height = generation_shape.height() as usize;
         let sampler = ChunkNoiseGenerator::new(
@@ -76,14 +139,15 @@ impl<'a> ProtoChunk<'a> {
             chunk_pos::start_block_x(&chunk_pos),
             chunk_pos::start_block_z(&chunk_pos),
             generation_shape,
-            sampler,
-            true,
-            true,
+            fluid_sampler,
+            aquifers_and_ore_veins,
+            aquifers_and_ore_veins,
         );
 
         Self {
             chunk_pos,
             sampler,
+            default_block,
             flat_block_map: vec![
                 ChunkBlockState::AIR;
                 CHUNK_DIM as usize * CHUNK_DIM as usize * height
@@ -176,7 +240,6 @@ impl<'a> ProtoChunk<'a> {
                                     assert!(cell_offset_z >= 0);
                                 }
 
-                                // TODO: Change default block
                                 let block_state = self
                                     .sampler
                                     .sample_block_state(
@@ -187,7 +250,7 @@ impl<'a> ProtoChunk<'a> {
                                         cell_offset_y as usize,
                                         cell_offset_z as usize,
                                     )
-                                    .unwrap_or(STONE_BLOCK);
+                                    .unwrap_or(self.default_block);
                                 //log::debug!("Sampled block state in {:?}", inst.elapsed());
 
                                 let local_pos = Vector3 {
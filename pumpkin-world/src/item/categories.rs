@@ -7,6 +7,8 @@ const HEAD_ARMOR_TAG: &str = "#minecraft:head_armor";
 const CHEST_ARMOR_TAG: &str = "#minecraft:chest_armor";
 const LEG_ARMOR_TAG: &str = "#minecraft:leg_armor";
 const FOOT_ARMOR_TAG: &str = "#minecraft:foot_armor";
+const SHULKER_BOXES_TAG: &str = "#minecraft:shulker_boxes";
+const BUNDLES_TAG: &str = "#minecraft:bundles";
 
 impl ItemStack {
     #[inline]
@@ -43,4 +45,25 @@ impl ItemStack {
             "This is a default minecraft tag that should have been gotten from the extractor",
         )
     }
+
+    #[inline]
+    pub fn is_shulker_box(&self) -> bool {
+        self.item.is_tagged_with(SHULKER_BOXES_TAG).expect(
+            "This is a default minecraft tag that should have been gotten from the extractor",
+        )
+    }
+
+    #[inline]
+    pub fn is_bundle(&self) -> bool {
+        self.item.is_tagged_with(BUNDLES_TAG).expect(
+            "This is a default minecraft tag that should have been gotten from the extractor",
+        )
+    }
+
+    /// Whether the item can be stored in the items of other items, shulker boxes can't go into
+    /// other shulker boxes or bundles
+    #[inline]
+    pub fn can_fit_inside_container_items(&self) -> bool {
+        !self.is_shulker_box()
+    }
 }
@@ -1,7 +1,12 @@
+use pumpkin_data::block::Block;
 use pumpkin_data::item::Item;
-use pumpkin_data::tag::{RegistryKey, get_tag_values};
+use pumpkin_data::potion::Potion;
+use pumpkin_data::tag::{RegistryKey, Tagable, get_tag_values};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::position::GlobalPos;
 
 mod categories;
+mod nbt;
 
 #[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -13,22 +18,94 @@ pub enum Rarity {
     Epic,
 }
 
+/// Data components which differ from the defaults of the item
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemStackComponents {
+    /// The potion of a potion, splash potion, lingering potion or tipped arrow
+    pub potion: Option<Potion>,
+    /// The items of a container block kept in its item, like a broken shulker box
+    pub container: Option<Vec<Option<ItemStack>>>,
+    /// The items in a bundle, the most recently added first
+    pub bundle_contents: Option<Vec<ItemStack>>,
+    /// The lodestone a compass points to
+    pub lodestone_tracker: Option<LodestoneTracker>,
+    /// What the mob caught in a bucket, like a cod, is restored with once released again
+    pub bucket_entity_data: Option<NbtCompound>,
+    /// The blocks the item may be placed on in adventure mode
+    pub can_place_on: Option<AdventurePredicate>,
+    /// The blocks the item may break in adventure mode
+    pub can_break: Option<AdventurePredicate>,
+}
+
+/// The blocks of the `can_place_on` and `can_break` components, a block has to match any of the
+/// sets
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdventurePredicate {
+    pub blocks: Vec<BlockSet>,
+    /// Whether the client lists the blocks in the tooltip of the item
+    pub show_in_tooltip: bool,
+}
+
+/// Blocks by id or all blocks of a tag, like `minecraft:logs`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockSet {
+    Blocks(Vec<u16>),
+    Tag(String),
+}
+
+impl AdventurePredicate {
+    #[must_use]
+    pub fn matches(&self, block: &Block) -> bool {
+        self.blocks.iter().any(|set| match set {
+            BlockSet::Blocks(ids) => ids.contains(&block.id),
+            BlockSet::Tag(tag) => block.is_tagged_with(tag).unwrap_or(false),
+        })
+    }
+}
+
+/// Where a lodestone compass points to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LodestoneTracker {
+    /// The lodestone, `None` once it is gone, the needle spins then
+    pub target: Option<GlobalPos>,
+    /// Whether the target is removed when the lodestone is gone. Compasses made to point
+    /// somewhere with commands don't need a lodestone
+    pub tracked: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct ItemStack {
     pub item_count: u8,
     // TODO: Should this be a ref? all of our items are const
     pub item: Item,
+    pub components: ItemStackComponents,
 }
 
 impl PartialEq for ItemStack {
     fn eq(&self, other: &Self) -> bool {
-        self.item.id == other.item.id
+        self.item.id == other.item.id && self.components == other.components
     }
 }
 
 impl ItemStack {
     pub fn new(item_count: u8, item: Item) -> Self {
-        Self { item_count, item }
+        Self {
+            item_count,
+            item,
+            components: ItemStackComponents::default(),
+        }
+    }
+
+    /// A stack of a potion item, like a splash potion, holding the given potion
+    pub fn with_potion(item_count: u8, item: Item, potion: Potion) -> Self {
+        Self {
+            item_count,
+            item,
+            components: ItemStackComponents {
+                potion: Some(potion),
+                ..Default::default()
+            },
+        }
     }
 
     /// Determines the mining speed for a block based on tool rules.
@@ -0,0 +1,281 @@
+use pumpkin_data::block::Block;
+use pumpkin_data::item::Item;
+use pumpkin_data::potion::Potion;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::tag::NbtTag;
+
+use pumpkin_util::math::position::GlobalPos;
+
+use crate::item::{AdventurePredicate, BlockSet, ItemStack, ItemStackComponents, LodestoneTracker};
+
+fn strip_namespace(name: &str) -> &str {
+    name.strip_prefix("minecraft:").unwrap_or(name)
+}
+
+/// Writes the predicate in its full form, `{predicates: [{blocks: ...}], show_in_tooltip: ...}`
+fn write_adventure_predicate(predicate: &AdventurePredicate) -> NbtCompound {
+    let predicates = predicate
+        .blocks
+        .iter()
+        .map(|set| {
+            let blocks = match set {
+                BlockSet::Tag(tag) => NbtTag::String(format!("#{tag}")),
+                BlockSet::Blocks(ids) => {
+                    let mut names: Vec<NbtTag> = ids
+                        .iter()
+                        .filter_map(|id| Block::from_id(*id))
+                        .map(|block| NbtTag::String(format!("minecraft:{}", block.name)))
+                        .collect();
+                    if names.len() == 1 {
+                        names.remove(0)
+                    } else {
+                        NbtTag::List(names.into_boxed_slice())
+                    }
+                }
+            };
+            let mut compound = NbtCompound::new();
+            compound.put("blocks", blocks);
+            NbtTag::Compound(compound)
+        })
+        .collect();
+    let mut compound = NbtCompound::new();
+    compound.put_list("predicates", predicates);
+    compound.put_bool("show_in_tooltip", predicate.show_in_tooltip);
+    compound
+}
+
+/// `{blocks: "minecraft:stone"}`, where the blocks may also be a list or a tag like `#minecraft:logs`
+fn read_block_set(predicate: &NbtCompound) -> Option<BlockSet> {
+    let block_id = |name: &str| Block::from_registry_key(strip_namespace(name)).map(|b| b.id);
+    match predicate.get("blocks")? {
+        NbtTag::String(name) => match name.strip_prefix('#') {
+            Some(tag) if tag.contains(':') => Some(BlockSet::Tag(tag.to_string())),
+            Some(tag) => Some(BlockSet::Tag(format!("minecraft:{tag}"))),
+            None => Some(BlockSet::Blocks(vec![block_id(name)?])),
+        },
+        NbtTag::List(names) => Some(BlockSet::Blocks(
+            names
+                .iter()
+                .filter_map(NbtTag::extract_string)
+                .filter_map(|name| block_id(name))
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+/// Reads the full form, a list of predicates or a single one, like vanilla does. Predicates
+/// on block states or block entity data aren't supported and left out
+fn read_adventure_predicate(tag: &NbtTag) -> Option<AdventurePredicate> {
+    let read_list = |list: &[NbtTag]| -> Vec<BlockSet> {
+        list.iter()
+            .filter_map(NbtTag::extract_compound)
+            .filter_map(read_block_set)
+            .collect()
+    };
+    let (blocks, show_in_tooltip) = match tag {
+        NbtTag::List(list) => (read_list(list), true),
+        NbtTag::Compound(compound) => match compound.get_list("predicates") {
+            Some(list) => (
+                read_list(list),
+                compound.get_bool("show_in_tooltip").unwrap_or(true),
+            ),
+            None => (vec![read_block_set(compound)?], true),
+        },
+        _ => return None,
+    };
+    Some(AdventurePredicate {
+        blocks,
+        show_in_tooltip,
+    })
+}
+
+fn write_stack(stack: &ItemStack) -> NbtTag {
+    let mut compound = NbtCompound::new();
+    stack.write_item_nbt(&mut compound);
+    NbtTag::Compound(compound)
+}
+
+impl ItemStack {
+    /// Writes the stack the way vanilla saves items, like `{id: "minecraft:stone", count: 1}`
+    pub fn write_item_nbt(&self, compound: &mut NbtCompound) {
+        compound.put_string("id", format!("minecraft:{}", self.item.registry_key));
+        compound.put_int("count", i32::from(self.item_count));
+
+        let mut components = NbtCompound::new();
+        if let Some(potion) = self.components.potion {
+            let mut contents = NbtCompound::new();
+            contents.put_string("potion", format!("minecraft:{}", potion.to_name()));
+            components.put_component("minecraft:potion_contents", contents);
+        }
+        if let Some(container) = &self.components.container {
+            let slots = container
+                .iter()
+                .enumerate()
+                .filter_map(|(slot, stack)| {
+                    let mut entry = NbtCompound::new();
+                    entry.put_int("slot", slot as i32);
+                    entry.put("item", write_stack(stack.as_ref()?));
+                    Some(NbtTag::Compound(entry))
+                })
+                .collect();
+            components.put_list("minecraft:container", slots);
+        }
+        if let Some(contents) = &self.components.bundle_contents {
+            let stacks = contents.iter().map(write_stack).collect();
+            components.put_list("minecraft:bundle_contents", stacks);
+        }
+        if let Some(tracker) = &self.components.lodestone_tracker {
+            let mut compound = NbtCompound::new();
+            if let Some(target) = &tracker.target {
+                compound.put_component("target", target.write_nbt());
+            }
+            compound.put_bool("tracked", tracker.tracked);
+            components.put_component("minecraft:lodestone_tracker", compound);
+        }
+        if let Some(predicate) = &self.components.can_place_on {
+            components.put_component(
+                "minecraft:can_place_on",
+                write_adventure_predicate(predicate),
+            );
+        }
+        if let Some(predicate) = &self.components.can_break {
+            components.put_component("minecraft:can_break", write_adventure_predicate(predicate));
+        }
+        if let Some(data) = &self.components.bucket_entity_data {
+            components.put_component("minecraft:bucket_entity_data", data.clone());
+        }
+        if !components.child_tags.is_empty() {
+            compound.put_component("components", components);
+        }
+    }
+
+    /// Reads a stack written by [`ItemStack::write_item_nbt`], `None` for unknown items.
+    /// Components which aren't supported are left out
+    pub fn read_item_nbt(compound: &NbtCompound) -> Option<Self> {
+        let item = Item::from_registry_key(strip_namespace(compound.get_string("id")?))?;
+        let count = compound.get_int("count").unwrap_or(1).clamp(1, 99) as u8;
+        let mut stack = Self::new(count, item);
+        let Some(components) = compound.get_compound("components") else {
+            return Some(stack);
+        };
+
+        stack.components = ItemStackComponents {
+            potion: components
+                .get_compound("minecraft:potion_contents")
+                .and_then(|contents| contents.get_string("potion"))
+                .and_then(|potion| Potion::from_name(strip_namespace(potion))),
+            container: components.get_list("minecraft:container").map(|slots| {
+                let mut container = Vec::new();
+                for entry in slots.iter().filter_map(NbtTag::extract_compound) {
+                    let Some(slot) = entry.get_int("slot") else {
+                        continue;
+                    };
+                    let Some(item) = entry.get_compound("item").and_then(Self::read_item_nbt)
+                    else {
+                        continue;
+                    };
+                    let slot = slot.clamp(0, 255) as usize;
+                    if container.len() <= slot {
+                        container.resize(slot + 1, None);
+                    }
+                    container[slot] = Some(item);
+                }
+                container
+            }),
+            bundle_contents: components
+                .get_list("minecraft:bundle_contents")
+                .map(|stacks| {
+                    stacks
+                        .iter()
+                        .filter_map(NbtTag::extract_compound)
+                        .filter_map(Self::read_item_nbt)
+                        .collect()
+                }),
+            lodestone_tracker: components.get_compound("minecraft:lodestone_tracker").map(
+                |tracker| LodestoneTracker {
+                    target: tracker.get_compound("target").and_then(GlobalPos::read_nbt),
+                    tracked: tracker.get_bool("tracked").unwrap_or(true),
+                },
+            ),
+            bucket_entity_data: components
+                .get_compound("minecraft:bucket_entity_data")
+                .cloned(),
+            can_place_on: components
+                .get("minecraft:can_place_on")
+                .and_then(read_adventure_predicate),
+            can_break: components
+                .get("minecraft:can_break")
+                .and_then(read_adventure_predicate),
+        };
+        Some(stack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::block::Block;
+    use pumpkin_data::item::Item;
+    use pumpkin_data::potion::Potion;
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::{
+        position::{BlockPos, GlobalPos},
+        vector3::Vector3,
+    };
+
+    use crate::item::{AdventurePredicate, BlockSet, ItemStack, LodestoneTracker};
+
+    #[test]
+    fn item_nbt_roundtrip() {
+        let mut bundle = ItemStack::new(1, Item::BUNDLE);
+        bundle.components.bundle_contents = Some(vec![ItemStack::new(12, Item::DIRT)]);
+        let mut shulker_box = ItemStack::new(1, Item::RED_SHULKER_BOX);
+        shulker_box.components.container = Some(vec![
+            None,
+            Some(ItemStack::with_potion(
+                1,
+                Item::SPLASH_POTION,
+                Potion::Healing,
+            )),
+            Some(bundle),
+        ]);
+
+        let mut compound = NbtCompound::new();
+        shulker_box.write_item_nbt(&mut compound);
+        assert_eq!(
+            compound.get_string("id").map(String::as_str),
+            Some("minecraft:red_shulker_box")
+        );
+        let read = ItemStack::read_item_nbt(&compound).unwrap();
+        assert_eq!(read, shulker_box);
+        assert_eq!(read.item_count, 1);
+
+        let mut compass = ItemStack::new(1, Item::COMPASS);
+        compass.components.lodestone_tracker = Some(LodestoneTracker {
+            target: Some(GlobalPos::new(
+                "minecraft:the_nether".to_string(),
+                BlockPos(Vector3::new(12, -30, 400)),
+            )),
+            tracked: true,
+        });
+        let mut compound = NbtCompound::new();
+        compass.write_item_nbt(&mut compound);
+        assert_eq!(ItemStack::read_item_nbt(&compound).unwrap(), compass);
+
+        let mut pickaxe = ItemStack::new(1, Item::IRON_PICKAXE);
+        pickaxe.components.can_break = Some(AdventurePredicate {
+            blocks: vec![
+                BlockSet::Blocks(vec![Block::STONE.id, Block::DIRT.id]),
+                BlockSet::Tag("minecraft:logs".to_string()),
+            ],
+            show_in_tooltip: false,
+        });
+        let mut compound = NbtCompound::new();
+        pickaxe.write_item_nbt(&mut compound);
+        assert_eq!(ItemStack::read_item_nbt(&compound).unwrap(), pickaxe);
+
+        let mut unknown = NbtCompound::new();
+        unknown.put_string("id", "minecraft:not_an_item".to_string());
+        assert!(ItemStack::read_item_nbt(&unknown).is_none());
+    }
+}
@@ -1,22 +1,44 @@
-use std::{fs, path::PathBuf, sync::Arc};
+use std::{
+    fs::{self, File},
+    io,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
-use dashmap::{DashMap, Entry};
+use dashmap::{DashMap, DashSet, Entry};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
 use log::trace;
 use num_traits::Zero;
 use pumpkin_config::{advanced_config, chunk::ChunkFormat};
+use pumpkin_nbt::{Nbt, compound::NbtCompound, deserializer::ReadAdaptor};
 use pumpkin_util::math::vector2::Vector2;
+use thiserror::Error;
 use tokio::{
     sync::{RwLock, mpsc},
     task::JoinSet,
 };
 
 use crate::{
+    chunk::io::migration,
     chunk::{
         ChunkData, ChunkParsingError, ChunkReadingError,
-        format::{anvil::AnvilChunkFile, linear::LinearFile},
-        io::{ChunkIO, LoadedData, chunk_file_manager::ChunkFileManager},
+        entities::{ChunkEntityData, SyncEntityChunk},
+        format::{
+            anvil::{AnvilChunkFile, WORLD_DATA_VERSION},
+            linear::LinearFile,
+        },
+        io::{
+            ChunkIO, LoadedData,
+            chunk_file_manager::ChunkFileManager,
+            io_metrics::ChunkIoMetrics,
+            migration::{ChunkMigrationError, MigrationReport},
+        },
     },
-    generation::{Seed, WorldGenerator, get_world_gen},
+    dimension::Dimension,
+    generation::{Seed, dependencies::ChunkDependencies, get_world_gen},
     lock::{LevelLocker, anvil::AnvilLevelLocker},
     world_info::{
         LevelData, WorldInfoError, WorldInfoReader, WorldInfoWriter,
@@ -26,6 +48,15 @@ use crate::{
 
 pub type SyncChunk = Arc<RwLock<ChunkData>>;
 
+/// The folder the data of every player who joined is saved to, like their ender chest
+const PLAYER_DATA_FOLDER: &str = "playerdata";
+/// The folder other data of the world is saved to
+const DATA_FOLDER: &str = "data";
+/// The folder the advancements of every player who joined are saved to
+const ADVANCEMENTS_FOLDER: &str = "advancements";
+/// The folder datapacks are loaded from
+const DATAPACKS_FOLDER: &str = "datapacks";
+
 /// The `Level` module provides functionality for working with chunks within or outside a Minecraft world.
 ///
 /// Key features include:
@@ -37,6 +68,7 @@ pub type SyncChunk = Arc<RwLock<ChunkData>>;
 /// For more details on world generation, refer to the `WorldGenerator` module.
 pub struct Level {
     pub seed: Seed,
+    pub dimension: Dimension,
     pub level_info: LevelData,
     world_info_writer: Arc<dyn WorldInfoWriter>,
     level_folder: LevelFolder,
@@ -48,38 +80,95 @@ pub struct Level {
     // from the loaded chunks map and sent to the underlying ChunkIO
     loaded_chunks: Arc<DashMap<Vector2<i32>, SyncChunk>>,
     chunk_watchers: Arc<DashMap<Vector2<i32>, usize>>,
+    // Chunks that are kept loaded by a `ChunkHandle`, with the amount of handles alive for each
+    forced_chunks: Arc<DashMap<Vector2<i32>, usize>>,
 
-    chunk_saver: Arc<dyn ChunkIO<Data = SyncChunk>>,
-    world_gen: Arc<dyn WorldGenerator>,
+    // Swapped when the world is migrated to another format at runtime
+    chunk_storage: std::sync::RwLock<ChunkStorage>,
+    migrating: AtomicBool,
+    // The entities of the chunks, like vanilla they are always saved in anvil region files
+    entity_io: Arc<dyn ChunkIO<Data = SyncEntityChunk>>,
+    // Generates chunks together with the neighbors their features spill into
+    chunk_generation: Arc<ChunkDependencies>,
     // Gets unlocked when dropped
     // TODO: Make this a trait
     _locker: Arc<AnvilLevelLocker>,
 }
 
+/// The estimated memory use of a level, see `Level::memory_usage`
+#[derive(Clone, Copy, Default, Debug)]
+pub struct LevelMemory {
+    pub chunks: usize,
+    pub chunk_bytes: usize,
+    pub file_cache_bytes: usize,
+}
+
+#[derive(Clone)]
+struct ChunkStorage {
+    format: ChunkFormat,
+    io: Arc<dyn ChunkIO<Data = SyncChunk>>,
+    // The chunks written through this storage while a migration is cutting over to it
+    cutover_writes: Option<Arc<DashSet<Vector2<i32>>>>,
+}
+
+impl ChunkStorage {
+    fn new(format: ChunkFormat) -> Self {
+        let io: Arc<dyn ChunkIO<Data = SyncChunk>> = match format {
+            ChunkFormat::Linear => Arc::new(ChunkFileManager::<LinearFile>::default()),
+            ChunkFormat::Anvil => Arc::new(ChunkFileManager::<AnvilChunkFile>::default()),
+        };
+        Self {
+            format,
+            io,
+            cutover_writes: None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct LevelFolder {
     pub root_folder: PathBuf,
     pub region_folder: PathBuf,
+    pub entities_folder: PathBuf,
 }
 
 impl Level {
     pub fn from_root_folder(root_folder: PathBuf) -> Self {
+        Self::from_dimension(root_folder, Dimension::OverWorld)
+    }
+
+    /// Loads a dimension of the world. All of them share the level.dat of the world folder,
+    /// which only the overworld creates and saves
+    pub fn from_dimension(world_folder: PathBuf, dimension: Dimension) -> Self {
         // If we are using an already existing world we want to read the seed from the level.dat, If not we want to check if there is a seed in the config, if not lets create a random one
+        let root_folder = dimension.folder(&world_folder);
         let region_folder = root_folder.join("region");
         if !region_folder.exists() {
             std::fs::create_dir_all(&region_folder).expect("Failed to create Region folder");
         }
+        let entities_folder = root_folder.join("entities");
+        if !entities_folder.exists() {
+            std::fs::create_dir_all(&entities_folder).expect("Failed to create Entities folder");
+        }
         let level_folder = LevelFolder {
             root_folder,
             region_folder,
+            entities_folder,
         };
 
         // if we fail to lock, lets crash ???. maybe not the best solution when we have a large server with many worlds and one is locked.
         // So TODO
         let locker = AnvilLevelLocker::look(&level_folder).expect("Failed to lock level");
 
+        let world_info_folder = LevelFolder {
+            root_folder: world_folder,
+            region_folder: level_folder.region_folder.clone(),
+            entities_folder: level_folder.entities_folder.clone(),
+        };
+        let is_overworld = dimension == Dimension::OverWorld;
+
         // TODO: Load info correctly based on world format type
-        let level_info = AnvilLevelInfo.read_world_info(&level_folder);
+        let level_info = AnvilLevelInfo.read_world_info(&world_info_folder);
         if let Err(error) = &level_info {
             match error {
                 // If it doesn't exist, just make a new one
@@ -93,7 +182,7 @@ impl Level {
                     panic!("World Error {}", e);
                 }
             }
-        } else {
+        } else if is_overworld {
             let dat_path = level_folder.root_folder.join(LEVEL_DAT_FILE_NAME);
             if dat_path.exists() {
                 let backup_path = level_folder.root_folder.join(LEVEL_DAT_BACKUP_FILE_NAME);
@@ -101,40 +190,69 @@ impl Level {
             }
         }
 
-        let level_info = level_info.unwrap_or_default(); // TODO: Improve error handling
+        let level_info = level_info.unwrap_or_else(|_| {
+            // Write the new level.dat right away, so the world can be opened by vanilla tools
+            // even before it is saved for the first time
+            let mut level_info = LevelData::default();
+            if let Some(name) = world_info_folder.root_folder.file_name() {
+                level_info.level_name = name.to_string_lossy().into_owned();
+            }
+            if is_overworld {
+                if let Err(err) = AnvilLevelInfo.write_world_info(level_info.clone(), &level_folder)
+                {
+                    log::error!("Failed to create level.dat: {}", err);
+                }
+            }
+            level_info
+        });
         log::info!(
             "Loading world with seed: {}",
             level_info.world_gen_settings.seed
         );
 
         let seed = Seed(level_info.world_gen_settings.seed as u64);
-        let world_gen = get_world_gen(seed).into();
+        let chunk_generation = Arc::new(ChunkDependencies::new(
+            get_world_gen(seed, dimension).into(),
+        ));
 
-        let chunk_saver: Arc<dyn ChunkIO<Data = SyncChunk>> = match advanced_config().chunk.format {
-            //ChunkFormat::Anvil => (Arc::new(AnvilChunkFormat), Arc::new(AnvilChunkFormat)),
-            ChunkFormat::Linear => Arc::new(ChunkFileManager::<LinearFile>::default()),
-            ChunkFormat::Anvil => Arc::new(ChunkFileManager::<AnvilChunkFile>::default()),
-        };
+        // A migration at runtime records the format it cut over to, which wins over the config
+        let chunk_format =
+            migration::read_format_marker(&level_folder).unwrap_or(advanced_config().chunk.format);
+        if chunk_format != advanced_config().chunk.format {
+            log::info!(
+                "Loading chunks as {:?}, the format the world was migrated to",
+                chunk_format
+            );
+        }
+        let chunk_storage = ChunkStorage::new(chunk_format);
 
         Self {
             seed,
-            world_gen,
+            dimension,
+            chunk_generation,
             world_info_writer: Arc::new(AnvilLevelInfo),
             level_folder,
-            chunk_saver,
+            chunk_storage: std::sync::RwLock::new(chunk_storage),
+            migrating: AtomicBool::new(false),
+            entity_io: Arc::new(ChunkFileManager::<AnvilChunkFile<ChunkEntityData>>::default()),
             spawn_chunks: Arc::new(DashMap::new()),
             loaded_chunks: Arc::new(DashMap::new()),
             chunk_watchers: Arc::new(DashMap::new()),
+            forced_chunks: Arc::new(DashMap::new()),
             level_info,
             _locker: Arc::new(locker),
         }
     }
 
-    pub async fn save(&self) {
+    /// Saves the loaded chunks and the level info. The info is passed in as the world keeps
+    /// parts of it up to date itself, like the time
+    pub async fn save(&self, level_info: LevelData) {
         log::info!("Saving level...");
 
+        let chunk_saver = self.chunk_saver();
         // wait for chunks currently saving in other threads
-        self.chunk_saver.block_and_await_ongoing_tasks().await;
+        chunk_saver.block_and_await_ongoing_tasks().await;
+        self.entity_io.block_and_await_ongoing_tasks().await;
 
         // save all chunks currently in memory
         let chunks_to_write = self
@@ -145,13 +263,18 @@ impl Level {
         self.loaded_chunks.clear();
 
         // TODO: I think the chunk_saver should be at the server level
-        self.chunk_saver.clear_watched_chunks().await;
+        chunk_saver.clear_watched_chunks().await;
         self.write_chunks(chunks_to_write).await;
 
+        // The other dimensions don't have a level.dat of their own
+        if self.dimension != Dimension::OverWorld {
+            return;
+        }
+
         // then lets save the world info
         let result = self
             .world_info_writer
-            .write_world_info(self.level_info.clone(), &self.level_folder);
+            .write_world_info(level_info, &self.level_folder);
 
         // Lets not stop the overall save for this
         if let Err(err) = result {
@@ -161,12 +284,370 @@ impl Level {
 
     pub fn get_block() {}
 
+    /// Reads what was saved about the player with the uuid, `None` if they never joined
+    pub fn read_player_data(&self, uuid: &str) -> Option<NbtCompound> {
+        let path = self
+            .level_folder
+            .root_folder
+            .join(PLAYER_DATA_FOLDER)
+            .join(format!("{uuid}.dat"));
+        let file = File::open(path).ok()?;
+        let mut reader = ReadAdaptor::new(GzDecoder::new(file));
+        match Nbt::read(&mut reader) {
+            Ok(nbt) => Some(nbt.root_tag),
+            Err(err) => {
+                log::error!("Failed to read the player data of {uuid}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Saves the data of the player with the uuid the way vanilla does, into `playerdata/<uuid>.dat`
+    pub fn write_player_data(&self, uuid: &str, data: NbtCompound) -> io::Result<()> {
+        let folder = self.level_folder.root_folder.join(PLAYER_DATA_FOLDER);
+        fs::create_dir_all(&folder)?;
+        let file = File::create(folder.join(format!("{uuid}.dat")))?;
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        Nbt::new(String::new(), data).write_to_writer(&mut encoder)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Reads the `data` of a file saved with [`Self::write_data`], `None` if there is none
+    pub fn read_data(&self, name: &str) -> Option<NbtCompound> {
+        let path = self.data_path(name);
+        let file = File::open(&path).ok()?;
+        let mut reader = ReadAdaptor::new(GzDecoder::new(file));
+        match Nbt::read(&mut reader) {
+            Ok(nbt) => nbt.root_tag.get_compound("data").cloned(),
+            Err(err) => {
+                log::error!("Failed to read {}: {err}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Saves the data into the `data` folder of the world the way vanilla does, like
+    /// `scoreboard.dat`
+    pub fn write_data(&self, name: &str, data: NbtCompound) -> io::Result<()> {
+        let path = self.data_path(name);
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        let mut root = NbtCompound::new();
+        root.put_component("data", data);
+        root.put_int("DataVersion", WORLD_DATA_VERSION);
+        let mut encoder = GzEncoder::new(File::create(path)?, Compression::default());
+        Nbt::new(String::new(), root).write_to_writer(&mut encoder)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Where the file with the given name is saved to in the `data` folder of the world
+    #[must_use]
+    pub fn data_path(&self, name: &str) -> PathBuf {
+        self.level_folder.root_folder.join(DATA_FOLDER).join(name)
+    }
+
+    /// Where the advancements of the player with the uuid are saved to, like vanilla does
+    #[must_use]
+    pub fn advancements_path(&self, uuid: &str) -> PathBuf {
+        self.level_folder
+            .root_folder
+            .join(ADVANCEMENTS_FOLDER)
+            .join(format!("{uuid}.json"))
+    }
+
+    #[must_use]
+    pub fn datapacks_path(&self) -> PathBuf {
+        self.level_folder.root_folder.join(DATAPACKS_FOLDER)
+    }
+
+    fn chunk_saver(&self) -> Arc<dyn ChunkIO<Data = SyncChunk>> {
+        self.chunk_storage
+            .read()
+            .expect("Chunk storage lock is poisoned")
+            .io
+            .clone()
+    }
+
+    /// The format chunks of this level are currently stored in
+    pub fn chunk_format(&self) -> ChunkFormat {
+        self.chunk_storage
+            .read()
+            .expect("Chunk storage lock is poisoned")
+            .format
+    }
+
+    pub fn is_migrating(&self) -> bool {
+        self.migrating.load(Ordering::Relaxed)
+    }
+
+    /// Migrates the chunks of this level to another storage format while the level is in use.
+    ///
+    /// Regions are copied one by one in the background (loaded chunks are taken from memory, the
+    /// rest through the current `ChunkIO`). Once every region has been copied, the regions the
+    /// old format wrote in the meantime are copied again and the level cuts over to the new
+    /// format. The new format is then recorded in the level folder for the next start, and the
+    /// old region files are moved to `region.old`.
+    pub async fn migrate_chunk_format(
+        &self,
+        target: ChunkFormat,
+    ) -> Result<MigrationReport, ChunkMigrationError> {
+        let source = self.chunk_format();
+        if source == target {
+            return Err(ChunkMigrationError::SameFormat(target));
+        }
+        if self.migrating.swap(true, Ordering::Relaxed) {
+            return Err(ChunkMigrationError::AlreadyRunning);
+        }
+
+        let result = self.run_chunk_migration(source, target).await;
+        self.migrating.store(false, Ordering::Relaxed);
+        result
+    }
+
+    async fn run_chunk_migration(
+        &self,
+        source_format: ChunkFormat,
+        target_format: ChunkFormat,
+    ) -> Result<MigrationReport, ChunkMigrationError> {
+        let source = self.chunk_saver();
+        let target = ChunkStorage::new(target_format);
+        let regions = migration::list_region_files(&self.level_folder, source_format)?;
+        log::info!(
+            "Migrating {} regions from {:?} to {:?}",
+            regions.len(),
+            source_format,
+            target_format
+        );
+
+        let total = regions.len();
+        let mut report = MigrationReport::default();
+        let mut migrated = Vec::with_capacity(total);
+        for (index, (path, region)) in regions.into_iter().enumerate() {
+            let modified = migration::last_modified(&path).await;
+            report.chunks += self
+                .migrate_region(&source, &target.io, region, None)
+                .await?;
+            report.regions += 1;
+            migrated.push((path, region, modified));
+            if (index + 1) % 16 == 0 {
+                log::info!("Migrated {}/{} regions", index + 1, total);
+            }
+        }
+
+        // Regions the old format wrote after we copied them are outdated in the new format. They
+        // are copied again before the cutover, so nothing the new format writes is overwritten
+        source.block_and_await_ongoing_tasks().await;
+        for (path, region, modified) in &mut migrated {
+            let now = migration::last_modified(path).await;
+            if now != *modified {
+                log::debug!(
+                    "Region {:?} changed while migrating, copying it again",
+                    region
+                );
+                self.migrate_region(&source, &target.io, *region, None)
+                    .await?;
+                report.remigrated_regions += 1;
+                *modified = now;
+            }
+        }
+
+        // Cutover: the new format must know about the chunks that are currently watched so it
+        // keeps their regions in memory like the old one did
+        let watched = self
+            .chunk_watchers
+            .iter()
+            .flat_map(|entry| std::iter::repeat_n(*entry.key(), *entry.value()))
+            .collect::<Vec<_>>();
+        target.io.watch_chunks(&self.level_folder, &watched).await;
+        let target_io = target.io.clone();
+        let cutover_writes = Arc::new(DashSet::new());
+        *self
+            .chunk_storage
+            .write()
+            .expect("Chunk storage lock is poisoned") = ChunkStorage {
+            cutover_writes: Some(cutover_writes.clone()),
+            ..target
+        };
+        source.block_and_await_ongoing_tasks().await;
+
+        // Saves which were already running in the old format when we switched. The chunks the
+        // new format wrote since then are newer, so they are kept
+        for (path, region, modified) in &migrated {
+            if migration::last_modified(path).await != *modified {
+                log::debug!(
+                    "Region {:?} changed during the cutover, copying it again",
+                    region
+                );
+                self.migrate_region(&source, &target_io, *region, Some(&cutover_writes))
+                    .await?;
+                report.remigrated_regions += 1;
+            }
+        }
+        self.chunk_storage
+            .write()
+            .expect("Chunk storage lock is poisoned")
+            .cutover_writes = None;
+
+        // Only move the old regions once the next start is sure to read the new format
+        migration::write_format_marker(&self.level_folder, target_format).await?;
+        let old_regions = migrated
+            .into_iter()
+            .map(|(path, ..)| path)
+            .collect::<Vec<_>>();
+        migration::move_old_regions(&self.level_folder, &old_regions).await?;
+
+        log::info!(
+            "Migrated {} chunks in {} regions to {:?} ({} regions copied again during cutover). The old region files were moved to {}",
+            report.chunks,
+            report.regions,
+            target_format,
+            report.remigrated_regions,
+            migration::OLD_REGION_FOLDER
+        );
+        Ok(report)
+    }
+
+    /// Copies every chunk of a region from `source` to `target`, except the ones in `skip`,
+    /// returning the amount of chunks copied
+    async fn migrate_region(
+        &self,
+        source: &Arc<dyn ChunkIO<Data = SyncChunk>>,
+        target: &Arc<dyn ChunkIO<Data = SyncChunk>>,
+        region: (i32, i32),
+        skip: Option<&DashSet<Vector2<i32>>>,
+    ) -> Result<usize, ChunkMigrationError> {
+        let mut chunks_to_write = Vec::new();
+        let mut unloaded = Vec::new();
+        for position in migration::region_chunks(region) {
+            if skip.is_some_and(|skip| skip.contains(&position)) {
+                continue;
+            }
+            let loaded = self
+                .loaded_chunks
+                .get(&position)
+                .map(|chunk| chunk.value().clone());
+            match loaded {
+                Some(chunk) => {
+                    // Copy the chunk so we don't touch the dirty flag of the live chunk, it still
+                    // has to be saved by whichever format is in use when it is unloaded
+                    let mut data = chunk.read().await.snapshot();
+                    data.dirty = true;
+                    chunks_to_write.push((position, Arc::new(RwLock::new(data))));
+                }
+                None => unloaded.push(position),
+            }
+        }
+
+        let (send, mut recv) = mpsc::channel(16);
+        let fetch = source.fetch_chunks(&self.level_folder, &unloaded, send);
+        let collect = async {
+            while let Some(data) = recv.recv().await {
+                match data {
+                    LoadedData::Loaded(chunk) => {
+                        let position = {
+                            let mut data = chunk.write().await;
+                            // Force the new format to write this chunk
+                            data.dirty = true;
+                            data.position
+                        };
+                        chunks_to_write.push((position, chunk));
+                    }
+                    LoadedData::Missing(_) => {}
+                    LoadedData::Error((pos, error)) => match error {
+                        ChunkReadingError::ChunkNotExist
+                        | ChunkReadingError::ParsingError(ChunkParsingError::ChunkNotGenerated) => {
+                        }
+                        error => log::warn!("Skipping chunk {:?} while migrating: {}", pos, error),
+                    },
+                }
+            }
+        };
+        let _ = tokio::join!(fetch, collect);
+        // Nobody is using these regions, don't keep them cached in the old format
+        source
+            .release_unwatched(&self.level_folder, &unloaded)
+            .await;
+
+        // The new format may have saved some of these chunks while they were read, those saves
+        // are newer. `write_chunks` records them under this lock
+        if let Some(skip) = skip {
+            let _storage = self
+                .chunk_storage
+                .write()
+                .expect("Chunk storage lock is poisoned");
+            chunks_to_write.retain(|(position, _)| !skip.contains(position));
+        }
+        let count = chunks_to_write.len();
+        target
+            .save_chunks(&self.level_folder, chunks_to_write)
+            .await
+            .map_err(ChunkMigrationError::Writing)?;
+        Ok(count)
+    }
+
     pub fn loaded_chunk_count(&self) -> usize {
         self.loaded_chunks.len()
     }
 
+    /// Estimates the memory held by the loaded chunks and the cached region files
+    pub async fn memory_usage(&self) -> LevelMemory {
+        let chunks: Vec<_> = self
+            .loaded_chunks
+            .iter()
+            .chain(self.spawn_chunks.iter())
+            .map(|entry| entry.value().clone())
+            .collect();
+        let mut chunk_bytes = 0;
+        for chunk in &chunks {
+            chunk_bytes += chunk.read().await.estimated_memory();
+        }
+        LevelMemory {
+            chunks: chunks.len(),
+            chunk_bytes,
+            file_cache_bytes: self.chunk_saver().cache_memory().await
+                + self.entity_io.cache_memory().await,
+        }
+    }
+
+    /// Saves and unloads every loaded chunk which is neither watched nor forced, returns how many
+    /// there were
+    pub async fn unload_unused_chunks(&self) -> usize {
+        let unused: Vec<_> = self
+            .loaded_chunks
+            .iter()
+            .filter(|entry| !self.is_chunk_in_use(entry.key()))
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+        let positions: Vec<_> = unused.iter().map(|(pos, _)| *pos).collect();
+        // Unlike `clean_chunks` this waits for the chunks to be written, so their region files
+        // can be dropped from the cache afterwards
+        self.write_chunks(unused).await;
+        let removed: Vec<_> = positions
+            .iter()
+            .filter(|pos| {
+                self.loaded_chunks
+                    .remove_if(pos, |_, _| !self.is_chunk_in_use(pos))
+                    .is_some()
+            })
+            .copied()
+            .collect();
+        self.chunk_generation.unloaded(&removed);
+        self.chunk_saver()
+            .release_unwatched(&self.level_folder, &positions)
+            .await;
+        positions.len()
+    }
+
     pub async fn clean_up_log(&self) {
-        self.chunk_saver.clean_up_log().await;
+        self.chunk_saver().clean_up_log().await;
+    }
+
+    /// The region file IO of the current chunk storage
+    pub fn chunk_io_metrics(&self) -> ChunkIoMetrics {
+        self.chunk_saver().metrics()
     }
 
     pub fn list_cached(&self) {
@@ -197,7 +678,7 @@ impl Level {
             }
         }
 
-        self.chunk_saver
+        self.chunk_saver()
             .watch_chunks(&self.level_folder, chunks)
             .await;
     }
@@ -233,7 +714,7 @@ impl Level {
             }
         }
 
-        self.chunk_saver
+        self.chunk_saver()
             .unwatch_chunks(&self.level_folder, chunks)
             .await;
         chunks_to_clean
@@ -257,11 +738,7 @@ impl Level {
             .iter()
             .filter_map(|pos| {
                 // Only chunks that have no entry in the watcher map or have 0 watchers
-                if self
-                    .chunk_watchers
-                    .get(pos)
-                    .is_none_or(|count| count.is_zero())
-                {
+                if !self.is_chunk_in_use(pos) {
                     self.loaded_chunks
                         .get(pos)
                         .map(|chunk| (*pos, chunk.value().clone()))
@@ -277,15 +754,17 @@ impl Level {
             level.write_chunks(chunks_with_no_watchers).await;
             // Only after we have written the chunks to the serializer do we remove them from the
             // cache
-            for (pos, _) in chunks_to_remove {
-                let _ = level.loaded_chunks.remove_if(&pos, |_, _| {
+            let removed: Vec<_> = chunks_to_remove
+                .into_iter()
+                .filter_map(|(pos, _)| {
                     // Recheck that there is no one watching
                     level
-                        .chunk_watchers
-                        .get(&pos)
-                        .is_none_or(|count| count.is_zero())
-                });
-            }
+                        .loaded_chunks
+                        .remove_if(&pos, |_, _| !level.is_chunk_in_use(&pos))
+                        .map(|_| pos)
+                })
+                .collect();
+            level.chunk_generation.unloaded(&removed);
         });
     }
 
@@ -294,13 +773,147 @@ impl Level {
     }
 
     pub fn is_chunk_watched(&self, chunk: &Vector2<i32>) -> bool {
-        self.chunk_watchers.get(chunk).is_some()
+        self.chunk_watchers.get(chunk).is_some() || self.is_chunk_forced(chunk)
+    }
+
+    /// Returns whether a `ChunkHandle` is currently keeping this chunk loaded
+    pub fn is_chunk_forced(&self, chunk: &Vector2<i32>) -> bool {
+        self.forced_chunks.contains_key(chunk)
+    }
+
+    /// Returns the positions of all the chunks currently kept loaded by a `ChunkHandle`
+    pub fn forced_chunks(&self) -> Vec<Vector2<i32>> {
+        self.forced_chunks
+            .iter()
+            .map(|entry| *entry.key())
+            .collect()
+    }
+
+    /// Returns the positions of the chunks which are watched by a player or forced to stay
+    /// loaded, their blocks get random ticks
+    pub fn ticking_chunks(&self) -> Vec<Vector2<i32>> {
+        let mut chunks: Vec<_> = self
+            .chunk_watchers
+            .iter()
+            .map(|entry| *entry.key())
+            .collect();
+        chunks.extend(
+            self.forced_chunks
+                .iter()
+                .map(|entry| *entry.key())
+                .filter(|chunk| !self.chunk_watchers.contains_key(chunk)),
+        );
+        chunks
+    }
+
+    pub fn forced_chunk_count(&self) -> usize {
+        self.forced_chunks.len()
+    }
+
+    pub fn is_chunk_loaded(&self, chunk: &Vector2<i32>) -> bool {
+        self.loaded_chunks.contains_key(chunk)
+    }
+
+    /// Returns the chunk if it is in memory, without loading or generating it
+    pub fn get_loaded_chunk(&self, chunk: &Vector2<i32>) -> Option<SyncChunk> {
+        self.loaded_chunks
+            .get(chunk)
+            .or_else(|| self.spawn_chunks.get(chunk))
+            .map(|chunk| chunk.value().clone())
+    }
+
+    /// Returns the amount of unique players watching this chunk
+    pub fn chunk_watcher_count(&self, chunk: &Vector2<i32>) -> usize {
+        self.chunk_watchers.get(chunk).map_or(0, |count| *count)
+    }
+
+    /// Returns the amount of `ChunkHandle`s alive for this chunk
+    pub fn chunk_ticket_count(&self, chunk: &Vector2<i32>) -> usize {
+        self.forced_chunks.get(chunk).map_or(0, |count| *count)
+    }
+
+    fn is_chunk_in_use(&self, chunk: &Vector2<i32>) -> bool {
+        self.chunk_watchers
+            .get(chunk)
+            .is_some_and(|count| !count.is_zero())
+            || self.is_chunk_forced(chunk)
+    }
+
+    /// Forces a chunk to stay loaded (and ticking) for as long as the returned `ChunkHandle` is
+    /// alive. The chunk is loaded or generated if it is not in memory yet.
+    ///
+    /// Many handles can be held for the same chunk; the chunk is released once the last one is
+    /// dropped. At most `max_forced_chunks` (see `ChunkConfig`) distinct chunks can be forced at
+    /// the same time.
+    pub async fn force_load_chunk(
+        self: &Arc<Self>,
+        chunk: Vector2<i32>,
+    ) -> Result<ChunkHandle, ChunkTicketError> {
+        let max_forced_chunks = advanced_config().chunk.max_forced_chunks;
+        // NOTE: `len` can't be called while holding an entry of the map, so the cap is only a
+        // soft limit when many tickets are requested concurrently
+        if !self.forced_chunks.contains_key(&chunk) && self.forced_chunks.len() >= max_forced_chunks
+        {
+            return Err(ChunkTicketError::LimitReached(max_forced_chunks));
+        }
+        *self.forced_chunks.entry(chunk).or_insert(0) += 1;
+        // Guard first, so the ticket is released if loading panics
+        let mut handle = ChunkHandle {
+            level: self.clone(),
+            position: chunk,
+            chunk: None,
+        };
+
+        let (send, mut recv) = mpsc::unbounded_channel();
+        self.fetch_chunks(&[chunk], send).await;
+        let Some((loaded, _)) = recv.recv().await else {
+            return Err(ChunkTicketError::LoadFailed(chunk));
+        };
+        log::debug!("Chunk {:?} is now forced loaded", chunk);
+        handle.chunk = Some(loaded);
+        Ok(handle)
+    }
+
+    fn release_chunk_ticket(self: &Arc<Self>, chunk: Vector2<i32>) {
+        let released = match self.forced_chunks.entry(chunk) {
+            Entry::Occupied(mut occupied) => {
+                let value = occupied.get_mut();
+                *value = value.saturating_sub(1);
+                if value.is_zero() {
+                    occupied.remove_entry();
+                    true
+                } else {
+                    false
+                }
+            }
+            Entry::Vacant(_) => false,
+        };
+
+        if released {
+            log::debug!("Chunk {:?} is no longer forced loaded", chunk);
+            // Handles can be dropped outside of the runtime (e.g. when a plugin is unloaded),
+            // in that case the chunk will be cleaned by the next memory clean up
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let level = self.clone();
+                runtime.spawn(async move {
+                    level.clean_chunk(&chunk).await;
+                });
+            }
+        }
     }
 
     pub fn clean_memory(&self) {
         self.chunk_watchers.retain(|_, watcher| !watcher.is_zero());
-        self.loaded_chunks
-            .retain(|at, _| self.chunk_watchers.get(at).is_some());
+        let mut unloaded = Vec::new();
+        self.loaded_chunks.retain(|at, _| {
+            let keep =
+                self.chunk_watchers.get(at).is_some() || self.forced_chunks.get(at).is_some();
+            if !keep {
+                unloaded.push(*at);
+            }
+            keep
+        });
+        self.chunk_generation.unloaded(&unloaded);
 
         // if the difference is too big, we can shrink the loaded chunks
         // (1024 chunks is the equivalent to a 32x32 chunks area)
@@ -320,7 +933,19 @@ impl Level {
             return;
         }
 
-        let chunk_saver = self.chunk_saver.clone();
+        let chunk_saver = {
+            let storage = self
+                .chunk_storage
+                .read()
+                .expect("Chunk storage lock is poisoned");
+            // Recorded under the lock, so a migration knows which chunks the new format wrote
+            if let Some(written) = &storage.cutover_writes {
+                for (position, _) in &chunks_to_write {
+                    written.insert(*position);
+                }
+            }
+            storage.io.clone()
+        };
         let level_folder = self.level_folder.clone();
 
         trace!("Sending chunks to ChunkIO {:}", chunks_to_write.len());
@@ -332,6 +957,54 @@ impl Level {
         }
     }
 
+    /// Reads the entities saved in these chunks, chunks without any saved are left out
+    pub async fn read_entities(&self, chunks: &[Vector2<i32>]) -> Vec<ChunkEntityData> {
+        if chunks.is_empty() {
+            return Vec::new();
+        }
+
+        let (send, mut recv) = mpsc::channel(chunks.len());
+        self.entity_io
+            .fetch_chunks(&self.level_folder, chunks, send)
+            .await;
+        // The entities are spawned now and saved again when their chunks unload, so the
+        // regions don't need to stay cached
+        self.entity_io
+            .release_unwatched(&self.level_folder, chunks)
+            .await;
+
+        let mut entities = Vec::new();
+        while let Some(data) = recv.recv().await {
+            match data {
+                LoadedData::Loaded(chunk) => entities.push(chunk.read().await.clone()),
+                LoadedData::Missing(_)
+                | LoadedData::Error((_, ChunkReadingError::ChunkNotExist)) => {}
+                LoadedData::Error((pos, error)) => {
+                    log::warn!("Failed to load the entities of chunk {:?}: {}", pos, error);
+                }
+            }
+        }
+        entities
+    }
+
+    /// Saves the entities of these chunks, replacing what was saved for them before
+    pub async fn write_entities(&self, chunks: Vec<ChunkEntityData>) {
+        if chunks.is_empty() {
+            return;
+        }
+
+        let chunks = chunks
+            .into_iter()
+            .map(|mut chunk| {
+                chunk.dirty = true;
+                (chunk.position, Arc::new(RwLock::new(chunk)))
+            })
+            .collect();
+        if let Err(error) = self.entity_io.save_chunks(&self.level_folder, chunks).await {
+            log::error!("Failed writing entities to disk {}", error);
+        }
+    }
+
     /// Initializes the spawn chunks to these chunks
     pub async fn read_spawn_chunks(self: &Arc<Self>, chunks: &[Vector2<i32>]) {
         let (send, mut recv) = mpsc::unbounded_channel();
@@ -396,11 +1069,13 @@ impl Level {
 
         let load_channel = channel.clone();
         let loaded_chunks = self.loaded_chunks.clone();
+        let chunk_generation = self.chunk_generation.clone();
         let handle_load = async move {
             while let Some(data) = load_bridge_recv.recv().await {
                 match data {
                     LoadedData::Loaded(chunk) => {
                         let position = chunk.read().await.position;
+                        chunk_generation.loaded(position);
                         let value = loaded_chunks
                             .entry(position)
                             .or_insert(chunk)
@@ -439,22 +1114,27 @@ impl Level {
         };
 
         let loaded_chunks = self.loaded_chunks.clone();
-        let world_gen = self.world_gen.clone();
+        let chunk_generation = self.chunk_generation.clone();
         let handle_generate = async move {
             while let Some(pos) = generate_bridge_recv.recv().await {
                 let loaded_chunks = loaded_chunks.clone();
-                let world_gen = world_gen.clone();
+                let chunk_generation = chunk_generation.clone();
                 let channel = channel.clone();
                 rayon::spawn(move || {
-                    let result = loaded_chunks
-                        .entry(pos)
-                        .or_insert_with(|| {
-                            // Avoid possible duplicating work by doing this within the dashmap lock
-                            let generated_chunk = world_gen.generate_chunk(pos);
-                            Arc::new(RwLock::new(generated_chunk))
-                        })
-                        .value()
-                        .clone();
+                    let result = match loaded_chunks.get(&pos) {
+                        Some(chunk) => chunk.value().clone(),
+                        None => {
+                            // Generating uses the rayon pool as well, so this must not hold the
+                            // dashmap lock. If the chunk got loaded in the meantime the generated
+                            // one is dropped
+                            let generated_chunk = chunk_generation.generate(pos);
+                            loaded_chunks
+                                .entry(pos)
+                                .or_insert_with(|| Arc::new(RwLock::new(generated_chunk)))
+                                .value()
+                                .clone()
+                        }
+                    };
 
                     send_chunk(true, result, &channel);
                 });
@@ -465,9 +1145,45 @@ impl Level {
         set.spawn(handle_load);
         set.spawn(handle_generate);
 
-        self.chunk_saver
+        self.chunk_saver()
             .fetch_chunks(&self.level_folder, &remaining_chunks, load_bridge_send)
             .await;
         let _ = set.join_all().await;
     }
 }
+
+#[derive(Error, Debug)]
+pub enum ChunkTicketError {
+    #[error("Only {0} chunks can be forced loaded at the same time")]
+    LimitReached(usize),
+    #[error("Failed to load the forced chunk at {0:?}")]
+    LoadFailed(Vector2<i32>),
+}
+
+/// A ticket that keeps a chunk loaded and ticking, see [`Level::force_load_chunk`].
+///
+/// Dropping the handle releases the ticket. Once no handles are left for a chunk and no players
+/// are watching it, the chunk is saved and removed from memory.
+pub struct ChunkHandle {
+    level: Arc<Level>,
+    position: Vector2<i32>,
+    chunk: Option<SyncChunk>,
+}
+
+impl ChunkHandle {
+    pub fn position(&self) -> Vector2<i32> {
+        self.position
+    }
+
+    pub fn chunk(&self) -> &SyncChunk {
+        self.chunk
+            .as_ref()
+            .expect("A ChunkHandle is only handed out once the chunk is loaded")
+    }
+}
+
+impl Drop for ChunkHandle {
+    fn drop(&mut self) {
+        self.level.release_chunk_ticket(self.position);
+    }
+}
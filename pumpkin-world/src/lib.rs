@@ -1,5 +1,6 @@
 use pumpkin_util::math::vector2::Vector2;
 
+pub mod advancement;
 pub mod biome;
 pub mod block;
 pub mod chunk;
@@ -11,7 +12,11 @@ pub mod item;
 pub mod level;
 mod lock;
 mod noise_router;
+pub mod raycast;
 pub mod world_info;
+
+pub use generation::{EndSpike, end_spikes};
+
 pub const WORLD_HEIGHT: usize = 384;
 pub const WORLD_LOWEST_Y: i16 = -64;
 pub const WORLD_MAX_Y: i16 = WORLD_HEIGHT as i16 - WORLD_LOWEST_Y.abs();
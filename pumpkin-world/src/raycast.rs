@@ -0,0 +1,452 @@
+use pumpkin_data::block::{Block, PartialBlockState};
+use pumpkin_util::math::{
+    boundingbox::BoundingBox, position::BlockPos, vector2::Vector2, vector3::Vector3,
+};
+use tokio::sync::OwnedRwLockReadGuard;
+
+use crate::{
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+    block::{BlockDirection, registry::get_block_collision_boxes},
+    chunk::ChunkData,
+    coordinates::ChunkRelativeBlockCoordinates,
+    level::Level,
+};
+
+/// Which fluids stop a block raycast
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FluidHandling {
+    /// Rays pass through fluids
+    #[default]
+    None,
+    /// Only fluid sources stop rays, like when placing a boat or filling a bucket
+    SourceOnly,
+    /// Any fluid stops rays
+    Any,
+}
+
+/// Where a ray hit a block
+#[derive(Clone, Copy, PartialEq)]
+pub struct BlockHitResult {
+    pub block_pos: BlockPos,
+    pub state_id: u16,
+    /// The face of the block the ray entered through
+    pub face: BlockDirection,
+    /// The exact point the ray hit the block
+    pub location: Vector3<f64>,
+    /// The distance from the origin of the ray to `location`
+    pub distance: f64,
+}
+
+/// Where a ray hit an entity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityHitResult<T> {
+    pub entity: T,
+    pub location: Vector3<f64>,
+    pub distance: f64,
+}
+
+/// A single block visited by a `VoxelRay`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoxelStep {
+    pub pos: Vector3<i32>,
+    /// The face the ray entered the block through, `None` for the block the ray starts in
+    pub face: Option<BlockDirection>,
+    /// The distance along the ray at which the block is entered
+    pub distance: f64,
+}
+
+/// Iterates over every block a ray passes through, in order, using the DDA algorithm
+/// (Amanatides & Woo)
+pub struct VoxelRay {
+    current: Vector3<i32>,
+    step: Vector3<i32>,
+    /// Distance along the ray to the next block boundary of every axis
+    t_max: Vector3<f64>,
+    /// Distance along the ray between two block boundaries of every axis
+    t_delta: Vector3<f64>,
+    max_distance: f64,
+    started: bool,
+}
+
+impl VoxelRay {
+    /// `direction` does not have to be normalized, `max_distance` is in blocks
+    #[must_use]
+    pub fn new(origin: Vector3<f64>, direction: Vector3<f64>, max_distance: f64) -> Self {
+        let length = direction.length();
+        let direction = if length > 0.0 {
+            direction * (1.0 / length)
+        } else {
+            direction
+        };
+
+        let axis = |origin: f64, direction: f64| -> (i32, f64, f64) {
+            let block = origin.floor();
+            if direction > 0.0 {
+                (1, (block + 1.0 - origin) / direction, 1.0 / direction)
+            } else if direction < 0.0 {
+                (-1, (origin - block) / -direction, -1.0 / direction)
+            } else {
+                (0, f64::INFINITY, f64::INFINITY)
+            }
+        };
+        let (step_x, t_max_x, t_delta_x) = axis(origin.x, direction.x);
+        let (step_y, t_max_y, t_delta_y) = axis(origin.y, direction.y);
+        let (step_z, t_max_z, t_delta_z) = axis(origin.z, direction.z);
+
+        Self {
+            current: Vector3::new(
+                origin.x.floor() as i32,
+                origin.y.floor() as i32,
+                origin.z.floor() as i32,
+            ),
+            step: Vector3::new(step_x, step_y, step_z),
+            t_max: Vector3::new(t_max_x, t_max_y, t_max_z),
+            t_delta: Vector3::new(t_delta_x, t_delta_y, t_delta_z),
+            max_distance: if length > 0.0 { max_distance } else { 0.0 },
+            started: false,
+        }
+    }
+}
+
+impl Iterator for VoxelRay {
+    type Item = VoxelStep;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            return Some(VoxelStep {
+                pos: self.current,
+                face: None,
+                distance: 0.0,
+            });
+        }
+
+        let distance;
+        let face;
+        if self.t_max.x <= self.t_max.y && self.t_max.x <= self.t_max.z {
+            distance = self.t_max.x;
+            self.current.x += self.step.x;
+            self.t_max.x += self.t_delta.x;
+            face = if self.step.x > 0 {
+                BlockDirection::West
+            } else {
+                BlockDirection::East
+            };
+        } else if self.t_max.y <= self.t_max.z {
+            distance = self.t_max.y;
+            self.current.y += self.step.y;
+            self.t_max.y += self.t_delta.y;
+            face = if self.step.y > 0 {
+                BlockDirection::Down
+            } else {
+                BlockDirection::Up
+            };
+        } else {
+            distance = self.t_max.z;
+            self.current.z += self.step.z;
+            self.t_max.z += self.t_delta.z;
+            face = if self.step.z > 0 {
+                BlockDirection::North
+            } else {
+                BlockDirection::South
+            };
+        }
+
+        if distance > self.max_distance {
+            return None;
+        }
+        Some(VoxelStep {
+            pos: self.current,
+            face: Some(face),
+            distance,
+        })
+    }
+}
+
+/// Intersects a ray with a bounding box using the slab method.
+///
+/// Returns the distance along the (normalized) ray and the face it entered through. A ray starting
+/// inside the box hits it at distance 0.
+#[must_use]
+pub fn ray_box_intersection(
+    origin: Vector3<f64>,
+    direction: Vector3<f64>,
+    aabb: &BoundingBox,
+) -> Option<(f64, BlockDirection)> {
+    let length = direction.length();
+    if length <= 0.0 {
+        return None;
+    }
+    let direction = direction * (1.0 / length);
+
+    let mut t_near = f64::NEG_INFINITY;
+    let mut t_far = f64::INFINITY;
+    let mut face = BlockDirection::Down;
+    let axes = [
+        (
+            origin.x,
+            direction.x,
+            aabb.min.x,
+            aabb.max.x,
+            BlockDirection::West,
+            BlockDirection::East,
+        ),
+        (
+            origin.y,
+            direction.y,
+            aabb.min.y,
+            aabb.max.y,
+            BlockDirection::Down,
+            BlockDirection::Up,
+        ),
+        (
+            origin.z,
+            direction.z,
+            aabb.min.z,
+            aabb.max.z,
+            BlockDirection::North,
+            BlockDirection::South,
+        ),
+    ];
+    for (origin, direction, min, max, min_face, max_face) in axes {
+        if direction == 0.0 {
+            if origin < min || origin > max {
+                return None;
+            }
+            continue;
+        }
+        let (t1, t2, near_face) = if direction > 0.0 {
+            (
+                (min - origin) / direction,
+                (max - origin) / direction,
+                min_face,
+            )
+        } else {
+            (
+                (max - origin) / direction,
+                (min - origin) / direction,
+                max_face,
+            )
+        };
+        if t1 > t_near {
+            t_near = t1;
+            face = near_face;
+        }
+        t_far = t_far.min(t2);
+        if t_near > t_far {
+            return None;
+        }
+    }
+
+    if t_far < 0.0 {
+        return None;
+    }
+    Some((t_near.max(0.0), face))
+}
+
+/// Finds the closest entity whose bounding box the ray hits within `max_distance`
+pub fn raycast_entities<T>(
+    origin: Vector3<f64>,
+    direction: Vector3<f64>,
+    max_distance: f64,
+    candidates: impl IntoIterator<Item = (T, BoundingBox)>,
+) -> Option<EntityHitResult<T>> {
+    let length = direction.length();
+    if length <= 0.0 {
+        return None;
+    }
+    let direction = direction * (1.0 / length);
+
+    candidates
+        .into_iter()
+        .filter_map(|(entity, aabb)| {
+            let (distance, _) = ray_box_intersection(origin, direction, &aabb)?;
+            (distance <= max_distance).then(|| EntityHitResult {
+                entity,
+                location: origin + direction * distance,
+                distance,
+            })
+        })
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
+
+/// Whether a block state stops a ray, returns the closest hit within the block
+fn hit_block(
+    origin: Vector3<f64>,
+    direction: Vector3<f64>,
+    pos: Vector3<i32>,
+    state_id: u16,
+    fluid_handling: FluidHandling,
+) -> Option<(f64, BlockDirection)> {
+    let state = PartialBlockState::from_state_id(state_id)?;
+    if state.is_liquid {
+        let stops = match fluid_handling {
+            FluidHandling::None => false,
+            // The default state of a fluid block is its source
+            FluidHandling::SourceOnly => Block::from_state_id(state_id)
+                .is_some_and(|block| block.default_state_id == state_id),
+            FluidHandling::Any => true,
+        };
+        if !stops {
+            return None;
+        }
+        return ray_box_intersection(origin, direction, &BoundingBox::from_block(&BlockPos(pos)));
+    }
+
+    get_block_collision_boxes(state_id, &BlockPos(pos))
+        .iter()
+        .filter_map(|aabb| ray_box_intersection(origin, direction, aabb))
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+}
+
+impl Level {
+    /// Casts a ray through the loaded chunks and returns the first block it hits.
+    ///
+    /// Blocks are hit by their collision shapes, fluids depending on `fluid_handling`. The ray
+    /// stops at unloaded chunks, they are never loaded by a raycast.
+    pub async fn raycast_blocks(
+        &self,
+        origin: Vector3<f64>,
+        direction: Vector3<f64>,
+        max_distance: f64,
+        fluid_handling: FluidHandling,
+    ) -> Option<BlockHitResult> {
+        let length = direction.length();
+        if length <= 0.0 {
+            return None;
+        }
+        let direction = direction * (1.0 / length);
+
+        // The chunk of the previous block, most steps stay in the same chunk
+        let mut cached: Option<(Vector2<i32>, OwnedRwLockReadGuard<ChunkData>)> = None;
+        for step in VoxelRay::new(origin, direction, max_distance) {
+            if step.pos.y < i32::from(WORLD_LOWEST_Y) || step.pos.y >= i32::from(WORLD_MAX_Y) {
+                continue;
+            }
+            let (chunk_pos, relative) = BlockPos(step.pos).chunk_and_chunk_relative_position();
+            if cached.as_ref().is_none_or(|(pos, _)| *pos != chunk_pos) {
+                let chunk = self.get_loaded_chunk(&chunk_pos)?;
+                cached = Some((chunk_pos, chunk.read_owned().await));
+            }
+            let (_, chunk) = cached.as_ref()?;
+            let Some(state_id) = chunk
+                .subchunks
+                .get_block(ChunkRelativeBlockCoordinates::from(relative))
+            else {
+                continue;
+            };
+
+            if let Some((distance, face)) =
+                hit_block(origin, direction, step.pos, state_id, fluid_handling)
+            {
+                if distance > max_distance {
+                    return None;
+                }
+                return Some(BlockHitResult {
+                    block_pos: BlockPos(step.pos),
+                    state_id,
+                    face,
+                    location: origin + direction * distance,
+                    distance,
+                });
+            }
+        }
+        None
+    }
+
+    /// Whether nothing blocks the line between two points, fluids don't block the view
+    pub async fn has_line_of_sight(&self, from: Vector3<f64>, to: Vector3<f64>) -> bool {
+        let direction = to.sub(&from);
+        let distance = direction.length();
+        self.raycast_blocks(from, direction, distance, FluidHandling::None)
+            .await
+            .is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::math::{boundingbox::BoundingBox, vector3::Vector3};
+
+    use super::{VoxelRay, ray_box_intersection, raycast_entities};
+    use crate::block::BlockDirection;
+
+    #[test]
+    fn voxel_ray_visits_blocks_in_order() {
+        let positions: Vec<_> = VoxelRay::new(
+            Vector3::new(0.5, 0.5, 0.5),
+            Vector3::new(1.0, 0.0, 0.0),
+            3.0,
+        )
+        .map(|step| (step.pos.x, step.pos.y, step.pos.z))
+        .collect();
+        assert_eq!(positions, vec![(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0)]);
+
+        // Diagonal rays never skip a block, every step changes one axis
+        let steps: Vec<_> = VoxelRay::new(
+            Vector3::new(0.2, 0.7, 0.5),
+            Vector3::new(-1.0, -2.0, 0.5),
+            10.0,
+        )
+        .collect();
+        for pair in steps.windows(2) {
+            let (a, b) = (pair[0].pos, pair[1].pos);
+            let changed = (a.x - b.x).abs() + (a.y - b.y).abs() + (a.z - b.z).abs();
+            assert_eq!(changed, 1);
+            assert!(pair[0].distance <= pair[1].distance);
+        }
+    }
+
+    #[test]
+    fn ray_hits_box_faces() {
+        let aabb = BoundingBox {
+            min: Vector3::new(2.0, 0.0, 0.0),
+            max: Vector3::new(3.0, 1.0, 1.0),
+        };
+        let hit = ray_box_intersection(
+            Vector3::new(0.0, 0.5, 0.5),
+            Vector3::new(1.0, 0.0, 0.0),
+            &aabb,
+        );
+        assert_eq!(hit, Some((2.0, BlockDirection::West)));
+
+        let miss = ray_box_intersection(
+            Vector3::new(0.0, 1.5, 0.5),
+            Vector3::new(1.0, 0.0, 0.0),
+            &aabb,
+        );
+        assert_eq!(miss, None);
+
+        let behind = ray_box_intersection(
+            Vector3::new(4.0, 0.5, 0.5),
+            Vector3::new(1.0, 0.0, 0.0),
+            &aabb,
+        );
+        assert_eq!(behind, None);
+    }
+
+    #[test]
+    fn closest_entity_is_hit() {
+        let entity_box = |x: f64| BoundingBox {
+            min: Vector3::new(x, 0.0, -0.5),
+            max: Vector3::new(x + 0.6, 1.8, 0.5),
+        };
+        let hit = raycast_entities(
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            10.0,
+            [("far", entity_box(6.0)), ("near", entity_box(3.0))],
+        )
+        .unwrap();
+        assert_eq!(hit.entity, "near");
+        assert_eq!(hit.distance, 3.0);
+
+        let out_of_range = raycast_entities(
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            2.0,
+            [("near", entity_box(3.0))],
+        );
+        assert!(out_of_range.is_none());
+    }
+}
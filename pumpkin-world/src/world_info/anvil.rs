@@ -9,7 +9,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     level::LevelFolder,
-    world_info::{MAXIMUM_SUPPORTED_WORLD_DATA_VERSION, MINIMUM_SUPPORTED_WORLD_DATA_VERSION},
+    world_info::{
+        MAXIMUM_SUPPORTED_WORLD_DATA_VERSION, MINIMUM_SUPPORTED_WORLD_DATA_VERSION, SERVER_BRAND,
+        WorldVersion,
+    },
 };
 
 use super::{LevelData, WorldInfoError, WorldInfoReader, WorldInfoWriter};
@@ -84,6 +87,17 @@ impl WorldInfoWriter for AnvilLevelInfo {
             .expect("Time went backwards");
         let mut level_data = info.clone();
         level_data.last_played = since_the_epoch.as_millis() as i64;
+        // The chunks are written in the format of the version Pumpkin supports
+        level_data.data_version = MAXIMUM_SUPPORTED_WORLD_DATA_VERSION;
+        level_data.version = WorldVersion::default();
+        if !level_data
+            .server_brands
+            .iter()
+            .any(|brand| brand == SERVER_BRAND)
+        {
+            level_data.server_brands.push(SERVER_BRAND.to_string());
+        }
+        level_data.was_modded = true;
         let level = LevelDat { data: level_data };
 
         // open file
@@ -95,10 +109,10 @@ impl WorldInfoWriter for AnvilLevelInfo {
             .open(path)?;
 
         // write compressed data into file
-        let compression_writer = GzEncoder::new(world_info_file, Compression::best());
-        // TODO: Proper error handling
-        pumpkin_nbt::to_bytes(&level, compression_writer)
-            .expect("Failed to write level.dat to disk");
+        let mut compression_writer = GzEncoder::new(world_info_file, Compression::best());
+        pumpkin_nbt::to_bytes(&level, &mut compression_writer)
+            .map_err(|e| WorldInfoError::SerializationError(e.to_string()))?;
+        compression_writer.finish()?;
         Ok(())
     }
 }
@@ -122,7 +136,11 @@ mod test {
     use crate::{
         global_path,
         level::LevelFolder,
-        world_info::{DataPacks, LevelData, WorldGenSettings, WorldInfoError, WorldVersion},
+        world_info::{
+            DataPacks, LevelData, SERVER_BRAND, WorldGenSettings, WorldInfoError, WorldVersion,
+            default_dimensions,
+            game_rules::{GameRules, KEEP_INVENTORY},
+        },
     };
 
     use super::{AnvilLevelInfo, LEVEL_DAT_FILE_NAME, LevelDat, WorldInfoReader, WorldInfoWriter};
@@ -133,11 +151,13 @@ mod test {
 
         let mut data = LevelData::default();
         data.world_gen_settings.seed = seed;
+        data.game_rules.set(KEEP_INVENTORY, true);
 
         let temp_dir = TempDir::new().unwrap();
         let level_folder = LevelFolder {
             root_folder: temp_dir.path().to_path_buf(),
             region_folder: temp_dir.path().join("region"),
+            entities_folder: temp_dir.path().join("entities"),
         };
 
         AnvilLevelInfo
@@ -147,6 +167,8 @@ mod test {
         let data = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
 
         assert_eq!(data.world_gen_settings.seed, seed);
+        assert!(data.game_rules.get(KEEP_INVENTORY));
+        assert_eq!(data.server_brands, [SERVER_BRAND]);
     }
 
     static LEVEL_DAT: LazyLock<LevelDat> = LazyLock::new(|| LevelDat {
@@ -171,12 +193,28 @@ mod test {
                 enabled: vec!["vanilla".to_string()],
             },
             data_version: 4189,
+            time: 1727,
             day_time: 1727,
             difficulty: 2,
             difficulty_locked: false,
-            world_gen_settings: WorldGenSettings { seed: 1 },
+            game_type: 1,
+            hardcore: false,
+            initialized: true,
+            game_rules: GameRules::default(),
+            server_brands: vec!["vanilla".to_string()],
+            was_modded: false,
+            world_gen_settings: WorldGenSettings {
+                bonus_chest: false,
+                seed: 1,
+                generate_features: false,
+                dimensions: default_dimensions(),
+            },
             last_played: 1733847709327,
             level_name: "New World".to_string(),
+            raining: false,
+            rain_time: 80942,
+            thundering: false,
+            thunder_time: 74299,
             spawn_x: 160,
             spawn_y: 70,
             spawn_z: 160,
@@ -221,6 +259,7 @@ mod test {
         let level_folder = LevelFolder {
             root_folder: temp_dir.path().to_path_buf(),
             region_folder: temp_dir.path().join("region"),
+            entities_folder: temp_dir.path().join("entities"),
         };
 
         let test_dat = global_path!("../../assets/level_1_20.dat");
@@ -0,0 +1,241 @@
+//! The game rules of a world, like whether players keep their inventory when they die. They are
+//! saved in the level.dat as strings, the way vanilla does it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A game rule that holds a `T`, the value it has in new worlds being `default`
+#[derive(Clone, Copy, Debug)]
+pub struct GameRule<T> {
+    pub name: &'static str,
+    pub default: T,
+}
+
+/// The value of any game rule
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameRuleValue {
+    Bool(bool),
+    Int(i32),
+}
+
+impl GameRuleValue {
+    /// Reads a value of the same kind as this one, like the value saved in the level.dat
+    #[must_use]
+    pub fn parse_like(self, value: &str) -> Option<Self> {
+        match self {
+            Self::Bool(_) => value.parse().ok().map(Self::Bool),
+            Self::Int(_) => value.parse().ok().map(Self::Int),
+        }
+    }
+}
+
+impl std::fmt::Display for GameRuleValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => value.fmt(f),
+            Self::Int(value) => value.fmt(f),
+        }
+    }
+}
+
+/// The types game rules can hold
+pub trait GameRuleType: Copy {
+    fn from_value(value: GameRuleValue) -> Option<Self>;
+    fn to_value(self) -> GameRuleValue;
+}
+
+impl GameRuleType for bool {
+    fn from_value(value: GameRuleValue) -> Option<Self> {
+        match value {
+            GameRuleValue::Bool(value) => Some(value),
+            GameRuleValue::Int(_) => None,
+        }
+    }
+
+    fn to_value(self) -> GameRuleValue {
+        GameRuleValue::Bool(self)
+    }
+}
+
+impl GameRuleType for i32 {
+    fn from_value(value: GameRuleValue) -> Option<Self> {
+        match value {
+            GameRuleValue::Int(value) => Some(value),
+            GameRuleValue::Bool(_) => None,
+        }
+    }
+
+    fn to_value(self) -> GameRuleValue {
+        GameRuleValue::Int(self)
+    }
+}
+
+macro_rules! game_rules {
+    ($($kind:ident $rule:ident = $name:literal, $default:expr;)*) => {
+        $(pub const $rule: GameRule<game_rules!(@type $kind)> = GameRule {
+            name: $name,
+            default: $default,
+        };)*
+
+        /// Every game rule with its default, sorted by name
+        pub const GAME_RULES: &[(&str, GameRuleValue)] = &[
+            $(($name, game_rules!(@value $kind $default)),)*
+        ];
+    };
+    (@type bool) => { bool };
+    (@type int) => { i32 };
+    (@value bool $default:expr) => { GameRuleValue::Bool($default) };
+    (@value int $default:expr) => { GameRuleValue::Int($default) };
+}
+
+game_rules! {
+    bool ANNOUNCE_ADVANCEMENTS = "announceAdvancements", true;
+    bool BLOCK_EXPLOSION_DROP_DECAY = "blockExplosionDropDecay", true;
+    bool COMMAND_BLOCK_OUTPUT = "commandBlockOutput", true;
+    int COMMAND_MODIFICATION_BLOCK_LIMIT = "commandModificationBlockLimit", 32768;
+    bool DISABLE_ELYTRA_MOVEMENT_CHECK = "disableElytraMovementCheck", false;
+    bool DISABLE_PLAYER_MOVEMENT_CHECK = "disablePlayerMovementCheck", false;
+    bool DISABLE_RAIDS = "disableRaids", false;
+    bool DO_DAYLIGHT_CYCLE = "doDaylightCycle", true;
+    bool DO_ENTITY_DROPS = "doEntityDrops", true;
+    bool DO_FIRE_TICK = "doFireTick", true;
+    bool DO_IMMEDIATE_RESPAWN = "doImmediateRespawn", false;
+    bool DO_INSOMNIA = "doInsomnia", true;
+    bool DO_LIMITED_CRAFTING = "doLimitedCrafting", false;
+    bool DO_MOB_LOOT = "doMobLoot", true;
+    bool DO_MOB_SPAWNING = "doMobSpawning", true;
+    bool DO_PATROL_SPAWNING = "doPatrolSpawning", true;
+    bool DO_TILE_DROPS = "doTileDrops", true;
+    bool DO_TRADER_SPAWNING = "doTraderSpawning", true;
+    bool DO_VINES_SPREAD = "doVinesSpread", true;
+    bool DO_WARDEN_SPAWNING = "doWardenSpawning", true;
+    bool DO_WEATHER_CYCLE = "doWeatherCycle", true;
+    bool DROWNING_DAMAGE = "drowningDamage", true;
+    bool ENDER_PEARLS_VANISH_ON_DEATH = "enderPearlsVanishOnDeath", true;
+    bool FALL_DAMAGE = "fallDamage", true;
+    bool FIRE_DAMAGE = "fireDamage", true;
+    bool FORGIVE_DEAD_PLAYERS = "forgiveDeadPlayers", true;
+    bool FREEZE_DAMAGE = "freezeDamage", true;
+    bool GLOBAL_SOUND_EVENTS = "globalSoundEvents", true;
+    bool KEEP_INVENTORY = "keepInventory", false;
+    bool LAVA_SOURCE_CONVERSION = "lavaSourceConversion", false;
+    bool LOG_ADMIN_COMMANDS = "logAdminCommands", true;
+    int MAX_COMMAND_CHAIN_LENGTH = "maxCommandChainLength", 65536;
+    int MAX_COMMAND_FORK_COUNT = "maxCommandForkCount", 65536;
+    int MAX_ENTITY_CRAMMING = "maxEntityCramming", 24;
+    bool MOB_EXPLOSION_DROP_DECAY = "mobExplosionDropDecay", true;
+    bool MOB_GRIEFING = "mobGriefing", true;
+    bool NATURAL_REGENERATION = "naturalRegeneration", true;
+    int PLAYERS_NETHER_PORTAL_CREATIVE_DELAY = "playersNetherPortalCreativeDelay", 0;
+    int PLAYERS_NETHER_PORTAL_DEFAULT_DELAY = "playersNetherPortalDefaultDelay", 80;
+    int PLAYERS_SLEEPING_PERCENTAGE = "playersSleepingPercentage", 100;
+    bool PROJECTILES_CAN_BREAK_BLOCKS = "projectilesCanBreakBlocks", true;
+    int RANDOM_TICK_SPEED = "randomTickSpeed", 3;
+    bool REDUCED_DEBUG_INFO = "reducedDebugInfo", false;
+    bool SEND_COMMAND_FEEDBACK = "sendCommandFeedback", true;
+    bool SHOW_DEATH_MESSAGES = "showDeathMessages", true;
+    int SNOW_ACCUMULATION_HEIGHT = "snowAccumulationHeight", 1;
+    int SPAWN_CHUNK_RADIUS = "spawnChunkRadius", 2;
+    int SPAWN_RADIUS = "spawnRadius", 10;
+    bool SPECTATORS_GENERATE_CHUNKS = "spectatorsGenerateChunks", true;
+    bool TNT_EXPLOSION_DROP_DECAY = "tntExplosionDropDecay", false;
+    bool UNIVERSAL_ANGER = "universalAnger", false;
+    bool WATER_SOURCE_CONVERSION = "waterSourceConversion", true;
+}
+
+/// The value of every game rule of a world
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameRules {
+    values: BTreeMap<&'static str, GameRuleValue>,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            values: GAME_RULES.iter().copied().collect(),
+        }
+    }
+}
+
+impl GameRules {
+    #[must_use]
+    pub fn get<T: GameRuleType>(&self, rule: GameRule<T>) -> T {
+        self.values
+            .get(rule.name)
+            .copied()
+            .and_then(T::from_value)
+            .unwrap_or(rule.default)
+    }
+
+    pub fn set<T: GameRuleType>(&mut self, rule: GameRule<T>, value: T) {
+        self.values.insert(rule.name, value.to_value());
+    }
+
+    /// The value of the game rule with the name, `None` if there is no such rule
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<GameRuleValue> {
+        self.values.get(name).copied()
+    }
+
+    /// Sets the game rule with the name, `false` if there is no such rule or the value is of
+    /// another kind
+    pub fn set_by_name(&mut self, name: &str, value: GameRuleValue) -> bool {
+        match self.values.get_mut(name) {
+            Some(current) if std::mem::discriminant(current) == std::mem::discriminant(&value) => {
+                *current = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, GameRuleValue)> + '_ {
+        self.values.iter().map(|(name, value)| (*name, *value))
+    }
+}
+
+impl Serialize for GameRules {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter().map(|(name, value)| (name, value.to_string())))
+    }
+}
+
+/// Rules the world doesn't have keep their default, unknown rules and invalid values are dropped
+/// like vanilla does
+impl<'de> Deserialize<'de> for GameRules {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let saved = BTreeMap::<String, String>::deserialize(deserializer)?;
+        let mut rules = Self::default();
+        for (name, value) in &mut rules.values {
+            if let Some(saved) = saved.get(*name).and_then(|saved| value.parse_like(saved)) {
+                *value = saved;
+            }
+        }
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_keep_their_kind() {
+        let mut rules = GameRules::default();
+        assert!(!rules.get(KEEP_INVENTORY));
+        assert_eq!(rules.get(RANDOM_TICK_SPEED), 3);
+
+        rules.set(KEEP_INVENTORY, true);
+        assert!(rules.get(KEEP_INVENTORY));
+        assert!(!rules.set_by_name("randomTickSpeed", GameRuleValue::Bool(true)));
+        assert!(!rules.set_by_name("noSuchRule", GameRuleValue::Int(1)));
+        assert!(rules.set_by_name("randomTickSpeed", GameRuleValue::Int(10)));
+        assert_eq!(rules.get(RANDOM_TICK_SPEED), 10);
+    }
+
+    #[test]
+    fn sorted_by_name() {
+        assert!(GAME_RULES.windows(2).all(|rules| rules[0].0 < rules[1].0));
+    }
+}
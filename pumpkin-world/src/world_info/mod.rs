@@ -1,14 +1,20 @@
 use pumpkin_config::BASIC_CONFIG;
-use pumpkin_util::Difficulty;
+use pumpkin_nbt::compound::NbtCompound;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{generation::Seed, level::LevelFolder};
+use game_rules::GameRules;
 
 pub mod anvil;
+pub mod game_rules;
 
 pub const MINIMUM_SUPPORTED_WORLD_DATA_VERSION: i32 = 4080; // 1.21.2
 pub const MAXIMUM_SUPPORTED_WORLD_DATA_VERSION: i32 = 4189; // 1.21.4
+/// The version of the Anvil level format, stays the same across game versions
+pub const NBT_VERSION: i32 = 19133;
+/// The brand added to `ServerBrands` when the world is saved
+pub const SERVER_BRAND: &str = "Pumpkin";
 
 pub(crate) trait WorldInfoReader {
     fn read_world_info(&self, level_folder: &LevelFolder) -> Result<LevelData, WorldInfoError>;
@@ -55,12 +61,32 @@ pub struct LevelData {
     pub data_packs: DataPacks,
     // An integer displaying the data version.
     pub data_version: i32,
+    // The number of ticks since the start of the level.
+    pub time: i64,
     // The time of day. 0 is sunrise, 6000 is mid day, 12000 is sunset, 18000 is mid night, 24000 is the next day's 0. This value keeps counting past 24000 and does not reset to 0.
     pub day_time: i64,
     // The current difficulty setting.
     pub difficulty: i8,
     // 1 or 0 (true/false) - True if the difficulty has been locked. Defaults to 0.
     pub difficulty_locked: bool,
+    // The default game mode of the world.
+    #[serde(default)]
+    pub game_type: i32,
+    // true if the player respawns in spectator mode when they die.
+    #[serde(rename = "hardcore", default)]
+    pub hardcore: bool,
+    // true if the spawn of the world has been set up.
+    #[serde(rename = "initialized", default)]
+    pub initialized: bool,
+    // The value of each game rule.
+    #[serde(default)]
+    pub game_rules: GameRules,
+    // The brands of the servers that opened the world.
+    #[serde(default)]
+    pub server_brands: Vec<String>,
+    // true if a modified server opened the world.
+    #[serde(default)]
+    pub was_modded: bool,
     // TODO: DimensionData
 
     // the generation settings for each dimension.
@@ -69,6 +95,18 @@ pub struct LevelData {
     pub last_played: i64,
     // The name of the level.
     pub level_name: String,
+    // true if the level is currently experiencing rain, snow, and cloud cover.
+    #[serde(rename = "raining")]
+    pub raining: bool,
+    // The number of ticks before "raining" is toggled and this value gets set to another random value.
+    #[serde(rename = "rainTime")]
+    pub rain_time: i32,
+    // true if the rain/snow/cloud cover is a lightning storm and dark enough for mobs to spawn under the sky.
+    #[serde(rename = "thundering")]
+    pub thundering: bool,
+    // The number of ticks before "thundering" is toggled and this value gets set to another random value.
+    #[serde(rename = "thunderTime")]
+    pub thunder_time: i32,
     // The X coordinate of the world spawn.
     pub spawn_x: i32,
     // The Y coordinate of the world spawn.
@@ -87,8 +125,64 @@ pub struct LevelData {
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct WorldGenSettings {
+    // true if a bonus chest should be generated at spawn.
+    #[serde(default)]
+    pub bonus_chest: bool,
     // the numerical seed of the world
     pub seed: i64,
+    // true if structures should be generated.
+    #[serde(default = "default_true")]
+    pub generate_features: bool,
+    // The generator of each dimension, vanilla refuses to load worlds without them.
+    #[serde(default = "default_dimensions")]
+    pub dimensions: NbtCompound,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+/// The generators of the vanilla dimensions, in the order vanilla writes them
+fn default_dimensions() -> NbtCompound {
+    fn noise_dimension(
+        dimension_type: &str,
+        settings: &str,
+        biome_source: NbtCompound,
+    ) -> NbtCompound {
+        let mut generator = NbtCompound::new();
+        generator.put_string("settings", format!("minecraft:{settings}"));
+        generator.put_component("biome_source", biome_source);
+        generator.put_string("type", "minecraft:noise".to_string());
+
+        let mut dimension = NbtCompound::new();
+        dimension.put_component("generator", generator);
+        dimension.put_string("type", format!("minecraft:{dimension_type}"));
+        dimension
+    }
+    fn multi_noise(preset: &str) -> NbtCompound {
+        let mut biome_source = NbtCompound::new();
+        biome_source.put_string("preset", format!("minecraft:{preset}"));
+        biome_source.put_string("type", "minecraft:multi_noise".to_string());
+        biome_source
+    }
+
+    let mut the_end = NbtCompound::new();
+    the_end.put_string("type", "minecraft:the_end".to_string());
+
+    let mut dimensions = NbtCompound::new();
+    dimensions.put_component(
+        "minecraft:overworld",
+        noise_dimension("overworld", "overworld", multi_noise("overworld")),
+    );
+    dimensions.put_component(
+        "minecraft:the_nether",
+        noise_dimension("the_nether", "nether", multi_noise("nether")),
+    );
+    dimensions.put_component(
+        "minecraft:the_end",
+        noise_dimension("the_end", "end", the_end),
+    );
+    dimensions
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -108,7 +202,10 @@ fn get_or_create_seed() -> Seed {
 impl Default for WorldGenSettings {
     fn default() -> Self {
         Self {
+            bonus_chest: false,
             seed: get_or_create_seed().0 as i64,
+            generate_features: true,
+            dimensions: default_dimensions(),
         }
     }
 }
@@ -129,8 +226,8 @@ pub struct WorldVersion {
 impl Default for WorldVersion {
     fn default() -> Self {
         Self {
-            name: "1.24.4".to_string(),
-            id: -1,
+            name: "1.21.4".to_string(),
+            id: MAXIMUM_SUPPORTED_WORLD_DATA_VERSION,
             snapshot: false,
             series: "main".to_string(),
         }
@@ -156,17 +253,28 @@ impl Default for LevelData {
                 enabled: vec!["vanilla".to_string()],
             },
             data_version: MAXIMUM_SUPPORTED_WORLD_DATA_VERSION,
+            time: 0,
             day_time: 0,
-            difficulty: Difficulty::Normal as i8,
+            difficulty: BASIC_CONFIG.default_difficulty.clone() as i8,
             difficulty_locked: false,
+            game_type: BASIC_CONFIG.default_gamemode as i32,
+            hardcore: BASIC_CONFIG.hardcore,
+            initialized: true,
+            game_rules: GameRules::default(),
+            server_brands: Vec::new(),
+            was_modded: false,
             world_gen_settings: Default::default(),
             last_played: -1,
             level_name: "world".to_string(),
+            raining: false,
+            rain_time: 0,
+            thundering: false,
+            thunder_time: 0,
             spawn_x: 0,
             spawn_y: 200,
             spawn_z: 0,
             spawn_angle: 0.0,
-            nbt_version: -1,
+            nbt_version: NBT_VERSION,
             version: Default::default(),
         }
     }
@@ -180,6 +288,8 @@ pub enum WorldInfoError {
     InfoNotFound,
     #[error("Deserialization error: {0}")]
     DeserializationError(String),
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
     #[error("Unsupported world data version: {0}")]
     UnsupportedVersion(i32),
 }
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block::{Block, BlockProperties, BlockState, CampfireLikeProperties};
+use pumpkin_data::item::Item;
+use pumpkin_inventory::smelting::find_cooking_recipe;
+use pumpkin_macros::block_entity;
+use pumpkin_protocol::client::play::CBlockEntityData;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_registry::RecipeType;
+use pumpkin_util::GameMode;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
+use serde::Serialize;
+
+use crate::block::drop_stack;
+use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
+use crate::block::registry::{BlockActionResult, BlockRegistry};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+
+pub fn register_campfire_blocks(manager: &mut BlockRegistry) {
+    for id in ["campfire", "soul_campfire"] {
+        manager.register(CampfireBlock { id });
+    }
+}
+
+pub struct CampfireBlock {
+    id: &'static str,
+}
+
+impl BlockMetadata for CampfireBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn id(&self) -> &'static str {
+        self.id
+    }
+}
+
+/// An item lying on a campfire
+struct CookingItem {
+    stack: ItemStack,
+    result: ItemStack,
+    cook_time: u16,
+    cook_time_total: u16,
+}
+
+/// The items cooking on a campfire, one on each side
+#[derive(Default)]
+pub struct CampfireItems {
+    slots: [Option<CookingItem>; 4],
+}
+
+impl CampfireItems {
+    fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    /// Puts a single item of the stack on the campfire, returns whether there was room
+    fn place(&mut self, stack: &ItemStack, result: ItemStack, cook_time_total: u16) -> bool {
+        let Some(slot) = self.slots.iter_mut().find(|slot| slot.is_none()) else {
+            return false;
+        };
+        *slot = Some(CookingItem {
+            stack: ItemStack::new(1, stack.item.clone()),
+            result,
+            cook_time: 0,
+            cook_time_total,
+        });
+        true
+    }
+
+    /// Advances the cooking and returns the finished results
+    fn tick(&mut self, lit: bool) -> Vec<ItemStack> {
+        let mut finished = Vec::new();
+        for slot in &mut self.slots {
+            let Some(cooking) = slot else {
+                continue;
+            };
+            if !lit {
+                // Items cool off slowly while the campfire is out
+                cooking.cook_time = cooking.cook_time.saturating_sub(2);
+                continue;
+            }
+            cooking.cook_time += 1;
+            if cooking.cook_time < cooking.cook_time_total {
+                continue;
+            }
+            if let Some(cooking) = slot.take() {
+                finished.push(cooking.result);
+            }
+        }
+        finished
+    }
+
+    /// The block entity data the client renders the items from
+    fn to_nbt(&self) -> Box<[u8]> {
+        #[derive(Serialize)]
+        struct Data {
+            #[serde(rename = "Items")]
+            items: Vec<SlotData>,
+        }
+
+        #[derive(Serialize)]
+        struct SlotData {
+            #[serde(rename = "Slot")]
+            slot: i8,
+            id: String,
+            count: i32,
+        }
+
+        let data = Data {
+            items: self
+                .slots
+                .iter()
+                .enumerate()
+                .filter_map(|(slot, cooking)| {
+                    let cooking = cooking.as_ref()?;
+                    Some(SlotData {
+                        slot: slot as i8,
+                        id: format!("minecraft:{}", cooking.stack.item.registry_key),
+                        count: i32::from(cooking.stack.item_count),
+                    })
+                })
+                .collect(),
+        };
+        let mut buf = Vec::new();
+        pumpkin_nbt::serializer::to_bytes_unnamed(&data, &mut buf).unwrap();
+        buf.into_boxed_slice()
+    }
+}
+
+async fn send_items(world: &World, location: BlockPos, items: &CampfireItems) {
+    world
+        .broadcast_packet_all(&CBlockEntityData::new(
+            location,
+            VarInt(block_entity!("campfire") as i32),
+            items.to_nbt(),
+        ))
+        .await;
+}
+
+/// Cooks the items on all campfires of the world, finished items pop off
+pub(crate) async fn tick_campfires(world: &Arc<World>) {
+    let mut campfires = world.campfire_items.lock().await;
+    let mut removed = Vec::new();
+    for (location, items) in campfires.iter_mut() {
+        let lit = match world.get_block_and_block_state(location).await {
+            Ok((block, state)) if block.name.ends_with("campfire") => {
+                CampfireLikeProperties::from_state_id(state.id, &block)
+                    .lit
+                    .to_bool()
+            }
+            // The campfire was replaced without being broken, like by an explosion
+            _ => {
+                removed.push(*location);
+                continue;
+            }
+        };
+        let finished = items.tick(lit);
+        if finished.is_empty() {
+            continue;
+        }
+        for stack in finished {
+            drop_stack(world, location, stack).await;
+        }
+        send_items(world, *location, items).await;
+        if items.is_empty() {
+            removed.push(*location);
+        }
+    }
+    for location in removed {
+        campfires.remove(&location);
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for CampfireBlock {
+    async fn use_with_item(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        item: &Item,
+        server: &Server,
+        world: &World,
+    ) -> BlockActionResult {
+        let stack = ItemStack::new(1, item.clone());
+        let Some((recipe, result)) = find_cooking_recipe(RecipeType::CampfireCooking, &stack)
+        else {
+            return BlockActionResult::Continue;
+        };
+
+        let mut campfires = world.campfire_items.lock().await;
+        let items = campfires.entry(location).or_default();
+        if !items.place(&stack, result, recipe.cooking_time) {
+            return BlockActionResult::Continue;
+        }
+        send_items(world, location, items).await;
+        drop(campfires);
+
+        if player.gamemode.load() != GameMode::Creative {
+            let mut inventory = player.inventory().lock().await;
+            inventory.decrease_current_stack(1);
+            let slot_id = inventory.get_selected_slot();
+            let held_item = inventory.held_item().cloned();
+            let _ = player
+                .handle_decrease_item(
+                    server,
+                    slot_id as i16,
+                    held_item.as_ref(),
+                    &mut inventory.state_id,
+                )
+                .await;
+        }
+        BlockActionResult::Consume
+    }
+
+    async fn broken(
+        &self,
+        _block: &Block,
+        _player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        world: Arc<World>,
+        _state: BlockState,
+    ) {
+        let Some(items) = world.campfire_items.lock().await.remove(&location) else {
+            return;
+        };
+        for cooking in items.slots.into_iter().flatten() {
+            drop_stack(&world, &location, cooking.stack).await;
+        }
+    }
+}
@@ -60,23 +60,25 @@ impl PumpkinBlock for ChestBlock {
     async fn broken(
         &self,
         block: &Block,
-        player: &Player,
+        _player: &Player,
         location: BlockPos,
         server: &Server,
-        _world: Arc<World>,
+        world: Arc<World>,
         _state: BlockState,
     ) {
-        super::standard_on_broken_with_container(block, player, location, server).await;
+        super::standard_on_broken_with_container(block, location, server, &world).await;
     }
     async fn close(
         &self,
-        _block: &Block,
+        block: &Block,
         player: &Player,
         location: BlockPos,
         server: &Server,
         container: &mut OpenContainer,
     ) {
         container.remove_player(player.entity_id());
+        let world = player.world().await;
+        super::save_container(block, location, container, &world).await;
 
         self.play_chest_action(container, player, location, server, ChestState::IsClosed)
             .await;
@@ -46,13 +46,13 @@ impl PumpkinBlock for CraftingTableBlock {
     async fn broken(
         &self,
         block: &Block,
-        player: &Player,
+        _player: &Player,
         location: BlockPos,
         server: &Server,
-        _world: Arc<World>,
+        world: Arc<World>,
         _state: BlockState,
     ) {
-        super::standard_on_broken_with_container(block, player, location, server).await;
+        super::standard_on_broken_with_container(block, location, server, &world).await;
     }
     async fn close(
         &self,
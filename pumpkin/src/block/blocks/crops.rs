@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block::Block;
+use pumpkin_data::item::Item;
+use pumpkin_data::world::WorldEvent;
+use pumpkin_protocol::client::play::CLevelEvent;
+use pumpkin_util::GameMode;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use rand::Rng;
+
+use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
+use crate::block::registry::{BlockActionResult, BlockRegistry};
+use crate::block::{get_property, with_property};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+
+pub fn register_crop_blocks(manager: &mut BlockRegistry) {
+    for (id, max_age) in [
+        ("wheat", 7),
+        ("carrots", 7),
+        ("potatoes", 7),
+        ("beetroots", 3),
+    ] {
+        manager.register(CropBlock { id, max_age });
+    }
+}
+
+/// A crop growing on farmland through its `age` stages, fully grown at `max_age`
+pub struct CropBlock {
+    id: &'static str,
+    max_age: u8,
+}
+
+impl BlockMetadata for CropBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn id(&self) -> &'static str {
+        self.id
+    }
+}
+
+impl CropBlock {
+    fn is_beetroot(&self) -> bool {
+        self.id == "beetroots"
+    }
+
+    async fn get_age(block: &Block, world: &World, location: BlockPos) -> Option<(u16, u8)> {
+        let state_id = world.get_block_state_id(&location).await.ok()?;
+        let age = get_property(block, state_id, "age")?.parse().ok()?;
+        Some((state_id, age))
+    }
+
+    async fn set_age(block: &Block, world: &World, location: BlockPos, state_id: u16, age: u8) {
+        if let Some(new_state) = with_property(block, state_id, "age", &age.to_string()) {
+            world.set_block_state(&location, new_state).await;
+        }
+    }
+
+    /// How well the crop grows, better on moist farmland and worse when crowded by the same crop
+    async fn growth_speed(block: &Block, world: &World, location: BlockPos) -> f32 {
+        let mut speed = 1.0;
+        for x in -1..=1 {
+            for z in -1..=1 {
+                let below = location.offset(Vector3::new(x, -1, z));
+                let Ok((below_block, state)) = world.get_block_and_block_state(&below).await else {
+                    continue;
+                };
+                if below_block.name != "farmland" {
+                    continue;
+                }
+                let moist = get_property(&below_block, state.id, "moisture")
+                    .is_some_and(|moisture| moisture != "0");
+                let mut farmland_speed = if moist { 3.0 } else { 1.0 };
+                if x != 0 || z != 0 {
+                    farmland_speed /= 4.0;
+                }
+                speed += farmland_speed;
+            }
+        }
+
+        let mut same_crop = [false; 8];
+        let neighbours = [
+            (0, -1),
+            (0, 1),
+            (-1, 0),
+            (1, 0),
+            (-1, -1),
+            (1, -1),
+            (1, 1),
+            (-1, 1),
+        ];
+        for (same, (x, z)) in same_crop.iter_mut().zip(neighbours) {
+            *same = world
+                .get_block(&location.offset(Vector3::new(x, 0, z)))
+                .await
+                .is_ok_and(|neighbour| neighbour.id == block.id);
+        }
+        let [north, south, west, east, ..] = same_crop;
+        let diagonal = same_crop[4..].iter().any(|same| *same);
+        if diagonal || ((west || east) && (north || south)) {
+            speed /= 2.0;
+        }
+        speed
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for CropBlock {
+    fn ticks_randomly(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(&self, block: &Block, world: &Arc<World>, location: BlockPos) {
+        if self.is_beetroot() && rand::thread_rng().gen_range(0..3) == 0 {
+            return;
+        }
+        if world.get_light(&location).await < 9 {
+            return;
+        }
+        let Some((state_id, age)) = Self::get_age(block, world, location).await else {
+            return;
+        };
+        if age >= self.max_age {
+            return;
+        }
+        let speed = Self::growth_speed(block, world, location).await;
+        if rand::thread_rng().gen_range(0..=(25.0 / speed) as u32) == 0 {
+            Self::set_age(block, world, location, state_id, age + 1).await;
+        }
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        item: &Item,
+        server: &Server,
+        world: &World,
+    ) -> BlockActionResult {
+        if item.id != Item::BONE_MEAL.id {
+            return BlockActionResult::Continue;
+        }
+        let Some((state_id, age)) = Self::get_age(block, world, location).await else {
+            return BlockActionResult::Continue;
+        };
+        if age >= self.max_age {
+            return BlockActionResult::Continue;
+        }
+
+        let mut growth = rand::thread_rng().gen_range(2..=5);
+        if self.is_beetroot() {
+            growth /= 3;
+        }
+        let age = (age + growth).min(self.max_age);
+        Self::set_age(block, world, location, state_id, age).await;
+        world
+            .broadcast_packet_all(&CLevelEvent::new(
+                WorldEvent::BoneMealUsed as i32,
+                location,
+                15,
+                false,
+            ))
+            .await;
+
+        if player.gamemode.load() != GameMode::Creative {
+            let mut inventory = player.inventory().lock().await;
+            inventory.decrease_current_stack(1);
+            let slot_id = inventory.get_selected_slot();
+            let held_item = inventory.held_item().cloned();
+            let _ = player
+                .handle_decrease_item(
+                    server,
+                    slot_id as i16,
+                    held_item.as_ref(),
+                    &mut inventory.state_id,
+                )
+                .await;
+        }
+        BlockActionResult::Consume
+    }
+}
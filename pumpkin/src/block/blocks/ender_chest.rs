@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block::{Block, BlockState};
+use pumpkin_data::item::Item;
+use pumpkin_data::screen::WindowType;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_inventory::OpenContainer;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::{client::play::CBlockAction, codec::var_int::VarInt};
+use pumpkin_util::math::position::BlockPos;
+
+use crate::block::{pumpkin_block::PumpkinBlock, registry::BlockActionResult};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+
+/// Every ender chest opens the same items of the player looking inside, so the container lives
+/// in the player instead of at the block
+#[pumpkin_block("minecraft:ender_chest")]
+pub struct EnderChestBlock;
+
+#[async_trait]
+impl PumpkinBlock for EnderChestBlock {
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        world: &World,
+    ) {
+        self.open_ender_chest(block, player, location, server, world)
+            .await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+        world: &World,
+    ) -> BlockActionResult {
+        self.open_ender_chest(block, player, location, server, world)
+            .await;
+        BlockActionResult::Consume
+    }
+
+    async fn broken(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        world: Arc<World>,
+        _state: BlockState,
+    ) {
+        // Unlike other containers the items are kept, only the players looking inside are
+        // thrown out
+        if let Some(all_container_ids) = server.get_all_container_ids(location, block.clone()).await
+        {
+            let mut open_containers = server.open_containers.write().await;
+            for container_id in all_container_ids {
+                if let Some(container) = open_containers.get_mut(&u64::from(container_id)) {
+                    super::close_all_in_container(player, container).await;
+                    container.clear_all_players();
+                    container.set_location(None).await;
+                }
+            }
+        }
+        world.ender_chest_viewers.lock().await.remove(&location);
+    }
+
+    async fn close(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        container.remove_player(player.entity_id());
+        let world = player.world().await;
+        let viewers = {
+            let mut viewers = world.ender_chest_viewers.lock().await;
+            let Some(count) = viewers.get_mut(&location) else {
+                return;
+            };
+            *count = count.saturating_sub(1);
+            let count = *count;
+            if count == 0 {
+                viewers.remove(&location);
+            }
+            count
+        };
+        if viewers == 0 {
+            world
+                .play_block_sound(Sound::BlockEnderChestClose, SoundCategory::Blocks, location)
+                .await;
+        }
+        Self::send_lid_action(block, viewers, location, server).await;
+    }
+}
+
+impl EnderChestBlock {
+    pub async fn open_ender_chest(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        world: &World,
+    ) {
+        let entity_id = player.entity_id();
+        {
+            let mut open_containers = server.open_containers.write().await;
+            // Every player keeps reusing the container of their own ender chest
+            let existing = open_containers.iter().find_map(|(id, container)| {
+                container.is_container(&player.ender_chest).then_some(*id)
+            });
+            if let Some(container_id) = existing {
+                if let Some(container) = open_containers.get_mut(&container_id) {
+                    container.set_location(Some(location)).await;
+                    container.add_player(entity_id);
+                }
+                player.open_container.store(Some(container_id));
+            } else {
+                let new_id = server.new_container_id();
+                open_containers.insert(
+                    new_id.into(),
+                    OpenContainer::with_shared_container(
+                        entity_id,
+                        player.ender_chest.clone(),
+                        Some(location),
+                        Some(block.clone()),
+                    ),
+                );
+                player.open_container.store(Some(new_id.into()));
+            }
+        }
+        player.open_container(server, WindowType::Generic9x3).await;
+
+        let viewers = {
+            let mut viewers = world.ender_chest_viewers.lock().await;
+            let count = viewers.entry(location).or_insert(0);
+            *count = count.saturating_add(1);
+            *count
+        };
+        if viewers == 1 {
+            world
+                .play_block_sound(Sound::BlockEnderChestOpen, SoundCategory::Blocks, location)
+                .await;
+        }
+        Self::send_lid_action(block, viewers, location, server).await;
+    }
+
+    /// Opens or closes the lid depending on how many players are looking inside
+    async fn send_lid_action(block: &Block, viewers: u8, location: BlockPos, server: &Server) {
+        server
+            .broadcast_packet_all(&CBlockAction::new(
+                &location,
+                1,
+                viewers,
+                VarInt(block.id.into()),
+            ))
+            .await;
+    }
+}
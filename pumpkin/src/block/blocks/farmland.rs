@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block::Block;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::tag::Tagable;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::world_info::game_rules;
+use rand::Rng;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::block::{get_property, with_property};
+use crate::entity::living::LivingEntity;
+use crate::world::World;
+
+const MAX_MOISTURE: u8 = 7;
+
+#[pumpkin_block("minecraft:farmland")]
+pub struct FarmlandBlock;
+
+/// Whether there is water up to 4 blocks away horizontally, on the same level or one above
+async fn is_water_nearby(world: &World, location: BlockPos) -> bool {
+    for x in -4..=4 {
+        for y in 0..=1 {
+            for z in -4..=4 {
+                let position = location.offset(Vector3::new(x, y, z));
+                if world
+                    .get_block(&position)
+                    .await
+                    .is_ok_and(|block| block.name == "water")
+                {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Whether the block above keeps the farmland from drying out into dirt, like crops
+async fn has_crop(world: &World, location: BlockPos) -> bool {
+    world
+        .get_block(&location.offset(Vector3::new(0, 1, 0)))
+        .await
+        .is_ok_and(|block| block.is_tagged_with("minecraft:maintains_farmland") == Some(true))
+}
+
+/// Turns the farmland back into dirt, the crop on it can't stay
+async fn set_to_dirt(world: &Arc<World>, location: BlockPos) {
+    let above = location.offset(Vector3::new(0, 1, 0));
+    if has_crop(world, location).await {
+        world.break_block(&above, None, true, None).await;
+    }
+    world
+        .set_block_state(&location, Block::DIRT.default_state_id)
+        .await;
+}
+
+#[async_trait]
+impl PumpkinBlock for FarmlandBlock {
+    fn ticks_randomly(&self) -> bool {
+        true
+    }
+
+    async fn random_tick(&self, block: &Block, world: &Arc<World>, location: BlockPos) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let moisture = get_property(block, state_id, "moisture")
+            .and_then(|moisture| moisture.parse::<u8>().ok())
+            .unwrap_or_default();
+
+        // TODO: Rain keeps the farmland moist too
+        let moisture = if is_water_nearby(world, location).await {
+            MAX_MOISTURE
+        } else if moisture > 0 {
+            moisture - 1
+        } else {
+            if !has_crop(world, location).await {
+                set_to_dirt(world, location).await;
+            }
+            return;
+        };
+        if let Some(new_state) = with_property(block, state_id, "moisture", &moisture.to_string()) {
+            if new_state != state_id {
+                world.set_block_state(&location, new_state).await;
+            }
+        }
+    }
+
+    async fn on_landed_upon(
+        &self,
+        _block: &Block,
+        world: &Arc<World>,
+        location: BlockPos,
+        entity: &LivingEntity,
+        fall_distance: f32,
+    ) {
+        if entity.entity.entity_type != EntityType::PLAYER
+            && !world.game_rule(game_rules::MOB_GRIEFING)
+        {
+            return;
+        }
+        let size = entity.entity.bounding_box_size.load();
+        if rand::thread_rng().r#gen::<f32>() < fall_distance - 0.5
+            && size.width * size.width * size.height > 0.512
+        {
+            set_to_dirt(world, location).await;
+        }
+    }
+}
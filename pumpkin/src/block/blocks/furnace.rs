@@ -1,19 +1,38 @@
 use std::sync::Arc;
 
+use crate::block::pumpkin_block::BlockMetadata;
+use crate::block::registry::BlockRegistry;
 use crate::entity::player::Player;
 use crate::{block::registry::BlockActionResult, world::World};
 use async_trait::async_trait;
 use pumpkin_data::block::{Block, BlockState};
 use pumpkin_data::item::Item;
 use pumpkin_data::screen::WindowType;
-use pumpkin_inventory::Furnace;
-use pumpkin_macros::pumpkin_block;
+use pumpkin_inventory::{BlastFurnace, Furnace, OpenContainer, Smoker};
 use pumpkin_util::math::position::BlockPos;
 
 use crate::{block::pumpkin_block::PumpkinBlock, server::Server};
 
-#[pumpkin_block("minecraft:furnace")]
-pub struct FurnaceBlock;
+/// The furnace, smoker and blast furnace only differ in their container
+pub fn register_furnace_blocks(manager: &mut BlockRegistry) {
+    for id in ["furnace", "smoker", "blast_furnace"] {
+        manager.register(FurnaceBlock { id });
+    }
+}
+
+pub struct FurnaceBlock {
+    id: &'static str,
+}
+
+impl BlockMetadata for FurnaceBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn id(&self) -> &'static str {
+        self.id
+    }
+}
 
 #[async_trait]
 impl PumpkinBlock for FurnaceBlock {
@@ -46,13 +65,25 @@ impl PumpkinBlock for FurnaceBlock {
     async fn broken(
         &self,
         block: &Block,
-        player: &Player,
+        _player: &Player,
         location: BlockPos,
         server: &Server,
-        _world: Arc<World>,
+        world: Arc<World>,
         _state: BlockState,
     ) {
-        super::standard_on_broken_with_container(block, player, location, server).await;
+        super::standard_on_broken_with_container(block, location, server, &world).await;
+    }
+
+    async fn close(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        let world = player.world().await;
+        super::save_container(block, location, container, &world).await;
     }
 }
 
@@ -64,13 +95,37 @@ impl FurnaceBlock {
         location: BlockPos,
         server: &Server,
     ) {
-        super::standard_open_container::<Furnace>(
-            block,
-            player,
-            location,
-            server,
-            WindowType::Furnace,
-        )
-        .await;
+        match self.id {
+            "smoker" => {
+                super::standard_open_container::<Smoker>(
+                    block,
+                    player,
+                    location,
+                    server,
+                    WindowType::Smoker,
+                )
+                .await;
+            }
+            "blast_furnace" => {
+                super::standard_open_container::<BlastFurnace>(
+                    block,
+                    player,
+                    location,
+                    server,
+                    WindowType::BlastFurnace,
+                )
+                .await;
+            }
+            _ => {
+                super::standard_open_container::<Furnace>(
+                    block,
+                    player,
+                    location,
+                    server,
+                    WindowType::Furnace,
+                )
+                .await;
+            }
+        }
     }
 }
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use crate::block::registry::BlockActionResult;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::{block::pumpkin_block::PumpkinBlock, world::World};
+use async_trait::async_trait;
+use pumpkin_data::block::{Block, BlockState};
+use pumpkin_data::item::Item;
+use pumpkin_data::screen::WindowType;
+use pumpkin_inventory::{Hopper, OpenContainer};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+
+#[pumpkin_block("minecraft:hopper")]
+pub struct HopperBlock;
+
+#[async_trait]
+impl PumpkinBlock for HopperBlock {
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        _world: &World,
+    ) {
+        super::standard_open_container::<Hopper>(
+            block,
+            player,
+            location,
+            server,
+            WindowType::Hopper,
+        )
+        .await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+        _world: &World,
+    ) -> BlockActionResult {
+        super::standard_open_container::<Hopper>(
+            block,
+            player,
+            location,
+            server,
+            WindowType::Hopper,
+        )
+        .await;
+        BlockActionResult::Consume
+    }
+
+    async fn broken(
+        &self,
+        block: &Block,
+        _player: &Player,
+        location: BlockPos,
+        server: &Server,
+        world: Arc<World>,
+        _state: BlockState,
+    ) {
+        super::standard_on_broken_with_container(block, location, server, &world).await;
+    }
+
+    async fn close(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        let world = player.world().await;
+        super::save_container(block, location, container, &world).await;
+    }
+}
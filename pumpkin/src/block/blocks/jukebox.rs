@@ -1,46 +1,79 @@
 use std::sync::Arc;
 
+use crate::block::drop_stack;
 use crate::block::pumpkin_block::PumpkinBlock;
 use crate::block::registry::BlockActionResult;
 use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::World;
 use async_trait::async_trait;
-use pumpkin_data::block::{Block, BlockState};
+use pumpkin_data::block::{Block, BlockProperties, BlockState, Boolean, JukeboxLikeProperties};
 use pumpkin_data::item::Item;
 use pumpkin_macros::pumpkin_block;
 use pumpkin_registry::SYNCED_REGISTRIES;
+use pumpkin_util::GameMode;
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::item::ItemStack;
 
 #[pumpkin_block("minecraft:jukebox")]
 pub struct JukeboxBlock;
 
+async fn set_has_record(world: &World, block: &Block, location: BlockPos, has_record: bool) {
+    let Ok(state_id) = world.get_block_state_id(&location).await else {
+        return;
+    };
+    let mut props = JukeboxLikeProperties::from_state_id(state_id, block);
+    props.has_record = Boolean::from_bool(has_record);
+    world
+        .set_block_state(&location, props.to_state_id(block))
+        .await;
+}
+
+/// Takes the disc out of the jukebox and stops the music
+async fn take_record(world: &World, location: BlockPos) -> Option<ItemStack> {
+    let record = world.jukebox_records.lock().await.remove(&location)?;
+    world.stop_record(location).await;
+    Some(record)
+}
+
+/// Ejects the disc on top of the jukebox, returns whether there was one
+async fn eject_record(world: &Arc<World>, block: &Block, location: BlockPos) -> bool {
+    let Some(record) = take_record(world, location).await else {
+        return false;
+    };
+    set_has_record(world, block, location, false).await;
+    drop_stack(world, &location.offset(Vector3::new(0, 1, 0)), record).await;
+    true
+}
+
 #[async_trait]
 impl PumpkinBlock for JukeboxBlock {
     async fn normal_use(
         &self,
-        _block: &Block,
+        block: &Block,
         player: &Player,
         location: BlockPos,
         _server: &Server,
         _world: &World,
     ) {
-        // For now just stop the music at this position
-        let world = &player.living_entity.entity.world.read().await;
-
-        world.stop_record(location).await;
+        eject_record(&player.world().await, block, location).await;
     }
 
     async fn use_with_item(
         &self,
-        _block: &Block,
+        block: &Block,
         player: &Player,
         location: BlockPos,
         item: &Item,
-        _server: &Server,
+        server: &Server,
         _world: &World,
     ) -> BlockActionResult {
-        let world = &player.living_entity.entity.world.read().await;
+        let world = player.world().await;
+        // Using anything on a playing jukebox ejects the disc
+        if eject_record(&world, block, location).await {
+            return BlockActionResult::Consume;
+        }
 
         let Some(jukebox_playable) = &item.components.jukebox_playable else {
             return BlockActionResult::Continue;
@@ -55,10 +88,29 @@ impl PumpkinBlock for JukeboxBlock {
             return BlockActionResult::Continue;
         };
 
-        //TODO: Update block state and block nbt
-
+        world
+            .jukebox_records
+            .lock()
+            .await
+            .insert(location, ItemStack::new(1, item.clone()));
+        set_has_record(&world, block, location, true).await;
         world.play_record(jukebox_song as i32, location).await;
 
+        if player.gamemode.load() != GameMode::Creative {
+            let mut inventory = player.inventory().lock().await;
+            inventory.decrease_current_stack(1);
+            let slot_id = inventory.get_selected_slot();
+            let held_item = inventory.held_item().cloned();
+            let _ = player
+                .handle_decrease_item(
+                    server,
+                    slot_id as i16,
+                    held_item.as_ref(),
+                    &mut inventory.state_id,
+                )
+                .await;
+        }
+
         BlockActionResult::Consume
     }
 
@@ -71,7 +123,8 @@ impl PumpkinBlock for JukeboxBlock {
         world: Arc<World>,
         _state: BlockState,
     ) {
-        // For now just stop the music at this position
-        world.stop_record(location).await;
+        if let Some(record) = take_record(&world, location).await {
+            drop_stack(&world, &location, record).await;
+        }
     }
 }
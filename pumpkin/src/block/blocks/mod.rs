@@ -1,45 +1,173 @@
+use std::sync::Arc;
+
 use pumpkin_data::block::Block;
 use pumpkin_data::screen::WindowType;
+use pumpkin_data::tag::Tagable;
 use pumpkin_inventory::{Container, OpenContainer};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
 
+use crate::block::drop_stack;
+use crate::world::World;
 use crate::{entity::player::Player, server::Server};
 
+pub(crate) mod brewing_stand;
+pub(crate) mod campfire;
 pub(crate) mod chest;
 pub(crate) mod crafting_table;
+pub(crate) mod crops;
 pub(crate) mod doors;
+pub(crate) mod ender_chest;
+pub(crate) mod farmland;
 pub(crate) mod fence_gates;
 pub(crate) mod fences;
 pub(crate) mod furnace;
+pub(crate) mod hopper;
 pub(crate) mod jukebox;
 pub(crate) mod lever;
 pub(crate) mod logs;
+pub(crate) mod nether_portal;
+pub(crate) mod note_block;
+pub(crate) mod sculk_sensor;
+pub(crate) mod shulker_box;
 pub(crate) mod tnt;
+pub(crate) mod turtle_egg;
 
-/// The standard destroy with container removes the player forcibly from the container,
-/// drops items to the floor, and back to the player's inventory if the item stack is in movement.
+/// The standard destroy with container removes the players forcibly from the container and
+/// drops its items to the floor
 pub async fn standard_on_broken_with_container(
     block: &Block,
-    player: &Player,
     location: BlockPos,
     server: &Server,
+    world: &Arc<World>,
 ) {
-    // TODO: drop all items and back to players inventory if in motion
+    // TODO: back to the player's inventory if the item stack is in motion
+    let contents = take_container_contents(block, location, server, world).await;
+    for stack in contents.into_iter().flatten() {
+        drop_stack(world, &location, stack).await;
+    }
+}
+
+/// Takes the items out of a container block which is gone and closes it for everyone looking
+/// inside. They are in the open container if it was opened since the server started, in the
+/// block entity otherwise
+pub async fn take_container_contents(
+    block: &Block,
+    location: BlockPos,
+    server: &Server,
+    world: &World,
+) -> Vec<Option<ItemStack>> {
+    let block_entity = world
+        .remove_block_entity(&location)
+        .await
+        .filter(|nbt| is_container_block_entity(block, nbt));
+    let mut contents = None;
     if let Some(all_container_ids) = server.get_all_container_ids(location, block.clone()).await {
         let mut open_containers = server.open_containers.write().await;
         for individual_id in all_container_ids {
-            if let Some(container) = open_containers.get_mut(&u64::from(individual_id)) {
-                container.clear_all_slots().await;
-                player.open_container.store(None);
-                close_all_in_container(player, container).await;
-                container.clear_all_players();
+            let Some(container) = open_containers.remove(&u64::from(individual_id)) else {
+                continue;
+            };
+            for id in container.all_player_ids() {
+                if let Some(remote_player) = world.get_player_by_id(id).await {
+                    remote_player.open_container.store(None);
+                    remote_player.close_container().await;
+                }
             }
+            let mut container = container.container().lock().await;
+            contents = Some(
+                container
+                    .all_slots()
+                    .into_iter()
+                    .map(Option::take)
+                    .collect(),
+            );
         }
     }
+    contents.unwrap_or_else(|| {
+        block_entity
+            .as_ref()
+            .map(read_container_items)
+            .unwrap_or_default()
+    })
+}
+
+/// Saves the items of a container block into its block entity, so they are still there after a
+/// restart. The rest of the block entity, like a custom name, is kept
+pub async fn save_container(
+    block: &Block,
+    location: BlockPos,
+    container: &OpenContainer,
+    world: &World,
+) {
+    let mut nbt = world
+        .get_block_entity(&location)
+        .await
+        .filter(|nbt| is_container_block_entity(block, nbt))
+        .unwrap_or_default();
+    write_container_items(&**container.container().lock().await, &mut nbt);
+    world
+        .set_block_entity(&container_block_entity_id(block), location, nbt)
+        .await;
+}
+
+/// The type of the block entity keeping the items of a container block
+pub fn container_block_entity_id(block: &Block) -> String {
+    if block
+        .is_tagged_with("minecraft:shulker_boxes")
+        .unwrap_or_default()
+    {
+        "minecraft:shulker_box".to_string()
+    } else {
+        format!("minecraft:{}", block.name)
+    }
+}
+
+/// Block entities left behind by another block, e.g. one replaced by a command, are ignored
+fn is_container_block_entity(block: &Block, nbt: &NbtCompound) -> bool {
+    nbt.get_string("id")
+        .is_some_and(|id| *id == container_block_entity_id(block))
+}
+
+/// Reads the items like vanilla saves them, `{Items: [{Slot: 0b, id: "minecraft:stone", count: 1}]}`
+fn read_container_items(nbt: &NbtCompound) -> Vec<Option<ItemStack>> {
+    let mut contents = Vec::new();
+    let items = nbt.get_list("Items").unwrap_or_default();
+    for item in items.iter().filter_map(NbtTag::extract_compound) {
+        let Some(slot) = item
+            .get_byte("Slot")
+            .and_then(|slot| usize::try_from(slot).ok())
+        else {
+            continue;
+        };
+        if contents.len() <= slot {
+            contents.resize(slot + 1, None);
+        }
+        contents[slot] = ItemStack::read_item_nbt(item);
+    }
+    contents
+}
+
+/// Writes the items like vanilla saves them, empty slots are left out
+pub fn write_container_items(container: &dyn Container, nbt: &mut NbtCompound) {
+    let items = container
+        .all_slots_ref()
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, stack)| {
+            let mut item = NbtCompound::new();
+            item.put_byte("Slot", slot as i8);
+            stack.as_ref()?.write_item_nbt(&mut item);
+            Some(NbtTag::Compound(item))
+        })
+        .collect();
+    nbt.child_tags.retain(|(name, _)| name != "Items");
+    nbt.put_list("Items", items);
 }
 
 /// The standard open container creates a new container if a container of the same block
-/// type does not exist at the selected block location. If a container of the same type exists, the player
+/// type does not exist at the selected block location, with the items of the block entity. If a container of the same type exists, the player
 /// is added to the currently connected players to that container.
 pub async fn standard_open_container<C: Container + Default + 'static>(
     block: &Block,
@@ -58,11 +186,22 @@ pub async fn standard_open_container<C: Container + Default + 'static>(
             player.open_container.store(Some(container_id.into()));
         }
     } else {
+        // The items are kept in the block entity while nobody opened the container yet
+        let mut container = C::default();
+        let block_entity = player.world().await.get_block_entity(&location).await;
+        if let Some(nbt) = block_entity.filter(|nbt| is_container_block_entity(block, nbt)) {
+            let contents = read_container_items(&nbt);
+            for (slot, stack) in container.all_slots().into_iter().zip(contents) {
+                *slot = stack;
+            }
+        }
+
         let mut open_containers = server.open_containers.write().await;
         let new_id = server.new_container_id();
         log::debug!("Creating new standard container ID: {}", new_id);
-        let open_container =
-            OpenContainer::new_empty_container::<C>(entity_id, Some(location), Some(block.clone()));
+        let mut open_container =
+            OpenContainer::with_container(container, Some(location), Some(block.clone()));
+        open_container.add_player(entity_id);
         open_containers.insert(new_id.into(), open_container);
         player.open_container.store(Some(new_id.into()));
     }
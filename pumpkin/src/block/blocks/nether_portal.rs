@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use pumpkin_data::block::Block;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::BlockDirection;
+
+use crate::{
+    block::pumpkin_block::PumpkinBlock,
+    server::Server,
+    world::{World, nether_portal},
+};
+
+#[pumpkin_block("minecraft:nether_portal")]
+pub struct NetherPortalBlock;
+
+#[async_trait]
+impl PumpkinBlock for NetherPortalBlock {
+    /// The portal goes out once its frame is broken, one block after another
+    async fn on_neighbor_update(
+        &self,
+        server: &Server,
+        world: &World,
+        _block: &Block,
+        block_pos: &BlockPos,
+        _source_face: &BlockDirection,
+        _source_block_pos: &BlockPos,
+    ) {
+        if !nether_portal::is_supported(world, *block_pos).await {
+            world
+                .set_block_state(block_pos, Block::AIR.default_state_id)
+                .await;
+            world.update_neighbors(server, block_pos, None).await;
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use async_trait::async_trait;
+use pumpkin_data::block::{
+    Block, BlockProperties, EnumVariants, HorizontalFacing, Instrument, Integer0To24,
+    NoteBlockLikeProperties,
+};
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::tag::Tagable;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::{
+    client::play::CBlockAction, codec::var_int::VarInt, server::play::SUseItemOn,
+};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::{BlockDirection, registry::get_block_by_item};
+
+use crate::{
+    block::{pumpkin_block::PumpkinBlock, registry::BlockActionResult},
+    entity::player::Player,
+    server::Server,
+    world::World,
+};
+
+#[pumpkin_block("minecraft:note_block")]
+pub struct NoteBlock;
+
+/// The instrument of a note block, a mob head on top wins over the block below
+fn instrument_for(below: &Block, above: &Block) -> Instrument {
+    match above.name {
+        "zombie_head" => return Instrument::Zombie,
+        "skeleton_skull" => return Instrument::Skeleton,
+        "creeper_head" => return Instrument::Creeper,
+        "dragon_head" => return Instrument::Dragon,
+        "wither_skeleton_skull" => return Instrument::WitherSkeleton,
+        "piglin_head" => return Instrument::Piglin,
+        "player_head" => return Instrument::CustomHead,
+        _ => {}
+    }
+    match below.name {
+        "clay" => Instrument::Flute,
+        "gold_block" => Instrument::Bell,
+        "packed_ice" => Instrument::Chime,
+        "bone_block" => Instrument::Xylophone,
+        "iron_block" => Instrument::IronXylophone,
+        "soul_sand" => Instrument::CowBell,
+        "pumpkin" => Instrument::Didgeridoo,
+        "emerald_block" => Instrument::Bit,
+        "hay_block" => Instrument::Banjo,
+        "glowstone" => Instrument::Pling,
+        "sand" | "red_sand" | "suspicious_sand" | "gravel" | "suspicious_gravel" => {
+            Instrument::Snare
+        }
+        "sea_lantern" | "beacon" => Instrument::Hat,
+        name if name.ends_with("concrete_powder") => Instrument::Snare,
+        name if name.ends_with("glass") || name.ends_with("glass_pane") => Instrument::Hat,
+        _ if below.is_tagged_with("minecraft:wool").unwrap_or_default() => Instrument::Guitar,
+        _ if below
+            .is_tagged_with("minecraft:mineable/axe")
+            .unwrap_or_default() =>
+        {
+            Instrument::Bass
+        }
+        _ if below
+            .is_tagged_with("minecraft:mineable/pickaxe")
+            .unwrap_or_default() =>
+        {
+            Instrument::Basedrum
+        }
+        _ => Instrument::Harp,
+    }
+}
+
+/// The sound of an instrument, `None` for player heads which play the sound stored in the head
+const fn instrument_sound(instrument: Instrument) -> Option<Sound> {
+    Some(match instrument {
+        Instrument::Harp => Sound::BlockNoteBlockHarp,
+        Instrument::Basedrum => Sound::BlockNoteBlockBasedrum,
+        Instrument::Snare => Sound::BlockNoteBlockSnare,
+        Instrument::Hat => Sound::BlockNoteBlockHat,
+        Instrument::Bass => Sound::BlockNoteBlockBass,
+        Instrument::Flute => Sound::BlockNoteBlockFlute,
+        Instrument::Bell => Sound::BlockNoteBlockBell,
+        Instrument::Guitar => Sound::BlockNoteBlockGuitar,
+        Instrument::Chime => Sound::BlockNoteBlockChime,
+        Instrument::Xylophone => Sound::BlockNoteBlockXylophone,
+        Instrument::IronXylophone => Sound::BlockNoteBlockIronXylophone,
+        Instrument::CowBell => Sound::BlockNoteBlockCowBell,
+        Instrument::Didgeridoo => Sound::BlockNoteBlockDidgeridoo,
+        Instrument::Bit => Sound::BlockNoteBlockBit,
+        Instrument::Banjo => Sound::BlockNoteBlockBanjo,
+        Instrument::Pling => Sound::BlockNoteBlockPling,
+        Instrument::Zombie => Sound::BlockNoteBlockImitateZombie,
+        Instrument::Skeleton => Sound::BlockNoteBlockImitateSkeleton,
+        Instrument::Creeper => Sound::BlockNoteBlockImitateCreeper,
+        Instrument::Dragon => Sound::BlockNoteBlockImitateEnderDragon,
+        Instrument::WitherSkeleton => Sound::BlockNoteBlockImitateWitherSkeleton,
+        Instrument::Piglin => Sound::BlockNoteBlockImitatePiglin,
+        Instrument::CustomHead => return None,
+    })
+}
+
+/// Mob heads imitate the mob instead of playing a note
+const fn is_mob_head(instrument: Instrument) -> bool {
+    matches!(
+        instrument,
+        Instrument::Zombie
+            | Instrument::Skeleton
+            | Instrument::Creeper
+            | Instrument::Dragon
+            | Instrument::WitherSkeleton
+            | Instrument::Piglin
+            | Instrument::CustomHead
+    )
+}
+
+/// The pitch of a note, 0 is F#3 and every step is a semitone up to F#5 at 24
+fn note_pitch(note: u16) -> f32 {
+    2f32.powf((f32::from(note) - 12.0) / 12.0)
+}
+
+async fn instrument_at(world: &World, location: &BlockPos) -> Instrument {
+    let below = world
+        .get_block(&location.offset(Vector3::new(0, -1, 0)))
+        .await;
+    let above = world
+        .get_block(&location.offset(Vector3::new(0, 1, 0)))
+        .await;
+    match (below, above) {
+        (Ok(below), Ok(above)) => instrument_for(&below, &above),
+        _ => Instrument::Harp,
+    }
+}
+
+async fn play_note(world: &World, block: &Block, location: BlockPos) {
+    let Ok(state_id) = world.get_block_state_id(&location).await else {
+        return;
+    };
+    let props = NoteBlockLikeProperties::from_state_id(state_id, block);
+    let mob_head = is_mob_head(props.instrument);
+    // Notes need room above to be heard, heads are placed right on top
+    if !mob_head {
+        let above = world
+            .get_block_state(&location.offset(Vector3::new(0, 1, 0)))
+            .await;
+        if !above.is_ok_and(|state| state.air) {
+            return;
+        }
+    }
+
+    let Some(sound) = instrument_sound(props.instrument) else {
+        return;
+    };
+    let pitch = if mob_head {
+        1.0
+    } else {
+        note_pitch(props.note.to_index())
+    };
+    let position = Vector3::new(
+        f64::from(location.0.x) + 0.5,
+        f64::from(location.0.y) + 0.5,
+        f64::from(location.0.z) + 0.5,
+    );
+    world
+        .play_sound_raw(sound as u16, SoundCategory::Records, &position, 3.0, pitch)
+        .await;
+    // The client shows the note particle
+    world
+        .broadcast_packet_all(&CBlockAction::new(
+            &location,
+            0,
+            0,
+            VarInt(i32::from(block.id)),
+        ))
+        .await;
+}
+
+/// Raises the note by a semitone, going back to the lowest one after the highest
+async fn tune(world: &World, block: &Block, location: BlockPos) {
+    let Ok(state_id) = world.get_block_state_id(&location).await else {
+        return;
+    };
+    let mut props = NoteBlockLikeProperties::from_state_id(state_id, block);
+    props.note =
+        Integer0To24::from_index((props.note.to_index() + 1) % Integer0To24::variant_count());
+    world
+        .set_block_state(&location, props.to_state_id(block))
+        .await;
+    play_note(world, block, location).await;
+}
+
+#[async_trait]
+impl PumpkinBlock for NoteBlock {
+    async fn on_place(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        _face: &BlockDirection,
+        block_pos: &BlockPos,
+        _use_item_on: &SUseItemOn,
+        _player_direction: &HorizontalFacing,
+        _other: bool,
+    ) -> u16 {
+        let mut props = NoteBlockLikeProperties::default(block);
+        props.instrument = instrument_at(world, block_pos).await;
+        props.to_state_id(block)
+    }
+
+    async fn normal_use(
+        &self,
+        block: &Block,
+        _player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        world: &World,
+    ) {
+        tune(world, block, location).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        _player: &Player,
+        location: BlockPos,
+        item: &Item,
+        _server: &Server,
+        world: &World,
+    ) -> BlockActionResult {
+        // Heads are placed on top instead
+        if get_block_by_item(item.id)
+            .is_some_and(|held| held.name.ends_with("_head") || held.name.ends_with("_skull"))
+        {
+            return BlockActionResult::Continue;
+        }
+        tune(world, block, location).await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        _server: &Server,
+        world: &World,
+        block: &Block,
+        block_pos: &BlockPos,
+        source_face: &BlockDirection,
+        _source_block_pos: &BlockPos,
+    ) {
+        if !matches!(source_face, BlockDirection::Up | BlockDirection::Down) {
+            return;
+        }
+        let Ok(state_id) = world.get_block_state_id(block_pos).await else {
+            return;
+        };
+        let mut props = NoteBlockLikeProperties::from_state_id(state_id, block);
+        let instrument = instrument_at(world, block_pos).await;
+        if props.instrument != instrument {
+            props.instrument = instrument;
+            world
+                .set_block_state(block_pos, props.to_state_id(block))
+                .await;
+        }
+    }
+}
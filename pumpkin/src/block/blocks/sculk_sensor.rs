@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block::{
+    Block, BlockProperties, BlockState, EnumVariants, Integer0To15, SculkSensorLikeProperties,
+    SculkSensorPhase,
+};
+use pumpkin_data::game_event::GameEvent;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use tokio::sync::Mutex;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+use crate::world::game_event::{
+    GameEventContext, GameEventListener, block_center, is_dampened, is_occluded,
+    vibration_frequency,
+};
+
+/// How far away a sculk sensor notices vibrations, in blocks
+const RANGE: u32 = 8;
+/// How long a sensor stays active after a vibration arrived
+const ACTIVE_TICKS: u32 = 30;
+/// How long a sensor ignores vibrations after being active
+const COOLDOWN_TICKS: u32 = 10;
+
+#[pumpkin_block("minecraft:sculk_sensor")]
+pub struct SculkSensorBlock;
+
+#[async_trait]
+impl PumpkinBlock for SculkSensorBlock {
+    async fn placed(
+        &self,
+        _block: &Block,
+        _player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        world: &World,
+    ) {
+        world
+            .game_event_listeners
+            .register_block(location, Arc::new(SculkSensorListener::new(location)))
+            .await;
+    }
+
+    async fn broken(
+        &self,
+        _block: &Block,
+        _player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        world: Arc<World>,
+        _state: BlockState,
+    ) {
+        world.game_event_listeners.unregister_block(&location).await;
+    }
+}
+
+enum SensorState {
+    Inactive,
+    /// A vibration is on its way to the sensor, it travels a block per tick
+    Receiving {
+        ticks_left: u32,
+        power: u8,
+    },
+    Active {
+        ticks_left: u32,
+    },
+    Cooldown {
+        ticks_left: u32,
+    },
+}
+
+/// Listens for vibrations and powers the sensor for a moment once one arrives, the closer the
+/// vibration the stronger
+pub struct SculkSensorListener {
+    location: BlockPos,
+    state: Mutex<SensorState>,
+}
+
+impl SculkSensorListener {
+    #[must_use]
+    pub fn new(location: BlockPos) -> Self {
+        Self {
+            location,
+            state: Mutex::new(SensorState::Inactive),
+        }
+    }
+
+    /// Sets the phase and power of the sensor, returns false if the sensor is gone
+    async fn set_phase(&self, world: &World, phase: SculkSensorPhase, power: u8) -> bool {
+        let Ok((block, state)) = world.get_block_and_block_state(&self.location).await else {
+            return false;
+        };
+        if block.name != "sculk_sensor" {
+            return false;
+        }
+        let mut props = SculkSensorLikeProperties::from_state_id(state.id, &block);
+        props.sculk_sensor_phase = phase;
+        props.power = Integer0To15::from_index(u16::from(power));
+        world
+            .set_block_state(&self.location, props.to_state_id(&block))
+            .await;
+        // TODO: Update neighbors once redstone is implemented, so the power is picked up
+        true
+    }
+}
+
+#[async_trait]
+impl GameEventListener for SculkSensorListener {
+    fn position(&self) -> Vector3<f64> {
+        block_center(&self.location)
+    }
+
+    fn range(&self) -> u32 {
+        RANGE
+    }
+
+    async fn handle(
+        &self,
+        world: &Arc<World>,
+        event: GameEvent,
+        position: Vector3<f64>,
+        context: &GameEventContext,
+    ) {
+        if vibration_frequency(event).is_none() || is_dampened(event, context) {
+            return;
+        }
+        // The sensor itself being placed or broken doesn't count
+        let at_sensor = BlockPos::floored(position.x, position.y, position.z) == self.location;
+        if at_sensor && matches!(event, GameEvent::BlockPlace | GameEvent::BlockDestroy) {
+            return;
+        }
+
+        let mut state = self.state.lock().await;
+        // Closer events are handled first, so the closest vibration of a tick wins
+        if !matches!(*state, SensorState::Inactive) {
+            return;
+        }
+        if is_occluded(world, position, self.position()).await {
+            return;
+        }
+        let distance = position.squared_distance_to_vec(self.position()).sqrt();
+        let power = 15 - (15.0 * distance / f64::from(RANGE)).floor() as i32;
+        *state = SensorState::Receiving {
+            ticks_left: distance.floor() as u32,
+            power: power.clamp(1, 15) as u8,
+        };
+    }
+
+    async fn tick(&self, world: &Arc<World>) -> bool {
+        let mut state = self.state.lock().await;
+        match &mut *state {
+            SensorState::Receiving { ticks_left, power } if *ticks_left == 0 => {
+                let power = *power;
+                *state = SensorState::Active {
+                    ticks_left: ACTIVE_TICKS,
+                };
+                world
+                    .play_block_sound(
+                        Sound::BlockSculkSensorClicking,
+                        SoundCategory::Blocks,
+                        self.location,
+                    )
+                    .await;
+                self.set_phase(world, SculkSensorPhase::Active, power).await
+            }
+            SensorState::Active { ticks_left } if *ticks_left == 0 => {
+                *state = SensorState::Cooldown {
+                    ticks_left: COOLDOWN_TICKS,
+                };
+                world
+                    .play_block_sound(
+                        Sound::BlockSculkSensorClickingStop,
+                        SoundCategory::Blocks,
+                        self.location,
+                    )
+                    .await;
+                self.set_phase(world, SculkSensorPhase::Cooldown, 0).await
+            }
+            SensorState::Cooldown { ticks_left } if *ticks_left == 0 => {
+                *state = SensorState::Inactive;
+                self.set_phase(world, SculkSensorPhase::Inactive, 0).await
+            }
+            SensorState::Receiving { ticks_left, .. }
+            | SensorState::Active { ticks_left }
+            | SensorState::Cooldown { ticks_left } => {
+                *ticks_left -= 1;
+                true
+            }
+            // The sensor may have been replaced without being broken, like by an explosion
+            SensorState::Inactive => world
+                .get_block(&self.location)
+                .await
+                .is_ok_and(|block| block.name == "sculk_sensor"),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block::{Block, BlockState};
+use pumpkin_data::item::Item;
+use pumpkin_data::screen::WindowType;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::tag::{RegistryKey, get_tag_values};
+use pumpkin_inventory::{OpenContainer, ShulkerBox};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::{client::play::CBlockAction, codec::var_int::VarInt};
+use pumpkin_util::GameMode;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
+
+use crate::block::drop_stack;
+use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
+use crate::block::registry::{BlockActionResult, BlockRegistry};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+
+pub fn register_shulker_box_blocks(manager: &mut BlockRegistry) {
+    let tag_values = get_tag_values(RegistryKey::Block, "minecraft:shulker_boxes").unwrap();
+    for id in tag_values {
+        manager.register(ShulkerBoxBlock { id });
+    }
+}
+
+/// A shulker box keeps its items when broken, they are stored in the dropped item and put back
+/// when it is placed again
+pub struct ShulkerBoxBlock {
+    id: &'static str,
+}
+
+impl BlockMetadata for ShulkerBoxBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn id(&self) -> &'static str {
+        self.id
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for ShulkerBoxBlock {
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        _world: &World,
+    ) {
+        self.open_shulker_box(block, player, location, server).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+        _world: &World,
+    ) -> BlockActionResult {
+        self.open_shulker_box(block, player, location, server).await;
+        BlockActionResult::Consume
+    }
+
+    async fn placed(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        world: &World,
+    ) {
+        let contents = {
+            let inventory = player.inventory().lock().await;
+            inventory
+                .held_item()
+                .and_then(|stack| stack.components.container.clone())
+        };
+        let Some(contents) = contents else {
+            return;
+        };
+        let mut nbt = NbtCompound::new();
+        super::write_container_items(&ShulkerBox::from_contents(contents), &mut nbt);
+        world
+            .set_block_entity(&super::container_block_entity_id(block), location, nbt)
+            .await;
+    }
+
+    async fn broken(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        world: Arc<World>,
+        _state: BlockState,
+    ) {
+        // The items go into the dropped item, a shulker box placed here later starts over
+        let slots = super::take_container_contents(block, location, server, &world).await;
+        let contents = slots.iter().any(Option::is_some).then_some(slots);
+
+        // Creative players only get the shulker box back when there was something in it
+        if player.gamemode.load() == GameMode::Creative && contents.is_none() {
+            return;
+        }
+        let Some(item) = Item::from_registry_key(block.name) else {
+            return;
+        };
+        let mut stack = ItemStack::new(1, item);
+        stack.components.container = contents;
+        drop_stack(&world, &location, stack).await;
+    }
+
+    async fn close(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        container.remove_player(player.entity_id());
+        let world = player.world().await;
+        super::save_container(block, location, container, &world).await;
+        if container.get_number_of_players() == 0 {
+            player
+                .world()
+                .await
+                .play_block_sound(Sound::BlockShulkerBoxClose, SoundCategory::Blocks, location)
+                .await;
+        }
+        Self::send_lid_action(block, container, location, server).await;
+    }
+}
+
+impl ShulkerBoxBlock {
+    pub async fn open_shulker_box(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        super::standard_open_container::<ShulkerBox>(
+            block,
+            player,
+            location,
+            server,
+            WindowType::ShulkerBox,
+        )
+        .await;
+
+        if let Some(container_id) = server.get_container_id(location, block.clone()).await {
+            let open_containers = server.open_containers.read().await;
+            if let Some(container) = open_containers.get(&u64::from(container_id)) {
+                if container.get_number_of_players() == 1 {
+                    player
+                        .world()
+                        .await
+                        .play_block_sound(
+                            Sound::BlockShulkerBoxOpen,
+                            SoundCategory::Blocks,
+                            location,
+                        )
+                        .await;
+                }
+                Self::send_lid_action(block, container, location, server).await;
+            }
+        }
+    }
+
+    /// Opens or closes the lid depending on how many players are looking inside
+    async fn send_lid_action(
+        block: &Block,
+        container: &OpenContainer,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        server
+            .broadcast_packet_all(&CBlockAction::new(
+                &location,
+                1,
+                container.get_number_of_players() as u8,
+                VarInt(block.id.into()),
+            ))
+            .await;
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use pumpkin_data::block::Block;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::world::WorldEvent;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::client::play::CLevelEvent;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::world_info::game_rules;
+use rand::Rng;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::block::{get_property, with_property};
+use crate::entity::living::LivingEntity;
+use crate::world::World;
+
+#[pumpkin_block("minecraft:turtle_egg")]
+pub struct TurtleEggBlock;
+
+/// Cracks one of the eggs with a chance of one in `inverse_chance`, the block breaks with the
+/// last one
+async fn try_break_egg(
+    block: &Block,
+    world: &Arc<World>,
+    location: BlockPos,
+    entity: &LivingEntity,
+    inverse_chance: u32,
+) {
+    let entity_type = entity.entity.entity_type;
+    if entity_type == EntityType::TURTLE || entity_type == EntityType::BAT {
+        return;
+    }
+    if entity_type != EntityType::PLAYER && !world.game_rule(game_rules::MOB_GRIEFING) {
+        return;
+    }
+    if rand::thread_rng().gen_range(0..inverse_chance) != 0 {
+        return;
+    }
+    let Ok(state_id) = world.get_block_state_id(&location).await else {
+        return;
+    };
+    let Some(eggs) = get_property(block, state_id, "eggs").and_then(|eggs| eggs.parse::<u8>().ok())
+    else {
+        return;
+    };
+
+    world
+        .play_block_sound(Sound::EntityTurtleEggBreak, SoundCategory::Blocks, location)
+        .await;
+    if eggs <= 1 {
+        world.break_block(&location, None, false, None).await;
+        return;
+    }
+    if let Some(new_state) = with_property(block, state_id, "eggs", &(eggs - 1).to_string()) {
+        world.set_block_state(&location, new_state).await;
+        world
+            .broadcast_packet_all(&CLevelEvent::new(
+                WorldEvent::BlockBroken as i32,
+                location,
+                i32::from(state_id),
+                false,
+            ))
+            .await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for TurtleEggBlock {
+    // TODO: Walking over the eggs cracks them as well
+    async fn on_landed_upon(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        location: BlockPos,
+        entity: &LivingEntity,
+        _fall_distance: f32,
+    ) {
+        if !entity.entity.sneaking.load(Ordering::Relaxed) {
+            try_break_egg(block, world, location, entity, 3).await;
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::block::registry::BlockActionResult;
+use crate::entity::living::LivingEntity;
 use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::World;
@@ -34,6 +35,22 @@ pub trait PumpkinBlock: Send + Sync {
         true
     }
     async fn explode(&self, _block: &Block, _world: &Arc<World>, _location: BlockPos) {}
+    /// Whether `random_tick` has to be called, most blocks don't change on their own
+    fn ticks_randomly(&self) -> bool {
+        false
+    }
+    /// Called for a few random blocks of every chunk near players each tick, like crops growing
+    async fn random_tick(&self, _block: &Block, _world: &Arc<World>, _location: BlockPos) {}
+    /// Called when an entity lands on the block after falling, before it takes fall damage
+    async fn on_landed_upon(
+        &self,
+        _block: &Block,
+        _world: &Arc<World>,
+        _location: BlockPos,
+        _entity: &LivingEntity,
+        _fall_distance: f32,
+    ) {
+    }
     async fn use_with_item(
         &self,
         _block: &Block,
@@ -1,4 +1,5 @@
 use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
+use crate::entity::living::LivingEntity;
 use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::World;
@@ -8,7 +9,7 @@ use pumpkin_inventory::OpenContainer;
 use pumpkin_protocol::server::play::SUseItemOn;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::block::BlockDirection;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 pub enum BlockActionResult {
@@ -21,13 +22,48 @@ pub enum BlockActionResult {
 #[derive(Default)]
 pub struct BlockRegistry {
     blocks: HashMap<String, Arc<dyn PumpkinBlock>>,
+    /// The ids of the blocks which get random ticks, looked up for many blocks each tick
+    random_ticking: HashSet<u16>,
 }
 
 impl BlockRegistry {
     pub fn register<T: PumpkinBlock + BlockMetadata + 'static>(&mut self, block: T) {
+        if block.ticks_randomly() {
+            if let Some(registered) = Block::from_registry_key(block.id()) {
+                self.random_ticking.insert(registered.id);
+            }
+        }
         self.blocks.insert(block.name(), Arc::new(block));
     }
 
+    #[must_use]
+    pub fn ticks_randomly(&self, block: &Block) -> bool {
+        self.random_ticking.contains(&block.id)
+    }
+
+    pub async fn random_tick(&self, block: &Block, world: &Arc<World>, location: BlockPos) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block.random_tick(block, world, location).await;
+        }
+    }
+
+    pub async fn on_landed_upon(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        location: BlockPos,
+        entity: &LivingEntity,
+        fall_distance: f32,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_landed_upon(block, world, location, entity, fall_distance)
+                .await;
+        }
+    }
+
     pub async fn on_use(
         &self,
         block: &Block,
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+use pumpkin_util::text::color::NamedColor;
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        CommandSender,
+        args::{ArgumentConsumer, RawArgs},
+    },
+    Arg, FindArg, GetClientSideArgParser,
+};
+
+/// A named color or `reset`, which is consumed as `None`
+pub struct ColorArgumentConsumer;
+
+impl GetClientSideArgParser for ColorArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::Color
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ColorArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        match args.pop()? {
+            "reset" => Some(Arg::Color(None)),
+            color => NamedColor::try_from(color)
+                .ok()
+                .map(|color| Arg::Color(Some(color))),
+        }
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl<'a> FindArg<'a> for ColorArgumentConsumer {
+    type Data = Option<NamedColor>;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Color(data)) => Ok(*data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+use pumpkin_util::player_name::is_valid_player_name;
 
 use crate::command::CommandSender;
 use crate::command::dispatcher::CommandError;
@@ -59,8 +60,12 @@ impl ArgumentConsumer for EntityArgumentConsumer {
             "@r" => server.get_random_player().await,
             // @a/@e/@r are not valid because we're looking for a single entity
             "@a" | "@e" => None,
-            // player name is only valid if player is online
-            name => server.get_player_by_name(name).await,
+            // entity uuid or player name, only online players can be targeted for now
+            name => match uuid::Uuid::parse_str(name) {
+                Ok(uuid) => server.get_player_by_uuid(uuid).await,
+                Err(_) if is_valid_player_name(name) => server.get_player_by_name(name).await,
+                Err(_) => None,
+            },
         };
 
         entity.map(Arg::Entity)
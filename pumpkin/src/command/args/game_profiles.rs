@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::command::CommandSender;
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::data::known_players::KNOWN_PLAYERS;
+use crate::net::GameProfile;
+use crate::server::Server;
+
+use super::players::PlayersArgumentConsumer;
+use super::{Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+
+/// Select one or multiple players, including players who are offline but joined before
+pub struct GameProfilesArgumentConsumer;
+
+impl GetClientSideArgParser for GameProfilesArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::GameProfile
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        // The client only knows the names of players who are online
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for GameProfilesArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        src: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let s = *args.last()?;
+
+        if let Some(Arg::Players(players)) =
+            PlayersArgumentConsumer.consume(src, server, args).await
+        {
+            let profiles = players
+                .iter()
+                .map(|player| player.gameprofile.clone())
+                .collect();
+            return Some(Arg::GameProfiles(profiles));
+        }
+
+        let known_players = KNOWN_PLAYERS.read().await;
+        let known = uuid::Uuid::parse_str(s).map_or_else(
+            |_| known_players.get_by_name(s),
+            |uuid| {
+                known_players
+                    .players
+                    .iter()
+                    .find(|player| player.uuid == uuid)
+            },
+        )?;
+        Some(Arg::GameProfiles(vec![known.profile()]))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        // Only the word being typed is replaced by the suggestion
+        let typed = input
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        let mut names = server
+            .get_all_players()
+            .await
+            .iter()
+            .map(|player| player.gameprofile.name.clone())
+            .collect::<Vec<_>>();
+        names.extend(
+            KNOWN_PLAYERS
+                .read()
+                .await
+                .players
+                .iter()
+                .map(|player| player.name.clone()),
+        );
+        names.sort_unstable();
+        names.dedup();
+
+        let suggestions = names
+            .into_iter()
+            .filter(|name| name.to_lowercase().starts_with(&typed))
+            .map(|name| CommandSuggestion::new(name, None))
+            .collect();
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for GameProfilesArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "targets"
+    }
+}
+
+impl<'a> FindArg<'a> for GameProfilesArgumentConsumer {
+    type Data = &'a [GameProfile];
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::GameProfiles(data)) => Ok(data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
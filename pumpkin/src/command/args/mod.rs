@@ -2,12 +2,13 @@ use std::{collections::HashMap, hash::Hash, sync::Arc};
 
 use async_trait::async_trait;
 use bounded_num::{NotInBounds, Number};
+use pumpkin_data::chunk::Biome;
 use pumpkin_data::damage::DamageType;
 use pumpkin_data::entity::EffectType;
 use pumpkin_data::particle::Particle;
 use pumpkin_data::sound::SoundCategory;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
-use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
 use pumpkin_util::{
     GameMode,
     math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
@@ -19,19 +20,22 @@ use super::{
     tree::{CommandTree, RawArgs},
 };
 use crate::world::bossbar::{BossbarColor, BossbarDivisions};
-use crate::{entity::player::Player, server::Server};
+use crate::{entity::player::Player, net::GameProfile, server::Server};
 
 pub mod block;
 pub mod bool;
 pub mod bossbar_color;
 pub mod bossbar_style;
 pub mod bounded_num;
+pub mod color;
 pub mod command;
 mod coordinate;
 pub mod entities;
 pub mod entity;
+pub mod game_profiles;
 pub mod gamemode;
 pub mod message;
+pub mod objective;
 pub mod players;
 pub mod position_2d;
 pub mod position_3d;
@@ -43,6 +47,7 @@ pub mod simple;
 pub mod sound;
 pub mod sound_category;
 pub mod summonable_entities;
+pub mod team;
 pub mod textcomponent;
 pub mod time;
 
@@ -83,6 +88,7 @@ pub enum Arg<'a> {
     Entities(Vec<Arc<Player>>),
     Entity(Arc<Player>),
     Players(Vec<Arc<Player>>),
+    GameProfiles(Vec<GameProfile>),
     BlockPos(BlockPos),
     Pos3D(Vector3<f64>),
     Pos2D(Vector2<f64>),
@@ -100,11 +106,14 @@ pub enum Arg<'a> {
     Time(i32),
     Num(Result<Number, NotInBounds>),
     Bool(bool),
+    /// `None` is the reset color
+    Color(Option<NamedColor>),
     #[allow(unused)]
     Simple(&'a str),
     SoundCategory(SoundCategory),
     DamageType(DamageType),
     Effect(EffectType),
+    Biome(Biome),
 }
 
 /// see [`crate::commands::tree::builder::argument`] and [`CommandTree::execute`]/[`crate::commands::tree::builder::NonLeafNodeBuilder::execute`]
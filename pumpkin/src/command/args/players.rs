@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+use pumpkin_util::player_name::is_valid_player_name;
 
 use crate::command::CommandSender;
 use crate::command::dispatcher::CommandError;
@@ -54,7 +55,12 @@ impl ArgumentConsumer for PlayersArgumentConsumer {
                 (server.get_random_player().await).map_or_else(|| Some(vec![]), |p| Some(vec![p]))
             }
             "@a" | "@e" => Some(server.get_all_players().await),
-            name => server.get_player_by_name(name).await.map(|p| vec![p]),
+            name => match uuid::Uuid::parse_str(name) {
+                Ok(uuid) => server.get_player_by_uuid(uuid).await,
+                Err(_) if is_valid_player_name(name) => server.get_player_by_name(name).await,
+                Err(_) => None,
+            }
+            .map(|p| vec![p]),
         };
 
         players.map(Arg::Players)
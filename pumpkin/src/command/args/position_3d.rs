@@ -32,9 +32,13 @@ impl ArgumentConsumer for Position3DArgumentConsumer {
         _server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        let pos = MaybeRelativePosition3D::try_new(args.pop()?, args.pop()?, args.pop()?)?;
-
-        let vec3 = pos.try_to_absolute(src.position())?;
+        let (x, y, z) = (args.pop()?, args.pop()?, args.pop()?);
+        let vec3 = if x.starts_with('^') {
+            let (yaw, pitch) = src.rotation()?;
+            LocalPosition3D::try_new(x, y, z)?.to_absolute(src.position()?, yaw, pitch)
+        } else {
+            MaybeRelativePosition3D::try_new(x, y, z)?.try_to_absolute(src.position())?
+        };
 
         Some(Arg::Pos3D(vec3))
     }
@@ -73,6 +77,58 @@ impl MaybeRelativePosition3D {
     }
 }
 
+/// Coordinates relative to where the sender looks, like `^ ^ ^5` for five blocks ahead. Either all
+/// of the coordinates are local or none
+struct LocalPosition3D {
+    left: f64,
+    up: f64,
+    forwards: f64,
+}
+
+impl LocalPosition3D {
+    fn try_new(x: &str, y: &str, z: &str) -> Option<Self> {
+        let parse = |s: &str| {
+            let offset = s.strip_prefix('^')?;
+            if offset.is_empty() {
+                Some(0.0)
+            } else {
+                offset.parse().ok()
+            }
+        };
+        Some(Self {
+            left: parse(x)?,
+            up: parse(y)?,
+            forwards: parse(z)?,
+        })
+    }
+
+    fn to_absolute(&self, origin: Vector3<f64>, yaw: f32, pitch: f32) -> Vector3<f64> {
+        let yaw = f64::from(yaw + 90.0).to_radians();
+        let pitch = f64::from(-pitch).to_radians();
+        let pitch_up = pitch + std::f64::consts::FRAC_PI_2;
+        let forwards = Vector3::new(
+            yaw.cos() * pitch.cos(),
+            pitch.sin(),
+            yaw.sin() * pitch.cos(),
+        );
+        let up = Vector3::new(
+            yaw.cos() * pitch_up.cos(),
+            pitch_up.sin(),
+            yaw.sin() * pitch_up.cos(),
+        );
+        // The cross product of both, pointing to the left
+        let left = Vector3::new(
+            forwards.z * up.y - forwards.y * up.z,
+            forwards.x * up.z - forwards.z * up.x,
+            forwards.y * up.x - forwards.x * up.y,
+        );
+        origin
+            .add(&(forwards * self.forwards))
+            .add(&(up * self.up))
+            .add(&(left * self.left))
+    }
+}
+
 impl DefaultNameArgConsumer for Position3DArgumentConsumer {
     fn default_name(&self) -> &'static str {
         "pos"
@@ -89,3 +145,33 @@ impl<'a> FindArg<'a> for Position3DArgumentConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::LocalPosition3D;
+
+    fn assert_close(a: Vector3<f64>, b: Vector3<f64>) {
+        assert!(a.sub(&b).length() < 1e-6, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn local_coordinates() {
+        let origin = Vector3::new(0.0, 64.0, 0.0);
+        // Yaw 0 looks to positive z, so left is positive x
+        let pos = LocalPosition3D::try_new("^1", "^2", "^3").unwrap();
+        assert_close(
+            pos.to_absolute(origin, 0.0, 0.0),
+            Vector3::new(1.0, 66.0, 3.0),
+        );
+        // Looking straight down, forwards is down
+        let pos = LocalPosition3D::try_new("^", "^", "^5").unwrap();
+        assert_close(
+            pos.to_absolute(origin, 0.0, 90.0),
+            Vector3::new(0.0, 59.0, 0.0),
+        );
+
+        assert!(LocalPosition3D::try_new("^", "~", "^").is_none());
+    }
+}
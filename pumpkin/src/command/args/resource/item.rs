@@ -1,6 +1,7 @@
 use async_trait::async_trait;
-use pumpkin_data::item::Item;
+use pumpkin_data::{block::Block, item::Item, potion::Potion};
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+use pumpkin_world::item::{AdventurePredicate, BlockSet, ItemStack};
 
 use crate::command::{
     CommandSender,
@@ -13,11 +14,12 @@ use crate::command::{
 };
 use crate::server::Server;
 
+/// An item with optional components, like `potion[potion_contents={potion:"healing"}]`
 pub struct ItemArgumentConsumer;
 
 impl GetClientSideArgParser for ItemArgumentConsumer {
     fn get_client_side_parser(&self) -> ArgumentType {
-        ArgumentType::Resource { identifier: "item" }
+        ArgumentType::ItemStack
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
@@ -33,7 +35,6 @@ impl ArgumentConsumer for ItemArgumentConsumer {
         _server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        // todo: get an actual item
         Some(Arg::Item(args.pop()?))
     }
 
@@ -54,20 +55,199 @@ impl DefaultNameArgConsumer for ItemArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for ItemArgumentConsumer {
-    type Data = (&'a str, Item);
+    /// The id of the item and a single item of it
+    type Data = (&'a str, ItemStack);
 
     fn find_arg(args: &'a ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Item(name)) => Item::from_registry_key(&name.replace("minecraft:", ""))
-                .map_or_else(
-                    || {
-                        Err(CommandError::GeneralCommandIssue(format!(
-                            "Item {name} does not exist."
-                        )))
-                    },
-                    |item| Ok((*name, item)),
-                ),
+            Some(Arg::Item(input)) => parse_item_stack(input),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
 }
+
+fn strip_namespace(name: &str) -> &str {
+    name.strip_prefix("minecraft:").unwrap_or(name)
+}
+
+fn parse_item_stack(input: &str) -> Result<(&str, ItemStack), CommandError> {
+    let (id, components) = match input.split_once('[') {
+        Some((id, rest)) => {
+            let components = rest.strip_suffix(']').ok_or_else(|| {
+                CommandError::GeneralCommandIssue(format!("Missing ] after components: {input}"))
+            })?;
+            (id, Some(components))
+        }
+        None => (input, None),
+    };
+    let item = Item::from_registry_key(strip_namespace(id))
+        .ok_or_else(|| CommandError::GeneralCommandIssue(format!("Item {id} does not exist.")))?;
+    let mut stack = ItemStack::new(1, item);
+
+    for component in components.into_iter().flat_map(split_components) {
+        let Some((key, value)) = component.split_once('=') else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Expected a value for component {component}"
+            )));
+        };
+        match strip_namespace(key.trim()) {
+            "potion_contents" => {
+                stack.components.potion = Some(parse_potion(value.trim())?);
+            }
+            "can_place_on" => {
+                stack.components.can_place_on = Some(parse_adventure_predicate(value.trim())?);
+            }
+            "can_break" => {
+                stack.components.can_break = Some(parse_adventure_predicate(value.trim())?);
+            }
+            key => {
+                return Err(CommandError::GeneralCommandIssue(format!(
+                    "Component {key} is not supported"
+                )));
+            }
+        }
+    }
+    Ok((id, stack))
+}
+
+/// Splits at the commas which aren't inside of braces, brackets or quotes
+fn split_components(components: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0u32;
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in components.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '{' | '[' if !in_quotes => depth += 1,
+            '}' | ']' if !in_quotes => depth = depth.saturating_sub(1),
+            ',' if !in_quotes && depth == 0 => {
+                parts.push(&components[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&components[start..]);
+    parts.retain(|part| !part.trim().is_empty());
+    parts
+}
+
+/// Either just the potion, like `"healing"`, or `{potion:"healing"}`
+fn parse_potion(value: &str) -> Result<Potion, CommandError> {
+    let potion = match value
+        .strip_prefix('{')
+        .and_then(|value| value.strip_suffix('}'))
+    {
+        Some(fields) => fields
+            .split(',')
+            .find_map(|field| {
+                let (key, value) = field.split_once(':')?;
+                (key.trim() == "potion").then_some(value)
+            })
+            .ok_or_else(|| {
+                CommandError::GeneralCommandIssue("Expected a potion in potion_contents".into())
+            })?,
+        None => value,
+    };
+    let potion = potion.trim().trim_matches('"');
+    Potion::from_name(strip_namespace(potion))
+        .ok_or_else(|| CommandError::GeneralCommandIssue(format!("Unknown potion {potion}")))
+}
+
+/// `{blocks:"stone"}`, a list of them or `{predicates:[...],show_in_tooltip:false}`. The blocks
+/// may be a list too, or a tag like `"#logs"`. Predicates on block states aren't supported
+fn parse_adventure_predicate(value: &str) -> Result<AdventurePredicate, CommandError> {
+    let invalid = || CommandError::GeneralCommandIssue(format!("Invalid block predicate {value}"));
+    let mut blocks = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("blocks") {
+        rest = rest[start + "blocks".len()..].trim_start();
+        rest = rest.strip_prefix(':').ok_or_else(invalid)?.trim_start();
+        let (names, after) = match rest.strip_prefix('[') {
+            Some(list) => list.split_once(']').ok_or_else(invalid)?,
+            None => rest.split_at(rest.find([',', '}']).unwrap_or(rest.len())),
+        };
+        rest = after;
+
+        let mut ids = Vec::new();
+        for name in names.split(',') {
+            let name = name.trim().trim_matches('"');
+            if let Some(tag) = name.strip_prefix('#') {
+                let tag = if tag.contains(':') {
+                    tag.to_string()
+                } else {
+                    format!("minecraft:{tag}")
+                };
+                blocks.push(BlockSet::Tag(tag));
+                continue;
+            }
+            let block = Block::from_registry_key(strip_namespace(name)).ok_or_else(|| {
+                CommandError::GeneralCommandIssue(format!("Block {name} does not exist."))
+            })?;
+            ids.push(block.id);
+        }
+        if !ids.is_empty() {
+            blocks.push(BlockSet::Blocks(ids));
+        }
+    }
+    if blocks.is_empty() {
+        return Err(invalid());
+    }
+    let hidden = value
+        .split_whitespace()
+        .collect::<String>()
+        .contains("show_in_tooltip:false");
+    Ok(AdventurePredicate {
+        blocks,
+        show_in_tooltip: !hidden,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::{block::Block, item::Item, potion::Potion};
+    use pumpkin_world::item::{AdventurePredicate, BlockSet};
+
+    use super::parse_item_stack;
+
+    #[test]
+    fn item_with_components() {
+        let (id, stack) = parse_item_stack("minecraft:stone").unwrap();
+        assert_eq!(id, "minecraft:stone");
+        assert_eq!(stack.item.id, Item::STONE.id);
+        assert!(stack.components.potion.is_none());
+
+        let (_, stack) =
+            parse_item_stack("potion[potion_contents={potion:\"minecraft:healing\"}]").unwrap();
+        assert_eq!(stack.components.potion, Some(Potion::Healing));
+        let (_, stack) = parse_item_stack("splash_potion[potion_contents=\"healing\"]").unwrap();
+        assert_eq!(stack.components.potion, Some(Potion::Healing));
+
+        let (_, stack) =
+            parse_item_stack("iron_pickaxe[can_break={blocks:[\"stone\",\"dirt\"]}]").unwrap();
+        assert_eq!(
+            stack.components.can_break,
+            Some(AdventurePredicate {
+                blocks: vec![BlockSet::Blocks(vec![Block::STONE.id, Block::DIRT.id])],
+                show_in_tooltip: true,
+            })
+        );
+        let (_, stack) = parse_item_stack(
+            "stone[can_place_on={predicates:[{blocks:\"#logs\"}],show_in_tooltip:false}]",
+        )
+        .unwrap();
+        let predicate = stack.components.can_place_on.unwrap();
+        assert_eq!(
+            predicate.blocks,
+            vec![BlockSet::Tag("minecraft:logs".to_string())]
+        );
+        assert!(!predicate.show_in_tooltip);
+        assert!(predicate.matches(&Block::OAK_LOG));
+        assert!(!predicate.matches(&Block::STONE));
+
+        assert!(parse_item_stack("stone[can_break={blocks:\"not_a_block\"}]").is_err());
+        assert!(parse_item_stack("stone[unknown=1]").is_err());
+        assert!(parse_item_stack("not_an_item").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod biome;
 pub mod damage_type;
 pub mod effect;
 pub mod item;
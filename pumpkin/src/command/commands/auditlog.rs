@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender,
+        args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+        tree::CommandTree,
+        tree::builder::{argument, literal},
+    },
+    data::audit_log::AUDIT_LOG,
+};
+
+const NAMES: [&str; 1] = ["auditlog"];
+const DESCRIPTION: &str = "Searches the latest operator actions.";
+
+const ARG_QUERY: &str = "query";
+
+/// How many entries are shown at most
+const MAX_RESULTS: usize = 10;
+
+struct SearchExecutor;
+
+#[async_trait]
+impl CommandExecutor for SearchExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let query = match args.get(ARG_QUERY) {
+            Some(Arg::Simple(query)) => Some(*query),
+            _ => None,
+        };
+
+        let entries = AUDIT_LOG.search(query, MAX_RESULTS);
+        if entries.is_empty() {
+            sender
+                .send_message(TextComponent::text("No matching audit log entries"))
+                .await;
+            return Ok(());
+        }
+
+        sender
+            .send_message(
+                TextComponent::text(format!("Latest {} audit log entries:", entries.len()))
+                    .color_named(NamedColor::Gold),
+            )
+            .await;
+        // Oldest first, so the newest entry ends up at the bottom of the chat
+        for entry in entries.iter().rev() {
+            sender
+                .send_message(TextComponent::text(entry.summary()))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        literal("search")
+            .execute(SearchExecutor)
+            .then(argument(ARG_QUERY, SimpleArgConsumer).execute(SearchExecutor)),
+    )
+}
@@ -1,17 +1,24 @@
 use crate::{
     command::{
         CommandError, CommandExecutor, CommandSender,
-        args::{Arg, ConsumedArgs, message::MsgArgConsumer, players::PlayersArgumentConsumer},
+        args::{
+            Arg, ConsumedArgs, FindArg, game_profiles::GameProfilesArgumentConsumer,
+            message::MsgArgConsumer,
+        },
         tree::{CommandTree, builder::argument},
     },
     data::{
-        SaveJSONConfiguration, banlist_serializer::BannedPlayerEntry,
+        SaveJSONConfiguration,
+        audit_log::{AuditAction, audit},
+        banlist_serializer::BannedPlayerEntry,
         banned_player_data::BANNED_PLAYER_LIST,
     },
-    entity::player::Player,
+    net::GameProfile,
+    server::Server,
 };
 use CommandError::InvalidConsumption;
 use async_trait::async_trait;
+use pumpkin_config::advanced_config;
 use pumpkin_util::text::TextComponent;
 
 const NAMES: [&str; 1] = ["ban"];
@@ -27,14 +34,14 @@ impl CommandExecutor for NoReasonExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
+        let targets = GameProfilesArgumentConsumer::find_arg(args, ARG_TARGET)?;
 
-        ban_player(sender, &targets[0], None).await;
+        for target in targets {
+            ban_player(sender, server, target, None).await;
+        }
         Ok(())
     }
 }
@@ -46,67 +53,73 @@ impl CommandExecutor for ReasonExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
+        let targets = GameProfilesArgumentConsumer::find_arg(args, ARG_TARGET)?;
 
         let Some(Arg::Msg(reason)) = args.get(ARG_REASON) else {
             return Err(InvalidConsumption(Some(ARG_REASON.into())));
         };
 
-        ban_player(sender, &targets[0], Some(reason.to_string())).await;
+        for target in targets {
+            ban_player(sender, server, target, Some(reason.clone())).await;
+        }
         Ok(())
     }
 }
 
-async fn ban_player(sender: &CommandSender<'_>, player: &Player, reason: Option<String>) {
+async fn ban_player(
+    sender: &CommandSender<'_>,
+    server: &Server,
+    profile: &GameProfile,
+    reason: Option<String>,
+) {
     let mut banned_players = BANNED_PLAYER_LIST.write().await;
 
-    let reason = reason.unwrap_or_else(|| "Banned by an operator.".to_string());
-    let profile = &player.gameprofile;
+    let reason = reason.unwrap_or_else(|| advanced_config().bans.default_reason.clone());
 
-    if banned_players.get_entry(&player.gameprofile).is_some() {
+    if banned_players.get_entry(profile).is_some() {
         sender
             .send_message(TextComponent::translate("commands.ban.failed", []))
             .await;
         return;
     }
 
-    banned_players.banned_players.push(BannedPlayerEntry::new(
-        profile,
-        sender.to_string(),
-        None,
-        reason.clone(),
-    ));
+    let entry = BannedPlayerEntry::new(profile, sender.to_string(), None, reason.clone());
+    let kick_message = entry.kick_message();
+    banned_players.banned_players.push(entry);
 
     banned_players.save();
     drop(banned_players);
+    audit(
+        sender,
+        AuditAction::Ban {
+            target: profile.name.clone(),
+            reason: reason.clone(),
+        },
+    );
 
     // Send messages
     sender
         .send_message(TextComponent::translate(
             "commands.ban.success",
             [
-                TextComponent::text(player.gameprofile.name.clone()),
+                TextComponent::text(profile.name.clone()),
                 TextComponent::text(reason),
             ],
         ))
         .await;
 
-    player
-        .kick(TextComponent::translate(
-            "multiplayer.disconnect.banned",
-            [],
-        ))
-        .await;
+    // Players who are offline are turned away when they join again
+    if let Some(player) = server.get_player_by_uuid(profile.id).await {
+        player.kick(kick_message).await;
+    }
 }
 
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
-        argument(ARG_TARGET, PlayersArgumentConsumer)
+        argument(ARG_TARGET, GameProfilesArgumentConsumer)
             .execute(NoReasonExecutor)
             .then(argument(ARG_REASON, MsgArgConsumer).execute(ReasonExecutor)),
     )
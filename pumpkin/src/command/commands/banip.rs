@@ -8,12 +8,16 @@ use crate::{
         tree::builder::argument,
     },
     data::{
-        SaveJSONConfiguration, banlist_serializer::BannedIpEntry, banned_ip_data::BANNED_IP_LIST,
+        SaveJSONConfiguration,
+        audit_log::{AuditAction, audit},
+        banlist_serializer::BannedIpEntry,
+        banned_ip_data::BANNED_IP_LIST,
     },
     server::Server,
 };
 use CommandError::InvalidConsumption;
 use async_trait::async_trait;
+use pumpkin_config::advanced_config;
 use pumpkin_util::text::TextComponent;
 
 const NAMES: [&str; 1] = ["ban-ip"];
@@ -79,7 +83,7 @@ impl CommandExecutor for ReasonExecutor {
 }
 
 async fn ban_ip(sender: &CommandSender<'_>, server: &Server, target: &str, reason: Option<String>) {
-    let reason = reason.unwrap_or_else(|| "Banned by an operator.".to_string());
+    let reason = reason.unwrap_or_else(|| advanced_config().bans.default_reason.clone());
 
     let Some(target_ip) = parse_ip(target, server).await else {
         sender
@@ -97,15 +101,19 @@ async fn ban_ip(sender: &CommandSender<'_>, server: &Server, target: &str, reaso
         return;
     }
 
-    banned_ips.banned_ips.push(BannedIpEntry::new(
-        target_ip,
-        sender.to_string(),
-        None,
-        reason.clone(),
-    ));
+    let entry = BannedIpEntry::new(target_ip, sender.to_string(), None, reason.clone());
+    let kick_message = entry.kick_message();
+    banned_ips.banned_ips.push(entry);
 
     banned_ips.save();
     drop(banned_ips);
+    audit(
+        sender,
+        AuditAction::BanIp {
+            target: target_ip.to_string(),
+            reason: reason.clone(),
+        },
+    );
 
     // Send messages
     let affected = server.get_players_by_ip(target_ip).await;
@@ -136,12 +144,7 @@ async fn ban_ip(sender: &CommandSender<'_>, server: &Server, target: &str, reaso
         .await;
 
     for target in affected {
-        target
-            .kick(TextComponent::translate(
-                "multiplayer.disconnect.ip_banned",
-                [],
-            ))
-            .await;
+        target.kick(kick_message.clone()).await;
     }
 }
 
@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use pumpkin_config::advanced_config;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender,
+        args::{ConsumedArgs, FindArgDefaultName, position_2d::Position2DArgumentConsumer},
+        tree::CommandTree,
+        tree::builder::argument_default_name,
+    },
+    server::Server,
+    world::World,
+};
+
+const NAMES: [&str; 1] = ["chunkinfo"];
+
+const DESCRIPTION: &str = "Displays diagnostics about loaded and forced chunks.";
+
+/// The maximum amount of forced chunks listed in the summary
+const MAX_LISTED_FORCED_CHUNKS: usize = 16;
+
+async fn send_level_summary(sender: &CommandSender<'_>, world: &World) {
+    let level = &world.level;
+    let forced = level.forced_chunks();
+
+    sender
+        .send_message(
+            TextComponent::text(format!(
+                "Loaded chunks: {}, forced chunks: {}/{}",
+                level.loaded_chunk_count(),
+                forced.len(),
+                advanced_config().chunk.max_forced_chunks
+            ))
+            .color_named(NamedColor::Gold),
+        )
+        .await;
+
+    let io = level.chunk_io_metrics();
+    sender
+        .send_message(TextComponent::text(format!(
+            "Region IO: {} reads ({} KiB, {} cache hits), {} writes ({} KiB), {} slow",
+            io.reads,
+            io.bytes_read / 1024,
+            io.cache_hits,
+            io.writes,
+            io.bytes_written / 1024,
+            io.slow_reads + io.slow_writes
+        )))
+        .await;
+
+    if forced.is_empty() {
+        return;
+    }
+
+    let listed = forced
+        .iter()
+        .take(MAX_LISTED_FORCED_CHUNKS)
+        .map(|pos| format!("[{}, {}] x{}", pos.x, pos.z, level.chunk_ticket_count(pos)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = match forced.len().checked_sub(MAX_LISTED_FORCED_CHUNKS) {
+        Some(more) if more > 0 => format!("Forced: {listed} and {more} more"),
+        _ => format!("Forced: {listed}"),
+    };
+    sender.send_message(TextComponent::text(message)).await;
+}
+
+async fn send_chunk_info(sender: &CommandSender<'_>, world: &World, chunk: Vector2<i32>) {
+    let level = &world.level;
+    sender
+        .send_message(TextComponent::text(format!(
+            "Chunk [{}, {}]: loaded: {}, watchers: {}, tickets: {}",
+            chunk.x,
+            chunk.z,
+            level.is_chunk_loaded(&chunk),
+            level.chunk_watcher_count(&chunk),
+            level.chunk_ticket_count(&chunk)
+        )))
+        .await;
+}
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        if let CommandSender::Player(player) = sender {
+            let entity = &player.living_entity.entity;
+            let world = entity.world.read().await.clone();
+            let chunk = entity.chunk_pos.load();
+            send_level_summary(sender, &world).await;
+            send_chunk_info(sender, &world, chunk).await;
+            return Ok(());
+        }
+
+        // TODO: Maybe ask player for world, or get the current world
+        send_level_summary(sender, server.worlds.default_world()).await;
+        Ok(())
+    }
+}
+
+struct PosExecutor;
+
+#[async_trait]
+impl CommandExecutor for PosExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Vector2 { x, z } = Position2DArgumentConsumer.find_arg_default_name(args)?;
+        let chunk = Vector2::new((x.floor() as i32) >> 4, (z.floor() as i32) >> 4);
+
+        let world = match sender {
+            CommandSender::Player(player) => player.living_entity.entity.world.read().await.clone(),
+            // TODO: Maybe ask player for world, or get the current world
+            _ => server.worlds.default_world().clone(),
+        };
+        send_chunk_info(sender, &world, chunk).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(Executor)
+        .then(argument_default_name(Position2DArgumentConsumer).execute(PosExecutor))
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::claims::{ClaimError, Region};
+
+const NAMES: [&str; 1] = ["claim"];
+const DESCRIPTION: &str = "Claims a region so only you and players you trust can build in it.";
+
+const ARG_NAME: &str = "name";
+const ARG_FROM: &str = "from";
+const ARG_TO: &str = "to";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+        let from = BlockPosArgumentConsumer::find_arg(args, ARG_FROM)?;
+        let to = BlockPosArgumentConsumer::find_arg(args, ARG_TO)?;
+
+        let region = Region::new(
+            player.gameprofile.id,
+            player.gameprofile.name.clone(),
+            from,
+            to,
+        );
+        let world = player.world().await;
+        let result = world.claims.lock().await.claim(name.to_string(), region);
+        if let Err(err) = result {
+            let issue = match err {
+                ClaimError::NameTaken => format!("There already is a region named {name}"),
+                ClaimError::Overlaps(other) => format!("The region would overlap {other}"),
+                ClaimError::TooBig => "The region contains too many blocks".into(),
+                ClaimError::TooMany => "You can't claim any more regions".into(),
+            };
+            return Err(CommandError::GeneralCommandIssue(issue));
+        }
+        world.save_claims().await;
+
+        sender
+            .send_message(TextComponent::text(format!("Claimed {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player()).then(
+            argument(ARG_NAME, SimpleArgConsumer).then(
+                argument(ARG_FROM, BlockPosArgumentConsumer)
+                    .then(argument(ARG_TO, BlockPosArgumentConsumer).execute(Executor)),
+            ),
+        ),
+    )
+}
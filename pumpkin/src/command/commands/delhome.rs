@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+use super::home::{ARG_NAME, home_name};
+
+const NAMES: [&str; 1] = ["delhome"];
+const DESCRIPTION: &str = "Removes one of your homes.";
+
+struct Executor {
+    named: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let name = home_name(args, self.named)?;
+
+        let removed = server
+            .homes
+            .lock()
+            .await
+            .remove(player.gameprofile.id, name);
+        if removed.is_none() {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "You have no home called {name}"
+            )));
+        }
+        server.save_homes().await;
+
+        sender
+            .send_message(TextComponent::text(format!("Removed home {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(Executor { named: false })
+            .then(argument(ARG_NAME, SimpleArgConsumer).execute(Executor { named: true })),
+    )
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+use super::home::ARG_NAME;
+
+const NAMES: [&str; 1] = ["delwarp"];
+const DESCRIPTION: &str = "Removes a warp.";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+
+        if server.warps.lock().await.remove(name).is_none() {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "There is no warp called {name}"
+            )));
+        }
+        server.save_warps().await;
+
+        sender
+            .send_message(TextComponent::text(format!("Removed warp {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_NAME, SimpleArgConsumer).execute(Executor))
+}
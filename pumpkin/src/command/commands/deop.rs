@@ -5,7 +5,11 @@ use crate::{
         tree::CommandTree,
         tree::builder::argument,
     },
-    data::{SaveJSONConfiguration, op_data::OPERATOR_CONFIG},
+    data::{
+        SaveJSONConfiguration,
+        audit_log::{AuditAction, audit},
+        op_data::OPERATOR_CONFIG,
+    },
 };
 use CommandError::InvalidConsumption;
 use async_trait::async_trait;
@@ -40,12 +44,15 @@ impl CommandExecutor for Executor {
                 config.ops.remove(op_index);
             }
             config.save();
+            audit(
+                sender,
+                AuditAction::Deop {
+                    target: player.gameprofile.name.clone(),
+                },
+            );
 
             player
-                .set_permission_lvl(
-                    pumpkin_util::PermissionLvl::Zero,
-                    &server.command_dispatcher,
-                )
+                .set_permission_lvl(pumpkin_util::PermissionLvl::Zero, server)
                 .await;
 
             let player_name = &player.gameprofile.name;
@@ -0,0 +1,128 @@
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::resource::biome::BiomeArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::{Color, NamedColor};
+
+const NAMES: [&str; 1] = ["fillbiome"];
+
+const DESCRIPTION: &str = "Sets the biome of a region.";
+
+const ARG_FROM: &str = "from";
+const ARG_TO: &str = "to";
+const ARG_BIOME: &str = "biome";
+const ARG_FILTER: &str = "filter";
+
+/// The same limit vanilla uses for the volume of `/fill` and `/fillbiome`
+const MAX_VOLUME: i64 = 32768;
+
+/// Biomes are stored for cells of 4x4x4 blocks, so the corners snap to them
+const fn quantize(position: BlockPos) -> BlockPos {
+    BlockPos(Vector3::new(
+        position.0.x & !3,
+        position.0.y & !3,
+        position.0.z & !3,
+    ))
+}
+
+struct Executor {
+    filtered: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let from = BlockPosArgumentConsumer::find_arg(args, ARG_FROM)?;
+        let to = BlockPosArgumentConsumer::find_arg(args, ARG_TO)?;
+        let biome = BiomeArgumentConsumer::find_arg(args, ARG_BIOME)?;
+        let filter = if self.filtered {
+            Some(BiomeArgumentConsumer::find_arg(args, ARG_FILTER)?)
+        } else {
+            None
+        };
+
+        let start = quantize(BlockPos(Vector3::new(
+            from.0.x.min(to.0.x),
+            from.0.y.min(to.0.y),
+            from.0.z.min(to.0.z),
+        )));
+        let end = quantize(BlockPos(Vector3::new(
+            from.0.x.max(to.0.x),
+            from.0.y.max(to.0.y),
+            from.0.z.max(to.0.z),
+        )));
+
+        let volume = [
+            end.0.x - start.0.x,
+            end.0.y - start.0.y,
+            end.0.z - start.0.z,
+        ]
+        .into_iter()
+        .map(|span| i64::from(span) + 1)
+        .product::<i64>();
+        if volume > MAX_VOLUME {
+            sender
+                .send_message(
+                    TextComponent::translate(
+                        "commands.fillbiome.toobig",
+                        [
+                            TextComponent::text(MAX_VOLUME.to_string()),
+                            TextComponent::text(volume.to_string()),
+                        ],
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
+
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+        let set = world.fill_biome(start, end, biome, filter).await;
+
+        let mut with = vec![TextComponent::text(set.to_string())];
+        with.extend(
+            [start.0.x, start.0.y, start.0.z, end.0.x, end.0.y, end.0.z]
+                .map(|coordinate| TextComponent::text(coordinate.to_string())),
+        );
+        sender
+            .send_message(TextComponent::translate(
+                "commands.fillbiome.success.count",
+                with,
+            ))
+            .await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_FROM, BlockPosArgumentConsumer).then(
+            argument(ARG_TO, BlockPosArgumentConsumer).then(
+                argument(ARG_BIOME, BiomeArgumentConsumer)
+                    .then(
+                        literal("replace").then(
+                            argument(ARG_FILTER, BiomeArgumentConsumer)
+                                .execute(Executor { filtered: true }),
+                        ),
+                    )
+                    .execute(Executor { filtered: false }),
+            ),
+        ),
+    )
+}
@@ -14,6 +14,7 @@ use crate::command::dispatcher::CommandError::{InvalidConsumption, InvalidRequir
 use crate::command::tree::CommandTree;
 use crate::command::tree::builder::{argument, require};
 use crate::command::{CommandExecutor, CommandSender};
+use crate::data::audit_log::{AuditAction, audit};
 use crate::server::Server;
 
 const NAMES: [&str; 1] = ["gamemode"];
@@ -48,6 +49,8 @@ impl CommandExecutor for TargetSelfExecutor {
                         [TextComponent::translate(gamemode_string, [])],
                     ))
                     .await;
+                let target = target.gameprofile.name.clone();
+                audit(sender, AuditAction::GamemodeChange { target, gamemode });
             }
             Ok(())
         } else {
@@ -78,6 +81,13 @@ impl CommandExecutor for TargetPlayerExecutor {
         for target in targets {
             if target.gamemode.load() != gamemode {
                 target.set_gamemode(gamemode).await;
+                audit(
+                    sender,
+                    AuditAction::GamemodeChange {
+                        target: target.gameprofile.name.clone(),
+                        gamemode,
+                    },
+                );
                 let gamemode_string = format!("{gamemode:?}").to_lowercase();
                 let gamemode_string = format!("gameMode.{gamemode_string}");
                 target
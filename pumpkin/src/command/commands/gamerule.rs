@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{CGameEvent, GameEvent};
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::world_info::game_rules::{
+    DO_IMMEDIATE_RESPAWN, DO_LIMITED_CRAFTING, GAME_RULES, GameRuleValue,
+};
+
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender,
+    args::{ConsumedArgs, FindArg, bool::BoolArgConsumer, bounded_num::BoundedNumArgumentConsumer},
+    tree::CommandTree,
+    tree::builder::{argument, literal},
+};
+
+const NAMES: [&str; 1] = ["gamerule"];
+const DESCRIPTION: &str = "Sets or queries a game rule value.";
+const ARG_VALUE: &str = "value";
+
+struct QueryExecutor(&'static str);
+
+#[async_trait]
+impl CommandExecutor for QueryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+        let value = world
+            .game_rules
+            .read()
+            .unwrap()
+            .get_by_name(self.0)
+            .ok_or(CommandError::InvalidRequirement)?;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.gamerule.query",
+                [
+                    TextComponent::text(self.0),
+                    TextComponent::text(value.to_string()),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetExecutor {
+    name: &'static str,
+    default: GameRuleValue,
+}
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+        let value = match self.default {
+            GameRuleValue::Bool(_) => {
+                GameRuleValue::Bool(BoolArgConsumer::find_arg(args, ARG_VALUE)?)
+            }
+            GameRuleValue::Int(_) => {
+                let Ok(value) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_VALUE)?
+                else {
+                    return Err(CommandError::InvalidConsumption(Some(
+                        ARG_VALUE.to_string(),
+                    )));
+                };
+                GameRuleValue::Int(value)
+            }
+        };
+        if !world
+            .game_rules
+            .write()
+            .unwrap()
+            .set_by_name(self.name, value)
+        {
+            return Err(CommandError::InvalidRequirement);
+        }
+
+        // The client keeps these rules itself, so it has to be told about the change
+        let event = if self.name == DO_IMMEDIATE_RESPAWN.name {
+            Some(GameEvent::EnabledRespawnScreen)
+        } else if self.name == DO_LIMITED_CRAFTING.name {
+            Some(GameEvent::LimitedCrafting)
+        } else {
+            None
+        };
+        if let Some(event) = event {
+            let enabled = value == GameRuleValue::Bool(true);
+            world
+                .broadcast_packet_all(&CGameEvent::new(event, f32::from(u8::from(enabled))))
+                .await;
+        }
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.gamerule.set",
+                [
+                    TextComponent::text(self.name),
+                    TextComponent::text(value.to_string()),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut tree = CommandTree::new(NAMES, DESCRIPTION);
+    for &(name, default) in GAME_RULES {
+        let value = match default {
+            GameRuleValue::Bool(_) => argument(ARG_VALUE, BoolArgConsumer),
+            GameRuleValue::Int(_) => argument(
+                ARG_VALUE,
+                BoundedNumArgumentConsumer::<i32>::new().name(ARG_VALUE),
+            ),
+        };
+        tree = tree.then(
+            literal(name)
+                .then(value.execute(SetExecutor { name, default }))
+                .execute(QueryExecutor(name)),
+        );
+    }
+    tree
+}
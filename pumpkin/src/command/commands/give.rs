@@ -3,6 +3,7 @@ use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::click::ClickEvent;
 use pumpkin_util::text::color::{Color, NamedColor};
 use pumpkin_util::text::hover::HoverEvent;
+use pumpkin_world::item::ItemStackComponents;
 
 use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
 use crate::command::args::players::PlayersArgumentConsumer;
@@ -18,6 +19,9 @@ const DESCRIPTION: &str = "Give items to player(s).";
 
 const ARG_ITEM: &str = "item";
 
+/// How many stacks of an item can be given at once
+const MAX_STACKS: i32 = 100;
+
 fn item_count_consumer() -> BoundedNumArgumentConsumer<i32> {
     BoundedNumArgumentConsumer::new()
         .name("count")
@@ -37,7 +41,8 @@ impl CommandExecutor for Executor {
     ) -> Result<(), CommandError> {
         let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
 
-        let (item_name, item) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
+        let (item_name, stack) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
+        let item = &stack.item;
 
         let item_count = match item_count_consumer().find_arg_default_name(args) {
             Err(_) => 1,
@@ -53,8 +58,32 @@ impl CommandExecutor for Executor {
             }
         };
 
+        let max_stack = i32::from(item.components.max_stack_size);
+        if item_count > max_stack * MAX_STACKS {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.give.failed.toomanyitems",
+                    [
+                        TextComponent::text((max_stack * MAX_STACKS).to_string()),
+                        item.translated_name(),
+                    ],
+                ))
+                .await;
+            return Ok(());
+        }
+
         for target in targets {
-            target.give_items(item.clone(), item_count as u32).await;
+            if stack.components == ItemStackComponents::default() {
+                target.give_items(item.clone(), item_count as u32).await;
+                continue;
+            }
+            let mut left = item_count;
+            while left > 0 {
+                let mut part = stack.clone();
+                part.item_count = left.min(max_stack) as u8;
+                left -= i32::from(part.item_count);
+                target.give_stack(part).await;
+            }
         }
         let msg = if targets.len() == 1 {
             TextComponent::translate(
@@ -64,11 +93,7 @@ impl CommandExecutor for Executor {
                     TextComponent::text("[")
                         .add_child(item.translated_name())
                         .add_child(TextComponent::text("]"))
-                        .hover_event(HoverEvent::ShowItem {
-                            id: item_name.to_string().into(),
-                            count: Some(item_count),
-                            tag: None,
-                        }),
+                        .hover_event(HoverEvent::show_item(item_name.to_string(), item_count)),
                     TextComponent::text(targets[0].gameprofile.name.to_string())
                         .hover_event(HoverEvent::show_entity(
                             targets[0].living_entity.entity.entity_uuid.to_string(),
@@ -93,11 +118,7 @@ impl CommandExecutor for Executor {
                     TextComponent::text("[")
                         .add_child(item.translated_name())
                         .add_child(TextComponent::text("]"))
-                        .hover_event(HoverEvent::ShowItem {
-                            id: item_name.to_string().into(),
-                            count: Some(item_count),
-                            tag: None,
-                        }),
+                        .hover_event(HoverEvent::show_item(item_name.to_string(), item_count)),
                     TextComponent::text(targets.len().to_string()),
                 ],
             )
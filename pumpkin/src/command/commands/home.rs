@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::server::homes::{SavedLocation, is_safe};
+
+const NAMES: [&str; 1] = ["home"];
+const DESCRIPTION: &str = "Teleports you to one of your homes.";
+
+pub(super) const ARG_NAME: &str = "name";
+
+/// The home used when no name is given
+pub(super) const DEFAULT_HOME: &str = "home";
+
+/// The name the player gave, or the default one
+pub(super) fn home_name<'a>(
+    args: &'a ConsumedArgs<'_>,
+    named: bool,
+) -> Result<&'a str, CommandError> {
+    if named {
+        SimpleArgConsumer::find_arg(args, ARG_NAME)
+    } else {
+        Ok(DEFAULT_HOME)
+    }
+}
+
+/// Where the player is right now
+pub(super) async fn location_of(player: &Player) -> SavedLocation {
+    let entity = &player.living_entity.entity;
+    let position = entity.pos.load();
+    SavedLocation {
+        dimension: player.world().await.dimension_type.name().to_string(),
+        x: position.x,
+        y: position.y,
+        z: position.z,
+        yaw: entity.yaw.load(),
+        pitch: entity.pitch.load(),
+    }
+}
+
+/// Teleports the player to a saved location, unless the place is not safe to stand at anymore
+pub(super) async fn teleport_to(
+    server: &Server,
+    player: &Player,
+    location: &SavedLocation,
+) -> Result<(), CommandError> {
+    let Some(world) = server.worlds.get_by_name(&location.dimension).cloned() else {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "The dimension {} is not loaded",
+            location.dimension
+        )));
+    };
+    if !is_safe(&world, location.position()).await {
+        return Err(CommandError::GeneralCommandIssue(
+            "It is not safe to teleport there anymore".into(),
+        ));
+    }
+    player
+        .living_entity
+        .entity
+        .teleport(location.position(), location.yaw, location.pitch, world)
+        .await;
+    Ok(())
+}
+
+struct Executor {
+    named: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let name = home_name(args, self.named)?;
+
+        let location = {
+            let homes = server.homes.lock().await;
+            let Some(location) = homes.get(player.gameprofile.id, name) else {
+                let names = homes.names(player.gameprofile.id);
+                return Err(CommandError::GeneralCommandIssue(if names.is_empty() {
+                    "You have no homes, set one with /sethome".into()
+                } else {
+                    format!(
+                        "You have no home called {name}, your homes are: {}",
+                        names.join(", ")
+                    )
+                }));
+            };
+            location.clone()
+        };
+        teleport_to(server, &player, &location).await?;
+
+        sender
+            .send_message(TextComponent::text(format!("Teleported to {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(Executor { named: false })
+            .then(argument(ARG_NAME, SimpleArgConsumer).execute(Executor { named: true })),
+    )
+}
@@ -1,6 +1,5 @@
 use async_trait::async_trait;
 use pumpkin_util::text::TextComponent;
-use pumpkin_util::text::color::NamedColor;
 
 use crate::command::CommandError;
 use crate::command::args::message::MsgArgConsumer;
@@ -9,6 +8,7 @@ use crate::command::args::{Arg, ConsumedArgs};
 use crate::command::tree::CommandTree;
 use crate::command::tree::builder::argument;
 use crate::command::{CommandExecutor, CommandSender};
+use crate::data::audit_log::{AuditAction, audit};
 use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["kick"];
@@ -25,7 +25,7 @@ impl CommandExecutor for Executor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let Some(Arg::Players(targets)) = args.get(&ARG_TARGETS) else {
@@ -39,16 +39,29 @@ impl CommandExecutor for Executor {
 
         for target in targets {
             target.kick(reason.clone()).await;
-            let name = &target.gameprofile.name;
-            let msg = TextComponent::text(format!("Kicked: {name}"));
-            sender.send_message(msg.color_named(NamedColor::Blue)).await;
+            let name = target.gameprofile.name.clone();
+            audit(
+                sender,
+                AuditAction::Kick {
+                    target: name.clone(),
+                    reason: reason.clone().get_text(),
+                },
+            );
+            sender
+                .send_feedback_to_ops(
+                    server,
+                    TextComponent::translate(
+                        "commands.kick.success",
+                        [TextComponent::text(name), reason.clone()],
+                    ),
+                )
+                .await;
         }
 
         Ok(())
     }
 }
 
-// TODO: Permission
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_TARGETS, PlayersArgumentConsumer)
@@ -0,0 +1,102 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use pumpkin_config::advanced_config;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::kits::give_kit;
+
+const NAMES: [&str; 1] = ["kit"];
+const DESCRIPTION: &str = "Gives you a kit, or lists the kits you can claim.";
+
+const ARG_NAME: &str = "name";
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let level = player.permission_lvl.load() as u8;
+        let names = advanced_config()
+            .kits
+            .kits
+            .iter()
+            .filter(|kit| kit.permission_level <= level)
+            .map(|kit| kit.name.as_str())
+            .collect::<Vec<_>>();
+        let message = if names.is_empty() {
+            "There are no kits you can claim".to_string()
+        } else {
+            format!("Kits: {}", names.join(", "))
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+
+        // Kits players may not claim are treated as if they did not exist
+        let kit = advanced_config()
+            .kits
+            .kits
+            .iter()
+            .find(|kit| kit.name == name)
+            .filter(|kit| kit.permission_level <= player.permission_lvl.load() as u8);
+        let Some(kit) = kit else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "There is no kit called {name}"
+            )));
+        };
+
+        let claimed =
+            server
+                .kit_cooldowns
+                .lock()
+                .await
+                .claim(player.gameprofile.id, kit, Instant::now());
+        if let Err(left) = claimed {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "You can claim the kit {name} again in {} seconds",
+                left.as_secs() + 1
+            )));
+        }
+        give_kit(&player, kit).await;
+
+        sender
+            .send_message(TextComponent::text(format!("Claimed the kit {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(ListExecutor)
+            .then(argument(ARG_NAME, SimpleArgConsumer).execute(Executor)),
+    )
+}
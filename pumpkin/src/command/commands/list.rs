@@ -25,7 +25,13 @@ impl CommandExecutor for Executor {
         server: &crate::server::Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let players: Vec<Arc<Player>> = server.get_all_players().await;
+        let viewer = sender.as_player();
+        let players: Vec<Arc<Player>> = server
+            .get_all_players()
+            .await
+            .into_iter()
+            .filter(|player| viewer.as_ref().is_none_or(|viewer| viewer.can_see(player)))
+            .collect();
 
         sender
             .send_message(TextComponent::translate(
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use log::LevelFilter;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    LOGGER_IMPL,
+    command::{
+        CommandError, CommandExecutor, CommandSender,
+        args::{ConsumedArgs, FindArg, simple::SimpleArgConsumer},
+        tree::CommandTree,
+        tree::builder::{argument, literal},
+    },
+};
+
+const NAMES: [&str; 1] = ["loglevel"];
+const DESCRIPTION: &str = "Shows or changes the log levels of modules.";
+
+const ARG_MODULE: &str = "module";
+
+fn logger() -> Result<&'static crate::ReadlineLogWrapper, CommandError> {
+    LOGGER_IMPL
+        .as_ref()
+        .map(|(logger, _)| logger)
+        .ok_or_else(|| CommandError::GeneralCommandIssue("Logging is disabled".to_string()))
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let levels = logger()?.log_levels();
+        sender
+            .send_message(
+                TextComponent::text(format!("Default log level: {}", levels.default_level()))
+                    .color_named(NamedColor::Gold),
+            )
+            .await;
+        for (module, level) in levels.modules() {
+            sender
+                .send_message(TextComponent::text(format!("{module}: {level}")))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Sets the level of the module, `None` removes its override
+struct SetExecutor(Option<LevelFilter>);
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let module = SimpleArgConsumer::find_arg(args, ARG_MODULE)?;
+        let logger = logger()?;
+        logger.set_module_level(module, self.0);
+
+        let message = self.0.map_or_else(
+            || {
+                format!(
+                    "{module} now logs at the default level {}",
+                    logger.log_levels().default_level()
+                )
+            },
+            |level| format!("{module} now logs at {level}"),
+        );
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut module =
+        argument(ARG_MODULE, SimpleArgConsumer).then(literal("reset").execute(SetExecutor(None)));
+    for level in LevelFilter::iter() {
+        module =
+            module.then(literal(level.as_str().to_lowercase()).execute(SetExecutor(Some(level))));
+    }
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(ListExecutor)
+        .then(module)
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use pumpkin_config::chunk::ChunkFormat;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender, args::ConsumedArgs, tree::CommandTree,
+        tree::builder::literal,
+    },
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["migrate"];
+
+const DESCRIPTION: &str = "Migrates the world to another chunk format while the server is running.";
+
+struct Executor {
+    format: ChunkFormat,
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = match sender {
+            CommandSender::Player(player) => player.living_entity.entity.world.read().await.clone(),
+            // TODO: Maybe ask player for world, or get the current world
+            _ => server.worlds.default_world().clone(),
+        };
+
+        let level = world.level.clone();
+        if level.chunk_format() == self.format {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "The world is already stored in the {:?} format",
+                self.format
+            )));
+        }
+        if level.is_migrating() {
+            return Err(CommandError::GeneralCommandIssue(
+                "A migration is already running for this world".to_string(),
+            ));
+        }
+
+        let format = self.format;
+        tokio::spawn(async move {
+            if let Err(err) = level.migrate_chunk_format(format).await {
+                log::error!("Failed to migrate the world to {format:?}: {err}");
+            }
+        });
+
+        sender
+            .send_message(
+                TextComponent::text(format!(
+                    "Started migrating the world to the {:?} format, see the console for progress",
+                    self.format
+                ))
+                .color_named(NamedColor::Gold),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("anvil").execute(Executor {
+            format: ChunkFormat::Anvil,
+        }))
+        .then(literal("linear").execute(Executor {
+            format: ChunkFormat::Linear,
+        }))
+}
@@ -1,45 +1,80 @@
+use pumpkin_config::advanced_config;
 use pumpkin_util::PermissionLvl;
 
 use super::dispatcher::CommandDispatcher;
 
+mod auditlog;
 mod ban;
 mod banip;
 mod banlist;
 mod bossbar;
+mod chunkinfo;
+mod claim;
 mod clear;
 mod damage;
 pub mod defaultgamemode;
+mod delhome;
+mod delwarp;
 mod deop;
 mod effect;
 mod experience;
 mod fill;
+mod fillbiome;
 mod gamemode;
+mod gamerule;
 mod give;
 mod help;
+mod home;
 mod kick;
 mod kill;
+mod kit;
 mod list;
+mod loglevel;
 mod me;
+mod migrate;
 mod msg;
+mod mute;
 mod op;
 mod pardon;
 mod pardonip;
 mod particle;
+mod perf;
 mod playsound;
 mod plugin;
 mod plugins;
+mod ptime;
 mod pumpkin;
+mod pweather;
+mod region;
+mod reloadconfig;
 mod say;
+mod scoreboard;
 mod seed;
+mod server;
 mod setblock;
+mod sethome;
+mod setwarp;
+mod slowchat;
+mod spreadplayers;
 mod stop;
 mod stopsound;
 mod summon;
+mod team;
 mod teleport;
+mod tick;
 mod time;
 mod title;
+mod tpa;
+mod tpaccept;
+mod tpdeny;
 mod transfer;
+mod trigger;
+mod trust;
+mod unmute;
+mod vanish;
+mod warp;
 mod weather;
+mod whitelist;
 mod worldborder;
 
 #[must_use]
@@ -53,6 +88,31 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(transfer::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(me::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(msg::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(trigger::init_command_tree(), PermissionLvl::Zero);
+    if advanced_config().claims.enabled {
+        dispatcher.register(claim::init_command_tree(), PermissionLvl::Zero);
+        dispatcher.register(trust::init_command_tree(), PermissionLvl::Zero);
+        dispatcher.register(region::init_command_tree(), PermissionLvl::Zero);
+    }
+    if advanced_config().tpa.enabled {
+        dispatcher.register(tpa::init_command_tree(), PermissionLvl::Zero);
+        dispatcher.register(tpaccept::init_command_tree(), PermissionLvl::Zero);
+        dispatcher.register(tpdeny::init_command_tree(), PermissionLvl::Zero);
+    }
+    if advanced_config().homes.enabled {
+        dispatcher.register(home::init_command_tree(), PermissionLvl::Zero);
+        dispatcher.register(sethome::init_command_tree(), PermissionLvl::Zero);
+        dispatcher.register(delhome::init_command_tree(), PermissionLvl::Zero);
+    }
+    if advanced_config().homes.warps_enabled {
+        dispatcher.register(warp::init_command_tree(), PermissionLvl::Zero);
+    }
+    if advanced_config().kits.enabled {
+        dispatcher.register(kit::init_command_tree(), PermissionLvl::Zero);
+    }
+    if advanced_config().transfer.enabled {
+        dispatcher.register(server::init_command_tree(), PermissionLvl::Zero);
+    }
     // Two
     dispatcher.register(kill::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(worldborder::init_command_tree(), PermissionLvl::Two);
@@ -63,7 +123,11 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(clear::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(setblock::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(seed::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(scoreboard::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(team::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(fill::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(fillbiome::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(spreadplayers::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(playsound::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(title::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(summon::init_command_tree(), PermissionLvl::Two);
@@ -76,19 +140,38 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(gamemode::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(stopsound::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(defaultgamemode::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(vanish::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(ptime::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(pweather::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(gamerule::init_command_tree(), PermissionLvl::Two);
+    if advanced_config().homes.warps_enabled {
+        dispatcher.register(setwarp::init_command_tree(), PermissionLvl::Two);
+        dispatcher.register(delwarp::init_command_tree(), PermissionLvl::Two);
+    }
     // Three
     dispatcher.register(op::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(deop::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(kick::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(mute::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(unmute::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(slowchat::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(plugin::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(plugins::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(chunkinfo::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(perf::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(tick::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(ban::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(banip::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(banlist::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(pardon::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(pardonip::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(whitelist::init_command_tree(), PermissionLvl::Three);
     // Four
     dispatcher.register(stop::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(migrate::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(auditlog::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(loglevel::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(reloadconfig::init_command_tree(), PermissionLvl::Four);
 
     dispatcher
 }
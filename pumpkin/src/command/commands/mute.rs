@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+
+use crate::command::CommandError;
+use crate::command::args::message::MsgArgConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::argument;
+use crate::command::{CommandExecutor, CommandSender};
+use crate::data::SaveJSONConfiguration;
+use crate::data::muted_player_data::{MUTED_PLAYER_LIST, MutedPlayerEntry};
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["mute"];
+const DESCRIPTION: &str = "Stops the target players from chatting.";
+
+const ARG_TARGETS: &str = "targets";
+
+const ARG_REASON: &str = "reason";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Players(targets)) = args.get(&ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+
+        let reason = match args.get(&ARG_REASON) {
+            Some(Arg::Msg(reason)) => reason.clone(),
+            _ => "Muted by an operator.".to_string(),
+        };
+
+        let mut muted_players = MUTED_PLAYER_LIST.write().await;
+        for target in targets {
+            let name = &target.gameprofile.name;
+            if muted_players.get_entry(&target.gameprofile.id).is_some() {
+                sender
+                    .send_message(
+                        TextComponent::text(format!("{name} is already muted"))
+                            .color_named(NamedColor::Red),
+                    )
+                    .await;
+                continue;
+            }
+
+            muted_players.muted_players.push(MutedPlayerEntry::new(
+                &target.gameprofile,
+                sender.to_string(),
+                reason.clone(),
+            ));
+            target
+                .send_system_message(
+                    &TextComponent::text(format!("You have been muted: {reason}"))
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            sender
+                .send_message(TextComponent::text(format!("Muted {name}: {reason}")))
+                .await;
+        }
+        muted_players.save();
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGETS, PlayersArgumentConsumer)
+            .execute(Executor)
+            .then(argument(ARG_REASON, MsgArgConsumer).execute(Executor)),
+    )
+}
@@ -5,7 +5,11 @@ use crate::{
         tree::CommandTree,
         tree::builder::argument,
     },
-    data::{SaveJSONConfiguration, op_data::OPERATOR_CONFIG},
+    data::{
+        SaveJSONConfiguration,
+        audit_log::{AuditAction, audit},
+        op_data::OPERATOR_CONFIG,
+    },
 };
 use CommandError::InvalidConsumption;
 use async_trait::async_trait;
@@ -61,10 +65,15 @@ impl CommandExecutor for Executor {
             }
 
             config.save();
+            audit(
+                sender,
+                AuditAction::Op {
+                    target: player.gameprofile.name.clone(),
+                    level: new_level,
+                },
+            );
 
-            player
-                .set_permission_lvl(new_level, &server.command_dispatcher)
-                .await;
+            player.set_permission_lvl(new_level, server).await;
 
             let player_name = &player.gameprofile.name;
             sender
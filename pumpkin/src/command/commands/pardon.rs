@@ -5,7 +5,11 @@ use crate::{
         tree::CommandTree,
         tree::builder::argument,
     },
-    data::{SaveJSONConfiguration, banned_player_data::BANNED_PLAYER_LIST},
+    data::{
+        SaveJSONConfiguration,
+        audit_log::{AuditAction, audit},
+        banned_player_data::BANNED_PLAYER_LIST,
+    },
 };
 use CommandError::InvalidConsumption;
 use async_trait::async_trait;
@@ -47,6 +51,13 @@ impl CommandExecutor for Executor {
         }
 
         lock.save();
+        drop(lock);
+        audit(
+            sender,
+            AuditAction::Pardon {
+                target: target.clone(),
+            },
+        );
 
         sender
             .send_message(TextComponent::translate(
@@ -7,7 +7,11 @@ use crate::{
         tree::CommandTree,
         tree::builder::argument,
     },
-    data::{SaveJSONConfiguration, banned_ip_data::BANNED_IP_LIST},
+    data::{
+        SaveJSONConfiguration,
+        audit_log::{AuditAction, audit},
+        banned_ip_data::BANNED_IP_LIST,
+    },
 };
 use CommandError::InvalidConsumption;
 use async_trait::async_trait;
@@ -51,6 +55,13 @@ impl CommandExecutor for Executor {
         }
 
         lock.save();
+        drop(lock);
+        audit(
+            sender,
+            AuditAction::PardonIp {
+                target: ip.to_string(),
+            },
+        );
 
         sender
             .send_message(TextComponent::translate(
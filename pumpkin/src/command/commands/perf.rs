@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use pumpkin_protocol::buffer_pool::BUFFER_POOL;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender, args::ConsumedArgs, tree::CommandTree,
+        tree::builder::literal,
+    },
+    net::outgoing::WRITE_STATS,
+    server::{
+        Server,
+        memory::{self, MIB, WorldMemory},
+    },
+};
+
+const NAMES: [&str; 1] = ["perf"];
+
+const DESCRIPTION: &str = "Shows what the server spends its resources on.";
+
+fn mib(bytes: usize) -> String {
+    format!("{:.1} MiB", bytes as f64 / MIB as f64)
+}
+
+struct MemoryExecutor;
+
+#[async_trait]
+impl CommandExecutor for MemoryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let mut total = 0;
+        for world in server.worlds.iter() {
+            let usage = WorldMemory::of(world).await;
+            total += usage.total_bytes();
+            sender
+                .send_message(TextComponent::text(format!(
+                    "{}: {} chunks ({}), {} entities and {} players ({}), region cache {}",
+                    world.dimension_type.name(),
+                    usage.level.chunks,
+                    mib(usage.level.chunk_bytes),
+                    usage.entities,
+                    usage.players,
+                    mib(usage.entity_bytes()),
+                    mib(usage.level.file_cache_bytes)
+                )))
+                .await;
+        }
+
+        let message = memory::soft_cap().map_or_else(
+            || format!("Estimated total: {}", mib(total)),
+            |cap| {
+                format!(
+                    "Estimated total: {} of the {} soft cap",
+                    mib(total),
+                    mib(cap)
+                )
+            },
+        );
+        sender
+            .send_message(TextComponent::text(message).color_named(NamedColor::Gold))
+            .await;
+        Ok(())
+    }
+}
+
+struct NetworkExecutor;
+
+#[async_trait]
+impl CommandExecutor for NetworkExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        sender
+            .send_message(TextComponent::text(format!(
+                "Sent {} in {} writes from {} batches, {:.0} bytes per write",
+                mib(WRITE_STATS.bytes() as usize),
+                WRITE_STATS.writes(),
+                WRITE_STATS.batches(),
+                WRITE_STATS.bytes_per_write()
+            )))
+            .await;
+        sender
+            .send_message(TextComponent::text(format!(
+                "Reused {} buffers and allocated {}, {} are pooled",
+                BUFFER_POOL.reused(),
+                BUFFER_POOL.allocated(),
+                BUFFER_POOL.pooled()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("memory").execute(MemoryExecutor))
+        .then(literal("network").execute(NetworkExecutor))
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender,
+    args::{
+        Arg, ConsumedArgs, FindArg, players::PlayersArgumentConsumer, time::TimeArgumentConsumer,
+    },
+    tree::{
+        CommandTree,
+        builder::{argument, literal, require},
+    },
+};
+use crate::entity::player::Player;
+use crate::world::time::ClientTime;
+
+const NAMES: [&str; 1] = ["ptime"];
+const DESCRIPTION: &str = "Changes the time only some players see.";
+
+const ARG_TIME: &str = "time";
+const ARG_TARGETS: &str = "targets";
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Set,
+    Offset,
+    Reset,
+}
+
+/// The players given as argument, or the sender when there are none
+fn targets(sender: &CommandSender<'_>, args: &ConsumedArgs<'_>) -> Vec<Arc<Player>> {
+    if let Some(Arg::Players(targets)) = args.get(ARG_TARGETS) {
+        targets.clone()
+    } else {
+        sender.as_player().into_iter().collect()
+    }
+}
+
+struct Executor(Mode);
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let time = match self.0 {
+            Mode::Reset => None,
+            mode => {
+                let Ok(ticks) = TimeArgumentConsumer::find_arg(args, ARG_TIME) else {
+                    return Err(CommandError::InvalidConsumption(Some(ARG_TIME.into())));
+                };
+                let ticks = i64::from(ticks);
+                Some(if matches!(mode, Mode::Set) {
+                    ClientTime::Fixed(ticks)
+                } else {
+                    ClientTime::Offset(ticks)
+                })
+            }
+        };
+
+        let targets = targets(sender, args);
+        for target in &targets {
+            target.set_client_time(time).await;
+        }
+
+        let count = targets.len();
+        let message = match time {
+            None => format!("Reset the time of {count} player(s) to the world time"),
+            Some(ClientTime::Fixed(ticks)) => {
+                format!("Set the time of {count} player(s) to {ticks}")
+            }
+            Some(ClientTime::Offset(ticks)) => {
+                format!("Set the time of {count} player(s) to {ticks} ticks ahead of the world")
+            }
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("set").then(
+                argument(ARG_TIME, TimeArgumentConsumer)
+                    .then(require(|sender| sender.is_player()).execute(Executor(Mode::Set)))
+                    .then(
+                        argument(ARG_TARGETS, PlayersArgumentConsumer).execute(Executor(Mode::Set)),
+                    ),
+            ),
+        )
+        .then(
+            literal("offset").then(
+                argument(ARG_TIME, TimeArgumentConsumer)
+                    .then(require(|sender| sender.is_player()).execute(Executor(Mode::Offset)))
+                    .then(
+                        argument(ARG_TARGETS, PlayersArgumentConsumer)
+                            .execute(Executor(Mode::Offset)),
+                    ),
+            ),
+        )
+        .then(
+            literal("reset")
+                .then(require(|sender| sender.is_player()).execute(Executor(Mode::Reset)))
+                .then(
+                    argument(ARG_TARGETS, PlayersArgumentConsumer).execute(Executor(Mode::Reset)),
+                ),
+        )
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender,
+    args::{Arg, ConsumedArgs, players::PlayersArgumentConsumer},
+    tree::{
+        CommandTree,
+        builder::{argument, literal, require},
+    },
+};
+use crate::entity::player::Player;
+use crate::world::weather::ClientWeather;
+
+const NAMES: [&str; 1] = ["pweather"];
+const DESCRIPTION: &str = "Changes the weather only some players see.";
+
+const ARG_TARGETS: &str = "targets";
+
+/// The players given as argument, or the sender when there are none
+fn targets(sender: &CommandSender<'_>, args: &ConsumedArgs<'_>) -> Vec<Arc<Player>> {
+    if let Some(Arg::Players(targets)) = args.get(ARG_TARGETS) {
+        targets.clone()
+    } else {
+        sender.as_player().into_iter().collect()
+    }
+}
+
+/// `None` resets the players to the world weather
+struct Executor(Option<ClientWeather>);
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = targets(sender, args);
+        for target in &targets {
+            target.set_client_weather(self.0).await;
+        }
+
+        let count = targets.len();
+        let message = self.0.map_or_else(
+            || format!("Reset the weather of {count} player(s) to the world weather"),
+            |weather| {
+                let weather = format!("{weather:?}").to_lowercase();
+                format!("Set the weather of {count} player(s) to {weather}")
+            },
+        );
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("clear")
+                .then(
+                    require(|sender| sender.is_player())
+                        .execute(Executor(Some(ClientWeather::Clear))),
+                )
+                .then(
+                    argument(ARG_TARGETS, PlayersArgumentConsumer)
+                        .execute(Executor(Some(ClientWeather::Clear))),
+                ),
+        )
+        .then(
+            literal("rain")
+                .then(
+                    require(|sender| sender.is_player())
+                        .execute(Executor(Some(ClientWeather::Rain))),
+                )
+                .then(
+                    argument(ARG_TARGETS, PlayersArgumentConsumer)
+                        .execute(Executor(Some(ClientWeather::Rain))),
+                ),
+        )
+        .then(
+            literal("thunder")
+                .then(
+                    require(|sender| sender.is_player())
+                        .execute(Executor(Some(ClientWeather::Thunder))),
+                )
+                .then(
+                    argument(ARG_TARGETS, PlayersArgumentConsumer)
+                        .execute(Executor(Some(ClientWeather::Thunder))),
+                ),
+        )
+        .then(
+            literal("reset")
+                .then(require(|sender| sender.is_player()).execute(Executor(None)))
+                .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(Executor(None))),
+        )
+}
@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use pumpkin_util::PermissionLvl;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::claims::{ClaimFlag, Claims, Region};
+
+const NAMES: [&str; 1] = ["region"];
+const DESCRIPTION: &str = "Shows, changes the flags of or removes claimed regions.";
+
+const ARG_NAME: &str = "name";
+const ARG_FLAG: &str = "flag";
+const ARG_VALUE: &str = "value";
+
+/// The region with the name if the sender may change it, which only its owner and operators can
+pub(super) fn managed_region<'c>(
+    claims: &'c mut Claims,
+    name: &str,
+    sender: &CommandSender<'_>,
+) -> Result<&'c mut Region, CommandError> {
+    let Some(region) = claims.get_mut(name) else {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "There is no region named {name}"
+        )));
+    };
+    let is_owner = sender
+        .as_player()
+        .is_some_and(|player| player.gameprofile.id == region.owner);
+    if !is_owner && !sender.has_permission_lvl(PermissionLvl::Two) {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "You don't own {name}"
+        )));
+    }
+    Ok(region)
+}
+
+struct InfoExecutor;
+
+#[async_trait]
+impl CommandExecutor for InfoExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let position = player.living_entity.entity.block_pos.load();
+        let world = player.world().await;
+        let claims = world.claims.lock().await;
+        let Some((name, region)) = claims.region_at(&position) else {
+            return Err(CommandError::GeneralCommandIssue(
+                "You are not in a claimed region".into(),
+            ));
+        };
+
+        let members = region.members.values().cloned().collect::<Vec<_>>();
+        let flags = ClaimFlag::ALL
+            .map(|flag| format!("{}={}", flag.name(), region.flag(flag)))
+            .join(", ");
+        let message = format!(
+            "{name} is claimed by {}, from {:?} to {:?}\nTrusted: {}\nFlags: {flags}",
+            region.owner_name,
+            region.min,
+            region.max,
+            if members.is_empty() {
+                String::from("nobody")
+            } else {
+                members.join(", ")
+            },
+        );
+        drop(claims);
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct FlagExecutor;
+
+#[async_trait]
+impl CommandExecutor for FlagExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+        let flag_name = SimpleArgConsumer::find_arg(args, ARG_FLAG)?;
+        let value = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+        let Some(flag) = ClaimFlag::from_name(flag_name) else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Unknown flag {flag_name}, the flags are build, interact and pvp"
+            )));
+        };
+
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+        managed_region(&mut *world.claims.lock().await, name, sender)?
+            .flags
+            .insert(flag, value);
+        world.save_claims().await;
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Set {flag_name} to {value} in {name}"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct RemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+        {
+            let mut claims = world.claims.lock().await;
+            managed_region(&mut claims, name, sender)?;
+            claims.remove(name)
+        };
+        world.save_claims().await;
+
+        sender
+            .send_message(TextComponent::text(format!("Removed {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("info").then(require(|sender| sender.is_player()).execute(InfoExecutor)))
+        .then(
+            literal("flag").then(
+                argument(ARG_NAME, SimpleArgConsumer).then(
+                    argument(ARG_FLAG, SimpleArgConsumer)
+                        .then(argument(ARG_VALUE, BoolArgConsumer).execute(FlagExecutor)),
+                ),
+            ),
+        )
+        .then(literal("remove").then(argument(ARG_NAME, SimpleArgConsumer).execute(RemoveExecutor)))
+}
@@ -0,0 +1,43 @@
+use std::env;
+
+use async_trait::async_trait;
+use pumpkin_config::CONFIG_MANAGER;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["reload-config"];
+
+const DESCRIPTION: &str = "Reloads features.toml. Some settings only apply after a restart.";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let exec_dir =
+            env::current_dir().map_err(|err| CommandError::GeneralCommandIssue(err.to_string()))?;
+        CONFIG_MANAGER
+            .reload(&exec_dir)
+            .map_err(|err| CommandError::GeneralCommandIssue(err.to_string()))?;
+
+        sender
+            .send_message(
+                TextComponent::text("Reloaded features.toml").color_named(NamedColor::Green),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
+}
@@ -0,0 +1,702 @@
+use async_trait::async_trait;
+use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+use pumpkin_protocol::client::play::RenderType;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender,
+        args::{
+            ConsumedArgs, FindArg, bounded_num::BoundedNumArgumentConsumer,
+            entity::EntityArgumentConsumer, objective::ObjectiveArgumentConsumer,
+            players::PlayersArgumentConsumer, simple::SimpleArgConsumer,
+            textcomponent::TextComponentArgConsumer,
+        },
+        tree::{
+            CommandTree,
+            builder::{NonLeafNodeBuilder, argument, literal},
+        },
+    },
+    server::{
+        Server,
+        scoreboard::{
+            ObjectiveCriteria, Scoreboard, ScoreboardObjective, ScoreboardScore, slot_name,
+        },
+    },
+};
+
+const NAMES: [&str; 1] = ["scoreboard"];
+const DESCRIPTION: &str = "Manages scoreboard objectives and players.";
+
+const ARG_OBJECTIVE: &str = "objective";
+const ARG_DISPLAY_NAME: &str = "displayName";
+const ARG_TARGET: &str = "target";
+const ARG_TARGETS: &str = "targets";
+const ARG_SCORE: &str = "score";
+
+fn score_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_SCORE)
+}
+
+/// Scores can only be added or removed in positive steps, like in vanilla
+fn amount_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_SCORE).min(0)
+}
+
+fn failure(key: &'static str) -> TextComponent {
+    TextComponent::translate(key, []).color_named(NamedColor::Red)
+}
+
+/// The display name of the objective, which has to exist
+fn objective_text(scoreboard: &Scoreboard, objective: &str) -> TextComponent {
+    scoreboard
+        .display_name(objective)
+        .cloned()
+        .unwrap_or_else(|| TextComponent::text(objective.to_string()))
+}
+
+/// Sends that the objective does not exist, and returns false then
+async fn check_objective(
+    sender: &mut CommandSender<'_>,
+    scoreboard: &Scoreboard,
+    objective: &str,
+) -> bool {
+    if scoreboard.has_objective(objective) {
+        return true;
+    }
+    sender
+        .send_message(
+            TextComponent::translate(
+                "arguments.objective.notFound",
+                [TextComponent::text(objective.to_string())],
+            )
+            .color_named(NamedColor::Red),
+        )
+        .await;
+    false
+}
+
+struct ObjectivesListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ObjectivesListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+        let objectives: Vec<_> = scoreboard
+            .objectives()
+            .map(|(_, display_name)| display_name.clone())
+            .collect();
+        drop(scoreboard);
+
+        let message = if objectives.is_empty() {
+            TextComponent::translate("commands.scoreboard.objectives.list.empty", [])
+        } else {
+            let count = TextComponent::text(objectives.len().to_string());
+            let mut list = TextComponent::text("");
+            for (i, objective) in objectives.into_iter().enumerate() {
+                if i > 0 {
+                    list = list.add_text(", ");
+                }
+                list = list.add_child(objective);
+            }
+            TextComponent::translate("commands.scoreboard.objectives.list.success", [count, list])
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct ObjectivesAddExecutor(ObjectiveCriteria);
+
+#[async_trait]
+impl CommandExecutor for ObjectivesAddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE)?;
+        let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)
+            .unwrap_or_else(|_| TextComponent::text(name.to_string()));
+
+        let objective =
+            ScoreboardObjective::new(name, display_name.clone(), RenderType::Integer, None)
+                .with_criteria(self.0);
+        let added = server
+            .scoreboard
+            .lock()
+            .await
+            .add_objective(server, objective)
+            .await;
+
+        let message = if added {
+            TextComponent::translate("commands.scoreboard.objectives.add.success", [display_name])
+        } else {
+            failure("commands.scoreboard.objectives.add.duplicate")
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct ObjectivesRemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for ObjectivesRemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = ObjectiveArgumentConsumer::find_arg(args, ARG_OBJECTIVE)?;
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        if !check_objective(sender, &scoreboard, name).await {
+            return Ok(());
+        }
+        let display_name = objective_text(&scoreboard, name);
+        scoreboard.remove_objective(server, name).await;
+        drop(scoreboard);
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.objectives.remove.success",
+                [display_name],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetDisplayExecutor(ScoreboardDisplaySlot);
+
+#[async_trait]
+impl CommandExecutor for SetDisplayExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let objective = ObjectiveArgumentConsumer::find_arg(args, ARG_OBJECTIVE).ok();
+        let slot = TextComponent::text(slot_name(self.0).unwrap_or_default().to_string());
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        if let Some(objective) = objective {
+            if !check_objective(sender, &scoreboard, objective).await {
+                return Ok(());
+            }
+        }
+        let message = match objective {
+            _ if scoreboard.displayed(self.0) == objective => {
+                if objective.is_some() {
+                    failure("commands.scoreboard.objectives.display.alreadySet")
+                } else {
+                    failure("commands.scoreboard.objectives.display.alreadyEmpty")
+                }
+            }
+            Some(objective) => {
+                scoreboard
+                    .set_display_slot(server, self.0, Some(objective))
+                    .await;
+                TextComponent::translate(
+                    "commands.scoreboard.objectives.display.set",
+                    [slot, objective_text(&scoreboard, objective)],
+                )
+            }
+            None => {
+                scoreboard.set_display_slot(server, self.0, None).await;
+                TextComponent::translate("commands.scoreboard.objectives.display.cleared", [slot])
+            }
+        };
+        drop(scoreboard);
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Modify {
+    DisplayName,
+    RenderType(RenderType),
+}
+
+struct ModifyExecutor(Modify);
+
+#[async_trait]
+impl CommandExecutor for ModifyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = ObjectiveArgumentConsumer::find_arg(args, ARG_OBJECTIVE)?;
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        if !check_objective(sender, &scoreboard, name).await {
+            return Ok(());
+        }
+        let message = match self.0 {
+            Modify::DisplayName => {
+                let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)?;
+                scoreboard
+                    .set_display_name(server, name, display_name.clone())
+                    .await;
+                TextComponent::translate(
+                    "commands.scoreboard.objectives.modify.displayname",
+                    [TextComponent::text(name.to_string()), display_name],
+                )
+            }
+            Modify::RenderType(render_type) => {
+                scoreboard.set_render_type(server, name, render_type).await;
+                TextComponent::translate(
+                    "commands.scoreboard.objectives.modify.rendertype",
+                    [objective_text(&scoreboard, name)],
+                )
+            }
+        };
+        drop(scoreboard);
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct PlayersListExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+        let entities: Vec<_> = scoreboard.entities().into_iter().collect();
+        let message = if entities.is_empty() {
+            TextComponent::translate("commands.scoreboard.players.list.empty", [])
+        } else {
+            TextComponent::translate(
+                "commands.scoreboard.players.list.success",
+                [
+                    TextComponent::text(entities.len().to_string()),
+                    TextComponent::text(entities.join(", ")),
+                ],
+            )
+        };
+        drop(scoreboard);
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct PlayersListEntityExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersListEntityExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let name = &target.gameprofile.name;
+
+        let scoreboard = server.scoreboard.lock().await;
+        let scores: Vec<_> = scoreboard
+            .scores_of(name)
+            .into_iter()
+            .map(|(objective, score)| (objective_text(&scoreboard, objective), score))
+            .collect();
+        drop(scoreboard);
+
+        if scores.is_empty() {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.scoreboard.players.list.entity.empty",
+                    [TextComponent::text(name.clone())],
+                ))
+                .await;
+            return Ok(());
+        }
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.players.list.entity.success",
+                [
+                    TextComponent::text(name.clone()),
+                    TextComponent::text(scores.len().to_string()),
+                ],
+            ))
+            .await;
+        for (objective, score) in scores {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.scoreboard.players.list.entity.entry",
+                    [objective, TextComponent::text(score.to_string())],
+                ))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct PlayersGetExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersGetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let objective = ObjectiveArgumentConsumer::find_arg(args, ARG_OBJECTIVE)?;
+        let name = TextComponent::text(target.gameprofile.name.clone());
+
+        let scoreboard = server.scoreboard.lock().await;
+        if !check_objective(sender, &scoreboard, objective).await {
+            return Ok(());
+        }
+        let display_name = objective_text(&scoreboard, objective);
+        let message = match scoreboard.get_score(&target.gameprofile.name, objective) {
+            Some(score) => TextComponent::translate(
+                "commands.scoreboard.players.get.success",
+                [name, TextComponent::text(score.to_string()), display_name],
+            ),
+            None => TextComponent::translate(
+                "commands.scoreboard.players.get.null",
+                [TextComponent::text(objective.to_string()), name],
+            )
+            .color_named(NamedColor::Red),
+        };
+        drop(scoreboard);
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Set,
+    Add,
+    Remove,
+}
+
+impl Operation {
+    const fn key(self, single: bool) -> &'static str {
+        match (self, single) {
+            (Self::Set, true) => "commands.scoreboard.players.set.success.single",
+            (Self::Set, false) => "commands.scoreboard.players.set.success.multiple",
+            (Self::Add, true) => "commands.scoreboard.players.add.success.single",
+            (Self::Add, false) => "commands.scoreboard.players.add.success.multiple",
+            (Self::Remove, true) => "commands.scoreboard.players.remove.success.single",
+            (Self::Remove, false) => "commands.scoreboard.players.remove.success.multiple",
+        }
+    }
+}
+
+struct PlayersScoreExecutor(Operation);
+
+#[async_trait]
+impl CommandExecutor for PlayersScoreExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let objective = ObjectiveArgumentConsumer::find_arg(args, ARG_OBJECTIVE)?;
+        let Ok(value) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_SCORE)? else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_SCORE.into())));
+        };
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        if !check_objective(sender, &scoreboard, objective).await {
+            return Ok(());
+        }
+        let mut last_score = value;
+        for target in targets {
+            let name = &target.gameprofile.name;
+            last_score = match self.0 {
+                Operation::Set => {
+                    scoreboard
+                        .update_score(
+                            server,
+                            ScoreboardScore::new(name, objective, value.into(), None, None),
+                        )
+                        .await;
+                    value
+                }
+                Operation::Add => scoreboard
+                    .add_score(server, name, objective, value)
+                    .await
+                    .unwrap_or_default(),
+                Operation::Remove => scoreboard
+                    .add_score(server, name, objective, value.wrapping_neg())
+                    .await
+                    .unwrap_or_default(),
+            };
+        }
+        let display_name = objective_text(&scoreboard, objective);
+        drop(scoreboard);
+
+        let value = TextComponent::text(value.to_string());
+        let message = match (self.0, targets) {
+            (Operation::Set, [target]) => TextComponent::translate(
+                self.0.key(true),
+                [
+                    display_name,
+                    TextComponent::text(target.gameprofile.name.clone()),
+                    value,
+                ],
+            ),
+            (Operation::Set, _) => TextComponent::translate(
+                self.0.key(false),
+                [
+                    display_name,
+                    TextComponent::text(targets.len().to_string()),
+                    value,
+                ],
+            ),
+            (_, [target]) => TextComponent::translate(
+                self.0.key(true),
+                [
+                    value,
+                    display_name,
+                    TextComponent::text(target.gameprofile.name.clone()),
+                    TextComponent::text(last_score.to_string()),
+                ],
+            ),
+            _ => TextComponent::translate(
+                self.0.key(false),
+                [
+                    value,
+                    display_name,
+                    TextComponent::text(targets.len().to_string()),
+                ],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct PlayersResetExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersResetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let objective = ObjectiveArgumentConsumer::find_arg(args, ARG_OBJECTIVE).ok();
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        if let Some(objective) = objective {
+            if !check_objective(sender, &scoreboard, objective).await {
+                return Ok(());
+            }
+        }
+        for target in targets {
+            scoreboard
+                .reset_score(server, &target.gameprofile.name, objective)
+                .await;
+        }
+        let display_name = objective.map(|objective| objective_text(&scoreboard, objective));
+        drop(scoreboard);
+
+        let entities = match targets {
+            [target] => TextComponent::text(target.gameprofile.name.clone()),
+            _ => TextComponent::text(targets.len().to_string()),
+        };
+        let single = targets.len() == 1;
+        let message = match display_name {
+            Some(display_name) if single => TextComponent::translate(
+                "commands.scoreboard.players.reset.specific.single",
+                [display_name, entities],
+            ),
+            Some(display_name) => TextComponent::translate(
+                "commands.scoreboard.players.reset.specific.multiple",
+                [display_name, entities],
+            ),
+            None if single => {
+                TextComponent::translate("commands.scoreboard.players.reset.all.single", [entities])
+            }
+            None => TextComponent::translate(
+                "commands.scoreboard.players.reset.all.multiple",
+                [entities],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct PlayersEnableExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersEnableExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let objective = ObjectiveArgumentConsumer::find_arg(args, ARG_OBJECTIVE)?;
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        if !check_objective(sender, &scoreboard, objective).await {
+            return Ok(());
+        }
+        if scoreboard.criteria(objective) != Some(ObjectiveCriteria::Trigger) {
+            drop(scoreboard);
+            sender
+                .send_message(failure("commands.scoreboard.players.enable.invalid"))
+                .await;
+            return Ok(());
+        }
+        for target in targets {
+            scoreboard.enable_trigger(&target.gameprofile.name, objective);
+        }
+        let display_name = objective_text(&scoreboard, objective);
+        drop(scoreboard);
+
+        let message = match targets {
+            [target] => TextComponent::translate(
+                "commands.scoreboard.players.enable.success.single",
+                [
+                    display_name,
+                    TextComponent::text(target.gameprofile.name.clone()),
+                ],
+            ),
+            _ => TextComponent::translate(
+                "commands.scoreboard.players.enable.success.multiple",
+                [display_name, TextComponent::text(targets.len().to_string())],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+fn objectives_tree() -> NonLeafNodeBuilder {
+    let mut criteria = argument(ARG_OBJECTIVE, SimpleArgConsumer);
+    for criterion in [ObjectiveCriteria::Dummy, ObjectiveCriteria::Trigger] {
+        criteria = criteria.then(
+            literal(criterion.name())
+                .execute(ObjectivesAddExecutor(criterion))
+                .then(
+                    argument(ARG_DISPLAY_NAME, TextComponentArgConsumer)
+                        .execute(ObjectivesAddExecutor(criterion)),
+                ),
+        );
+    }
+
+    let mut set_display = literal("setdisplay");
+    for slot in [
+        ScoreboardDisplaySlot::List,
+        ScoreboardDisplaySlot::Sidebar,
+        ScoreboardDisplaySlot::BelowName,
+    ] {
+        set_display = set_display.then(
+            literal(slot_name(slot).unwrap_or_default())
+                .execute(SetDisplayExecutor(slot))
+                .then(
+                    argument(ARG_OBJECTIVE, ObjectiveArgumentConsumer)
+                        .execute(SetDisplayExecutor(slot)),
+                ),
+        );
+    }
+
+    let modify = argument(ARG_OBJECTIVE, ObjectiveArgumentConsumer)
+        .then(
+            literal("displayname").then(
+                argument(ARG_DISPLAY_NAME, TextComponentArgConsumer)
+                    .execute(ModifyExecutor(Modify::DisplayName)),
+            ),
+        )
+        .then(
+            literal("rendertype")
+                .then(
+                    literal("integer")
+                        .execute(ModifyExecutor(Modify::RenderType(RenderType::Integer))),
+                )
+                .then(
+                    literal("hearts")
+                        .execute(ModifyExecutor(Modify::RenderType(RenderType::Hearts))),
+                ),
+        );
+
+    literal("objectives")
+        .then(literal("list").execute(ObjectivesListExecutor))
+        .then(literal("add").then(criteria))
+        .then(literal("remove").then(
+            argument(ARG_OBJECTIVE, ObjectiveArgumentConsumer).execute(ObjectivesRemoveExecutor),
+        ))
+        .then(set_display)
+        .then(literal("modify").then(modify))
+}
+
+/// `<targets> <objective> <score>` for setting, adding or removing scores
+fn score_change(
+    operation: Operation,
+    score: BoundedNumArgumentConsumer<i32>,
+) -> NonLeafNodeBuilder {
+    argument(ARG_TARGETS, PlayersArgumentConsumer).then(
+        argument(ARG_OBJECTIVE, ObjectiveArgumentConsumer)
+            .then(argument(ARG_SCORE, score).execute(PlayersScoreExecutor(operation))),
+    )
+}
+
+fn players_tree() -> NonLeafNodeBuilder {
+    literal("players")
+        .then(
+            literal("list").execute(PlayersListExecutor).then(
+                argument(ARG_TARGET, EntityArgumentConsumer).execute(PlayersListEntityExecutor),
+            ),
+        )
+        .then(literal("get").then(
+            argument(ARG_TARGET, EntityArgumentConsumer).then(
+                argument(ARG_OBJECTIVE, ObjectiveArgumentConsumer).execute(PlayersGetExecutor),
+            ),
+        ))
+        .then(literal("set").then(score_change(Operation::Set, score_consumer())))
+        .then(literal("add").then(score_change(Operation::Add, amount_consumer())))
+        .then(literal("remove").then(score_change(Operation::Remove, amount_consumer())))
+        .then(
+            literal("reset").then(
+                argument(ARG_TARGETS, PlayersArgumentConsumer)
+                    .execute(PlayersResetExecutor)
+                    .then(
+                        argument(ARG_OBJECTIVE, ObjectiveArgumentConsumer)
+                            .execute(PlayersResetExecutor),
+                    ),
+            ),
+        )
+        .then(
+            literal("enable").then(argument(ARG_TARGETS, PlayersArgumentConsumer).then(
+                argument(ARG_OBJECTIVE, ObjectiveArgumentConsumer).execute(PlayersEnableExecutor),
+            )),
+        )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(objectives_tree())
+        .then(players_tree())
+}
@@ -1,6 +1,7 @@
 use crate::command::{
     CommandError, CommandExecutor, CommandSender, args::ConsumedArgs, tree::CommandTree,
 };
+use crate::world::seed::visible_seed;
 use async_trait::async_trait;
 use pumpkin_util::text::click::ClickEvent;
 use pumpkin_util::text::hover::HoverEvent;
@@ -26,16 +27,10 @@ impl CommandExecutor for Executor {
                 player.living_entity.entity.world.read().await.level.seed.0
             }
             // TODO: Maybe ask player for world, or get the current world
-            _ => match server.worlds.read().await.first() {
-                Some(world) => world.level.seed.0,
-                None => {
-                    return Err(CommandError::GeneralCommandIssue(
-                        "Unable to get Seed".to_string(),
-                    ));
-                }
-            },
+            _ => server.worlds.default_world().level.seed.0,
         };
-        let seed = (seed as i64).to_string();
+        // Players below the reveal level only get the fake seed while it's hidden
+        let seed = visible_seed(seed, sender.permission_lvl()).to_string();
 
         sender
             .send_message(TextComponent::translate(
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use pumpkin_config::advanced_config;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["server"];
+const DESCRIPTION: &str = "Sends you to another server, or lists the servers you can switch to.";
+
+const ARG_NAME: &str = "name";
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let names = advanced_config()
+            .transfer
+            .servers
+            .iter()
+            .map(|server| server.name.as_str())
+            .collect::<Vec<_>>();
+        let message = if names.is_empty() {
+            "There are no servers to switch to".to_string()
+        } else {
+            format!("Servers: {}", names.join(", "))
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+
+        let Some(target) = advanced_config().transfer.server(name) else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "There is no server called {name}"
+            )));
+        };
+
+        sender
+            .send_message(TextComponent::text(format!("Sending you to {name}")))
+            .await;
+        player.transfer(&target.host, i32::from(target.port)).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(ListExecutor)
+            .then(argument(ARG_NAME, SimpleArgConsumer).execute(Executor)),
+    )
+}
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use pumpkin_config::advanced_config;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::homes::{HomeError, home_limit};
+
+use super::home::{ARG_NAME, home_name, location_of};
+
+const NAMES: [&str; 1] = ["sethome"];
+const DESCRIPTION: &str = "Saves where you are as a home.";
+
+struct Executor {
+    named: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let name = home_name(args, self.named)?;
+
+        let limit = home_limit(
+            &advanced_config().homes.limits,
+            player.permission_lvl.load(),
+        );
+        let location = location_of(&player).await;
+        let result = server
+            .homes
+            .lock()
+            .await
+            .set(player.gameprofile.id, name, location, limit);
+        if let Err(HomeError::TooMany(limit)) = result {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "You can't have more than {limit} homes, remove one with /delhome first"
+            )));
+        }
+        server.save_homes().await;
+
+        sender
+            .send_message(TextComponent::text(format!("Set home {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(Executor { named: false })
+            .then(argument(ARG_NAME, SimpleArgConsumer).execute(Executor { named: true })),
+    )
+}
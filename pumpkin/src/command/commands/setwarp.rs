@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+use super::home::{ARG_NAME, location_of};
+
+const NAMES: [&str; 1] = ["setwarp"];
+const DESCRIPTION: &str = "Saves where you are as a warp everyone can use.";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+
+        let location = location_of(&player).await;
+        server.warps.lock().await.set(name, location);
+        server.save_warps().await;
+
+        sender
+            .send_message(TextComponent::text(format!("Set warp {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .then(argument(ARG_NAME, SimpleArgConsumer).execute(Executor)),
+    )
+}
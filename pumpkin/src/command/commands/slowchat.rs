@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender,
+    args::{ConsumedArgs, FindArg, bounded_num::BoundedNumArgumentConsumer},
+    tree::{CommandTree, builder::argument},
+};
+
+const NAMES: [&str; 1] = ["slowchat"];
+const DESCRIPTION: &str = "Sets how many seconds players have to wait between chat messages.";
+
+const ARG_SECONDS: &str = "seconds";
+
+fn seconds_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_SECONDS).min(0)
+}
+
+struct QueryExecutor;
+
+#[async_trait]
+impl CommandExecutor for QueryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let cooldown = server.slow_chat.cooldown().as_secs();
+        let message = if cooldown == 0 {
+            "Slow chat is off".to_string()
+        } else {
+            format!("Slow chat is on, players have to wait {cooldown} seconds between messages")
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct SetExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Ok(Ok(seconds)) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_SECONDS) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_SECONDS.into())));
+        };
+        let seconds = u64::from(seconds.unsigned_abs());
+        server.slow_chat.set_cooldown(seconds);
+
+        let message = if seconds == 0 {
+            "Slow chat is now off".to_string()
+        } else {
+            format!("Players now have to wait {seconds} seconds between chat messages")
+        };
+        let message = TextComponent::text(message);
+        for player in server.get_all_players().await {
+            player.send_system_message(&message).await;
+        }
+        if !sender.is_player() {
+            sender.send_message(message).await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(QueryExecutor)
+        .then(argument(ARG_SECONDS, seconds_consumer()).execute(SetExecutor))
+}
@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::tag::Tagable;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::{Color, NamedColor};
+use pumpkin_world::block::registry::{get_block_by_state_id, get_state_by_state_id};
+use pumpkin_world::coordinates::ChunkRelativeBlockCoordinates;
+use pumpkin_world::{WORLD_LOWEST_Y, WORLD_MAX_Y};
+use rand::Rng;
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::entities::EntitiesArgumentConsumer;
+use crate::command::args::position_2d::Position2DArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{NonLeafNodeBuilder, argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["spreadplayers"];
+const DESCRIPTION: &str = "Teleports players to random surface locations in an area.";
+
+const ARG_CENTER: &str = "center";
+const ARG_SPREAD_DISTANCE: &str = "spreadDistance";
+const ARG_MAX_RANGE: &str = "maxRange";
+const ARG_MAX_HEIGHT: &str = "maxHeight";
+const ARG_RESPECT_TEAMS: &str = "respectTeams";
+const ARG_TARGETS: &str = "targets";
+
+/// How often the positions get pushed apart before giving up
+const MAX_ITERATIONS: usize = 10000;
+
+fn spread_distance_consumer() -> BoundedNumArgumentConsumer<f64> {
+    BoundedNumArgumentConsumer::new()
+        .name(ARG_SPREAD_DISTANCE)
+        .min(0.0)
+}
+
+fn max_range_consumer() -> BoundedNumArgumentConsumer<f64> {
+    BoundedNumArgumentConsumer::new()
+        .name(ARG_MAX_RANGE)
+        .min(1.0)
+}
+
+fn max_height_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_MAX_HEIGHT)
+}
+
+struct Area {
+    min: Vector2<f64>,
+    max: Vector2<f64>,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Position {
+    x: f64,
+    z: f64,
+}
+
+impl Position {
+    fn random(area: &Area) -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            x: rng.gen_range(area.min.x..area.max.x),
+            z: rng.gen_range(area.min.z..area.max.z),
+        }
+    }
+
+    fn distance(self, other: Self) -> f64 {
+        (self.x - other.x).hypot(self.z - other.z)
+    }
+
+    fn length(self) -> f64 {
+        self.x.hypot(self.z)
+    }
+
+    /// Moves the position back into the area, returns whether it was outside
+    fn clamp(&mut self, area: &Area) -> bool {
+        let clamped = Self {
+            x: self.x.clamp(area.min.x, area.max.x),
+            z: self.z.clamp(area.min.z, area.max.z),
+        };
+        let changed = clamped != *self;
+        *self = clamped;
+        changed
+    }
+
+    fn block_x(self) -> i32 {
+        self.x.floor() as i32
+    }
+
+    fn block_z(self) -> i32 {
+        self.z.floor() as i32
+    }
+}
+
+/// Finds the highest block below `max_height` with two blocks of air above it, returns the y
+/// to stand at and the block stood on
+async fn find_ground(world: &World, position: Position, max_height: i32) -> (i32, Option<u16>) {
+    let (x, z) = (position.block_x(), position.block_z());
+    let chunk = world.receive_chunk(Vector2::new(x >> 4, z >> 4)).await.0;
+    let chunk = chunk.read().await;
+    let state_id = |y: i32| {
+        if y < i32::from(WORLD_LOWEST_Y) || y >= i32::from(WORLD_MAX_Y) {
+            return 0;
+        }
+        let relative = ChunkRelativeBlockCoordinates::from(Vector3::new(x & 15, y, z & 15));
+        chunk.subchunks.get_block(relative).unwrap_or(0)
+    };
+    let is_air = |y: i32| get_state_by_state_id(state_id(y)).is_none_or(|state| state.air);
+
+    let mut above = is_air(max_height + 1);
+    let mut current = is_air(max_height);
+    let mut y = max_height;
+    while y > i32::from(WORLD_LOWEST_Y) {
+        y -= 1;
+        let below = is_air(y);
+        if !below && current && above {
+            return (y + 1, Some(state_id(y)));
+        }
+        above = current;
+        current = below;
+    }
+    (max_height + 1, None)
+}
+
+/// Players should not end up in lava, water or fire
+async fn is_safe(world: &World, position: Position, max_height: i32) -> bool {
+    let (y, ground) = find_ground(world, position, max_height).await;
+    let Some(ground) = ground else {
+        return false;
+    };
+    let liquid = get_state_by_state_id(ground).is_some_and(|state| state.is_liquid);
+    let fire = get_block_by_state_id(ground)
+        .and_then(|block| block.is_tagged_with("minecraft:fire"))
+        .unwrap_or_default();
+    y - 1 < max_height && !liquid && !fire
+}
+
+/// Pushes the positions apart until they are at least `spread_distance` apart and all on safe
+/// ground. Returns the smallest distance between two positions, which is also the error when
+/// they could not be spread
+async fn spread_positions(
+    world: &World,
+    area: &Area,
+    spread_distance: f64,
+    max_height: i32,
+    positions: &mut [Position],
+) -> Result<f64, f64> {
+    let mut min_distance = f64::MAX;
+    let mut moved = true;
+    let mut iterations = 0;
+    while iterations < MAX_ITERATIONS && moved {
+        iterations += 1;
+        moved = false;
+        min_distance = f64::MAX;
+        for i in 0..positions.len() {
+            let position = positions[i];
+            let mut push = Position::default();
+            let mut close = 0;
+            for (j, other) in positions.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let distance = position.distance(*other);
+                min_distance = min_distance.min(distance);
+                if distance < spread_distance {
+                    close += 1;
+                    push.x += other.x - position.x;
+                    push.z += other.z - position.z;
+                }
+            }
+
+            let position = &mut positions[i];
+            if close > 0 {
+                let length = push.length();
+                if length > 0.0 {
+                    position.x -= push.x / length;
+                    position.z -= push.z / length;
+                } else {
+                    *position = Position::random(area);
+                }
+                moved = true;
+            }
+            if position.clamp(area) {
+                moved = true;
+            }
+        }
+
+        if !moved {
+            for position in positions.iter_mut() {
+                if !is_safe(world, *position, max_height).await {
+                    *position = Position::random(area);
+                    moved = true;
+                }
+            }
+        }
+    }
+
+    // A single position has no distance to others
+    if positions.len() < 2 {
+        min_distance = 0.0;
+    }
+    if iterations >= MAX_ITERATIONS {
+        return Err(min_distance);
+    }
+    Ok(min_distance)
+}
+
+struct Executor {
+    under: bool,
+}
+
+#[expect(clippy::too_many_lines)]
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let center = Position2DArgumentConsumer::find_arg(args, ARG_CENTER)?;
+        let Ok(spread_distance) =
+            BoundedNumArgumentConsumer::<f64>::find_arg(args, ARG_SPREAD_DISTANCE)?
+        else {
+            return Err(CommandError::InvalidConsumption(Some(
+                ARG_SPREAD_DISTANCE.into(),
+            )));
+        };
+        let Ok(max_range) = BoundedNumArgumentConsumer::<f64>::find_arg(args, ARG_MAX_RANGE)?
+        else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_MAX_RANGE.into())));
+        };
+        let max_height = if self.under {
+            let Ok(max_height) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_MAX_HEIGHT)?
+            else {
+                return Err(CommandError::InvalidConsumption(Some(
+                    ARG_MAX_HEIGHT.into(),
+                )));
+            };
+            max_height
+        } else {
+            i32::from(WORLD_MAX_Y)
+        };
+        let respect_teams = BoolArgConsumer::find_arg(args, ARG_RESPECT_TEAMS)?;
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        if max_height < i32::from(WORLD_LOWEST_Y) {
+            sender
+                .send_message(
+                    TextComponent::translate(
+                        "commands.spreadplayers.failed.invalid.height",
+                        [
+                            TextComponent::text(max_height.to_string()),
+                            TextComponent::text(WORLD_LOWEST_Y.to_string()),
+                        ],
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
+
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        // Players in the same team end up at the same position, players without one count as a
+        // team as well
+        let groups: Vec<usize> = if respect_teams {
+            let scoreboard = server.scoreboard.lock().await;
+            let mut teams: HashMap<Option<&str>, usize> = HashMap::new();
+            targets
+                .iter()
+                .map(|target| {
+                    let team = scoreboard.get_team(&target.gameprofile.name);
+                    let next = teams.len();
+                    *teams.entry(team).or_insert(next)
+                })
+                .collect()
+        } else {
+            (0..targets.len()).collect()
+        };
+        let count = groups.iter().max().map_or(0, |max| max + 1);
+
+        let area = Area {
+            min: Vector2::new(center.x - max_range, center.z - max_range),
+            max: Vector2::new(center.x + max_range, center.z + max_range),
+        };
+        let mut positions: Vec<Position> = (0..count).map(|_| Position::random(&area)).collect();
+        let kind = if respect_teams { "teams" } else { "entities" };
+        let with = |distance: f64| {
+            [
+                TextComponent::text(count.to_string()),
+                TextComponent::text(center.x.to_string()),
+                TextComponent::text(center.z.to_string()),
+                TextComponent::text(format!("{distance:.2}")),
+            ]
+        };
+
+        let spread =
+            spread_positions(&world, &area, spread_distance, max_height, &mut positions).await;
+        if let Err(distance) = spread {
+            sender
+                .send_message(
+                    TextComponent::translate(
+                        format!("commands.spreadplayers.failed.{kind}"),
+                        with(distance),
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
+
+        let mut distance_sum = 0.0;
+        for (target, group) in targets.iter().zip(&groups) {
+            let position = positions[*group];
+            let (y, _) = find_ground(&world, position, max_height).await;
+            let entity = &target.living_entity.entity;
+            entity
+                .teleport(
+                    Vector3::new(
+                        f64::from(position.block_x()) + 0.5,
+                        f64::from(y),
+                        f64::from(position.block_z()) + 0.5,
+                    ),
+                    entity.yaw.load(),
+                    entity.pitch.load(),
+                    Arc::clone(&world),
+                )
+                .await;
+
+            let closest = positions
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| other != group)
+                .map(|(_, other)| position.distance(*other))
+                .fold(f64::MAX, f64::min);
+            distance_sum += closest;
+        }
+        let average = if targets.len() < 2 {
+            0.0
+        } else {
+            distance_sum / targets.len() as f64
+        };
+
+        sender
+            .send_message(TextComponent::translate(
+                format!("commands.spreadplayers.success.{kind}"),
+                with(average),
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+fn targets(under: bool) -> NonLeafNodeBuilder {
+    argument(ARG_RESPECT_TEAMS, BoolArgConsumer)
+        .then(argument(ARG_TARGETS, EntitiesArgumentConsumer).execute(Executor { under }))
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_CENTER, Position2DArgumentConsumer).then(
+            argument(ARG_SPREAD_DISTANCE, spread_distance_consumer()).then(
+                argument(ARG_MAX_RANGE, max_range_consumer())
+                    .then(targets(false))
+                    .then(
+                        literal("under").then(
+                            argument(ARG_MAX_HEIGHT, max_height_consumer()).then(targets(true)),
+                        ),
+                    ),
+            ),
+        ),
+    )
+}
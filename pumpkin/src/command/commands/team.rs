@@ -0,0 +1,554 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender,
+        args::{
+            ConsumedArgs, FindArg, bool::BoolArgConsumer, color::ColorArgumentConsumer,
+            players::PlayersArgumentConsumer, simple::SimpleArgConsumer,
+            team::TeamArgumentConsumer, textcomponent::TextComponentArgConsumer,
+        },
+        tree::{
+            CommandTree,
+            builder::{NonLeafNodeBuilder, argument, literal},
+        },
+    },
+    server::{
+        Server,
+        scoreboard::Scoreboard,
+        team::{CollisionRule, Team, Visibility},
+    },
+};
+
+const NAMES: [&str; 1] = ["team"];
+const DESCRIPTION: &str = "Controls teams.";
+
+const ARG_TEAM: &str = "team";
+const ARG_DISPLAY_NAME: &str = "displayName";
+const ARG_MEMBERS: &str = "members";
+const ARG_VALUE: &str = "value";
+
+fn failure(key: impl Into<Cow<'static, str>>) -> TextComponent {
+    TextComponent::translate(key, []).color_named(NamedColor::Red)
+}
+
+/// The colored display name of the team, or sends that it does not exist
+async fn find_team(
+    sender: &mut CommandSender<'_>,
+    scoreboard: &Scoreboard,
+    team: &str,
+) -> Option<TextComponent> {
+    if let Some(team) = scoreboard.team(team) {
+        return Some(team.formatted_name());
+    }
+    sender
+        .send_message(
+            TextComponent::translate("team.notFound", [TextComponent::text(team.to_string())])
+                .color_named(NamedColor::Red),
+        )
+        .await;
+    None
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+        let teams: Vec<_> = scoreboard
+            .teams()
+            .map(|(_, team)| team.formatted_name())
+            .collect();
+        drop(scoreboard);
+
+        let message = if teams.is_empty() {
+            TextComponent::translate("commands.team.list.teams.empty", [])
+        } else {
+            let count = TextComponent::text(teams.len().to_string());
+            let mut list = TextComponent::text("");
+            for (i, team) in teams.into_iter().enumerate() {
+                if i > 0 {
+                    list = list.add_text(", ");
+                }
+                list = list.add_child(team);
+            }
+            TextComponent::translate("commands.team.list.teams.success", [count, list])
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct ListMembersExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListMembersExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer::find_arg(args, ARG_TEAM)?;
+
+        let scoreboard = server.scoreboard.lock().await;
+        let Some(team_name) = find_team(sender, &scoreboard, name).await else {
+            return Ok(());
+        };
+        let members: Vec<_> = scoreboard
+            .team(name)
+            .map(|team| team.members().collect())
+            .unwrap_or_default();
+        let message = if members.is_empty() {
+            TextComponent::translate("commands.team.list.members.empty", [team_name])
+        } else {
+            TextComponent::translate(
+                "commands.team.list.members.success",
+                [
+                    team_name,
+                    TextComponent::text(members.len().to_string()),
+                    TextComponent::text(members.join(", ")),
+                ],
+            )
+        };
+        drop(scoreboard);
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct AddExecutor;
+
+#[async_trait]
+impl CommandExecutor for AddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_TEAM)?;
+        let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)
+            .unwrap_or_else(|_| TextComponent::text(name.to_string()));
+
+        let added = server
+            .scoreboard
+            .lock()
+            .await
+            .add_team(server, name, display_name.clone())
+            .await;
+
+        let message = if added {
+            TextComponent::translate("commands.team.add.success", [display_name])
+        } else {
+            failure("commands.team.add.duplicate")
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct RemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer::find_arg(args, ARG_TEAM)?;
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        let Some(team_name) = find_team(sender, &scoreboard, name).await else {
+            return Ok(());
+        };
+        scoreboard.remove_team(server, name).await;
+        drop(scoreboard);
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.team.remove.success",
+                [team_name],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct EmptyExecutor;
+
+#[async_trait]
+impl CommandExecutor for EmptyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer::find_arg(args, ARG_TEAM)?;
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        let Some(team_name) = find_team(sender, &scoreboard, name).await else {
+            return Ok(());
+        };
+        let removed = scoreboard.empty_team(server, name).await;
+        drop(scoreboard);
+
+        let message = if removed == 0 {
+            failure("commands.team.empty.unchanged")
+        } else {
+            TextComponent::translate(
+                "commands.team.empty.success",
+                [TextComponent::text(removed.to_string()), team_name],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct JoinExecutor;
+
+#[async_trait]
+impl CommandExecutor for JoinExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer::find_arg(args, ARG_TEAM)?;
+        // Without members the sender joins the team
+        let members: Vec<String> = match PlayersArgumentConsumer::find_arg(args, ARG_MEMBERS) {
+            Ok(targets) => targets
+                .iter()
+                .map(|target| target.gameprofile.name.clone())
+                .collect(),
+            Err(_) => vec![
+                sender
+                    .as_player()
+                    .ok_or(CommandError::InvalidRequirement)?
+                    .gameprofile
+                    .name
+                    .clone(),
+            ],
+        };
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        let Some(team_name) = find_team(sender, &scoreboard, name).await else {
+            return Ok(());
+        };
+        for member in &members {
+            scoreboard.join_team(server, member, name).await;
+        }
+        drop(scoreboard);
+
+        let message = match members.as_slice() {
+            [member] => TextComponent::translate(
+                "commands.team.join.success.single",
+                [TextComponent::text(member.clone()), team_name],
+            ),
+            _ => TextComponent::translate(
+                "commands.team.join.success.multiple",
+                [TextComponent::text(members.len().to_string()), team_name],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct LeaveExecutor;
+
+#[async_trait]
+impl CommandExecutor for LeaveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_MEMBERS)?;
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        for target in targets {
+            scoreboard
+                .leave_team(server, &target.gameprofile.name)
+                .await;
+        }
+        drop(scoreboard);
+
+        let message = match targets {
+            [target] => TextComponent::translate(
+                "commands.team.leave.success.single",
+                [TextComponent::text(target.gameprofile.name.clone())],
+            ),
+            _ => TextComponent::translate(
+                "commands.team.leave.success.multiple",
+                [TextComponent::text(targets.len().to_string())],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TeamOption {
+    DisplayName,
+    Color,
+    NameTagVisibility(Visibility),
+    CollisionRule(CollisionRule),
+    Prefix,
+    Suffix,
+}
+
+struct ModifyExecutor(TeamOption);
+
+#[async_trait]
+impl CommandExecutor for ModifyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer::find_arg(args, ARG_TEAM)?;
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        let Some(team_name) = find_team(sender, &scoreboard, name).await else {
+            return Ok(());
+        };
+        let Some(team) = scoreboard.team(name) else {
+            return Ok(());
+        };
+        let message = match self.0 {
+            TeamOption::DisplayName => {
+                let display_name = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                if team.display_name == display_name {
+                    failure("commands.team.option.name.unchanged")
+                } else {
+                    scoreboard
+                        .update_team(server, name, |team| team.display_name = display_name)
+                        .await;
+                    TextComponent::translate("commands.team.option.name.success", [team_name])
+                }
+            }
+            TeamOption::Color => {
+                let color = ColorArgumentConsumer::find_arg(args, ARG_VALUE)?;
+                if team.color == color {
+                    failure("commands.team.option.color.unchanged")
+                } else {
+                    scoreboard
+                        .update_team(server, name, |team| team.color = color)
+                        .await;
+                    let color = color.map_or("reset", NamedColor::name);
+                    TextComponent::translate(
+                        "commands.team.option.color.success",
+                        [team_name, TextComponent::text(color)],
+                    )
+                }
+            }
+            TeamOption::NameTagVisibility(visibility) => {
+                if team.name_tag_visibility == visibility {
+                    failure("commands.team.option.nametagVisibility.unchanged")
+                } else {
+                    scoreboard
+                        .update_team(server, name, |team| team.name_tag_visibility = visibility)
+                        .await;
+                    TextComponent::translate(
+                        "commands.team.option.nametagVisibility.success",
+                        [
+                            team_name,
+                            TextComponent::translate(
+                                format!("team.visibility.{}", visibility.name()),
+                                [],
+                            ),
+                        ],
+                    )
+                }
+            }
+            TeamOption::CollisionRule(rule) => {
+                if team.collision_rule == rule {
+                    failure("commands.team.option.collisionRule.unchanged")
+                } else {
+                    scoreboard
+                        .update_team(server, name, |team| team.collision_rule = rule)
+                        .await;
+                    TextComponent::translate(
+                        "commands.team.option.collisionRule.success",
+                        [
+                            team_name,
+                            TextComponent::translate(format!("team.collision.{}", rule.name()), []),
+                        ],
+                    )
+                }
+            }
+            TeamOption::Prefix => {
+                let prefix = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                scoreboard
+                    .update_team(server, name, |team| team.prefix = prefix.clone())
+                    .await;
+                TextComponent::translate("commands.team.option.prefix.success", [prefix])
+            }
+            TeamOption::Suffix => {
+                let suffix = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                scoreboard
+                    .update_team(server, name, |team| team.suffix = suffix.clone())
+                    .await;
+                TextComponent::translate("commands.team.option.suffix.success", [suffix])
+            }
+        };
+        drop(scoreboard);
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Flag {
+    FriendlyFire,
+    SeeFriendlyInvisibles,
+}
+
+impl Flag {
+    /// The start of the translation keys of the flag
+    const fn key(self) -> &'static str {
+        match self {
+            Self::FriendlyFire => "commands.team.option.friendlyfire",
+            Self::SeeFriendlyInvisibles => "commands.team.option.seeFriendlyInvisibles",
+        }
+    }
+
+    const fn get(self, team: &Team) -> bool {
+        match self {
+            Self::FriendlyFire => team.friendly_fire,
+            Self::SeeFriendlyInvisibles => team.see_friendly_invisibles,
+        }
+    }
+
+    const fn set(self, team: &mut Team, value: bool) {
+        match self {
+            Self::FriendlyFire => team.friendly_fire = value,
+            Self::SeeFriendlyInvisibles => team.see_friendly_invisibles = value,
+        }
+    }
+}
+
+struct ModifyFlagExecutor(Flag);
+
+#[async_trait]
+impl CommandExecutor for ModifyFlagExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer::find_arg(args, ARG_TEAM)?;
+        let value = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+        let flag = self.0;
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        let Some(team_name) = find_team(sender, &scoreboard, name).await else {
+            return Ok(());
+        };
+        let unchanged = scoreboard
+            .team(name)
+            .is_some_and(|team| flag.get(team) == value);
+        let message = match (unchanged, value) {
+            (true, true) => failure(format!("{}.alreadyEnabled", flag.key())),
+            (true, false) => failure(format!("{}.alreadyDisabled", flag.key())),
+            (false, _) => {
+                scoreboard
+                    .update_team(server, name, |team| flag.set(team, value))
+                    .await;
+                let state = if value { "enabled" } else { "disabled" };
+                TextComponent::translate(format!("{}.{state}", flag.key()), [team_name])
+            }
+        };
+        drop(scoreboard);
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+fn modify_tree() -> NonLeafNodeBuilder {
+    let text = |option: TeamOption| {
+        argument(ARG_VALUE, TextComponentArgConsumer).execute(ModifyExecutor(option))
+    };
+    let flag = |flag: Flag| argument(ARG_VALUE, BoolArgConsumer).execute(ModifyFlagExecutor(flag));
+
+    let mut name_tag_visibility = literal("nametagVisibility");
+    for visibility in Visibility::ALL {
+        name_tag_visibility = name_tag_visibility.then(
+            literal(visibility.name())
+                .execute(ModifyExecutor(TeamOption::NameTagVisibility(visibility))),
+        );
+    }
+    let mut collision_rule = literal("collisionRule");
+    for rule in CollisionRule::ALL {
+        collision_rule = collision_rule
+            .then(literal(rule.name()).execute(ModifyExecutor(TeamOption::CollisionRule(rule))));
+    }
+
+    argument(ARG_TEAM, TeamArgumentConsumer)
+        .then(literal("displayName").then(text(TeamOption::DisplayName)))
+        .then(literal("color").then(
+            argument(ARG_VALUE, ColorArgumentConsumer).execute(ModifyExecutor(TeamOption::Color)),
+        ))
+        .then(literal("friendlyFire").then(flag(Flag::FriendlyFire)))
+        .then(literal("seeFriendlyInvisibles").then(flag(Flag::SeeFriendlyInvisibles)))
+        .then(name_tag_visibility)
+        .then(collision_rule)
+        .then(literal("prefix").then(text(TeamOption::Prefix)))
+        .then(literal("suffix").then(text(TeamOption::Suffix)))
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("list")
+                .execute(ListExecutor)
+                .then(argument(ARG_TEAM, TeamArgumentConsumer).execute(ListMembersExecutor)),
+        )
+        .then(
+            literal("add").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .execute(AddExecutor)
+                    .then(
+                        argument(ARG_DISPLAY_NAME, TextComponentArgConsumer).execute(AddExecutor),
+                    ),
+            ),
+        )
+        .then(
+            literal("remove")
+                .then(argument(ARG_TEAM, TeamArgumentConsumer).execute(RemoveExecutor)),
+        )
+        .then(
+            literal("empty").then(argument(ARG_TEAM, TeamArgumentConsumer).execute(EmptyExecutor)),
+        )
+        .then(
+            literal("join").then(
+                argument(ARG_TEAM, TeamArgumentConsumer)
+                    .execute(JoinExecutor)
+                    .then(argument(ARG_MEMBERS, PlayersArgumentConsumer).execute(JoinExecutor)),
+            ),
+        )
+        .then(
+            literal("leave")
+                .then(argument(ARG_MEMBERS, PlayersArgumentConsumer).execute(LeaveExecutor)),
+        )
+        .then(literal("modify").then(modify_tree()))
+}
@@ -63,11 +63,16 @@ impl CommandExecutor for EntitiesToEntityExecutor {
 
         let destination = EntityArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
         let pos = destination.living_entity.entity.pos.load();
+        let world = destination.world().await;
 
         for target in targets {
             let yaw = target.living_entity.entity.yaw.load();
             let pitch = target.living_entity.entity.pitch.load();
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target
+                .living_entity
+                .entity
+                .teleport(pos, yaw, pitch, world.clone())
+                .await;
         }
 
         Ok(())
@@ -92,7 +97,11 @@ impl CommandExecutor for EntitiesToPosFacingPosExecutor {
         let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_pos);
 
         for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target
+                .living_entity
+                .entity
+                .teleport(pos, yaw, pitch, target.world().await)
+                .await;
         }
 
         Ok(())
@@ -119,7 +128,11 @@ impl CommandExecutor for EntitiesToPosFacingEntityExecutor {
         let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_entity.pos.load());
 
         for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target
+                .living_entity
+                .entity
+                .teleport(pos, yaw, pitch, target.world().await)
+                .await;
         }
 
         Ok(())
@@ -143,7 +156,11 @@ impl CommandExecutor for EntitiesToPosWithRotationExecutor {
         let (yaw, pitch) = RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?;
 
         for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target
+                .living_entity
+                .entity
+                .teleport(pos, yaw, pitch, target.world().await)
+                .await;
         }
 
         Ok(())
@@ -167,7 +184,11 @@ impl CommandExecutor for EntitiesToPosExecutor {
         for target in targets {
             let yaw = target.living_entity.entity.yaw.load();
             let pitch = target.living_entity.entity.pitch.load();
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target
+                .living_entity
+                .entity
+                .teleport(pos, yaw, pitch, target.world().await)
+                .await;
         }
 
         Ok(())
@@ -186,12 +207,17 @@ impl CommandExecutor for SelfToEntityExecutor {
     ) -> Result<(), CommandError> {
         let destination = EntityArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
         let pos = destination.living_entity.entity.pos.load();
+        let world = destination.world().await;
 
         match sender {
             CommandSender::Player(player) => {
                 let yaw = player.living_entity.entity.yaw.load();
                 let pitch = player.living_entity.entity.pitch.load();
-                player.living_entity.entity.teleport(pos, yaw, pitch).await;
+                player
+                    .living_entity
+                    .entity
+                    .teleport(pos, yaw, pitch, world)
+                    .await;
             }
             _ => {
                 sender
@@ -219,7 +245,11 @@ impl CommandExecutor for SelfToPosExecutor {
                 let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
                 let yaw = player.living_entity.entity.yaw.load();
                 let pitch = player.living_entity.entity.pitch.load();
-                player.living_entity.entity.teleport(pos, yaw, pitch).await;
+                player
+                    .living_entity
+                    .entity
+                    .teleport(pos, yaw, pitch, player.world().await)
+                    .await;
             }
             _ => {
                 sender
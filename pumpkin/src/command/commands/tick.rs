@@ -0,0 +1,269 @@
+use async_trait::async_trait;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender,
+        args::{
+            ConsumedArgs, FindArg, FindArgDefaultName, bounded_num::BoundedNumArgumentConsumer,
+            time::TimeArgumentConsumer,
+        },
+        tree::CommandTree,
+        tree::builder::{argument, literal},
+    },
+    server::{
+        Server,
+        tick_manager::{MAX_TICK_RATE, MIN_TICK_RATE},
+    },
+};
+
+const NAMES: [&str; 1] = ["tick"];
+const DESCRIPTION: &str = "Controls the tick rate of the game.";
+
+const ARG_RATE: &str = "rate";
+const ARG_TIME: &str = "time";
+
+fn rate_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new()
+        .name(ARG_RATE)
+        .min(MIN_TICK_RATE)
+        .max(MAX_TICK_RATE)
+}
+
+struct QueryExecutor;
+
+#[async_trait]
+impl CommandExecutor for QueryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let manager = &server.tick_manager;
+        let status = if manager.is_sprinting() {
+            "commands.tick.status.sprinting"
+        } else if manager.is_frozen() {
+            "commands.tick.status.frozen"
+        } else {
+            "commands.tick.status.running"
+        };
+        sender
+            .send_message(TextComponent::translate(status, []))
+            .await;
+        sender
+            .send_message(TextComponent::text(format!(
+                "Target tick rate: {} per second",
+                manager.tick_rate()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct RateExecutor;
+
+#[async_trait]
+impl CommandExecutor for RateExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Ok(rate) = rate_consumer().find_arg_default_name(args)? else {
+            sender
+                .send_message(
+                    TextComponent::text(format!(
+                        "The tick rate must be between {MIN_TICK_RATE} and {MAX_TICK_RATE}"
+                    ))
+                    .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        };
+
+        server.tick_manager.set_tick_rate(rate);
+        server.broadcast_tick_state().await;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.tick.rate.success",
+                [TextComponent::text(rate.to_string())],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct StepExecutor;
+
+#[async_trait]
+impl CommandExecutor for StepExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let ticks = TimeArgumentConsumer::find_arg(args, ARG_TIME).unwrap_or(1);
+        let ticks = u32::try_from(ticks).unwrap_or(0).max(1);
+
+        if !server.tick_manager.step(ticks) {
+            sender
+                .send_message(
+                    TextComponent::translate("commands.tick.step.fail", [])
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        }
+
+        server.broadcast_tick_step().await;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.tick.step.success",
+                [TextComponent::text(ticks.to_string())],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct StepStopExecutor;
+
+#[async_trait]
+impl CommandExecutor for StepStopExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        if server.tick_manager.stop_stepping() {
+            server.broadcast_tick_step().await;
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.tick.step.stop.success",
+                    [],
+                ))
+                .await;
+        } else {
+            sender
+                .send_message(
+                    TextComponent::translate("commands.tick.step.stop.fail", [])
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct SprintExecutor;
+
+#[async_trait]
+impl CommandExecutor for SprintExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let ticks = TimeArgumentConsumer::find_arg(args, ARG_TIME)?;
+        let ticks = u64::try_from(ticks).unwrap_or(0).max(1);
+
+        if server.tick_manager.start_sprint(ticks) {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.tick.sprint.stop.success",
+                    [],
+                ))
+                .await;
+        }
+        sender
+            .send_message(TextComponent::translate(
+                "commands.tick.status.sprinting",
+                [],
+            ))
+            .await;
+        // Sprinting unfreezes the game until the sprint is over
+        server.broadcast_tick_state().await;
+        Ok(())
+    }
+}
+
+struct SprintStopExecutor;
+
+#[async_trait]
+impl CommandExecutor for SprintStopExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(report) = server.tick_manager.stop_sprint() else {
+            sender
+                .send_message(
+                    TextComponent::translate("commands.tick.sprint.stop.fail", [])
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        };
+
+        server.broadcast_tick_state().await;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.tick.sprint.report",
+                [
+                    TextComponent::text(format!("{:.1}", report.ticks_per_second())),
+                    TextComponent::text(format!("{:.2}", report.millis_per_tick())),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct FreezeExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for FreezeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let frozen = self.0;
+        server.tick_manager.set_frozen(frozen);
+        server.broadcast_tick_state().await;
+        let key = if frozen {
+            "commands.tick.status.frozen"
+        } else {
+            "commands.tick.status.running"
+        };
+        sender.send_message(TextComponent::translate(key, [])).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("query").execute(QueryExecutor))
+        .then(literal("rate").then(argument(ARG_RATE, rate_consumer()).execute(RateExecutor)))
+        .then(
+            literal("step")
+                .then(literal("stop").execute(StepStopExecutor))
+                .then(argument(ARG_TIME, TimeArgumentConsumer).execute(StepExecutor))
+                .execute(StepExecutor),
+        )
+        .then(
+            literal("sprint")
+                .then(literal("stop").execute(SprintStopExecutor))
+                .then(argument(ARG_TIME, TimeArgumentConsumer).execute(SprintExecutor)),
+        )
+        .then(literal("freeze").execute(FreezeExecutor(true)))
+        .then(literal("unfreeze").execute(FreezeExecutor(false)))
+}
@@ -56,10 +56,7 @@ impl CommandExecutor for QueryExecutor {
     ) -> Result<(), CommandError> {
         let mode = self.0;
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let level_time = world.level_time.lock().await;
 
         let msg = match mode {
@@ -117,10 +114,7 @@ impl CommandExecutor for ChangeExecutor {
 
         let mode = self.0;
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut level_time = world.level_time.lock().await;
 
         let msg = match mode {
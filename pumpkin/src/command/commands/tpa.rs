@@ -0,0 +1,92 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use pumpkin_util::text::color::NamedColor;
+use pumpkin_util::text::{TextComponent, click::ClickEvent};
+
+use crate::command::args::entity::EntityArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::teleport_requests::RequestError;
+
+const NAMES: [&str; 1] = ["tpa"];
+const DESCRIPTION: &str = "Asks a player whether you may teleport to them.";
+
+const ARG_TARGET: &str = "target";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let target_name = &target.gameprofile.name;
+        if target.gameprofile.id == player.gameprofile.id {
+            return Err(CommandError::GeneralCommandIssue(
+                "You can't ask yourself to teleport".into(),
+            ));
+        }
+
+        let result = server.teleport_requests.lock().await.send(
+            player.gameprofile.id,
+            target.gameprofile.id,
+            Instant::now(),
+        );
+        match result {
+            Ok(()) => {}
+            Err(RequestError::Cooldown(remaining)) => {
+                let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+                return Err(CommandError::GeneralCommandIssue(format!(
+                    "You have to wait {seconds} seconds before sending another teleport request"
+                )));
+            }
+            Err(RequestError::AlreadySent) => {
+                return Err(CommandError::GeneralCommandIssue(format!(
+                    "You already asked {target_name} to teleport"
+                )));
+            }
+        }
+
+        let name = &player.gameprofile.name;
+        target
+            .send_system_message(
+                &TextComponent::text(format!("{name} wants to teleport to you "))
+                    .add_child(
+                        TextComponent::text("[Accept]")
+                            .color_named(NamedColor::Green)
+                            .click_event(ClickEvent::RunCommand(
+                                format!("/tpaccept {name}").into(),
+                            )),
+                    )
+                    .add_text(" ")
+                    .add_child(
+                        TextComponent::text("[Deny]")
+                            .color_named(NamedColor::Red)
+                            .click_event(ClickEvent::RunCommand(format!("/tpdeny {name}").into())),
+                    ),
+            )
+            .await;
+        sender
+            .send_message(TextComponent::text(format!(
+                "Asked {target_name} whether you may teleport to them"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .then(argument(ARG_TARGET, EntityArgumentConsumer).execute(Executor)),
+    )
+}
@@ -0,0 +1,94 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::entity::EntityArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["tpaccept"];
+const DESCRIPTION: &str = "Lets a player who asked with /tpa teleport to you.";
+
+const ARG_TARGET: &str = "target";
+
+struct Executor {
+    specific: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let from = if self.specific {
+            Some(
+                EntityArgumentConsumer::find_arg(args, ARG_TARGET)?
+                    .gameprofile
+                    .id,
+            )
+        } else {
+            None
+        };
+
+        let requester =
+            server
+                .teleport_requests
+                .lock()
+                .await
+                .take(player.gameprofile.id, from, Instant::now());
+        let requester = match requester {
+            Some(id) => server.get_player_by_uuid(id).await,
+            None => None,
+        };
+        let Some(requester) = requester else {
+            return Err(CommandError::GeneralCommandIssue(
+                "You have no pending teleport request".into(),
+            ));
+        };
+
+        // The teleport API takes care of moving them into another dimension
+        let entity = &player.living_entity.entity;
+        requester
+            .living_entity
+            .entity
+            .teleport(
+                entity.pos.load(),
+                entity.yaw.load(),
+                entity.pitch.load(),
+                player.world().await,
+            )
+            .await;
+
+        requester
+            .send_system_message(&TextComponent::text(format!(
+                "{} accepted your teleport request",
+                player.gameprofile.name
+            )))
+            .await;
+        sender
+            .send_message(TextComponent::text(format!(
+                "Teleported {} to you",
+                requester.gameprofile.name
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(Executor { specific: false })
+            .then(
+                argument(ARG_TARGET, EntityArgumentConsumer).execute(Executor { specific: true }),
+            ),
+    )
+}
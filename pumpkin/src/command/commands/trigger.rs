@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::{Color, NamedColor};
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::scoreboard::{TriggerAction, TriggerError};
+
+const NAMES: [&str; 1] = ["trigger"];
+const DESCRIPTION: &str = "Sets a trigger to be activated.";
+const ARG_OBJECTIVE: &str = "objective";
+const ARG_VALUE: &str = "value";
+
+fn value_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_VALUE)
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Simple,
+    Add,
+    Set,
+}
+
+struct Executor(Mode);
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let objective = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE)?;
+        let action = match self.0 {
+            Mode::Simple => TriggerAction::Add(1),
+            Mode::Add | Mode::Set => {
+                let Ok(value) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_VALUE)?
+                else {
+                    return Err(CommandError::InvalidConsumption(Some(ARG_VALUE.into())));
+                };
+                match self.0 {
+                    Mode::Set => TriggerAction::Set(value),
+                    _ => TriggerAction::Add(value),
+                }
+            }
+        };
+
+        let result = server
+            .scoreboard
+            .lock()
+            .await
+            .trigger(server, &player.gameprofile.name, objective, action)
+            .await;
+        let objective = TextComponent::text(objective.to_string());
+        let message = match (result, self.0, action) {
+            (Ok(_), Mode::Simple, _) => {
+                TextComponent::translate("commands.trigger.simple.success", [objective])
+            }
+            (Ok(_), _, TriggerAction::Add(value)) => TextComponent::translate(
+                "commands.trigger.add.success",
+                [objective, TextComponent::text(value.to_string())],
+            ),
+            (Ok(_), _, TriggerAction::Set(value)) => TextComponent::translate(
+                "commands.trigger.set.success",
+                [objective, TextComponent::text(value.to_string())],
+            ),
+            (Err(TriggerError::Unprimed), _, _) => {
+                TextComponent::translate("commands.trigger.failed.unprimed", [objective])
+                    .color(Color::Named(NamedColor::Red))
+            }
+            (Err(TriggerError::Invalid), _, _) => {
+                TextComponent::translate("commands.trigger.failed.invalid", [])
+                    .color(Color::Named(NamedColor::Red))
+            }
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player()).then(
+            argument(ARG_OBJECTIVE, SimpleArgConsumer)
+                .execute(Executor(Mode::Simple))
+                .then(
+                    literal("add")
+                        .then(argument(ARG_VALUE, value_consumer()).execute(Executor(Mode::Add))),
+                )
+                .then(
+                    literal("set")
+                        .then(argument(ARG_VALUE, value_consumer()).execute(Executor(Mode::Set))),
+                ),
+        ),
+    )
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+use super::region::managed_region;
+
+const NAMES: [&str; 1] = ["trust"];
+const DESCRIPTION: &str = "Lets players build in a region you claimed, or not anymore.";
+
+const ARG_REGION: &str = "region";
+const ARG_TARGETS: &str = "targets";
+
+struct Executor {
+    trusted: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_REGION)?;
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+        {
+            let mut claims = world.claims.lock().await;
+            let region = managed_region(&mut claims, name, sender)?;
+            for target in targets {
+                let profile = &target.gameprofile;
+                if self.trusted {
+                    region.members.insert(profile.id, profile.name.clone());
+                } else {
+                    region.members.remove(&profile.id);
+                }
+            }
+        }
+        world.save_claims().await;
+
+        let names = targets
+            .iter()
+            .map(|target| target.gameprofile.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = if self.trusted {
+            format!("{names} may now build in {name}")
+        } else {
+            format!("{names} may no longer build in {name}")
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_REGION, SimpleArgConsumer)
+            .then(literal("add").then(
+                argument(ARG_TARGETS, PlayersArgumentConsumer).execute(Executor { trusted: true }),
+            ))
+            .then(literal("remove").then(
+                argument(ARG_TARGETS, PlayersArgumentConsumer).execute(Executor { trusted: false }),
+            )),
+    )
+}
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+
+use crate::command::CommandError;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::argument;
+use crate::command::{CommandExecutor, CommandSender};
+use crate::data::SaveJSONConfiguration;
+use crate::data::muted_player_data::MUTED_PLAYER_LIST;
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["unmute"];
+const DESCRIPTION: &str = "Allows muted players to chat again.";
+
+const ARG_TARGETS: &str = "targets";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Players(targets)) = args.get(&ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+
+        let mut muted_players = MUTED_PLAYER_LIST.write().await;
+        for target in targets {
+            let name = &target.gameprofile.name;
+            let Some(index) = muted_players
+                .muted_players
+                .iter()
+                .position(|entry| entry.uuid == target.gameprofile.id)
+            else {
+                sender
+                    .send_message(
+                        TextComponent::text(format!("{name} is not muted"))
+                            .color_named(NamedColor::Red),
+                    )
+                    .await;
+                continue;
+            };
+
+            muted_players.muted_players.remove(index);
+            target
+                .send_system_message(&TextComponent::text("You are no longer muted"))
+                .await;
+            sender
+                .send_message(TextComponent::text(format!("Unmuted {name}")))
+                .await;
+        }
+        muted_players.save();
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(Executor))
+}
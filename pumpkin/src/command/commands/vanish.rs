@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender,
+    args::{Arg, ConsumedArgs, players::PlayersArgumentConsumer},
+    tree::{
+        CommandTree,
+        builder::{argument, require},
+    },
+};
+use crate::entity::player::Player;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["vanish"];
+const DESCRIPTION: &str = "Hides players from everyone who can't see vanished players.";
+
+const ARG_TARGETS: &str = "targets";
+
+async fn toggle(player: &Arc<Player>, server: &Server) -> TextComponent {
+    let vanished = !player.is_vanished();
+    player.set_vanished(server, vanished).await;
+    let name = &player.gameprofile.name;
+    TextComponent::text(if vanished {
+        format!("{name} is now vanished")
+    } else {
+        format!("{name} is visible again")
+    })
+}
+
+struct SelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for SelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::InvalidRequirement);
+        };
+        let message = toggle(&player, server).await;
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct TargetsExecutor;
+
+#[async_trait]
+impl CommandExecutor for TargetsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Players(targets)) = args.get(ARG_TARGETS) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+        for target in targets {
+            let message = toggle(target, server).await;
+            sender.send_message(message).await;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
+        .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(TargetsExecutor))
+}
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+use super::home::{ARG_NAME, teleport_to};
+
+const NAMES: [&str; 1] = ["warp"];
+const DESCRIPTION: &str = "Teleports you to a warp, or lists them.";
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let message = {
+            let warps = server.warps.lock().await;
+            let names = warps.names();
+            if names.is_empty() {
+                "There are no warps".to_string()
+            } else {
+                format!("Warps: {}", names.join(", "))
+            }
+        };
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+
+        let Some(location) = server.warps.lock().await.get(name).cloned() else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "There is no warp called {name}"
+            )));
+        };
+        teleport_to(server, &player, &location).await?;
+
+        sender
+            .send_message(TextComponent::text(format!("Teleported to {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(ListExecutor)
+        .then(
+            require(|sender| sender.is_player())
+                .then(argument(ARG_NAME, SimpleArgConsumer).execute(Executor)),
+        )
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender,
+        args::{ConsumedArgs, FindArg, game_profiles::GameProfilesArgumentConsumer},
+        tree::CommandTree,
+        tree::builder::{argument, literal},
+    },
+    data::whitelist_data::WHITELIST,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["whitelist"];
+const DESCRIPTION: &str = "Manages the players who may join while the whitelist is enabled.";
+
+const ARG_TARGETS: &str = "targets";
+
+struct AddExecutor;
+
+#[async_trait]
+impl CommandExecutor for AddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = GameProfilesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        for target in targets {
+            let message = if server.add_to_whitelist(target).await {
+                TextComponent::translate(
+                    "commands.whitelist.add.success",
+                    [TextComponent::text(target.name.clone())],
+                )
+            } else {
+                TextComponent::translate("commands.whitelist.add.failed", [])
+            };
+            sender.send_message(message).await;
+        }
+        Ok(())
+    }
+}
+
+struct RemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = GameProfilesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        for target in targets {
+            let message = if server.remove_from_whitelist(target).await {
+                TextComponent::translate(
+                    "commands.whitelist.remove.success",
+                    [TextComponent::text(target.name.clone())],
+                )
+            } else {
+                TextComponent::translate("commands.whitelist.remove.failed", [])
+            };
+            sender.send_message(message).await;
+        }
+        Ok(())
+    }
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let names: Vec<_> = WHITELIST
+            .read()
+            .await
+            .players
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect();
+
+        let message = if names.is_empty() {
+            TextComponent::translate("commands.whitelist.none", [])
+        } else {
+            TextComponent::translate(
+                "commands.whitelist.list",
+                [
+                    TextComponent::text(names.len().to_string()),
+                    TextComponent::text(names.join(", ")),
+                ],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct ReloadExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReloadExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        server.reload_whitelist().await;
+        sender
+            .send_message(TextComponent::translate("commands.whitelist.reloaded", []))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("add")
+                .then(argument(ARG_TARGETS, GameProfilesArgumentConsumer).execute(AddExecutor)),
+        )
+        .then(
+            literal("remove")
+                .then(argument(ARG_TARGETS, GameProfilesArgumentConsumer).execute(RemoveExecutor)),
+        )
+        .then(literal("list").execute(ListExecutor))
+        .then(literal("reload").execute(ReloadExecutor))
+}
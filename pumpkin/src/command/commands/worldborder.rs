@@ -59,10 +59,7 @@ impl CommandExecutor for GetExecutor {
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let border = world.worldborder.lock().await;
 
         let diameter = border.new_diameter.round() as i32;
@@ -87,10 +84,7 @@ impl CommandExecutor for SetExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -139,10 +133,7 @@ impl CommandExecutor for SetTimeExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -224,10 +215,7 @@ impl CommandExecutor for AddExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -278,10 +266,7 @@ impl CommandExecutor for AddTimeExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -365,10 +350,7 @@ impl CommandExecutor for CenterExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Vector2 { x, z } = Position2DArgumentConsumer.find_arg_default_name(args)?;
@@ -398,10 +380,7 @@ impl CommandExecutor for DamageAmountExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Ok(damage_per_block) = damage_per_block_consumer().find_arg_default_name(args)? else {
@@ -450,10 +429,7 @@ impl CommandExecutor for DamageBufferExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Ok(buffer) = damage_buffer_consumer().find_arg_default_name(args)? else {
@@ -502,10 +478,7 @@ impl CommandExecutor for WarningDistanceExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = warning_distance_consumer().find_arg_default_name(args)? else {
@@ -553,10 +526,7 @@ impl CommandExecutor for WarningTimeExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = server.worlds.default_world();
         let mut border = world.worldborder.lock().await;
 
         let Ok(time) = time_consumer().find_arg_default_name(args)? else {
@@ -1,14 +1,17 @@
 use pumpkin_protocol::client::play::CommandSuggestion;
 use pumpkin_util::permission::PermissionLvl;
 use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::click::ClickEvent;
 
 use super::args::ConsumedArgs;
 
 use crate::command::CommandSender;
 use crate::command::dispatcher::CommandError::{
     GeneralCommandIssue, InvalidConsumption, InvalidRequirement, OtherPumpkin, PermissionDenied,
+    SyntaxError,
 };
 use crate::command::tree::{Command, CommandTree, NodeType, RawArgs};
+use crate::data::audit_log::{AuditAction, audit};
 use crate::error::PumpkinError;
 use crate::server::Server;
 use pumpkin_util::text::color::{Color, NamedColor};
@@ -29,33 +32,107 @@ pub enum CommandError {
     OtherPumpkin(Box<dyn PumpkinError>),
 
     GeneralCommandIssue(String),
+
+    /// The input does not fit the command, `cursor` is the byte position in it where it stopped
+    /// fitting. Shown like vanilla does, with the translation `key` and the input up to there
+    SyntaxError {
+        key: &'static str,
+        cursor: usize,
+    },
 }
 
 impl CommandError {
-    pub fn into_string_or_pumpkin_error(self, cmd: &str) -> Result<String, Box<dyn PumpkinError>> {
+    const fn unknown_command() -> Self {
+        SyntaxError {
+            key: "command.unknown.command",
+            cursor: 0,
+        }
+    }
+
+    /// The error for input which stopped fitting the command at the argument with the index,
+    /// or which is missing arguments when there is no such argument
+    fn syntax_error_at(cmd: &str, argument: usize) -> Self {
+        cmd.split_whitespace().nth(argument + 1).map_or_else(
+            || SyntaxError {
+                key: "command.unknown.command",
+                cursor: cmd.trim_end().len(),
+            },
+            |part| SyntaxError {
+                key: "command.unknown.argument",
+                cursor: part.as_ptr() as usize - cmd.as_ptr() as usize,
+            },
+        )
+    }
+
+    /// The messages shown to the sender, with vanilla's translation keys where vanilla has one
+    pub fn into_messages_or_pumpkin_error(
+        self,
+        cmd: &str,
+    ) -> Result<Vec<TextComponent>, Box<dyn PumpkinError>> {
         match self {
             InvalidConsumption(s) => {
                 log::error!(
                     "Error while parsing command \"{cmd}\": {s:?} was consumed, but couldn't be parsed"
                 );
-                Ok("Internal Error (See logs for details)".into())
+                Ok(vec![
+                    TextComponent::translate("command.failed", []).color_named(NamedColor::Red),
+                ])
             }
             InvalidRequirement => {
                 log::error!(
                     "Error while parsing command \"{cmd}\": a requirement that was expected was not met."
                 );
-                Ok("Internal Error (See logs for details)".into())
+                Ok(vec![
+                    TextComponent::translate("command.failed", []).color_named(NamedColor::Red),
+                ])
             }
             PermissionDenied => {
                 log::warn!("Permission denied for command \"{cmd}\"");
-                Ok("I'm sorry, but you do not have permission to perform this command. Please contact the server administrator if you believe this is an error.".into())
+                // Like vanilla, commands the sender may not use look like they don't exist
+                Self::unknown_command().into_messages_or_pumpkin_error(cmd)
             }
-            GeneralCommandIssue(s) => Ok(s),
+            GeneralCommandIssue(s) => Ok(vec![TextComponent::text(s).color_named(NamedColor::Red)]),
+            SyntaxError { key, cursor } => Ok(vec![
+                TextComponent::translate(key, []).color_named(NamedColor::Red),
+                syntax_error_context(cmd, cursor),
+            ]),
             OtherPumpkin(e) => Err(e),
         }
     }
 }
 
+/// Shows the input up to where it stopped fitting the way vanilla does, like
+/// `...memode @s survivl<--[HERE]`, clicking it suggests the command again
+fn syntax_error_context(cmd: &str, cursor: usize) -> TextComponent {
+    // At most 10 characters before the cursor are shown
+    let before = &cmd[..cursor];
+    let start = before
+        .char_indices()
+        .rev()
+        .nth(9)
+        .map_or(0, |(index, _)| index);
+
+    let mut context = TextComponent::text("")
+        .color_named(NamedColor::Gray)
+        .click_event(ClickEvent::SuggestCommand(format!("/{cmd}").into()));
+    if start > 0 {
+        context = context.add_text("...");
+    }
+    context = context.add_text(before[start..].to_string());
+    if cursor < cmd.len() {
+        context = context.add_child(
+            TextComponent::text(cmd[cursor..].to_string())
+                .color_named(NamedColor::Red)
+                .underlined(),
+        );
+    }
+    context.add_child(
+        TextComponent::translate("command.context.here", [])
+            .color_named(NamedColor::Red)
+            .italic(),
+    )
+}
+
 #[derive(Default)]
 pub struct CommandDispatcher {
     pub(crate) commands: HashMap<String, Command>,
@@ -70,15 +147,18 @@ impl CommandDispatcher {
         server: &'a Server,
         cmd: &'a str,
     ) {
+        audit(
+            sender,
+            AuditAction::Command {
+                command: cmd.to_string(),
+            },
+        );
         if let Err(e) = self.dispatch(sender, server, cmd).await {
-            match e.into_string_or_pumpkin_error(cmd) {
-                Ok(err) => {
-                    sender
-                        .send_message(
-                            TextComponent::text(err)
-                                .color_named(pumpkin_util::text::color::NamedColor::Red),
-                        )
-                        .await;
+            match e.into_messages_or_pumpkin_error(cmd) {
+                Ok(messages) => {
+                    for message in messages {
+                        sender.send_message(message).await;
+                    }
                 }
                 Err(pumpkin_error) => {
                     pumpkin_error.log();
@@ -137,6 +217,10 @@ impl CommandDispatcher {
                     log::error!("Error while parsing command \"{cmd}\": {issue}");
                     return Vec::new();
                 }
+                Err(SyntaxError { key, .. }) => {
+                    log::error!("Error while parsing command \"{cmd}\": {key}");
+                    return Vec::new();
+                }
                 Err(OtherPumpkin(e)) => {
                     log::error!("Error while parsing command \"{cmd}\": {e}");
                     return Vec::new();
@@ -162,19 +246,15 @@ impl CommandDispatcher {
     ) -> Result<(), CommandError> {
         // Other languages dont use the ascii whitespace
         let mut parts = cmd.split_whitespace();
-        let key = parts
-            .next()
-            .ok_or(GeneralCommandIssue("Empty Command".to_string()))?;
+        let key = parts.next().ok_or(CommandError::unknown_command())?;
         let raw_args: Vec<&str> = parts.rev().collect();
 
         if !self.commands.contains_key(key) {
-            return Err(GeneralCommandIssue(format!("Command {key} does not exist")));
+            return Err(CommandError::unknown_command());
         }
 
         let Some(permission) = self.permissions.get(key) else {
-            return Err(GeneralCommandIssue(
-                "Permission for Command not found".to_string(),
-            ));
+            return Err(CommandError::unknown_command());
         };
 
         if !src.has_permission_lvl(*permission) {
@@ -183,22 +263,35 @@ impl CommandDispatcher {
 
         let tree = self.get_tree(key)?;
 
-        // try paths until fitting path is found
+        // try paths until fitting path is found, remembering how far the furthest one got
+        let mut fewest_left = raw_args.len();
         for path in tree.iter_paths() {
-            if Self::try_is_fitting_path(src, server, &path, tree, &mut raw_args.clone()).await? {
+            if Self::try_is_fitting_path(
+                src,
+                server,
+                &path,
+                tree,
+                &mut raw_args.clone(),
+                &mut fewest_left,
+            )
+            .await?
+            {
                 return Ok(());
             }
         }
-        Err(GeneralCommandIssue(format!(
-            "Invalid Syntax. Usage: {tree}"
-        )))
+
+        // Arguments are popped from the back, so this is the first one that didn't fit
+        Err(CommandError::syntax_error_at(
+            cmd,
+            raw_args.len() - fewest_left,
+        ))
     }
 
     pub(crate) fn get_tree(&self, key: &str) -> Result<&CommandTree, CommandError> {
         let command = self
             .commands
             .get(key)
-            .ok_or(GeneralCommandIssue("Command not found".to_string()))?;
+            .ok_or(CommandError::unknown_command())?;
 
         match command {
             Command::Tree(tree) => Ok(tree),
@@ -220,43 +313,42 @@ impl CommandDispatcher {
         self.permissions.get(key).copied()
     }
 
+    /// `fewest_left` is lowered to how many arguments were left when the path stopped fitting
     async fn try_is_fitting_path<'a>(
         src: &mut CommandSender<'a>,
         server: &'a Server,
         path: &[usize],
         tree: &'a CommandTree,
         raw_args: &mut RawArgs<'a>,
+        fewest_left: &mut usize,
     ) -> Result<bool, CommandError> {
         let mut parsed_args: ConsumedArgs = HashMap::new();
 
         for node in path.iter().map(|&i| &tree.nodes[i]) {
-            match &node.node_type {
+            let left = raw_args.len();
+            let fits = match &node.node_type {
                 NodeType::ExecuteLeaf { executor } => {
-                    return if raw_args.is_empty() {
+                    if raw_args.is_empty() {
                         executor.execute(src, server, &parsed_args).await?;
-                        Ok(true)
-                    } else {
-                        Ok(false)
-                    };
-                }
-                NodeType::Literal { string, .. } => {
-                    if raw_args.pop() != Some(string) {
-                        return Ok(false);
+                        return Ok(true);
                     }
+                    false
                 }
+                NodeType::Literal { string, .. } => raw_args.pop() == Some(string),
                 NodeType::Argument { consumer, name, .. } => {
                     match consumer.consume(src, server, raw_args).await {
                         Some(consumed) => {
                             parsed_args.insert(name, consumed);
+                            true
                         }
-                        None => return Ok(false),
-                    }
-                }
-                NodeType::Require { predicate, .. } => {
-                    if !predicate(src) {
-                        return Ok(false);
+                        None => false,
                     }
                 }
+                NodeType::Require { predicate, .. } => predicate(src),
+            };
+            if !fits {
+                *fewest_left = (*fewest_left).min(left);
+                return Ok(false);
             }
         }
 
@@ -349,7 +441,11 @@ impl CommandDispatcher {
 
 #[cfg(test)]
 mod test {
-    use crate::command::{commands::default_dispatcher, tree::CommandTree};
+    use crate::command::{
+        commands::default_dispatcher,
+        dispatcher::CommandError::{self, SyntaxError},
+        tree::CommandTree,
+    };
     use pumpkin_util::permission::PermissionLvl;
     #[test]
     fn test_dynamic_command() {
@@ -357,4 +453,23 @@ mod test {
         let tree = CommandTree::new(["test"], "test_desc");
         dispatcher.register(tree, PermissionLvl::Zero);
     }
+
+    #[test]
+    fn test_syntax_error_cursor() {
+        let cmd = "gamemode  survivl @s";
+        assert!(matches!(
+            CommandError::syntax_error_at(cmd, 0),
+            SyntaxError {
+                key: "command.unknown.argument",
+                cursor: 10
+            }
+        ));
+        assert!(matches!(
+            CommandError::syntax_error_at(cmd, 2),
+            SyntaxError {
+                key: "command.unknown.command",
+                cursor: 20
+            }
+        ));
+    }
 }
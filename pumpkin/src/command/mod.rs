@@ -11,6 +11,7 @@ use dispatcher::CommandError;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::permission::PermissionLvl;
 use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
 
 pub mod args;
 pub mod client_suggestions;
@@ -20,6 +21,8 @@ pub mod tree;
 
 pub enum CommandSender<'a> {
     Rcon(&'a tokio::sync::Mutex<Vec<String>>),
+    /// A request to the HTTP admin API, which collects the output like RCON
+    Api(&'a tokio::sync::Mutex<Vec<String>>),
     Console,
     Player(Arc<Player>),
 }
@@ -32,6 +35,7 @@ impl fmt::Display for CommandSender<'_> {
             match self {
                 CommandSender::Console => "Server",
                 CommandSender::Rcon(_) => "Rcon",
+                CommandSender::Api(_) => "API",
                 CommandSender::Player(p) => &p.gameprofile.name,
             }
         )
@@ -43,7 +47,33 @@ impl CommandSender<'_> {
         match self {
             CommandSender::Console => log::info!("{}", text.to_pretty_console()),
             CommandSender::Player(c) => c.send_system_message(&text).await,
-            CommandSender::Rcon(s) => s.lock().await.push(text.to_pretty_console()),
+            CommandSender::Rcon(s) | CommandSender::Api(s) => {
+                s.lock().await.push(text.to_pretty_console());
+            }
+        }
+    }
+
+    /// Sends the feedback of a command which changed something on the server, letting the other
+    /// operators know as well, like vanilla does
+    pub async fn send_feedback_to_ops(&self, server: &Server, text: TextComponent) {
+        self.send_message(text.clone()).await;
+
+        let admin_text = TextComponent::translate(
+            "chat.type.admin",
+            [TextComponent::text(self.to_string()), text],
+        )
+        .color_named(NamedColor::Gray)
+        .italic();
+        for player in server.get_all_players().await {
+            let is_sender = self
+                .as_player()
+                .is_some_and(|sender| sender.gameprofile.id == player.gameprofile.id);
+            if !is_sender && player.permission_lvl.load() > PermissionLvl::Zero {
+                player.send_system_message(&admin_text).await;
+            }
+        }
+        if !self.is_console() {
+            log::info!("{}", admin_text.to_pretty_console());
         }
     }
 
@@ -68,7 +98,9 @@ impl CommandSender<'_> {
     #[must_use]
     pub fn permission_lvl(&self) -> PermissionLvl {
         match self {
-            CommandSender::Console | CommandSender::Rcon(_) => PermissionLvl::Four,
+            CommandSender::Console | CommandSender::Rcon(_) | CommandSender::Api(_) => {
+                PermissionLvl::Four
+            }
             CommandSender::Player(p) => p.permission_lvl.load(),
         }
     }
@@ -76,7 +108,7 @@ impl CommandSender<'_> {
     #[must_use]
     pub fn has_permission_lvl(&self, lvl: PermissionLvl) -> bool {
         match self {
-            CommandSender::Console | CommandSender::Rcon(_) => true,
+            CommandSender::Console | CommandSender::Rcon(_) | CommandSender::Api(_) => true,
             CommandSender::Player(p) => p.permission_lvl.load().ge(&lvl),
         }
     }
@@ -84,16 +116,28 @@ impl CommandSender<'_> {
     #[must_use]
     pub fn position(&self) -> Option<Vector3<f64>> {
         match self {
-            CommandSender::Console | CommandSender::Rcon(..) => None,
+            CommandSender::Console | CommandSender::Rcon(..) | CommandSender::Api(..) => None,
             CommandSender::Player(p) => Some(p.living_entity.entity.pos.load()),
         }
     }
 
+    /// The yaw and pitch the sender looks in
+    #[must_use]
+    pub fn rotation(&self) -> Option<(f32, f32)> {
+        match self {
+            CommandSender::Console | CommandSender::Rcon(..) | CommandSender::Api(..) => None,
+            CommandSender::Player(p) => {
+                let entity = &p.living_entity.entity;
+                Some((entity.yaw.load(), entity.pitch.load()))
+            }
+        }
+    }
+
     #[must_use]
     pub async fn world(&self) -> Option<Arc<World>> {
         match self {
             // TODO: maybe return first world when console
-            CommandSender::Console | CommandSender::Rcon(..) => None,
+            CommandSender::Console | CommandSender::Rcon(..) | CommandSender::Api(..) => None,
             CommandSender::Player(p) => Some(p.living_entity.entity.world.read().await.clone()),
         }
     }
@@ -0,0 +1,265 @@
+use std::{
+    collections::VecDeque,
+    env,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    sync::{LazyLock, Mutex},
+};
+
+use chrono::{DateTime, FixedOffset, Local};
+use pumpkin_config::advanced_config;
+use pumpkin_util::{GameMode, PermissionLvl};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::command::CommandSender;
+
+use super::DATA_FOLDER;
+
+/// How many of the latest entries are kept in memory for `/auditlog search`
+const RECENT_ENTRIES: usize = 1000;
+
+pub static AUDIT_LOG: LazyLock<AuditLog> = LazyLock::new(|| {
+    let exe_dir = env::current_dir().unwrap();
+    AuditLog::open(exe_dir.join(DATA_FOLDER).join("audit-log.jsonl"))
+});
+
+/// Something an operator did
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AuditAction {
+    Command {
+        command: String,
+    },
+    GamemodeChange {
+        target: String,
+        gamemode: GameMode,
+    },
+    Op {
+        target: String,
+        level: PermissionLvl,
+    },
+    Deop {
+        target: String,
+    },
+    Kick {
+        target: String,
+        reason: String,
+    },
+    Ban {
+        target: String,
+        reason: String,
+    },
+    BanIp {
+        target: String,
+        reason: String,
+    },
+    Pardon {
+        target: String,
+    },
+    PardonIp {
+        target: String,
+    },
+}
+
+impl AuditAction {
+    fn describe(&self) -> String {
+        match self {
+            Self::Command { command } => format!("ran /{command}"),
+            Self::GamemodeChange { target, gamemode } => {
+                format!("set the gamemode of {target} to {gamemode:?}")
+            }
+            Self::Op { target, level } => format!("made {target} operator ({})", *level as u8),
+            Self::Deop { target } => format!("removed operator from {target}"),
+            Self::Kick { target, reason } => format!("kicked {target} ({reason})"),
+            Self::Ban { target, reason } => format!("banned {target} ({reason})"),
+            Self::BanIp { target, reason } => format!("banned the ip {target} ({reason})"),
+            Self::Pardon { target } => format!("unbanned {target}"),
+            Self::PardonIp { target } => format!("unbanned the ip {target}"),
+        }
+    }
+}
+
+/// A single line of the audit log
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub time: DateTime<FixedOffset>,
+    /// The name of the player, or `Server`, `Rcon` and `API`
+    pub actor: String,
+    pub actor_uuid: Option<Uuid>,
+    #[serde(flatten)]
+    pub action: AuditAction,
+}
+
+impl AuditEntry {
+    #[must_use]
+    pub fn new(actor: &CommandSender<'_>, action: AuditAction) -> Self {
+        Self {
+            time: Local::now().fixed_offset(),
+            actor: actor.to_string(),
+            actor_uuid: match actor {
+                CommandSender::Player(player) => Some(player.gameprofile.id),
+                CommandSender::Console | CommandSender::Rcon(_) | CommandSender::Api(_) => None,
+            },
+            action,
+        }
+    }
+
+    /// Whether the actor or what was done contains the query, ignoring case
+    #[must_use]
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.actor.to_lowercase().contains(&query)
+            || self.action.describe().to_lowercase().contains(&query)
+    }
+
+    /// The entry as a single line for chat or the console
+    #[must_use]
+    pub fn summary(&self) -> String {
+        format!(
+            "[{}] {} {}",
+            self.time.format("%Y-%m-%d %H:%M:%S"),
+            self.actor,
+            self.action.describe()
+        )
+    }
+}
+
+/// An append only log of operator actions, one JSON object per line
+pub struct AuditLog {
+    path: PathBuf,
+    inner: Mutex<AuditLogInner>,
+}
+
+struct AuditLogInner {
+    /// Opened on the first write
+    file: Option<File>,
+    recent: VecDeque<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Opens the log and reads its latest entries
+    #[must_use]
+    pub fn open(path: PathBuf) -> Self {
+        let mut recent = VecDeque::new();
+        if let Ok(file) = File::open(&path) {
+            for line in BufReader::new(file).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                match serde_json::from_str(&line) {
+                    Ok(entry) => {
+                        if recent.len() == RECENT_ENTRIES {
+                            recent.pop_front();
+                        }
+                        recent.push_back(entry);
+                    }
+                    Err(err) => log::warn!("Skipping invalid audit log entry: {err}"),
+                }
+            }
+        }
+
+        Self {
+            path,
+            inner: Mutex::new(AuditLogInner { file: None, recent }),
+        }
+    }
+
+    /// Appends the entry to the log file
+    pub fn record(&self, entry: AuditEntry) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Err(err) = self.append(&mut inner.file, &entry) {
+            log::error!(
+                "Couldn't write to the audit log {}: {err}",
+                self.path.display()
+            );
+        }
+
+        if inner.recent.len() == RECENT_ENTRIES {
+            inner.recent.pop_front();
+        }
+        inner.recent.push_back(entry);
+    }
+
+    fn append(&self, file: &mut Option<File>, entry: &AuditEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        if file.is_none() {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            *file = Some(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?,
+            );
+        }
+        let file = file.as_mut().unwrap();
+        file.write_all(line.as_bytes())?;
+        file.flush()
+    }
+
+    /// The latest entries matching the query, newest first
+    #[must_use]
+    pub fn search(&self, query: Option<&str>, limit: usize) -> Vec<AuditEntry> {
+        self.inner
+            .lock()
+            .unwrap()
+            .recent
+            .iter()
+            .rev()
+            .filter(|entry| query.is_none_or(|query| entry.matches(query)))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Records an action in the audit log, if it is enabled
+pub fn audit(actor: &CommandSender<'_>, action: AuditAction) {
+    if advanced_config().commands.audit_log {
+        AUDIT_LOG.record(AuditEntry::new(actor, action));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{AuditAction, AuditEntry, AuditLog};
+    use crate::command::CommandSender;
+
+    #[test]
+    fn entries_survive_reopening() {
+        let path = std::env::temp_dir().join(format!("audit-log-{}.jsonl", uuid::Uuid::new_v4()));
+        let log = AuditLog::open(path.clone());
+        log.record(AuditEntry::new(
+            &CommandSender::Console,
+            AuditAction::Ban {
+                target: "Alex".to_string(),
+                reason: "griefing".to_string(),
+            },
+        ));
+        log.record(AuditEntry::new(
+            &CommandSender::Console,
+            AuditAction::Command {
+                command: "time set day".to_string(),
+            },
+        ));
+
+        let log = AuditLog::open(path.clone());
+        let all = log.search(None, 10);
+        assert_eq!(all.len(), 2);
+        // Newest first
+        assert!(matches!(all[0].action, AuditAction::Command { .. }));
+
+        let found = log.search(Some("alex"), 10);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].actor, "Server");
+
+        fs::remove_file(path).unwrap();
+    }
+}
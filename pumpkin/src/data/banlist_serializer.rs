@@ -1,6 +1,8 @@
 use std::net::IpAddr;
 
 use chrono::{DateTime, FixedOffset, Local};
+use pumpkin_config::advanced_config;
+use pumpkin_util::text::TextComponent;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -35,6 +37,18 @@ impl BannedPlayerEntry {
             reason,
         }
     }
+
+    /// What the player is disconnected with
+    #[must_use]
+    pub fn kick_message(&self) -> TextComponent {
+        kick_message(
+            &advanced_config().bans.message,
+            "multiplayer.disconnect.banned",
+            &self.source,
+            self.expires,
+            &self.reason,
+        )
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -64,9 +78,55 @@ impl BannedIpEntry {
             reason,
         }
     }
+
+    /// What players joining from the IP are disconnected with
+    #[must_use]
+    pub fn kick_message(&self) -> TextComponent {
+        kick_message(
+            &advanced_config().bans.ip_message,
+            "multiplayer.disconnect.banned_ip",
+            &self.source,
+            self.expires,
+            &self.reason,
+        )
+    }
+}
+
+/// The configured message with its placeholders filled in, or the translated one of the client
+/// if none is configured
+fn kick_message(
+    message: &str,
+    translation_key: &str,
+    source: &str,
+    expires: Option<DateTime<FixedOffset>>,
+    reason: &str,
+) -> TextComponent {
+    let expires_text = expires.map(|expires| expires.format("%F at %T %Z").to_string());
+    if !message.is_empty() {
+        let expires_text =
+            expires_text.unwrap_or_else(|| advanced_config().bans.never_expires.clone());
+        return TextComponent::text(
+            message
+                .replace("{reason}", reason)
+                .replace("{source}", source)
+                .replace("{expires}", &expires_text),
+        );
+    }
+
+    let text = TextComponent::translate(
+        format!("{translation_key}.reason"),
+        [TextComponent::text(reason.to_string())],
+    );
+    match expires_text {
+        Some(expires) => text.add_child(TextComponent::translate(
+            format!("{translation_key}.expiration"),
+            [TextComponent::text(expires)],
+        )),
+        None => text,
+    }
 }
 
-mod format {
+pub(crate) mod format {
     const FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
 
     pub mod date {
@@ -131,3 +191,25 @@ mod format {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::text::TextComponent;
+
+    use super::kick_message;
+
+    #[test]
+    fn configured_kick_message() {
+        let message = kick_message(
+            "Banned by {source} until {expires}: {reason}",
+            "multiplayer.disconnect.banned",
+            "Server",
+            None,
+            "Griefing",
+        );
+        assert_eq!(
+            message,
+            TextComponent::text("Banned by Server until never: Griefing")
+        );
+    }
+}
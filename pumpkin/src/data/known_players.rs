@@ -0,0 +1,138 @@
+use std::{path::Path, sync::LazyLock};
+
+use chrono::{DateTime, FixedOffset, Local};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration, banlist_serializer::format};
+
+pub static KNOWN_PLAYERS: LazyLock<tokio::sync::RwLock<KnownPlayers>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(KnownPlayers::load()));
+
+/// Records that the player was just online and saves the list
+pub async fn remember(profile: &GameProfile) {
+    let mut known_players = KNOWN_PLAYERS.write().await;
+    known_players.seen(profile);
+    known_players.save();
+}
+
+/// How many players are remembered, the ones seen the longest time ago are forgotten first
+const MAX_KNOWN_PLAYERS: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownPlayer {
+    pub uuid: Uuid,
+    pub name: String,
+    #[serde(with = "format::date")]
+    pub last_seen: DateTime<FixedOffset>,
+}
+
+impl KnownPlayer {
+    /// The profile of the player as far as it is known, without the skin
+    #[must_use]
+    pub fn profile(&self) -> GameProfile {
+        GameProfile {
+            id: self.uuid,
+            name: self.name.clone(),
+            properties: Vec::new(),
+            profile_actions: None,
+        }
+    }
+}
+
+/// Every player who joined the server, so commands can find players who are offline
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct KnownPlayers {
+    pub players: Vec<KnownPlayer>,
+}
+
+impl KnownPlayers {
+    /// Remembers the player as seen right now, under the name they have now
+    pub fn seen(&mut self, profile: &GameProfile) {
+        self.seen_at(profile, Local::now().fixed_offset());
+    }
+
+    fn seen_at(&mut self, profile: &GameProfile, time: DateTime<FixedOffset>) {
+        self.players.retain(|player| player.uuid != profile.id);
+        self.players.push(KnownPlayer {
+            uuid: profile.id,
+            name: profile.name.clone(),
+            last_seen: time,
+        });
+        if self.players.len() > MAX_KNOWN_PLAYERS {
+            self.players.sort_by_key(|player| player.last_seen);
+            let forgotten = self.players.len() - MAX_KNOWN_PLAYERS;
+            self.players.drain(..forgotten);
+        }
+    }
+
+    /// Names are compared ignoring the case like the client does
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<&KnownPlayer> {
+        self.players
+            .iter()
+            .find(|player| player.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl LoadJSONConfiguration for KnownPlayers {
+    fn get_path() -> &'static Path {
+        Path::new("known-players.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the list
+    }
+}
+
+impl SaveJSONConfiguration for KnownPlayers {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Local};
+    use uuid::Uuid;
+
+    use crate::net::GameProfile;
+
+    use super::{KnownPlayers, MAX_KNOWN_PLAYERS};
+
+    fn profile(name: &str, id: u128) -> GameProfile {
+        GameProfile {
+            id: Uuid::from_u128(id),
+            name: name.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        }
+    }
+
+    #[test]
+    fn renamed_players_are_found_by_their_new_name() {
+        let mut known = KnownPlayers::default();
+        known.seen(&profile("Steve", 1));
+        known.seen(&profile("Alex", 2));
+        known.seen(&profile("NotSteve", 1));
+
+        assert_eq!(known.players.len(), 2);
+        assert!(known.get_by_name("Steve").is_none());
+        assert_eq!(
+            known.get_by_name("notsteve").map(|player| player.uuid),
+            Some(Uuid::from_u128(1))
+        );
+    }
+
+    #[test]
+    fn forgets_the_oldest_players() {
+        let mut known = KnownPlayers::default();
+        let start = Local::now().fixed_offset();
+        for id in 0..=MAX_KNOWN_PLAYERS as u128 {
+            let time = start + Duration::seconds(id as i64);
+            known.seen_at(&profile(&format!("player{id}"), id), time);
+        }
+
+        assert_eq!(known.players.len(), MAX_KNOWN_PLAYERS);
+        assert!(known.get_by_name("player0").is_none());
+        assert!(known.get_by_name("player1").is_some());
+    }
+}
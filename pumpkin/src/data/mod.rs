@@ -6,9 +6,14 @@ const DATA_FOLDER: &str = "data/";
 
 pub mod op_data;
 
+pub mod audit_log;
+
 pub mod banlist_serializer;
 pub mod banned_ip_data;
 pub mod banned_player_data;
+pub mod known_players;
+pub mod muted_player_data;
+pub mod whitelist_data;
 
 pub trait LoadJSONConfiguration {
     #[must_use]
@@ -0,0 +1,55 @@
+use std::{path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static MUTED_PLAYER_LIST: LazyLock<tokio::sync::RwLock<MutedPlayerList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(MutedPlayerList::load()));
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MutedPlayerEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    pub source: String,
+    pub reason: String,
+}
+
+impl MutedPlayerEntry {
+    #[must_use]
+    pub fn new(profile: &GameProfile, source: String, reason: String) -> Self {
+        Self {
+            uuid: profile.id,
+            name: profile.name.clone(),
+            source,
+            reason,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct MutedPlayerList {
+    pub muted_players: Vec<MutedPlayerEntry>,
+}
+
+impl MutedPlayerList {
+    #[must_use]
+    pub fn get_entry(&self, uuid: &Uuid) -> Option<&MutedPlayerEntry> {
+        self.muted_players.iter().find(|entry| entry.uuid == *uuid)
+    }
+}
+
+impl LoadJSONConfiguration for MutedPlayerList {
+    fn get_path() -> &'static Path {
+        Path::new("muted-players.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the list
+    }
+}
+
+impl SaveJSONConfiguration for MutedPlayerList {}
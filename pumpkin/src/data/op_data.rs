@@ -1,6 +1,7 @@
 use std::{path::Path, sync::LazyLock};
 
-use pumpkin_config::op;
+use pumpkin_config::{advanced_config, op};
+use pumpkin_util::PermissionLvl;
 use serde::{Deserialize, Serialize};
 
 use super::{LoadJSONConfiguration, SaveJSONConfiguration};
@@ -14,6 +15,17 @@ pub struct OperatorConfig {
     pub ops: Vec<op::Op>,
 }
 
+impl OperatorConfig {
+    /// The level of the operator, or the default level for everyone else
+    #[must_use]
+    pub fn get_permission_lvl(&self, uuid: uuid::Uuid) -> PermissionLvl {
+        self.ops
+            .iter()
+            .find(|op| op.uuid == uuid)
+            .map_or(advanced_config().commands.default_op_level, |op| op.level)
+    }
+}
+
 impl LoadJSONConfiguration for OperatorConfig {
     fn get_path() -> &'static Path {
         Path::new("ops.json")
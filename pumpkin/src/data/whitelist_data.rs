@@ -0,0 +1,112 @@
+use std::{path::Path, sync::LazyLock};
+
+use pumpkin_config::advanced_config;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration, op_data::OPERATOR_CONFIG};
+
+pub static WHITELIST: LazyLock<tokio::sync::RwLock<Whitelist>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(Whitelist::load()));
+
+/// Whether the player may join with the current whitelist config, operators always may
+pub async fn may_join(uuid: Uuid) -> bool {
+    !advanced_config().whitelist.enabled
+        || WHITELIST.read().await.contains(uuid)
+        || OPERATOR_CONFIG
+            .read()
+            .await
+            .ops
+            .iter()
+            .any(|op| op.uuid == uuid)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhitelistEntry {
+    pub uuid: Uuid,
+    pub name: String,
+}
+
+impl WhitelistEntry {
+    #[must_use]
+    pub fn new(profile: &GameProfile) -> Self {
+        Self {
+            uuid: profile.id,
+            name: profile.name.clone(),
+        }
+    }
+}
+
+/// The players who may join while the whitelist is enabled, in the format of vanilla
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct Whitelist {
+    pub players: Vec<WhitelistEntry>,
+}
+
+impl Whitelist {
+    #[must_use]
+    pub fn contains(&self, uuid: Uuid) -> bool {
+        self.players.iter().any(|entry| entry.uuid == uuid)
+    }
+
+    /// Returns false if the player was whitelisted already
+    pub fn add(&mut self, profile: &GameProfile) -> bool {
+        if self.contains(profile.id) {
+            return false;
+        }
+        self.players.push(WhitelistEntry::new(profile));
+        true
+    }
+
+    /// Returns false if the player wasn't whitelisted
+    pub fn remove(&mut self, uuid: Uuid) -> bool {
+        let len = self.players.len();
+        self.players.retain(|entry| entry.uuid != uuid);
+        len != self.players.len()
+    }
+}
+
+impl LoadJSONConfiguration for Whitelist {
+    fn get_path() -> &'static Path {
+        Path::new("whitelist.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the whitelist
+    }
+}
+
+impl SaveJSONConfiguration for Whitelist {}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::net::GameProfile;
+
+    use super::Whitelist;
+
+    #[test]
+    fn vanilla_format() {
+        let json = r#"[{"uuid":"00000000-0000-0000-0000-000000000001","name":"Steve"}]"#;
+        let mut whitelist: Whitelist = serde_json::from_str(json).unwrap();
+        let alex = GameProfile {
+            id: Uuid::from_u128(2),
+            name: "Alex".to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        };
+
+        assert!(whitelist.contains(Uuid::from_u128(1)));
+        assert!(whitelist.add(&alex));
+        assert!(!whitelist.add(&alex));
+        assert!(whitelist.remove(Uuid::from_u128(1)));
+        assert!(!whitelist.remove(Uuid::from_u128(1)));
+        assert_eq!(
+            serde_json::to_string(&whitelist).unwrap(),
+            r#"[{"uuid":"00000000-0000-0000-0000-000000000002","name":"Alex"}]"#
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use pumpkin_util::math::vector3::Vector3;
+use rand::{Rng, thread_rng};
+
+use crate::entity::mob::MobEntity;
+
+use super::{Control, Goal};
+
+/// How far away the mob runs
+const FLEE_DISTANCE: f64 = 8.0;
+/// Players further away than this are not run away from, the mob runs in a random direction
+const THREAT_RANGE: f64 = 16.0;
+
+/// Runs away after getting hurt, like animals panicking
+pub struct FleeGoal {
+    speed: f64,
+}
+
+impl FleeGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self { speed }
+    }
+}
+
+#[async_trait]
+impl Goal for FleeGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        let living = &mob.living_entity;
+        // Getting hurt makes the mob invulnerable for a moment
+        living.time_until_regen.load(Ordering::Relaxed) > 0 && living.health.load() > 0.0
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        !mob.navigator.lock().await.is_idle()
+    }
+
+    async fn tick(&self, _mob: &MobEntity) {}
+
+    async fn start(&self, mob: &MobEntity) {
+        let entity = &mob.living_entity.entity;
+        let pos = entity.pos.load();
+        let world = entity.world.read().await.clone();
+        let threat = world.get_closest_player(pos, THREAT_RANGE).await;
+
+        let direction = threat.map_or_else(
+            || {
+                let angle = thread_rng().gen_range(0.0..std::f64::consts::TAU);
+                Vector3::new(angle.cos(), 0.0, angle.sin())
+            },
+            |player| {
+                let away = pos.sub(&player.living_entity.entity.pos.load());
+                Vector3::new(away.x, 0.0, away.z)
+            },
+        );
+        // A player standing right on top of the mob gives no direction
+        let direction = if direction.horizontal_length_squared() < 1.0e-4 {
+            Vector3::new(1.0, 0.0, 0.0)
+        } else {
+            direction.normalize()
+        };
+        let destination = pos.add(&direction.multiply(FLEE_DISTANCE, 0.0, FLEE_DISTANCE));
+
+        mob.navigator
+            .lock()
+            .await
+            .navigate_to(pos, destination, self.speed);
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        mob.navigator.lock().await.cancel();
+    }
+
+    fn controls(&self) -> &[Control] {
+        &[Control::Move]
+    }
+}
@@ -5,7 +5,7 @@ use tokio::sync::Mutex;
 
 use crate::entity::{mob::MobEntity, player::Player};
 
-use super::Goal;
+use super::{Control, Goal};
 
 pub struct LookAtEntityGoal {
     // TODO: make this an entity
@@ -55,4 +55,8 @@ impl Goal for LookAtEntityGoal {
             mob.living_entity.entity.look_at(target_pos).await;
         }
     }
+
+    fn controls(&self) -> &[Control] {
+        &[Control::Look]
+    }
 }
@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use async_trait::async_trait;
+use pumpkin_data::damage::DamageType;
+
+use crate::entity::mob::MobEntity;
+
+use super::{Control, Goal};
+
+/// Ticks between two attacks
+const ATTACK_INTERVAL: i32 = 20;
+
+/// How close (squared) the target has to be to get hit, wider mobs reach further
+#[must_use]
+pub fn attack_reach_squared(mob_width: f32, target_width: f32) -> f64 {
+    let reach = f64::from(mob_width) * 2.0;
+    reach.mul_add(reach, f64::from(target_width))
+}
+
+/// Walks to the target of the mob and hits it, the target is picked by a `TargetGoal`
+pub struct MeleeAttackGoal {
+    speed: f64,
+    damage: f32,
+    cooldown: AtomicI32,
+}
+
+impl MeleeAttackGoal {
+    #[must_use]
+    pub const fn new(speed: f64, damage: f32) -> Self {
+        Self {
+            speed,
+            damage,
+            cooldown: AtomicI32::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for MeleeAttackGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        mob.target.lock().await.is_some()
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        mob.target.lock().await.is_some()
+    }
+
+    async fn tick(&self, mob: &MobEntity) {
+        let Some(target) = mob.target.lock().await.clone() else {
+            return;
+        };
+        let entity = &mob.living_entity.entity;
+        let target_entity = &target.living_entity.entity;
+        let target_pos = target_entity.pos.load();
+
+        entity.look_at(target_pos).await;
+        mob.navigator
+            .lock()
+            .await
+            .navigate_to(entity.pos.load(), target_pos, self.speed);
+
+        let cooldown = (self.cooldown.load(Ordering::Relaxed) - 1).max(0);
+        self.cooldown.store(cooldown, Ordering::Relaxed);
+        let reach = attack_reach_squared(
+            entity.entity_type.dimension[0],
+            target_entity.entity_type.dimension[0],
+        );
+        if cooldown == 0 && entity.pos.load().squared_distance_to_vec(target_pos) <= reach {
+            self.cooldown.store(ATTACK_INTERVAL, Ordering::Relaxed);
+            let world = entity.world.read().await.clone();
+            world
+                .damage_entity(target.clone(), self.damage, DamageType::MOB_ATTACK)
+                .await;
+        }
+    }
+
+    async fn start(&self, _mob: &MobEntity) {
+        self.cooldown.store(0, Ordering::Relaxed);
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        mob.navigator.lock().await.cancel();
+    }
+
+    fn controls(&self) -> &[Control] {
+        &[Control::Move, Control::Look]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::entity::EntityType;
+
+    use super::attack_reach_squared;
+
+    #[test]
+    fn zombies_reach_players_next_to_them() {
+        let reach = attack_reach_squared(
+            EntityType::ZOMBIE.dimension[0],
+            EntityType::PLAYER.dimension[0],
+        );
+        assert!((reach.sqrt() - 1.43).abs() < 0.01);
+    }
+}
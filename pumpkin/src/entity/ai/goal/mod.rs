@@ -1,9 +1,22 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 
 use crate::entity::mob::MobEntity;
 
+pub mod flee;
 pub mod look_at_entity;
+pub mod melee_attack;
 pub mod target_goal;
+pub mod wander;
+
+/// What a goal takes over while it runs, two goals needing the same control never run together
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Control {
+    Move,
+    Look,
+    Target,
+}
 
 #[async_trait]
 pub trait Goal: Send + Sync {
@@ -13,4 +26,142 @@ pub trait Goal: Send + Sync {
     async fn should_continue(&self, mob: &MobEntity) -> bool;
     /// If the Goal is running, this gets called every tick
     async fn tick(&self, mob: &MobEntity);
+    /// Called once when the goal starts running
+    async fn start(&self, _mob: &MobEntity) {}
+    /// Called once when the goal stops running, either by itself or because another goal took over
+    async fn stop(&self, _mob: &MobEntity) {}
+    fn controls(&self) -> &[Control] {
+        &[]
+    }
+}
+
+struct PrioritizedGoal {
+    priority: u8,
+    goal: Arc<dyn Goal>,
+    running: bool,
+}
+
+/// Runs the goals of a mob, goals with a lower priority number win over the others
+#[derive(Default)]
+pub struct GoalSelector {
+    goals: Vec<PrioritizedGoal>,
+}
+
+impl GoalSelector {
+    pub fn add(&mut self, priority: u8, goal: Arc<dyn Goal>) {
+        // Goals with the same priority keep the order they were added in
+        let index = self
+            .goals
+            .partition_point(|other| other.priority <= priority);
+        self.goals.insert(
+            index,
+            PrioritizedGoal {
+                priority,
+                goal,
+                running: false,
+            },
+        );
+    }
+
+    /// The running goal which has the control
+    fn holder(&self, control: Control) -> Option<usize> {
+        self.goals
+            .iter()
+            .position(|entry| entry.running && entry.goal.controls().contains(&control))
+    }
+
+    /// Whether the goal may take all of its controls from the goals having them now
+    fn can_replace(&self, index: usize) -> bool {
+        let candidate = &self.goals[index];
+        candidate.goal.controls().iter().all(|control| {
+            self.holder(*control)
+                .is_none_or(|holder| self.goals[holder].priority > candidate.priority)
+        })
+    }
+
+    pub async fn tick(&mut self, mob: &MobEntity) {
+        for entry in &mut self.goals {
+            if entry.running && !entry.goal.should_continue(mob).await {
+                entry.running = false;
+                entry.goal.stop(mob).await;
+            }
+        }
+
+        for index in 0..self.goals.len() {
+            if self.goals[index].running || !self.can_replace(index) {
+                continue;
+            }
+            let goal = self.goals[index].goal.clone();
+            if !goal.can_start(mob).await {
+                continue;
+            }
+            for control in goal.controls() {
+                if let Some(holder) = self.holder(*control) {
+                    self.goals[holder].running = false;
+                    self.goals[holder].goal.stop(mob).await;
+                }
+            }
+            self.goals[index].running = true;
+            goal.start(mob).await;
+        }
+
+        for entry in &self.goals {
+            if entry.running {
+                entry.goal.tick(mob).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+
+    use crate::entity::mob::MobEntity;
+
+    use super::{Control, Goal, GoalSelector};
+
+    struct TestGoal(&'static [Control]);
+
+    #[async_trait]
+    impl Goal for TestGoal {
+        async fn can_start(&self, _mob: &MobEntity) -> bool {
+            true
+        }
+        async fn should_continue(&self, _mob: &MobEntity) -> bool {
+            true
+        }
+        async fn tick(&self, _mob: &MobEntity) {}
+        fn controls(&self) -> &[Control] {
+            self.0
+        }
+    }
+
+    #[test]
+    fn goals_with_a_lower_priority_number_take_the_controls() {
+        let mut selector = GoalSelector::default();
+        selector.add(6, Arc::new(TestGoal(&[Control::Move])));
+        selector.add(2, Arc::new(TestGoal(&[Control::Move, Control::Look])));
+        selector.add(8, Arc::new(TestGoal(&[Control::Look])));
+        selector.add(6, Arc::new(TestGoal(&[Control::Move])));
+        let priorities = selector
+            .goals
+            .iter()
+            .map(|entry| entry.priority)
+            .collect::<Vec<_>>();
+        assert_eq!(priorities, [2, 6, 6, 8]);
+
+        // The wander like goal runs, the other one with the same priority has to wait
+        selector.goals[1].running = true;
+        assert!(selector.can_replace(0));
+        assert!(!selector.can_replace(2));
+        assert!(selector.can_replace(3));
+
+        selector.goals[1].running = false;
+        selector.goals[0].running = true;
+        assert!(!selector.can_replace(1));
+        assert!(!selector.can_replace(3));
+    }
 }
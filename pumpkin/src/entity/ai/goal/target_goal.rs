@@ -1,25 +1,19 @@
-use std::sync::Arc;
-
 use async_trait::async_trait;
-use tokio::sync::Mutex;
 
-use crate::entity::{ai::path::NavigatorGoal, mob::MobEntity, player::Player};
+use crate::entity::mob::MobEntity;
 
-use super::Goal;
+use super::{Control, Goal};
 
+/// Picks the closest player as the target of the mob, other goals like `MeleeAttackGoal` go
+/// after it
 pub struct TargetGoal {
-    // TODO: make this an entity
-    target: Mutex<Option<Arc<Player>>>,
     range: f64,
 }
 
 impl TargetGoal {
     #[must_use]
-    pub fn new(range: f64) -> Self {
-        Self {
-            target: Mutex::new(None),
-            range,
-        }
+    pub const fn new(range: f64) -> Self {
+        Self { range }
     }
 }
 
@@ -27,10 +21,8 @@ impl TargetGoal {
 impl Goal for TargetGoal {
     async fn can_start(&self, mob: &MobEntity) -> bool {
         // TODO: make this an entity
-        let mut target = self.target.lock().await;
-
         // gets the closest entity (currently player)
-        *target = mob
+        let mut target = mob
             .living_entity
             .entity
             .world
@@ -41,33 +33,33 @@ impl Goal for TargetGoal {
         // we can't use filter, because of async clousrers
         if let Some(player) = target.as_ref() {
             if player.abilities.lock().await.invulnerable {
-                *target = None;
+                target = None;
             }
         }
 
-        target.is_some()
+        let found = target.is_some();
+        *mob.target.lock().await = target;
+        found
     }
     async fn should_continue(&self, mob: &MobEntity) -> bool {
         // if an entity is found, lets check so its in range
-        if let Some(target) = self.target.lock().await.as_ref() {
+        if let Some(target) = mob.target.lock().await.as_ref() {
             let mob_pos = mob.living_entity.entity.pos.load();
             let target_pos = target.living_entity.entity.pos.load();
             let abilities = target.abilities.lock().await;
             return !abilities.invulnerable
+                && target.living_entity.health.load() > 0.0
                 && mob_pos.squared_distance_to_vec(target_pos) <= (self.range * self.range);
         }
         false
     }
-    async fn tick(&self, mob: &MobEntity) {
-        if let Some(target) = self.target.lock().await.as_ref() {
-            let mut navigator = mob.navigator.lock().await;
-            let target_player = target.living_entity.entity.pos.load();
+    async fn tick(&self, _mob: &MobEntity) {}
 
-            navigator.set_progress(NavigatorGoal {
-                current_progress: mob.living_entity.entity.pos.load(),
-                destination: target_player,
-                speed: 0.1,
-            });
-        }
+    async fn stop(&self, mob: &MobEntity) {
+        *mob.target.lock().await = None;
+    }
+
+    fn controls(&self) -> &[Control] {
+        &[Control::Target]
     }
 }
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use pumpkin_util::math::vector3::Vector3;
+use rand::{Rng, thread_rng};
+
+use crate::entity::mob::MobEntity;
+
+use super::{Control, Goal};
+
+/// On average a mob starts walking around once every 6 seconds
+const CHANCE: u32 = 120;
+const HORIZONTAL_RANGE: i32 = 10;
+const VERTICAL_RANGE: i32 = 7;
+
+/// Walks to random spots around the mob from time to time
+pub struct WanderGoal {
+    speed: f64,
+}
+
+impl WanderGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self { speed }
+    }
+}
+
+#[async_trait]
+impl Goal for WanderGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        mob.navigator.lock().await.is_idle() && thread_rng().gen_ratio(1, CHANCE)
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        !mob.navigator.lock().await.is_idle()
+    }
+
+    async fn tick(&self, _mob: &MobEntity) {}
+
+    async fn start(&self, mob: &MobEntity) {
+        let pos = mob.living_entity.entity.pos.load();
+        let destination = {
+            let mut rng = thread_rng();
+            pos.add(&Vector3::new(
+                f64::from(rng.gen_range(-HORIZONTAL_RANGE..=HORIZONTAL_RANGE)),
+                f64::from(rng.gen_range(-VERTICAL_RANGE..=VERTICAL_RANGE)),
+                f64::from(rng.gen_range(-HORIZONTAL_RANGE..=HORIZONTAL_RANGE)),
+            ))
+        };
+        // Spots which can't be reached make the navigator give up, which ends the goal
+        mob.navigator
+            .lock()
+            .await
+            .navigate_to(pos, destination, self.speed);
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        mob.navigator.lock().await.cancel();
+    }
+
+    fn controls(&self) -> &[Control] {
+        &[Control::Move]
+    }
+}
@@ -0,0 +1,417 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use pumpkin_util::math::vector3::Vector3;
+
+use super::node::{NodeView, PathNodeType};
+
+/// How many blocks a mob is willing to drop down in a single step
+const MAX_FALL_DISTANCE: i32 = 3;
+
+/// Horizontal directions, straight ones first
+const DIRECTIONS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// A path found by `PathSearch`, a list of block positions (the feet of the mob)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    nodes: Vec<Vector3<i32>>,
+    next: usize,
+    /// Whether the path ends at the destination, or only gets as close as possible
+    reaches_target: bool,
+}
+
+impl Path {
+    #[must_use]
+    pub fn nodes(&self) -> &[Vector3<i32>] {
+        &self.nodes
+    }
+
+    #[must_use]
+    pub fn next_node(&self) -> Option<Vector3<i32>> {
+        self.nodes.get(self.next).copied()
+    }
+
+    pub fn advance(&mut self) {
+        self.next += 1;
+    }
+
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.next >= self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn reaches_target(&self) -> bool {
+        self.reaches_target
+    }
+
+    #[must_use]
+    pub fn end(&self) -> Option<Vector3<i32>> {
+        self.nodes.last().copied()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct OpenNode {
+    pos: Vector3<i32>,
+    cost: f32,
+    estimate: f32,
+}
+
+impl OpenNode {
+    fn total(&self) -> f32 {
+        self.cost + self.estimate
+    }
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    // Reversed, so the `BinaryHeap` pops the cheapest node first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .total()
+            .total_cmp(&self.total())
+            .then_with(|| other.estimate.total_cmp(&self.estimate))
+    }
+}
+
+/// The state of a search, see `PathSearch::step`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchState {
+    /// The budget of this step ran out, call `step` again
+    Running,
+    Found(Path),
+    /// There is no way to get closer to the destination than where the mob already is
+    NotFound,
+}
+
+/// An incremental A* search over walkable nodes.
+///
+/// The search can be spread over multiple ticks by calling `step` with a budget of nodes.
+pub struct PathSearch {
+    start: Vector3<i32>,
+    destination: Vector3<i32>,
+    open: BinaryHeap<OpenNode>,
+    /// The cheapest known cost of every visited node and the node it was reached from
+    visited: HashMap<Vector3<i32>, (f32, Option<Vector3<i32>>)>,
+    closest: (Vector3<i32>, f32),
+    max_nodes: usize,
+    expanded: usize,
+}
+
+impl PathSearch {
+    #[must_use]
+    pub fn new(start: Vector3<i32>, destination: Vector3<i32>, max_nodes: usize) -> Self {
+        let estimate = distance(start, destination);
+        let mut open = BinaryHeap::new();
+        open.push(OpenNode {
+            pos: start,
+            cost: 0.0,
+            estimate,
+        });
+        let mut visited = HashMap::new();
+        visited.insert(start, (0.0, None));
+        Self {
+            start,
+            destination,
+            open,
+            visited,
+            closest: (start, estimate),
+            max_nodes,
+            expanded: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn destination(&self) -> Vector3<i32> {
+        self.destination
+    }
+
+    /// Expands up to `budget` nodes
+    pub async fn step(&mut self, view: &dyn NodeView, budget: usize) -> SearchState {
+        for _ in 0..budget {
+            let Some(node) = self.open.pop() else {
+                return self.finish(false);
+            };
+            // Skip outdated entries, the node was reached cheaper in the meantime
+            if self
+                .visited
+                .get(&node.pos)
+                .is_some_and(|(cost, _)| *cost < node.cost)
+            {
+                continue;
+            }
+            if node.pos == self.destination {
+                self.closest = (node.pos, 0.0);
+                return self.finish(true);
+            }
+            if node.estimate < self.closest.1 {
+                self.closest = (node.pos, node.estimate);
+            }
+
+            self.expanded += 1;
+            if self.expanded >= self.max_nodes {
+                return self.finish(false);
+            }
+
+            for (neighbor, malus) in neighbors(view, node.pos).await {
+                let cost = node.cost + distance(node.pos, neighbor) + malus;
+                if self
+                    .visited
+                    .get(&neighbor)
+                    .is_some_and(|(known, _)| *known <= cost)
+                {
+                    continue;
+                }
+                self.visited.insert(neighbor, (cost, Some(node.pos)));
+                self.open.push(OpenNode {
+                    pos: neighbor,
+                    cost,
+                    estimate: distance(neighbor, self.destination),
+                });
+            }
+        }
+        SearchState::Running
+    }
+
+    fn finish(&self, reaches_target: bool) -> SearchState {
+        let end = self.closest.0;
+        if end == self.start {
+            return SearchState::NotFound;
+        }
+
+        let mut nodes = vec![end];
+        let mut current = end;
+        while let Some((_, Some(parent))) = self.visited.get(&current) {
+            if *parent == self.start {
+                break;
+            }
+            nodes.push(*parent);
+            current = *parent;
+        }
+        nodes.reverse();
+        SearchState::Found(Path {
+            nodes,
+            next: 0,
+            reaches_target,
+        })
+    }
+}
+
+fn distance(from: Vector3<i32>, to: Vector3<i32>) -> f32 {
+    let x = (from.x - to.x) as f32;
+    let y = (from.y - to.y) as f32;
+    let z = (from.z - to.z) as f32;
+    (x * x + y * y + z * z).sqrt()
+}
+
+/// Finds the node a mob ends up on when it moves from `from` to the column `x`, `z`: either on the
+/// same height, one block up or by falling down
+async fn reachable_node(
+    view: &dyn NodeView,
+    from: Vector3<i32>,
+    x: i32,
+    z: i32,
+) -> Option<(Vector3<i32>, f32)> {
+    let target = Vector3::new(x, from.y, z);
+    let node_type = view.node_type(target).await;
+    match node_type {
+        PathNodeType::Open => {
+            for drop in 1..=MAX_FALL_DISTANCE {
+                let below = Vector3::new(x, from.y - drop, z);
+                match view.node_type(below).await {
+                    PathNodeType::Open => {}
+                    node_type => return node_type.malus().map(|malus| (below, malus)),
+                }
+            }
+            None
+        }
+        PathNodeType::Blocked => {
+            // Step up, which needs room above the mob's head
+            let up = Vector3::new(x, from.y + 1, z);
+            let above_head = Vector3::new(from.x, from.y + 2, from.z);
+            if view.node_type(up).await != PathNodeType::Walkable
+                || view.node_type(above_head).await == PathNodeType::Blocked
+            {
+                return None;
+            }
+            Some((up, 0.0))
+        }
+        node_type => node_type.malus().map(|malus| (target, malus)),
+    }
+}
+
+async fn neighbors(view: &dyn NodeView, from: Vector3<i32>) -> Vec<(Vector3<i32>, f32)> {
+    let mut found = Vec::with_capacity(DIRECTIONS.len() + 2);
+    // Climbing goes straight up or down, as long as there is something to climb or stand on
+    if view.node_type(from).await == PathNodeType::Climbable {
+        for dy in [1, -1] {
+            let next = Vector3::new(from.x, from.y + dy, from.z);
+            if matches!(
+                view.node_type(next).await,
+                PathNodeType::Climbable | PathNodeType::Walkable
+            ) {
+                found.push((next, 0.0));
+            }
+        }
+    }
+
+    // Whether the straight neighbors are on the same height, diagonals need both to not cut corners
+    let mut straight_level = [false; 4];
+    for (i, (dx, dz)) in DIRECTIONS.into_iter().enumerate() {
+        if i >= 4 {
+            let x_index = usize::from(dx <= 0);
+            let z_index = 2 + usize::from(dz <= 0);
+            if !straight_level[x_index] || !straight_level[z_index] {
+                continue;
+            }
+        }
+        if let Some((node, malus)) = reachable_node(view, from, from.x + dx, from.z + dz).await {
+            if i < 4 {
+                straight_level[i] = node.y == from.y;
+            } else if node.y != from.y {
+                continue;
+            }
+            found.push((node, malus));
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use async_trait::async_trait;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{PathSearch, SearchState};
+    use crate::entity::ai::path::node::{NodeView, PathNodeType};
+
+    /// A flat floor at y = 0 with walls on some positions
+    struct Floor {
+        walls: HashSet<(i32, i32)>,
+    }
+
+    #[async_trait]
+    impl NodeView for Floor {
+        async fn node_type(&self, pos: Vector3<i32>) -> PathNodeType {
+            if self.walls.contains(&(pos.x, pos.z)) {
+                return PathNodeType::Blocked;
+            }
+            match pos.y {
+                0 => PathNodeType::Walkable,
+                y if y > 0 => PathNodeType::Open,
+                _ => PathNodeType::Blocked,
+            }
+        }
+    }
+
+    /// A ladder at x = 1 leading onto a three blocks high wall starting at x = 2
+    struct Ladder;
+
+    #[async_trait]
+    impl NodeView for Ladder {
+        async fn node_type(&self, pos: Vector3<i32>) -> PathNodeType {
+            if pos.x >= 2 {
+                return match pos.y {
+                    3 => PathNodeType::Walkable,
+                    y if y > 3 => PathNodeType::Open,
+                    _ => PathNodeType::Blocked,
+                };
+            }
+            match pos.y {
+                0..=3 if pos.x == 1 && pos.z == 0 => PathNodeType::Climbable,
+                0 => PathNodeType::Walkable,
+                y if y > 0 => PathNodeType::Open,
+                _ => PathNodeType::Blocked,
+            }
+        }
+    }
+
+    async fn search(view: &dyn NodeView, to: Vector3<i32>) -> SearchState {
+        let mut search = PathSearch::new(Vector3::new(0, 0, 0), to, 1000);
+        loop {
+            match search.step(view, 8).await {
+                SearchState::Running => {}
+                state => return state,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn walks_around_a_wall() {
+        let view = Floor {
+            walls: (-3..=3).map(|z| (2, z)).collect(),
+        };
+        let SearchState::Found(path) = search(&view, Vector3::new(4, 0, 0)).await else {
+            panic!("no path found");
+        };
+        assert!(path.reaches_target());
+        assert_eq!(path.end(), Some(Vector3::new(4, 0, 0)));
+        assert!(
+            path.nodes()
+                .iter()
+                .all(|node| !view.walls.contains(&(node.x, node.z)))
+        );
+    }
+
+    #[tokio::test]
+    async fn climbs_a_ladder() {
+        let SearchState::Found(path) = search(&Ladder, Vector3::new(3, 3, 0)).await else {
+            panic!("no path found");
+        };
+        assert!(path.reaches_target());
+        assert!(path.nodes().contains(&Vector3::new(1, 2, 0)));
+    }
+
+    #[tokio::test]
+    async fn gets_as_close_as_possible() {
+        // The destination is walled in
+        let view = Floor {
+            walls: [
+                (4, 1),
+                (4, -1),
+                (3, 0),
+                (5, 0),
+                (3, 1),
+                (5, 1),
+                (3, -1),
+                (5, -1),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let SearchState::Found(path) = search(&view, Vector3::new(4, 0, 0)).await else {
+            panic!("no path found");
+        };
+        assert!(!path.reaches_target());
+        // One of the nodes two blocks away from the destination
+        let end = path.end().unwrap();
+        assert_eq!((end.x - 4).pow(2) + (end.z).pow(2), 4);
+    }
+}
@@ -1,110 +1,150 @@
+use astar::{Path, PathSearch, SearchState};
 use pumpkin_protocol::client::play::CUpdateEntityPos;
 use pumpkin_util::math::vector3::Vector3;
 
 use crate::entity::living::LivingEntity;
 
+pub mod astar;
+pub mod node;
+
+/// How many nodes a navigator may expand per tick, longer searches continue in the next tick
+const NODE_BUDGET_PER_TICK: usize = 64;
+/// How many nodes a single search may expand before it settles for the closest node
+const MAX_SEARCH_NODES: usize = 1024;
+/// How close (horizontally, in blocks) the mob has to get to a node to move on to the next one
+const NODE_REACHED_DISTANCE: f64 = 0.5;
+
+/// Moves a mob along paths found by `PathSearch`
 #[derive(Default)]
 pub struct Navigator {
     current_goal: Option<NavigatorGoal>,
 }
 
 pub struct NavigatorGoal {
-    pub current_progress: Vector3<f64>,
     pub destination: Vector3<f64>,
     pub speed: f64,
+    state: NavigationState,
+}
+
+enum NavigationState {
+    Searching(PathSearch),
+    Following(Path),
+}
+
+fn block_of(pos: Vector3<f64>) -> Vector3<i32> {
+    Vector3::new(
+        pos.x.floor() as i32,
+        pos.y.floor() as i32,
+        pos.z.floor() as i32,
+    )
 }
 
 impl Navigator {
-    pub fn set_progress(&mut self, goal: NavigatorGoal) {
-        self.current_goal = Some(goal);
+    /// Starts moving to the destination. Does nothing if the navigator is already moving to the
+    /// same block, so this can be called every tick
+    pub fn navigate_to(&mut self, from: Vector3<f64>, destination: Vector3<f64>, speed: f64) {
+        let target = block_of(destination);
+        if let Some(goal) = &mut self.current_goal {
+            if block_of(goal.destination) == target {
+                goal.speed = speed;
+                return;
+            }
+        }
+
+        self.current_goal = Some(NavigatorGoal {
+            destination,
+            speed,
+            state: NavigationState::Searching(PathSearch::new(
+                block_of(from),
+                target,
+                MAX_SEARCH_NODES,
+            )),
+        });
     }
 
     pub fn cancel(&mut self) {
         self.current_goal = None;
     }
 
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        self.current_goal.is_none()
+    }
+
     pub async fn tick(&mut self, entity: &LivingEntity) {
-        if let Some(goal) = &mut self.current_goal {
-            // first lets check if we reached destination
-            if goal.current_progress == goal.destination {
-                // if yes, we are done here
-                self.current_goal = None;
-                return;
-            }
+        let Some(goal) = &mut self.current_goal else {
+            return;
+        };
 
-            // A star algorithm
-            let mut best_move = Vector3::new(0.0, 0.0, 0.0);
-            let mut lowest_cost = f64::MAX;
-
-            for x in -1..=1 {
-                for z in -1..=1 {
-                    let x = f64::from(x);
-                    let z = f64::from(z);
-                    let potential_pos = Vector3::new(
-                        goal.current_progress.x + x,
-                        goal.current_progress.y,
-                        goal.current_progress.z + z,
-                    );
-
-                    let node = Node::new(potential_pos);
-                    let cost = node.get_expense(goal.destination);
-
-                    if cost < lowest_cost {
-                        lowest_cost = cost;
-                        best_move = Vector3::new(x, 0.0, z);
-                    }
+        if let NavigationState::Searching(search) = &mut goal.state {
+            let world = entity.entity.world.read().await.clone();
+            match search.step(world.as_ref(), NODE_BUDGET_PER_TICK).await {
+                SearchState::Running => return,
+                SearchState::Found(path) => goal.state = NavigationState::Following(path),
+                SearchState::NotFound => {
+                    self.current_goal = None;
+                    return;
                 }
             }
+        }
 
-            // this is important, first this saves us many packets when we don't actually move, and second this prevents division using zero
-            // when normalize
-            if best_move.x == 0.0 && best_move.z == 0.0 {
-                return;
+        let NavigationState::Following(path) = &mut goal.state else {
+            return;
+        };
+        let pos = entity.entity.pos.load();
+        let Some(next) = path.next_node() else {
+            self.current_goal = None;
+            return;
+        };
+
+        // Aim at the center of the node
+        let target = Vector3::new(
+            f64::from(next.x) + 0.5,
+            f64::from(next.y),
+            f64::from(next.z) + 0.5,
+        );
+        let horizontal = Vector3::new(target.x - pos.x, 0.0, target.z - pos.z);
+        let horizontal_distance = horizontal.length();
+        if horizontal_distance <= NODE_REACHED_DISTANCE.max(goal.speed) {
+            path.advance();
+            if path.is_done() {
+                self.current_goal = None;
             }
-            // Update current progress based on the best move
-            goal.current_progress += best_move.normalize() * goal.speed;
-
-            // now lets move
-            entity.set_pos(goal.current_progress);
-            let pos = entity.entity.pos.load();
-            let last_pos = entity.last_pos.load();
-
-            entity
-                .entity
-                .world
-                .read()
-                .await
-                .broadcast_packet_all(&CUpdateEntityPos::new(
-                    entity.entity.entity_id.into(),
-                    Vector3::new(
-                        pos.x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
-                        pos.y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
-                        pos.z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
-                    ),
-                    entity
-                        .entity
-                        .on_ground
-                        .load(std::sync::atomic::Ordering::Relaxed),
-                ))
-                .await;
+            Self::move_entity(entity, Vector3::new(target.x, target.y, target.z)).await;
+            return;
         }
+
+        let step = horizontal * (goal.speed / horizontal_distance);
+        // Steps up and drops are taken at once, as there is no physics yet
+        Self::move_entity(
+            entity,
+            Vector3::new(pos.x + step.x, target.y, pos.z + step.z),
+        )
+        .await;
     }
-}
 
-pub struct Node {
-    pub location: Vector3<f64>,
-}
+    async fn move_entity(entity: &LivingEntity, new_pos: Vector3<f64>) {
+        let last_pos = entity.entity.pos.load();
+        entity.set_pos(new_pos);
+        let pos = entity.entity.pos.load();
 
-impl Node {
-    #[must_use]
-    pub fn new(location: Vector3<f64>) -> Self {
-        Self { location }
-    }
-    /// How expensive is it to go to a location
-    ///
-    /// Returns a f64, Higher = More Expensive
-    #[must_use]
-    pub fn get_expense(&self, end: Vector3<f64>) -> f64 {
-        self.location.squared_distance_to_vec(end).sqrt()
+        entity
+            .entity
+            .world
+            .read()
+            .await
+            .broadcast_packet_all(&CUpdateEntityPos::new(
+                entity.entity.entity_id.into(),
+                Vector3::new(
+                    pos.x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
+                    pos.y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
+                    pos.z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
+                ),
+                entity
+                    .entity
+                    .on_ground
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ))
+            .await;
     }
 }
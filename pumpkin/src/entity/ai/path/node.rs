@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use pumpkin_data::block::{Block, BlockState};
+use pumpkin_data::tag::Tagable;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::registry::{get_block_and_state_by_state_id, get_collision_height};
+
+use crate::world::World;
+
+/// What a mob standing at a block position (its feet) would find there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathNodeType {
+    /// Something solid is in the way
+    Blocked,
+    /// Nothing to stand on, the mob would fall
+    Open,
+    /// Solid ground below and enough room above
+    Walkable,
+    Water,
+    Lava,
+    /// Next to or on something that sets mobs on fire
+    DamageFire,
+    /// Something that hurts to walk through, like cactus or berry bushes
+    DamageOther,
+    /// Too high to step onto, like fences and walls
+    Fence,
+    /// Inside a ladder, vine or scaffolding, which can be climbed up and down
+    Climbable,
+}
+
+impl PathNodeType {
+    /// The extra cost of walking through a node of this type, `None` when it can't be walked
+    /// through at all
+    #[must_use]
+    pub const fn malus(self) -> Option<f32> {
+        match self {
+            Self::Walkable | Self::Open | Self::Climbable => Some(0.0),
+            Self::Water => Some(8.0),
+            Self::DamageFire => Some(16.0),
+            Self::Blocked | Self::Lava | Self::DamageOther | Self::Fence => None,
+        }
+    }
+}
+
+/// Gives the pathfinder access to the blocks of a world
+#[async_trait]
+pub trait NodeView: Send + Sync {
+    async fn node_type(&self, pos: Vector3<i32>) -> PathNodeType;
+}
+
+/// How a single block behaves for pathfinding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockPathType {
+    /// No collision, can be walked through
+    Passable,
+    /// Has a collision shape, the value is the height of its top in 1/16 blocks
+    Solid(u8),
+    Water,
+    Lava,
+    Fire,
+    Harmful,
+    Climbable,
+}
+
+impl BlockPathType {
+    #[must_use]
+    pub fn of(block: &Block, state: &BlockState) -> Self {
+        match block.name {
+            "fire" | "soul_fire" | "campfire" | "soul_campfire" | "magma_block" => {
+                return Self::Fire;
+            }
+            "cactus" | "sweet_berry_bush" | "wither_rose" | "powder_snow" => return Self::Harmful,
+            _ => {}
+        }
+        if block
+            .is_tagged_with("minecraft:climbable")
+            .unwrap_or_default()
+        {
+            return Self::Climbable;
+        }
+        if state.is_liquid {
+            return if block.name == "lava" {
+                Self::Lava
+            } else {
+                Self::Water
+            };
+        }
+
+        get_collision_height(state.id).map_or(Self::Passable, |top| {
+            Self::Solid((top * 16.0).round().clamp(0.0, 255.0) as u8)
+        })
+    }
+}
+
+/// Combines the block below, at the feet and at the head of a mob into a node type
+#[must_use]
+pub fn classify(below: BlockPathType, feet: BlockPathType, head: BlockPathType) -> PathNodeType {
+    match (feet, head) {
+        (BlockPathType::Lava, _) | (_, BlockPathType::Lava) => return PathNodeType::Lava,
+        (BlockPathType::Fire, _) | (_, BlockPathType::Fire) => return PathNodeType::DamageFire,
+        (BlockPathType::Harmful, _) | (_, BlockPathType::Harmful) => {
+            return PathNodeType::DamageOther;
+        }
+        (BlockPathType::Water, _) => return PathNodeType::Water,
+        _ => {}
+    }
+    // Low blocks like carpets and slabs can be stood on
+    if matches!(feet, BlockPathType::Solid(height) if height > 8)
+        || matches!(head, BlockPathType::Solid(_))
+    {
+        return PathNodeType::Blocked;
+    }
+    if feet == BlockPathType::Climbable {
+        return PathNodeType::Climbable;
+    }
+
+    match below {
+        BlockPathType::Solid(height) if height > 16 => PathNodeType::Fence,
+        BlockPathType::Solid(_) => PathNodeType::Walkable,
+        BlockPathType::Fire => PathNodeType::DamageFire,
+        BlockPathType::Lava => PathNodeType::Lava,
+        BlockPathType::Water
+        | BlockPathType::Passable
+        | BlockPathType::Harmful
+        | BlockPathType::Climbable => PathNodeType::Open,
+    }
+}
+
+impl World {
+    async fn block_path_type(&self, pos: Vector3<i32>) -> BlockPathType {
+        let Ok(state_id) = self.get_block_state_id(&BlockPos(pos)).await else {
+            // Outside of the world
+            return BlockPathType::Solid(16);
+        };
+        get_block_and_state_by_state_id(state_id)
+            .map_or(BlockPathType::Passable, |(block, state)| {
+                BlockPathType::of(&block, &state)
+            })
+    }
+}
+
+#[async_trait]
+impl NodeView for World {
+    async fn node_type(&self, pos: Vector3<i32>) -> PathNodeType {
+        let below = self
+            .block_path_type(Vector3::new(pos.x, pos.y - 1, pos.z))
+            .await;
+        let feet = self.block_path_type(pos).await;
+        let head = self
+            .block_path_type(Vector3::new(pos.x, pos.y + 1, pos.z))
+            .await;
+        classify(below, feet, head)
+    }
+}
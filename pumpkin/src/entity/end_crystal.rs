@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use async_trait::async_trait;
+use pumpkin_data::{block::Block, damage::DamageType};
+
+use super::{Entity, EntityBase, living::LivingEntity};
+use crate::server::Server;
+
+/// How strong the explosion of a destroyed crystal is
+const EXPLOSION_POWER: f32 = 6.0;
+
+/// The crystals on the obsidian pillars of the End, which heal the ender dragon. Any damage
+/// destroys them with an explosion
+pub struct EndCrystalEntity {
+    entity: Entity,
+    destroyed: AtomicBool,
+}
+
+impl EndCrystalEntity {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            destroyed: AtomicBool::new(false),
+        }
+    }
+}
+
+#[async_trait]
+impl EntityBase for EndCrystalEntity {
+    async fn tick(&self, server: &Server) {
+        let world = self.entity.world.read().await.clone();
+        if self.destroyed.load(Ordering::Relaxed) {
+            // Damage doesn't come with the server, so the explosion happens here
+            self.entity.remove().await;
+            let position = self.entity.pos.load();
+            world.explode(server, position, EXPLOSION_POWER).await;
+            if let Some(fight) = &world.dragon_fight {
+                fight
+                    .lock()
+                    .await
+                    .crystal_destroyed(self.entity.entity_uuid, position)
+                    .await;
+            }
+            return;
+        }
+
+        // The crystals of the fight keep a fire burning where they stand
+        if world.dragon_fight.is_some() {
+            let position = self.entity.block_pos.load();
+            if world
+                .get_block_state(&position)
+                .await
+                .is_ok_and(|state| state.air)
+            {
+                world
+                    .set_block_state(&position, Block::FIRE.default_state_id)
+                    .await;
+            }
+        }
+    }
+
+    async fn damage(&self, _amount: f32, damage_type: DamageType) -> bool {
+        if self.entity.is_invulnerable_to(&damage_type) {
+            return false;
+        }
+        !self.destroyed.swap(true, Ordering::Relaxed)
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
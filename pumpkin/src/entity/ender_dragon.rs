@@ -0,0 +1,322 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{damage::DamageType, entity::EntityType};
+use pumpkin_util::math::vector3::Vector3;
+use rand::Rng;
+use tokio::sync::Mutex;
+
+use super::{Entity, EntityBase, combat, living::LivingEntity};
+use crate::{server::Server, world::World};
+
+/// How many parts the dragon is made of, the client gives them the entity ids after the dragon's
+pub const PARTS: i32 = 8;
+const MAX_HEALTH: f32 = 200.0;
+
+/// How far from the exit portal the dragon circles
+const CIRCLE_RADIUS: f64 = 60.0;
+/// How far above the exit portal the dragon circles
+const CIRCLE_HEIGHT: f64 = 25.0;
+/// Blocks per tick
+const FLYING_SPEED: f64 = 0.8;
+const CHARGING_SPEED: f64 = 1.2;
+const LANDING_SPEED: f64 = 0.5;
+/// How often the dragon decides whether to keep circling
+const DECISION_INTERVAL: u32 = 100;
+/// Charges end after this long, even if the dragon didn't get there
+const CHARGE_TICKS: u32 = 100;
+/// Players further away than this are never charged at
+const CHARGE_RANGE: f64 = 150.0;
+
+/// Crystals closer than this heal the dragon
+pub const CRYSTAL_HEAL_RANGE: f64 = 32.0;
+/// The dragon heals 1 health this often while a crystal is close
+const CRYSTAL_HEAL_INTERVAL: u32 = 10;
+/// The crystals on the pillars are never further away from the exit portal than this
+const CRYSTAL_SEARCH_RANGE: f64 = 64.0;
+
+const SITTING_TICKS: u32 = 200;
+/// The dragon takes off again once it took this much damage while sitting
+const SITTING_DAMAGE_LIMIT: f32 = 50.0;
+/// How long the dragon breaks apart before it's gone
+const DYING_TICKS: u32 = 200;
+/// The damage the dragon does to players it flies into
+const HIT_DAMAGE: f32 = 10.0;
+
+enum DragonPhase {
+    /// Circles around the exit portal, at `angle` around it
+    HoldingPattern { angle: f64, ticks: u32 },
+    /// Flies at where a player stood
+    Charging { target: Vector3<f64>, ticks: u32 },
+    /// Flies down onto the exit portal
+    Landing,
+    /// Sits on the exit portal, where it takes full damage
+    Sitting { ticks: u32, damage_taken: f32 },
+    /// Flies back up after sitting
+    TakingOff,
+    /// Killed, it rises while breaking apart
+    Dying { ticks: u32 },
+}
+
+/// The boss of the End. It circles the exit portal while the end crystals heal it, charges at
+/// players, and lands on the portal now and then, more often the fewer crystals are left
+pub struct EnderDragonEntity {
+    living_entity: LivingEntity,
+    /// Where it lands, on top of the exit portal
+    perch: Vector3<f64>,
+    phase: Mutex<DragonPhase>,
+    ticks: AtomicU32,
+}
+
+impl EnderDragonEntity {
+    pub fn new(entity: Entity, perch: Vector3<f64>) -> Self {
+        let living_entity = LivingEntity::new(entity);
+        living_entity.health.store(MAX_HEALTH);
+        Self {
+            living_entity,
+            perch,
+            phase: Mutex::new(DragonPhase::HoldingPattern {
+                angle: 0.0,
+                ticks: 0,
+            }),
+            ticks: AtomicU32::new(0),
+        }
+    }
+
+    /// How much health the dragon has left, from 0 to 1
+    pub fn health_progress(&self) -> f32 {
+        (self.living_entity.health.load() / MAX_HEALTH).clamp(0.0, 1.0)
+    }
+
+    /// Moves towards the target, facing where it flies. Returns whether it got there
+    async fn fly_towards(&self, target: Vector3<f64>, speed: f64) -> bool {
+        let entity = &self.living_entity.entity;
+        let position = entity.pos.load();
+        let delta = target.sub(&position);
+        let distance = delta.length();
+        let step = if distance <= speed {
+            delta
+        } else {
+            delta * (speed / distance)
+        };
+        let new_position = position.add(&step);
+        entity.set_pos(new_position);
+        if step.horizontal_length_squared() > 0.0 {
+            // The model of the dragon faces the other way than other entities do
+            entity
+                .look_at(new_position.sub(&Vector3::new(step.x, 0.0, step.z)))
+                .await;
+        }
+        entity.send_movement(position).await;
+        distance <= speed
+    }
+
+    /// The angle around the exit portal the dragon is at
+    fn angle(&self) -> f64 {
+        let position = self.living_entity.entity.pos.load();
+        (position.z - self.perch.z).atan2(position.x - self.perch.x)
+    }
+
+    /// What the dragon does after circling for a while
+    async fn next_after_circling(&self, world: &World) -> Option<DragonPhase> {
+        let crystals = end_crystals(world, self.perch, CRYSTAL_SEARCH_RANGE).len();
+        let position = self.living_entity.entity.pos.load();
+        let target = world
+            .players
+            .read()
+            .await
+            .values()
+            .filter(|player| player.living_entity.health.load() > 0.0)
+            .map(|player| player.living_entity.entity.pos.load())
+            .filter(|target| target.squared_distance_to_vec(position) <= CHARGE_RANGE.powi(2))
+            .min_by(|a, b| {
+                a.squared_distance_to_vec(position)
+                    .total_cmp(&b.squared_distance_to_vec(position))
+            });
+
+        let mut rng = rand::thread_rng();
+        if rng.gen_range(0..crystals + 3) == 0 {
+            return Some(DragonPhase::Landing);
+        }
+        match target {
+            Some(target) if rng.gen_bool(1.0 / 3.0) => {
+                Some(DragonPhase::Charging { target, ticks: 0 })
+            }
+            _ => None,
+        }
+    }
+
+    /// Hurts the players the dragon flies into and pushes them away
+    async fn hit_players(&self, world: &World) {
+        let entity = &self.living_entity.entity;
+        for player in world.players_in_box(&entity.bounding_box.load()) {
+            let victim = &player.living_entity.entity;
+            world
+                .damage_entity(player.clone(), HIT_DAMAGE, DamageType::MOB_ATTACK)
+                .await;
+            combat::handle_knockback(entity, world, victim, 2.0).await;
+        }
+    }
+
+    /// Heals the dragon while an end crystal is close
+    async fn heal_from_crystals(&self, world: &World) {
+        let health = self.living_entity.health.load();
+        if health >= MAX_HEALTH || self.ticks.load(Ordering::Relaxed) % CRYSTAL_HEAL_INTERVAL != 0 {
+            return;
+        }
+        let position = self.living_entity.entity.pos.load();
+        if !end_crystals(world, position, CRYSTAL_HEAL_RANGE).is_empty() {
+            self.living_entity
+                .set_health((health + 1.0).min(MAX_HEALTH))
+                .await;
+        }
+    }
+
+    /// Lets the crystal blow up under the dragon, when it was healing from it
+    pub async fn crystal_destroyed(&self, position: Vector3<f64>) {
+        let dragon = self.living_entity.entity.pos.load();
+        if dragon.squared_distance_to_vec(position) <= CRYSTAL_HEAL_RANGE.powi(2) {
+            self.damage(HIT_DAMAGE, DamageType::EXPLOSION).await;
+        }
+    }
+}
+
+/// The end crystals within `radius` of the position
+pub fn end_crystals(
+    world: &World,
+    position: Vector3<f64>,
+    radius: f64,
+) -> Vec<Arc<dyn EntityBase>> {
+    world
+        .entities_in_range(position, radius)
+        .into_iter()
+        .filter(|entity| entity.get_entity().entity_type == EntityType::END_CRYSTAL)
+        .collect()
+}
+
+#[async_trait]
+impl EntityBase for EnderDragonEntity {
+    async fn tick(&self, server: &Server) {
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        self.ticks.fetch_add(1, Ordering::Relaxed);
+
+        let mut phase = self.phase.lock().await;
+        if let DragonPhase::Dying { ticks } = &mut *phase {
+            *ticks += 1;
+            let ticks = *ticks;
+            // The fight takes the lock of the phase when a crystal is destroyed
+            drop(phase);
+            let position = entity.pos.load();
+            self.fly_towards(position.add_raw(0.0, 0.1, 0.0), 0.1).await;
+            if ticks >= DYING_TICKS {
+                entity.remove().await;
+                if let Some(fight) = &world.dragon_fight {
+                    fight
+                        .lock()
+                        .await
+                        .dragon_died(&world, server, position)
+                        .await;
+                }
+            }
+            return;
+        }
+
+        self.living_entity.tick(server).await;
+        self.heal_from_crystals(&world).await;
+
+        let next = match &mut *phase {
+            DragonPhase::HoldingPattern { angle, ticks } => {
+                *angle += FLYING_SPEED / CIRCLE_RADIUS;
+                *ticks += 1;
+                let target = self.perch.add_raw(
+                    angle.cos() * CIRCLE_RADIUS,
+                    CIRCLE_HEIGHT,
+                    angle.sin() * CIRCLE_RADIUS,
+                );
+                self.fly_towards(target, FLYING_SPEED).await;
+                if *ticks % DECISION_INTERVAL == 0 {
+                    self.next_after_circling(&world).await
+                } else {
+                    None
+                }
+            }
+            DragonPhase::Charging { target, ticks } => {
+                *ticks += 1;
+                let reached = self.fly_towards(*target, CHARGING_SPEED).await;
+                (reached || *ticks >= CHARGE_TICKS).then(|| DragonPhase::HoldingPattern {
+                    angle: self.angle(),
+                    ticks: 0,
+                })
+            }
+            DragonPhase::Landing => {
+                self.fly_towards(self.perch, LANDING_SPEED)
+                    .await
+                    .then_some(DragonPhase::Sitting {
+                        ticks: 0,
+                        damage_taken: 0.0,
+                    })
+            }
+            DragonPhase::Sitting { ticks, .. } => {
+                *ticks += 1;
+                (*ticks >= SITTING_TICKS).then_some(DragonPhase::TakingOff)
+            }
+            DragonPhase::TakingOff => {
+                let target = self.perch.add_raw(0.0, CIRCLE_HEIGHT, 0.0);
+                self.fly_towards(target, LANDING_SPEED)
+                    .await
+                    .then(|| DragonPhase::HoldingPattern {
+                        angle: self.angle(),
+                        ticks: 0,
+                    })
+            }
+            DragonPhase::Dying { .. } => None,
+        };
+        let flying = !matches!(*phase, DragonPhase::Sitting { .. });
+        if let Some(next) = next {
+            *phase = next;
+        }
+        drop(phase);
+
+        if flying {
+            self.hit_players(&world).await;
+        }
+    }
+
+    async fn damage(&self, amount: f32, damage_type: DamageType) -> bool {
+        let mut phase = self.phase.lock().await;
+        let amount = match *phase {
+            DragonPhase::Dying { .. } => return false,
+            DragonPhase::Sitting { .. } => amount,
+            // Only its head takes the full damage while it flies
+            _ => amount / 4.0 + amount.min(1.0),
+        };
+        if !self.living_entity.damage(amount, damage_type).await {
+            return false;
+        }
+
+        let took_too_much = if let DragonPhase::Sitting { damage_taken, .. } = &mut *phase {
+            *damage_taken += amount;
+            *damage_taken > SITTING_DAMAGE_LIMIT
+        } else {
+            false
+        };
+        if self.living_entity.health.load() <= 0.0 {
+            *phase = DragonPhase::Dying { ticks: 0 };
+        } else if took_too_much {
+            *phase = DragonPhase::TakingOff;
+        }
+        true
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.living_entity.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        Some(&self.living_entity)
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use uuid::Uuid;
+
+use super::EntityId;
+
+/// Hands out the ids of all entities, see `EntityIds`
+pub static ENTITY_IDS: LazyLock<EntityIds> = LazyLock::new(EntityIds::default);
+
+/// How long an id stays unused after its entity is gone. Clients may still get packets for the
+/// old entity in the meantime, which must not end up at a new one
+const RECYCLE_DELAY: Duration = Duration::from_secs(10);
+
+struct IdsInner {
+    next: EntityId,
+    live: HashMap<EntityId, Uuid>,
+    by_uuid: HashMap<Uuid, EntityId>,
+    /// Ids of removed entities with when they were removed, oldest first
+    released: VecDeque<(EntityId, Instant)>,
+    /// How many parts the entities which have some have, their ids follow the id of the entity
+    parts: HashMap<EntityId, i32>,
+}
+
+/// Entity ids with the uuids of their entities.
+///
+/// The ids are shared by all worlds, so an entity keeps a unique id when it changes dimension.
+/// Ids of dropped entities are reused after a while, and an id is never handed out twice while its
+/// entity is alive, even once the counter wrapped around.
+pub struct EntityIds {
+    inner: Mutex<IdsInner>,
+}
+
+impl Default for EntityIds {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(IdsInner {
+                next: 0,
+                live: HashMap::new(),
+                by_uuid: HashMap::new(),
+                released: VecDeque::new(),
+                parts: HashMap::new(),
+            }),
+        }
+    }
+}
+
+impl EntityIds {
+    /// A free id for the entity with the uuid
+    #[must_use]
+    pub fn allocate(&self, uuid: Uuid) -> EntityId {
+        self.allocate_at(uuid, Instant::now())
+    }
+
+    fn allocate_at(&self, uuid: Uuid, now: Instant) -> EntityId {
+        let mut inner = self.inner.lock().unwrap();
+        let recycled = inner
+            .released
+            .front()
+            .is_some_and(|(_, released)| now.duration_since(*released) >= RECYCLE_DELAY);
+        let id = if recycled {
+            inner.released.pop_front().unwrap().0
+        } else {
+            loop {
+                let id = inner.next;
+                inner.next = inner.next.wrapping_add(1).max(0);
+                if !inner.live.contains_key(&id) {
+                    break id;
+                }
+            }
+        };
+
+        inner.live.insert(id, uuid);
+        inner.by_uuid.insert(uuid, id);
+        id
+    }
+
+    /// A free id for the entity with the uuid, followed by free ids for its parts. The client gives
+    /// the parts of an entity like the ender dragon the ids after the one of the entity
+    #[must_use]
+    pub fn allocate_with_parts(&self, uuid: Uuid, parts: i32) -> EntityId {
+        let mut inner = self.inner.lock().unwrap();
+        // Recycled ids are scattered, so these always come from the counter
+        let id = loop {
+            let id = inner.next;
+            let Some(last) = id.checked_add(parts) else {
+                inner.next = 0;
+                continue;
+            };
+            inner.next = last.wrapping_add(1).max(0);
+            let free = |id| {
+                !inner.live.contains_key(&id)
+                    && !inner.released.iter().any(|(released, _)| *released == id)
+            };
+            if (id..=last).all(free) {
+                break id;
+            }
+        };
+
+        for part in id..=id + parts {
+            inner.live.insert(part, uuid);
+        }
+        inner.by_uuid.insert(uuid, id);
+        inner.parts.insert(id, parts);
+        id
+    }
+
+    /// Whether the id belongs to a part of the entity with the id `owner`
+    #[must_use]
+    pub fn is_part_of(&self, id: EntityId, owner: EntityId) -> bool {
+        self.inner
+            .lock()
+            .unwrap()
+            .parts
+            .get(&owner)
+            .is_some_and(|parts| id > owner && id - owner <= *parts)
+    }
+
+    /// Called once the entity with the id is gone, making the id available again later
+    pub fn release(&self, id: EntityId) {
+        self.release_at(id, Instant::now());
+    }
+
+    fn release_at(&self, id: EntityId, now: Instant) {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(uuid) = inner.live.remove(&id) else {
+            return;
+        };
+        if let Some(parts) = inner.parts.remove(&id) {
+            for part in id + 1..=id + parts {
+                inner.live.remove(&part);
+                inner.released.push_back((part, now));
+            }
+        }
+        // A new entity with the same uuid, like a player who joined again, keeps its mapping
+        if inner.by_uuid.get(&uuid) == Some(&id) {
+            inner.by_uuid.remove(&uuid);
+        }
+        inner.released.push_back((id, now));
+    }
+
+    #[must_use]
+    pub fn get_uuid(&self, id: EntityId) -> Option<Uuid> {
+        self.inner.lock().unwrap().live.get(&id).copied()
+    }
+
+    #[must_use]
+    pub fn get_id(&self, uuid: &Uuid) -> Option<EntityId> {
+        self.inner.lock().unwrap().by_uuid.get(uuid).copied()
+    }
+
+    /// How many entities currently have an id
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().live.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use uuid::Uuid;
+
+    use super::{EntityIds, RECYCLE_DELAY};
+
+    #[test]
+    fn recycles_after_delay() {
+        let ids = EntityIds::default();
+        let start = Instant::now();
+        let (first, second) = (Uuid::from_u128(1), Uuid::from_u128(2));
+
+        assert_eq!(ids.allocate_at(first, start), 0);
+        assert_eq!(ids.allocate_at(second, start), 1);
+        assert_eq!(ids.get_id(&second), Some(1));
+        assert_eq!(ids.get_uuid(0), Some(first));
+
+        ids.release_at(0, start);
+        assert_eq!(ids.get_uuid(0), None);
+        assert_eq!(ids.get_id(&first), None);
+        // Too early to reuse it
+        assert_eq!(ids.allocate_at(Uuid::from_u128(3), start), 2);
+        assert_eq!(
+            ids.allocate_at(Uuid::from_u128(4), start + RECYCLE_DELAY),
+            0
+        );
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn skips_live_ids_after_wrapping() {
+        let ids = EntityIds::default();
+        let now = Instant::now();
+        assert_eq!(ids.allocate_at(Uuid::from_u128(1), now), 0);
+        ids.inner.lock().unwrap().next = i32::MAX;
+
+        assert_eq!(ids.allocate_at(Uuid::from_u128(2), now), i32::MAX);
+        // 0 is still alive
+        assert_eq!(ids.allocate_at(Uuid::from_u128(3), now), 1);
+    }
+
+    #[test]
+    fn same_uuid_keeps_newest_mapping() {
+        let ids = EntityIds::default();
+        let now = Instant::now();
+        let uuid = Uuid::from_u128(7);
+        let old = ids.allocate_at(uuid, now);
+        let new = ids.allocate_at(uuid, now);
+
+        ids.release_at(old, now);
+        assert_eq!(ids.get_id(&uuid), Some(new));
+    }
+
+    #[test]
+    fn parts_follow_their_entity() {
+        let ids = EntityIds::default();
+        let now = Instant::now();
+        let dragon = Uuid::from_u128(1);
+        ids.release_at(ids.allocate_at(Uuid::from_u128(2), now), now);
+        ids.inner.lock().unwrap().next = 0;
+
+        // The released id can't be used for a part yet
+        let id = ids.allocate_with_parts(dragon, 8);
+        assert_eq!(id, 9);
+        assert_eq!(ids.get_uuid(17), Some(dragon));
+        assert_eq!(ids.get_id(&dragon), Some(9));
+        assert!(ids.is_part_of(17, 9));
+        assert!(!ids.is_part_of(18, 9));
+        assert_eq!(ids.allocate_at(Uuid::from_u128(3), now), 18);
+
+        ids.release_at(id, now);
+        assert_eq!(ids.get_uuid(17), None);
+        assert!(!ids.is_part_of(17, 9));
+    }
+}
@@ -1,4 +1,7 @@
-use std::sync::{Arc, atomic::AtomicU32};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
 
 use async_trait::async_trait;
 use pumpkin_data::{damage::DamageType, item::Item};
@@ -6,17 +9,25 @@ use pumpkin_protocol::{
     client::play::{CTakeItemEntity, MetaDataType, Metadata},
     codec::slot::Slot,
 };
-use pumpkin_util::math::vector3::Vector3;
-use pumpkin_world::item::ItemStack;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::item::{ItemStack, ItemStackComponents};
 use tokio::sync::Mutex;
 
 use crate::server::Server;
 
 use super::{Entity, EntityBase, living::LivingEntity, player::Player};
 
+/// Dropped items despawn after 5 minutes
+const DESPAWN_AGE: u32 = 6000;
+const GRAVITY: f64 = 0.04;
+/// How much of its velocity an item keeps each tick while in the air
+const AIR_DRAG: f64 = 0.98;
+
 pub struct ItemEntity {
     entity: Entity,
     item: Item,
+    /// The components of the dropped stack, like the items in a shulker box
+    components: ItemStackComponents,
     item_age: AtomicU32,
     // These cannot be atomic values because we mutate their state based on what they are; we run
     // into the ABA problem
@@ -25,7 +36,7 @@ pub struct ItemEntity {
 }
 
 impl ItemEntity {
-    pub async fn new(entity: Entity, item_id: u16, count: u32) -> Self {
+    pub async fn new(entity: Entity, stack: &ItemStack, count: u32) -> Self {
         entity
             .set_velocity(Vector3::new(
                 rand::random::<f64>() * 0.2 - 0.1,
@@ -36,14 +47,150 @@ impl ItemEntity {
         entity.yaw.store(rand::random::<f32>() * 360.0);
         Self {
             entity,
-            item: Item::from_id(item_id).expect("We passed a bad item id into ItemEntity"),
+            item: stack.item.clone(),
+            components: stack.components.clone(),
             item_age: AtomicU32::new(0),
             item_count: Mutex::new(count),
             pickup_delay: Mutex::new(10), // Vanilla pickup delay is 10 ticks
         }
     }
+    /// Applies gravity and friction and moves the item, returns whether it reached another block
+    #[expect(clippy::float_cmp)]
+    async fn tick_movement(&self) -> bool {
+        let entity = &self.entity;
+        let mut velocity = entity.velocity.load();
+        // TODO: Float up in water and lava
+        velocity.y -= GRAVITY;
+
+        let age = self.item_age.load(Ordering::Relaxed);
+        // Resting items only check every few ticks whether they started to fall
+        if entity.on_ground.load(Ordering::Relaxed)
+            && velocity.horizontal_length_squared() <= 1.0E-5
+            && age.wrapping_add(entity.entity_id as u32) % 4 != 0
+        {
+            entity.velocity.store(velocity);
+            return false;
+        }
+
+        let last_pos = entity.pos.load();
+        let last_block_pos = entity.block_pos.load();
+        let moved = entity.move_with_collisions(velocity).await;
+        // Running into a block stops the movement in that direction
+        if moved.x != velocity.x {
+            velocity.x = 0.0;
+        }
+        if moved.y != velocity.y {
+            velocity.y = 0.0;
+        }
+        if moved.z != velocity.z {
+            velocity.z = 0.0;
+        }
+
+        let on_ground = entity.on_ground.load(Ordering::Relaxed);
+        let friction = if on_ground {
+            let pos = entity.pos.load();
+            let below = BlockPos::floored(pos.x, pos.y - 0.500_001, pos.z);
+            let world = entity.world.read().await.clone();
+            world
+                .get_block(&below)
+                .await
+                .map_or(AIR_DRAG, |block| f64::from(block.slipperiness) * AIR_DRAG)
+        } else {
+            AIR_DRAG
+        };
+        velocity = velocity.multiply(friction, AIR_DRAG, friction);
+        if on_ground && velocity.y < 0.0 {
+            velocity.y *= -0.5;
+        }
+        entity.velocity.store(velocity);
+
+        if moved.length_squared() > 0.0 {
+            entity.send_movement(last_pos).await;
+        }
+        entity.block_pos.load() != last_block_pos
+    }
+
+    /// Whether the other item can be merged into the same stack as this one
+    fn can_merge_with(&self, other: &Self) -> bool {
+        self.item.id == other.item.id && self.components == other.components
+    }
+
+    /// Merges the stacks of the same item lying close to this one
+    async fn try_merge(&self) {
+        let world = self.entity.world.read().await.clone();
+        let area = self.entity.bounding_box.load().expand(0.5, 0.0, 0.5);
+        for other in world.entities_in_box(&area) {
+            let Some(other) = other.get_item_entity() else {
+                continue;
+            };
+            if other.entity.entity_id == self.entity.entity_id || !self.can_merge_with(other) {
+                continue;
+            }
+            if !self.merge(other).await {
+                break;
+            }
+        }
+    }
+
+    /// Moves the items of the smaller stack into the bigger one, as far as they fit. Returns
+    /// whether this item is still there to merge with others
+    async fn merge(&self, other: &Self) -> bool {
+        let max_stack = u32::from(self.item.components.max_stack_size);
+        let (own_count, into_self) = {
+            let mut own_count = self.item_count.lock().await;
+            // The other item could be merging into us at the same time, so don't wait for it
+            let Ok(mut other_count) = other.item_count.try_lock() else {
+                return *own_count > 0;
+            };
+            if *own_count == 0
+                || *other_count == 0
+                || *own_count >= max_stack
+                || *other_count >= max_stack
+            {
+                return *own_count > 0;
+            }
+
+            let into_self = *own_count >= *other_count;
+            let (target, source) = if into_self {
+                (&mut *own_count, &mut *other_count)
+            } else {
+                (&mut *other_count, &mut *own_count)
+            };
+            let amount = (max_stack - *target).min(*source);
+            *target += amount;
+            *source -= amount;
+            (*own_count, into_self)
+        };
+
+        let (target, source) = if into_self {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let age = self
+            .item_age
+            .load(Ordering::Relaxed)
+            .min(other.item_age.load(Ordering::Relaxed));
+        target.item_age.store(age, Ordering::Relaxed);
+        let source_delay = *source.pickup_delay.lock().await;
+        let mut target_delay = target.pickup_delay.lock().await;
+        *target_delay = (*target_delay).max(source_delay);
+        drop(target_delay);
+
+        target.send_meta_packet().await;
+        if *source.item_count.lock().await == 0 {
+            source.entity.remove().await;
+        } else {
+            source.send_meta_packet().await;
+        }
+        own_count > 0
+    }
+
     pub async fn send_meta_packet(&self) {
-        let slot = Slot::new(self.item.id, *self.item_count.lock().await);
+        let mut stack = ItemStack::new(0, self.item.clone());
+        stack.components = self.components.clone();
+        let mut slot = Slot::from(&stack);
+        slot.item_count = (*self.item_count.lock().await).into();
         self.entity
             .send_meta_data(&[Metadata::new(8, MetaDataType::ItemStack, &slot)])
             .await;
@@ -52,18 +199,22 @@ impl ItemEntity {
 
 #[async_trait]
 impl EntityBase for ItemEntity {
-    async fn tick(&self, server: &Server) {
-        self.entity.tick(server).await;
+    async fn tick(&self, _server: &Server) {
+        let moved_block = self.tick_movement().await;
         {
             let mut delay = self.pickup_delay.lock().await;
             *delay = delay.saturating_sub(1);
         };
 
-        let age = self
-            .item_age
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        if age >= 6000 {
+        let age = self.item_age.fetch_add(1, Ordering::Relaxed);
+        if age >= DESPAWN_AGE {
             self.entity.remove().await;
+            return;
+        }
+        // Moving items look for stacks to merge with more often
+        let merge_interval = if moved_block { 2 } else { 40 };
+        if age % merge_interval == 0 {
+            self.try_merge().await;
         }
     }
     async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
@@ -121,7 +272,9 @@ impl EntityBase for ItemEntity {
                             *stack_size -= amount_to_add;
 
                             // Therefore this is safe
-                            let item_stack = ItemStack::new(amount_to_add as u8, self.item.clone());
+                            let mut item_stack =
+                                ItemStack::new(amount_to_add as u8, self.item.clone());
+                            item_stack.components = self.components.clone();
 
                             // Update referenced stack so next call to get_pickup_item_slot is
                             // correct
@@ -170,4 +323,8 @@ impl EntityBase for ItemEntity {
     fn get_living_entity(&self) -> Option<&LivingEntity> {
         None
     }
+
+    fn get_item_entity(&self) -> Option<&ItemEntity> {
+        Some(self)
+    }
 }
@@ -1,11 +1,16 @@
-use std::sync::atomic::AtomicU8;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::{collections::HashMap, sync::atomic::AtomicI32};
 
+use crate::block::drop_stack;
 use crate::server::Server;
+use crate::world::World;
+use crate::world::loot::{LootContextParameters, LootTableExt};
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
 use pumpkin_config::advanced_config;
-use pumpkin_data::entity::{EffectType, EntityStatus};
+use pumpkin_data::block::Block;
+use pumpkin_data::entity::{EffectType, EntityStatus, EntityType};
+use pumpkin_data::tag::Tagable;
 use pumpkin_data::{damage::DamageType, sound::Sound};
 use pumpkin_nbt::tag::NbtTag;
 use pumpkin_protocol::client::play::{CHurtAnimation, CTakeItemEntity};
@@ -14,12 +19,16 @@ use pumpkin_protocol::{
     client::play::{CDamageEvent, CSetEquipment, EquipmentSlot, MetaDataType, Metadata},
     codec::slot::Slot,
 };
-use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::registry::{
+    get_block_and_state_by_state_id, get_block_by_state_id, get_block_collision_boxes, is_full_cube,
+};
 use pumpkin_world::item::ItemStack;
+use pumpkin_world::world_info::game_rules;
 use tokio::sync::Mutex;
 
 use super::EntityBase;
-use super::{Entity, EntityId, NBTStorage, effect::Effect};
+use super::{Entity, EntityId, Flag, NBTStorage, effect::Effect};
 
 /// Represents a living entity within the game world.
 ///
@@ -39,7 +48,74 @@ pub struct LivingEntity {
     /// The distance the entity has been falling
     pub fall_distance: AtomicCell<f32>,
     pub active_effects: Mutex<HashMap<EffectType, Effect>>,
+    /// How many ticks the entity can stay underwater before it starts drowning
+    pub air_supply: AtomicI32,
+    /// How many more ticks the entity is burning for
+    pub fire_ticks: AtomicI32,
+}
+
+/// The air supply of an entity that can breathe, in ticks
+pub const MAX_AIR_SUPPLY: i32 = 300;
+/// How long entities burn after touching fire
+const FIRE_BURN_TICKS: i32 = 160;
+/// How long entities burn after touching lava
+const LAVA_BURN_TICKS: i32 = 300;
+
+/// Shortens the time an entity burns by 15% per level of fire protection
+#[must_use]
+pub fn reduced_burn_ticks(ticks: i32, fire_protection_level: i32) -> i32 {
+    let reduction = (ticks as f32 * fire_protection_level as f32 * 0.15).floor() as i32;
+    (ticks - reduction).max(0)
+}
+
+/// The next air supply of an entity and whether it takes drowning damage
+#[must_use]
+pub fn next_air_supply(air: i32, underwater: bool, can_breathe: bool) -> (i32, bool) {
+    if !underwater {
+        return ((air + 4).min(MAX_AIR_SUPPLY), false);
+    }
+    if can_breathe {
+        return (air, false);
+    }
+    let air = air - 1;
+    if air <= -20 { (0, true) } else { (air, false) }
+}
+
+/// The fall damage for falling `fall_distance` blocks onto a block with the given damage multiplier
+#[must_use]
+pub fn fall_damage(fall_distance: f32, safe_fall_distance: f32, multiplier: f32) -> f32 {
+    ((fall_distance - safe_fall_distance) * multiplier)
+        .ceil()
+        .max(0.0)
+}
+
+/// How much of the fall damage is taken when landing on a block
+#[must_use]
+pub fn landing_damage_multiplier(block: &Block, sneaking: bool) -> f32 {
+    match block.name {
+        "hay_block" | "honey_block" => 0.2,
+        // The client bounces back up, unless it is sneaking
+        "slime_block" if !sneaking => 0.0,
+        _ if block.is_tagged_with("minecraft:beds").unwrap_or_default() => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Reduces damage by protection points, like the enchantment protection of armor, each point
+/// blocks 4% of the damage up to 80%
+#[must_use]
+pub fn reduce_by_protection(damage: f32, protection: i32) -> f32 {
+    damage * (1.0 - protection.clamp(0, 20) as f32 / 25.0)
 }
+
+/// The blocks touching an entity, see `LivingEntity::tick_environment`
+#[derive(Default)]
+struct BlockContact {
+    fire: bool,
+    lava: bool,
+    water: bool,
+}
+
 impl LivingEntity {
     pub fn new(entity: Entity) -> Self {
         Self {
@@ -51,6 +127,8 @@ impl LivingEntity {
             fall_distance: AtomicCell::new(0.0),
             death_time: AtomicU8::new(0),
             active_effects: Mutex::new(HashMap::new()),
+            air_supply: AtomicI32::new(MAX_AIR_SUPPLY),
+            fire_ticks: AtomicI32::new(0),
         }
     }
 
@@ -134,10 +212,20 @@ impl LivingEntity {
             ))
             .await;
 
-        let new_health = (self.health.load() - amount).max(0.0);
+        let health = self.health.load();
+        let new_health = (health - amount).max(0.0);
 
         if new_health == 0.0 {
             self.kill().await;
+            // Entities which were already dead don't drop their loot again
+            if health > 0.0 {
+                let killed_by_player = damage_type.id == DamageType::PLAYER_ATTACK.id
+                    || [source, cause]
+                        .into_iter()
+                        .flatten()
+                        .any(|entity| entity.entity_type == EntityType::PLAYER);
+                self.drop_loot(killed_by_player).await;
+            }
         } else {
             self.set_health(new_health).await;
         }
@@ -151,6 +239,10 @@ impl LivingEntity {
         // TODO broadcast metadata
     }
 
+    pub async fn remove_effect(&self, effect: EffectType) -> Option<Effect> {
+        self.active_effects.lock().await.remove(&effect)
+    }
+
     pub async fn has_effect(&self, effect: EffectType) -> bool {
         let effects = self.active_effects.lock().await;
         effects.contains_key(&effect)
@@ -182,22 +274,72 @@ impl LivingEntity {
         amount > 0.0
     }
 
+    /// Tracks the fall distance while falling and applies fall damage on landing.
+    ///
+    /// The ground flag comes from the client, it is only trusted when there is a block below the
+    /// entity that it can stand on.
     pub async fn update_fall_distance(
         &self,
+        server: &Server,
         height_difference: f64,
         ground: bool,
         dont_damage: bool,
     ) {
+        let world = self.entity.world.read().await.clone();
+        let pos = self.entity.pos.load();
+        let feet = BlockPos(Vector3::new(
+            pos.x.floor() as i32,
+            pos.y.floor() as i32,
+            pos.z.floor() as i32,
+        ));
+        let in_water = world
+            .get_block(&feet)
+            .await
+            .is_ok_and(|block| block.name == "water");
+        // Water and climbing stop any fall, slow falling prevents it from building up
+        if in_water
+            || self.is_climbing(&world).await
+            || self.has_effect(EffectType::SlowFalling).await
+        {
+            self.fall_distance.store(0.0);
+            return;
+        }
+
+        let ground =
+            ground && (self.fall_distance.load() <= 0.0 || self.is_supported(&world).await);
         if ground {
             let fall_distance = self.fall_distance.swap(0.0);
-            if fall_distance <= 0.0 || dont_damage {
+            if fall_distance <= 0.0 {
                 return;
             }
 
-            let safe_fall_distance = 3.0;
-            let mut damage = fall_distance - safe_fall_distance;
-            damage = (damage).round();
-            if !self.check_damage(damage) {
+            let landing_pos = self.landing_pos();
+            let landing_block = world.get_block(&landing_pos).await.ok();
+            if let Some(block) = &landing_block {
+                server
+                    .block_registry
+                    .on_landed_upon(block, &world, landing_pos, self, fall_distance)
+                    .await;
+            }
+            if dont_damage || !world.game_rule(game_rules::FALL_DAMAGE) {
+                return;
+            }
+
+            let multiplier = landing_block.map_or(1.0, |block| {
+                landing_damage_multiplier(&block, self.entity.sneaking.load(Ordering::Relaxed))
+            });
+            let safe_fall_distance = 3.0
+                + self
+                    .get_effect(EffectType::JumpBoost)
+                    .await
+                    .map_or(0.0, |effect| f32::from(effect.amplifier) + 1.0);
+            // TODO: Use the feather falling level of the boots once items store their enchantments
+            let feather_falling_level = 0;
+            let damage = reduce_by_protection(
+                fall_damage(fall_distance, safe_fall_distance, multiplier),
+                feather_falling_level * 3,
+            );
+            if damage <= 0.0 || !self.check_damage(damage) {
                 return;
             }
 
@@ -213,6 +355,59 @@ impl LivingEntity {
         }
     }
 
+    /// Whether the entity is inside a block it can climb, like ladders, vines or scaffolding
+    pub async fn is_climbing(&self, world: &World) -> bool {
+        world
+            .get_block(&self.entity.block_pos.load())
+            .await
+            .is_ok_and(|block| {
+                block
+                    .is_tagged_with("minecraft:climbable")
+                    .unwrap_or_default()
+            })
+    }
+
+    /// The block the entity stands on, slightly below the feet so that carpets and snow layers
+    /// don't count
+    fn landing_pos(&self) -> BlockPos {
+        let pos = self.entity.pos.load();
+        BlockPos(Vector3::new(
+            pos.x.floor() as i32,
+            (pos.y - 0.2).floor() as i32,
+            pos.z.floor() as i32,
+        ))
+    }
+
+    /// Whether there is a block right below the entity that it can stand on
+    async fn is_supported(&self, world: &World) -> bool {
+        let pos = self.entity.pos.load();
+        let feet =
+            BoundingBox::new_from_pos(pos.x, pos.y, pos.z, &self.entity.bounding_box_size.load());
+        // Give the client some leeway, its position is from before it settled on the block
+        let below = BoundingBox::new(
+            Vector3::new(feet.min.x, feet.min.y - 0.5, feet.min.z),
+            Vector3::new(feet.max.x, feet.min.y + 0.001, feet.max.z),
+        );
+
+        for x in below.min.x.floor() as i32..=below.max.x.floor() as i32 {
+            for y in below.min.y.floor() as i32..=below.max.y.floor() as i32 {
+                for z in below.min.z.floor() as i32..=below.max.z.floor() as i32 {
+                    let block_pos = BlockPos(Vector3::new(x, y, z));
+                    let Ok(state_id) = world.get_block_state_id(&block_pos).await else {
+                        continue;
+                    };
+                    if get_block_collision_boxes(state_id, &block_pos)
+                        .iter()
+                        .any(|block_box| block_box.intersects(&below))
+                    {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
     fn get_fall_sound(distance: i32) -> Sound {
         if distance > 4 {
             Sound::EntityGenericBigFall
@@ -221,6 +416,146 @@ impl LivingEntity {
         }
     }
 
+    pub fn is_on_fire(&self) -> bool {
+        self.fire_ticks.load(Ordering::Relaxed) > 0
+    }
+
+    /// Sets the entity on fire, if it isn't already burning for longer
+    pub async fn set_on_fire_for(&self, ticks: i32) {
+        let previous = self.fire_ticks.fetch_max(ticks, Ordering::Relaxed);
+        if previous <= 0 && ticks > 0 {
+            self.entity.set_flag(Flag::OnFire, true).await;
+        }
+    }
+
+    pub async fn extinguish(&self) {
+        if self.fire_ticks.swap(0, Ordering::Relaxed) > 0 {
+            self.entity.set_flag(Flag::OnFire, false).await;
+        }
+    }
+
+    async fn set_air_supply(&self, air: i32) {
+        if self.air_supply.swap(air, Ordering::Relaxed) != air {
+            self.entity
+                .send_meta_data(&[Metadata::new(1, MetaDataType::Integer, VarInt(air))])
+                .await;
+        }
+    }
+
+    /// Finds the fire, lava and water blocks inside the bounding box of the entity
+    async fn block_contact(&self, world: &World) -> BlockContact {
+        let pos = self.entity.pos.load();
+        let aabb =
+            BoundingBox::new_from_pos(pos.x, pos.y, pos.z, &self.entity.bounding_box_size.load())
+                .expand(-0.001, -0.001, -0.001);
+
+        let mut contact = BlockContact::default();
+        for x in aabb.min.x.floor() as i32..=aabb.max.x.floor() as i32 {
+            for y in aabb.min.y.floor() as i32..=aabb.max.y.floor() as i32 {
+                for z in aabb.min.z.floor() as i32..=aabb.max.z.floor() as i32 {
+                    let Ok(state_id) = world
+                        .get_block_state_id(&BlockPos(Vector3::new(x, y, z)))
+                        .await
+                    else {
+                        continue;
+                    };
+                    let Some(block) = get_block_by_state_id(state_id) else {
+                        continue;
+                    };
+                    match block.name {
+                        "fire" | "soul_fire" => contact.fire = true,
+                        "lava" => contact.lava = true,
+                        "water" => contact.water = true,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        contact
+    }
+
+    /// Applies suffocation, drowning and burning
+    async fn tick_environment(&self) {
+        let world = self.entity.world.read().await.clone();
+        let pos = self.entity.pos.load();
+        let eye_pos = BlockPos(Vector3::new(
+            pos.x.floor() as i32,
+            (pos.y + f64::from(self.entity.standing_eye_height)).floor() as i32,
+            pos.z.floor() as i32,
+        ));
+        let eye_state = world.get_block_state_id(&eye_pos).await.ok();
+
+        // Suffocation, the damage cooldown limits this to every 10 ticks
+        if eye_state.is_some_and(is_full_cube) {
+            self.damage(1.0, DamageType::IN_WALL).await;
+        }
+
+        // Drowning
+        let underwater = eye_state
+            .and_then(get_block_and_state_by_state_id)
+            .is_some_and(|(block, state)| state.is_liquid && block.name == "water");
+        let can_breathe = self.entity.invulnerable.load(Ordering::Relaxed)
+            || self.has_effect(EffectType::WaterBreathing).await
+            || self.has_effect(EffectType::ConduitPower).await;
+        let (air, drowning) = next_air_supply(
+            self.air_supply.load(Ordering::Relaxed),
+            underwater,
+            can_breathe,
+        );
+        self.set_air_supply(air).await;
+        if drowning {
+            self.damage(2.0, DamageType::DROWN).await;
+        }
+
+        // Fire and lava
+        let fire_resistant = self.has_effect(EffectType::FireResistance).await;
+        let contact = self.block_contact(&world).await;
+        // TODO: Use the fire protection of the armor once items store their enchantments
+        let fire_protection_level = 0;
+        if contact.lava {
+            if !fire_resistant {
+                self.damage(4.0, DamageType::LAVA).await;
+            }
+            self.set_on_fire_for(reduced_burn_ticks(LAVA_BURN_TICKS, fire_protection_level))
+                .await;
+        } else if contact.fire {
+            if !fire_resistant {
+                self.damage(1.0, DamageType::IN_FIRE).await;
+            }
+            self.set_on_fire_for(reduced_burn_ticks(FIRE_BURN_TICKS, fire_protection_level))
+                .await;
+        }
+        if contact.water {
+            self.extinguish().await;
+        }
+
+        let fire_ticks = self.fire_ticks.load(Ordering::Relaxed);
+        if fire_ticks > 0 {
+            if fire_ticks % 20 == 0 && !fire_resistant {
+                self.damage(1.0, DamageType::ON_FIRE).await;
+            }
+            if self.fire_ticks.fetch_sub(1, Ordering::Relaxed) == 1 {
+                self.entity.set_flag(Flag::OnFire, false).await;
+            }
+        }
+    }
+
+    /// Drops what the loot table of the entity gives
+    async fn drop_loot(&self, killed_by_player: bool) {
+        let Some(table) = self.entity.entity_type.loot_table() else {
+            return;
+        };
+        let params = LootContextParameters {
+            killed_by_player,
+            ..Default::default()
+        };
+        let world = self.entity.world.read().await.clone();
+        let position = self.entity.block_pos.load();
+        for stack in table.get_loot(&params) {
+            drop_stack(&world, &position, stack).await;
+        }
+    }
+
     /// Kills the Entity
     ///
     /// This is similar to `kill` but Spawn Particles, Animation and plays death sound
@@ -253,7 +588,9 @@ impl EntityBase for LivingEntity {
             self.time_until_regen
                 .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
         }
-        if self.health.load() <= 0.0 {
+        if self.health.load() > 0.0 {
+            self.tick_environment().await;
+        } else {
             let time = self
                 .death_time
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -305,12 +642,77 @@ impl NBTStorage for LivingEntity {
     async fn write_nbt(&self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
         self.entity.write_nbt(nbt).await;
         nbt.put("Health", NbtTag::Float(self.health.load()));
+        nbt.put_short("Air", self.air_supply.load(Ordering::Relaxed) as i16);
+        nbt.put_short("Fire", self.fire_ticks.load(Ordering::Relaxed) as i16);
         // todo more...
     }
 
     async fn read_nbt(&mut self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
         self.entity.read_nbt(nbt).await;
         self.health.store(nbt.get_float("Health").unwrap_or(0.0));
+        self.air_supply.store(
+            nbt.get_short("Air").map_or(MAX_AIR_SUPPLY, i32::from),
+            Ordering::Relaxed,
+        );
+        self.fire_ticks.store(
+            nbt.get_short("Fire").map_or(0, i32::from),
+            Ordering::Relaxed,
+        );
         // todo more...
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::block::Block;
+
+    use super::{
+        MAX_AIR_SUPPLY, fall_damage, landing_damage_multiplier, next_air_supply,
+        reduce_by_protection, reduced_burn_ticks,
+    };
+
+    #[test]
+    fn air_runs_out_underwater() {
+        let mut air = MAX_AIR_SUPPLY;
+        let mut ticks = 0;
+        loop {
+            let (next, drowning) = next_air_supply(air, true, false);
+            ticks += 1;
+            if drowning {
+                assert_eq!(next, 0);
+                break;
+            }
+            air = next;
+        }
+        assert_eq!(ticks, MAX_AIR_SUPPLY + 20);
+
+        assert_eq!(next_air_supply(10, true, true), (10, false));
+        assert_eq!(next_air_supply(298, false, false), (MAX_AIR_SUPPLY, false));
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp)]
+    fn fall_damage_on_landing_blocks() {
+        assert_eq!(fall_damage(3.0, 3.0, 1.0), 0.0);
+        assert_eq!(fall_damage(10.0, 3.0, 1.0), 7.0);
+        assert_eq!(fall_damage(10.0, 3.0, 0.2), 2.0);
+        assert_eq!(fall_damage(23.0, 3.0, 0.5), 10.0);
+
+        assert_eq!(landing_damage_multiplier(&Block::HAY_BLOCK, false), 0.2);
+        assert_eq!(landing_damage_multiplier(&Block::SLIME_BLOCK, false), 0.0);
+        assert_eq!(landing_damage_multiplier(&Block::SLIME_BLOCK, true), 1.0);
+        assert_eq!(landing_damage_multiplier(&Block::RED_BED, false), 0.5);
+        assert_eq!(landing_damage_multiplier(&Block::STONE, false), 1.0);
+
+        assert_eq!(reduce_by_protection(10.0, 5), 8.0);
+        // Protection caps at 80%
+        assert!((reduce_by_protection(10.0, 30) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn fire_protection_shortens_burning() {
+        assert_eq!(reduced_burn_ticks(160, 0), 160);
+        assert_eq!(reduced_burn_ticks(160, 4), 64);
+        assert_eq!(reduced_burn_ticks(160, 8), 0);
+    }
+}
@@ -0,0 +1,16 @@
+use crate::entity::ai::goal::{
+    flee::FleeGoal, look_at_entity::LookAtEntityGoal, wander::WanderGoal,
+};
+
+use super::MobEntity;
+
+/// Passive mobs like cows and pigs
+pub struct Animal;
+
+impl Animal {
+    pub async fn make(mob: &MobEntity) {
+        mob.goal(1, FleeGoal::new(0.25)).await;
+        mob.goal(6, WanderGoal::new(0.1)).await;
+        mob.goal(7, LookAtEntityGoal::new(6.0)).await;
+    }
+}
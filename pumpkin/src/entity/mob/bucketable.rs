@@ -0,0 +1,126 @@
+//! Mobs which can be caught with a water bucket, like fish and axolotls, and let out again by
+//! emptying the bucket.
+
+use std::sync::Arc;
+
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::item::ItemStack;
+
+use crate::entity::player::Player;
+use crate::world::World;
+
+use super::{MobEntity, from_type};
+
+const BUCKETABLE: [EntityType; 6] = [
+    EntityType::COD,
+    EntityType::SALMON,
+    EntityType::PUFFERFISH,
+    EntityType::TROPICAL_FISH,
+    EntityType::AXOLOTL,
+    EntityType::TADPOLE,
+];
+
+/// The bucket the mob is caught in and the sound of catching it
+fn bucket_of(entity_type: EntityType) -> Option<(Item, Sound)> {
+    Some(match entity_type {
+        EntityType::COD => (Item::COD_BUCKET, Sound::ItemBucketFillFish),
+        EntityType::SALMON => (Item::SALMON_BUCKET, Sound::ItemBucketFillFish),
+        EntityType::PUFFERFISH => (Item::PUFFERFISH_BUCKET, Sound::ItemBucketFillFish),
+        EntityType::TROPICAL_FISH => (Item::TROPICAL_FISH_BUCKET, Sound::ItemBucketFillFish),
+        EntityType::AXOLOTL => (Item::AXOLOTL_BUCKET, Sound::ItemBucketFillAxolotl),
+        EntityType::TADPOLE => (Item::TADPOLE_BUCKET, Sound::ItemBucketFillTadpole),
+        _ => return None,
+    })
+}
+
+/// The mob living in a bucket, like a cod in a bucket of cod
+#[must_use]
+pub fn mob_in(bucket: &Item) -> Option<EntityType> {
+    BUCKETABLE
+        .into_iter()
+        .find(|entity_type| bucket_of(*entity_type).is_some_and(|(item, _)| item.id == bucket.id))
+}
+
+/// The sound of letting the mob out of its bucket
+#[must_use]
+pub fn empty_sound(entity_type: EntityType) -> Sound {
+    match entity_type {
+        EntityType::AXOLOTL => Sound::ItemBucketEmptyAxolotl,
+        EntityType::TADPOLE => Sound::ItemBucketEmptyTadpole,
+        _ => Sound::ItemBucketEmptyFish,
+    }
+}
+
+/// Catches the mob if the player holds a water bucket, returns false if the mob can't be caught
+pub async fn try_catch(mob: &MobEntity, player: &Player) -> bool {
+    let living = &mob.living_entity;
+    let Some((bucket, sound)) = bucket_of(living.entity.entity_type) else {
+        return false;
+    };
+    let holds_water = player
+        .inventory()
+        .lock()
+        .await
+        .held_item()
+        .is_some_and(|stack| stack.item.id == Item::WATER_BUCKET.id);
+    if !holds_water || living.health.load() <= 0.0 {
+        return false;
+    }
+
+    let world = living.entity.world.read().await.clone();
+    world
+        .play_sound(sound, SoundCategory::Neutral, &living.entity.pos.load())
+        .await;
+
+    let mut data = NbtCompound::new();
+    data.put_float("Health", living.health.load());
+    let mut stack = ItemStack::new(1, bucket);
+    stack.components.bucket_entity_data = Some(data);
+    living.entity.remove().await;
+    player.exchange_held_item(stack).await;
+    true
+}
+
+/// Lets the mob in the bucket out at the position
+pub async fn release(
+    world: &Arc<World>,
+    entity_type: EntityType,
+    data: Option<&NbtCompound>,
+    position: BlockPos,
+) {
+    let position = Vector3::new(
+        f64::from(position.0.x) + 0.5,
+        f64::from(position.0.y),
+        f64::from(position.0.z) + 0.5,
+    );
+    let mob = from_type(entity_type, position, world).await;
+    let health = data.and_then(|data| data.get_float("Health"));
+    if let (Some(health), Some(living)) = (health, mob.get_living_entity()) {
+        living.health.store(health);
+    }
+    world.spawn_entity(mob).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_data::item::Item;
+
+    use super::{BUCKETABLE, bucket_of, mob_in};
+
+    #[test]
+    fn buckets_of_mobs() {
+        assert_eq!(mob_in(&Item::COD_BUCKET), Some(EntityType::COD));
+        assert_eq!(mob_in(&Item::AXOLOTL_BUCKET), Some(EntityType::AXOLOTL));
+        assert_eq!(mob_in(&Item::WATER_BUCKET), None);
+        for entity_type in BUCKETABLE {
+            let (bucket, _) = bucket_of(entity_type).unwrap();
+            assert_eq!(mob_in(&bucket), Some(entity_type));
+        }
+        assert!(bucket_of(EntityType::ZOMBIE).is_none());
+    }
+}
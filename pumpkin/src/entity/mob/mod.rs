@@ -1,45 +1,95 @@
 use std::sync::Arc;
 
+use animal::Animal;
 use async_trait::async_trait;
 use pumpkin_data::entity::EntityType;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::Difficulty;
+use pumpkin_util::java_uuid::uuid_from_nbt;
 use pumpkin_util::math::vector3::Vector3;
 use tokio::sync::Mutex;
+use villager::{Gossips, Villager};
 use zombie::Zombie;
+use zombie_villager::ZombieVillager;
 
 use crate::{server::Server, world::World};
 
 use super::{
-    Entity, EntityBase,
-    ai::{goal::Goal, path::Navigator},
+    Entity, EntityBase, NBTStorage,
+    ai::{
+        goal::{Goal, GoalSelector},
+        path::Navigator,
+    },
     living::LivingEntity,
+    player::Player,
 };
 
+pub mod animal;
+pub mod bucketable;
+pub mod villager;
 pub mod zombie;
+pub mod zombie_villager;
 
 pub struct MobEntity {
     pub living_entity: LivingEntity,
-    pub goals: Mutex<Vec<(Arc<dyn Goal>, bool)>>,
+    pub goal_selector: Mutex<GoalSelector>,
+    /// The player the mob is after, set by its `TargetGoal`
+    // TODO: make this an entity
+    pub target: Mutex<Option<Arc<Player>>>,
     pub navigator: Mutex<Navigator>,
+    /// The mob turns into another one once this runs out, like a zombie villager being cured
+    pub conversion: Mutex<Option<Conversion>>,
+    /// What villagers think of the players, this changes their prices
+    pub gossips: Mutex<Gossips>,
+}
+
+pub struct Conversion {
+    pub into: EntityType,
+    pub ticks_left: i32,
+    /// The player who started it, like the one curing a zombie villager
+    pub started_by: Option<uuid::Uuid>,
 }
 
 #[async_trait]
 impl EntityBase for MobEntity {
     async fn tick(&self, server: &Server) {
         self.living_entity.tick(server).await;
-        let mut goals = self.goals.lock().await;
-        for (goal, running) in goals.iter_mut() {
-            if *running {
-                if goal.should_continue(self).await {
-                    goal.tick(self).await;
-                } else {
-                    *running = false;
-                }
-            } else {
-                *running = goal.can_start(self).await;
-            }
-        }
+        self.goal_selector.lock().await.tick(self).await;
         let mut navigator = self.navigator.lock().await;
         navigator.tick(&self.living_entity).await;
+        drop(navigator);
+        self.tick_conversion().await;
+    }
+
+    async fn interact(&self, player: &Player, server: &Server) {
+        if bucketable::try_catch(self, player).await {
+            return;
+        }
+        if self.living_entity.entity.entity_type == EntityType::ZOMBIE_VILLAGER {
+            zombie_villager::try_cure(self, player, server).await;
+        }
+    }
+
+    async fn struck_by_lightning(&self) {
+        let into = match self.living_entity.entity.entity_type {
+            EntityType::VILLAGER => EntityType::WITCH,
+            EntityType::PIG => EntityType::ZOMBIFIED_PIGLIN,
+            _ => return,
+        };
+        let world = self.living_entity.entity.world.read().await.clone();
+        if world.difficulty() != Difficulty::Peaceful {
+            self.convert_to(into).await;
+        }
+    }
+
+    async fn to_nbt(&self) -> Option<NbtCompound> {
+        let entity_type = self.living_entity.entity.entity_type;
+        // Things like lightning bolts are created as mobs too, but they don't last
+        entity_type.max_health?;
+        let mut nbt = NbtCompound::new();
+        nbt.put_string("id", format!("minecraft:{}", entity_type.resource_name));
+        self.living_entity.write_nbt(&mut nbt).await;
+        Some(nbt)
     }
 
     fn get_entity(&self) -> &Entity {
@@ -51,27 +101,118 @@ impl EntityBase for MobEntity {
     }
 }
 
-pub async fn from_type(
-    entity_type: EntityType,
-    position: Vector3<f64>,
-    world: &Arc<World>,
-) -> Arc<dyn EntityBase> {
-    let entity = world.create_entity(position, entity_type);
+async fn create(entity_type: EntityType, position: Vector3<f64>, world: &Arc<World>) -> MobEntity {
+    create_from(world.create_entity(position, entity_type)).await
+}
+
+async fn create_from(entity: Entity) -> MobEntity {
+    let entity_type = entity.entity_type;
     let mob = MobEntity {
         living_entity: LivingEntity::new(entity),
-        goals: Mutex::new(vec![]),
+        goal_selector: Mutex::new(GoalSelector::default()),
+        target: Mutex::new(None),
         navigator: Mutex::new(Navigator::default()),
+        conversion: Mutex::new(None),
+        gossips: Mutex::new(Gossips::default()),
     };
     match entity_type {
-        EntityType::ZOMBIE => Zombie::make(&mob).await,
+        EntityType::ZOMBIE | EntityType::HUSK => Zombie::make(&mob).await,
+        EntityType::ZOMBIE_VILLAGER => ZombieVillager::make(&mob).await,
+        EntityType::VILLAGER => Villager::make(&mob).await,
+        EntityType::SHEEP
+        | EntityType::PIG
+        | EntityType::CHICKEN
+        | EntityType::COW
+        | EntityType::MOOSHROOM
+        | EntityType::RABBIT
+        | EntityType::HORSE
+        | EntityType::DONKEY => Animal::make(&mob).await,
         // TODO
         _ => (),
     }
-    Arc::new(mob)
+    mob
+}
+
+pub async fn from_type(
+    entity_type: EntityType,
+    position: Vector3<f64>,
+    world: &Arc<World>,
+) -> Arc<dyn EntityBase> {
+    if entity_type == EntityType::LIGHTNING_BOLT {
+        strike_lightning(world, position).await;
+    }
+    Arc::new(create(entity_type, position, world).await)
+}
+
+/// Creates the mob saved in the NBT, `None` if it isn't one which can be loaded
+pub async fn from_nbt(mut nbt: NbtCompound, world: &Arc<World>) -> Option<Arc<dyn EntityBase>> {
+    let id = nbt.get_string("id")?;
+    let entity_type = EntityType::from_name(id.strip_prefix("minecraft:").unwrap_or(id))?;
+    entity_type.max_health?;
+    let position = nbt.get_list("Pos")?;
+    let position = Vector3::new(
+        position.first()?.extract_double()?,
+        position.get(1)?.extract_double()?,
+        position.get(2)?.extract_double()?,
+    );
+    let uuid = nbt
+        .get("UUID")
+        .and_then(uuid_from_nbt)
+        .unwrap_or_else(uuid::Uuid::new_v4);
+
+    let entity = Entity::new(uuid, world.clone(), position, entity_type, false);
+    let mut mob = create_from(entity).await;
+    mob.living_entity.read_nbt(&mut nbt).await;
+    Some(Arc::new(mob))
+}
+
+/// Lets a lightning bolt strike the entities around where it hits
+pub async fn strike_lightning(world: &World, position: Vector3<f64>) {
+    for entity in world.entities_in_range(position, 3.0) {
+        entity.struck_by_lightning().await;
+    }
 }
 
 impl MobEntity {
-    pub async fn goal<T: Goal + 'static>(&self, goal: T) {
-        self.goals.lock().await.push((Arc::new(goal), false));
+    pub async fn goal<T: Goal + 'static>(&self, priority: u8, goal: T) {
+        self.goal_selector
+            .lock()
+            .await
+            .add(priority, Arc::new(goal));
+    }
+
+    /// Replaces the mob with a new one of the given type, standing where it stood
+    pub async fn convert_to(&self, entity_type: EntityType) -> Arc<Self> {
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        let mob = create(entity_type, entity.pos.load(), &world).await;
+        let new_entity = &mob.living_entity.entity;
+        new_entity.set_rotation(entity.yaw.load(), entity.pitch.load());
+        new_entity.head_yaw.store(entity.head_yaw.load());
+
+        let mob = Arc::new(mob);
+        entity.remove().await;
+        world.spawn_entity(mob.clone()).await;
+        mob
+    }
+
+    async fn tick_conversion(&self) {
+        let conversion = {
+            let mut conversion = self.conversion.lock().await;
+            let Some(running) = conversion.as_mut() else {
+                return;
+            };
+            running.ticks_left -= 1;
+            if running.ticks_left > 0 {
+                return;
+            }
+            conversion.take()
+        };
+        if let Some(conversion) = conversion {
+            let mob = self.convert_to(conversion.into).await;
+            if conversion.into == EntityType::VILLAGER {
+                zombie_villager::finish_curing(&mob, conversion.started_by).await;
+            }
+        }
     }
 }
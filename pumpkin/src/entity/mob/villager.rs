@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::entity::ai::goal::{look_at_entity::LookAtEntityGoal, wander::WanderGoal};
+
+use super::MobEntity;
+
+pub struct Villager;
+
+impl Villager {
+    pub async fn make(mob: &MobEntity) {
+        mob.goal(8, LookAtEntityGoal::new(8.0)).await;
+        mob.goal(10, WanderGoal::new(0.1)).await;
+    }
+}
+
+/// What a villager heard about a player
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GossipType {
+    MajorNegative,
+    MinorNegative,
+    MinorPositive,
+    MajorPositive,
+    Trading,
+}
+
+impl GossipType {
+    /// How much the gossip counts towards the reputation of the player
+    const fn weight(self) -> i32 {
+        match self {
+            Self::MajorNegative => -5,
+            Self::MinorNegative => -1,
+            Self::MinorPositive | Self::Trading => 1,
+            Self::MajorPositive => 5,
+        }
+    }
+
+    const fn max(self) -> i32 {
+        match self {
+            Self::MajorNegative => 100,
+            Self::MinorNegative | Self::MinorPositive => 200,
+            Self::MajorPositive => 20,
+            Self::Trading => 25,
+        }
+    }
+}
+
+/// The reputation players have with a villager, a good reputation lowers the prices
+#[derive(Default)]
+pub struct Gossips {
+    gossips: HashMap<(Uuid, GossipType), i32>,
+}
+
+impl Gossips {
+    pub fn add(&mut self, player: Uuid, gossip: GossipType, value: i32) {
+        let current = self.gossips.entry((player, gossip)).or_default();
+        *current = (*current + value).min(gossip.max());
+    }
+
+    #[must_use]
+    pub fn reputation(&self, player: Uuid) -> i32 {
+        self.gossips
+            .iter()
+            .filter(|((uuid, _), _)| *uuid == player)
+            .map(|((_, gossip), value)| gossip.weight() * value)
+            .sum()
+    }
+
+    /// How much the price of a trade changes for the player, negative for a discount
+    #[must_use]
+    pub fn price_adjustment(&self, player: Uuid, price_multiplier: f32) -> i32 {
+        -(self.reputation(player) as f32 * price_multiplier).floor() as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{GossipType, Gossips};
+
+    #[test]
+    fn curing_gives_a_discount() {
+        let player = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let mut gossips = Gossips::default();
+        gossips.add(player, GossipType::MajorPositive, 20);
+        gossips.add(player, GossipType::MinorPositive, 25);
+        assert_eq!(gossips.reputation(player), 125);
+        assert_eq!(gossips.reputation(other), 0);
+        // Emeralds for a trade with a price multiplier of 0.05
+        assert_eq!(gossips.price_adjustment(player, 0.05), -6);
+
+        // Curing the same villager twice does not stack the major gossip
+        gossips.add(player, GossipType::MajorPositive, 20);
+        assert_eq!(gossips.reputation(player), 125);
+    }
+}
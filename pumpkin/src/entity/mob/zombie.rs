@@ -1,4 +1,7 @@
-use crate::entity::ai::goal::{look_at_entity::LookAtEntityGoal, target_goal::TargetGoal};
+use crate::entity::ai::goal::{
+    look_at_entity::LookAtEntityGoal, melee_attack::MeleeAttackGoal, target_goal::TargetGoal,
+    wander::WanderGoal,
+};
 
 use super::MobEntity;
 
@@ -6,7 +9,10 @@ pub struct Zombie;
 
 impl Zombie {
     pub async fn make(mob: &MobEntity) {
-        mob.goal(LookAtEntityGoal::new(8.0)).await;
-        mob.goal(TargetGoal::new(16.0)).await;
+        mob.goal(2, MeleeAttackGoal::new(0.15, 3.0)).await;
+        mob.goal(7, WanderGoal::new(0.1)).await;
+        mob.goal(8, LookAtEntityGoal::new(8.0)).await;
+        // TODO: Target villagers as well, which get infected by `zombie_villager::infect`
+        mob.goal(2, TargetGoal::new(16.0)).await;
     }
 }
@@ -0,0 +1,132 @@
+use pumpkin_data::entity::{EffectType, EntityStatus, EntityType};
+use pumpkin_data::item::Item;
+use pumpkin_data::world::WorldEvent;
+use pumpkin_protocol::client::play::{CLevelEvent, MetaDataType, Metadata};
+use pumpkin_util::{Difficulty, GameMode};
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::entity::ai::goal::{
+    look_at_entity::LookAtEntityGoal, melee_attack::MeleeAttackGoal, target_goal::TargetGoal,
+    wander::WanderGoal,
+};
+use crate::entity::effect::Effect;
+use crate::entity::player::Player;
+use crate::server::Server;
+
+use super::villager::GossipType;
+use super::{Conversion, MobEntity};
+
+/// The metadata index telling the client the zombie villager is shaking while being cured
+const CONVERTING_INDEX: u8 = 19;
+
+pub struct ZombieVillager;
+
+impl ZombieVillager {
+    pub async fn make(mob: &MobEntity) {
+        mob.goal(2, MeleeAttackGoal::new(0.15, 3.0)).await;
+        mob.goal(7, WanderGoal::new(0.1)).await;
+        mob.goal(8, LookAtEntityGoal::new(8.0)).await;
+        mob.goal(2, TargetGoal::new(16.0)).await;
+    }
+}
+
+const fn effect(r#type: EffectType, duration: i32) -> Effect {
+    Effect {
+        r#type,
+        duration,
+        amplifier: 0,
+        ambient: false,
+        show_particles: true,
+        show_icon: true,
+    }
+}
+
+/// Starts curing a zombie villager which has weakness, when the player feeds it a golden apple
+pub async fn try_cure(mob: &MobEntity, player: &Player, server: &Server) {
+    if mob.conversion.lock().await.is_some()
+        || !mob.living_entity.has_effect(EffectType::Weakness).await
+    {
+        return;
+    }
+
+    {
+        let mut inventory = player.inventory().lock().await;
+        if inventory
+            .held_item()
+            .is_none_or(|stack| stack.item.id != Item::GOLDEN_APPLE.id)
+        {
+            return;
+        }
+        if player.gamemode.load() != GameMode::Creative {
+            inventory.decrease_current_stack(1);
+            let slot_id = inventory.get_selected_slot();
+            let held_item = inventory.held_item().cloned();
+            let _ = player
+                .handle_decrease_item(
+                    server,
+                    slot_id as i16,
+                    held_item.as_ref(),
+                    &mut inventory.state_id,
+                )
+                .await;
+        }
+    }
+
+    // Takes 3 to 5 minutes
+    let ticks = rand::thread_rng().gen_range(3600..=6000);
+    *mob.conversion.lock().await = Some(Conversion {
+        into: EntityType::VILLAGER,
+        ticks_left: ticks,
+        started_by: Some(player.gameprofile.id),
+    });
+
+    let living = &mob.living_entity;
+    living.remove_effect(EffectType::Weakness).await;
+    living.add_effect(effect(EffectType::Strength, ticks)).await;
+    living
+        .entity
+        .send_meta_data(&[Metadata::new(CONVERTING_INDEX, MetaDataType::Boolean, true)])
+        .await;
+    let world = living.entity.world.read().await.clone();
+    world
+        .send_entity_status(&living.entity, EntityStatus::PlayCureZombieVillagerSound)
+        .await;
+}
+
+/// Called on the villager a zombie villager was cured into, the player who cured it gets a
+/// discount when trading
+pub async fn finish_curing(villager: &MobEntity, cured_by: Option<Uuid>) {
+    let living = &villager.living_entity;
+    living.add_effect(effect(EffectType::Nausea, 200)).await;
+    if let Some(player) = cured_by {
+        let mut gossips = villager.gossips.lock().await;
+        gossips.add(player, GossipType::MajorPositive, 20);
+        gossips.add(player, GossipType::MinorPositive, 25);
+    }
+
+    let world = living.entity.world.read().await.clone();
+    world
+        .broadcast_packet_all(&CLevelEvent::new(
+            WorldEvent::ZombieVillagerCured as i32,
+            living.entity.block_pos.load(),
+            0,
+            false,
+        ))
+        .await;
+}
+
+/// Called when a zombie kills a villager, returns whether it came back as a zombie villager.
+/// Villagers only get infected on normal difficulty, where half of them do, and hard
+pub async fn infect(villager: &MobEntity) -> bool {
+    let world = villager.living_entity.entity.world.read().await.clone();
+    let infected = match world.difficulty() {
+        Difficulty::Hard => true,
+        Difficulty::Normal => rand::random(),
+        Difficulty::Easy | Difficulty::Peaceful => false,
+    };
+    if infected {
+        villager.convert_to(EntityType::ZOMBIE_VILLAGER).await;
+    }
+    infected
+}
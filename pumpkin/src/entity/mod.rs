@@ -3,6 +3,8 @@ use async_trait::async_trait;
 use bytes::{BufMut, BytesMut};
 use core::f32;
 use crossbeam::atomic::AtomicCell;
+use ids::ENTITY_IDS;
+use item::ItemEntity;
 use living::LivingEntity;
 use player::Player;
 use pumpkin_data::{
@@ -15,10 +17,11 @@ use pumpkin_protocol::{
     bytebuf::serializer::Serializer,
     client::play::{
         CEntityVelocity, CHeadRot, CSetEntityMetadata, CSpawnEntity, CTeleportEntity,
-        CUpdateEntityRot, MetaDataType, Metadata,
+        CUpdateEntityPos, CUpdateEntityRot, MetaDataType, Metadata,
     },
     codec::var_int::VarInt,
 };
+use pumpkin_util::java_uuid::uuid_to_nbt;
 use pumpkin_util::math::{
     boundingbox::{BoundingBox, EntityDimensions},
     get_section_cord,
@@ -27,19 +30,20 @@ use pumpkin_util::math::{
     vector3::Vector3,
     wrap_degrees,
 };
+use pumpkin_world::block::registry::get_block_collision_boxes;
 use serde::Serialize;
-use std::sync::{
-    Arc,
-    atomic::{AtomicBool, AtomicI32},
-};
+use std::sync::{Arc, atomic::AtomicBool};
 use tokio::sync::RwLock;
 
 use crate::world::World;
 
 pub mod ai;
 pub mod effect;
+pub mod end_crystal;
+pub mod ender_dragon;
 pub mod experience_orb;
 pub mod hunger;
+pub mod ids;
 pub mod item;
 pub mod living;
 pub mod mob;
@@ -73,12 +77,25 @@ pub trait EntityBase: Send + Sync {
 
     /// Called when a player collides with a entity
     async fn on_player_collision(&self, _player: Arc<Player>) {}
+
+    /// Called when a player right clicks the entity with their main hand
+    async fn interact(&self, _player: &Player, _server: &Server) {}
+
+    /// Called when a lightning bolt strikes close to the entity
+    async fn struck_by_lightning(&self) {}
+
+    /// The entity as it is saved with its chunk, `None` for entities which aren't saved, like players
+    async fn to_nbt(&self) -> Option<NbtCompound> {
+        None
+    }
     fn get_entity(&self) -> &Entity;
     fn get_living_entity(&self) -> Option<&LivingEntity>;
+    /// The dropped item if the entity is one, used to merge nearby stacks
+    fn get_item_entity(&self) -> Option<&ItemEntity> {
+        None
+    }
 }
 
-static CURRENT_ID: AtomicI32 = AtomicI32::new(0);
-
 /// Represents a not living Entity (e.g. Item, Egg, Snowball...)
 pub struct Entity {
     /// A unique identifier for the entity
@@ -143,13 +160,20 @@ impl Entity {
         };
 
         Self {
-            entity_id: CURRENT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            entity_id: if entity_type == EntityType::ENDER_DRAGON {
+                ENTITY_IDS.allocate_with_parts(entity_uuid, ender_dragon::PARTS)
+            } else {
+                ENTITY_IDS.allocate(entity_uuid)
+            },
             entity_uuid,
             entity_type,
             on_ground: AtomicBool::new(false),
             pos: AtomicCell::new(position),
             block_pos: AtomicCell::new(BlockPos(Vector3::new(floor_x, floor_y, floor_z))),
-            chunk_pos: AtomicCell::new(Vector2::new(floor_x, floor_z)),
+            chunk_pos: AtomicCell::new(Vector2::new(
+                get_section_cord(floor_x),
+                get_section_cord(floor_z),
+            )),
             sneaking: AtomicBool::new(false),
             world: Arc::new(RwLock::new(world)),
             // TODO: Load this from previous instance
@@ -190,9 +214,9 @@ impl Entity {
         if pos != new_position {
             self.pos.store(new_position);
             self.bounding_box.store(BoundingBox::new_from_pos(
-                pos.x,
-                pos.y,
-                pos.z,
+                new_position.x,
+                new_position.y,
+                new_position.z,
                 &self.bounding_box_size.load(),
             ));
 
@@ -222,6 +246,67 @@ impl Entity {
         }
     }
 
+    /// Moves the entity by `movement` without passing through the collision shapes of blocks and
+    /// updates whether it is on the ground. Returns how far it actually moved
+    pub async fn move_with_collisions(&self, movement: Vector3<f64>) -> Vector3<f64> {
+        let world = self.world.read().await.clone();
+        let bounding_box = self.bounding_box.load();
+        let area = bounding_box.stretch(movement);
+
+        let mut block_boxes = Vec::new();
+        for x in area.min.x.floor() as i32..=area.max.x.floor() as i32 {
+            for y in area.min.y.floor() as i32..=area.max.y.floor() as i32 {
+                for z in area.min.z.floor() as i32..=area.max.z.floor() as i32 {
+                    let block_pos = BlockPos(Vector3::new(x, y, z));
+                    if let Ok(state_id) = world.get_block_state_id(&block_pos).await {
+                        block_boxes.extend(get_block_collision_boxes(state_id, &block_pos));
+                    }
+                }
+            }
+        }
+
+        // Like vanilla, the vertical movement is resolved first so entities land on blocks
+        let y = block_boxes
+            .iter()
+            .fold(movement.y, |y, block| bounding_box.max_offset_y(block, y));
+        let bounding_box = bounding_box.shift(0.0, y, 0.0);
+        let x = block_boxes
+            .iter()
+            .fold(movement.x, |x, block| bounding_box.max_offset_x(block, x));
+        let bounding_box = bounding_box.shift(x, 0.0, 0.0);
+        let z = block_boxes
+            .iter()
+            .fold(movement.z, |z, block| bounding_box.max_offset_z(block, z));
+
+        #[expect(clippy::float_cmp)]
+        let landed = movement.y < 0.0 && y != movement.y;
+        self.on_ground
+            .store(landed, std::sync::atomic::Ordering::Relaxed);
+
+        let moved = Vector3::new(x, y, z);
+        let pos = self.pos.load();
+        self.set_pos(Vector3::new(pos.x + x, pos.y + y, pos.z + z));
+        moved
+    }
+
+    /// Tells the clients about a movement of the entity, which started at `last_pos`
+    pub async fn send_movement(&self, last_pos: Vector3<f64>) {
+        let pos = self.pos.load();
+        self.world
+            .read()
+            .await
+            .broadcast_packet_all(&CUpdateEntityPos::new(
+                self.entity_id.into(),
+                Vector3::new(
+                    pos.x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
+                    pos.y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
+                    pos.z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
+                ),
+                self.on_ground.load(std::sync::atomic::Ordering::Relaxed),
+            ))
+            .await;
+    }
+
     /// Returns entity rotation as vector
     pub fn rotation(&self) -> Vector3<f32> {
         // Convert degrees to radians if necessary
@@ -267,23 +352,70 @@ impl Entity {
             .await;
     }
 
-    pub async fn teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) {
-        self.world
+    /// Teleports the entity to a position in the given world.
+    ///
+    /// Players are moved through `Player::teleport` and `Player::teleport_world`, so they get their
+    /// events and the packets for changing dimension. Other entities are removed from their old
+    /// world and spawned again in the new one, so observers start tracking them there.
+    // TODO: Unmount passengers once entities can ride each other
+    pub async fn teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32, world: Arc<World>) {
+        let current_world = self.world.read().await.clone();
+        let same_world = Arc::ptr_eq(&current_world, &world);
+
+        if self.entity_type == EntityType::PLAYER {
+            if let Some(player) = current_world.get_player_by_uuid(self.entity_uuid).await {
+                if same_world {
+                    player.teleport(position, yaw, pitch).await;
+                } else {
+                    player
+                        .teleport_world(world, Some(position), Some(yaw), Some(pitch))
+                        .await;
+                }
+                return;
+            }
+        }
+
+        // Make sure the destination is loaded before the entity arrives
+        let chunk = BlockPos(Vector3::new(
+            position.x.floor() as i32,
+            position.y.floor() as i32,
+            position.z.floor() as i32,
+        ))
+        .chunk_and_chunk_relative_position()
+        .0;
+        world.receive_chunk(chunk).await;
+
+        if same_world {
+            world
+                .broadcast_packet_all(&CTeleportEntity::new(
+                    self.entity_id.into(),
+                    position,
+                    Vector3::new(0.0, 0.0, 0.0),
+                    yaw,
+                    pitch,
+                    // TODO
+                    &[],
+                    self.on_ground.load(std::sync::atomic::Ordering::SeqCst),
+                ))
+                .await;
+            self.set_pos(position);
+            self.set_rotation(yaw, pitch);
+            return;
+        }
+
+        let entity = current_world
+            .entities
             .read()
             .await
-            .broadcast_packet_all(&CTeleportEntity::new(
-                self.entity_id.into(),
-                position,
-                Vector3::new(0.0, 0.0, 0.0),
-                yaw,
-                pitch,
-                // TODO
-                &[],
-                self.on_ground.load(std::sync::atomic::Ordering::SeqCst),
-            ))
-            .await;
+            .get(&self.entity_uuid)
+            .cloned();
+        current_world.remove_entity(self).await;
+        *self.world.write().await = world.clone();
         self.set_pos(position);
         self.set_rotation(yaw, pitch);
+        if let Some(entity) = entity {
+            world.spawn_entity(entity).await;
+        }
     }
 
     /// Sets the Entity yaw & pitch Rotation
@@ -456,6 +588,12 @@ impl Entity {
     }
 }
 
+impl Drop for Entity {
+    fn drop(&mut self) {
+        ENTITY_IDS.release(self.entity_id);
+    }
+}
+
 #[async_trait]
 impl EntityBase for Entity {
     async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
@@ -496,6 +634,7 @@ impl NBTStorage for Entity {
             "Rotation",
             NbtTag::List(vec![self.yaw.load().into(), self.pitch.load().into()].into_boxed_slice()),
         );
+        nbt.put("UUID", uuid_to_nbt(self.entity_uuid));
 
         // todo more...
     }
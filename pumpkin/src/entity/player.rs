@@ -13,25 +13,28 @@ use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_data::{
-    block::BlockState,
+    block::{Block, BlockState},
     damage::DamageType,
     entity::{EffectType, EntityStatus, EntityType},
-    item::Operation,
+    item::{Item, Operation},
     particle::Particle,
     sound::{Sound, SoundCategory},
 };
 use pumpkin_inventory::player::PlayerInventory;
+use pumpkin_inventory::{Container, EnderChest};
 use pumpkin_macros::send_cancellable;
 use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::tag::NbtTag;
 use pumpkin_protocol::{
     RawPacket, ServerPacket,
     bytebuf::packet::Packet,
     client::play::{
-        CAcknowledgeBlockChange, CActionBar, CChunkBatchEnd, CChunkBatchStart, CChunkData,
-        CCombatDeath, CDisguisedChatMessage, CGameEvent, CKeepAlive, CParticle, CPlayDisconnect,
-        CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition, CRespawn, CSetExperience, CSetHealth,
-        CStopSound, CSubtitle, CSystemChatMessage, CTeleportEntity, CTitleText, CUnloadChunk,
-        CUpdateMobEffect, GameEvent, MetaDataType, PlayerAction,
+        CAcknowledgeBlockChange, CActionBar, CBlockUpdate, CChunkBatchEnd, CChunkBatchStart,
+        CChunkData, CCombatDeath, CDisguisedChatMessage, CGameEvent, CKeepAlive, CParticle,
+        CPlayDisconnect, CPlayServerLinks, CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition,
+        CRemoveEntities, CRemoveMobEffect, CRemovePlayerInfo, CRespawn, CSetExperience, CSetHealth,
+        CSpawnEntity, CStopSound, CSubtitle, CSystemChatMessage, CTeleportEntity, CTitleText,
+        CTransfer, CUnloadChunk, CUpdateMobEffect, GameEvent, MetaDataType, PlayerAction,
     },
     codec::identifier::Identifier,
     server::play::{
@@ -53,17 +56,24 @@ use pumpkin_protocol::{
     client::play::Metadata,
     server::play::{SClickContainer, SKeepAlive},
 };
+use pumpkin_registry::DimensionType;
 use pumpkin_util::{
     GameMode,
     math::{
-        boundingbox::BoundingBox, experience, position::BlockPos, vector2::Vector2,
+        boundingbox::BoundingBox,
+        experience,
+        position::{BlockPos, GlobalPos},
+        vector2::Vector2,
         vector3::Vector3,
     },
     permission::PermissionLvl,
-    text::TextComponent,
+    text::{TextComponent, color::NamedColor},
 };
-use pumpkin_world::{cylindrical_chunk_iterator::Cylindrical, item::ItemStack, level::SyncChunk};
-use tokio::sync::{Mutex, Notify, RwLock};
+use pumpkin_world::{
+    WORLD_LOWEST_Y, cylindrical_chunk_iterator::Cylindrical, item::ItemStack, level::SyncChunk,
+    world_info::game_rules,
+};
+use tokio::sync::{Mutex, Notify};
 
 use super::{
     Entity, EntityBase, EntityId, NBTStorage,
@@ -74,15 +84,25 @@ use super::{
 };
 use crate::{
     block,
-    command::{client_suggestions, dispatcher::CommandDispatcher},
+    command::client_suggestions,
     data::op_data::OPERATOR_CONFIG,
-    net::{Client, PlayerConfig},
+    net::{Client, PlayerConfig, disconnect::DisconnectReason},
     plugin::player::{
-        player_change_world::PlayerChangeWorldEvent,
+        player_afk_change::PlayerAfkChangeEvent, player_change_world::PlayerChangeWorldEvent,
         player_gamemode_change::PlayerGamemodeChangeEvent, player_teleport::PlayerTeleportEvent,
     },
-    server::Server,
-    world::World,
+    server::{
+        Server,
+        advancements::{PlayerAdvancements, Trigger},
+        links::ServerLinks,
+    },
+    world::{
+        World, end,
+        game_event::GameEventContext,
+        nether_portal, seed,
+        time::{ClientTime, LevelTime},
+        weather::{ClientWeather, Weather},
+    },
 };
 use crate::{error::PumpkinError, net::GameProfile};
 
@@ -182,6 +202,8 @@ pub struct Player {
     pub open_container: AtomicCell<Option<u64>>,
     /// The item currently being held by the player.
     pub carried_item: Mutex<Option<ItemStack>>,
+    /// The items in the player's ender chest, shared with the containers opened by ender chests
+    pub ender_chest: Arc<Mutex<Box<dyn Container>>>,
     /// send `send_abilities_update` when changed
     /// The player's abilities and special powers.
     ///
@@ -219,6 +241,8 @@ pub struct Player {
     pub client_loaded: AtomicBool,
     /// timeout (in ticks) client has to report it has finished loading.
     pub client_loaded_timeout: AtomicU32,
+    /// Whether nothing was saved about the player yet, so this is the first time they joined
+    pub first_join: AtomicBool,
     /// The player's experience level
     pub experience_level: AtomicI32,
     /// The player's experience progress (0.0 to 1.0)
@@ -227,9 +251,42 @@ pub struct Player {
     pub experience_points: AtomicI32,
     pub experience_pick_up_delay: Mutex<u32>,
     pub chunk_manager: Mutex<ChunkManager>,
+    /// The last time the player moved, chatted or interacted with something
+    last_action_time: AtomicCell<Instant>,
+    /// Since when the player is AFK, `None` if the player is not AFK
+    afk_since: AtomicCell<Option<Instant>>,
+    /// When the player last sent a chat message, for slow chat
+    pub last_chat_time: AtomicCell<Option<Instant>>,
+    /// Whether the player is hidden from players who can't see vanished players
+    vanished: AtomicBool,
+    /// The time this player sees instead of the world time
+    client_time: AtomicCell<Option<ClientTime>>,
+    /// The weather this player sees instead of the world weather
+    client_weather: AtomicCell<Option<ClientWeather>>,
+    /// How far the player walked since the last step, see `step`
+    step_distance: AtomicCell<f64>,
+    /// Where the player died last, the recovery compass points there
+    pub last_death_location: Mutex<Option<GlobalPos>>,
+    /// The item the player is using, like drinking milk, and for how many more ticks
+    item_in_use: AtomicCell<Option<(u16, u32)>>,
+    /// Which advancements the player has done
+    pub advancements: Mutex<PlayerAdvancements>,
+    /// The dimensions the player went from and to since the last tick, for advancements
+    changed_dimension: AtomicCell<Option<(DimensionType, DimensionType)>>,
+    /// Whether the player left the End through the exit portal before, then the credits are skipped
+    pub seen_credits: AtomicBool,
+    /// Whether the player is leaving the End, waiting for the client to be done with the credits
+    pub won_game: AtomicBool,
+    /// How many ticks the player has been standing in a nether portal
+    pub portal_ticks: AtomicU32,
+    /// Ticks until a nether portal can take the player again, see `nether_portal::COOLDOWN`
+    pub portal_cooldown: AtomicU32,
 }
 
 impl Player {
+    /// The permission level needed to see vanished players
+    pub const SEE_VANISHED_LEVEL: PermissionLvl = PermissionLvl::Two;
+
     pub async fn new(client: Arc<Client>, world: Arc<World>, gamemode: GameMode) -> Self {
         let gameprofile = client.gameprofile.lock().await.clone().map_or_else(
             || {
@@ -268,6 +325,7 @@ impl Player {
             packet_sequence: AtomicI32::new(-1),
             start_mining_time: AtomicI32::new(0),
             carried_item: Mutex::new(None),
+            ender_chest: Arc::new(Mutex::new(Box::new(EnderChest::default()))),
             experience_pick_up_delay: Mutex::new(0),
             teleport_id_count: AtomicI32::new(0),
             mining: AtomicBool::new(false),
@@ -288,24 +346,36 @@ impl Player {
             cancel_tasks: Notify::new(),
             client_loaded: AtomicBool::new(false),
             client_loaded_timeout: AtomicU32::new(60),
+            first_join: AtomicBool::new(true),
             // Minecraft has no why to change the default permission level of new players.
             // Minecrafts default permission level is 0
-            permission_lvl: OPERATOR_CONFIG
-                .read()
-                .await
-                .ops
-                .iter()
-                .find(|op| op.uuid == gameprofile_clone.id)
-                .map_or(
-                    AtomicCell::new(advanced_config().commands.default_op_level),
-                    |op| AtomicCell::new(op.level),
-                ),
+            permission_lvl: AtomicCell::new(
+                OPERATOR_CONFIG
+                    .read()
+                    .await
+                    .get_permission_lvl(gameprofile_clone.id),
+            ),
             inventory: Mutex::new(PlayerInventory::new()),
             experience_level: AtomicI32::new(0),
             experience_progress: AtomicCell::new(0.0),
             experience_points: AtomicI32::new(0),
             // Default to sending 16 chunks per tick
             chunk_manager: Mutex::new(ChunkManager::new(16)),
+            last_action_time: AtomicCell::new(Instant::now()),
+            afk_since: AtomicCell::new(None),
+            last_chat_time: AtomicCell::new(None),
+            vanished: AtomicBool::new(false),
+            client_time: AtomicCell::new(None),
+            client_weather: AtomicCell::new(None),
+            step_distance: AtomicCell::new(0.0),
+            last_death_location: Mutex::new(None),
+            item_in_use: AtomicCell::new(None),
+            advancements: Mutex::new(PlayerAdvancements::default()),
+            changed_dimension: AtomicCell::new(None),
+            seen_credits: AtomicBool::new(false),
+            won_game: AtomicBool::new(false),
+            portal_ticks: AtomicU32::new(0),
+            portal_cooldown: AtomicU32::new(0),
         }
     }
 
@@ -517,7 +587,8 @@ impl Player {
         self.cancel_tasks.notified().await;
     }
 
-    pub async fn tick(&self, server: &Server) {
+    #[expect(clippy::too_many_lines)]
+    pub async fn tick(self: &Arc<Self>, server: &Server) {
         if self
             .client
             .closed
@@ -548,19 +619,64 @@ impl Player {
 
         if let Some(chunk_of_chunks) = chunk_of_chunks {
             let chunk_count = chunk_of_chunks.len();
+            let dimension = self.world().await.dimension_type.dimension();
             self.client.send_packet(&CChunkBatchStart {}).await;
             for chunk in chunk_of_chunks {
                 let chunk = chunk.read().await;
                 // TODO: Can we check if we still need the chunk to send? Like if its a fast moving
                 // player or something
-                self.client.send_packet(&CChunkData(&chunk)).await;
+                let packet = CChunkData::new(&chunk, dimension.min_y(), dimension.height());
+                self.client.send_packet(&packet).await;
             }
             self.client
                 .send_packet(&CChunkBatchEnd::new(chunk_count))
                 .await;
         }
 
-        self.tick_counter.fetch_add(1, Ordering::Relaxed);
+        if self.tick_counter.fetch_add(1, Ordering::Relaxed) % 20 == 0 {
+            self.tick_lodestone_compasses().await;
+            let items: Vec<ItemStack> = self
+                .inventory
+                .lock()
+                .await
+                .slots()
+                .iter()
+                .flatten()
+                .map(|stack| (*stack).clone())
+                .collect();
+            self.trigger_advancements(server, Trigger::InventoryChanged(&items))
+                .await;
+        }
+        self.tick_item_in_use(server).await;
+        self.trigger_advancements(server, Trigger::Tick).await;
+        let feet = self
+            .world()
+            .await
+            .get_block(&self.living_entity.entity.block_pos.load())
+            .await
+            .map_or(Block::AIR.id, |block| block.id);
+        if feet == Block::END_PORTAL.id {
+            end::enter_portal(self, server).await;
+        } else if feet == Block::END_GATEWAY.id {
+            end::enter_gateway(self).await;
+        }
+        nether_portal::tick_player(self, server, feet == Block::NETHER_PORTAL.id).await;
+        // Falling into the void, like off the islands of the End, hurts
+        let below_world = self.living_entity.entity.pos.load().y < f64::from(WORLD_LOWEST_Y - 64);
+        if below_world && self.living_entity.health.load() > 0.0 {
+            self.damage(4.0, DamageType::OUT_OF_WORLD).await;
+        }
+        if let Some((from, to)) = self.changed_dimension.take() {
+            let (from, to) = (from.name().to_string(), to.name().to_string());
+            self.trigger_advancements(
+                server,
+                Trigger::ChangedDimension {
+                    from: &from,
+                    to: &to,
+                },
+            )
+            .await;
+        }
 
         if self.mining.load(Ordering::Relaxed) {
             let pos = self.mining_pos.lock().await;
@@ -595,6 +711,7 @@ impl Player {
 
         // timeout/keep alive handling
         self.tick_client_load_timeout();
+        self.tick_afk().await;
 
         let now = Instant::now();
         if now.duration_since(self.last_keep_alive_time.load()) >= Duration::from_secs(15) {
@@ -603,6 +720,7 @@ impl Player {
                 .wait_for_keep_alive
                 .load(std::sync::atomic::Ordering::Relaxed)
             {
+                self.client.set_disconnect_reason(DisconnectReason::Timeout);
                 self.kick(TextComponent::translate("disconnect.timeout", []))
                     .await;
                 return;
@@ -617,6 +735,106 @@ impl Player {
         }
     }
 
+    /// Starts using the held item, it is finished after the given ticks unless the player lets go
+    /// or switches to another item before
+    pub fn start_using_item(&self, item: &Item, ticks: u32) {
+        self.item_in_use.store(Some((item.id, ticks)));
+    }
+
+    /// Stops using the item without finishing it
+    pub fn stop_using_item(&self) {
+        self.item_in_use.store(None);
+    }
+
+    async fn tick_item_in_use(self: &Arc<Self>, server: &Server) {
+        let Some((item_id, ticks_left)) = self.item_in_use.load() else {
+            return;
+        };
+        if ticks_left > 0 {
+            self.item_in_use.store(Some((item_id, ticks_left - 1)));
+            return;
+        }
+        self.item_in_use.store(None);
+        let held = self.inventory.lock().await.held_item().cloned();
+        if let Some(held) = held.filter(|held| held.item.id == item_id) {
+            server
+                .item_registry
+                .finish_using(&held.item, self, server)
+                .await;
+            self.trigger_advancements(server, Trigger::ConsumeItem(&held))
+                .await;
+        }
+    }
+
+    /// Compasses pointing to a lodestone which is gone stop pointing there. Only lodestones in
+    /// loaded chunks are checked, to not load chunks for it
+    async fn tick_lodestone_compasses(&self) {
+        let world = self.world().await;
+        let dimension = world.dimension_type.name().to_string();
+        let targets: Vec<BlockPos> = self
+            .inventory
+            .lock()
+            .await
+            .slots()
+            .iter()
+            .flatten()
+            .filter_map(|stack| {
+                let tracker = stack.components.lodestone_tracker.as_ref()?;
+                let target = tracker.target.as_ref()?;
+                (tracker.tracked && target.dimension == dimension).then_some(target.pos)
+            })
+            .filter(|pos| {
+                world
+                    .level
+                    .is_chunk_loaded(&pos.chunk_and_chunk_relative_position().0)
+            })
+            .collect();
+
+        let mut gone = Vec::new();
+        for pos in targets {
+            let lodestone = world
+                .get_block(&pos)
+                .await
+                .is_ok_and(|block| block.id == Block::LODESTONE.id);
+            if !lodestone {
+                gone.push(pos);
+            }
+        }
+        if gone.is_empty() {
+            return;
+        }
+
+        {
+            let mut inventory = self.inventory.lock().await;
+            for stack in inventory.slots_mut().into_iter().flatten() {
+                let Some(tracker) = &mut stack.components.lodestone_tracker else {
+                    continue;
+                };
+                let points_to_gone = tracker.target.as_ref().is_some_and(|target| {
+                    tracker.tracked && target.dimension == dimension && gone.contains(&target.pos)
+                });
+                if points_to_gone {
+                    tracker.target = None;
+                }
+            }
+        }
+        self.set_container_content(None).await;
+    }
+
+    /// Where the player died last, as sent when joining or respawning
+    pub async fn last_death_location_for_client(&self) -> Option<(Identifier, BlockPos)> {
+        let location = self.last_death_location.lock().await;
+        let location = location.as_ref()?;
+        let (namespace, path) = location.dimension.split_once(':')?;
+        Some((
+            Identifier {
+                namespace: namespace.to_string(),
+                path: path.to_string(),
+            },
+            location.pos,
+        ))
+    }
+
     async fn continue_mining(
         &self,
         location: BlockPos,
@@ -656,10 +874,38 @@ impl Player {
                 } else {
                     self.add_exhaustion(0.0 * delta as f32 * 0.01).await;
                 }
+                self.step(delta_pos.horizontal_length()).await;
             }
         }
     }
 
+    /// Emits a `step` game event every few blocks walked, with the block stepped on
+    async fn step(&self, distance: f64) {
+        // Like the step sounds of vanilla, a step is made every 1 / 0.6 blocks
+        let walked = distance.mul_add(0.6, self.step_distance.load());
+        if walked < 1.0 {
+            self.step_distance.store(walked);
+            return;
+        }
+        self.step_distance.store(walked.fract());
+
+        let entity = &self.living_entity.entity;
+        let below = entity.block_pos.load().offset(Vector3::new(0, -1, 0));
+        let world = self.world().await;
+        let context = GameEventContext::entity(entity);
+        let context = world
+            .get_block_state_id(&below)
+            .await
+            .map_or(context, |state_id| context.with_state(state_id));
+        world
+            .emit_game_event(
+                pumpkin_data::game_event::GameEvent::Step,
+                entity.pos.load(),
+                context,
+            )
+            .await;
+    }
+
     pub fn has_client_loaded(&self) -> bool {
         self.client_loaded.load(Ordering::Relaxed)
             || self.client_loaded_timeout.load(Ordering::Relaxed) == 0
@@ -737,24 +983,94 @@ impl Player {
     }
 
     /// sets the players permission level and syncs it with the client
-    pub async fn set_permission_lvl(
-        self: &Arc<Self>,
-        lvl: PermissionLvl,
-        command_dispatcher: &RwLock<CommandDispatcher>,
-    ) {
+    pub async fn set_permission_lvl(self: &Arc<Self>, lvl: PermissionLvl, server: &Server) {
         self.permission_lvl.store(lvl);
         self.send_permission_lvl_update().await;
-        client_suggestions::send_c_commands_packet(self, command_dispatcher).await;
+        client_suggestions::send_c_commands_packet(self, &server.command_dispatcher).await;
+        let links = server.links.read().await;
+        self.send_links(&links).await;
+    }
+
+    /// Replaces the links in the pause menu with the ones the player may see
+    pub async fn send_links(&self, links: &ServerLinks) {
+        let links = links.visible_to(self.permission_lvl.load() as u8);
+        self.client
+            .send_packet(&CPlayServerLinks::new(&VarInt(links.len() as i32), &links))
+            .await;
     }
 
     /// Sends the world time to just the player.
     pub async fn send_time(&self, world: &World) {
-        let l_world = world.level_time.lock().await;
+        let level_time = world.level_time.lock().await;
+        self.send_level_time(&level_time).await;
+    }
+
+    /// Sends the time to the player, or the player's own time if set
+    pub async fn send_level_time(&self, level_time: &LevelTime) {
+        let daylight_cycle = self.world().await.game_rule(game_rules::DO_DAYLIGHT_CYCLE);
+        let (time_of_day, increasing) = self
+            .client_time
+            .load()
+            .map_or((level_time.time_of_day, daylight_cycle), |time| {
+                time.time_of_day(level_time.time_of_day)
+            });
         self.client
             .send_packet(&CUpdateTime::new(
-                l_world.world_age,
-                l_world.time_of_day,
-                true,
+                level_time.world_age,
+                time_of_day,
+                increasing,
+            ))
+            .await;
+    }
+
+    pub fn client_time(&self) -> Option<ClientTime> {
+        self.client_time.load()
+    }
+
+    /// Overrides the time only this player sees, `None` goes back to the world time.
+    /// The player keeps the time when the world time changes
+    pub async fn set_client_time(&self, time: Option<ClientTime>) {
+        self.client_time.store(time);
+        let world = self.world().await;
+        self.send_time(&world).await;
+    }
+
+    pub fn client_weather(&self) -> Option<ClientWeather> {
+        self.client_weather.load()
+    }
+
+    /// Overrides the weather only this player sees, `None` goes back to the world weather.
+    /// The player keeps the weather when the world weather changes
+    pub async fn set_client_weather(&self, weather: Option<ClientWeather>) {
+        self.client_weather.store(weather);
+        let world = self.world().await;
+        let weather = world.weather.lock().await;
+        self.send_weather(&weather).await;
+    }
+
+    /// Sends the weather to the player, or the player's own weather if set
+    pub async fn send_weather(&self, weather: &Weather) {
+        let (raining, rain_level, thunder_level) = self.client_weather.load().map_or(
+            (
+                weather.raining,
+                weather.rain_level.clamp(0.0, 1.0),
+                weather.thunder_level.clamp(0.0, 1.0),
+            ),
+            ClientWeather::levels,
+        );
+        let event = if raining {
+            GameEvent::BeginRaining
+        } else {
+            GameEvent::EndRaining
+        };
+        self.client.send_packet(&CGameEvent::new(event, 0.0)).await;
+        self.client
+            .send_packet(&CGameEvent::new(GameEvent::RainLevelChange, rain_level))
+            .await;
+        self.client
+            .send_packet(&CGameEvent::new(
+                GameEvent::ThunderLevelChange,
+                thunder_level,
             ))
             .await;
     }
@@ -792,6 +1108,23 @@ impl Player {
         ));
     }
 
+    /// Takes the player out of one world and puts them into another, without telling the client.
+    /// The caller sends the respawn packet and the position afterwards
+    pub async fn move_to_world(self: &Arc<Self>, from: &Arc<World>, to: &Arc<World>) {
+        let uuid = self.gameprofile.id;
+        from.remove_player(self, false).await;
+        *self.living_entity.entity.world.write().await = to.clone();
+        to.players.write().await.insert(uuid, self.clone());
+        to.player_index.insert(
+            uuid,
+            self.living_entity.entity.chunk_pos.load(),
+            self.clone(),
+        );
+        self.unload_watched_chunks(from).await;
+        self.changed_dimension
+            .store(Some((from.dimension_type, to.dimension_type)));
+    }
+
     /// Teleports the player to a different world or dimension with an optional position, yaw, and pitch.
     pub async fn teleport_world(
         self: &Arc<Self>,
@@ -840,28 +1173,18 @@ impl Player {
                 let new_world = event.new_world;
 
                 self.set_client_loaded(false);
-                let uuid = self.gameprofile.id;
-                current_world.remove_player(self, false).await;
-                *self.living_entity.entity.world.write().await = new_world.clone();
-                new_world.players.write().await.insert(uuid, self.clone());
-                self.unload_watched_chunks(&current_world).await;
-                let last_pos = self.living_entity.last_pos.load();
-                let death_dimension = self.world().await.dimension_type.name();
-                let death_location = BlockPos(Vector3::new(
-                    last_pos.x.round() as i32,
-                    last_pos.y.round() as i32,
-                    last_pos.z.round() as i32,
-                ));
+                self.move_to_world(&current_world, &new_world).await;
+                let death_location = self.last_death_location_for_client().await;
                 self.client
                     .send_packet(&CRespawn::new(
                         (new_world.dimension_type as u8).into(),
                         new_world.dimension_type.name(),
-                        0, // seed
+                        seed::client_hashed_seed(new_world.level.seed.0),
                         self.gamemode.load() as u8,
                         self.gamemode.load() as i8,
                         false,
                         false,
-                        Some((death_dimension, death_location)),
+                        death_location,
                         0.into(),
                         0.into(),
                         1,
@@ -918,8 +1241,8 @@ impl Player {
         }}
     }
 
-    /// Teleports the player to a different position with an optional yaw and pitch.
-    /// This method is identical to `entity.teleport()` but emits a `PlayerTeleportEvent` instead of a `EntityTeleportEvent`.
+    /// Teleports the player to a different position in its world, emitting a `PlayerTeleportEvent`.
+    /// Use `entity.teleport()` to teleport into other worlds as well.
     pub async fn teleport(self: &Arc<Self>, position: Vector3<f64>, yaw: f32, pitch: f32) {
         send_cancellable! {{
             PlayerTeleportEvent {
@@ -976,6 +1299,17 @@ impl Player {
         }) < d * d
     }
 
+    /// Sends the player the block at `position` as it is on the server, used to undo what the
+    /// client already predicted when an action on the block gets denied, like a cancelled event
+    pub async fn resync_block(&self, position: &BlockPos) {
+        let world = self.world().await;
+        if let Ok(state_id) = world.get_block_state_id(position).await {
+            self.client
+                .send_packet(&CBlockUpdate::new(position, VarInt(i32::from(state_id))))
+                .await;
+        }
+    }
+
     /// Kicks the Client with a reason depending on the connection state
     pub async fn kick(&self, reason: TextComponent) {
         if self
@@ -989,6 +1323,7 @@ impl Player {
             );
             return;
         }
+        self.client.set_disconnect_reason(DisconnectReason::Kicked);
 
         let _ = self
             .client
@@ -1005,6 +1340,30 @@ impl Player {
         self.client.close().await;
     }
 
+    /// Sends the player to another server, which only lets them in if it accepts transfers
+    pub async fn transfer(&self, host: &str, port: i32) {
+        if self
+            .client
+            .closed
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return;
+        }
+        self.client
+            .set_disconnect_reason(DisconnectReason::Transferred);
+        self.client
+            .send_packet(&CTransfer::new(host, VarInt(port)))
+            .await;
+
+        log::info!(
+            "Transferred Player {} ({}) to {host}:{port}",
+            self.gameprofile.name,
+            self.client.id
+        );
+
+        self.client.close().await;
+    }
+
     pub fn can_food_heal(&self) -> bool {
         let health = self.living_entity.health.load();
         let max_health = 20.0; // TODO
@@ -1111,6 +1470,178 @@ impl Player {
         }}
     }
 
+    /// Marks the player as active, called when the player moves, chats or interacts with something
+    pub fn update_last_action_time(&self) {
+        self.last_action_time.store(Instant::now());
+    }
+
+    /// How long the player has not done anything
+    pub fn idle_time(&self) -> Duration {
+        self.last_action_time.load().elapsed()
+    }
+
+    pub fn is_afk(&self) -> bool {
+        self.afk_since.load().is_some()
+    }
+
+    /// Marks the player as AFK or back, the player stops being AFK on its next action
+    pub async fn set_afk(self: &Arc<Self>, afk: bool) {
+        if self.is_afk() == afk {
+            return;
+        }
+        send_cancellable! {{
+            PlayerAfkChangeEvent::new(self.clone(), afk);
+
+            'after: {
+                self.afk_since.store(event.afk.then(Instant::now));
+                if advanced_config().afk.gray_in_tab_list {
+                    self.world()
+                        .await
+                        .broadcast_packet_all(&CPlayerInfoUpdate::new(
+                            0x20,
+                            &[pumpkin_protocol::client::play::Player {
+                                uuid: self.gameprofile.id,
+                                actions: vec![PlayerAction::UpdateDisplayName(
+                                    self.tab_list_name(),
+                                )],
+                            }],
+                        ))
+                        .await;
+                }
+            }
+
+            'cancelled: {
+                // Don't try again every tick
+                if afk {
+                    self.update_last_action_time();
+                }
+            }
+        }}
+    }
+
+    /// The name shown in the tab list, `None` for the plain name
+    pub fn tab_list_name(&self) -> Option<TextComponent> {
+        (self.is_afk() && advanced_config().afk.gray_in_tab_list).then(|| {
+            TextComponent::text(self.gameprofile.name.clone()).color_named(NamedColor::Gray)
+        })
+    }
+
+    async fn tick_afk(self: &Arc<Self>) {
+        let config = &advanced_config().afk;
+        let idle_time = self.idle_time();
+        match self.afk_since.load() {
+            Some(since) if self.last_action_time.load() > since => self.set_afk(false).await,
+            None if config.timeout_seconds > 0
+                && idle_time >= Duration::from_secs(config.timeout_seconds) =>
+            {
+                self.set_afk(true).await;
+            }
+            _ => {}
+        }
+
+        if config.kick_timeout_seconds > 0
+            && idle_time >= Duration::from_secs(config.kick_timeout_seconds)
+        {
+            self.kick(TextComponent::translate(
+                "multiplayer.disconnect.idling",
+                [],
+            ))
+            .await;
+        }
+    }
+
+    pub fn is_vanished(&self) -> bool {
+        self.vanished.load(Ordering::Relaxed)
+    }
+
+    /// Whether this player sees `other`, vanished players are only seen by themselves and by
+    /// players with `SEE_VANISHED_LEVEL`
+    pub fn can_see(&self, other: &Self) -> bool {
+        !other.is_vanished()
+            || self.gameprofile.id == other.gameprofile.id
+            || self.permission_lvl.load() >= Self::SEE_VANISHED_LEVEL
+    }
+
+    /// Hides the player from, or shows the player to, everyone who can't see vanished players.
+    /// Vanished players are also left out of the player count of the server list
+    pub async fn set_vanished(&self, server: &Server, vanished: bool) {
+        if self.vanished.swap(vanished, Ordering::Relaxed) == vanished {
+            return;
+        }
+        {
+            let mut status = server.get_status().lock().await;
+            if vanished {
+                status.remove_player();
+            } else {
+                status.add_player();
+            }
+        }
+
+        let world = self.world().await;
+        for observer in world.players.read().await.values() {
+            if observer.gameprofile.id == self.gameprofile.id
+                || observer.permission_lvl.load() >= Self::SEE_VANISHED_LEVEL
+            {
+                continue;
+            }
+            if vanished {
+                observer
+                    .client
+                    .send_packet(&CRemoveEntities::new(&[self.entity_id().into()]))
+                    .await;
+                observer
+                    .client
+                    .send_packet(&CRemovePlayerInfo::new(1.into(), &[self.gameprofile.id]))
+                    .await;
+            } else {
+                self.show_to(observer).await;
+            }
+        }
+        if !vanished {
+            // Skin parts
+            self.send_client_information().await;
+        }
+    }
+
+    /// Adds the player to the tab list of `observer` and spawns it for them
+    async fn show_to(&self, observer: &Self) {
+        let gameprofile = &self.gameprofile;
+        observer
+            .client
+            .send_packet(&CPlayerInfoUpdate::new(
+                0x01 | 0x04 | 0x08 | 0x20,
+                &[pumpkin_protocol::client::play::Player {
+                    uuid: gameprofile.id,
+                    actions: vec![
+                        PlayerAction::AddPlayer {
+                            name: &gameprofile.name,
+                            properties: &gameprofile.properties,
+                        },
+                        PlayerAction::UpdateGameMode(VarInt(self.gamemode.load() as i32)),
+                        PlayerAction::UpdateListed(true),
+                        PlayerAction::UpdateDisplayName(self.tab_list_name()),
+                    ],
+                }],
+            ))
+            .await;
+
+        let entity = &self.living_entity.entity;
+        observer
+            .client
+            .send_packet(&CSpawnEntity::new(
+                self.entity_id().into(),
+                gameprofile.id,
+                i32::from(EntityType::PLAYER.id).into(),
+                entity.pos.load(),
+                entity.pitch.load(),
+                entity.yaw.load(),
+                entity.head_yaw.load(),
+                0.into(),
+                Vector3::new(0.0, 0.0, 0.0),
+            ))
+            .await;
+    }
+
     /// Send skin layers and used hand to all players
     pub async fn send_client_information(&self) {
         let config = self.config.lock().await;
@@ -1123,6 +1654,38 @@ impl Player {
             .await;
     }
 
+    /// Whether the player may break the block, in adventure mode only with an item whose
+    /// `can_break` component allows it
+    pub async fn may_break(&self, block: &Block) -> bool {
+        match self.gamemode.load() {
+            GameMode::Spectator => false,
+            GameMode::Adventure => self
+                .inventory
+                .lock()
+                .await
+                .held_item()
+                .and_then(|stack| stack.components.can_break.as_ref())
+                .is_some_and(|predicate| predicate.matches(block)),
+            GameMode::Survival | GameMode::Creative => true,
+        }
+    }
+
+    /// Whether the player may place the held item against the block, in adventure mode only if
+    /// its `can_place_on` component allows it
+    pub async fn may_place_on(&self, block: &Block) -> bool {
+        match self.gamemode.load() {
+            GameMode::Spectator => false,
+            GameMode::Adventure => self
+                .inventory
+                .lock()
+                .await
+                .held_item()
+                .and_then(|stack| stack.components.can_place_on.as_ref())
+                .is_some_and(|predicate| predicate.matches(block)),
+            GameMode::Survival | GameMode::Creative => true,
+        }
+    }
+
     pub async fn can_harvest(&self, block: &BlockState, block_name: &str) -> bool {
         !block.tool_required
             || self
@@ -1203,24 +1766,39 @@ impl Player {
             .await;
     }
 
-    pub async fn drop_item(&self, item_id: u16, count: u32) {
+    /// Drops `count` items of the stack, keeping its components
+    pub async fn drop_item(&self, stack: &ItemStack, count: u32) {
         let entity = self
             .world()
             .await
             .create_entity(self.living_entity.entity.pos.load(), EntityType::ITEM);
 
-        // TODO: Merge stacks together
-        let item_entity = Arc::new(ItemEntity::new(entity, item_id, count).await);
+        let item_entity = Arc::new(ItemEntity::new(entity, stack, count).await);
         self.world().await.spawn_entity(item_entity.clone()).await;
         item_entity.send_meta_packet().await;
     }
 
+    /// Drops every item of the inventory, like on death without keepInventory
+    pub async fn drop_inventory(&self) {
+        let stacks: Vec<ItemStack> = self
+            .inventory
+            .lock()
+            .await
+            .all_slots()
+            .iter_mut()
+            .filter_map(|slot| slot.take())
+            .collect();
+        for stack in stacks {
+            self.drop_item(&stack, u32::from(stack.item_count)).await;
+        }
+        self.set_container_content(None).await;
+    }
+
     pub async fn drop_held_item(&self, drop_stack: bool) {
         let mut inv = self.inventory.lock().await;
         if let Some(item_stack) = inv.held_item_mut() {
             let drop_amount = if drop_stack { item_stack.item_count } else { 1 };
-            self.drop_item(item_stack.item.id, u32::from(drop_amount))
-                .await;
+            self.drop_item(item_stack, u32::from(drop_amount)).await;
             inv.decrease_current_stack(drop_amount);
         }
     }
@@ -1299,6 +1877,33 @@ impl Player {
         self.living_entity.add_effect(effect).await;
     }
 
+    pub async fn remove_effect(&self, effect: EffectType) -> Option<Effect> {
+        let removed = self.living_entity.remove_effect(effect).await?;
+        self.client
+            .send_packet(&CRemoveMobEffect::new(
+                self.entity_id().into(),
+                VarInt(effect as i32),
+            ))
+            .await;
+        Some(removed)
+    }
+
+    /// Removes all effects, like when drinking milk. Returns false if there were none
+    pub async fn clear_effects(&self) -> bool {
+        let effects: Vec<EffectType> = self
+            .living_entity
+            .active_effects
+            .lock()
+            .await
+            .keys()
+            .copied()
+            .collect();
+        for effect in &effects {
+            self.remove_effect(*effect).await;
+        }
+        !effects.is_empty()
+    }
+
     /// Add experience levels to the player
     pub async fn add_experience_levels(&self, added_levels: i32) {
         let current_level = self.experience_level.load(Ordering::Relaxed);
@@ -1353,6 +1958,31 @@ impl NBTStorage for Player {
         let total_exp = experience::points_to_level(self.experience_level.load(Ordering::Relaxed))
             + self.experience_points.load(Ordering::Relaxed);
         nbt.put_int("XpTotal", total_exp);
+
+        let ender_items = self
+            .ender_chest
+            .lock()
+            .await
+            .all_slots_ref()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(slot, stack)| {
+                let mut compound = NbtCompound::new();
+                stack?.write_item_nbt(&mut compound);
+                compound.put_byte("Slot", slot as i8);
+                Some(NbtTag::Compound(compound))
+            })
+            .collect();
+        nbt.put_list("EnderItems", ender_items);
+
+        if let Some(location) = self.last_death_location.lock().await.as_ref() {
+            nbt.put_component("LastDeathLocation", location.write_nbt());
+        }
+        nbt.put_bool("seenCredits", self.seen_credits.load(Ordering::Relaxed));
+        nbt.put_string(
+            "Dimension",
+            self.world().await.dimension_type.name().to_string(),
+        );
     }
 
     async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
@@ -1368,21 +1998,52 @@ impl NBTStorage for Player {
         self.experience_level.store(level, Ordering::Relaxed);
         self.experience_progress.store(progress);
         self.experience_points.store(points, Ordering::Relaxed);
+
+        if let Some(ender_items) = nbt.get_list("EnderItems") {
+            let mut ender_chest = self.ender_chest.lock().await;
+            let mut slots = ender_chest.all_slots();
+            for compound in ender_items.iter().filter_map(NbtTag::extract_compound) {
+                let Some(slot) = compound
+                    .get_byte("Slot")
+                    .and_then(|slot| slots.get_mut(slot as usize))
+                else {
+                    continue;
+                };
+                **slot = ItemStack::read_item_nbt(compound);
+            }
+        }
+
+        *self.last_death_location.lock().await = nbt
+            .get_compound("LastDeathLocation")
+            .and_then(GlobalPos::read_nbt);
+        self.seen_credits.store(
+            nbt.get_bool("seenCredits").unwrap_or(false),
+            Ordering::Relaxed,
+        );
     }
 }
 
 #[async_trait]
 impl EntityBase for Player {
     async fn damage(&self, amount: f32, damage_type: DamageType) -> bool {
-        self.world()
-            .await
+        let world = self.world().await;
+        world
             .play_sound(
                 Sound::EntityPlayerHurt,
                 SoundCategory::Players,
                 &self.living_entity.entity.pos.load(),
             )
             .await;
-        self.living_entity.damage(amount, damage_type).await
+        let health = self.living_entity.health.load();
+        let damaged = self.living_entity.damage(amount, damage_type).await;
+        if damaged
+            && health > 0.0
+            && self.living_entity.health.load() <= 0.0
+            && !world.game_rule(game_rules::KEEP_INVENTORY)
+        {
+            self.drop_inventory().await;
+        }
+        damaged
     }
 
     fn get_entity(&self) -> &Entity {
@@ -1408,6 +2069,8 @@ impl Player {
                         Ok(()) => {}
                         Err(e) => {
                             if e.is_kick() {
+                                self.client
+                                    .set_disconnect_reason(DisconnectReason::ProtocolError);
                                 if let Some(kick_reason) = e.client_kick_reason() {
                                     self.kick(TextComponent::text(kick_reason)).await;
                                 } else {
@@ -1431,6 +2094,23 @@ impl Player {
         server: &Arc<Server>,
         packet: &mut RawPacket,
     ) -> Result<(), Box<dyn PumpkinError>> {
+        // Movement only counts when the player actually moved, which is checked in its handlers
+        if matches!(
+            packet.id.0,
+            SChatCommand::PACKET_ID
+                | SChatMessage::PACKET_ID
+                | SInteract::PACKET_ID
+                | SPlayerAction::PACKET_ID
+                | SPlayerCommand::PACKET_ID
+                | SClickContainer::PACKET_ID
+                | SSetHeldItem::PACKET_ID
+                | SSwingArm::PACKET_ID
+                | SUseItemOn::PACKET_ID
+                | SUseItem::PACKET_ID
+        ) {
+            self.update_last_action_time();
+        }
+
         let bytebuf = &mut packet.bytebuf;
         match packet.id.0 {
             SConfirmTeleport::PACKET_ID => {
@@ -1442,21 +2122,23 @@ impl Player {
                     .await;
             }
             SChatMessage::PACKET_ID => {
-                self.handle_chat_message(SChatMessage::read(bytebuf)?).await;
+                self.handle_chat_message(server, SChatMessage::read(bytebuf)?)
+                    .await;
             }
             SClientInformationPlay::PACKET_ID => {
                 self.handle_client_information(SClientInformationPlay::read(bytebuf)?)
                     .await;
             }
             SClientCommand::PACKET_ID => {
-                self.handle_client_status(SClientCommand::read(bytebuf)?)
+                self.handle_client_status(server, SClientCommand::read(bytebuf)?)
                     .await;
             }
             SPlayerInput::PACKET_ID => {
                 // TODO
             }
             SInteract::PACKET_ID => {
-                self.handle_interact(SInteract::read(bytebuf)?).await;
+                self.handle_interact(server, SInteract::read(bytebuf)?)
+                    .await;
             }
             SKeepAlive::PACKET_ID => {
                 self.handle_keep_alive(SKeepAlive::read(bytebuf)?).await;
@@ -1465,10 +2147,11 @@ impl Player {
                 // TODO
             }
             SPlayerPosition::PACKET_ID => {
-                self.handle_position(SPlayerPosition::read(bytebuf)?).await;
+                self.handle_position(server, SPlayerPosition::read(bytebuf)?)
+                    .await;
             }
             SPlayerPositionRotation::PACKET_ID => {
-                self.handle_position_rotation(SPlayerPositionRotation::read(bytebuf)?)
+                self.handle_position_rotation(server, SPlayerPositionRotation::read(bytebuf)?)
                     .await;
             }
             SPlayerRotation::PACKET_ID => {
@@ -1596,8 +2279,8 @@ impl NBTStorage for Abilities {
             self.allow_flying = component.get_bool("mayfly").unwrap_or(false);
             self.creative = component.get_bool("instabuild").unwrap_or(false);
             self.allow_modify_world = component.get_bool("mayBuild").unwrap_or(false);
-            self.fly_speed = component.get_float("flySpeed").unwrap_or(0.0);
-            self.walk_speed = component.get_float("walk_speed").unwrap_or(0.0);
+            self.fly_speed = component.get_float("flySpeed").unwrap_or(0.05);
+            self.walk_speed = component.get_float("walkSpeed").unwrap_or(0.1);
         }
     }
 }
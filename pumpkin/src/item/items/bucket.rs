@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use crate::PLUGIN_MANAGER;
+use crate::entity::mob::bucketable;
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::{ItemMetadata, PumpkinItem};
+use crate::plugin::player::player_bucket_empty::PlayerBucketEmptyEvent;
+use crate::plugin::player::player_bucket_fill::PlayerBucketFillEvent;
+use crate::server::Server;
+use crate::world::World;
+use crate::world::game_event::{GameEventContext, block_center};
+use async_trait::async_trait;
+use pumpkin_data::block::Block;
+use pumpkin_data::game_event::GameEvent;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::item::ItemStack;
+use pumpkin_world::raycast::{BlockHitResult, FluidHandling};
+
+pub struct BucketItem;
+
+impl ItemMetadata for BucketItem {
+    const IDS: &'static [u16] = &[
+        Item::BUCKET.id,
+        Item::WATER_BUCKET.id,
+        Item::LAVA_BUCKET.id,
+        Item::COD_BUCKET.id,
+        Item::SALMON_BUCKET.id,
+        Item::PUFFERFISH_BUCKET.id,
+        Item::TROPICAL_FISH_BUCKET.id,
+        Item::AXOLOTL_BUCKET.id,
+        Item::TADPOLE_BUCKET.id,
+    ];
+}
+
+/// The fluid a filled bucket places
+fn fluid_of(item: &Item) -> Option<Block> {
+    if item.id == Item::WATER_BUCKET.id || bucketable::mob_in(item).is_some() {
+        Some(Block::WATER)
+    } else if item.id == Item::LAVA_BUCKET.id {
+        Some(Block::LAVA)
+    } else {
+        None
+    }
+}
+
+/// The direction a player with the given rotation, in degrees, is looking at
+fn look_direction(yaw: f32, pitch: f32) -> Vector3<f64> {
+    let pitch = f64::from(pitch).to_radians();
+    let yaw = -f64::from(yaw).to_radians();
+    Vector3::new(
+        yaw.sin() * pitch.cos(),
+        -pitch.sin(),
+        yaw.cos() * pitch.cos(),
+    )
+}
+
+#[async_trait]
+impl PumpkinItem for BucketItem {
+    async fn normal_use(&self, item: &Item, player: &Arc<Player>, server: &Server) {
+        let fluid = fluid_of(item);
+        let entity = &player.living_entity.entity;
+        let position = entity.pos.load();
+        let origin = Vector3::new(
+            position.x,
+            position.y + f64::from(entity.standing_eye_height),
+            position.z,
+        );
+        let direction = look_direction(entity.yaw.load(), entity.pitch.load());
+        // Filled buckets go through fluids, empty ones stop at the sources they can pick up
+        let fluid_handling = if fluid.is_some() {
+            FluidHandling::None
+        } else {
+            FluidHandling::SourceOnly
+        };
+
+        let world = player.world().await;
+        let Some(hit) = world
+            .raycast_blocks(
+                origin,
+                direction,
+                player.block_interaction_range(),
+                fluid_handling,
+            )
+            .await
+        else {
+            return;
+        };
+        // Players who may not build, like in adventure mode, need a bucket which may be used there
+        let Ok(clicked) = world.get_block(&hit.block_pos).await else {
+            return;
+        };
+        if !player.may_place_on(&clicked).await {
+            return;
+        }
+        let Some(stack) = player.inventory().lock().await.held_item().cloned() else {
+            return;
+        };
+        match fluid {
+            Some(fluid) => empty(player, &world, server, &hit, fluid, stack).await,
+            None => fill(player, &world, server, &hit, stack).await,
+        }
+    }
+}
+
+async fn fill(
+    player: &Arc<Player>,
+    world: &Arc<World>,
+    server: &Server,
+    hit: &BlockHitResult,
+    stack: ItemStack,
+) {
+    let (result, sound) = if hit.state_id == Block::WATER.default_state_id {
+        (Item::WATER_BUCKET, Sound::ItemBucketFill)
+    } else if hit.state_id == Block::LAVA.default_state_id {
+        (Item::LAVA_BUCKET, Sound::ItemBucketFillLava)
+    } else {
+        return;
+    };
+
+    let event = PLUGIN_MANAGER
+        .lock()
+        .await
+        .fire(PlayerBucketFillEvent {
+            player: player.clone(),
+            position: hit.block_pos,
+            state_id: hit.state_id,
+            item: stack,
+            result,
+            cancelled: false,
+        })
+        .await;
+    if event.cancelled {
+        player.resync_block(&hit.block_pos).await;
+        player.set_container_content(None).await;
+        return;
+    }
+
+    world
+        .set_block_state(&hit.block_pos, Block::AIR.default_state_id)
+        .await;
+    world
+        .play_block_sound(sound, SoundCategory::Players, hit.block_pos)
+        .await;
+    world
+        .emit_game_event(
+            GameEvent::FluidPickup,
+            block_center(&hit.block_pos),
+            GameEventContext::entity(&player.living_entity.entity).with_state(hit.state_id),
+        )
+        .await;
+    world.update_neighbors(server, &hit.block_pos, None).await;
+    player
+        .exchange_held_item(ItemStack::new(1, event.result))
+        .await;
+}
+
+async fn empty(
+    player: &Arc<Player>,
+    world: &Arc<World>,
+    server: &Server,
+    hit: &BlockHitResult,
+    fluid: Block,
+    stack: ItemStack,
+) {
+    // The fluid replaces the clicked block when it can, like grass, and otherwise goes next to it
+    let mut position = hit.block_pos;
+    let Ok(mut state) = world.get_block_state(&position).await else {
+        return;
+    };
+    if !state.replaceable {
+        position = BlockPos(hit.block_pos.0 + hit.face.to_offset());
+        let Ok(next) = world.get_block_state(&position).await else {
+            return;
+        };
+        if !next.replaceable {
+            return;
+        }
+        state = next;
+    }
+
+    let event = PLUGIN_MANAGER
+        .lock()
+        .await
+        .fire(PlayerBucketEmptyEvent {
+            player: player.clone(),
+            position,
+            state_id: state.id,
+            fluid,
+            item: stack,
+            cancelled: false,
+        })
+        .await;
+    if event.cancelled {
+        player.resync_block(&position).await;
+        player.set_container_content(None).await;
+        return;
+    }
+
+    let mob = bucketable::mob_in(&event.item.item);
+    // Water boils away in the nether, the bucket is emptied anyway
+    let evaporates = event.fluid.id == Block::WATER.id
+        && matches!(world.dimension_type, DimensionType::TheNether);
+    if evaporates {
+        world
+            .play_block_sound(Sound::BlockFireExtinguish, SoundCategory::Blocks, position)
+            .await;
+    } else {
+        let sound = if event.fluid.id == Block::LAVA.id {
+            Sound::ItemBucketEmptyLava
+        } else {
+            mob.map_or(Sound::ItemBucketEmpty, bucketable::empty_sound)
+        };
+        world
+            .set_block_state(&position, event.fluid.default_state_id)
+            .await;
+        world
+            .play_block_sound(sound, SoundCategory::Blocks, position)
+            .await;
+        world
+            .emit_game_event(
+                GameEvent::FluidPlace,
+                block_center(&position),
+                GameEventContext::entity(&player.living_entity.entity)
+                    .with_state(event.fluid.default_state_id),
+            )
+            .await;
+        world.update_neighbors(server, &position, None).await;
+    }
+    if let Some(mob) = mob {
+        let data = event.item.components.bucket_entity_data.as_ref();
+        bucketable::release(world, mob, data, position).await;
+    }
+    player
+        .exchange_held_item(ItemStack::new(1, Item::BUCKET))
+        .await;
+}
@@ -0,0 +1,73 @@
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::{ItemMetadata, PumpkinItem};
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_data::block::Block;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::GameMode;
+use pumpkin_util::math::position::{BlockPos, GlobalPos};
+use pumpkin_world::block::BlockDirection;
+use pumpkin_world::item::{ItemStack, LodestoneTracker};
+
+pub struct CompassItem;
+
+impl ItemMetadata for CompassItem {
+    const IDS: &'static [u16] = &[Item::COMPASS.id];
+}
+
+#[async_trait]
+impl PumpkinItem for CompassItem {
+    /// Using a compass on a lodestone makes it point there. A single compass is bound itself,
+    /// from a stack one is taken off
+    async fn use_on_block(
+        &self,
+        _item: &Item,
+        player: &Player,
+        location: BlockPos,
+        _face: &BlockDirection,
+        block: &Block,
+        _server: &Server,
+    ) {
+        if block.id != Block::LODESTONE.id {
+            return;
+        }
+        let world = player.world().await;
+        world
+            .play_block_sound(
+                Sound::ItemLodestoneCompassLock,
+                SoundCategory::Players,
+                location,
+            )
+            .await;
+        let tracker = LodestoneTracker {
+            target: Some(GlobalPos::new(
+                world.dimension_type.name().to_string(),
+                location,
+            )),
+            tracked: true,
+        };
+
+        let mut inventory = player.inventory().lock().await;
+        let Some(mut held) = inventory.held_item().cloned() else {
+            return;
+        };
+        let slot = inventory.get_selected_slot();
+        let creative = player.gamemode.load() == GameMode::Creative;
+        if !creative && held.item_count == 1 {
+            held.components.lodestone_tracker = Some(tracker);
+            player.update_single_slot(&mut inventory, slot, held).await;
+            return;
+        }
+
+        let mut compass = ItemStack::new(1, Item::COMPASS);
+        compass.components = held.components.clone();
+        compass.components.lodestone_tracker = Some(tracker);
+        if !creative {
+            held.item_count -= 1;
+            player.update_single_slot(&mut inventory, slot, held).await;
+        }
+        drop(inventory);
+        player.give_stack(compass).await;
+    }
+}
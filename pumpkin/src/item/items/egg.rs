@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::entity::player::Player;
 use crate::entity::projectile::ThrownItemEntity;
 use crate::item::pumpkin_item::{ItemMetadata, PumpkinItem};
+use crate::server::Server;
 use async_trait::async_trait;
 use pumpkin_data::entity::EntityType;
 use pumpkin_data::item::Item;
@@ -18,7 +19,7 @@ const POWER: f32 = 1.5;
 
 #[async_trait]
 impl PumpkinItem for EggItem {
-    async fn normal_use(&self, _block: &Item, player: &Player) {
+    async fn normal_use(&self, _block: &Item, player: &Arc<Player>, _server: &Server) {
         let position = player.position();
         let world = player.world().await;
         world
@@ -0,0 +1,46 @@
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::{ItemMetadata, PumpkinItem};
+use crate::server::Server;
+use crate::world::nether_portal;
+use async_trait::async_trait;
+use pumpkin_data::block::Block;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::BlockDirection;
+
+pub struct FlintAndSteelItem;
+
+impl ItemMetadata for FlintAndSteelItem {
+    const IDS: &'static [u16] = &[Item::FLINT_AND_STEEL.id];
+}
+
+#[async_trait]
+impl PumpkinItem for FlintAndSteelItem {
+    /// Lights the block in front of the clicked face, which opens a nether portal when it's inside
+    /// an obsidian frame
+    async fn use_on_block(
+        &self,
+        _item: &Item,
+        player: &Player,
+        location: BlockPos,
+        face: &BlockDirection,
+        _block: &Block,
+        _server: &Server,
+    ) {
+        let world = player.world().await;
+        let target = location.offset(face.to_offset());
+        if !world
+            .get_block(&target)
+            .await
+            .is_ok_and(|block| block.id == Block::AIR.id)
+        {
+            return;
+        }
+        world
+            .play_block_sound(Sound::ItemFlintandsteelUse, SoundCategory::Blocks, target)
+            .await;
+        // TODO: Set fire when the block isn't inside a frame
+        nether_portal::light(&world, target).await;
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::{ItemMetadata, PumpkinItem};
+use crate::server::Server;
+use crate::world::game_event::GameEventContext;
+use async_trait::async_trait;
+use pumpkin_data::game_event::GameEvent;
+use pumpkin_data::item::Item;
+use pumpkin_world::item::ItemStack;
+
+/// How long drinking milk takes
+const DRINK_TICKS: u32 = 32;
+
+pub struct MilkBucketItem;
+
+impl ItemMetadata for MilkBucketItem {
+    const IDS: &'static [u16] = &[Item::MILK_BUCKET.id];
+}
+
+#[async_trait]
+impl PumpkinItem for MilkBucketItem {
+    async fn normal_use(&self, item: &Item, player: &Arc<Player>, _server: &Server) {
+        player.start_using_item(item, DRINK_TICKS);
+    }
+
+    /// Drinking milk clears all effects
+    async fn finish_using(&self, _item: &Item, player: &Arc<Player>, _server: &Server) {
+        player.clear_effects().await;
+        let entity = &player.living_entity.entity;
+        player
+            .world()
+            .await
+            .emit_game_event(
+                GameEvent::Drink,
+                entity.pos.load(),
+                GameEventContext::entity(entity),
+            )
+            .await;
+        player
+            .exchange_held_item(ItemStack::new(1, Item::BUCKET))
+            .await;
+    }
+}
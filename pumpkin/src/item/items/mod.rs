@@ -1,11 +1,19 @@
+mod bucket;
+mod compass;
 mod egg;
+mod flint_and_steel;
+mod milk_bucket;
 mod snowball;
 mod sword;
 mod trident;
 
 use std::sync::Arc;
 
+use bucket::BucketItem;
+use compass::CompassItem;
 use egg::EggItem;
+use flint_and_steel::FlintAndSteelItem;
+use milk_bucket::MilkBucketItem;
 use snowball::SnowBallItem;
 use sword::SwordItem;
 use trident::TridentItem;
@@ -19,6 +27,10 @@ pub fn default_registry() -> Arc<ItemRegistry> {
     manager.register(EggItem);
     manager.register(SwordItem);
     manager.register(TridentItem);
+    manager.register(BucketItem);
+    manager.register(CompassItem);
+    manager.register(MilkBucketItem);
+    manager.register(FlintAndSteelItem);
 
     Arc::new(manager)
 }
@@ -4,6 +4,8 @@ use async_trait::async_trait;
 use pumpkin_data::block::Block;
 use pumpkin_data::item::Item;
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::BlockDirection;
+use std::sync::Arc;
 
 pub trait ItemMetadata {
     const IDS: &'static [u16];
@@ -11,12 +13,17 @@ pub trait ItemMetadata {
 
 #[async_trait]
 pub trait PumpkinItem: Send + Sync {
-    async fn normal_use(&self, _block: &Item, _player: &Player) {}
+    async fn normal_use(&self, _block: &Item, _player: &Arc<Player>, _server: &Server) {}
+
+    /// Called once the player used the item long enough, see `Player::start_using_item`
+    async fn finish_using(&self, _item: &Item, _player: &Arc<Player>, _server: &Server) {}
+
     async fn use_on_block(
         &self,
         _item: &Item,
         _player: &Player,
         _location: BlockPos,
+        _face: &BlockDirection,
         _block: &Block,
         _server: &Server,
     ) {
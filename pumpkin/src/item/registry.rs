@@ -3,6 +3,7 @@ use crate::server::Server;
 use pumpkin_data::block::Block;
 use pumpkin_data::item::Item;
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::BlockDirection;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -18,10 +19,16 @@ impl ItemRegistry {
         self.items.insert(T::IDS, Arc::new(item));
     }
 
-    pub async fn on_use(&self, item: &Item, player: &Player) {
+    pub async fn on_use(&self, item: &Item, player: &Arc<Player>, server: &Server) {
         let pumpkin_block = self.get_pumpkin_item(item.id);
         if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block.normal_use(item, player).await;
+            pumpkin_block.normal_use(item, player, server).await;
+        }
+    }
+
+    pub async fn finish_using(&self, item: &Item, player: &Arc<Player>, server: &Server) {
+        if let Some(pumpkin_item) = self.get_pumpkin_item(item.id) {
+            pumpkin_item.finish_using(item, player, server).await;
         }
     }
 
@@ -30,13 +37,14 @@ impl ItemRegistry {
         item: &Item,
         player: &Player,
         location: BlockPos,
+        face: &BlockDirection,
         block: &Block,
         server: &Server,
     ) {
         let pumpkin_item = self.get_pumpkin_item(item.id);
         if let Some(pumpkin_item) = pumpkin_item {
             return pumpkin_item
-                .use_on_block(item, player, location, block, server)
+                .use_on_block(item, player, location, face, block, server)
                 .await;
         }
     }
@@ -1,17 +1,27 @@
 // Not warn event sending macros
 #![allow(unused_labels)]
 
-use crate::net::{Client, lan_broadcast, query, rcon::RCONServer};
-use crate::server::{Server, ticker::Ticker};
+use crate::data::known_players;
+use crate::net::{
+    Client, disconnect::DisconnectReason, http_api, lan_broadcast, outgoing::write_parts, query,
+    rcon::RCONServer, webhook::Webhooks,
+};
+use crate::server::{Server, kits, memory, ticker::Ticker};
+use crate::world::claims::ClaimProtection;
 use log::{Level, LevelFilter, Log};
+use logging::LogLevels;
 use net::PacketHandlerState;
 use plugin::PluginManager;
-use plugin::server::server_command::ServerCommandEvent;
-use pumpkin_config::{BASIC_CONFIG, advanced_config};
+use plugin::player::player_disconnect::PlayerDisconnectEvent;
+use plugin::server::{
+    server_command::ServerCommandEvent, server_started::ServerStartedEvent,
+    server_stop::ServerStopEvent,
+};
+use pumpkin_config::{BASIC_CONFIG, CONFIG_MANAGER, advanced_config};
 use pumpkin_macros::send_cancellable;
 use pumpkin_util::text::TextComponent;
 use rustyline_async::{Readline, ReadlineEvent};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
 use std::{
@@ -22,7 +32,7 @@ use tokio::select;
 use tokio::sync::Notify;
 use tokio::task::JoinHandle;
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::AsyncReadExt,
     net::{TcpListener, tcp::OwnedReadHalf},
     sync::Mutex,
 };
@@ -33,9 +43,11 @@ pub mod data;
 pub mod entity;
 pub mod error;
 pub mod item;
+pub mod logging;
 pub mod net;
 pub mod plugin;
 pub mod server;
+pub mod tui;
 pub mod world;
 
 const GIT_VERSION: &str = env!("GIT_VERSION");
@@ -44,20 +56,44 @@ pub static PLUGIN_MANAGER: LazyLock<Mutex<PluginManager>> =
     LazyLock::new(|| Mutex::new(PluginManager::new()));
 
 /// A wrapper for our logger to hold the terminal input while no input is expected in order to
-/// properly flush logs to output while they happen instead of batched
+/// properly flush logs to output while they happen instead of batched. It also filters the logs
+/// by the level of the module they come from
 pub struct ReadlineLogWrapper {
     internal: Box<dyn Log>,
     readline: std::sync::Mutex<Option<Readline>>,
+    levels: std::sync::RwLock<LogLevels>,
 }
 
 impl ReadlineLogWrapper {
-    fn new(log: impl Log + 'static, rl: Option<Readline>) -> Self {
+    fn new(log: impl Log + 'static, rl: Option<Readline>, levels: LogLevels) -> Self {
         Self {
             internal: Box::new(log),
             readline: std::sync::Mutex::new(rl),
+            levels: std::sync::RwLock::new(levels),
         }
     }
 
+    #[must_use]
+    pub fn log_levels(&self) -> LogLevels {
+        self.levels.read().unwrap().clone()
+    }
+
+    /// Changes the level of the module and everything inside it, `None` goes back to the
+    /// default level
+    pub fn set_module_level(&self, module: &str, level: Option<LevelFilter>) {
+        let mut levels = self.levels.write().unwrap();
+        levels.set(module, level);
+        log::set_max_level(levels.max_level());
+    }
+
+    /// Takes the module levels of a reloaded logging config, overrides made with `/loglevel`
+    /// are dropped
+    pub fn apply_config(&self, modules: &BTreeMap<String, String>) {
+        let mut levels = self.levels.write().unwrap();
+        *levels = LogLevels::from_config(levels.default_level(), modules);
+        log::set_max_level(levels.max_level());
+    }
+
     fn take_readline(&self) -> Option<Readline> {
         if let Ok(mut result) = self.readline.lock() {
             result.take()
@@ -77,6 +113,9 @@ impl ReadlineLogWrapper {
 // writing to stdout is expensive anyway, so I dont think having a mutex here is a big deal.
 impl Log for ReadlineLogWrapper {
     fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
         self.internal.log(record);
         if let Ok(mut lock) = self.readline.lock() {
             if let Some(rl) = lock.as_mut() {
@@ -95,7 +134,8 @@ impl Log for ReadlineLogWrapper {
     }
 
     fn enabled(&self, metadata: &log::Metadata) -> bool {
-        self.internal.enabled(metadata)
+        metadata.level() <= self.levels.read().unwrap().level_for(metadata.target())
+            && self.internal.enabled(metadata)
     }
 }
 
@@ -127,18 +167,27 @@ pub static LOGGER_IMPL: LazyLock<Option<(ReadlineLogWrapper, LevelFilter)>> = La
             config.set_thread_level(LevelFilter::Info);
         }
 
-        let level = std::env::var("RUST_LOG")
+        let default_level = std::env::var("RUST_LOG")
             .ok()
             .as_deref()
             .map(LevelFilter::from_str)
             .and_then(Result::ok)
             .unwrap_or(LevelFilter::Info);
-
-        if advanced_config().commands.use_console {
+        let levels = LogLevels::from_config(default_level, &advanced_config().logging.modules);
+        let max_level = levels.max_level();
+        // The wrapper filters by module, so the inner logger lets everything through
+        let level = LevelFilter::Trace;
+
+        if tui::enabled() {
+            // The dashboard draws the log itself, escape codes would only get in the way
+            config.set_write_log_enable_colors(false);
+            let logger = simplelog::WriteLogger::new(level, config.build(), tui::LogWriter);
+            Some((ReadlineLogWrapper::new(logger, None, levels), max_level))
+        } else if advanced_config().commands.use_console {
             match Readline::new("$ ".to_owned()) {
                 Ok((rl, stdout)) => {
                     let logger = simplelog::WriteLogger::new(level, config.build(), stdout);
-                    Some((ReadlineLogWrapper::new(logger, Some(rl)), level))
+                    Some((ReadlineLogWrapper::new(logger, Some(rl), levels), max_level))
                 }
                 Err(e) => {
                     log::warn!(
@@ -146,12 +195,12 @@ pub static LOGGER_IMPL: LazyLock<Option<(ReadlineLogWrapper, LevelFilter)>> = La
                         e
                     );
                     let logger = simplelog::SimpleLogger::new(level, config.build());
-                    Some((ReadlineLogWrapper::new(logger, None), level))
+                    Some((ReadlineLogWrapper::new(logger, None, levels), max_level))
                 }
             }
         } else {
             let logger = simplelog::SimpleLogger::new(level, config.build());
-            Some((ReadlineLogWrapper::new(logger, None), level))
+            Some((ReadlineLogWrapper::new(logger, None, levels), max_level))
         }
     } else {
         None
@@ -187,8 +236,11 @@ impl PumpkinServer {
     pub async fn new() -> Self {
         let server = Arc::new(Server::new());
 
-        for world in &*server.worlds.read().await {
+        for world in server.worlds.iter() {
             world.level.read_spawn_chunks(&Server::spawn_chunks()).await;
+            if let Some(fight) = &world.dragon_fight {
+                fight.lock().await.load().await;
+            }
         }
 
         // Setup the TCP server socket.
@@ -202,7 +254,7 @@ impl PumpkinServer {
 
         let rcon = advanced_config().networking.rcon.clone();
 
-        let mut ticker = Ticker::new(BASIC_CONFIG.tps);
+        let mut ticker = Ticker::new();
 
         let mut tasks_to_await = Vec::new();
         if let Some((wrapper, _)) = &*LOGGER_IMPL {
@@ -210,6 +262,16 @@ impl PumpkinServer {
                 let handle = setup_console(rl, server.clone());
                 tasks_to_await.push(handle);
             }
+
+            let reloads = CONFIG_MANAGER.subscribe();
+            std::thread::Builder::new()
+                .name("config-reload".to_string())
+                .spawn(move || {
+                    for config in reloads {
+                        wrapper.apply_config(&config.logging.modules);
+                    }
+                })
+                .expect("Failed to spawn the config reload thread");
         }
 
         if rcon.enabled {
@@ -219,6 +281,16 @@ impl PumpkinServer {
             });
         }
 
+        let http_api = advanced_config().networking.http_api.clone();
+        if http_api.enabled {
+            let server = server.clone();
+            tokio::spawn(async move {
+                if let Err(err) = http_api::start_http_api(&http_api, server).await {
+                    log::error!("The HTTP API stopped: {err}");
+                }
+            });
+        }
+
         if advanced_config().networking.query.enabled {
             log::info!("Query protocol enabled. Starting...");
             tokio::spawn(query::start_query_handler(server.clone(), addr));
@@ -229,6 +301,14 @@ impl PumpkinServer {
             tokio::spawn(lan_broadcast::start_lan_broadcast(addr));
         }
 
+        if memory::soft_cap().is_some() {
+            tokio::spawn(memory::watch_soft_cap(server.clone()));
+        }
+
+        if tui::enabled() {
+            tasks_to_await.push(tui::start(server.clone()));
+        }
+
         // Ticker
         {
             let server = server.clone();
@@ -249,6 +329,10 @@ impl PumpkinServer {
     pub async fn init_plugins(&self) {
         let mut loader_lock = PLUGIN_MANAGER.lock().await;
         loader_lock.set_server(self.server.clone());
+        if advanced_config().claims.enabled {
+            ClaimProtection::register(&loader_lock).await;
+        }
+        Webhooks::register(&loader_lock).await;
         if let Err(err) = loader_lock.load_plugins().await {
             log::error!("{}", err.to_string());
         };
@@ -258,6 +342,12 @@ impl PumpkinServer {
         let mut master_client_id: usize = 0;
         let tasks = Arc::new(Mutex::new(HashMap::new()));
 
+        PLUGIN_MANAGER
+            .lock()
+            .await
+            .fire(ServerStartedEvent::new(self.server_addr))
+            .await;
+
         while !SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
             let await_new_client = || async {
                 let t1 = self.listener.accept();
@@ -307,16 +397,26 @@ impl PumpkinServer {
                 while let Some(notif) = rx.recv().await {
                     match notif {
                         PacketHandlerState::PacketReady => {
-                            let buf = {
-                                let mut enc = client_clone.enc.lock().await;
-                                enc.take()
-                            };
+                            // Packets queued since are sent along, one notification is enough
+                            let mut stop = false;
+                            while let Ok(notif) = rx.try_recv() {
+                                if matches!(notif, PacketHandlerState::Stop) {
+                                    stop = true;
+                                    break;
+                                }
+                            }
+                            let parts = client_clone.enc.lock().await.take_parts();
 
-                            if let Err(e) = connection_writer.write_all(&buf).await {
+                            if let Err(e) = write_parts(&mut connection_writer, parts).await {
                                 log::warn!("Failed to write packet to client: {e}");
+                                client_clone
+                                    .set_disconnect_reason(DisconnectReason::ConnectionLost);
                                 client_clone.close().await;
                                 break;
                             }
+                            if stop {
+                                break;
+                            }
                         }
                         PacketHandlerState::Stop => break,
                     }
@@ -345,6 +445,9 @@ impl PumpkinServer {
                         world
                             .spawn_player(&BASIC_CONFIG, player.clone(), &server)
                             .await;
+                        if player.first_join.load(std::sync::atomic::Ordering::Relaxed) {
+                            kits::give_starter_kit(&player).await;
+                        }
 
                         // poll Player
                         while !player
@@ -357,6 +460,42 @@ impl PumpkinServer {
                                 player.process_packets(&server).await;
                             };
                         }
+
+                        let reason = player.client.disconnect_reason();
+                        let count = server.disconnect_stats.record(reason);
+                        log::info!(
+                            "Player {} ({}) disconnected: {reason} ({count} since start)",
+                            player.gameprofile.name,
+                            id
+                        );
+                        PLUGIN_MANAGER
+                            .lock()
+                            .await
+                            .fire(PlayerDisconnectEvent::new(player.clone(), reason))
+                            .await;
+
+                        // Free everything the player held on to, even if the connection dropped
+                        // in the middle of something
+                        player.close_open_container(&server).await;
+                        server.save_player_data(&player).await;
+                        server.remove_ender_chest_container(&player).await;
+                        server
+                            .teleport_requests
+                            .lock()
+                            .await
+                            .forget(player.gameprofile.id);
+                        server
+                            .boss_events
+                            .lock()
+                            .await
+                            .forget(player.gameprofile.id);
+                        player.remove().await;
+                        server.join_queue.free_slot();
+                        known_players::remember(&player.gameprofile).await;
+                        // Vanished players already don't count as online
+                        if !player.is_vanished() {
+                            server.remove_player().await;
+                        }
                     }
                 }
 
@@ -370,9 +509,19 @@ impl PumpkinServer {
 
         log::info!("Stopped accepting incoming connections");
 
+        PLUGIN_MANAGER.lock().await.fire(ServerStopEvent).await;
+
         let kick_message = TextComponent::text("Server stopped");
+        let fallback = advanced_config().transfer.fallback_server();
         for player in self.server.get_all_players().await {
-            player.kick(kick_message.clone()).await;
+            match fallback {
+                Some(fallback) => {
+                    player
+                        .transfer(&fallback.host, i32::from(fallback.port))
+                        .await;
+                }
+                None => player.kick(kick_message.clone()).await,
+            }
         }
 
         log::info!("Ending server tasks");
@@ -411,6 +560,21 @@ impl PumpkinServer {
     }
 }
 
+/// Runs a command typed into the console, unless a plugin cancels it
+pub(crate) async fn run_console_command(server: &Server, line: String) {
+    send_cancellable! {{
+        ServerCommandEvent::new(line.clone());
+
+        'after: {
+            let dispatcher = server.command_dispatcher.read().await;
+
+            dispatcher
+                .handle_command(&mut command::CommandSender::Console, server, &line)
+                .await;
+        }
+    }}
+}
+
 fn setup_console(rl: Readline, server: Arc<Server>) -> JoinHandle<()> {
     // This needs to be async or it will hog a thread
     tokio::spawn(async move {
@@ -428,18 +592,8 @@ fn setup_console(rl: Readline, server: Arc<Server>) -> JoinHandle<()> {
 
             match result {
                 Ok(ReadlineEvent::Line(line)) => {
-                    send_cancellable! {{
-                        ServerCommandEvent::new(line.clone());
-
-                        'after: {
-                            let dispatcher = server.command_dispatcher.read().await;
-
-                            dispatcher
-                                .handle_command(&mut command::CommandSender::Console, &server, &line)
-                                .await;
-                            rl.add_history_entry(line).unwrap();
-                        }
-                    }}
+                    run_console_command(&server, line.clone()).await;
+                    rl.add_history_entry(line).unwrap();
                 }
                 Ok(ReadlineEvent::Interrupted) => {
                     stop_server();
@@ -477,6 +631,7 @@ async fn poll(client: &Client, connection_reader: &mut OwnedReadHalf) -> bool {
             Ok(None) => (), //log::debug!("Waiting for more data to complete packet..."),
             Err(err) => {
                 log::warn!("Failed to decode packet for: {}", err.to_string());
+                client.set_disconnect_reason(DisconnectReason::ProtocolError);
                 client.close().await;
                 return false; // return to avoid reserving additional bytes
             }
@@ -490,12 +645,14 @@ async fn poll(client: &Client, connection_reader: &mut OwnedReadHalf) -> bool {
             Ok(cnt) => {
                 //log::debug!("Read {} bytes", cnt);
                 if cnt == 0 {
+                    client.set_disconnect_reason(DisconnectReason::ClientQuit);
                     client.close().await;
                     return false;
                 }
             }
             Err(error) => {
                 log::error!("Error while reading incoming packet {}", error);
+                client.set_disconnect_reason(DisconnectReason::ConnectionLost);
                 client.close().await;
                 return false;
             }
@@ -0,0 +1,118 @@
+//! The log levels of single modules, they overwrite the level everything else logs at.
+//! Overrides come from the logging config and can be changed at runtime with `/loglevel`.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use log::LevelFilter;
+
+/// The level everything logs at and the levels of modules that log at another one
+#[derive(Clone, Debug)]
+pub struct LogLevels {
+    default: LevelFilter,
+    modules: BTreeMap<String, LevelFilter>,
+}
+
+impl LogLevels {
+    #[must_use]
+    pub const fn new(default: LevelFilter) -> Self {
+        Self {
+            default,
+            modules: BTreeMap::new(),
+        }
+    }
+
+    /// The module levels of the logging config, invalid levels are skipped
+    #[must_use]
+    pub fn from_config(default: LevelFilter, modules: &BTreeMap<String, String>) -> Self {
+        let mut levels = Self::new(default);
+        for (module, level) in modules {
+            match LevelFilter::from_str(level) {
+                Ok(level) => levels.set(module, Some(level)),
+                Err(_) => log::warn!("Invalid log level {level} for module {module}"),
+            }
+        }
+        levels
+    }
+
+    #[must_use]
+    pub const fn default_level(&self) -> LevelFilter {
+        self.default
+    }
+
+    /// Sets the level of the module and everything inside it, `None` removes the override
+    pub fn set(&mut self, module: &str, level: Option<LevelFilter>) {
+        match level {
+            Some(level) => {
+                self.modules.insert(module.to_string(), level);
+            }
+            None => {
+                self.modules.remove(module);
+            }
+        }
+    }
+
+    /// The level a log target, which is the module path by default, logs at. The innermost
+    /// module with an override wins
+    #[must_use]
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        let mut module = target;
+        loop {
+            if let Some(level) = self.modules.get(module) {
+                return *level;
+            }
+            match module.rfind("::") {
+                Some(end) => module = &module[..end],
+                None => return self.default,
+            }
+        }
+    }
+
+    /// The most verbose level anything logs at, used as the max level of the `log` crate
+    #[must_use]
+    pub fn max_level(&self) -> LevelFilter {
+        self.modules.values().copied().fold(self.default, Ord::max)
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = (&str, LevelFilter)> {
+        self.modules
+            .iter()
+            .map(|(module, level)| (module.as_str(), *level))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use log::LevelFilter;
+
+    use super::LogLevels;
+
+    #[test]
+    fn innermost_module_wins() {
+        let config = BTreeMap::from([
+            ("pumpkin_world".to_string(), "warn".to_string()),
+            ("pumpkin_world::chunk".to_string(), "debug".to_string()),
+            ("pumpkin::net".to_string(), "nonsense".to_string()),
+        ]);
+        let mut levels = LogLevels::from_config(LevelFilter::Info, &config);
+
+        assert_eq!(levels.level_for("pumpkin_world::chunk"), LevelFilter::Debug);
+        assert_eq!(
+            levels.level_for("pumpkin_world::chunk::format::anvil"),
+            LevelFilter::Debug
+        );
+        assert_eq!(levels.level_for("pumpkin_world::level"), LevelFilter::Warn);
+        assert_eq!(levels.level_for("pumpkin_worldgen"), LevelFilter::Info);
+        assert_eq!(levels.level_for("pumpkin::net"), LevelFilter::Info);
+        assert_eq!(levels.max_level(), LevelFilter::Debug);
+
+        levels.set("pumpkin_world::chunk", None);
+        assert_eq!(
+            levels.level_for("pumpkin_world::chunk::format::anvil"),
+            LevelFilter::Warn
+        );
+        assert_eq!(levels.max_level(), LevelFilter::Info);
+    }
+}
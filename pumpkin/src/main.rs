@@ -48,7 +48,8 @@ use tokio::signal::unix::{SignalKind, signal};
 use tokio::sync::Mutex;
 
 use crate::server::CURRENT_MC_VERSION;
-use pumpkin::{PumpkinServer, SHOULD_STOP, init_log, stop_server};
+use pumpkin::{LOGGER_IMPL, PumpkinServer, ReadlineLogWrapper, SHOULD_STOP, init_log, stop_server};
+use pumpkin_config::{BASIC_CONFIG, advanced_config, take_load_warnings};
 use pumpkin_protocol::CURRENT_MC_PROTOCOL;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 use std::time::Instant;
@@ -74,15 +75,35 @@ const GIT_VERSION: &str = env!("GIT_VERSION");
 // WARNING: All rayon calls from the tokio runtime must be non-blocking! This includes things
 // like `par_iter`. These should be spawned in the the rayon pool and then passed to the tokio
 // runtime with a channel! See `Level::fetch_chunks` as an example!
-#[tokio::main]
-async fn main() {
+fn main() {
+    let config = &advanced_config().runtime;
+    let mut runtime = tokio::runtime::Builder::new_multi_thread();
+    runtime
+        .enable_all()
+        .max_blocking_threads(config.max_blocking_threads.max(1));
+    if config.worker_threads > 0 {
+        runtime.worker_threads(config.worker_threads);
+    }
+    runtime
+        .build()
+        .expect("Failed to build the async runtime")
+        .block_on(run());
+}
+
+async fn run() {
     let time = Instant::now();
 
     init_log!();
 
+    LazyLock::force(&BASIC_CONFIG);
+    for warning in take_load_warnings() {
+        log::warn!("{warning}");
+    }
+
     let default_panic = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         default_panic(info);
+        pumpkin::net::webhook::notify_crash(format!("The server crashed: {info}"));
         // TODO: Gracefully exit?
         // we need to abide by the panic rules here
         std::process::exit(1);
@@ -90,6 +111,7 @@ async fn main() {
 
     rayon::ThreadPoolBuilder::new()
         .thread_name(|_| "rayon-worker".to_string())
+        .num_threads(advanced_config().runtime.rayon_threads)
         .build_global()
         .expect("Rayon thread pool can only be initialized once");
 
@@ -1,3 +1,4 @@
+use crate::entity::experience_orb::ExperienceOrbEntity;
 use crate::entity::player::Player;
 use crate::server::Server;
 use pumpkin_data::item::Item;
@@ -18,7 +19,7 @@ use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::server::play::SClickContainer;
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::{GameMode, MutableSplitSlice};
-use pumpkin_world::item::ItemStack;
+use pumpkin_world::item::{ItemStack, ItemStackComponents};
 use std::sync::Arc;
 
 impl Player {
@@ -95,10 +96,14 @@ impl Player {
     }
 
     pub async fn set_container_property<T: WindowPropertyTrait>(
-        &mut self,
+        &self,
         window_property: WindowProperty<T>,
     ) {
         let (id, value) = window_property.into_tuple();
+        self.send_container_property(id, value).await;
+    }
+
+    pub async fn send_container_property(&self, id: i16, value: i16) {
         self.client
             .send_packet(&CSetContainerProperty::new(
                 self.inventory().lock().await.total_opened_containers.into(),
@@ -165,6 +170,12 @@ impl Player {
             crafted_is_picked,
         )
         .await?;
+        if crafted_is_picked {
+            if let Some(container) = opened_container.as_deref_mut() {
+                self.award_cooking_experience(container.take_experience())
+                    .await;
+            }
+        }
         // Checks for if crafted item has been taken
         {
             let mut inventory = self.inventory().lock().await;
@@ -202,6 +213,22 @@ impl Player {
         Ok(())
     }
 
+    /// Furnaces store the experience of everything they cooked until the result is taken out
+    async fn award_cooking_experience(&self, experience: f32) {
+        let mut points = experience.floor() as u32;
+        if rand::random::<f32>() < experience.fract() {
+            points += 1;
+        }
+        if points > 0 {
+            ExperienceOrbEntity::spawn(
+                &self.world().await,
+                self.living_entity.entity.pos.load(),
+                points,
+            )
+            .await;
+        }
+    }
+
     pub async fn handle_decrease_item(
         &self,
         _server: &Server,
@@ -284,15 +311,12 @@ impl Player {
                     if let Some(item_stack) = slots[slot].as_mut() {
                         match drop_type {
                             DropType::FullStack => {
-                                self.drop_item(
-                                    item_stack.item.id,
-                                    u32::from(item_stack.item_count),
-                                )
-                                .await;
+                                self.drop_item(item_stack, u32::from(item_stack.item_count))
+                                    .await;
                                 *slots[slot] = None;
                             }
                             DropType::SingleItem => {
-                                self.drop_item(item_stack.item.id, 1).await;
+                                self.drop_item(item_stack, 1).await;
                                 item_stack.item_count -= 1;
                                 if item_stack.item_count == 0 {
                                     *slots[slot] = None;
@@ -324,12 +348,12 @@ impl Player {
                 if let Some(item_stack) = carried_item.as_mut() {
                     match mouse_click {
                         MouseClick::Left => {
-                            self.drop_item(item_stack.item.id, u32::from(item_stack.item_count))
+                            self.drop_item(item_stack, u32::from(item_stack.item_count))
                                 .await;
                             *carried_item = None;
                         }
                         MouseClick::Right => {
-                            self.drop_item(item_stack.item.id, 1).await;
+                            self.drop_item(item_stack, 1).await;
                             item_stack.item_count -= 1;
                             if item_stack.item_count == 0 {
                                 *carried_item = None;
@@ -343,6 +367,7 @@ impl Player {
     }
 
     /// TODO: Allow equiping/de equiping armor and allow taking items from crafting grid
+    #[expect(clippy::too_many_lines)]
     async fn shift_mouse_click(
         &self,
         opened_container: Option<&mut Box<dyn Container>>,
@@ -358,6 +383,14 @@ impl Player {
 
         match slot {
             container_click::Slot::Normal(slot) => {
+                // Whether the opened container takes the clicked stack, checked up front since
+                // the slots are borrowed while moving it
+                let container_takes_stack = container
+                    .all_slots_ref()
+                    .get(slot)
+                    .copied()
+                    .flatten()
+                    .is_none_or(|stack| container.can_place(0, stack));
                 let mut all_slots = container.all_slots();
                 let (item_stack, mut split_slice) =
                     MutableSplitSlice::extract_ith(&mut all_slots, slot);
@@ -388,6 +421,10 @@ impl Player {
                     (&(0..9), &(9..45))
                 };
 
+                if has_container && target_inv == &inv1_range && !container_takes_stack {
+                    return Ok(());
+                }
+
                 // If moving to hotbar, reverse the order to fill from right to left
                 let target_slots: Vec<usize> =
                     if has_container && source_inv.contains(&slot) && source_inv == &inv1_range {
@@ -435,6 +472,7 @@ impl Player {
                 for target_idx in &target_slots {
                     if let Some(target_item) = split_slice[*target_idx].as_mut() {
                         if target_item.item.id == clicked_item_stack.item.id
+                            && target_item.components == clicked_item_stack.components
                             && target_item.item_count < max_stack_size
                         {
                             let space_in_stack = max_stack_size - target_item.item_count;
@@ -669,8 +707,9 @@ impl Player {
         }
     }
 
+    /// Puts the items into the inventory as far as they fit, returns how many didn't
     // TODO: Use this method when actually picking up items instead of just the command
-    async fn pickup_items(&self, item: Item, amount: u32) {
+    async fn pickup_items(&self, item: Item, amount: u32) -> u32 {
         let mut amount_left = amount;
         let max_stack = item.components.max_stack_size;
         let mut inventory = self.inventory().lock().await;
@@ -690,36 +729,95 @@ impl Player {
                     item_stack.item_count = max_stack - (amount_to_add - amount_left as u8);
                     // Return here because if we have less than the max amount left then the whole
                     // stack will be moved
-                    return;
+                    return 0;
                 }
             } else if let Some(new_amount_left) = amount_left.checked_sub(u32::from(max_stack)) {
-                *item_stack = Some(ItemStack {
-                    item: item.clone(),
-                    item_count: max_stack,
-                });
+                *item_stack = Some(ItemStack::new(max_stack, item.clone()));
                 amount_left = new_amount_left;
             } else {
-                *item_stack = Some(ItemStack {
-                    item: item.clone(),
-                    // This is safe because amount left is less than max_stack which is a u8
-                    item_count: amount_left as u8,
-                });
+                // This is safe because amount left is less than max_stack which is a u8
+                *item_stack = Some(ItemStack::new(amount_left as u8, item.clone()));
                 // Return here because if we have less than the max amount left then the whole
                 // stack will be moved
-                return;
+                return 0;
+            }
+            if amount_left == 0 {
+                return 0;
             }
         }
-
-        log::warn!(
-            "{amount} items were discarded because dropping them to the ground is not implemented"
-        );
+        amount_left
     }
 
     /// Add items to inventory if there's space, else drop them to the ground.
     ///
     /// This method automatically syncs changes with the client.
     pub async fn give_items(&self, item: Item, amount: u32) {
-        self.pickup_items(item, amount).await;
+        let mut left = self.pickup_items(item.clone(), amount).await;
         self.set_container_content(None).await;
+
+        let stack = ItemStack::new(1, item);
+        let max_stack = u32::from(stack.item.components.max_stack_size);
+        while left > 0 {
+            let count = left.min(max_stack);
+            self.drop_item(&stack, count).await;
+            left -= count;
+        }
+    }
+
+    /// Like [`Player::give_items`], but keeps the components of the stack. Stacks with components
+    /// always go into an empty slot
+    pub async fn give_stack(&self, stack: ItemStack) {
+        if stack.components == ItemStackComponents::default() {
+            self.give_items(stack.item, u32::from(stack.item_count))
+                .await;
+            return;
+        }
+        let leftover = {
+            let mut inventory = self.inventory().lock().await;
+            match inventory.get_empty_slot() {
+                Some(slot) => match inventory.get_slot(slot) {
+                    Ok(item_stack) => {
+                        *item_stack = Some(stack);
+                        None
+                    }
+                    Err(_) => Some(stack),
+                },
+                None => Some(stack),
+            }
+        };
+        match leftover {
+            Some(stack) => {
+                self.drop_item(&stack, u32::from(stack.item_count)).await;
+            }
+            None => self.set_container_content(None).await,
+        }
+    }
+
+    /// Swaps one of the held items for `result`, like an empty bucket for a filled one. Creative
+    /// players keep the held item and only get `result` when they don't have one yet
+    pub async fn exchange_held_item(&self, result: ItemStack) {
+        let mut inventory = self.inventory().lock().await;
+        if self.gamemode.load() == GameMode::Creative {
+            let has_result = inventory
+                .slots()
+                .iter()
+                .flatten()
+                .any(|stack| **stack == result);
+            drop(inventory);
+            if !has_result {
+                self.give_stack(result).await;
+            }
+            return;
+        }
+
+        inventory.decrease_current_stack(1);
+        let slot = inventory.get_selected_slot();
+        let Some(held) = inventory.held_item().cloned() else {
+            self.update_single_slot(&mut inventory, slot, result).await;
+            return;
+        };
+        self.update_single_slot(&mut inventory, slot, held).await;
+        drop(inventory);
+        self.give_stack(result).await;
     }
 }
@@ -0,0 +1,97 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Why a connection ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisconnectReason {
+    /// The client closed the connection, like when leaving the server
+    ClientQuit,
+    /// The client stopped answering keep alives
+    Timeout,
+    /// The client sent a packet which could not be decoded or handled
+    ProtocolError,
+    /// The server kicked the client, this includes bans and the server stopping
+    Kicked,
+    /// The connection broke, like a reset or a failed write
+    ConnectionLost,
+    /// The server sent the client to another server
+    Transferred,
+}
+
+impl DisconnectReason {
+    pub const ALL: [Self; 6] = [
+        Self::ClientQuit,
+        Self::Timeout,
+        Self::ProtocolError,
+        Self::Kicked,
+        Self::ConnectionLost,
+        Self::Transferred,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ClientQuit => "client quit",
+            Self::Timeout => "timeout",
+            Self::ProtocolError => "protocol error",
+            Self::Kicked => "kicked",
+            Self::ConnectionLost => "connection lost",
+            Self::Transferred => "transferred",
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Counts how often players disconnected for each reason since the server started
+#[derive(Default)]
+pub struct DisconnectStats {
+    counts: [AtomicU64; DisconnectReason::ALL.len()],
+}
+
+impl DisconnectStats {
+    /// Counts a disconnect and returns how many there were for this reason so far
+    pub fn record(&self, reason: DisconnectReason) -> u64 {
+        self.counts[reason.index()].fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    #[must_use]
+    pub fn count(&self, reason: DisconnectReason) -> u64 {
+        self.counts[reason.index()].load(Ordering::Relaxed)
+    }
+
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        DisconnectReason::ALL
+            .into_iter()
+            .map(|reason| self.count(reason))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisconnectReason, DisconnectStats};
+
+    #[test]
+    fn counts_per_reason() {
+        let stats = DisconnectStats::default();
+        assert_eq!(stats.record(DisconnectReason::Timeout), 1);
+        assert_eq!(stats.record(DisconnectReason::Timeout), 2);
+        assert_eq!(stats.record(DisconnectReason::Kicked), 1);
+
+        assert_eq!(stats.count(DisconnectReason::Timeout), 2);
+        assert_eq!(stats.count(DisconnectReason::ClientQuit), 0);
+        assert_eq!(stats.total(), 3);
+    }
+}
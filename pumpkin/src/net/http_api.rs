@@ -0,0 +1,212 @@
+//! An HTTP API for web panels. Every request needs the configured token as a bearer token.
+//!
+//! - `GET /status` the version, MOTD and player count
+//! - `GET /players` the online players
+//! - `POST /players/{name}/kick` and `POST /players/{name}/ban` with an optional `reason`
+//! - `POST /command` runs a `command` and returns its output
+//!
+//! Kicks, bans and commands go through the command dispatcher, so they are audited and
+//! announced to operators like they would be from the console.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use pumpkin_config::{BASIC_CONFIG, HttpApiConfig};
+use pumpkin_util::player_name::is_valid_player_name;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    command::CommandSender,
+    server::{CURRENT_MC_VERSION, Server},
+};
+
+#[derive(Serialize)]
+struct Status {
+    version: &'static str,
+    motd: String,
+    online_players: usize,
+    max_players: u32,
+}
+
+#[derive(Serialize)]
+struct PlayerInfo {
+    name: String,
+    uuid: Uuid,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Reason {
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Command {
+    command: String,
+}
+
+#[derive(Serialize)]
+struct CommandOutput {
+    output: Vec<String>,
+}
+
+pub async fn start_http_api(config: &HttpApiConfig, server: Arc<Server>) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(config.address).await?;
+    log::info!("HTTP API listening on {}", config.address);
+
+    let token: Arc<str> = config.token.as_str().into();
+    let app = Router::new()
+        .route("/status", get(status))
+        .route("/players", get(players))
+        .route("/players/{name}/kick", post(kick))
+        .route("/players/{name}/ban", post(ban))
+        .route("/command", post(command))
+        .layer(middleware::from_fn_with_state(token, authorize))
+        .with_state(server);
+    axum::serve(listener, app).await
+}
+
+async fn authorize(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes()));
+    if authorized {
+        next.run(request).await
+    } else {
+        StatusCode::UNAUTHORIZED.into_response()
+    }
+}
+
+/// Compares without stopping at the first difference, so the time taken doesn't tell how much
+/// of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Runs the command as the API and returns what it sent back
+async fn run_command(server: &Server, command: &str) -> Vec<String> {
+    let output = tokio::sync::Mutex::new(Vec::new());
+    let dispatcher = server.command_dispatcher.read().await;
+    dispatcher
+        .handle_command(&mut CommandSender::Api(&output), server, command)
+        .await;
+    drop(dispatcher);
+    output.into_inner()
+}
+
+/// The command with the reason appended, reasons end at the first line break like in chat
+fn with_reason(command: String, reason: Option<&str>) -> String {
+    match reason.and_then(|reason| reason.lines().next()) {
+        Some(reason) if !reason.trim().is_empty() => format!("{command} {}", reason.trim()),
+        _ => command,
+    }
+}
+
+async fn status(State(server): State<Arc<Server>>) -> Json<Status> {
+    Json(Status {
+        version: CURRENT_MC_VERSION,
+        motd: BASIC_CONFIG.motd.clone(),
+        online_players: server.get_player_count().await,
+        max_players: BASIC_CONFIG.max_players,
+    })
+}
+
+async fn players(State(server): State<Arc<Server>>) -> Json<Vec<PlayerInfo>> {
+    let players = server
+        .get_all_players()
+        .await
+        .iter()
+        .map(|player| PlayerInfo {
+            name: player.gameprofile.name.clone(),
+            uuid: player.gameprofile.id,
+        })
+        .collect();
+    Json(players)
+}
+
+async fn kick(
+    State(server): State<Arc<Server>>,
+    Path(name): Path<String>,
+    body: Option<Json<Reason>>,
+) -> Response {
+    // Only valid player names can be put into a command safely
+    if !is_valid_player_name(&name) {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+    if server.get_player_by_name(&name).await.is_none() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let reason = body.map(|Json(body)| body.reason).unwrap_or_default();
+    let output = run_command(
+        &server,
+        &with_reason(format!("kick {name}"), reason.as_deref()),
+    )
+    .await;
+    Json(CommandOutput { output }).into_response()
+}
+
+async fn ban(
+    State(server): State<Arc<Server>>,
+    Path(name): Path<String>,
+    body: Option<Json<Reason>>,
+) -> Response {
+    // Only valid player names can be put into a command safely
+    if !is_valid_player_name(&name) {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+    let reason = body.map(|Json(body)| body.reason).unwrap_or_default();
+    let output = run_command(
+        &server,
+        &with_reason(format!("ban {name}"), reason.as_deref()),
+    )
+    .await;
+    Json(CommandOutput { output }).into_response()
+}
+
+async fn command(
+    State(server): State<Arc<Server>>,
+    Json(body): Json<Command>,
+) -> Json<CommandOutput> {
+    let command = body.command.strip_prefix('/').unwrap_or(&body.command);
+    Json(CommandOutput {
+        output: run_command(&server, command).await,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{constant_time_eq, is_valid_player_name, with_reason};
+
+    #[test]
+    fn tokens() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+
+    #[test]
+    fn commands_stay_single_commands() {
+        assert!(is_valid_player_name("Steve_01"));
+        assert!(!is_valid_player_name("@a"));
+        assert!(!is_valid_player_name("Steve kill"));
+        assert_eq!(
+            with_reason("kick Steve".to_string(), Some("griefing\nop Steve")),
+            "kick Steve griefing"
+        );
+        assert_eq!(
+            with_reason("kick Steve".to_string(), Some("  ")),
+            "kick Steve"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     net::SocketAddr,
     num::NonZeroU8,
     sync::{
@@ -9,12 +9,15 @@ use std::{
 };
 
 use crate::{
-    data::{banned_ip_data::BANNED_IP_LIST, banned_player_data::BANNED_PLAYER_LIST},
+    data::{
+        banned_ip_data::BANNED_IP_LIST, banned_player_data::BANNED_PLAYER_LIST, whitelist_data,
+    },
     entity::player::{ChatMode, Hand},
     server::Server,
 };
 
 use crossbeam::atomic::AtomicCell;
+use disconnect::DisconnectReason;
 use pumpkin_config::networking::compression::CompressionInfo;
 use pumpkin_protocol::{
     ClientPacket, CompressionLevel, CompressionThreshold, ConnectionState, Property, RawPacket,
@@ -22,11 +25,11 @@ use pumpkin_protocol::{
     bytebuf::{ReadingError, packet::Packet},
     client::{config::CConfigDisconnect, login::CLoginDisconnect, play::CPlayDisconnect},
     packet_decoder::PacketDecoder,
-    packet_encoder::{PacketEncodeError, PacketEncoder},
+    packet_encoder::{EncodedPacket, PacketEncodeError, PacketEncoder},
     server::{
         config::{
             SAcknowledgeFinishConfig, SClientInformationConfig, SConfigCookieResponse,
-            SConfigResourcePack, SKnownPacks, SPluginMessage,
+            SConfigKeepAlive, SConfigResourcePack, SKnownPacks, SPluginMessage,
         },
         handshake::SHandShake,
         login::{
@@ -38,8 +41,6 @@ use pumpkin_protocol::{
 };
 use pumpkin_util::{ProfileAction, text::TextComponent};
 use serde::Deserialize;
-use sha1::Digest;
-use sha2::Sha256;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 
@@ -47,11 +48,15 @@ use thiserror::Error;
 use uuid::Uuid;
 mod authentication;
 mod container;
+pub mod disconnect;
+pub mod http_api;
 pub mod lan_broadcast;
+pub mod outgoing;
 mod packet;
 mod proxy;
 pub mod query;
 pub mod rcon;
+pub mod webhook;
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct GameProfile {
@@ -62,10 +67,6 @@ pub struct GameProfile {
     pub profile_actions: Option<Vec<ProfileAction>>,
 }
 
-pub fn offline_uuid(username: &str) -> Result<Uuid, uuid::Error> {
-    Uuid::from_slice(&Sha256::digest(username)[..16])
-}
-
 /// Represents a player's configuration settings.
 ///
 /// This struct contains various options that can be customized by the player, affecting their gameplay experience.
@@ -125,6 +126,8 @@ pub struct Client {
     pub config: Mutex<Option<PlayerConfig>>,
     /// The client's brand or modpack information, Optional.
     pub brand: Mutex<Option<String>>,
+    /// Data plugins attached to the connection while it logged in
+    pub login_data: Mutex<HashMap<String, String>>,
     /// The minecraft protocol version used by the client.
     pub protocol_version: AtomicI32,
     /// The Address used to connect to the Server, Send in the Handshake
@@ -133,6 +136,8 @@ pub struct Client {
     pub connection_state: AtomicCell<ConnectionState>,
     /// Indicates if the client connection is closed.
     pub closed: AtomicBool,
+    /// Why the connection ended, set by whatever closes it first
+    disconnect_reason: AtomicCell<Option<DisconnectReason>>,
     /// The client's IP address.
     pub address: Mutex<SocketAddr>,
     /// The packet encoder for outgoing packets.
@@ -145,6 +150,8 @@ pub struct Client {
     pub client_packets_queue: Arc<Mutex<VecDeque<RawPacket>>>,
     /// Indicates whether the client should be converted into a player.
     pub make_player: AtomicBool,
+    /// Whether packets were queued while flushes were deferred, see `outgoing::defer_flushes`
+    flush_pending: AtomicBool,
 }
 
 impl Client {
@@ -160,15 +167,18 @@ impl Client {
             gameprofile: Mutex::new(None),
             config: Mutex::new(None),
             brand: Mutex::new(None),
+            login_data: Mutex::new(HashMap::new()),
             server_address: Mutex::new(String::new()),
             address: Mutex::new(address),
             connection_state: AtomicCell::new(ConnectionState::HandShake),
             enc: Arc::new(Mutex::new(PacketEncoder::default())),
             dec: Arc::new(Mutex::new(PacketDecoder::default())),
             closed: AtomicBool::new(false),
+            disconnect_reason: AtomicCell::new(None),
             server_packets_channel,
             client_packets_queue: Arc::new(Mutex::new(VecDeque::new())),
             make_player: AtomicBool::new(false),
+            flush_pending: AtomicBool::new(false),
         }
     }
 
@@ -260,10 +270,7 @@ impl Client {
             }
         }
 
-        let _ = self
-            .server_packets_channel
-            .send(PacketHandlerState::PacketReady)
-            .await;
+        self.packets_queued().await;
 
         /* let mut writer = self.connection_writer.lock().await;
         if let Err(error) = writer.write_all(&enc.take()).await {
@@ -281,6 +288,51 @@ impl Client {
         */
     }
 
+    /// Lets the writer task send the queued packets, or leaves that to the `NetworkFlush` phase
+    /// while the server ticks
+    async fn packets_queued(&self) {
+        if outgoing::flushes_deferred() && self.connection_state.load() == ConnectionState::Play {
+            self.flush_pending
+                .store(true, std::sync::atomic::Ordering::Relaxed);
+            return;
+        }
+        let _ = self
+            .server_packets_channel
+            .send(PacketHandlerState::PacketReady)
+            .await;
+    }
+
+    /// Lets the writer task send the packets queued while flushes were deferred
+    pub fn flush(&self) {
+        if self
+            .flush_pending
+            .swap(false, std::sync::atomic::Ordering::Relaxed)
+        {
+            // A full channel already holds a notification, which sends everything queued
+            let _ = self
+                .server_packets_channel
+                .try_send(PacketHandlerState::PacketReady);
+        }
+    }
+
+    /// Sends a packet which is shared with other clients, see `EncodedPacket`. Broadcasts use
+    /// this so the packet is only serialized and compressed once
+    pub async fn send_encoded(&self, packet: &EncodedPacket) {
+        if self.closed.load(std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
+
+        {
+            let mut enc = self.enc.lock().await;
+            if let Err(error) = enc.append_encoded(packet) {
+                self.kick(TextComponent::text(error.to_string())).await;
+                return;
+            }
+        }
+
+        self.packets_queued().await;
+    }
+
     /// Sends a clientbound packet to the connected client.
     ///
     /// # Arguments
@@ -354,6 +406,7 @@ impl Client {
                     i32::from(packet.id),
                     error
                 );
+                self.set_disconnect_reason(DisconnectReason::ProtocolError);
                 self.kick(TextComponent::text(text)).await;
             };
         }
@@ -519,6 +572,8 @@ impl Client {
                 self.handle_resource_pack_response(SConfigResourcePack::read(bytebuf)?)
                     .await;
             }
+            // Only sent back while waiting in the join queue
+            SConfigKeepAlive::PACKET_ID => {}
             _ => {
                 log::error!(
                     "Failed to handle client packet id {} in Config State",
@@ -537,13 +592,12 @@ impl Client {
     ///
     /// * `reason`: A string describing the reason for kicking the client.
     pub async fn kick(&self, reason: TextComponent) {
+        self.set_disconnect_reason(DisconnectReason::Kicked);
         let result = match self.connection_state.load() {
             ConnectionState::Login => {
-                // TextComponent implements Serialze and writes in bytes instead of String, thats the reasib we only use content
-                self.try_send_packet(&CLoginDisconnect::new(
-                    &serde_json::to_string(&reason.0).unwrap_or_else(|_| String::new()),
-                ))
-                .await
+                // The login state still expects the reason as a JSON string instead of NBT
+                self.try_send_packet(&CLoginDisconnect::new(&reason.to_json()))
+                    .await
             }
             ConnectionState::Config => {
                 self.try_send_packet(&CConfigDisconnect::new(&reason.get_text()))
@@ -572,43 +626,40 @@ impl Client {
 
         let mut banned_players = BANNED_PLAYER_LIST.write().await;
         if let Some(entry) = banned_players.get_entry(profile) {
-            let text = TextComponent::translate(
-                "multiplayer.disconnect.banned.reason",
-                [TextComponent::text(entry.reason.clone())],
-            );
-            return Some(match entry.expires {
-                Some(expires) => text.add_child(TextComponent::translate(
-                    "multiplayer.disconnect.banned.expiration",
-                    [TextComponent::text(
-                        expires.format("%F at %T %Z").to_string(),
-                    )],
-                )),
-                None => text,
-            });
+            return Some(entry.kick_message());
         }
         drop(banned_players);
 
         let mut banned_ips = BANNED_IP_LIST.write().await;
         let address = self.address.lock().await;
         if let Some(entry) = banned_ips.get_entry(&address.ip()) {
-            let text = TextComponent::translate(
-                "multiplayer.disconnect.banned_ip.reason",
-                [TextComponent::text(entry.reason.clone())],
-            );
-            return Some(match entry.expires {
-                Some(expires) => text.add_child(TextComponent::translate(
-                    "multiplayer.disconnect.banned_ip.expiration",
-                    [TextComponent::text(
-                        expires.format("%F at %T %Z").to_string(),
-                    )],
-                )),
-                None => text,
-            });
+            return Some(entry.kick_message());
+        }
+
+        if !whitelist_data::may_join(profile.id).await {
+            return Some(TextComponent::translate(
+                "multiplayer.disconnect.not_whitelisted",
+                [],
+            ));
         }
 
         None
     }
 
+    /// Records why the connection ends. Only the first reason is kept, so a more specific reason
+    /// can be set right before a generic one like a kick
+    pub fn set_disconnect_reason(&self, reason: DisconnectReason) {
+        let _ = self.disconnect_reason.compare_exchange(None, Some(reason));
+    }
+
+    /// Why the connection ended, a connection closed without a recorded reason was closed by the
+    /// client
+    pub fn disconnect_reason(&self) -> DisconnectReason {
+        self.disconnect_reason
+            .load()
+            .unwrap_or(DisconnectReason::ClientQuit)
+    }
+
     /// Closes the connection to the client.
     ///
     /// This function marks the connection as closed using an atomic flag. It's generally preferable
@@ -619,6 +670,8 @@ impl Client {
     ///
     /// This function does not attempt to send any disconnect packets to the client.
     pub async fn close(&self) {
+        // Send what was queued for this tick before the writer stops
+        self.flush();
         self.closed
             .store(true, std::sync::atomic::Ordering::Relaxed);
         // We dont care if this fails because if it doesn that means the task has already stopped
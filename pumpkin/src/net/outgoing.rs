@@ -0,0 +1,157 @@
+use std::{
+    io::{self, IoSlice},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use bytes::{Buf, Bytes};
+use pumpkin_protocol::buffer_pool::BUFFER_POOL;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// The most parts handed to the operating system in one write
+const MAX_PARTS_PER_WRITE: usize = 64;
+
+/// Set while the server ticks. Packets sent to players meanwhile are only queued, and the
+/// `NetworkFlush` phase hands them to the writer of each connection at once
+static FLUSHES_DEFERRED: AtomicBool = AtomicBool::new(false);
+
+pub fn defer_flushes(defer: bool) {
+    FLUSHES_DEFERRED.store(defer, Ordering::Relaxed);
+}
+
+pub fn flushes_deferred() -> bool {
+    FLUSHES_DEFERRED.load(Ordering::Relaxed)
+}
+
+/// How much all connections wrote and in how many write calls, to see how well packets are
+/// batched
+pub static WRITE_STATS: WriteStats = WriteStats::new();
+
+pub struct WriteStats {
+    bytes: AtomicU64,
+    writes: AtomicU64,
+    batches: AtomicU64,
+}
+
+impl WriteStats {
+    const fn new() -> Self {
+        Self {
+            bytes: AtomicU64::new(0),
+            writes: AtomicU64::new(0),
+            batches: AtomicU64::new(0),
+        }
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Write calls, each one is a syscall
+    pub fn writes(&self) -> u64 {
+        self.writes.load(Ordering::Relaxed)
+    }
+
+    /// Times a connection sent the packets queued for it
+    pub fn batches(&self) -> u64 {
+        self.batches.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_per_write(&self) -> f64 {
+        self.bytes() as f64 / self.writes().max(1) as f64
+    }
+}
+
+/// Writes the parts one after another with as few write calls as possible, then gives their
+/// buffers back to the pool
+pub async fn write_parts<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    mut parts: Vec<Bytes>,
+) -> io::Result<()> {
+    parts.retain(|part| !part.is_empty());
+    if parts.is_empty() {
+        return Ok(());
+    }
+    WRITE_STATS.batches.fetch_add(1, Ordering::Relaxed);
+
+    let mut first = 0;
+    while first < parts.len() {
+        let mut written = {
+            let slices: Vec<_> = parts[first..]
+                .iter()
+                .take(MAX_PARTS_PER_WRITE)
+                .map(|part| IoSlice::new(part))
+                .collect();
+            writer.write_vectored(&slices).await?
+        };
+        if written == 0 {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        WRITE_STATS.writes.fetch_add(1, Ordering::Relaxed);
+        WRITE_STATS
+            .bytes
+            .fetch_add(written as u64, Ordering::Relaxed);
+
+        // Skip what was written, the last part may only be written partly
+        while written > 0 {
+            let part = &mut parts[first];
+            if written < part.len() {
+                part.advance(written);
+                break;
+            }
+            written -= part.len();
+            first += 1;
+        }
+    }
+    BUFFER_POOL.recycle(parts);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use bytes::Bytes;
+    use tokio::io::AsyncWrite;
+
+    use super::write_parts;
+
+    /// Takes at most 3 bytes per write, like a full socket buffer
+    #[derive(Default)]
+    struct SlowWriter(Vec<u8>);
+
+    impl AsyncWrite for SlowWriter {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let len = buf.len().min(3);
+            self.0.extend_from_slice(&buf[..len]);
+            Poll::Ready(Ok(len))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn partial_writes_continue_where_they_stopped() {
+        let mut writer = SlowWriter::default();
+        let parts = vec![
+            Bytes::from_static(b"hello"),
+            Bytes::new(),
+            Bytes::from_static(b" "),
+            Bytes::from_static(b"world"),
+        ];
+        write_parts(&mut writer, parts).await.unwrap();
+        assert_eq!(writer.0, b"hello world");
+    }
+}
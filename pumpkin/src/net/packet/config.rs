@@ -1,15 +1,15 @@
-use std::num::NonZeroU8;
+use std::{num::NonZeroU8, sync::atomic::Ordering, time::Duration};
 
 use crate::{
     entity::player::{ChatMode, Hand},
     net::{Client, PlayerConfig},
-    server::Server,
+    server::{Server, slots},
 };
 use core::str;
-use pumpkin_config::advanced_config;
+use pumpkin_config::{BASIC_CONFIG, advanced_config, resource_pack::ResourcePackConfig};
 use pumpkin_protocol::{
     ConnectionState,
-    client::config::{CFinishConfig, CRegistryData},
+    client::config::{CConfigKeepAlive, CFinishConfig, CRegistryData},
     codec::var_int::VarInt,
     server::config::{
         ResourcePackResponseResult, SClientInformationConfig, SConfigCookieResponse,
@@ -18,6 +18,10 @@ use pumpkin_protocol::{
 };
 use pumpkin_util::text::TextComponent;
 
+/// How often a player waiting in the join queue checks for a slot, a leaving player wakes them
+/// up earlier
+const QUEUE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 impl Client {
     pub async fn handle_client_information_config(
         &self,
@@ -69,8 +73,16 @@ impl Client {
         }
     }
 
+    /// The resource pack of the virtual host the client connected through, or the server's
+    pub async fn resource_pack_config(&self) -> &'static ResourcePackConfig {
+        BASIC_CONFIG
+            .virtual_host(&self.server_address.lock().await)
+            .and_then(|host| host.resource_pack.as_ref())
+            .unwrap_or(&advanced_config().resource_pack)
+    }
+
     pub async fn handle_resource_pack_response(&self, packet: SConfigResourcePack) {
-        let resource_config = &advanced_config().resource_pack;
+        let resource_config = self.resource_pack_config().await;
         if resource_config.enabled {
             let expected_uuid =
                 uuid::Uuid::new_v3(&uuid::Uuid::NAMESPACE_DNS, resource_config.url.as_bytes());
@@ -148,6 +160,10 @@ impl Client {
 
     pub async fn handle_known_packs(&self, server: &Server, _config_acknowledged: SKnownPacks) {
         log::debug!("Handling known packs");
+        if !self.wait_for_slot(server).await {
+            return;
+        }
+
         for registry in &server.cached_registry {
             self.send_packet(&CRegistryData::new(
                 &registry.registry_id,
@@ -161,6 +177,54 @@ impl Client {
         self.send_packet(&CFinishConfig).await;
     }
 
+    /// Waits in the join queue while the server is full, the configuration is held back until
+    /// then. Returns false if the client was kicked or left
+    async fn wait_for_slot(&self, server: &Server) -> bool {
+        let Some(profile) = self.gameprofile.lock().await.clone() else {
+            return true;
+        };
+        if slots::has_free_slot(server, &profile).await {
+            return true;
+        }
+
+        let server_full = TextComponent::translate("multiplayer.disconnect.server_full", []);
+        if !advanced_config().slots.queue.enabled {
+            self.kick(server_full).await;
+            return false;
+        }
+        // Players who can't join anyway shouldn't wait for nothing
+        if let Some(reason) = self.can_not_join().await {
+            self.kick(reason).await;
+            return false;
+        }
+        let Some(position) = server.join_queue.enqueue(profile.id).await else {
+            self.kick(server_full).await;
+            return false;
+        };
+        log::info!(
+            "{} is waiting for a free slot, position {position} in the queue",
+            profile.name
+        );
+
+        let got_slot = loop {
+            if self.closed.load(Ordering::Relaxed) {
+                break false;
+            }
+            if server.join_queue.is_next(profile.id).await
+                && slots::has_free_slot(server, &profile).await
+            {
+                break true;
+            }
+            // The client gives up on connections which stay silent for too long
+            self.send_packet(&CConfigKeepAlive::new(rand::random()))
+                .await;
+            let _ =
+                tokio::time::timeout(QUEUE_CHECK_INTERVAL, server.join_queue.slot_freed()).await;
+        };
+        server.join_queue.leave(profile.id).await;
+        got_slot
+    }
+
     pub async fn handle_config_acknowledged(&self) {
         log::debug!("Handling config acknowledge");
         self.connection_state.store(ConnectionState::Play);
@@ -1,8 +1,7 @@
-use std::sync::LazyLock;
-
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
+use pumpkin_macros::send_cancellable;
 use pumpkin_protocol::{
-    ConnectionState, KnownPack, Label, Link, LinkType,
+    ConnectionState, KnownPack,
     client::{
         config::{CConfigAddResourcePack, CConfigServerLinks, CKnownPacks, CUpdateTags},
         login::{CLoginSuccess, CSetCompression},
@@ -10,104 +9,48 @@ use pumpkin_protocol::{
     codec::var_int::VarInt,
     server::login::{SEncryptionResponse, SLoginCookieResponse, SLoginPluginResponse, SLoginStart},
 };
+use pumpkin_util::java_uuid::offline_uuid;
+use pumpkin_util::player_name::is_valid_player_name;
 use pumpkin_util::text::TextComponent;
 use uuid::Uuid;
 
 use crate::{
+    data::op_data::OPERATOR_CONFIG,
     net::{
         Client, GameProfile,
         authentication::{self, AuthError},
-        offline_uuid,
-        packet::is_valid_player_name,
         proxy::{bungeecord, velocity},
     },
+    plugin::player::{
+        player_pre_login::PlayerPreLoginEvent, player_profile_fetched::PlayerProfileFetchedEvent,
+    },
     server::Server,
 };
 
-static LINKS: LazyLock<Vec<Link>> = LazyLock::new(|| {
-    let mut links: Vec<Link> = Vec::new();
-
-    let bug_report = &advanced_config().server_links.bug_report;
-    if !bug_report.is_empty() {
-        links.push(Link::new(Label::BuiltIn(LinkType::BugReport), bug_report));
-    }
-
-    let support = &advanced_config().server_links.support;
-    if !support.is_empty() {
-        links.push(Link::new(Label::BuiltIn(LinkType::Support), support));
-    }
-
-    let status = &advanced_config().server_links.status;
-    if !status.is_empty() {
-        links.push(Link::new(Label::BuiltIn(LinkType::Status), status));
-    }
-
-    let feedback = &advanced_config().server_links.feedback;
-    if !feedback.is_empty() {
-        links.push(Link::new(Label::BuiltIn(LinkType::Feedback), feedback));
-    }
-
-    let community = &advanced_config().server_links.community;
-    if !community.is_empty() {
-        links.push(Link::new(Label::BuiltIn(LinkType::Community), community));
-    }
-
-    let website = &advanced_config().server_links.website;
-    if !website.is_empty() {
-        links.push(Link::new(Label::BuiltIn(LinkType::Website), website));
-    }
-
-    let forums = &advanced_config().server_links.forums;
-    if !forums.is_empty() {
-        links.push(Link::new(Label::BuiltIn(LinkType::Forums), forums));
-    }
-
-    let news = &advanced_config().server_links.news;
-    if !news.is_empty() {
-        links.push(Link::new(Label::BuiltIn(LinkType::News), news));
-    }
-
-    let announcements = &advanced_config().server_links.announcements;
-    if !announcements.is_empty() {
-        links.push(Link::new(
-            Label::BuiltIn(LinkType::Announcements),
-            announcements,
-        ));
-    }
-
-    for (key, value) in &advanced_config().server_links.custom {
-        links.push(Link::new(
-            Label::TextComponent(TextComponent::text(key).into()),
-            value,
-        ));
-    }
-    links
-});
-
 impl Client {
     pub async fn handle_login_start(&self, server: &Server, login_start: SLoginStart) {
         log::debug!("login start");
 
-        // Don't allow new logons when server is full.
-        // If max players is set to zero, then there is no max player count enforced.
-        // TODO: If client is an operator or otherwise suitable elevated permissions, allow client to bypass this requirement.
-        let max_players = BASIC_CONFIG.max_players;
-        if max_players > 0 && server.get_player_count().await >= max_players as usize {
-            self.kick(TextComponent::translate(
-                "multiplayer.disconnect.server_full",
-                [],
-            ))
-            .await;
-            return;
-        }
+        // Whether the server is full is checked once the profile is known, see
+        // `Client::wait_for_slot`
 
         if !is_valid_player_name(&login_start.name) {
             self.kick(TextComponent::text("Invalid characters in username"))
                 .await;
             return;
         }
+
+        let address = self.address.lock().await.ip();
+        send_cancellable! {{
+            PlayerPreLoginEvent::new(login_start.name.clone(), address);
+
+            'cancelled: {
+                self.kick(event.kick_message).await;
+                return;
+            }
+        }}
+
         // default game profile, when no online mode
-        // TODO: make offline uuid
         let mut gameprofile = self.gameprofile.lock().await;
         let proxy = &advanced_config().networking.proxy;
         if proxy.enabled {
@@ -121,10 +64,11 @@ impl Client {
                 )
                 .await
                 {
-                    Ok((_ip, profile)) => {
+                    Ok((_ip, mut profile)) => {
                         // self.address.lock() = ip;
-                        self.finish_login(&profile).await;
-                        *gameprofile = Some(profile);
+                        if self.finish_login(&mut profile).await {
+                            *gameprofile = Some(profile);
+                        }
                     }
                     Err(error) => self.kick(TextComponent::text(error.to_string())).await,
                 }
@@ -133,10 +77,10 @@ impl Client {
             let id = if BASIC_CONFIG.online_mode {
                 login_start.uuid
             } else {
-                offline_uuid(&login_start.name).expect("This is very not safe and bad")
+                offline_uuid(&login_start.name)
             };
 
-            let profile = GameProfile {
+            let mut profile = GameProfile {
                 id,
                 name: login_start.name,
                 properties: vec![],
@@ -153,7 +97,9 @@ impl Client {
                 if advanced_config().networking.packet_compression.enabled {
                     self.enable_compression().await;
                 }
-                self.finish_login(&profile).await;
+                if !self.finish_login(&mut profile).await {
+                    return;
+                }
             }
 
             *gameprofile = Some(profile);
@@ -199,6 +145,7 @@ impl Client {
                         e => TextComponent::text(e.to_string()),
                     })
                     .await;
+                    return;
                 }
             }
         }
@@ -252,9 +199,26 @@ impl Client {
         self.set_compression(Some(compression)).await;
     }
 
-    async fn finish_login(&self, profile: &GameProfile) {
-        let packet = CLoginSuccess::new(&profile.id, &profile.name, &profile.properties);
-        self.send_packet(&packet).await;
+    /// Lets plugins check and change the profile before finishing the login, returns false when
+    /// one of them denied it
+    async fn finish_login(&self, profile: &mut GameProfile) -> bool {
+        let address = self.address.lock().await.ip();
+        send_cancellable! {{
+            PlayerProfileFetchedEvent::new(profile.clone(), address);
+
+            'after: {
+                *profile = event.profile;
+                self.login_data.lock().await.extend(event.data);
+                let packet = CLoginSuccess::new(&profile.id, &profile.name, &profile.properties);
+                self.send_packet(&packet).await;
+                true
+            }
+
+            'cancelled: {
+                self.kick(event.kick_message).await;
+                false
+            }
+        }}
     }
 
     async fn authenticate(
@@ -319,16 +283,15 @@ impl Client {
         log::debug!("Handling plugin");
         let velocity_config = &advanced_config().networking.proxy.velocity;
         if velocity_config.enabled {
-            let mut address = self.address.lock().await;
-            match velocity::receive_velocity_plugin_response(
-                address.port(),
-                velocity_config,
-                plugin_response,
-            ) {
-                Ok((profile, new_address)) => {
-                    self.finish_login(&profile).await;
-                    *self.gameprofile.lock().await = Some(profile);
-                    *address = new_address;
+            let port = self.address.lock().await.port();
+            match velocity::receive_velocity_plugin_response(port, velocity_config, plugin_response)
+            {
+                Ok((mut profile, new_address)) => {
+                    // The plugins should see the address of the player, not the one of the proxy
+                    *self.address.lock().await = new_address;
+                    if self.finish_login(&mut profile).await {
+                        *self.gameprofile.lock().await = Some(profile);
+                    }
                 }
                 Err(error) => self.kick(TextComponent::text(error.to_string())).await,
             }
@@ -340,13 +303,20 @@ impl Client {
         self.connection_state.store(ConnectionState::Config);
         self.send_packet(&server.get_branding()).await;
 
-        if advanced_config().server_links.enabled {
+        let permission_lvl = match self.gameprofile.lock().await.as_ref() {
+            Some(profile) => OPERATOR_CONFIG.read().await.get_permission_lvl(profile.id),
+            None => advanced_config().commands.default_op_level,
+        };
+        let server_links = server.links.read().await;
+        let links = server_links.visible_to(permission_lvl as u8);
+        if !links.is_empty() {
             self.send_packet(&CConfigServerLinks::new(
-                &VarInt(LINKS.len() as i32),
-                &LINKS,
+                &VarInt(links.len() as i32),
+                &links,
             ))
             .await;
         }
+        drop(server_links);
 
         // TODO: Is this the right place to send them?
         // send tags
@@ -356,7 +326,7 @@ impl Client {
         ]))
         .await;
 
-        let resource_config = &advanced_config().resource_pack;
+        let resource_config = self.resource_pack_config().await;
         if resource_config.enabled {
             let uuid = Uuid::new_v3(&uuid::Uuid::NAMESPACE_DNS, resource_config.url.as_bytes());
             let resource_pack = CConfigAddResourcePack::new(
@@ -1,23 +1,33 @@
 use std::num::NonZeroU8;
 use std::sync::Arc;
+use std::time::Instant;
 
 use crate::block;
 use crate::block::registry::BlockActionResult;
 use crate::entity::mob;
 use crate::net::PlayerConfig;
+use crate::plugin::block::block_interact::BlockInteractEvent;
+use crate::plugin::block::block_place::BlockPlaceEvent;
 use crate::plugin::player::player_chat::PlayerChatEvent;
 use crate::plugin::player::player_command_send::PlayerCommandSendEvent;
 use crate::plugin::player::player_move::PlayerMoveEvent;
+use crate::plugin::player::player_pre_chat::PlayerPreChatEvent;
 use crate::{
+    PLUGIN_MANAGER,
     command::CommandSender,
+    data::muted_player_data::MUTED_PLAYER_LIST,
     entity::player::{ChatMode, Hand, Player},
     error::PumpkinError,
-    server::Server,
-    world::chunker,
+    server::{Server, chat_filter::CHAT_FILTERS},
+    world::{
+        chunker, end,
+        game_event::{self, GameEventContext},
+    },
 };
-use pumpkin_config::advanced_config;
+use pumpkin_config::{ChatFilterAction, advanced_config};
 use pumpkin_data::block::{Block, HorizontalFacing};
 use pumpkin_data::entity::{EntityType, entity_from_egg};
+use pumpkin_data::game_event::GameEvent;
 use pumpkin_data::item::Item;
 use pumpkin_data::sound::Sound;
 use pumpkin_data::sound::SoundCategory;
@@ -49,7 +59,6 @@ use pumpkin_protocol::{
         SSetPlayerGround, SSwingArm, SUseItem, SUseItemOn, Status,
     },
 };
-use pumpkin_util::math::boundingbox::BoundingBox;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::{
@@ -58,7 +67,7 @@ use pumpkin_util::{
     text::TextComponent,
 };
 use pumpkin_world::block::interactive::sign::Sign;
-use pumpkin_world::block::registry::get_block_collision_shapes;
+use pumpkin_world::block::registry::get_block_collision_boxes;
 use pumpkin_world::block::{BlockDirection, registry::get_block_by_item};
 use pumpkin_world::item::ItemStack;
 
@@ -141,7 +150,7 @@ impl Player {
         self.set_client_loaded(true);
     }
 
-    pub async fn handle_position(self: &Arc<Self>, packet: SPlayerPosition) {
+    pub async fn handle_position(self: &Arc<Self>, server: &Server, packet: SPlayerPosition) {
         if !self.has_client_loaded() {
             return;
         }
@@ -173,6 +182,9 @@ impl Player {
                 let position = event.to;
                 let entity = &self.living_entity.entity;
                 let last_pos = entity.pos.load();
+                if position != last_pos {
+                    self.update_last_action_time();
+                }
                 self.living_entity.set_pos(position);
 
                 let height_difference = position.y - last_pos.y;
@@ -221,6 +233,7 @@ impl Player {
                 if !self.abilities.lock().await.flying {
                     self.living_entity
                         .update_fall_distance(
+                            server,
                             height_difference,
                             packet.ground,
                             self.gamemode.load() == GameMode::Creative,
@@ -249,7 +262,11 @@ impl Player {
         }}
     }
 
-    pub async fn handle_position_rotation(self: &Arc<Self>, packet: SPlayerPositionRotation) {
+    pub async fn handle_position_rotation(
+        self: &Arc<Self>,
+        server: &Server,
+        packet: SPlayerPositionRotation,
+    ) {
         if !self.has_client_loaded() {
             return;
         }
@@ -286,6 +303,9 @@ impl Player {
                 let position = event.to;
                 let entity = &self.living_entity.entity;
                 let last_pos = entity.pos.load();
+                if position != last_pos {
+                    self.update_last_action_time();
+                }
                 self.living_entity.set_pos(position);
 
                 let height_difference = position.y - last_pos.y;
@@ -348,6 +368,7 @@ impl Player {
                 if !self.abilities.lock().await.flying {
                     self.living_entity
                         .update_fall_distance(
+                            server,
                             height_difference,
                             packet.ground,
                             self.gamemode.load() == GameMode::Creative,
@@ -403,10 +424,16 @@ impl Player {
         entity
             .on_ground
             .store(rotation.ground, std::sync::atomic::Ordering::Relaxed);
-        entity.set_rotation(
+        let (yaw, pitch) = (
             wrap_degrees(rotation.yaw) % 360.0,
             wrap_degrees(rotation.pitch),
         );
+        if (yaw - entity.yaw.load()).abs() > f32::EPSILON
+            || (pitch - entity.pitch.load()).abs() > f32::EPSILON
+        {
+            self.update_last_action_time();
+        }
+        entity.set_rotation(yaw, pitch);
         // send new position to all other players
         let entity_id = entity.entity_id;
         let yaw = (entity.yaw.load() * 256.0 / 360.0).rem_euclid(256.0);
@@ -662,7 +689,12 @@ impl Player {
             .await;
     }
 
-    pub async fn handle_chat_message(self: &Arc<Self>, chat_message: SChatMessage) {
+    #[expect(clippy::too_many_lines)]
+    pub async fn handle_chat_message(
+        self: &Arc<Self>,
+        server: &Server,
+        chat_message: SChatMessage,
+    ) {
         let message = chat_message.message;
         if message.len() > 256 {
             self.kick(TextComponent::text("Oversized message")).await;
@@ -678,9 +710,78 @@ impl Player {
             return;
         }
 
+        if let Some(entry) = MUTED_PLAYER_LIST
+            .read()
+            .await
+            .get_entry(&self.gameprofile.id)
+        {
+            self.send_system_message(
+                &TextComponent::text(format!("You are muted: {}", entry.reason))
+                    .color_named(NamedColor::Red),
+            )
+            .await;
+            return;
+        }
+
+        if self.permission_lvl.load() < advanced_config().chat.slow_chat_bypass_level {
+            let now = Instant::now();
+            if let Some(remaining) = server.slow_chat.remaining(self.last_chat_time.load(), now) {
+                self.send_system_message(
+                    &TextComponent::text(format!(
+                        "Slow chat is on, you can chat again in {:.0} seconds",
+                        remaining.as_secs_f64().ceil()
+                    ))
+                    .color_named(NamedColor::Red),
+                )
+                .await;
+                return;
+            }
+            self.last_chat_time.store(Some(now));
+        }
+
+        let event = PLUGIN_MANAGER
+            .lock()
+            .await
+            .fire(PlayerPreChatEvent::new(self.clone(), message.clone()))
+            .await;
+        if event.cancelled {
+            return;
+        }
+
+        let filtered = CHAT_FILTERS.apply(&event.message);
+        match filtered.action {
+            Some(ChatFilterAction::Block) => {
+                log::info!(
+                    "Blocked chat message from {}: {}",
+                    self.gameprofile.name,
+                    event.message
+                );
+                self.send_system_message(
+                    &TextComponent::text("Your message was blocked by the chat filter")
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+                return;
+            }
+            Some(ChatFilterAction::Warn) => {
+                self.send_system_message(
+                    &TextComponent::text("Please watch your language")
+                        .color_named(NamedColor::Yellow),
+                )
+                .await;
+            }
+            Some(ChatFilterAction::Censor) | None => {}
+        }
+        // The signature only matches the message the client sent
+        let signature = if filtered.message == message {
+            chat_message.signature.as_deref()
+        } else {
+            None
+        };
+
         let gameprofile = &self.gameprofile;
         send_cancellable! {{
-            PlayerChatEvent::new(self.clone(), message.clone(), vec![]);
+            PlayerChatEvent::new(self.clone(), filtered.message, vec![]);
 
             'after: {
                 log::info!("<chat>{}: {}", gameprofile.name, event.message);
@@ -692,7 +793,7 @@ impl Player {
                         .broadcast_packet_all(&CPlayerChatMessage::new(
                             gameprofile.id,
                             1.into(),
-                            chat_message.signature.as_deref(),
+                            signature,
                             &event.message,
                             chat_message.timestamp,
                             chat_message.salt,
@@ -710,7 +811,7 @@ impl Player {
                             &CPlayerChatMessage::new(
                                 gameprofile.id,
                                 1.into(),
-                                chat_message.signature.as_deref(),
+                                signature,
                                 &event.message,
                                 chat_message.timestamp,
                                 chat_message.salt,
@@ -810,14 +911,22 @@ impl Player {
         }
     }
 
-    pub async fn handle_client_status(self: &Arc<Self>, client_status: SClientCommand) {
+    pub async fn handle_client_status(
+        self: &Arc<Self>,
+        server: &Server,
+        client_status: SClientCommand,
+    ) {
         match client_status.action_id.0 {
             0 => {
-                // Perform Respawn
-                if self.living_entity.health.load() > 0.0 {
+                // Perform Respawn, also sent once the credits are done
+                if end::leave_end(self, server).await || self.living_entity.health.load() > 0.0 {
                     return;
                 }
-                self.world().await.respawn_player(self, false).await;
+                server
+                    .worlds
+                    .default_world()
+                    .respawn_player(self, false)
+                    .await;
 
                 // Restore abilities based on gamemode after respawn
                 let mut abilities = self.abilities.lock().await;
@@ -836,7 +945,7 @@ impl Player {
         };
     }
 
-    pub async fn handle_interact(&self, interact: SInteract) {
+    pub async fn handle_interact(&self, server: &Server, interact: SInteract) {
         if !self.has_client_loaded() {
             return;
         }
@@ -891,6 +1000,23 @@ impl Player {
                             .await;
                         return;
                     }
+                    let victim_pos = player_victim.living_entity.entity.block_pos.load();
+                    if !world
+                        .claims
+                        .lock()
+                        .await
+                        .allows_pvp(&entity.block_pos.load(), &victim_pos)
+                    {
+                        return;
+                    }
+                    if !server
+                        .scoreboard
+                        .lock()
+                        .await
+                        .allows_damage(&self.gameprofile.name, &player_victim.gameprofile.name)
+                    {
+                        return;
+                    }
                     self.attack(player_victim).await;
                 } else if let Some(entity_victim) = world.get_entity_by_id(entity_id.0).await {
                     self.attack(entity_victim).await;
@@ -908,9 +1034,18 @@ impl Player {
                     return;
                 };
             }
-            ActionType::Interact | ActionType::InteractAt => {
-                log::debug!("todo");
+            // The client sends both, unless the entity is an armor stand
+            ActionType::Interact => {
+                // TODO: Interactions with the offhand
+                if interact.hand.is_some_and(|hand| hand.0 != 0) {
+                    return;
+                }
+                let world = entity.world.read().await.clone();
+                if let Some(target) = world.get_entity_by_id(interact.entity_id.0).await {
+                    target.interact(self, server).await;
+                }
             }
+            ActionType::InteractAt => {}
         }
     }
 
@@ -940,6 +1075,14 @@ impl Player {
                     let block = world.get_block(&location).await.unwrap();
                     let state = world.get_block_state(&location).await.unwrap();
 
+                    // Like in adventure mode without an item allowed to break the block
+                    if !self.may_break(&block).await {
+                        self.client
+                            .send_packet(&CBlockUpdate::new(&location, VarInt(i32::from(state.id))))
+                            .await;
+                        self.update_sequence(player_action.sequence.0);
+                        return;
+                    }
                     if let Some(held) = self.inventory.lock().await.held_item() {
                         if !server.item_registry.can_mine(&held.item, self) {
                             self.client
@@ -959,20 +1102,23 @@ impl Player {
                         // Block break & block break sound
 
                         let broken_state = world.get_block_state(&location).await.unwrap();
-                        world
+                        if world
                             .break_block(&location, Some(self.clone()), false, None)
-                            .await;
-                        server
-                            .block_registry
-                            .broken(
-                                Arc::clone(world),
-                                &block,
-                                self,
-                                location,
-                                server,
-                                broken_state,
-                            )
-                            .await;
+                            .await
+                        {
+                            server
+                                .block_registry
+                                .broken(
+                                    Arc::clone(world),
+                                    &block,
+                                    self,
+                                    location,
+                                    server,
+                                    broken_state,
+                                )
+                                .await;
+                        }
+                        self.update_sequence(player_action.sequence.0);
                         return;
                     }
                     self.start_mining_time.store(
@@ -984,20 +1130,22 @@ impl Player {
                         // Instant break
                         if speed >= 1.0 {
                             let broken_state = world.get_block_state(&location).await.unwrap();
-                            world
+                            if world
                                 .break_block(&location, Some(self.clone()), true, None)
-                                .await;
-                            server
-                                .block_registry
-                                .broken(
-                                    Arc::clone(world),
-                                    &block,
-                                    self,
-                                    location,
-                                    server,
-                                    broken_state,
-                                )
-                                .await;
+                                .await
+                            {
+                                server
+                                    .block_registry
+                                    .broken(
+                                        Arc::clone(world),
+                                        &block,
+                                        self,
+                                        location,
+                                        server,
+                                        broken_state,
+                                    )
+                                    .await;
+                            }
                         } else {
                             self.mining
                                 .store(true, std::sync::atomic::Ordering::Relaxed);
@@ -1047,26 +1195,28 @@ impl Player {
                     world.set_block_breaking(entity, location, -1).await;
                     let block = world.get_block(&location).await;
                     let state = world.get_block_state(&location).await;
-                    if let Ok(block) = block {
-                        let broken_state = world.get_block_state(&location).await.unwrap();
-                        if let Ok(state) = state {
-                            let drop = self.gamemode.load() != GameMode::Creative
-                                && self.can_harvest(&state, block.name).await;
-                            world
-                                .break_block(&location, Some(self.clone()), drop, None)
+                    if let (Ok(block), Ok(state)) = (block, state) {
+                        if !self.may_break(&block).await {
+                            self.client
+                                .send_packet(&CBlockUpdate::new(
+                                    &location,
+                                    VarInt(i32::from(state.id)),
+                                ))
+                                .await;
+                            self.update_sequence(player_action.sequence.0);
+                            return;
+                        }
+                        let drop = self.gamemode.load() != GameMode::Creative
+                            && self.can_harvest(&state, block.name).await;
+                        if world
+                            .break_block(&location, Some(self.clone()), drop, None)
+                            .await
+                        {
+                            server
+                                .block_registry
+                                .broken(Arc::clone(world), &block, self, location, server, state)
                                 .await;
                         }
-                        server
-                            .block_registry
-                            .broken(
-                                Arc::clone(world),
-                                &block,
-                                self,
-                                location,
-                                server,
-                                broken_state,
-                            )
-                            .await;
                     }
                     self.update_sequence(player_action.sequence.0);
                 }
@@ -1076,7 +1226,11 @@ impl Player {
                 Status::DropItemStack => {
                     self.drop_held_item(true).await;
                 }
-                Status::ShootArrowOrFinishEating | Status::SwapItem => {
+                // Sent when the player lets go of the use key before finishing to use the item
+                Status::ShootArrowOrFinishEating => {
+                    self.stop_using_item();
+                }
+                Status::SwapItem => {
                     log::debug!("todo");
                 }
             },
@@ -1129,8 +1283,9 @@ impl Player {
             .await;
     }
 
+    #[expect(clippy::too_many_lines)]
     pub async fn handle_use_item_on(
-        &self,
+        self: &Arc<Self>,
         use_item_on: SUseItemOn,
         server: &Arc<Server>,
     ) -> Result<(), Box<dyn PumpkinError>> {
@@ -1166,6 +1321,31 @@ impl Player {
             .entity
             .sneaking
             .load(std::sync::atomic::Ordering::Relaxed);
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return Err(BlockPlacingError::NoBaseBlock.into());
+        };
+        let event = PLUGIN_MANAGER
+            .lock()
+            .await
+            .fire(BlockInteractEvent {
+                player: self.clone(),
+                block: block.clone(),
+                state_id,
+                position: location,
+                face,
+                item: held_item.clone(),
+                cancelled: false,
+            })
+            .await;
+        if event.cancelled {
+            // The client might already show a block placed against the clicked one
+            self.resync_block(&location).await;
+            self.resync_block(&BlockPos(location.0 + face.to_offset()))
+                .await;
+            self.set_container_content(None).await;
+            return Ok(());
+        }
+
         let Some(stack) = held_item else {
             if !sneaking {
                 // Using block with empty hand
@@ -1188,6 +1368,10 @@ impl Player {
                 }
             }
         }
+        server
+            .item_registry
+            .use_on_block(&stack.item, self, location, &face, &block, server)
+            .await;
         // check if item is a block, Because Not every item can be placed :D
         if let Some(block) = get_block_by_item(stack.item.id) {
             should_try_decrement = self
@@ -1248,12 +1432,14 @@ impl Player {
             .await;
     }
 
-    pub async fn handle_use_item(&self, _use_item: &SUseItem, server: &Server) {
+    pub async fn handle_use_item(self: &Arc<Self>, _use_item: &SUseItem, server: &Server) {
         if !self.has_client_loaded() {
             return;
         }
-        if let Some(held) = self.inventory().lock().await.held_item() {
-            server.item_registry.on_use(&held.item, self).await;
+        // Items may change the inventory when used, so it can't stay locked
+        let held = self.inventory().lock().await.held_item().cloned();
+        if let Some(held) = held {
+            server.item_registry.on_use(&held.item, self, server).await;
         }
     }
 
@@ -1263,6 +1449,7 @@ impl Player {
             self.kick(TextComponent::text("Invalid held slot")).await;
             return;
         }
+        self.stop_using_item();
         let mut inv = self.inventory().lock().await;
         inv.set_selected(slot as usize);
         let empty = &ItemStack::new(0, Item::AIR);
@@ -1288,7 +1475,7 @@ impl Player {
                 .set_slot(packet.slot as usize, item_stack, true)?;
         } else if let Some(item_stack) = item_stack {
             // Item drop
-            self.drop_item(item_stack.item.id, u32::from(item_stack.item_count))
+            self.drop_item(&item_stack, u32::from(item_stack.item_count))
                 .await;
         };
         Ok(())
@@ -1315,6 +1502,11 @@ impl Player {
         //     return;
         // };
         // window_id 0 represents both 9x1 Generic AND inventory here
+        self.close_open_container(server).await;
+    }
+
+    /// Closes the container the player has open, if any, without sending anything to the client
+    pub async fn close_open_container(&self, server: &Server) {
         let open_container = self.open_container.load();
         if let Some(id) = open_container {
             let mut open_containers = server.open_containers.write().await;
@@ -1423,7 +1615,7 @@ impl Player {
 
     #[allow(clippy::too_many_lines)]
     async fn run_is_block_place(
-        &self,
+        self: &Arc<Self>,
         block: Block,
         server: &Server,
         use_item_on: SUseItemOn,
@@ -1435,7 +1627,7 @@ impl Player {
 
         let clicked_block_pos = BlockPos(location.0);
         let clicked_block_state = world.get_block_state(&clicked_block_pos).await?;
-        let _clicked_block = world.get_block(&clicked_block_pos).await?;
+        let clicked_block = world.get_block(&clicked_block_pos).await?;
 
         // check block under the world
         if location.0.y + face.to_offset().y < WORLD_LOWEST_Y.into() {
@@ -1456,11 +1648,16 @@ impl Player {
             return Err(BlockPlacingError::BlockOutOfWorld.into());
         }
 
-        match self.gamemode.load() {
-            GameMode::Spectator | GameMode::Adventure => {
-                return Err(BlockPlacingError::InvalidGamemode.into());
+        if !self.may_place_on(&clicked_block).await {
+            // The client predicts placing when it thinks the item may be placed there
+            let position = BlockPos(location.0 + face.to_offset());
+            if let Ok(state_id) = world.get_block_state_id(&position).await {
+                self.client
+                    .send_packet(&CBlockUpdate::new(&position, VarInt(i32::from(state_id))))
+                    .await;
             }
-            _ => {}
+            self.set_container_content(None).await;
+            return Err(BlockPlacingError::InvalidGamemode.into());
         }
 
         // TODO: Implement this
@@ -1495,17 +1692,16 @@ impl Player {
             .await;
 
         // To this point we must have the new block state
-        let shapes = get_block_collision_shapes(new_state).unwrap_or_default();
+        let block_boxes = get_block_collision_boxes(new_state, &final_block_pos);
         let mut intersects = false;
-        for player in world.get_nearby_players(location.0.to_f64(), 3.0).await {
+        for player in world.get_nearby_players(location.0.to_f64(), 3.0) {
             let player_box = player.1.living_entity.entity.bounding_box.load();
-            for shape in &shapes {
-                let block_box = BoundingBox::from_block_raw(&final_block_pos)
-                    .offset(BoundingBox::new_array(shape.min, shape.max));
-                if player_box.intersects(&block_box) {
-                    intersects = true;
-                    break;
-                }
+            if block_boxes
+                .iter()
+                .any(|block_box| player_box.intersects(block_box))
+            {
+                intersects = true;
+                break;
             }
         }
         if !intersects
@@ -1521,11 +1717,42 @@ impl Player {
                 )
                 .await
         {
+            let Some(item) = self.inventory().lock().await.held_item().cloned() else {
+                return Ok(false);
+            };
+            let event = PLUGIN_MANAGER
+                .lock()
+                .await
+                .fire(BlockPlaceEvent {
+                    player: self.clone(),
+                    block_placed: block.clone(),
+                    state_id: new_state,
+                    position: final_block_pos,
+                    block_placed_against: clicked_block,
+                    item,
+                    can_build: true,
+                    cancelled: false,
+                })
+                .await;
+            if event.cancelled {
+                self.resync_block(&final_block_pos).await;
+                self.resync_block(&clicked_block_pos).await;
+                self.set_container_content(None).await;
+                return Ok(false);
+            }
+
             let _replaced_id = world.set_block_state(&final_block_pos, new_state).await;
             server
                 .block_registry
                 .on_placed(world, &block, self, final_block_pos, server)
                 .await;
+            world
+                .emit_game_event(
+                    GameEvent::BlockPlace,
+                    game_event::block_center(&final_block_pos),
+                    GameEventContext::entity(entity).with_state(new_state),
+                )
+                .await;
 
             self.send_sign_packet(block, final_block_pos, face).await;
             // Block was placed successfully, decrement inventory
@@ -5,8 +5,10 @@ use crate::{net::Client, server::Server};
 impl Client {
     pub async fn handle_status_request(&self, server: &Server) {
         log::debug!("Handling status request");
+        let address = self.server_address.lock().await.clone();
         let status = server.get_status();
-        self.send_packet(&status.lock().await.get_status()).await;
+        self.send_packet(&status.lock().await.get_status(&address))
+            .await;
     }
 
     pub async fn handle_ping_request(&self, ping_request: SStatusPingRequest) {
@@ -1,10 +1,11 @@
 use std::{net::IpAddr, net::SocketAddr};
 
 use pumpkin_protocol::Property;
+use pumpkin_util::java_uuid::offline_uuid;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
-use crate::net::{GameProfile, offline_uuid};
+use crate::net::GameProfile;
 
 #[derive(Error, Debug)]
 pub enum BungeeCordError {
@@ -14,8 +15,6 @@ pub enum BungeeCordError {
     FailedParseUUID,
     #[error("Failed to parse Properties")]
     FailedParseProperties,
-    #[error("Failed to make offline UUID")]
-    FailedMakeOfflineUUID,
 }
 
 /// Attempts to login a player via `BungeeCord`.
@@ -48,7 +47,7 @@ pub async fn bungeecord_login(
     // Uuid of player, only given if ip_forward on bungee is true
     let id = match data.get(2) {
         Some(uuid) => uuid.parse().map_err(|_| BungeeCordError::FailedParseUUID)?,
-        None => offline_uuid(name.as_str()).map_err(|_| BungeeCordError::FailedMakeOfflineUUID)?,
+        None => offline_uuid(&name),
     };
 
     // Read properties and get textures
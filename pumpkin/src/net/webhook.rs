@@ -0,0 +1,216 @@
+//! Posts server and player events to the webhooks in the config, e.g. to a Discord channel.
+//! Webhooks listen on the plugin event bus like any plugin does, except for crashes, which are
+//! sent from the panic hook.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use pumpkin_config::{WebhookConfig, WebhookEvent, WebhookFormat, advanced_config};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    entity::player::Player,
+    plugin::{
+        EventHandler, EventPriority, PluginManager,
+        player::{player_join::PlayerJoinEvent, player_leave::PlayerLeaveEvent},
+        server::{server_started::ServerStartedEvent, server_stop::ServerStopEvent},
+    },
+};
+
+#[derive(Serialize)]
+struct WebhookPlayer {
+    name: String,
+    uuid: Uuid,
+}
+
+#[derive(Serialize)]
+struct Payload {
+    event: WebhookEvent,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    player: Option<WebhookPlayer>,
+}
+
+#[derive(Serialize)]
+struct DiscordPayload<'a> {
+    content: &'a str,
+}
+
+impl Payload {
+    fn new(event: WebhookEvent, message: String, player: Option<&Player>) -> Self {
+        Self {
+            event,
+            message,
+            player: player.map(|player| WebhookPlayer {
+                name: player.gameprofile.name.clone(),
+                uuid: player.gameprofile.id,
+            }),
+        }
+    }
+
+    fn body(&self, format: WebhookFormat) -> serde_json::Value {
+        match format {
+            WebhookFormat::Json => serde_json::json!(self),
+            WebhookFormat::Discord => serde_json::json!(DiscordPayload {
+                content: &self.message
+            }),
+        }
+    }
+}
+
+/// The webhooks which want the event, none if webhooks are disabled
+fn hooks_for(event: WebhookEvent) -> Vec<WebhookConfig> {
+    let config = &advanced_config().webhooks;
+    if !config.enabled {
+        return Vec::new();
+    }
+    config
+        .hooks
+        .iter()
+        .filter(|hook| hook.wants(event))
+        .cloned()
+        .collect()
+}
+
+/// Posts the payload to every webhook which wants it, failures are only logged
+async fn send(payload: Payload) {
+    let hooks = hooks_for(payload.event);
+    if hooks.is_empty() {
+        return;
+    }
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_millis(advanced_config().webhooks.timeout))
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            log::warn!("Failed to create the webhook client: {err}");
+            return;
+        }
+    };
+
+    for hook in hooks {
+        let result = client
+            .post(&hook.url)
+            .json(&payload.body(hook.format))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status);
+        if let Err(err) = result {
+            log::warn!("Failed to post {:?} to a webhook: {err}", payload.event);
+        }
+    }
+}
+
+/// Posts the crash to the webhooks before the process exits. This blocks, so it can be called
+/// from the panic hook
+pub fn notify_crash(message: String) {
+    if hooks_for(WebhookEvent::Crash).is_empty() {
+        return;
+    }
+    // The panic may happen on a runtime thread, where another runtime can't be started
+    let thread = std::thread::spawn(move || {
+        let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        else {
+            return;
+        };
+        runtime.block_on(send(Payload::new(WebhookEvent::Crash, message, None)));
+    });
+    let _ = thread.join();
+}
+
+pub struct Webhooks;
+
+impl Webhooks {
+    /// Webhooks are registered even while disabled, so enabling them with a config reload
+    /// works
+    pub async fn register(manager: &PluginManager) {
+        let handler = Arc::new(Self);
+        manager
+            .register::<ServerStartedEvent, _>(handler.clone(), EventPriority::Lowest, false)
+            .await;
+        manager
+            .register::<ServerStopEvent, _>(handler.clone(), EventPriority::Lowest, false)
+            .await;
+        manager
+            .register::<PlayerJoinEvent, _>(handler.clone(), EventPriority::Lowest, false)
+            .await;
+        manager
+            .register::<PlayerLeaveEvent, _>(handler, EventPriority::Lowest, false)
+            .await;
+    }
+}
+
+#[async_trait]
+impl EventHandler<ServerStartedEvent> for Webhooks {
+    async fn handle(&self, event: &ServerStartedEvent) {
+        let message = format!("The server started on {}", event.address);
+        tokio::spawn(send(Payload::new(WebhookEvent::ServerStart, message, None)));
+    }
+}
+
+#[async_trait]
+impl EventHandler<ServerStopEvent> for Webhooks {
+    async fn handle(&self, _event: &ServerStopEvent) {
+        // Waits, so the server doesn't exit before the webhooks got it
+        send(Payload::new(
+            WebhookEvent::ServerStop,
+            "The server is stopping".to_string(),
+            None,
+        ))
+        .await;
+    }
+}
+
+#[async_trait]
+impl EventHandler<PlayerJoinEvent> for Webhooks {
+    async fn handle(&self, event: &PlayerJoinEvent) {
+        if event.cancelled || event.player.is_vanished() {
+            return;
+        }
+        let message = event.join_message.clone().get_text();
+        tokio::spawn(send(Payload::new(
+            WebhookEvent::PlayerJoin,
+            message,
+            Some(&event.player),
+        )));
+    }
+}
+
+#[async_trait]
+impl EventHandler<PlayerLeaveEvent> for Webhooks {
+    async fn handle(&self, event: &PlayerLeaveEvent) {
+        if event.cancelled || event.player.is_vanished() {
+            return;
+        }
+        let message = event.leave_message.clone().get_text();
+        tokio::spawn(send(Payload::new(
+            WebhookEvent::PlayerLeave,
+            message,
+            Some(&event.player),
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_config::{WebhookEvent, WebhookFormat};
+
+    use super::Payload;
+
+    #[test]
+    fn formats() {
+        let payload = Payload::new(WebhookEvent::ServerStop, "Stopping".to_string(), None);
+        assert_eq!(
+            payload.body(WebhookFormat::Json).to_string(),
+            r#"{"event":"server_stop","message":"Stopping"}"#
+        );
+        assert_eq!(
+            payload.body(WebhookFormat::Discord).to_string(),
+            r#"{"content":"Stopping"}"#
+        );
+    }
+}
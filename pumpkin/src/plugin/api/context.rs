@@ -2,12 +2,17 @@ use std::{fs, path::Path, sync::Arc};
 
 use crate::command::client_suggestions;
 use pumpkin_util::PermissionLvl;
+use pumpkin_world::dimension::Dimension;
 use tokio::sync::RwLock;
 
 use crate::{
     entity::player::Player,
     plugin::{EventHandler, HandlerMap, TypedEventHandler},
-    server::Server,
+    server::{
+        Server,
+        tick::{TickHook, TickPhase},
+    },
+    world::query::WorldQuery,
 };
 
 use super::{Event, EventPriority, PluginMetadata};
@@ -71,6 +76,19 @@ impl Context {
         self.server.get_player_by_name(&player_name).await
     }
 
+    /// Read-only access to the blocks and biomes of a world, which can be used from plugin tasks
+    /// without holding up the world tick.
+    ///
+    /// # Arguments
+    /// - `dimension`: The dimension of the world.
+    ///
+    /// # Returns
+    /// A query over the loaded chunks of the world, or `None` if the dimension is not loaded.
+    #[must_use]
+    pub fn query_world(&self, dimension: Dimension) -> Option<WorldQuery> {
+        self.server.worlds.get(dimension).map(|world| world.query())
+    }
+
     /// Asynchronously registers a command with the server.
     ///
     /// # Arguments
@@ -86,7 +104,7 @@ impl Context {
             dispatcher_lock.register(tree, permission);
         };
 
-        for world in self.server.worlds.read().await.iter() {
+        for world in self.server.worlds.iter() {
             for player in world.players.read().await.values() {
                 client_suggestions::send_c_commands_packet(player, &self.server.command_dispatcher)
                     .await;
@@ -104,7 +122,7 @@ impl Context {
             dispatcher_lock.unregister(name);
         };
 
-        for world in self.server.worlds.read().await.iter() {
+        for world in self.server.worlds.iter() {
             for player in world.players.read().await.values() {
                 client_suggestions::send_c_commands_packet(player, &self.server.command_dispatcher)
                     .await;
@@ -147,4 +165,19 @@ impl Context {
         };
         handlers_vec.push(Box::new(typed_handler));
     }
+
+    /// Asynchronously registers a hook that runs every world tick during the given phase.
+    ///
+    /// # Arguments
+    /// - `phase`: The tick phase after which the hook runs.
+    /// - `priority`: The order of the hook relative to other hooks of the same phase.
+    /// - `hook`: The hook to run.
+    pub async fn register_tick_hook(
+        &self,
+        phase: TickPhase,
+        priority: EventPriority,
+        hook: Arc<dyn TickHook>,
+    ) {
+        self.server.tick_hooks.register(phase, priority, hook).await;
+    }
 }
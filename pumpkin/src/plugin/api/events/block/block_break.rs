@@ -1,5 +1,7 @@
 use pumpkin_data::block::Block;
 use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
 use std::sync::Arc;
 
 use crate::entity::player::Player;
@@ -10,6 +12,8 @@ use super::BlockEvent;
 ///
 /// This event contains information about the player breaking the block, the block itself,
 /// the experience gained, and whether the block should drop items.
+///
+/// If the event is cancelled, the block stays and the player breaking it is sent the block again.
 #[cancellable]
 #[derive(Event, Clone)]
 pub struct BlockBreakEvent {
@@ -19,6 +23,15 @@ pub struct BlockBreakEvent {
     /// The block that is being broken.
     pub block: Block,
 
+    /// The exact state of the block that is being broken.
+    pub state_id: u16,
+
+    /// The position of the block that is being broken.
+    pub position: BlockPos,
+
+    /// The item the player is breaking the block with, if any.
+    pub item: Option<ItemStack>,
+
     /// The amount of experience gained from breaking the block.
     pub exp: u32,
 
@@ -32,16 +45,30 @@ impl BlockBreakEvent {
     /// # Arguments
     /// - `player`: An optional reference to the player breaking the block.
     /// - `block`: The block that is being broken.
+    /// - `state_id`: The exact state of the block that is being broken.
+    /// - `position`: The position of the block that is being broken.
+    /// - `item`: The item the player is breaking the block with, if any.
     /// - `exp`: The amount of experience gained from breaking the block.
     /// - `drop`: A boolean indicating whether the block should drop items.
     ///
     /// # Returns
     /// A new instance of `BlockBreakEvent`.
     #[must_use]
-    pub fn new(player: Option<Arc<Player>>, block: Block, exp: u32, drop: bool) -> Self {
+    pub fn new(
+        player: Option<Arc<Player>>,
+        block: Block,
+        state_id: u16,
+        position: BlockPos,
+        item: Option<ItemStack>,
+        exp: u32,
+        drop: bool,
+    ) -> Self {
         Self {
             player,
             block,
+            state_id,
+            position,
+            item,
             exp,
             drop,
             cancelled: false,
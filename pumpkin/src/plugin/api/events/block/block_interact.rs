@@ -0,0 +1,42 @@
+use pumpkin_data::block::Block;
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::BlockDirection;
+use pumpkin_world::item::ItemStack;
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::BlockEvent;
+
+/// An event that occurs when a player right clicks a block, like opening a door or a chest.
+///
+/// If the event is cancelled, the block is not used, nothing is placed against it and the
+/// player is sent the block again.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct BlockInteractEvent {
+    /// The player interacting with the block.
+    pub player: Arc<Player>,
+
+    /// The block that is being interacted with.
+    pub block: Block,
+
+    /// The exact state of the block that is being interacted with.
+    pub state_id: u16,
+
+    /// The position of the block that is being interacted with.
+    pub position: BlockPos,
+
+    /// The face of the block the player clicked.
+    pub face: BlockDirection,
+
+    /// The item the player is holding, if any.
+    pub item: Option<ItemStack>,
+}
+
+impl BlockEvent for BlockInteractEvent {
+    fn get_block(&self) -> &Block {
+        &self.block
+    }
+}
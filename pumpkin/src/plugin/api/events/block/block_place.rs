@@ -1,5 +1,7 @@
 use pumpkin_data::block::Block;
 use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
 use std::sync::Arc;
 
 use crate::entity::player::Player;
@@ -10,6 +12,9 @@ use super::BlockEvent;
 ///
 /// This event contains information about the player placing the block, the block being placed,
 /// the block being placed against, and whether the player can build.
+///
+/// If the event is cancelled, the block is not placed, the item is kept and the player is sent
+/// the blocks again.
 #[cancellable]
 #[derive(Event, Clone)]
 pub struct BlockPlaceEvent {
@@ -19,9 +24,18 @@ pub struct BlockPlaceEvent {
     /// The block that is being placed.
     pub block_placed: Block,
 
+    /// The exact state the block is being placed with.
+    pub state_id: u16,
+
+    /// The position the block is being placed at.
+    pub position: BlockPos,
+
     /// The block that the new block is being placed against.
     pub block_placed_against: Block,
 
+    /// The item stack the block is being placed from.
+    pub item: ItemStack,
+
     /// A boolean indicating whether the player can build.
     pub can_build: bool,
 }
@@ -1,6 +1,7 @@
 pub mod block_break;
 pub mod block_burn;
 pub mod block_can_build;
+pub mod block_interact;
 pub mod block_place;
 
 use pumpkin_data::block::Block;
@@ -1,11 +1,18 @@
+pub mod player_afk_change;
+pub mod player_bucket_empty;
+pub mod player_bucket_fill;
 pub mod player_change_world;
 pub mod player_chat;
 pub mod player_command_send;
+pub mod player_disconnect;
 pub mod player_gamemode_change;
 pub mod player_join;
 pub mod player_leave;
 pub mod player_login;
 pub mod player_move;
+pub mod player_pre_chat;
+pub mod player_pre_login;
+pub mod player_profile_fetched;
 pub mod player_teleport;
 
 use std::sync::Arc;
@@ -0,0 +1,44 @@
+use pumpkin_macros::{Event, cancellable};
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::PlayerEvent;
+
+/// An event that occurs when a player becomes AFK or comes back.
+///
+/// Cancelling it keeps the current state. A player is marked as AFK after some time without
+/// activity, or by a plugin.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerAfkChangeEvent {
+    /// The player whose AFK state is changing.
+    pub player: Arc<Player>,
+
+    /// Whether the player becomes AFK (`true`) or comes back (`false`).
+    pub afk: bool,
+}
+
+impl PlayerAfkChangeEvent {
+    /// Creates a new instance of `PlayerAfkChangeEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: A reference to the player whose AFK state is changing.
+    /// - `afk`: Whether the player becomes AFK.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerAfkChangeEvent`.
+    pub fn new(player: Arc<Player>, afk: bool) -> Self {
+        Self {
+            player,
+            afk,
+            cancelled: false,
+        }
+    }
+}
+
+impl PlayerEvent for PlayerAfkChangeEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
@@ -0,0 +1,37 @@
+use pumpkin_data::block::Block;
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::PlayerEvent;
+
+/// An event that occurs when a player empties a bucket, placing its fluid.
+///
+/// If the event is cancelled, nothing is placed and the player keeps the filled bucket.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerBucketEmptyEvent {
+    /// The player emptying the bucket.
+    pub player: Arc<Player>,
+
+    /// The position the fluid is placed at.
+    pub position: BlockPos,
+
+    /// The exact state of the block the fluid replaces.
+    pub state_id: u16,
+
+    /// The fluid that is placed.
+    pub fluid: Block,
+
+    /// The bucket that is being emptied.
+    pub item: ItemStack,
+}
+
+impl PlayerEvent for PlayerBucketEmptyEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
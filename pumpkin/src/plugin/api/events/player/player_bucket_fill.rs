@@ -0,0 +1,37 @@
+use pumpkin_data::item::Item;
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::PlayerEvent;
+
+/// An event that occurs when a player fills a bucket from a fluid source.
+///
+/// If the event is cancelled, the fluid stays and the player keeps the empty bucket.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerBucketFillEvent {
+    /// The player filling the bucket.
+    pub player: Arc<Player>,
+
+    /// The position of the fluid source.
+    pub position: BlockPos,
+
+    /// The exact state of the fluid source.
+    pub state_id: u16,
+
+    /// The bucket that is being filled.
+    pub item: ItemStack,
+
+    /// The filled bucket the player gets.
+    pub result: Item,
+}
+
+impl PlayerEvent for PlayerBucketFillEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
@@ -0,0 +1,39 @@
+use pumpkin_macros::Event;
+use std::sync::Arc;
+
+use crate::{entity::player::Player, net::disconnect::DisconnectReason};
+
+use super::PlayerEvent;
+
+/// An event that occurs after the connection of a player has ended, before the player is removed
+/// from the world.
+///
+/// This event can not be cancelled, the connection is already gone.
+#[derive(Event, Clone)]
+pub struct PlayerDisconnectEvent {
+    /// The player who disconnected.
+    pub player: Arc<Player>,
+
+    /// Why the connection ended.
+    pub reason: DisconnectReason,
+}
+
+impl PlayerDisconnectEvent {
+    /// Creates a new instance of `PlayerDisconnectEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: A reference to the player who disconnected.
+    /// - `reason`: Why the connection ended.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerDisconnectEvent`.
+    pub fn new(player: Arc<Player>, reason: DisconnectReason) -> Self {
+        Self { player, reason }
+    }
+}
+
+impl PlayerEvent for PlayerDisconnectEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
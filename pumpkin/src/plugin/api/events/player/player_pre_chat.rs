@@ -0,0 +1,44 @@
+use pumpkin_macros::{Event, cancellable};
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::PlayerEvent;
+
+/// An event that occurs when a player sends a chat message, before the chat filters run.
+///
+/// Changing the message here means the filters check the changed message. Cancelling it drops
+/// the message without telling the player.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerPreChatEvent {
+    /// The player who sent the message.
+    pub player: Arc<Player>,
+
+    /// The message as sent by the player.
+    pub message: String,
+}
+
+impl PlayerPreChatEvent {
+    /// Creates a new instance of `PlayerPreChatEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: A reference to the player sending the message.
+    /// - `message`: The message being sent.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerPreChatEvent`.
+    pub fn new(player: Arc<Player>, message: String) -> Self {
+        Self {
+            player,
+            message,
+            cancelled: false,
+        }
+    }
+}
+
+impl PlayerEvent for PlayerPreChatEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
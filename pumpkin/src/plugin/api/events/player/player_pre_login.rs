@@ -0,0 +1,42 @@
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::text::TextComponent;
+use std::net::IpAddr;
+
+/// An event that occurs when a client starts logging in, before its profile is fetched.
+///
+/// If the event is cancelled, the client will be disconnected with the kick message.
+///
+/// Only the name the client sent and its address are known at this point, so this is the place
+/// to deny logins by IP before doing any authentication.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerPreLoginEvent {
+    /// The name the client wants to log in with.
+    pub name: String,
+
+    /// The IP address of the client, behind a proxy this is the address of the proxy.
+    pub address: IpAddr,
+
+    /// The kick message to display if the event is cancelled.
+    pub kick_message: TextComponent,
+}
+
+impl PlayerPreLoginEvent {
+    /// Creates a new instance of `PlayerPreLoginEvent`.
+    ///
+    /// # Arguments
+    /// - `name`: The name the client wants to log in with.
+    /// - `address`: The IP address of the client.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerPreLoginEvent`.
+    #[must_use]
+    pub fn new(name: String, address: IpAddr) -> Self {
+        Self {
+            name,
+            address,
+            kick_message: TextComponent::text("You are not allowed to join this server"),
+            cancelled: false,
+        }
+    }
+}
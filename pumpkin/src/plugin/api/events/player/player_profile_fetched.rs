@@ -0,0 +1,50 @@
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::text::TextComponent;
+use std::{collections::HashMap, net::IpAddr};
+
+use crate::net::GameProfile;
+
+/// An event that occurs once the profile of a logging in client is known, after authenticating
+/// it with Mojang in online mode or through the proxy.
+///
+/// If the event is cancelled, the client will be disconnected with the kick message.
+///
+/// Handlers can change the profile the player joins with, which lets offline mode servers check
+/// the player against their own auth database, and attach data to the connection which stays
+/// available as `Client::login_data`, for example in the `PlayerLoginEvent`.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerProfileFetchedEvent {
+    /// The profile the player will join with.
+    pub profile: GameProfile,
+
+    /// The IP address of the client.
+    pub address: IpAddr,
+
+    /// Data attached to the connection by plugins.
+    pub data: HashMap<String, String>,
+
+    /// The kick message to display if the event is cancelled.
+    pub kick_message: TextComponent,
+}
+
+impl PlayerProfileFetchedEvent {
+    /// Creates a new instance of `PlayerProfileFetchedEvent`.
+    ///
+    /// # Arguments
+    /// - `profile`: The profile the player will join with.
+    /// - `address`: The IP address of the client.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerProfileFetchedEvent`.
+    #[must_use]
+    pub fn new(profile: GameProfile, address: IpAddr) -> Self {
+        Self {
+            profile,
+            address,
+            data: HashMap::new(),
+            kick_message: TextComponent::text("You are not allowed to join this server"),
+            cancelled: false,
+        }
+    }
+}
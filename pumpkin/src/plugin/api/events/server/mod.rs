@@ -2,3 +2,5 @@ pub mod server_broadcast;
 pub mod server_command;
 pub mod server_plugin_disable;
 pub mod server_plugin_enable;
+pub mod server_started;
+pub mod server_stop;
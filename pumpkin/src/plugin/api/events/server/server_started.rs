@@ -0,0 +1,24 @@
+use std::net::SocketAddr;
+
+use pumpkin_macros::Event;
+
+/// An event that occurs when the server has started and accepts connections.
+#[derive(Event, Clone)]
+pub struct ServerStartedEvent {
+    /// The address the server listens on.
+    pub address: SocketAddr,
+}
+
+impl ServerStartedEvent {
+    /// Creates a new instance of `ServerStartedEvent`.
+    ///
+    /// # Arguments
+    /// * `address` - The address the server listens on.
+    ///
+    /// # Returns
+    /// A new instance of `ServerStartedEvent`.
+    #[must_use]
+    pub fn new(address: SocketAddr) -> Self {
+        Self { address }
+    }
+}
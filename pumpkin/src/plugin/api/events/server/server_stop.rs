@@ -0,0 +1,7 @@
+use pumpkin_macros::Event;
+
+/// An event that occurs when the server stops accepting connections and is about to shut down.
+///
+/// The players are still online while this event is handled.
+#[derive(Event, Clone)]
+pub struct ServerStopEvent;
@@ -0,0 +1,448 @@
+//! The progress of players on the advancements loaded from the datapacks of the world, saved
+//! like vanilla does into `advancements/<uuid>.json`.
+//!
+//! Criteria are granted when the player does what their trigger describes. Only some triggers
+//! are supported, and criteria with conditions this doesn't understand are never granted rather
+//! than granted too early.
+
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use chrono::{DateTime, FixedOffset, Local};
+use pumpkin_data::item::Item;
+use pumpkin_data::tag::Tagable;
+use pumpkin_protocol::client::play::{
+    AdvancementEntry, AdvancementEntryDisplay, AdvancementProgressEntry, CUpdateAdvancements,
+};
+use pumpkin_protocol::codec::slot::Slot;
+use pumpkin_util::text::{TextComponent, color::NamedColor, hover::HoverEvent};
+use pumpkin_world::advancement::{Advancement, AdvancementFrame, Advancements, namespaced};
+use pumpkin_world::item::ItemStack;
+use pumpkin_world::world_info::MAXIMUM_SUPPORTED_WORLD_DATA_VERSION;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::Server;
+use crate::data::banlist_serializer::format;
+use crate::entity::player::Player;
+
+/// Something a player did, which grants the criteria with a matching trigger
+pub enum Trigger<'a> {
+    /// Happens every tick
+    Tick,
+    /// The items in the inventory of the player, checked once a second
+    InventoryChanged(&'a [ItemStack]),
+    /// The player finished eating or drinking the item
+    ConsumeItem(&'a ItemStack),
+    /// The player went from one dimension to another, like `minecraft:the_nether`
+    ChangedDimension { from: &'a str, to: &'a str },
+}
+
+impl Trigger<'_> {
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Tick => "minecraft:tick",
+            Self::InventoryChanged(_) => "minecraft:inventory_changed",
+            Self::ConsumeItem(_) => "minecraft:consume_item",
+            Self::ChangedDimension { .. } => "minecraft:changed_dimension",
+        }
+    }
+
+    /// Whether the conditions of a criterion match, unknown conditions never do
+    #[must_use]
+    pub fn matches(&self, conditions: Option<&Value>) -> bool {
+        let Some(conditions) = conditions else {
+            return true;
+        };
+        let Some(conditions) = conditions.as_object() else {
+            return false;
+        };
+        conditions
+            .iter()
+            .all(|(key, value)| match (self, key.as_str()) {
+                (Self::InventoryChanged(items), "items") => {
+                    value.as_array().is_some_and(|predicates| {
+                        predicates.iter().all(|predicate| {
+                            items.iter().any(|stack| item_matches(predicate, stack))
+                        })
+                    })
+                }
+                (Self::ConsumeItem(stack), "item") => item_matches(value, stack),
+                (Self::ChangedDimension { from, .. }, "from") => {
+                    value.as_str().is_some_and(|id| namespaced(id) == *from)
+                }
+                (Self::ChangedDimension { to, .. }, "to") => {
+                    value.as_str().is_some_and(|id| namespaced(id) == *to)
+                }
+                _ => false,
+            })
+    }
+}
+
+/// Whether the stack matches an item predicate, only the `items` and `count` of it are known
+fn item_matches(predicate: &Value, stack: &ItemStack) -> bool {
+    let Some(predicate) = predicate.as_object() else {
+        return false;
+    };
+    predicate.iter().all(|(key, value)| match key.as_str() {
+        "items" => {
+            let is = |id: &str| {
+                id.strip_prefix('#').map_or_else(
+                    || namespaced(id) == format!("minecraft:{}", stack.item.registry_key),
+                    |tag| stack.item.is_tagged_with(&namespaced(tag)) == Some(true),
+                )
+            };
+            match value {
+                Value::String(id) => is(id),
+                Value::Array(ids) => ids.iter().any(|id| id.as_str().is_some_and(is)),
+                _ => false,
+            }
+        }
+        "count" => {
+            let count = i64::from(stack.item_count);
+            match value {
+                Value::Number(exact) => exact.as_i64() == Some(count),
+                Value::Object(range) => {
+                    let bound = |name| range.get(name).and_then(Value::as_i64);
+                    bound("min").is_none_or(|min| count >= min)
+                        && bound("max").is_none_or(|max| count <= max)
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct Obtained(#[serde(with = "format::date")] DateTime<FixedOffset>);
+
+#[derive(Serialize, Deserialize, Default)]
+struct AdvancementProgress {
+    /// When each done criterion was done
+    #[serde(default)]
+    criteria: BTreeMap<String, Obtained>,
+    #[serde(default)]
+    done: bool,
+}
+
+/// Which criteria of which advancements a player has done
+#[derive(Serialize, Deserialize, Default)]
+pub struct PlayerAdvancements {
+    #[serde(rename = "DataVersion", default)]
+    data_version: i32,
+    #[serde(flatten)]
+    progress: BTreeMap<String, AdvancementProgress>,
+}
+
+impl PlayerAdvancements {
+    /// Starts without progress if the player has no file yet or it is broken
+    #[must_use]
+    pub fn load(path: &Path) -> Self {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                log::error!("Failed to read advancements from {}: {err}", path.display());
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&json).unwrap_or_else(|err| {
+            log::error!(
+                "Failed to parse advancements from {}: {err}",
+                path.display()
+            );
+            Self::default()
+        })
+    }
+
+    pub fn save(&mut self, path: &Path) -> io::Result<()> {
+        self.data_version = MAXIMUM_SUPPORTED_WORLD_DATA_VERSION;
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    #[must_use]
+    pub fn is_done(&self, id: &str) -> bool {
+        self.progress.get(id).is_some_and(|progress| progress.done)
+    }
+
+    #[must_use]
+    pub fn is_criterion_done(&self, id: &str, criterion: &str) -> bool {
+        self.progress
+            .get(id)
+            .is_some_and(|progress| progress.criteria.contains_key(criterion))
+    }
+
+    /// Returns whether this completed the advancement, `None` if the criterion was already done
+    pub fn grant(&mut self, id: &str, advancement: &Advancement, criterion: &str) -> Option<bool> {
+        let progress = self.progress.entry(id.to_string()).or_default();
+        if progress.criteria.contains_key(criterion) {
+            return None;
+        }
+        progress
+            .criteria
+            .insert(criterion.to_string(), Obtained(Local::now().fixed_offset()));
+        let was_done = progress.done;
+        progress.done = advancement.is_done(|criterion| progress.criteria.contains_key(criterion));
+        Some(progress.done && !was_done)
+    }
+
+    /// Returns false if the criterion wasn't done
+    pub fn revoke(&mut self, id: &str, advancement: &Advancement, criterion: &str) -> bool {
+        let Some(progress) = self.progress.get_mut(id) else {
+            return false;
+        };
+        if progress.criteria.remove(criterion).is_none() {
+            return false;
+        }
+        progress.done = advancement.is_done(|criterion| progress.criteria.contains_key(criterion));
+        if progress.criteria.is_empty() {
+            self.progress.remove(id);
+        }
+        true
+    }
+
+    /// The progress on the advancement the way the client gets it
+    #[must_use]
+    pub fn entry(&self, id: &str, advancement: &Advancement) -> AdvancementProgressEntry {
+        let progress = self.progress.get(id);
+        AdvancementProgressEntry {
+            id: id.to_string(),
+            criteria: advancement
+                .criteria
+                .keys()
+                .map(|criterion| {
+                    let obtained = progress.and_then(|progress| progress.criteria.get(criterion));
+                    (
+                        criterion.clone(),
+                        obtained.map(|obtained| obtained.0.timestamp_millis()),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The advancement the way the client gets it
+fn advancement_entry(
+    advancements: &Advancements,
+    id: &str,
+    advancement: &Advancement,
+) -> AdvancementEntry {
+    let display = advancement.display.as_ref().map(|display| {
+        let icon = display
+            .icon
+            .id
+            .strip_prefix("minecraft:")
+            .and_then(Item::from_registry_key)
+            .map(|item| ItemStack::new(display.icon.count, item));
+        let (x, y) = advancements.position(id);
+        AdvancementEntryDisplay {
+            title: display.title(),
+            description: display.description(),
+            icon: Slot::from(icon.as_ref()),
+            frame: (display.frame as i32).into(),
+            background: display.background.clone(),
+            show_toast: display.show_toast,
+            hidden: display.hidden,
+            x,
+            y,
+        }
+    });
+    AdvancementEntry {
+        id: id.to_string(),
+        parent: advancement.parent.clone(),
+        display,
+        requirements: advancement.requirements(),
+        sends_telemetry_event: advancement.sends_telemetry_event,
+    }
+}
+
+impl Player {
+    /// Sends every advancement and the progress on them, replacing what the client knew
+    pub async fn send_advancements(&self, server: &Server) {
+        let (added, progress): (Vec<_>, Vec<_>) = {
+            let player_advancements = self.advancements.lock().await;
+            server
+                .advancements
+                .iter()
+                .map(|(id, advancement)| {
+                    (
+                        advancement_entry(&server.advancements, id, advancement),
+                        player_advancements.entry(id, advancement),
+                    )
+                })
+                .unzip()
+        };
+        self.client
+            .send_packet(&CUpdateAdvancements::new(true, added, Vec::new(), progress))
+            .await;
+    }
+
+    /// Grants the criteria of every advancement the trigger matches
+    pub async fn trigger_advancements(&self, server: &Server, trigger: Trigger<'_>) {
+        let waiting = server.advancements.with_trigger(trigger.name());
+        if waiting.is_empty() {
+            return;
+        }
+        let matching: Vec<_> = {
+            let progress = self.advancements.lock().await;
+            waiting
+                .iter()
+                .filter(|(id, criterion)| !progress.is_criterion_done(id, criterion))
+                .filter(|(id, criterion)| {
+                    server
+                        .advancements
+                        .get(id)
+                        .and_then(|advancement| advancement.criteria.get(criterion))
+                        .is_some_and(|criterion| trigger.matches(criterion.conditions.as_ref()))
+                })
+                .cloned()
+                .collect()
+        };
+        for (id, criterion) in matching {
+            self.grant_criterion(server, &id, &criterion).await;
+        }
+    }
+
+    /// Returns false if the advancement or criterion doesn't exist or it was already done
+    pub async fn grant_criterion(&self, server: &Server, id: &str, criterion: &str) -> bool {
+        let Some(advancement) = server.advancements.get(id) else {
+            return false;
+        };
+        if !advancement.criteria.contains_key(criterion) {
+            return false;
+        }
+        let (completed, progress) = {
+            let mut progress = self.advancements.lock().await;
+            let Some(completed) = progress.grant(id, advancement, criterion) else {
+                return false;
+            };
+            (completed, progress.entry(id, advancement))
+        };
+        self.client
+            .send_packet(&CUpdateAdvancements::new(
+                false,
+                Vec::new(),
+                Vec::new(),
+                vec![progress],
+            ))
+            .await;
+        if completed {
+            self.complete_advancement(server, advancement).await;
+        }
+        true
+    }
+
+    /// Returns false if the advancement or criterion doesn't exist or it wasn't done
+    pub async fn revoke_criterion(&self, server: &Server, id: &str, criterion: &str) -> bool {
+        let Some(advancement) = server.advancements.get(id) else {
+            return false;
+        };
+        let progress = {
+            let mut progress = self.advancements.lock().await;
+            if !progress.revoke(id, advancement, criterion) {
+                return false;
+            }
+            progress.entry(id, advancement)
+        };
+        self.client
+            .send_packet(&CUpdateAdvancements::new(
+                false,
+                Vec::new(),
+                Vec::new(),
+                vec![progress],
+            ))
+            .await;
+        true
+    }
+
+    /// Gives the rewards and tells everyone about it, if the advancement wants that
+    async fn complete_advancement(&self, server: &Server, advancement: &Advancement) {
+        if advancement.rewards.experience > 0 {
+            self.add_experience_points(advancement.rewards.experience)
+                .await;
+        }
+        let Some(display) = &advancement.display else {
+            return;
+        };
+        if !display.announce_to_chat {
+            return;
+        }
+        let color = match display.frame {
+            AdvancementFrame::Challenge => NamedColor::DarkPurple,
+            AdvancementFrame::Task | AdvancementFrame::Goal => NamedColor::Green,
+        };
+        let tooltip = display
+            .title()
+            .color_named(color)
+            .add_text("\n")
+            .add_child(display.description());
+        let name = TextComponent::text("[")
+            .add_child(display.title())
+            .add_text("]")
+            .color_named(color)
+            .hover_event(HoverEvent::show_text(tooltip));
+        let message = TextComponent::translate(
+            format!("chat.type.advancement.{}", display.frame.name()),
+            [TextComponent::text(self.gameprofile.name.clone()), name],
+        );
+        for player in server.get_all_players().await {
+            player.send_system_message(&message).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::item::Item;
+    use pumpkin_world::item::ItemStack;
+    use serde_json::json;
+
+    use super::{PlayerAdvancements, Trigger};
+
+    #[test]
+    fn item_conditions() {
+        let logs = [ItemStack::new(3, Item::OAK_LOG)];
+        let trigger = Trigger::InventoryChanged(&logs);
+        assert!(trigger.matches(None));
+        assert!(trigger.matches(Some(&json!({"items": [{"items": "#minecraft:logs"}]}))));
+        assert!(trigger.matches(Some(
+            &json!({"items": [{"items": ["oak_log"], "count": 3}]})
+        )));
+        assert!(!trigger.matches(Some(&json!({"items": [{"items": "minecraft:stone"}]}))));
+        assert!(!trigger.matches(Some(&json!({"items": [{"count": {"min": 4}}]}))));
+        assert!(!trigger.matches(Some(&json!({"player": []}))));
+
+        let to_nether = Trigger::ChangedDimension {
+            from: "minecraft:overworld",
+            to: "minecraft:the_nether",
+        };
+        assert!(to_nether.matches(Some(&json!({"to": "the_nether"}))));
+        assert!(!to_nether.matches(Some(&json!({"from": "minecraft:the_end"}))));
+    }
+
+    #[test]
+    fn vanilla_format() {
+        let json = r#"{
+            "minecraft:story/root": {
+                "criteria": {"crafting_table": "2024-06-01 12:30:00 +0200"},
+                "done": true
+            },
+            "DataVersion": 4189
+        }"#;
+        let progress: PlayerAdvancements = serde_json::from_str(json).unwrap();
+        assert!(progress.is_done("minecraft:story/root"));
+        assert!(progress.is_criterion_done("minecraft:story/root", "crafting_table"));
+        assert!(!progress.is_criterion_done("minecraft:story/root", "other"));
+
+        let saved = serde_json::to_value(&progress).unwrap();
+        assert_eq!(
+            saved["minecraft:story/root"]["criteria"]["crafting_table"],
+            "2024-06-01 12:30:00 +0200"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Boss bars which belong to something happening in the game, like raids, the dragon fight or a
+//! plugin, instead of being made with `/bossbar`. They are neither listed by `/bossbar` nor saved,
+//! whoever added one is in charge of updating and removing it.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use pumpkin_util::text::TextComponent;
+use uuid::Uuid;
+
+use crate::{
+    entity::player::Player,
+    world::bossbar::{Bossbar, BossbarColor, BossbarDivisions, BossbarFlags},
+};
+
+use super::Server;
+
+#[derive(Default)]
+pub struct BossEvents {
+    /// The boss bars by their UUID
+    events: HashMap<Uuid, BossEvent>,
+}
+
+struct BossEvent {
+    bossbar: Bossbar,
+    /// The players who see the boss bar while it's visible
+    players: HashSet<Uuid>,
+    visible: bool,
+}
+
+impl BossEvents {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a boss bar nobody sees yet, it is updated and removed by its UUID
+    pub fn add(&mut self, bossbar: Bossbar) -> Uuid {
+        let uuid = bossbar.uuid;
+        self.events.insert(
+            uuid,
+            BossEvent {
+                bossbar,
+                players: HashSet::new(),
+                visible: true,
+            },
+        );
+        uuid
+    }
+
+    /// Removes the boss bar from everyone seeing it. Returns false if there is none
+    pub async fn remove(&mut self, server: &Server, uuid: Uuid) -> bool {
+        let Some(event) = self.events.remove(&uuid) else {
+            return false;
+        };
+        if event.visible {
+            for player in event.online_players(server).await {
+                player.remove_bossbar(uuid).await;
+            }
+        }
+        true
+    }
+
+    #[must_use]
+    pub fn get(&self, uuid: Uuid) -> Option<&Bossbar> {
+        self.events.get(&uuid).map(|event| &event.bossbar)
+    }
+
+    /// The players who see the boss bar while it's visible
+    #[must_use]
+    pub fn players(&self, uuid: Uuid) -> Option<&HashSet<Uuid>> {
+        self.events.get(&uuid).map(|event| &event.players)
+    }
+
+    /// Shows the boss bar to the player. Returns false if there is none or the player already
+    /// sees it
+    pub async fn add_player(&mut self, uuid: Uuid, player: &Player) -> bool {
+        let Some(event) = self.events.get_mut(&uuid) else {
+            return false;
+        };
+        if !event.players.insert(player.gameprofile.id) {
+            return false;
+        }
+        if event.visible {
+            player.send_bossbar(&event.bossbar).await;
+        }
+        true
+    }
+
+    /// Returns false if there is no boss bar or the player did not see it
+    pub async fn remove_player(&mut self, uuid: Uuid, player: &Player) -> bool {
+        let Some(event) = self.events.get_mut(&uuid) else {
+            return false;
+        };
+        if !event.players.remove(&player.gameprofile.id) {
+            return false;
+        }
+        if event.visible {
+            player.remove_bossbar(uuid).await;
+        }
+        true
+    }
+
+    /// Sets how full the boss bar is, between 0 and 1
+    pub async fn set_progress(&mut self, server: &Server, uuid: Uuid, progress: f32) -> bool {
+        let Some(event) = self.events.get_mut(&uuid) else {
+            return false;
+        };
+        event.bossbar.health = progress.clamp(0.0, 1.0);
+        for player in event.shown_to(server).await {
+            player
+                .update_bossbar_health(&uuid, event.bossbar.health)
+                .await;
+        }
+        true
+    }
+
+    pub async fn set_title(&mut self, server: &Server, uuid: Uuid, title: TextComponent) -> bool {
+        let Some(event) = self.events.get_mut(&uuid) else {
+            return false;
+        };
+        event.bossbar.title = title;
+        for player in event.shown_to(server).await {
+            player
+                .update_bossbar_title(&uuid, event.bossbar.title.clone())
+                .await;
+        }
+        true
+    }
+
+    pub async fn set_style(
+        &mut self,
+        server: &Server,
+        uuid: Uuid,
+        color: BossbarColor,
+        division: BossbarDivisions,
+    ) -> bool {
+        let Some(event) = self.events.get_mut(&uuid) else {
+            return false;
+        };
+        event.bossbar.color = color;
+        event.bossbar.division = division;
+        for player in event.shown_to(server).await {
+            player
+                .update_bossbar_style(
+                    &uuid,
+                    event.bossbar.color.clone(),
+                    event.bossbar.division.clone(),
+                )
+                .await;
+        }
+        true
+    }
+
+    pub async fn set_flags(&mut self, server: &Server, uuid: Uuid, flags: BossbarFlags) -> bool {
+        let Some(event) = self.events.get_mut(&uuid) else {
+            return false;
+        };
+        event.bossbar.flags = flags;
+        for player in event.shown_to(server).await {
+            player
+                .update_bossbar_flags(&uuid, event.bossbar.flags.clone())
+                .await;
+        }
+        true
+    }
+
+    /// Hides the boss bar from its players while keeping them, or shows it again
+    pub async fn set_visible(&mut self, server: &Server, uuid: Uuid, visible: bool) -> bool {
+        let Some(event) = self.events.get_mut(&uuid) else {
+            return false;
+        };
+        if event.visible == visible {
+            return true;
+        }
+        event.visible = visible;
+        for player in event.online_players(server).await {
+            if visible {
+                player.send_bossbar(&event.bossbar).await;
+            } else {
+                player.remove_bossbar(uuid).await;
+            }
+        }
+        true
+    }
+
+    /// Sends the boss bars the player sees again after changing worlds
+    pub async fn init_player(&self, player: &Player) {
+        for event in self.events.values() {
+            if event.visible && event.players.contains(&player.gameprofile.id) {
+                player.send_bossbar(&event.bossbar).await;
+            }
+        }
+    }
+
+    /// Stops showing any boss bar to a player who left
+    pub fn forget(&mut self, player: Uuid) {
+        for event in self.events.values_mut() {
+            event.players.remove(&player);
+        }
+    }
+}
+
+impl BossEvent {
+    async fn online_players(&self, server: &Server) -> Vec<Arc<Player>> {
+        let mut players = Vec::with_capacity(self.players.len());
+        for uuid in &self.players {
+            if let Some(player) = server.get_player_by_uuid(*uuid).await {
+                players.push(player);
+            }
+        }
+        players
+    }
+
+    /// The online players who currently see the boss bar
+    async fn shown_to(&self, server: &Server) -> Vec<Arc<Player>> {
+        if self.visible {
+            self.online_players(server).await
+        } else {
+            Vec::new()
+        }
+    }
+}
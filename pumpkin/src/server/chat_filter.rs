@@ -0,0 +1,106 @@
+use std::sync::LazyLock;
+
+use pumpkin_config::{ChatFilterAction, ChatFilterConfig, advanced_config};
+use regex::Regex;
+
+pub static CHAT_FILTERS: LazyLock<ChatFilters> =
+    LazyLock::new(|| ChatFilters::new(&advanced_config().chat.filters));
+
+/// The chat filters from the config, compiled once
+pub struct ChatFilters {
+    filters: Vec<(Regex, ChatFilterAction)>,
+}
+
+/// What the filters made of a message
+#[derive(Debug, PartialEq, Eq)]
+pub struct FilteredMessage {
+    /// The message with censored parts replaced
+    pub message: String,
+    /// The strictest action of all matching filters, `None` if nothing matched
+    pub action: Option<ChatFilterAction>,
+}
+
+impl ChatFilters {
+    #[must_use]
+    pub fn new(config: &[ChatFilterConfig]) -> Self {
+        let filters = config
+            .iter()
+            .filter_map(|filter| {
+                let pattern = if filter.regex {
+                    filter.pattern.clone()
+                } else {
+                    format!(r"(?i)\b{}\b", regex::escape(&filter.pattern))
+                };
+                match Regex::new(&pattern) {
+                    Ok(regex) => Some((regex, filter.action)),
+                    Err(err) => {
+                        log::warn!("Ignoring invalid chat filter {:?}: {err}", filter.pattern);
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self { filters }
+    }
+
+    #[must_use]
+    pub fn apply(&self, message: &str) -> FilteredMessage {
+        let mut filtered = FilteredMessage {
+            message: message.to_string(),
+            action: None,
+        };
+        for (regex, action) in &self.filters {
+            if !regex.is_match(&filtered.message) {
+                continue;
+            }
+            filtered.action = filtered.action.max(Some(*action));
+            if *action == ChatFilterAction::Censor {
+                filtered.message = regex
+                    .replace_all(&filtered.message, |captures: &regex::Captures| {
+                        "*".repeat(captures[0].chars().count())
+                    })
+                    .into_owned();
+            }
+        }
+        filtered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_config::{ChatFilterAction, ChatFilterConfig};
+
+    use super::ChatFilters;
+
+    fn filter(pattern: &str, regex: bool, action: ChatFilterAction) -> ChatFilterConfig {
+        ChatFilterConfig {
+            pattern: pattern.to_string(),
+            regex,
+            action,
+        }
+    }
+
+    #[test]
+    fn censors_whole_words_only() {
+        let filters = ChatFilters::new(&[filter("heck", false, ChatFilterAction::Censor)]);
+        let filtered = filters.apply("Heck, what the heck? Checkers!");
+        assert_eq!(filtered.message, "****, what the ****? Checkers!");
+        assert_eq!(filtered.action, Some(ChatFilterAction::Censor));
+
+        assert_eq!(filters.apply("all fine").action, None);
+    }
+
+    #[test]
+    fn strictest_action_wins() {
+        let filters = ChatFilters::new(&[
+            filter("darn", false, ChatFilterAction::Warn),
+            filter(r"discord\.gg/\w+", true, ChatFilterAction::Block),
+            filter("([", true, ChatFilterAction::Block),
+        ]);
+        assert_eq!(filters.apply("darn").action, Some(ChatFilterAction::Warn));
+        assert_eq!(
+            filters.apply("darn, join discord.gg/abc").action,
+            Some(ChatFilterAction::Block)
+        );
+    }
+}
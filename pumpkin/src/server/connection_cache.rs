@@ -1,5 +1,6 @@
 use core::error;
 use std::{
+    collections::HashMap,
     fs::File,
     io::{Cursor, Read},
     num::NonZeroU32,
@@ -7,7 +8,9 @@ use std::{
 };
 
 use base64::{Engine as _, engine::general_purpose};
-use pumpkin_config::{BASIC_CONFIG, BasicConfiguration};
+use pumpkin_config::{
+    BASIC_CONFIG, BasicConfiguration, VirtualHostConfig, advanced_config, normalize_hostname,
+};
 use pumpkin_protocol::{
     CURRENT_MC_PROTOCOL, Players, StatusResponse, Version,
     client::{config::CPluginMessage, status::CStatusResponse},
@@ -15,6 +18,7 @@ use pumpkin_protocol::{
 };
 
 use super::CURRENT_MC_VERSION;
+use super::motd::Motds;
 
 const DEFAULT_ICON: &[u8] = include_bytes!("../../../assets/default_icon.png");
 
@@ -44,6 +48,41 @@ pub struct CachedStatus {
     // We cache the json response here so we don't parse it every time someone makes a Status request.
     // Keep in mind that we must parse this again, when the StatusResponse changes which usually happen when a player joins or leaves
     status_response_json: String,
+    /// The players counted as online, including the ones in reserved slots
+    online: u32,
+    motds: Motds,
+    /// The virtual hosts by their normalized hostname
+    hosts: HashMap<String, HostStatus>,
+    /// The last response built for a virtual host, they are not cached
+    host_response_json: String,
+}
+
+/// What differs in the status of a virtual host
+struct HostStatus {
+    /// `None` to use the server's MOTDs
+    motds: Option<Motds>,
+    favicon: Option<String>,
+}
+
+impl HostStatus {
+    fn new(hostname: &str, config: &VirtualHostConfig) -> Self {
+        let motds = (!config.motds.is_empty()).then(|| {
+            Motds::new(
+                config.motds.clone(),
+                advanced_config().motd.full_motds.clone(),
+                advanced_config().motd.tips.clone(),
+                &BASIC_CONFIG.motd,
+            )
+        });
+        let favicon = config.favicon_path.as_ref().and_then(|path| {
+            load_icon_from_file(path)
+                .map_err(|err| {
+                    log::error!("Unable to load the favicon of {hostname} at '{path}': {err}");
+                })
+                .ok()
+        });
+        Self { motds, favicon }
+    }
 }
 
 pub struct CachedBranding {
@@ -78,31 +117,82 @@ impl CachedStatus {
         let status_response_json = serde_json::to_string(&status_response)
             .expect("Failed to parse Status response into JSON");
 
+        let config = &advanced_config().motd;
         Self {
             status_response,
             status_response_json,
+            online: 0,
+            motds: Motds::new(
+                config.motds.clone(),
+                config.full_motds.clone(),
+                config.tips.clone(),
+                &BASIC_CONFIG.motd,
+            ),
+            hosts: BASIC_CONFIG
+                .virtual_hosts
+                .iter()
+                .map(|(hostname, host)| {
+                    (
+                        normalize_hostname(hostname),
+                        HostStatus::new(hostname, host),
+                    )
+                })
+                .collect(),
+            host_response_json: String::new(),
         }
     }
 
-    pub fn get_status(&self) -> CStatusResponse<'_> {
+    /// `address` is the server address from the client's handshake, it picks the virtual host
+    /// and fills in the `{hostname}` placeholder
+    pub fn get_status(&mut self, address: &str) -> CStatusResponse<'_> {
+        let hostname = normalize_hostname(address);
+        let (online, max) = self
+            .status_response
+            .players
+            .as_ref()
+            .map_or((0, 0), |players| (players.online, players.max));
+
+        if let Some(host) = self.hosts.get_mut(&hostname) {
+            let motds = host.motds.as_mut().unwrap_or(&mut self.motds);
+            let mut response = self.status_response.clone();
+            response.description = motds.next(online, max, &hostname);
+            if host.favicon.is_some() {
+                response.favicon.clone_from(&host.favicon);
+            }
+            self.host_response_json = serde_json::to_string(&response)
+                .expect("Failed to parse Status response into JSON");
+            return CStatusResponse::new(&self.host_response_json);
+        }
+
+        if !self.motds.is_static() {
+            self.status_response.description = self.motds.next(online, max, &hostname);
+            self.status_response_json = serde_json::to_string(&self.status_response)
+                .expect("Failed to parse Status response into JSON");
+        }
         CStatusResponse::new(&self.status_response_json)
     }
 
     // TODO: Player samples
     pub fn add_player(&mut self) {
-        let status_response = &mut self.status_response;
-        if let Some(players) = &mut status_response.players {
-            players.online += 1;
-        }
-
-        self.status_response_json = serde_json::to_string(&status_response)
-            .expect("Failed to parse Status response into JSON");
+        self.online += 1;
+        self.update_online();
     }
 
     pub fn remove_player(&mut self) {
+        self.online = self.online.saturating_sub(1);
+        self.update_online();
+    }
+
+    /// Players in reserved slots are left out if they are hidden
+    fn update_online(&mut self) {
         let status_response = &mut self.status_response;
         if let Some(players) = &mut status_response.players {
-            players.online -= 1;
+            players.online =
+                if advanced_config().slots.hide_reserved && BASIC_CONFIG.max_players > 0 {
+                    self.online.min(BASIC_CONFIG.max_players)
+                } else {
+                    self.online
+                };
         }
 
         self.status_response_json = serde_json::to_string(&status_response)
@@ -144,13 +234,21 @@ impl CachedStatus {
             None
         };
 
+        let slots = &advanced_config().slots;
+        // Reserved slots can only be added to a limit
+        let max = if slots.hide_reserved || config.max_players == 0 {
+            config.max_players
+        } else {
+            config.max_players.saturating_add(slots.reserved_slots)
+        };
+
         StatusResponse {
             version: Some(Version {
                 name: CURRENT_MC_VERSION.into(),
                 protocol: NonZeroU32::from(CURRENT_MC_PROTOCOL).get(),
             }),
             players: Some(Players {
-                max: config.max_players,
+                max,
                 online: 0,
                 sample: vec![],
             }),
@@ -0,0 +1,250 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use pumpkin_config::HomeLimit;
+use pumpkin_data::tag::Tagable;
+use pumpkin_util::PermissionLvl;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use uuid::Uuid;
+
+use crate::world::World;
+
+/// Saved in the `data` folder of the default world
+pub const HOMES_FILE: &str = "homes.json";
+pub const WARPS_FILE: &str = "warps.json";
+
+/// Blocks players should not be teleported onto, besides the ones tagged as fire
+const DANGEROUS_GROUND: [&str; 4] = ["magma_block", "cactus", "sweet_berry_bush", "powder_snow"];
+
+/// A place players can teleport back to later
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SavedLocation {
+    /// The name of the dimension type, like `minecraft:overworld`
+    pub dimension: String,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl SavedLocation {
+    #[must_use]
+    pub fn position(&self) -> Vector3<f64> {
+        Vector3::new(self.x, self.y, self.z)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HomeError {
+    /// The player already has as many homes as they may have
+    TooMany(u32),
+}
+
+/// The homes of every player, by their uuid and the name of the home
+#[derive(Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct Homes {
+    homes: BTreeMap<Uuid, BTreeMap<String, SavedLocation>>,
+}
+
+impl Homes {
+    #[must_use]
+    pub fn load(path: &Path) -> Self {
+        load(path, "homes")
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        save(path, self)
+    }
+
+    #[must_use]
+    pub fn get(&self, player: Uuid, name: &str) -> Option<&SavedLocation> {
+        self.homes.get(&player)?.get(name)
+    }
+
+    /// The names of the player's homes, sorted
+    #[must_use]
+    pub fn names(&self, player: Uuid) -> Vec<&str> {
+        self.homes
+            .get(&player)
+            .map(|homes| homes.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Overwriting an existing home never counts against the limit, `None` is unlimited
+    pub fn set(
+        &mut self,
+        player: Uuid,
+        name: &str,
+        location: SavedLocation,
+        limit: Option<u32>,
+    ) -> Result<(), HomeError> {
+        let homes = self.homes.entry(player).or_default();
+        if let Some(limit) = limit {
+            if !homes.contains_key(name) && homes.len() >= limit as usize {
+                return Err(HomeError::TooMany(limit));
+            }
+        }
+        homes.insert(name.to_string(), location);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, player: Uuid, name: &str) -> Option<SavedLocation> {
+        let homes = self.homes.get_mut(&player)?;
+        let removed = homes.remove(name);
+        if homes.is_empty() {
+            self.homes.remove(&player);
+        }
+        removed
+    }
+}
+
+/// The server-wide warps, by their name
+#[derive(Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct Warps {
+    warps: BTreeMap<String, SavedLocation>,
+}
+
+impl Warps {
+    #[must_use]
+    pub fn load(path: &Path) -> Self {
+        load(path, "warps")
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        save(path, self)
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&SavedLocation> {
+        self.warps.get(name)
+    }
+
+    /// The names of all warps, sorted
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.warps.keys().map(String::as_str).collect()
+    }
+
+    pub fn set(&mut self, name: &str, location: SavedLocation) {
+        self.warps.insert(name.to_string(), location);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<SavedLocation> {
+        self.warps.remove(name)
+    }
+}
+
+fn load<T: DeserializeOwned + Default>(path: &Path, what: &str) -> T {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+            log::error!("Failed to parse the {what} at {}: {err}", path.display());
+            T::default()
+        }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => T::default(),
+        Err(err) => {
+            log::error!("Failed to read the {what} at {}: {err}", path.display());
+            T::default()
+        }
+    }
+}
+
+fn save<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(value)?)
+}
+
+/// How many homes a player with the permission level may have, `None` is unlimited. The entry
+/// with the highest level up to the player's counts, without one players can't set any homes
+#[must_use]
+pub fn home_limit(limits: &[HomeLimit], level: PermissionLvl) -> Option<u32> {
+    let level = level as u8;
+    let limit = limits
+        .iter()
+        .filter(|limit| limit.permission_level <= level)
+        .max_by_key(|limit| limit.permission_level);
+    match limit {
+        Some(limit) if limit.max_homes == 0 => None,
+        Some(limit) => Some(limit.max_homes),
+        None => Some(0),
+    }
+}
+
+/// Whether a player can stand at the position without suffocating, drowning or burning: the
+/// feet and head have to be free and the ground solid
+pub async fn is_safe(world: &World, position: Vector3<f64>) -> bool {
+    let feet = BlockPos::floored(position.x, position.y, position.z);
+    let head = BlockPos(feet.0.add(&Vector3::new(0, 1, 0)));
+    let ground = BlockPos(feet.0.sub(&Vector3::new(0, 1, 0)));
+
+    for position in [feet, head] {
+        match world.get_block_and_block_state(&position).await {
+            Ok((block, state)) => {
+                let fire = block.is_tagged_with("minecraft:fire").unwrap_or_default();
+                if !state.collision_shapes.is_empty() || state.is_liquid || fire {
+                    return false;
+                }
+            }
+            Err(_) => return false,
+        }
+    }
+    match world.get_block_and_block_state(&ground).await {
+        Ok((block, state)) => {
+            let fire = block.is_tagged_with("minecraft:fire").unwrap_or_default();
+            !state.collision_shapes.is_empty()
+                && !state.is_liquid
+                && !fire
+                && !DANGEROUS_GROUND.contains(&block.name)
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location() -> SavedLocation {
+        SavedLocation {
+            dimension: "minecraft:overworld".into(),
+            x: 0.5,
+            y: 64.0,
+            z: 0.5,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+
+    #[test]
+    fn limits() {
+        let limits = [
+            HomeLimit {
+                permission_level: 0,
+                max_homes: 1,
+            },
+            HomeLimit {
+                permission_level: 4,
+                max_homes: 0,
+            },
+        ];
+        assert_eq!(home_limit(&limits, PermissionLvl::Two), Some(1));
+        assert_eq!(home_limit(&limits, PermissionLvl::Four), None);
+        assert_eq!(home_limit(&limits[1..], PermissionLvl::Zero), Some(0));
+
+        let player = Uuid::new_v4();
+        let mut homes = Homes::default();
+        assert_eq!(homes.set(player, "base", location(), Some(1)), Ok(()));
+        assert_eq!(
+            homes.set(player, "mine", location(), Some(1)),
+            Err(HomeError::TooMany(1))
+        );
+        // Moving a home is always fine
+        assert_eq!(homes.set(player, "base", location(), Some(1)), Ok(()));
+        assert!(homes.remove(player, "base").is_some());
+        assert!(homes.names(player).is_empty());
+    }
+}
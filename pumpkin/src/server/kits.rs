@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use pumpkin_config::{Kit, KitItem, advanced_config};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_world::item::ItemStack;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+
+/// When players last claimed each kit, cooldowns start over when the server restarts
+#[derive(Default)]
+pub struct KitCooldowns {
+    last_claimed: HashMap<(Uuid, String), Instant>,
+}
+
+impl KitCooldowns {
+    /// Remembers the player claimed the kit, unless they still have to wait this long
+    pub fn claim(&mut self, player: Uuid, kit: &Kit, now: Instant) -> Result<(), Duration> {
+        let cooldown = Duration::from_secs(kit.cooldown_seconds);
+        let key = (player, kit.name.clone());
+        if let Some(last) = self.last_claimed.get(&key) {
+            let ready = *last + cooldown;
+            if ready > now {
+                return Err(ready - now);
+            }
+        }
+        if !cooldown.is_zero() {
+            self.last_claimed.insert(key, now);
+        }
+        Ok(())
+    }
+}
+
+/// Converts the components written in the config, integers become ints where they fit
+fn to_nbt(value: &Value) -> Option<NbtTag> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(bool) => NbtTag::Byte(i8::from(*bool)),
+        Value::Number(number) => match number.as_i64() {
+            Some(int) => i32::try_from(int).map_or(NbtTag::Long(int), NbtTag::Int),
+            None => NbtTag::Double(number.as_f64()?),
+        },
+        Value::String(string) => NbtTag::String(string.clone()),
+        Value::Array(values) => NbtTag::List(values.iter().filter_map(to_nbt).collect()),
+        Value::Object(map) => {
+            let mut compound = NbtCompound::new();
+            for (name, value) in map {
+                if let Some(tag) = to_nbt(value) {
+                    compound.put(name, tag);
+                }
+            }
+            NbtTag::Compound(compound)
+        }
+    })
+}
+
+/// Reads the item with the same component serialization used for saving items, `None` for
+/// unknown items
+#[must_use]
+pub fn kit_stack(item: &KitItem) -> Option<ItemStack> {
+    let mut compound = NbtCompound::new();
+    compound.put_string("id", item.id.clone());
+    compound.put_int("count", i32::from(item.count));
+    if let Some(components) = &item.components {
+        if let Some(tag) = to_nbt(&Value::Object(components.clone())) {
+            compound.put("components", tag);
+        }
+    }
+    ItemStack::read_item_nbt(&compound)
+}
+
+/// Puts the items of the kit into the player's inventory
+pub async fn give_kit(player: &Player, kit: &Kit) {
+    for item in &kit.items {
+        match kit_stack(item) {
+            Some(stack) => player.give_stack(stack).await,
+            None => log::warn!("The kit {} has an unknown item {}", kit.name, item.id),
+        }
+    }
+}
+
+/// Gives the kit configured as the starter kit to a player who joined for the first time
+pub async fn give_starter_kit(player: &Player) {
+    let config = &advanced_config().kits;
+    if !config.enabled || config.starter_kit.is_empty() {
+        return;
+    }
+    match config
+        .kits
+        .iter()
+        .find(|kit| kit.name == config.starter_kit)
+    {
+        Some(kit) => give_kit(player, kit).await,
+        None => log::warn!("The starter kit {} does not exist", config.starter_kit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::item::Item;
+    use pumpkin_data::potion::Potion;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn kit_items() {
+        let potion = KitItem {
+            id: "minecraft:splash_potion".into(),
+            count: 2,
+            components: json!({
+                "minecraft:potion_contents": { "potion": "minecraft:healing" }
+            })
+            .as_object()
+            .cloned(),
+        };
+        let stack = kit_stack(&potion).unwrap();
+        assert_eq!(stack.item_count, 2);
+        assert_eq!(
+            stack,
+            ItemStack::with_potion(2, Item::SPLASH_POTION, Potion::Healing)
+        );
+
+        let unknown = KitItem {
+            id: "minecraft:not_an_item".into(),
+            count: 1,
+            components: None,
+        };
+        assert!(kit_stack(&unknown).is_none());
+    }
+
+    #[test]
+    fn cooldown() {
+        let kit = Kit {
+            name: "food".into(),
+            items: Vec::new(),
+            cooldown_seconds: 60,
+            permission_level: 0,
+        };
+        let player = Uuid::new_v4();
+        let now = Instant::now();
+        let mut cooldowns = KitCooldowns::default();
+        assert_eq!(cooldowns.claim(player, &kit, now), Ok(()));
+        assert_eq!(
+            cooldowns.claim(player, &kit, now + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+        assert_eq!(
+            cooldowns.claim(player, &kit, now + Duration::from_secs(60)),
+            Ok(())
+        );
+    }
+}
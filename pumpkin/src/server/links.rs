@@ -0,0 +1,151 @@
+use pumpkin_config::ServerLinksConfig;
+use pumpkin_protocol::{Label, Link, LinkType};
+use pumpkin_util::text::TextComponent;
+
+/// A link in the pause menu of the client
+#[derive(Clone)]
+pub struct ServerLink {
+    pub label: Label,
+    pub url: String,
+    /// The operator permission level from 0 to 4 players need to see the link
+    pub permission_level: u8,
+}
+
+impl ServerLink {
+    pub fn built_in(link_type: LinkType, url: impl Into<String>) -> Self {
+        Self {
+            label: Label::BuiltIn(link_type),
+            url: url.into(),
+            permission_level: 0,
+        }
+    }
+
+    pub fn custom(label: TextComponent, url: impl Into<String>) -> Self {
+        Self {
+            label: Label::TextComponent(Box::new(label)),
+            url: url.into(),
+            permission_level: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn permission_level(mut self, permission_level: u8) -> Self {
+        self.permission_level = permission_level;
+        self
+    }
+}
+
+/// The links of the server, plugins may change them at runtime and resend them with
+/// `Server::send_links`
+#[derive(Default)]
+pub struct ServerLinks {
+    links: Vec<ServerLink>,
+}
+
+impl ServerLinks {
+    #[must_use]
+    pub fn from_config(config: &ServerLinksConfig) -> Self {
+        let mut links = Self::default();
+        if !config.enabled {
+            return links;
+        }
+
+        let built_in = [
+            (LinkType::BugReport, &config.bug_report),
+            (LinkType::CommunityGuidelines, &config.community_guidelines),
+            (LinkType::Support, &config.support),
+            (LinkType::Status, &config.status),
+            (LinkType::Feedback, &config.feedback),
+            (LinkType::Community, &config.community),
+            (LinkType::Website, &config.website),
+            (LinkType::Forums, &config.forums),
+            (LinkType::News, &config.news),
+            (LinkType::Announcements, &config.announcements),
+        ];
+        for (link_type, url) in built_in {
+            if !url.is_empty() {
+                links.add(ServerLink::built_in(link_type, url));
+            }
+        }
+        for (label, url) in &config.custom {
+            links.add(ServerLink::custom(TextComponent::text(label.clone()), url));
+        }
+        for link in &config.conditional {
+            links.add(
+                ServerLink::custom(TextComponent::text(link.label.clone()), &link.url)
+                    .permission_level(link.permission_level),
+            );
+        }
+        links
+    }
+
+    pub fn add(&mut self, link: ServerLink) {
+        self.links.push(link);
+    }
+
+    /// Removes every link to the URL, returns whether there was one
+    pub fn remove(&mut self, url: &str) -> bool {
+        let len = self.links.len();
+        self.links.retain(|link| link.url != url);
+        self.links.len() != len
+    }
+
+    pub fn clear(&mut self) {
+        self.links.clear();
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ServerLink> {
+        self.links.iter()
+    }
+
+    /// The links a player with the permission level gets to see
+    #[must_use]
+    pub fn visible_to(&self, permission_level: u8) -> Vec<Link<'_>> {
+        self.links
+            .iter()
+            .filter(|link| link.permission_level <= permission_level)
+            .map(|link| Link::new(link.label.clone(), &link.url))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_config::{ConditionalLink, ServerLinksConfig};
+
+    use super::*;
+
+    #[test]
+    fn links_from_config() {
+        let mut config = ServerLinksConfig {
+            website: "https://example.com".to_string(),
+            ..Default::default()
+        };
+        config
+            .custom
+            .insert("Map".to_string(), "https://map.example.com".to_string());
+        config.conditional.push(ConditionalLink {
+            label: "Staff".to_string(),
+            url: "https://staff.example.com".to_string(),
+            permission_level: 3,
+        });
+
+        let mut links = ServerLinks::from_config(&config);
+        assert_eq!(links.visible_to(0).len(), 3);
+        assert_eq!(links.visible_to(3).len(), 4);
+        assert!(links.visible_to(0)[0].is_built_in);
+        assert!(!links.visible_to(0)[2].is_built_in);
+
+        assert!(links.remove("https://map.example.com"));
+        assert!(!links.remove("https://map.example.com"));
+        assert_eq!(links.visible_to(4).len(), 3);
+
+        config.enabled = false;
+        assert!(ServerLinks::from_config(&config).is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    sync::{Arc, atomic::Ordering},
+    time::Duration,
+};
+
+use pumpkin_config::advanced_config;
+use pumpkin_world::level::LevelMemory;
+use tokio::time;
+
+use crate::{SHOULD_STOP, server::Server, world::World};
+
+/// What an entity is guessed to take up, most of its data is shared with the other entities
+const ENTITY_MEMORY: usize = 2 * 1024;
+/// Players also hold their inventory, the chunks they see and their connection
+const PLAYER_MEMORY: usize = 64 * 1024;
+/// How often the soft cap is checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+pub const MIB: usize = 1024 * 1024;
+
+/// The estimated memory held by the data of a world
+pub struct WorldMemory {
+    pub level: LevelMemory,
+    pub entities: usize,
+    pub players: usize,
+}
+
+impl WorldMemory {
+    pub async fn of(world: &World) -> Self {
+        Self {
+            level: world.level.memory_usage().await,
+            entities: world.entities.read().await.len(),
+            players: world.players.read().await.len(),
+        }
+    }
+
+    #[must_use]
+    pub const fn entity_bytes(&self) -> usize {
+        self.entities * ENTITY_MEMORY + self.players * PLAYER_MEMORY
+    }
+
+    #[must_use]
+    pub const fn total_bytes(&self) -> usize {
+        self.level.chunk_bytes + self.level.file_cache_bytes + self.entity_bytes()
+    }
+}
+
+/// The soft cap from the config in bytes, if there is one
+#[must_use]
+pub fn soft_cap() -> Option<usize> {
+    match advanced_config().chunk.memory_soft_cap_mb {
+        0 => None,
+        cap => Some(cap as usize * MIB),
+    }
+}
+
+/// Unloads the chunks nobody is in from all worlds when the estimated memory is above the soft
+/// cap, returns how many chunks were unloaded
+pub async fn enforce_soft_cap(server: &Server) -> usize {
+    let Some(cap) = soft_cap() else {
+        return 0;
+    };
+    let mut total = 0;
+    for world in server.worlds.iter() {
+        total += WorldMemory::of(world).await.total_bytes();
+    }
+    if total <= cap {
+        return 0;
+    }
+
+    let mut unloaded = 0;
+    for world in server.worlds.iter() {
+        unloaded += world.level.unload_unused_chunks().await;
+    }
+    log::warn!(
+        "Estimated memory of {} MiB is above the soft cap of {} MiB, unloaded {unloaded} chunks",
+        total / MIB,
+        cap / MIB
+    );
+    unloaded
+}
+
+/// Checks the soft cap from time to time until the server stops
+pub async fn watch_soft_cap(server: Arc<Server>) {
+    let mut interval = time::interval(CHECK_INTERVAL);
+    while !SHOULD_STOP.load(Ordering::Relaxed) {
+        interval.tick().await;
+        enforce_soft_cap(&server).await;
+    }
+}
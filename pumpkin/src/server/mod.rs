@@ -1,41 +1,77 @@
 use crate::block::registry::BlockRegistry;
 use crate::command::commands::default_dispatcher;
 use crate::command::commands::defaultgamemode::DefaultGamemode;
-use crate::entity::EntityId;
+use crate::data::whitelist_data::{self, WHITELIST, Whitelist};
+use crate::data::{LoadJSONConfiguration, SaveJSONConfiguration, known_players};
+use crate::entity::{EntityBase, EntityId, NBTStorage, ids::ENTITY_IDS};
 use crate::item::registry::ItemRegistry;
-use crate::net::EncryptionError;
+use crate::net::disconnect::DisconnectStats;
+use crate::net::{EncryptionError, GameProfile, outgoing};
 use crate::plugin::player::player_login::PlayerLoginEvent;
 use crate::plugin::server::server_broadcast::ServerBroadcastEvent;
 use crate::world::custom_bossbar::CustomBossbars;
+use crate::world::manager::WorldManager;
 use crate::{
     command::dispatcher::CommandDispatcher, entity::player::Player, net::Client, world::World,
 };
+use advancements::PlayerAdvancements;
+use boss_events::BossEvents;
 use connection_cache::{CachedBranding, CachedStatus};
+use homes::{Homes, Warps};
 use key_store::KeyStore;
+use kits::KitCooldowns;
+use links::ServerLinks;
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
-use pumpkin_data::block::Block;
+use pumpkin_data::block::{
+    Block, BlockProperties, Boolean, BrewingStandLikeProperties, FurnaceLikeProperties,
+};
 use pumpkin_inventory::drag_handler::DragHandler;
 use pumpkin_inventory::{Container, OpenContainer};
 use pumpkin_macros::send_cancellable;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_protocol::client::login::CEncryptionRequest;
-use pumpkin_protocol::{ClientPacket, client::config::CPluginMessage};
-use pumpkin_registry::{DimensionType, Registry};
+use pumpkin_protocol::{
+    ClientPacket, client::config::CPluginMessage, packet_encoder::EncodedPacket,
+};
+use pumpkin_registry::{Music, Registry};
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector2::Vector2;
 use pumpkin_util::text::TextComponent;
-use pumpkin_world::dimension::Dimension;
+use pumpkin_world::advancement::Advancements;
 use rand::prelude::SliceRandom;
-use std::collections::HashMap;
+use scoreboard::Scoreboard;
+use slots::JoinQueue;
+use slow_chat::SlowChat;
+use std::collections::{BTreeMap, HashMap};
 use std::net::IpAddr;
+use std::path::Path;
 use std::sync::atomic::AtomicU32;
 use std::{
     sync::{Arc, atomic::Ordering},
     time::Duration,
 };
+use teleport_requests::TeleportRequests;
+use tick::TickHooks;
+use tick_manager::{SprintReport, TickManager};
 use tokio::sync::{Mutex, RwLock};
 
+pub mod advancements;
+pub mod boss_events;
+pub mod chat_filter;
 mod connection_cache;
+pub mod homes;
 mod key_store;
+pub mod kits;
+pub mod links;
+pub mod memory;
+pub mod motd;
+pub mod scoreboard;
+pub mod slots;
+pub mod slow_chat;
+pub mod team;
+pub mod teleport_requests;
+pub mod tick;
+pub mod tick_manager;
 pub mod ticker;
 
 pub const CURRENT_MC_VERSION: &str = "1.21.4";
@@ -54,10 +90,8 @@ pub struct Server {
     pub block_registry: Arc<BlockRegistry>,
     /// Item Behaviour
     pub item_registry: Arc<ItemRegistry>,
-    /// Manages multiple worlds within the server.
-    pub worlds: RwLock<Vec<Arc<World>>>,
-    // All the dimensions that exists on the server,
-    pub dimensions: Vec<DimensionType>,
+    /// The worlds of the dimensions the server has.
+    pub worlds: WorldManager,
     /// Caches game registries for efficient access.
     pub cached_registry: Vec<Registry>,
     /// Tracks open containers used for item interactions.
@@ -70,8 +104,52 @@ pub struct Server {
     pub auth_client: Option<reqwest::Client>,
     /// The server's custom bossbars
     pub bossbars: Mutex<CustomBossbars>,
+    /// The boss bars of raids, boss fights and plugins
+    pub boss_events: Mutex<BossEvents>,
     /// The default gamemode when a player joins the server (reset every restart)
     pub defaultgamemode: Mutex<DefaultGamemode>,
+    /// Hooks run by the game loop during each world tick
+    pub tick_hooks: TickHooks,
+    /// Controls the tick rate and freezing of the game loop
+    pub tick_manager: TickManager,
+    /// How many connections ended for each reason since the server started
+    pub disconnect_stats: DisconnectStats,
+    /// The cooldown between chat messages, see `/slowchat`
+    pub slow_chat: SlowChat,
+    /// The pending `/tpa` requests
+    pub teleport_requests: Mutex<TeleportRequests>,
+    /// The homes players set with `/sethome`
+    pub homes: Mutex<Homes>,
+    /// The warps operators set with `/setwarp`
+    pub warps: Mutex<Warps>,
+    /// When players last claimed each kit with `/kit`
+    pub kit_cooldowns: Mutex<KitCooldowns>,
+    /// The advancements of the datapacks of the default world
+    pub advancements: Advancements,
+    /// The links shown in the pause menu, see `Server::send_links`
+    pub links: RwLock<ServerLinks>,
+    /// The players waiting for a slot while the server is full
+    pub join_queue: JoinQueue,
+    /// The objectives and scores of all worlds
+    pub scoreboard: Mutex<Scoreboard>,
+}
+
+/// The music configured to replace the one of the biomes, by dimension
+fn world_music() -> BTreeMap<String, Music> {
+    advanced_config()
+        .music
+        .worlds
+        .iter()
+        .map(|(world, music)| {
+            let music = Music {
+                sound: music.sound.clone(),
+                min_delay: music.min_delay,
+                max_delay: music.max_delay,
+                replace_current_music: music.replace_current_music,
+            };
+            (world.clone(), music)
+        })
+        .collect()
 }
 
 impl Server {
@@ -93,26 +171,25 @@ impl Server {
         // First register default command, after that plugins can put in their own
         let command_dispatcher = RwLock::new(default_dispatcher());
 
-        let world = World::load(
-            Dimension::OverWorld.into_level(
-                // TODO: load form config
-                "./world".parse().unwrap(),
-            ),
-            DimensionType::Overworld,
-        );
+        // TODO: load form config
+        let worlds = WorldManager::load(Path::new("./world"));
+        let world = worlds.default_world();
+
+        let homes = Homes::load(&world.level.data_path(homes::HOMES_FILE));
+        let warps = Warps::load(&world.level.data_path(homes::WARPS_FILE));
+        let advancements = Advancements::load(&world.level.datapacks_path());
+        let scoreboard = world
+            .level
+            .read_data(scoreboard::SCOREBOARD_FILE)
+            .map(|nbt| Scoreboard::from_nbt(&nbt))
+            .unwrap_or_default();
 
         Self {
-            cached_registry: Registry::get_synced(),
+            cached_registry: Registry::get_synced(&world_music()),
             open_containers: RwLock::new(HashMap::new()),
             drag_handler: DragHandler::new(),
             container_id: 0.into(),
-            worlds: RwLock::new(vec![Arc::new(world)]),
-            dimensions: vec![
-                DimensionType::Overworld,
-                DimensionType::OverworldCaves,
-                DimensionType::TheNether,
-                DimensionType::TheEnd,
-            ],
+            worlds,
             command_dispatcher,
             block_registry: super::block::default_registry(),
             item_registry: super::item::items::default_registry(),
@@ -121,9 +198,25 @@ impl Server {
             server_listing: Mutex::new(CachedStatus::new()),
             server_branding: CachedBranding::new(),
             bossbars: Mutex::new(CustomBossbars::new()),
+            boss_events: Mutex::new(BossEvents::new()),
             defaultgamemode: Mutex::new(DefaultGamemode {
                 gamemode: BASIC_CONFIG.default_gamemode,
             }),
+            tick_hooks: TickHooks::default(),
+            tick_manager: TickManager::new(BASIC_CONFIG.tps),
+            disconnect_stats: DisconnectStats::default(),
+            slow_chat: SlowChat::new(advanced_config().chat.slow_chat_seconds),
+            teleport_requests: Mutex::new(TeleportRequests::new(
+                Duration::from_secs(advanced_config().tpa.expire_seconds),
+                Duration::from_secs(advanced_config().tpa.cooldown_seconds),
+            )),
+            homes: Mutex::new(homes),
+            warps: Mutex::new(warps),
+            kit_cooldowns: Mutex::new(KitCooldowns::default()),
+            advancements,
+            links: RwLock::new(ServerLinks::from_config(&advanced_config().server_links)),
+            join_queue: JoinQueue::default(),
+            scoreboard: Mutex::new(scoreboard),
         }
     }
 
@@ -166,11 +259,27 @@ impl Server {
     /// You still have to spawn the Player in the World to make then to let them Join and make them Visible
     pub async fn add_player(&self, client: Arc<Client>) -> Option<(Arc<Player>, Arc<World>)> {
         let gamemode = self.defaultgamemode.lock().await.gamemode;
-        // Basically the default world
-        // TODO: select default from config
-        let world = &self.worlds.read().await[0];
-
-        let player = Arc::new(Player::new(client, world.clone(), gamemode).await);
+        // The player data is kept in the default world, new players join there
+        let default_world = self.worlds.default_world();
+        let mut world = default_world;
+
+        let mut player = Player::new(client, world.clone(), gamemode).await;
+        let uuid = player.gameprofile.id.to_string();
+        if let Some(mut data) = default_world.level.read_player_data(&uuid) {
+            player.read_nbt(&mut data).await;
+            player.first_join.store(false, Ordering::Relaxed);
+            // TODO: Move players out of dimensions which were disabled since they left
+            if let Some(left_in) = data
+                .get_string("Dimension")
+                .and_then(|dimension| self.worlds.get_by_name(dimension))
+            {
+                world = left_in;
+                *player.living_entity.entity.world.write().await = world.clone();
+            }
+        }
+        *player.advancements.get_mut() =
+            PlayerAdvancements::load(&default_world.level.advancements_path(&uuid));
+        let player = Arc::new(player);
         send_cancellable! {{
             PlayerLoginEvent::new(player.clone(), TextComponent::text("You have been kicked from the server"));
 
@@ -178,6 +287,7 @@ impl Server {
                 world
                     .add_player(player.gameprofile.id, player.clone())
                     .await;
+                known_players::remember(&player.gameprofile).await;
                 // TODO: Config if we want increase online
                 if let Some(config) = player.client.config.lock().await.as_ref() {
                     // TODO: Config so we can also just ignore this hehe
@@ -201,8 +311,78 @@ impl Server {
         self.server_listing.lock().await.remove_player();
     }
 
+    /// Saves the player into the playerdata of the default world, like their ender chest
+    pub async fn save_player_data(&self, player: &Player) {
+        let mut data = NbtCompound::new();
+        player.write_nbt(&mut data).await;
+        let world = self.worlds.default_world();
+        let uuid = player.gameprofile.id.to_string();
+        if let Err(err) = world.level.write_player_data(&uuid, data) {
+            log::error!(
+                "Failed to save the player data of {}: {err}",
+                player.gameprofile.name
+            );
+        }
+        let path = world.level.advancements_path(&uuid);
+        if let Err(err) = player.advancements.lock().await.save(&path) {
+            log::error!(
+                "Failed to save the advancements of {}: {err}",
+                player.gameprofile.name
+            );
+        }
+    }
+
+    /// Saves the homes right after they changed, into the data of the default world
+    pub async fn save_homes(&self) {
+        let path = self
+            .worlds
+            .default_world()
+            .level
+            .data_path(homes::HOMES_FILE);
+        if let Err(err) = self.homes.lock().await.save(&path) {
+            log::error!("Failed to save the homes to {}: {err}", path.display());
+        }
+    }
+
+    /// Saves the warps right after they changed, into the data of the default world
+    pub async fn save_warps(&self) {
+        let path = self
+            .worlds
+            .default_world()
+            .level
+            .data_path(homes::WARPS_FILE);
+        if let Err(err) = self.warps.lock().await.save(&path) {
+            log::error!("Failed to save the warps to {}: {err}", path.display());
+        }
+    }
+
+    /// Saves the scoreboard into the data of the default world
+    pub async fn save_scoreboard(&self) {
+        let nbt = self.scoreboard.lock().await.to_nbt();
+        let level = &self.worlds.default_world().level;
+        if let Err(err) = level.write_data(scoreboard::SCOREBOARD_FILE, nbt) {
+            log::error!("Failed to save the scoreboard: {err}");
+        }
+    }
+
+    /// Forgets the container of the ender chest of a player who left, nobody else can open it
+    pub async fn remove_ender_chest_container(&self, player: &Player) {
+        self.open_containers
+            .write()
+            .await
+            .retain(|_, container| !container.is_container(&player.ender_chest));
+    }
+
     pub async fn save(&self) {
-        for world in self.worlds.read().await.iter() {
+        let mut players = Vec::new();
+        for world in self.worlds.iter() {
+            players.extend(world.players.read().await.values().cloned());
+        }
+        for player in players {
+            self.save_player_data(&player).await;
+        }
+        self.save_scoreboard().await;
+        for world in self.worlds.iter() {
             world.save().await;
         }
 
@@ -277,8 +457,17 @@ impl Server {
     where
         P: ClientPacket,
     {
-        for world in self.worlds.read().await.iter() {
-            world.broadcast_packet_all(packet).await;
+        let packet = EncodedPacket::new(packet);
+        for world in self.worlds.iter() {
+            world.broadcast_encoded(&packet).await;
+        }
+    }
+
+    /// Sends the current links to every player, after they were changed
+    pub async fn send_links(&self) {
+        let links = self.links.read().await;
+        for player in self.get_all_players().await {
+            player.send_links(&links).await;
         }
     }
 
@@ -293,7 +482,7 @@ impl Server {
             ServerBroadcastEvent::new(message.clone(), sender_name.clone());
 
             'after: {
-                for world in self.worlds.read().await.iter() {
+                for world in self.worlds.iter() {
                     world
                         .broadcast_message(&event.message, &event.sender, chat_type, target_name)
                         .await;
@@ -315,7 +504,7 @@ impl Server {
     ///
     /// An `Option<Arc<Player>>` containing the player if found, or `None` if not found.
     pub async fn get_player_by_name(&self, name: &str) -> Option<Arc<Player>> {
-        for world in self.worlds.read().await.iter() {
+        for world in self.worlds.iter() {
             if let Some(player) = world.get_player_by_name(name).await {
                 return Some(player);
             }
@@ -326,7 +515,7 @@ impl Server {
     pub async fn get_players_by_ip(&self, ip: IpAddr) -> Vec<Arc<Player>> {
         let mut players = Vec::<Arc<Player>>::new();
 
-        for world in self.worlds.read().await.iter() {
+        for world in self.worlds.iter() {
             for (_, player) in world.players.read().await.iter() {
                 if player.client.address.lock().await.ip() == ip {
                     players.push(player.clone());
@@ -337,11 +526,60 @@ impl Server {
         players
     }
 
+    /// Adds the player to the whitelist and saves it, returns false if they were on it already
+    pub async fn add_to_whitelist(&self, profile: &GameProfile) -> bool {
+        let mut whitelist = WHITELIST.write().await;
+        let added = whitelist.add(profile);
+        if added {
+            whitelist.save();
+        }
+        added
+    }
+
+    /// Removes the player from the whitelist and saves it, returns false if they weren't on it
+    pub async fn remove_from_whitelist(&self, profile: &GameProfile) -> bool {
+        let mut whitelist = WHITELIST.write().await;
+        let removed = whitelist.remove(profile.id);
+        if removed {
+            whitelist.save();
+        }
+        drop(whitelist);
+
+        if removed {
+            self.enforce_whitelist().await;
+        }
+        removed
+    }
+
+    /// Reads `whitelist.json` again, e.g. after it was edited by hand
+    pub async fn reload_whitelist(&self) {
+        *WHITELIST.write().await = Whitelist::load();
+        self.enforce_whitelist().await;
+    }
+
+    /// Kicks the players who aren't whitelisted if the whitelist is enabled and enforced
+    pub async fn enforce_whitelist(&self) {
+        let config = &advanced_config().whitelist;
+        if !config.enabled || !config.enforce {
+            return;
+        }
+        for player in self.get_all_players().await {
+            if !whitelist_data::may_join(player.gameprofile.id).await {
+                player
+                    .kick(TextComponent::translate(
+                        "multiplayer.disconnect.not_whitelisted",
+                        [],
+                    ))
+                    .await;
+            }
+        }
+    }
+
     /// Returns all players from all worlds.
     pub async fn get_all_players(&self) -> Vec<Arc<Player>> {
         let mut players = Vec::<Arc<Player>>::new();
 
-        for world in self.worlds.read().await.iter() {
+        for world in self.worlds.iter() {
             for (_, player) in world.players.read().await.iter() {
                 players.push(player.clone());
             }
@@ -370,7 +608,7 @@ impl Server {
     ///
     /// An `Option<Arc<Player>>` containing the player if found, or `None` if not found.
     pub async fn get_player_by_uuid(&self, id: uuid::Uuid) -> Option<Arc<Player>> {
-        for world in self.worlds.read().await.iter() {
+        for world in self.worlds.iter() {
             if let Some(player) = world.get_player_by_uuid(id).await {
                 return Some(player);
             }
@@ -378,6 +616,20 @@ impl Server {
         None
     }
 
+    /// Searches for a Player or any other Entity by its UUID across all worlds.
+    ///
+    /// Used by selectors and plugins which only know the UUID of an entity.
+    pub async fn get_entity_by_uuid(&self, id: uuid::Uuid) -> Option<Arc<dyn EntityBase>> {
+        // The id index knows whether the entity exists at all, saving the search otherwise
+        ENTITY_IDS.get_id(&id)?;
+        for world in self.worlds.iter() {
+            if let Some(entity) = world.get_entity_by_uuid(id).await {
+                return Some(entity);
+            }
+        }
+        None
+    }
+
     /// Counts the total number of players across all worlds.
     ///
     /// This function iterates through each world and sums up the number of players currently connected to that world.
@@ -387,7 +639,7 @@ impl Server {
     /// The total number of players connected to the server.
     pub async fn get_player_count(&self) -> usize {
         let mut count = 0;
-        for world in self.worlds.read().await.iter() {
+        for world in self.worlds.iter() {
             count += world.players.read().await.len();
         }
         count
@@ -396,7 +648,7 @@ impl Server {
     /// Similar to [`Server::get_player_count`] >= n, but may be more efficient since it stops it's iteration through all worlds as soon as n players were found.
     pub async fn has_n_players(&self, n: usize) -> bool {
         let mut count = 0;
-        for world in self.worlds.read().await.iter() {
+        for world in self.worlds.iter() {
             count += world.players.read().await.len();
             if count >= n {
                 return true;
@@ -435,9 +687,121 @@ impl Server {
         self.key_store.get_digest(secret)
     }
 
-    async fn tick(&self) {
-        for world in self.worlds.read().await.iter() {
-            world.tick(self).await;
+    /// Sends the current tick rate and frozen state to every player
+    pub async fn broadcast_tick_state(&self) {
+        self.broadcast_packet_all(&self.tick_manager.state_packet())
+            .await;
+    }
+
+    /// Sends the amount of frozen steps left to every player
+    pub async fn broadcast_tick_step(&self) {
+        self.broadcast_packet_all(&self.tick_manager.step_packet())
+            .await;
+    }
+
+    async fn finish_sprint(&self, report: &SprintReport) {
+        log::info!(
+            "Sprint completed with {:.1} ticks per second, or {:.2} ms per tick",
+            report.ticks_per_second(),
+            report.millis_per_tick()
+        );
+        // The game may have been frozen before the sprint
+        self.broadcast_tick_state().await;
+    }
+
+    async fn tick(self: &Arc<Self>) {
+        // Decided once so every world agrees on whether this tick runs while frozen
+        let runs_normally = self.tick_manager.begin_tick();
+        outgoing::defer_flushes(true);
+        // Worlds always tick in the same order, one after another
+        for world in self.worlds.iter() {
+            world.tick(self, runs_normally).await;
+        }
+        if runs_normally {
+            self.tick_containers().await;
+        }
+        outgoing::defer_flushes(false);
+        // What was sent after the world flushed its players, like the changes of containers
+        for player in self.get_all_players().await {
+            player.client.flush();
+        }
+    }
+
+    /// Ticks containers which work on their own, like furnaces, and shows the changes to
+    /// everyone looking into them
+    async fn tick_containers(&self) {
+        // Containers don't know their world yet
+        let world = self.worlds.default_world().clone();
+        let containers: Vec<_> = self
+            .open_containers
+            .read()
+            .await
+            .values()
+            .map(|open_container| {
+                (
+                    open_container.container().clone(),
+                    open_container
+                        .get_location()
+                        .zip(open_container.get_block()),
+                    open_container.all_player_ids(),
+                )
+            })
+            .collect();
+
+        for (container, block, player_ids) in containers {
+            let mut container = container.lock().await;
+            let changed = container.tick();
+            if let Some((location, block)) = block {
+                update_container_block(&world, &block, &location, &**container).await;
+            }
+
+            let properties = container.window_properties();
+            if !changed && properties.is_empty() {
+                continue;
+            }
+            for player_id in player_ids {
+                let Some(player) = world.get_player_by_id(player_id).await else {
+                    continue;
+                };
+                if changed {
+                    player.set_container_content(Some(&mut *container)).await;
+                }
+                for (id, value) in &properties {
+                    player.send_container_property(*id, *value).await;
+                }
+            }
+        }
+    }
+}
+
+/// Shows the state of the container on its block, like a lit furnace or the bottles in a brewing
+/// stand
+async fn update_container_block(
+    world: &World,
+    block: &Block,
+    location: &BlockPos,
+    container: &dyn Container,
+) {
+    let Ok(state_id) = world.get_block_state_id(location).await else {
+        return;
+    };
+    let new_state_id = match block.name {
+        "furnace" | "smoker" | "blast_furnace" => {
+            let mut props = FurnaceLikeProperties::from_state_id(state_id, block);
+            props.lit = Boolean::from_bool(container.is_lit());
+            props.to_state_id(block)
+        }
+        "brewing_stand" => {
+            let slots = container.all_slots_ref();
+            let mut props = BrewingStandLikeProperties::from_state_id(state_id, block);
+            props.has_bottle_0 = Boolean::from_bool(slots[0].is_some());
+            props.has_bottle_1 = Boolean::from_bool(slots[1].is_some());
+            props.has_bottle_2 = Boolean::from_bool(slots[2].is_some());
+            props.to_state_id(block)
         }
+        _ => return,
+    };
+    if new_state_id != state_id {
+        world.set_block_state(location, new_state_id).await;
     }
 }
@@ -0,0 +1,97 @@
+use rand::seq::SliceRandom;
+
+/// The MOTDs shown on the status screen, rotated on every ping
+pub struct Motds {
+    messages: Vec<String>,
+    /// Shown instead of `messages` while the server is full
+    full: Vec<String>,
+    tips: Vec<String>,
+    next: usize,
+}
+
+impl Motds {
+    /// Falls back to `default` when no MOTDs are given
+    #[must_use]
+    pub fn new(motds: Vec<String>, full: Vec<String>, tips: Vec<String>, default: &str) -> Self {
+        let messages = if motds.is_empty() {
+            vec![default.to_string()]
+        } else {
+            motds
+        };
+        Self {
+            messages,
+            full,
+            tips,
+            next: 0,
+        }
+    }
+
+    /// Whether every ping gets the same MOTD, so the status response can stay cached
+    #[must_use]
+    pub fn is_static(&self) -> bool {
+        self.full.is_empty() && self.messages.len() == 1 && !self.messages[0].contains('{')
+    }
+
+    /// The next MOTD with its placeholders filled in
+    pub fn next(&mut self, online: u32, max: u32, hostname: &str) -> String {
+        let messages = if !self.full.is_empty() && max > 0 && online >= max {
+            &self.full
+        } else {
+            &self.messages
+        };
+        let motd = &messages[self.next % messages.len()];
+        self.next = self.next.wrapping_add(1);
+
+        let tip = self
+            .tips
+            .choose(&mut rand::thread_rng())
+            .map_or("", String::as_str);
+        motd.replace("{online}", &online.to_string())
+            .replace("{max}", &max.to_string())
+            .replace("{tip}", tip)
+            .replace("{hostname}", hostname)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        let mut motds = Motds::new(
+            vec![
+                "{online}/{max} online".into(),
+                "Welcome to {hostname}".into(),
+            ],
+            Vec::new(),
+            Vec::new(),
+            "unused",
+        );
+        assert!(!motds.is_static());
+        assert_eq!(motds.next(3, 20, "play.example.com"), "3/20 online");
+        assert_eq!(
+            motds.next(3, 20, "play.example.com"),
+            "Welcome to play.example.com"
+        );
+        assert_eq!(motds.next(4, 20, "play.example.com"), "4/20 online");
+
+        let mut motds = Motds::new(Vec::new(), Vec::new(), Vec::new(), "A Pumpkin server");
+        assert!(motds.is_static());
+        assert_eq!(motds.next(0, 20, ""), "A Pumpkin server");
+    }
+
+    #[test]
+    fn full() {
+        let mut motds = Motds::new(
+            Vec::new(),
+            vec!["Full, {online}/{max}".into()],
+            Vec::new(),
+            "Join us",
+        );
+        assert!(!motds.is_static());
+        assert_eq!(motds.next(19, 20, ""), "Join us");
+        assert_eq!(motds.next(20, 20, ""), "Full, 20/20");
+        assert_eq!(motds.next(20, 0, ""), "Join us");
+    }
+}
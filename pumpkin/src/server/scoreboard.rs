@@ -0,0 +1,865 @@
+//! The scoreboard is shared by all worlds like in vanilla, and saved into `data/scoreboard.dat`
+//! of the default world.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::{
+    NumberFormat,
+    client::play::{
+        CDisplayObjective, CResetScore, CUpdateObjectives, CUpdateScore, CUpdateTeams, Mode,
+        RenderType, TeamMethod,
+    },
+    codec::var_int::VarInt,
+};
+use pumpkin_util::text::TextComponent;
+
+use crate::net::Client;
+
+use super::{Server, team::Team};
+
+pub const SCOREBOARD_FILE: &str = "scoreboard.dat";
+
+#[derive(Default)]
+pub struct Scoreboard {
+    /// The objectives by name, sorted for listing them
+    objectives: BTreeMap<String, Objective>,
+    /// The objective shown in each display slot
+    display_slots: HashMap<ScoreboardDisplaySlot, String>,
+    /// The teams by name, sorted for listing them
+    teams: BTreeMap<String, Team>,
+    /// The team of each entity by its name
+    entity_teams: HashMap<String, String>,
+}
+
+impl Scoreboard {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends the whole scoreboard to a player who just joined
+    pub async fn init_client(&self, client: &Client) {
+        for (name, objective) in &self.objectives {
+            client.send_packet(&objective.packet(name, Mode::Add)).await;
+            for (entity_name, score) in &objective.scores {
+                client.send_packet(&score.packet(entity_name, name)).await;
+            }
+        }
+        for (slot, name) in &self.display_slots {
+            client
+                .send_packet(&CDisplayObjective::new(*slot, name))
+                .await;
+        }
+        for (name, team) in &self.teams {
+            let members = team.members().collect();
+            client
+                .send_packet(&CUpdateTeams::new(
+                    name,
+                    TeamMethod::Create(team.info(), members),
+                ))
+                .await;
+        }
+    }
+
+    #[must_use]
+    pub fn has_objective(&self, name: &str) -> bool {
+        self.objectives.contains_key(name)
+    }
+
+    /// The names of the objectives with their display names, sorted by name
+    pub fn objectives(&self) -> impl Iterator<Item = (&str, &TextComponent)> {
+        self.objectives
+            .iter()
+            .map(|(name, objective)| (name.as_str(), &objective.display_name))
+    }
+
+    #[must_use]
+    pub fn display_name(&self, objective: &str) -> Option<&TextComponent> {
+        Some(&self.objectives.get(objective)?.display_name)
+    }
+
+    #[must_use]
+    pub fn criteria(&self, objective: &str) -> Option<ObjectiveCriteria> {
+        Some(self.objectives.get(objective)?.criteria)
+    }
+
+    /// Returns false if there already is an objective with the name
+    pub async fn add_objective(
+        &mut self,
+        server: &Server,
+        objective: ScoreboardObjective<'_>,
+    ) -> bool {
+        if self.objectives.contains_key(objective.name) {
+            return false;
+        }
+        let added = Objective {
+            display_name: objective.display_name,
+            render_type: objective.render_type,
+            number_format: objective.number_format,
+            criteria: objective.criteria,
+            scores: BTreeMap::new(),
+        };
+        server
+            .broadcast_packet_all(&added.packet(objective.name, Mode::Add))
+            .await;
+        self.objectives.insert(objective.name.to_string(), added);
+        true
+    }
+
+    /// Returns false if there is no objective with the name. Clients clear the display slots
+    /// which showed it by themselves
+    pub async fn remove_objective(&mut self, server: &Server, name: &str) -> bool {
+        let Some(objective) = self.objectives.remove(name) else {
+            return false;
+        };
+        self.display_slots.retain(|_, shown| shown != name);
+        server
+            .broadcast_packet_all(&objective.packet(name, Mode::Remove))
+            .await;
+        true
+    }
+
+    /// Returns false if there is no objective with the name
+    pub async fn set_display_name(
+        &mut self,
+        server: &Server,
+        name: &str,
+        display_name: TextComponent,
+    ) -> bool {
+        let Some(objective) = self.objectives.get_mut(name) else {
+            return false;
+        };
+        objective.display_name = display_name;
+        server
+            .broadcast_packet_all(&objective.packet(name, Mode::Update))
+            .await;
+        true
+    }
+
+    /// Returns false if there is no objective with the name
+    pub async fn set_render_type(
+        &mut self,
+        server: &Server,
+        name: &str,
+        render_type: RenderType,
+    ) -> bool {
+        let Some(objective) = self.objectives.get_mut(name) else {
+            return false;
+        };
+        objective.render_type = render_type;
+        server
+            .broadcast_packet_all(&objective.packet(name, Mode::Update))
+            .await;
+        true
+    }
+
+    /// The objective shown in the display slot
+    #[must_use]
+    pub fn displayed(&self, slot: ScoreboardDisplaySlot) -> Option<&str> {
+        self.display_slots.get(&slot).map(String::as_str)
+    }
+
+    /// Shows the objective in the display slot, `None` clears the slot. Returns false if the
+    /// objective does not exist
+    pub async fn set_display_slot(
+        &mut self,
+        server: &Server,
+        slot: ScoreboardDisplaySlot,
+        objective: Option<&str>,
+    ) -> bool {
+        match objective {
+            Some(name) if !self.objectives.contains_key(name) => return false,
+            Some(name) => {
+                self.display_slots.insert(slot, name.to_string());
+            }
+            None => {
+                self.display_slots.remove(&slot);
+            }
+        }
+        // An empty name clears the slot on the client
+        server
+            .broadcast_packet_all(&CDisplayObjective::new(slot, objective.unwrap_or_default()))
+            .await;
+        true
+    }
+
+    pub async fn update_score(&mut self, server: &Server, score: ScoreboardScore<'_>) {
+        let Some(objective) = self.objectives.get_mut(score.objective_name) else {
+            log::warn!(
+                "Tried to place a score into a Objective which does not exist, {}",
+                &score.objective_name
+            );
+            return;
+        };
+        let criteria = objective.criteria;
+        let updated = objective
+            .scores
+            .entry(score.entity_name.to_string())
+            .or_insert_with(|| Score::new(criteria));
+        updated.value = score.value.0;
+        updated.display_name = score.display_name;
+        updated.number_format = score.number_format;
+        server
+            .broadcast_packet_all(&updated.packet(score.entity_name, score.objective_name))
+            .await;
+    }
+
+    /// Adds to the score of the entity, which starts at 0. Returns the new score, `None` if
+    /// the objective does not exist
+    pub async fn add_score(
+        &mut self,
+        server: &Server,
+        entity_name: &str,
+        objective_name: &str,
+        amount: i32,
+    ) -> Option<i32> {
+        let objective = self.objectives.get_mut(objective_name)?;
+        let criteria = objective.criteria;
+        let score = objective
+            .scores
+            .entry(entity_name.to_string())
+            .or_insert_with(|| Score::new(criteria));
+        score.value = score.value.wrapping_add(amount);
+        server
+            .broadcast_packet_all(&score.packet(entity_name, objective_name))
+            .await;
+        Some(score.value)
+    }
+
+    #[must_use]
+    pub fn get_score(&self, entity_name: &str, objective: &str) -> Option<i32> {
+        let score = self.objectives.get(objective)?.scores.get(entity_name)?;
+        Some(score.value)
+    }
+
+    /// The scores of the entity by objective name, sorted by it
+    #[must_use]
+    pub fn scores_of(&self, entity_name: &str) -> Vec<(&str, i32)> {
+        self.objectives
+            .iter()
+            .filter_map(|(name, objective)| {
+                let score = objective.scores.get(entity_name)?;
+                Some((name.as_str(), score.value))
+            })
+            .collect()
+    }
+
+    /// The names of all entities which have a score, sorted
+    #[must_use]
+    pub fn entities(&self) -> BTreeSet<&str> {
+        self.objectives
+            .values()
+            .flat_map(|objective| objective.scores.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// Removes the score of the entity on the objective, or all of its scores without an
+    /// objective. Returns false if there was none
+    pub async fn reset_score(
+        &mut self,
+        server: &Server,
+        entity_name: &str,
+        objective: Option<&str>,
+    ) -> bool {
+        let mut removed = false;
+        for (name, stored) in &mut self.objectives {
+            if objective.is_none_or(|objective| objective == name) {
+                removed |= stored.scores.remove(entity_name).is_some();
+            }
+        }
+        if removed {
+            server
+                .broadcast_packet_all(&CResetScore::new(
+                    entity_name.to_string(),
+                    objective.map(str::to_string),
+                ))
+                .await;
+        }
+        removed
+    }
+
+    /// Lets the entity use `/trigger` once on the objective, map makers do this for every player
+    /// who should be able to click their menus. Returns false if it is not a trigger objective
+    pub fn enable_trigger(&mut self, entity_name: &str, objective: &str) -> bool {
+        let Some(objective) = self.objectives.get_mut(objective) else {
+            return false;
+        };
+        if objective.criteria != ObjectiveCriteria::Trigger {
+            return false;
+        }
+        objective
+            .scores
+            .entry(entity_name.to_string())
+            .or_insert_with(|| Score::new(ObjectiveCriteria::Trigger))
+            .locked = false;
+        true
+    }
+
+    /// Changes the score of the entity on a trigger objective, which has to be enabled for it.
+    /// The trigger gets disabled again afterwards and the new score is returned
+    pub async fn trigger(
+        &mut self,
+        server: &Server,
+        entity_name: &str,
+        objective_name: &str,
+        action: TriggerAction,
+    ) -> Result<i32, TriggerError> {
+        let objective = self
+            .objectives
+            .get_mut(objective_name)
+            .filter(|objective| objective.criteria == ObjectiveCriteria::Trigger)
+            .ok_or(TriggerError::Invalid)?;
+        let score = objective
+            .scores
+            .get_mut(entity_name)
+            .filter(|score| !score.locked)
+            .ok_or(TriggerError::Unprimed)?;
+        score.value = match action {
+            TriggerAction::Add(value) => score.value.wrapping_add(value),
+            TriggerAction::Set(value) => value,
+        };
+        score.locked = true;
+        server
+            .broadcast_packet_all(&score.packet(entity_name, objective_name))
+            .await;
+        Ok(score.value)
+    }
+
+    #[must_use]
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        self.teams.get(name)
+    }
+
+    /// The teams with their names, sorted by name
+    pub fn teams(&self) -> impl Iterator<Item = (&str, &Team)> {
+        self.teams.iter().map(|(name, team)| (name.as_str(), team))
+    }
+
+    /// Returns false if there already is a team with the name
+    pub async fn add_team(
+        &mut self,
+        server: &Server,
+        name: &str,
+        display_name: TextComponent,
+    ) -> bool {
+        if self.teams.contains_key(name) {
+            return false;
+        }
+        let team = Team::new(display_name);
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(
+                name,
+                TeamMethod::Create(team.info(), Vec::new()),
+            ))
+            .await;
+        self.teams.insert(name.to_string(), team);
+        true
+    }
+
+    /// Returns false if there is no team with the name, its members are left without a team
+    pub async fn remove_team(&mut self, server: &Server, name: &str) -> bool {
+        let Some(team) = self.teams.remove(name) else {
+            return false;
+        };
+        for member in &team.members {
+            self.entity_teams.remove(member);
+        }
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(name, TeamMethod::Remove))
+            .await;
+        true
+    }
+
+    /// Changes the options of the team and sends them to the clients. Returns false if there is
+    /// no team with the name
+    pub async fn update_team(
+        &mut self,
+        server: &Server,
+        name: &str,
+        update: impl FnOnce(&mut Team) + Send,
+    ) -> bool {
+        let Some(team) = self.teams.get_mut(name) else {
+            return false;
+        };
+        update(team);
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(name, TeamMethod::Update(team.info())))
+            .await;
+        true
+    }
+
+    /// Puts the entity into the team, leaving the one it was in before. Returns false if there
+    /// is no team with the name
+    pub async fn join_team(&mut self, server: &Server, entity_name: &str, team: &str) -> bool {
+        if !self.teams.contains_key(team) {
+            return false;
+        }
+        if self.get_team(entity_name) != Some(team) {
+            // The client moves the entity out of its old team by itself
+            self.leave_team_silently(entity_name);
+        }
+        if let Some(joined) = self.teams.get_mut(team) {
+            joined.members.insert(entity_name.to_string());
+        }
+        self.entity_teams
+            .insert(entity_name.to_string(), team.to_string());
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(
+                team,
+                TeamMethod::AddEntities(vec![entity_name]),
+            ))
+            .await;
+        true
+    }
+
+    /// Returns false if the entity was in no team
+    pub async fn leave_team(&mut self, server: &Server, entity_name: &str) -> bool {
+        let Some(team) = self.leave_team_silently(entity_name) else {
+            return false;
+        };
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(
+                &team,
+                TeamMethod::RemoveEntities(vec![entity_name]),
+            ))
+            .await;
+        true
+    }
+
+    /// Removes all members from the team and returns how many there were
+    pub async fn empty_team(&mut self, server: &Server, name: &str) -> usize {
+        let Some(team) = self.teams.get_mut(name) else {
+            return 0;
+        };
+        let members = std::mem::take(&mut team.members);
+        if members.is_empty() {
+            return 0;
+        }
+        for member in &members {
+            self.entity_teams.remove(member);
+        }
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(
+                name,
+                TeamMethod::RemoveEntities(members.iter().map(String::as_str).collect()),
+            ))
+            .await;
+        members.len()
+    }
+
+    /// Returns the team the entity was in
+    fn leave_team_silently(&mut self, entity_name: &str) -> Option<String> {
+        let team = self.entity_teams.remove(entity_name)?;
+        if let Some(left) = self.teams.get_mut(&team) {
+            left.members.remove(entity_name);
+        }
+        Some(team)
+    }
+
+    #[must_use]
+    pub fn get_team(&self, entity_name: &str) -> Option<&str> {
+        self.entity_teams.get(entity_name).map(String::as_str)
+    }
+
+    /// Whether the attacker may hurt the victim, members of a team can only hurt each other with
+    /// friendly fire
+    #[must_use]
+    pub fn allows_damage(&self, attacker: &str, victim: &str) -> bool {
+        match (self.get_team(attacker), self.get_team(victim)) {
+            (Some(attacker), Some(victim)) if attacker == victim => self
+                .teams
+                .get(attacker)
+                .is_none_or(|team| team.friendly_fire),
+            _ => true,
+        }
+    }
+
+    /// Reads the `data` compound of `scoreboard.dat` like vanilla saves it. Number formats are
+    /// not saved, criteria the server doesn't know are loaded as dummies
+    #[must_use]
+    pub fn from_nbt(nbt: &NbtCompound) -> Self {
+        let mut scoreboard = Self::new();
+        for tag in nbt.get_list("Objectives").unwrap_or_default() {
+            let Some(objective) = tag.extract_compound() else {
+                continue;
+            };
+            let Some(name) = objective.get_string("Name") else {
+                continue;
+            };
+            let display_name = objective
+                .get_string("DisplayName")
+                .and_then(|json| serde_json::from_str(json).ok())
+                .unwrap_or_else(|| TextComponent::text(name.clone()));
+            scoreboard.objectives.insert(
+                name.clone(),
+                Objective {
+                    display_name,
+                    render_type: match objective.get_string("RenderType").map(String::as_str) {
+                        Some("hearts") => RenderType::Hearts,
+                        _ => RenderType::Integer,
+                    },
+                    number_format: None,
+                    criteria: objective
+                        .get_string("CriteriaName")
+                        .and_then(|criteria| ObjectiveCriteria::from_name(criteria))
+                        .unwrap_or_default(),
+                    scores: BTreeMap::new(),
+                },
+            );
+        }
+
+        for tag in nbt.get_list("PlayerScores").unwrap_or_default() {
+            let Some(score) = tag.extract_compound() else {
+                continue;
+            };
+            let (Some(entity_name), Some(objective)) = (
+                score.get_string("Name"),
+                score
+                    .get_string("Objective")
+                    .and_then(|name| scoreboard.objectives.get_mut(name)),
+            ) else {
+                continue;
+            };
+            objective.scores.insert(
+                entity_name.clone(),
+                Score {
+                    value: score.get_int("Score").unwrap_or_default(),
+                    locked: score.get_bool("Locked").unwrap_or_default(),
+                    display_name: None,
+                    number_format: None,
+                },
+            );
+        }
+
+        for tag in nbt.get_list("Teams").unwrap_or_default() {
+            let Some((name, team)) = tag.extract_compound().and_then(Team::from_nbt) else {
+                continue;
+            };
+            for member in &team.members {
+                scoreboard.entity_teams.insert(member.clone(), name.clone());
+            }
+            scoreboard.teams.insert(name, team);
+        }
+
+        if let Some(slots) = nbt.get_compound("DisplaySlots") {
+            for (slot, name) in &slots.child_tags {
+                if let (Some(slot), Some(name)) = (slot_from_name(slot), name.extract_string()) {
+                    if scoreboard.objectives.contains_key(name) {
+                        scoreboard.display_slots.insert(slot, name.clone());
+                    }
+                }
+            }
+        }
+        scoreboard
+    }
+
+    /// Writes the `data` compound of `scoreboard.dat`
+    #[must_use]
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut objectives = Vec::new();
+        let mut scores = Vec::new();
+        for (name, objective) in &self.objectives {
+            let mut nbt = NbtCompound::new();
+            nbt.put_string("Name", name.clone());
+            nbt.put_string("CriteriaName", objective.criteria.name().to_string());
+            nbt.put_string(
+                "DisplayName",
+                serde_json::to_string(&objective.display_name).unwrap_or_default(),
+            );
+            nbt.put_string(
+                "RenderType",
+                match objective.render_type {
+                    RenderType::Integer => "integer",
+                    RenderType::Hearts => "hearts",
+                }
+                .to_string(),
+            );
+            objectives.push(NbtTag::Compound(nbt));
+
+            for (entity_name, score) in &objective.scores {
+                let mut nbt = NbtCompound::new();
+                nbt.put_string("Name", entity_name.clone());
+                nbt.put_string("Objective", name.clone());
+                nbt.put_int("Score", score.value);
+                nbt.put_bool("Locked", score.locked);
+                scores.push(NbtTag::Compound(nbt));
+            }
+        }
+
+        let mut slots = NbtCompound::new();
+        for (slot, name) in &self.display_slots {
+            if let Some(slot) = slot_name(*slot) {
+                slots.put_string(slot, name.clone());
+            }
+        }
+
+        let teams: Vec<_> = self
+            .teams
+            .iter()
+            .map(|(name, team)| NbtTag::Compound(team.to_nbt(name)))
+            .collect();
+
+        let mut nbt = NbtCompound::new();
+        nbt.put_list("Objectives", objectives.into_boxed_slice());
+        nbt.put_list("PlayerScores", scores.into_boxed_slice());
+        nbt.put_list("Teams", teams.into_boxed_slice());
+        nbt.put_component("DisplaySlots", slots);
+        nbt
+    }
+}
+
+/// The name of a display slot in `scoreboard.dat` and commands. Team sidebars are not
+/// supported yet
+#[must_use]
+pub const fn slot_name(slot: ScoreboardDisplaySlot) -> Option<&'static str> {
+    match slot {
+        ScoreboardDisplaySlot::List => Some("list"),
+        ScoreboardDisplaySlot::Sidebar => Some("sidebar"),
+        ScoreboardDisplaySlot::BelowName => Some("below_name"),
+        _ => None,
+    }
+}
+
+#[must_use]
+pub fn slot_from_name(name: &str) -> Option<ScoreboardDisplaySlot> {
+    match name {
+        "list" => Some(ScoreboardDisplaySlot::List),
+        "sidebar" => Some(ScoreboardDisplaySlot::Sidebar),
+        "below_name" => Some(ScoreboardDisplaySlot::BelowName),
+        _ => None,
+    }
+}
+
+/// What changes the scores of an objective, the server only does this for triggers
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ObjectiveCriteria {
+    /// Only changed by commands and plugins
+    #[default]
+    Dummy,
+    /// Players can change their own score with `/trigger` once it got enabled for them
+    Trigger,
+}
+
+impl ObjectiveCriteria {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Dummy => "dummy",
+            Self::Trigger => "trigger",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dummy" => Some(Self::Dummy),
+            "trigger" => Some(Self::Trigger),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriggerAction {
+    Add(i32),
+    Set(i32),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriggerError {
+    /// The objective does not exist or is not a trigger
+    Invalid,
+    /// The trigger was not enabled for the entity
+    Unprimed,
+}
+
+struct Objective {
+    display_name: TextComponent,
+    render_type: RenderType,
+    number_format: Option<NumberFormat>,
+    criteria: ObjectiveCriteria,
+    /// The scores by entity name
+    scores: BTreeMap<String, Score>,
+}
+
+impl Objective {
+    fn packet<'a>(&self, name: &'a str, mode: Mode) -> CUpdateObjectives<'a> {
+        CUpdateObjectives::new(
+            name,
+            mode,
+            self.display_name.clone(),
+            self.render_type,
+            self.number_format.clone(),
+        )
+    }
+}
+
+struct Score {
+    value: i32,
+    /// Locked trigger scores can not be changed with `/trigger`
+    locked: bool,
+    display_name: Option<TextComponent>,
+    number_format: Option<NumberFormat>,
+}
+
+impl Score {
+    fn new(criteria: ObjectiveCriteria) -> Self {
+        Self {
+            value: 0,
+            locked: criteria == ObjectiveCriteria::Trigger,
+            display_name: None,
+            number_format: None,
+        }
+    }
+
+    fn packet<'a>(&self, entity_name: &'a str, objective_name: &'a str) -> CUpdateScore<'a> {
+        CUpdateScore::new(
+            entity_name,
+            objective_name,
+            VarInt(self.value),
+            self.display_name.clone(),
+            self.number_format.clone(),
+        )
+    }
+}
+
+pub struct ScoreboardObjective<'a> {
+    name: &'a str,
+    display_name: TextComponent,
+    render_type: RenderType,
+    number_format: Option<NumberFormat>,
+    criteria: ObjectiveCriteria,
+}
+
+impl<'a> ScoreboardObjective<'a> {
+    #[must_use]
+    pub const fn new(
+        name: &'a str,
+        display_name: TextComponent,
+        render_type: RenderType,
+        number_format: Option<NumberFormat>,
+    ) -> Self {
+        Self {
+            name,
+            display_name,
+            render_type,
+            number_format,
+            criteria: ObjectiveCriteria::Dummy,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_criteria(mut self, criteria: ObjectiveCriteria) -> Self {
+        self.criteria = criteria;
+        self
+    }
+}
+
+pub struct ScoreboardScore<'a> {
+    entity_name: &'a str,
+    objective_name: &'a str,
+    value: VarInt,
+    display_name: Option<TextComponent>,
+    number_format: Option<NumberFormat>,
+}
+
+impl<'a> ScoreboardScore<'a> {
+    #[must_use]
+    pub const fn new(
+        entity_name: &'a str,
+        objective_name: &'a str,
+        value: VarInt,
+        display_name: Option<TextComponent>,
+        number_format: Option<NumberFormat>,
+    ) -> Self {
+        Self {
+            entity_name,
+            objective_name,
+            value,
+            display_name,
+            number_format,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+    use pumpkin_protocol::client::play::RenderType;
+    use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+    use super::{Objective, ObjectiveCriteria, Score, Scoreboard};
+    use crate::server::team::{CollisionRule, Team};
+
+    #[test]
+    fn saves_like_vanilla() {
+        let mut scoreboard = Scoreboard::new();
+        let mut scores = BTreeMap::new();
+        let mut score = Score::new(ObjectiveCriteria::Trigger);
+        score.value = 3;
+        scores.insert("Steve".to_string(), score);
+        scoreboard.objectives.insert(
+            "menu".to_string(),
+            Objective {
+                display_name: TextComponent::text("Menu"),
+                render_type: RenderType::Hearts,
+                number_format: None,
+                criteria: ObjectiveCriteria::Trigger,
+                scores,
+            },
+        );
+        scoreboard
+            .display_slots
+            .insert(ScoreboardDisplaySlot::Sidebar, "menu".to_string());
+
+        let nbt = scoreboard.to_nbt();
+        assert_eq!(
+            nbt.get_compound("DisplaySlots")
+                .and_then(|slots| slots.get_string("sidebar"))
+                .map(String::as_str),
+            Some("menu")
+        );
+
+        let loaded = Scoreboard::from_nbt(&nbt);
+        assert_eq!(loaded.get_score("Steve", "menu"), Some(3));
+        assert_eq!(loaded.criteria("menu"), Some(ObjectiveCriteria::Trigger));
+        assert_eq!(
+            loaded.display_name("menu"),
+            Some(&TextComponent::text("Menu"))
+        );
+        assert_eq!(loaded.objectives["menu"].render_type, RenderType::Hearts);
+        assert!(loaded.objectives["menu"].scores["Steve"].locked);
+        assert_eq!(
+            loaded.displayed(ScoreboardDisplaySlot::Sidebar),
+            Some("menu")
+        );
+    }
+
+    #[test]
+    fn saves_teams() {
+        let mut scoreboard = Scoreboard::new();
+        let mut team = Team::new(TextComponent::text("Red"));
+        team.color = Some(NamedColor::Red);
+        team.friendly_fire = false;
+        team.collision_rule = CollisionRule::PushOwnTeam;
+        for member in ["Steve", "Alex"] {
+            team.members.insert(member.to_string());
+            scoreboard
+                .entity_teams
+                .insert(member.to_string(), "red".to_string());
+        }
+        scoreboard.teams.insert("red".to_string(), team);
+
+        let loaded = Scoreboard::from_nbt(&scoreboard.to_nbt());
+        assert_eq!(loaded.get_team("Alex"), Some("red"));
+        let team = loaded.team("red").unwrap();
+        assert_eq!(team.color, Some(NamedColor::Red));
+        assert_eq!(team.collision_rule, CollisionRule::PushOwnTeam);
+        assert!(!loaded.allows_damage("Steve", "Alex"));
+        assert!(loaded.allows_damage("Steve", "Notch"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Who may join while the server is full. Players with a reserved slot may join past
+//! `max_players`, everyone else is turned away or waits in the join queue.
+
+use std::collections::VecDeque;
+
+use pumpkin_config::{BASIC_CONFIG, SlotsConfig, advanced_config};
+use tokio::sync::{Mutex, Notify};
+use uuid::Uuid;
+
+use crate::{data::op_data::OPERATOR_CONFIG, net::GameProfile};
+
+use super::Server;
+
+/// Whether the player is one of the reserved players in the config
+fn is_reserved(config: &SlotsConfig, profile: &GameProfile) -> bool {
+    config.reserved_players.iter().any(|entry| {
+        entry.eq_ignore_ascii_case(&profile.name)
+            || Uuid::parse_str(entry).is_ok_and(|uuid| uuid == profile.id)
+    })
+}
+
+/// Whether the player may take a reserved slot, like vanilla operators may bypass the limit
+pub async fn has_reserved_slot(profile: &GameProfile) -> bool {
+    is_reserved(&advanced_config().slots, profile)
+        || OPERATOR_CONFIG
+            .read()
+            .await
+            .ops
+            .iter()
+            .any(|op| op.uuid == profile.id && op.bypasses_player_limit)
+}
+
+/// How many players may be online for the player to join, `None` if there is no limit
+async fn capacity_for(profile: &GameProfile) -> Option<usize> {
+    let max = BASIC_CONFIG.max_players;
+    if max == 0 {
+        return None;
+    }
+    if !has_reserved_slot(profile).await {
+        return Some(max as usize);
+    }
+    match advanced_config().slots.reserved_slots {
+        0 => None,
+        reserved => Some(max.saturating_add(reserved) as usize),
+    }
+}
+
+/// Whether the player could join right now
+pub async fn has_free_slot(server: &Server, profile: &GameProfile) -> bool {
+    match capacity_for(profile).await {
+        Some(capacity) => server.get_player_count().await < capacity,
+        None => true,
+    }
+}
+
+/// The players waiting for a slot, first come first served
+#[derive(Default)]
+pub struct JoinQueue {
+    waiting: Mutex<VecDeque<Uuid>>,
+    slot_freed: Notify,
+}
+
+impl JoinQueue {
+    /// Returns the position in the queue, starting at 1, or `None` if the queue is full
+    pub async fn enqueue(&self, uuid: Uuid) -> Option<usize> {
+        let max_size = advanced_config().slots.queue.max_size as usize;
+        let mut waiting = self.waiting.lock().await;
+        if max_size != 0 && waiting.len() >= max_size {
+            return None;
+        }
+        waiting.push_back(uuid);
+        Some(waiting.len())
+    }
+
+    pub async fn leave(&self, uuid: Uuid) {
+        self.waiting.lock().await.retain(|waiting| *waiting != uuid);
+    }
+
+    pub async fn is_next(&self, uuid: Uuid) -> bool {
+        self.waiting.lock().await.front() == Some(&uuid)
+    }
+
+    /// Waits until a player left, the caller has to check again whether it's their turn
+    pub async fn slot_freed(&self) {
+        self.slot_freed.notified().await;
+    }
+
+    /// Lets the waiting players check again
+    pub fn free_slot(&self) {
+        self.slot_freed.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_config::SlotsConfig;
+    use uuid::Uuid;
+
+    use crate::net::GameProfile;
+
+    use super::{JoinQueue, is_reserved};
+
+    fn profile(name: &str, id: u128) -> GameProfile {
+        GameProfile {
+            id: Uuid::from_u128(id),
+            name: name.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        }
+    }
+
+    #[test]
+    fn reserved_by_name_or_uuid() {
+        let config = SlotsConfig {
+            reserved_players: vec![
+                "steve".to_string(),
+                Uuid::from_u128(2).hyphenated().to_string(),
+            ],
+            ..Default::default()
+        };
+        assert!(is_reserved(&config, &profile("Steve", 1)));
+        assert!(is_reserved(&config, &profile("Alex", 2)));
+        assert!(!is_reserved(&config, &profile("Herobrine", 3)));
+    }
+
+    #[tokio::test]
+    async fn first_come_first_served() {
+        let queue = JoinQueue::default();
+        assert_eq!(queue.enqueue(Uuid::from_u128(1)).await, Some(1));
+        assert_eq!(queue.enqueue(Uuid::from_u128(2)).await, Some(2));
+        assert!(queue.is_next(Uuid::from_u128(1)).await);
+        assert!(!queue.is_next(Uuid::from_u128(2)).await);
+
+        queue.leave(Uuid::from_u128(1)).await;
+        assert!(queue.is_next(Uuid::from_u128(2)).await);
+    }
+}
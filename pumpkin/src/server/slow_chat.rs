@@ -0,0 +1,62 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// A cooldown between the chat messages of a player, changed at runtime with `/slowchat`
+pub struct SlowChat {
+    cooldown_secs: AtomicU64,
+}
+
+impl SlowChat {
+    #[must_use]
+    pub fn new(cooldown_secs: u64) -> Self {
+        Self {
+            cooldown_secs: AtomicU64::new(cooldown_secs),
+        }
+    }
+
+    /// The cooldown, zero when slow chat is off
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_secs(self.cooldown_secs.load(Ordering::Relaxed))
+    }
+
+    pub fn set_cooldown(&self, cooldown_secs: u64) {
+        self.cooldown_secs.store(cooldown_secs, Ordering::Relaxed);
+    }
+
+    /// How long a player who last chatted at `last_message` still has to wait, `None` if the
+    /// player may chat now
+    pub fn remaining(&self, last_message: Option<Instant>, now: Instant) -> Option<Duration> {
+        let elapsed = now.duration_since(last_message?);
+        self.cooldown()
+            .checked_sub(elapsed)
+            .filter(|remaining| !remaining.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::SlowChat;
+
+    #[test]
+    fn waits_for_the_cooldown() {
+        let slow_chat = SlowChat::new(0);
+        let now = Instant::now();
+        let last = now.checked_sub(Duration::from_secs(2)).unwrap();
+        assert_eq!(slow_chat.remaining(Some(last), now), None);
+
+        slow_chat.set_cooldown(5);
+        assert_eq!(
+            slow_chat.remaining(Some(last), now),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(slow_chat.remaining(None, now), None);
+        assert_eq!(
+            slow_chat.remaining(Some(last), now + Duration::from_secs(3)),
+            None
+        );
+    }
+}
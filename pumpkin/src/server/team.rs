@@ -0,0 +1,211 @@
+use std::collections::BTreeSet;
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::{client::play::TeamInfo, codec::var_int::VarInt};
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+/// The color id clients use for a team without a color
+const RESET_COLOR: i32 = 21;
+
+pub struct Team {
+    pub display_name: TextComponent,
+    /// Colors the names of the members, `None` keeps them white
+    pub color: Option<NamedColor>,
+    /// Whether members can hurt each other
+    pub friendly_fire: bool,
+    /// Whether members see invisible members of their team as translucent
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: Visibility,
+    pub collision_rule: CollisionRule,
+    /// Shown before the names of the members
+    pub prefix: TextComponent,
+    /// Shown after the names of the members
+    pub suffix: TextComponent,
+    /// The names of the entities in the team
+    pub(super) members: BTreeSet<String>,
+}
+
+impl Team {
+    #[must_use]
+    pub fn new(display_name: TextComponent) -> Self {
+        Self {
+            display_name,
+            color: None,
+            friendly_fire: true,
+            see_friendly_invisibles: true,
+            name_tag_visibility: Visibility::Always,
+            collision_rule: CollisionRule::Always,
+            prefix: TextComponent::text(""),
+            suffix: TextComponent::text(""),
+            members: BTreeSet::new(),
+        }
+    }
+
+    /// The names of the entities in the team, sorted
+    pub fn members(&self) -> impl Iterator<Item = &str> {
+        self.members.iter().map(String::as_str)
+    }
+
+    /// The display name in the color of the team, how commands show it
+    #[must_use]
+    pub fn formatted_name(&self) -> TextComponent {
+        let name = self.display_name.clone();
+        match self.color {
+            Some(color) => name.color_named(color),
+            None => name,
+        }
+    }
+
+    pub(super) fn info(&self) -> TeamInfo<'_> {
+        TeamInfo {
+            display_name: &self.display_name,
+            friendly_fire: self.friendly_fire,
+            see_friendly_invisibles: self.see_friendly_invisibles,
+            name_tag_visibility: self.name_tag_visibility.name(),
+            collision_rule: self.collision_rule.name(),
+            color: VarInt(self.color.map_or(RESET_COLOR, |color| color as i32)),
+            prefix: &self.prefix,
+            suffix: &self.suffix,
+        }
+    }
+
+    /// Reads a team from the `Teams` list of `scoreboard.dat`, options which are missing keep
+    /// their defaults
+    pub(super) fn from_nbt(nbt: &NbtCompound) -> Option<(String, Self)> {
+        let name = nbt.get_string("Name")?;
+        let text = |key| {
+            nbt.get_string(key)
+                .and_then(|json| serde_json::from_str(json).ok())
+        };
+        let mut team =
+            Self::new(text("DisplayName").unwrap_or_else(|| TextComponent::text(name.clone())));
+        team.color = nbt
+            .get_string("TeamColor")
+            .and_then(|color| NamedColor::try_from(color.as_str()).ok());
+        if let Some(friendly_fire) = nbt.get_bool("AllowFriendlyFire") {
+            team.friendly_fire = friendly_fire;
+        }
+        if let Some(see_friendly_invisibles) = nbt.get_bool("SeeFriendlyInvisibles") {
+            team.see_friendly_invisibles = see_friendly_invisibles;
+        }
+        if let Some(visibility) = nbt
+            .get_string("NameTagVisibility")
+            .and_then(|name| Visibility::from_name(name))
+        {
+            team.name_tag_visibility = visibility;
+        }
+        if let Some(rule) = nbt
+            .get_string("CollisionRule")
+            .and_then(|name| CollisionRule::from_name(name))
+        {
+            team.collision_rule = rule;
+        }
+        if let Some(prefix) = text("MemberNamePrefix") {
+            team.prefix = prefix;
+        }
+        if let Some(suffix) = text("MemberNameSuffix") {
+            team.suffix = suffix;
+        }
+        team.members = nbt
+            .get_list("Players")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|player| player.extract_string().cloned())
+            .collect();
+        Some((name.clone(), team))
+    }
+
+    pub(super) fn to_nbt(&self, name: &str) -> NbtCompound {
+        let text = |text: &TextComponent| serde_json::to_string(text).unwrap_or_default();
+        let mut nbt = NbtCompound::new();
+        nbt.put_string("Name", name.to_string());
+        nbt.put_string("DisplayName", text(&self.display_name));
+        if let Some(color) = self.color {
+            nbt.put_string("TeamColor", color.name().to_string());
+        }
+        nbt.put_bool("AllowFriendlyFire", self.friendly_fire);
+        nbt.put_bool("SeeFriendlyInvisibles", self.see_friendly_invisibles);
+        nbt.put_string(
+            "NameTagVisibility",
+            self.name_tag_visibility.name().to_string(),
+        );
+        nbt.put_string("CollisionRule", self.collision_rule.name().to_string());
+        nbt.put_string("MemberNamePrefix", text(&self.prefix));
+        nbt.put_string("MemberNameSuffix", text(&self.suffix));
+        let players: Vec<_> = self
+            .members
+            .iter()
+            .map(|member| NbtTag::String(member.clone()))
+            .collect();
+        nbt.put_list("Players", players.into_boxed_slice());
+        nbt
+    }
+}
+
+/// Who can see the name tags of the members
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Visibility {
+    Always,
+    Never,
+    HideForOtherTeams,
+    HideForOwnTeam,
+}
+
+impl Visibility {
+    pub const ALL: [Self; 4] = [
+        Self::Always,
+        Self::Never,
+        Self::HideForOtherTeams,
+        Self::HideForOwnTeam,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::HideForOtherTeams => "hideForOtherTeams",
+            Self::HideForOwnTeam => "hideForOwnTeam",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|visibility| visibility.name() == name)
+    }
+}
+
+/// Which entities push the members around
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CollisionRule {
+    Always,
+    Never,
+    PushOtherTeams,
+    PushOwnTeam,
+}
+
+impl CollisionRule {
+    pub const ALL: [Self; 4] = [
+        Self::Always,
+        Self::Never,
+        Self::PushOtherTeams,
+        Self::PushOwnTeam,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::PushOtherTeams => "pushOtherTeams",
+            Self::PushOwnTeam => "pushOwnTeam",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+}
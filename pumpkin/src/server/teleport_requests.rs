@@ -0,0 +1,128 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use uuid::Uuid;
+
+struct Request {
+    from: Uuid,
+    sent: Instant,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RequestError {
+    /// The player has to wait this long until they may send another request
+    Cooldown(Duration),
+    /// The player already asked the same player and is still waiting for an answer
+    AlreadySent,
+}
+
+/// Players asking others to teleport to them, see `/tpa`. Requests nobody answers run out
+pub struct TeleportRequests {
+    expiry: Duration,
+    cooldown: Duration,
+    /// The requests each player got, the latest last
+    pending: HashMap<Uuid, Vec<Request>>,
+    /// When each player sent their last request
+    last_sent: HashMap<Uuid, Instant>,
+}
+
+impl TeleportRequests {
+    #[must_use]
+    pub fn new(expiry: Duration, cooldown: Duration) -> Self {
+        Self {
+            expiry,
+            cooldown,
+            pending: HashMap::new(),
+            last_sent: HashMap::new(),
+        }
+    }
+
+    /// Lets `from` ask `to` whether they may teleport to them
+    pub fn send(&mut self, from: Uuid, to: Uuid, now: Instant) -> Result<(), RequestError> {
+        self.remove_expired(now);
+        if let Some(last) = self.last_sent.get(&from) {
+            let remaining = self.cooldown.saturating_sub(now.duration_since(*last));
+            if !remaining.is_zero() {
+                return Err(RequestError::Cooldown(remaining));
+            }
+        }
+        let requests = self.pending.entry(to).or_default();
+        if requests.iter().any(|request| request.from == from) {
+            return Err(RequestError::AlreadySent);
+        }
+        requests.push(Request { from, sent: now });
+        self.last_sent.insert(from, now);
+        Ok(())
+    }
+
+    /// Removes the request `to` got from `from`, or the latest one they got if `from` is `None`,
+    /// and returns who sent it
+    pub fn take(&mut self, to: Uuid, from: Option<Uuid>, now: Instant) -> Option<Uuid> {
+        self.remove_expired(now);
+        let requests = self.pending.get_mut(&to)?;
+        let index = match from {
+            Some(from) => requests.iter().position(|request| request.from == from)?,
+            None => requests.len().checked_sub(1)?,
+        };
+        let request = requests.remove(index);
+        if requests.is_empty() {
+            self.pending.remove(&to);
+        }
+        Some(request.from)
+    }
+
+    /// Drops the requests the player sent or got, like when they leave
+    pub fn forget(&mut self, player: Uuid) {
+        self.pending.remove(&player);
+        for requests in self.pending.values_mut() {
+            requests.retain(|request| request.from != player);
+        }
+        self.pending.retain(|_, requests| !requests.is_empty());
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        for requests in self.pending.values_mut() {
+            requests.retain(|request| now.duration_since(request.sent) < self.expiry);
+        }
+        self.pending.retain(|_, requests| !requests.is_empty());
+        self.last_sent
+            .retain(|_, sent| now.duration_since(*sent) < self.cooldown);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use uuid::Uuid;
+
+    use super::{RequestError, TeleportRequests};
+
+    #[test]
+    fn requests_expire_and_have_a_cooldown() {
+        let mut requests = TeleportRequests::new(Duration::from_secs(60), Duration::from_secs(10));
+        let (alex, steve, notch) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let now = Instant::now();
+
+        requests.send(alex, steve, now).unwrap();
+        assert_eq!(
+            requests.send(alex, notch, now + Duration::from_secs(4)),
+            Err(RequestError::Cooldown(Duration::from_secs(6)))
+        );
+        assert_eq!(
+            requests.send(alex, steve, now + Duration::from_secs(10)),
+            Err(RequestError::AlreadySent)
+        );
+        requests.send(notch, steve, now).unwrap();
+
+        // The latest request is answered first
+        assert_eq!(requests.take(steve, None, now), Some(notch));
+        assert_eq!(requests.take(steve, Some(notch), now), None);
+        assert_eq!(
+            requests.take(steve, Some(alex), now + Duration::from_secs(60)),
+            None
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::{plugin::EventPriority, world::World};
+
+use super::Server;
+
+/// The phases of a world tick, in the order they are run.
+///
+/// Every phase first runs the built-in logic and then the hooks registered for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TickPhase {
+    /// Advances the world age and day time
+    Time,
+    /// Advances rain and thunder
+    Weather,
+    /// Runs scheduled block and fluid ticks and random block ticks
+    ScheduledTicks,
+    /// Ticks players and then every other entity
+    Entities,
+    /// Ticks block entities
+    BlockEntities,
+    /// Hands the packets sent to the players of the world during the tick to their connections,
+    /// so each connection writes them together
+    NetworkFlush,
+}
+
+impl TickPhase {
+    pub const ORDER: [Self; 6] = [
+        Self::Time,
+        Self::Weather,
+        Self::ScheduledTicks,
+        Self::Entities,
+        Self::BlockEntities,
+        Self::NetworkFlush,
+    ];
+}
+
+/// A hook that is run every tick for each world, after the built-in logic of its `TickPhase`.
+#[async_trait]
+pub trait TickHook: Send + Sync {
+    async fn on_tick(&self, phase: TickPhase, world: &World, server: &Server);
+}
+
+struct RegisteredTickHook {
+    phase: TickPhase,
+    priority: EventPriority,
+    hook: Arc<dyn TickHook>,
+}
+
+/// The hooks run by the tick orchestrator.
+///
+/// Hooks of the same phase run ordered by their priority (highest first), hooks with the same
+/// priority run in registration order.
+#[derive(Default)]
+pub struct TickHooks {
+    hooks: RwLock<Vec<RegisteredTickHook>>,
+}
+
+impl TickHooks {
+    pub async fn register(
+        &self,
+        phase: TickPhase,
+        priority: EventPriority,
+        hook: Arc<dyn TickHook>,
+    ) {
+        let mut hooks = self.hooks.write().await;
+        // Insert after every hook that runs before or together with this one, keeping the
+        // registration order for hooks with the same phase and priority
+        let index = hooks.partition_point(|registered| {
+            (registered.phase, &registered.priority) <= (phase, &priority)
+        });
+        hooks.insert(
+            index,
+            RegisteredTickHook {
+                phase,
+                priority,
+                hook,
+            },
+        );
+    }
+
+    /// Removes a previously registered hook, returns whether it was registered
+    pub async fn unregister(&self, hook: &Arc<dyn TickHook>) -> bool {
+        let mut hooks = self.hooks.write().await;
+        let len = hooks.len();
+        hooks.retain(|registered| !Arc::ptr_eq(&registered.hook, hook));
+        hooks.len() != len
+    }
+
+    /// The hooks of the phase, in the order they run
+    async fn hooks_of(&self, phase: TickPhase) -> Vec<Arc<dyn TickHook>> {
+        self.hooks
+            .read()
+            .await
+            .iter()
+            .filter(|registered| registered.phase == phase)
+            .map(|registered| registered.hook.clone())
+            .collect()
+    }
+
+    pub async fn run(&self, phase: TickPhase, world: &World, server: &Server) {
+        // Clone the hooks so they can register other hooks while running
+        for hook in self.hooks_of(phase).await {
+            hook.on_tick(phase, world, server).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+
+    use super::{TickHook, TickHooks, TickPhase};
+    use crate::{plugin::EventPriority, server::Server, world::World};
+
+    struct NoopHook;
+
+    #[async_trait]
+    impl TickHook for NoopHook {
+        async fn on_tick(&self, _phase: TickPhase, _world: &World, _server: &Server) {}
+    }
+
+    fn positions(hooks: &[Arc<dyn TickHook>], registered: &[Arc<dyn TickHook>]) -> Vec<usize> {
+        hooks
+            .iter()
+            .map(|hook| {
+                registered
+                    .iter()
+                    .position(|registered| Arc::ptr_eq(registered, hook))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn hooks_run_by_priority_then_registration_order() {
+        let hooks = TickHooks::default();
+        let registered: Vec<Arc<dyn TickHook>> = (0..6).map(|_| Arc::new(NoopHook) as _).collect();
+        let order = [
+            (TickPhase::Entities, EventPriority::Normal),
+            (TickPhase::Time, EventPriority::Lowest),
+            (TickPhase::Entities, EventPriority::Highest),
+            (TickPhase::Entities, EventPriority::Normal),
+            (TickPhase::Time, EventPriority::High),
+            (TickPhase::Entities, EventPriority::Low),
+        ];
+        for (hook, (phase, priority)) in registered.iter().zip(order) {
+            hooks.register(phase, priority, hook.clone()).await;
+        }
+
+        let time = hooks.hooks_of(TickPhase::Time).await;
+        assert_eq!(positions(&time, &registered), [4, 1]);
+        let entities = hooks.hooks_of(TickPhase::Entities).await;
+        assert_eq!(positions(&entities, &registered), [2, 0, 3, 5]);
+        assert!(hooks.hooks_of(TickPhase::Weather).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unregistered_hooks_stop_running() {
+        let hooks = TickHooks::default();
+        let hook: Arc<dyn TickHook> = Arc::new(NoopHook);
+        hooks
+            .register(TickPhase::Time, EventPriority::Normal, hook.clone())
+            .await;
+        assert!(hooks.unregister(&hook).await);
+        assert!(!hooks.unregister(&hook).await);
+        assert!(hooks.hooks_of(TickPhase::Time).await.is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crossbeam::atomic::AtomicCell;
+use pumpkin_protocol::client::play::{CTickingState, CTickingStep};
+
+pub const MIN_TICK_RATE: f32 = 1.0;
+pub const MAX_TICK_RATE: f32 = 10000.0;
+
+/// State of a running `/tick sprint`
+struct Sprint {
+    remaining_ticks: u64,
+    total_ticks: u64,
+    started: Instant,
+    /// Whether the game was frozen before the sprint started, restored once it ends
+    was_frozen: bool,
+}
+
+/// Summary of a finished sprint
+pub struct SprintReport {
+    pub ticks: u64,
+    pub elapsed: Duration,
+}
+
+impl SprintReport {
+    /// The ticks per second reached while sprinting
+    #[must_use]
+    pub fn ticks_per_second(&self) -> f64 {
+        self.ticks as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// The average time one tick took while sprinting, in milliseconds
+    #[must_use]
+    pub fn millis_per_tick(&self) -> f64 {
+        self.elapsed.as_secs_f64() * 1000.0 / self.ticks.max(1) as f64
+    }
+}
+
+/// Controls how fast the game loop runs, see the `/tick` command.
+///
+/// While frozen, only players are ticked unless steps are requested. While sprinting, the
+/// ticker runs as fast as possible until the sprint is over.
+pub struct TickManager {
+    tick_rate: AtomicCell<f32>,
+    frozen: AtomicBool,
+    frozen_steps: AtomicU32,
+    sprint: Mutex<Option<Sprint>>,
+    /// Ticks run so far and how long they took together, to measure the TPS
+    ticks: AtomicU64,
+    tick_nanos: AtomicU64,
+}
+
+impl TickManager {
+    #[must_use]
+    pub fn new(tick_rate: f32) -> Self {
+        Self {
+            tick_rate: AtomicCell::new(tick_rate.clamp(MIN_TICK_RATE, MAX_TICK_RATE)),
+            frozen: AtomicBool::new(false),
+            frozen_steps: AtomicU32::new(0),
+            sprint: Mutex::new(None),
+            ticks: AtomicU64::new(0),
+            tick_nanos: AtomicU64::new(0),
+        }
+    }
+
+    #[must_use]
+    pub fn tick_rate(&self) -> f32 {
+        self.tick_rate.load()
+    }
+
+    pub fn set_tick_rate(&self, tick_rate: f32) {
+        self.tick_rate
+            .store(tick_rate.clamp(MIN_TICK_RATE, MAX_TICK_RATE));
+    }
+
+    /// The time between the start of two ticks for the current tick rate
+    #[must_use]
+    pub fn tick_interval(&self) -> Duration {
+        Duration::from_secs_f32(1.0 / self.tick_rate())
+    }
+
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.load(Ordering::Relaxed)
+    }
+
+    pub fn set_frozen(&self, frozen: bool) {
+        self.frozen.store(frozen, Ordering::Relaxed);
+        if !frozen {
+            self.frozen_steps.store(0, Ordering::Relaxed);
+        }
+    }
+
+    #[must_use]
+    pub fn frozen_steps(&self) -> u32 {
+        self.frozen_steps.load(Ordering::Relaxed)
+    }
+
+    #[must_use]
+    pub fn is_stepping(&self) -> bool {
+        self.frozen_steps() > 0
+    }
+
+    /// Runs the given amount of ticks while frozen, returns false if the game is not frozen
+    pub fn step(&self, ticks: u32) -> bool {
+        if !self.is_frozen() {
+            return false;
+        }
+        self.frozen_steps.store(ticks, Ordering::Relaxed);
+        true
+    }
+
+    /// Stops the steps left, returns false if there were none
+    pub fn stop_stepping(&self) -> bool {
+        self.frozen_steps.swap(0, Ordering::Relaxed) > 0
+    }
+
+    #[must_use]
+    pub fn is_sprinting(&self) -> bool {
+        self.sprint.lock().unwrap().is_some()
+    }
+
+    /// Runs the given amount of ticks as fast as possible, returns true if this replaced a running
+    /// sprint
+    pub fn start_sprint(&self, ticks: u64) -> bool {
+        let mut sprint = self.sprint.lock().unwrap();
+        let previous = sprint.take();
+        let interrupted = previous.is_some();
+        let was_frozen = previous.map_or_else(|| self.is_frozen(), |running| running.was_frozen);
+        self.frozen_steps.store(0, Ordering::Relaxed);
+        self.frozen.store(false, Ordering::Relaxed);
+        *sprint = Some(Sprint {
+            remaining_ticks: ticks,
+            total_ticks: ticks,
+            started: Instant::now(),
+            was_frozen,
+        });
+        interrupted
+    }
+
+    /// Ends the running sprint, if any
+    pub fn stop_sprint(&self) -> Option<SprintReport> {
+        let sprint = self.sprint.lock().unwrap().take()?;
+        Some(self.finish_sprint(&sprint))
+    }
+
+    fn finish_sprint(&self, sprint: &Sprint) -> SprintReport {
+        self.frozen.store(sprint.was_frozen, Ordering::Relaxed);
+        SprintReport {
+            ticks: sprint.total_ticks - sprint.remaining_ticks,
+            elapsed: sprint.started.elapsed(),
+        }
+    }
+
+    /// Counts one tick towards the running sprint, returns the report when the sprint is over
+    pub(crate) fn advance_sprint(&self) -> Option<SprintReport> {
+        let mut guard = self.sprint.lock().unwrap();
+        let sprint = guard.as_mut()?;
+        sprint.remaining_ticks = sprint.remaining_ticks.saturating_sub(1);
+        if sprint.remaining_ticks > 0 {
+            return None;
+        }
+        let sprint = guard.take()?;
+        Some(self.finish_sprint(&sprint))
+    }
+
+    /// Decides whether the upcoming tick runs normally, consuming a step if the game is frozen
+    pub(crate) fn begin_tick(&self) -> bool {
+        if !self.is_frozen() {
+            return true;
+        }
+        self.frozen_steps
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |steps| {
+                steps.checked_sub(1)
+            })
+            .is_ok()
+    }
+
+    /// Counts a tick which just ran and took `elapsed`
+    pub(crate) fn record_tick(&self, elapsed: Duration) {
+        self.ticks.fetch_add(1, Ordering::Relaxed);
+        self.tick_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// How many ticks ran since the server started
+    #[must_use]
+    pub fn tick_count(&self) -> u64 {
+        self.ticks.load(Ordering::Relaxed)
+    }
+
+    /// The time all ticks since the server started took together
+    #[must_use]
+    pub fn total_tick_time(&self) -> Duration {
+        Duration::from_nanos(self.tick_nanos.load(Ordering::Relaxed))
+    }
+
+    #[must_use]
+    pub fn state_packet(&self) -> CTickingState {
+        CTickingState::new(self.tick_rate(), self.is_frozen())
+    }
+
+    #[must_use]
+    pub fn step_packet(&self) -> CTickingStep {
+        CTickingStep::new(self.frozen_steps().into())
+    }
+}
@@ -1,40 +1,165 @@
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use tokio::time::sleep;
+use tokio::time::sleep_until;
 
 use crate::SHOULD_STOP;
 
 use super::Server;
 
+/// When the ticker falls further behind than this, the missed ticks are skipped instead of being
+/// caught up
+const MAX_CATCH_UP: Duration = Duration::from_secs(2);
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    /// The next tick isn't due yet
+    Wait,
+    /// Run a tick now, the one after is due at `next_tick`
+    Tick { next_tick: Instant, skipped: u128 },
+}
+
+/// What the ticker does at `now` when the next tick is due at `next_tick`. Ticks which are due
+/// already run back to back, unless the ticker is more than `MAX_CATCH_UP` behind, then they are
+/// skipped
+fn next_step(now: Instant, next_tick: Instant, tick_interval: Duration) -> Step {
+    if now < next_tick {
+        return Step::Wait;
+    }
+    let behind = now - next_tick;
+    if behind > MAX_CATCH_UP {
+        return Step::Tick {
+            next_tick: now + tick_interval,
+            skipped: behind.as_nanos() / tick_interval.as_nanos(),
+        };
+    }
+    Step::Tick {
+        next_tick: next_tick + tick_interval,
+        skipped: 0,
+    }
+}
+
+/// The single game loop of the server.
+///
+/// Ticks run at the rate of the server's `TickManager`. When a tick takes longer than the tick
+/// interval, the following ticks run back to back until the ticker caught up, unless it is more
+/// than `MAX_CATCH_UP` behind. While sprinting, ticks run back to back without waiting.
 pub struct Ticker {
-    tick_interval: Duration,
-    last_tick: Instant,
+    next_tick: Instant,
+}
+
+impl Default for Ticker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Ticker {
     #[must_use]
-    pub fn new(tps: f32) -> Self {
+    pub fn new() -> Self {
         Self {
-            tick_interval: Duration::from_millis((1000.0 / tps) as u64),
-            last_tick: Instant::now(),
+            next_tick: Instant::now(),
         }
     }
 
     /// IMPORTANT: Run this in a new thread/tokio task
-    pub async fn run(&mut self, server: &Server) {
+    pub async fn run(&mut self, server: &Arc<Server>) {
         while !SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
             let now = Instant::now();
-            let elapsed = now - self.last_tick;
-
-            if elapsed >= self.tick_interval {
+            if server.tick_manager.is_sprinting() {
                 server.tick().await;
-                self.last_tick = now;
-            } else {
-                // Wait for the remaining time until the next tick
-                let sleep_time = self.tick_interval - elapsed;
-                sleep(sleep_time).await;
+                server.tick_manager.record_tick(now.elapsed());
+                if let Some(report) = server.tick_manager.advance_sprint() {
+                    server.finish_sprint(&report).await;
+                }
+                self.next_tick = Instant::now();
+                continue;
+            }
+
+            // The rate can change at any time, so it is read again for every tick
+            let tick_interval = server.tick_manager.tick_interval();
+            match next_step(now, self.next_tick, tick_interval) {
+                Step::Wait => {
+                    // Wait for the remaining time until the next tick
+                    sleep_until(self.next_tick.into()).await;
+                }
+                Step::Tick { next_tick, skipped } => {
+                    if skipped > 0 {
+                        log::warn!(
+                            "Can't keep up! Is the server overloaded? Running {}ms or {} ticks behind",
+                            (now - self.next_tick).as_millis(),
+                            skipped
+                        );
+                    }
+                    server.tick().await;
+                    server.tick_manager.record_tick(now.elapsed());
+                    self.next_tick = next_tick;
+                }
             }
         }
         log::debug!("Ticker stopped");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{MAX_CATCH_UP, Step, next_step};
+
+    const INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Runs every tick that is due at `now`, returning how many ran and when the next one is due
+    fn catch_up(now: Instant, mut next_tick: Instant) -> (u32, Instant) {
+        let mut ticks = 0;
+        while let Step::Tick {
+            next_tick: next, ..
+        } = next_step(now, next_tick, INTERVAL)
+        {
+            ticks += 1;
+            next_tick = next;
+        }
+        (ticks, next_tick)
+    }
+
+    #[test]
+    fn waits_for_the_next_tick() {
+        let now = Instant::now();
+        assert_eq!(next_step(now, now + INTERVAL, INTERVAL), Step::Wait);
+        assert_eq!(
+            next_step(now, now, INTERVAL),
+            Step::Tick {
+                next_tick: now + INTERVAL,
+                skipped: 0
+            }
+        );
+    }
+
+    #[test]
+    fn catches_up_on_missed_ticks() {
+        let start = Instant::now();
+        let now = start + Duration::from_secs(1);
+        let (ticks, next_tick) = catch_up(now, start);
+        // The tick that was due at the start and the 20 missed since
+        assert_eq!(ticks, 21);
+        assert_eq!(next_tick, start + INTERVAL * 21);
+    }
+
+    #[test]
+    fn skips_ticks_beyond_max_catch_up() {
+        let start = Instant::now();
+        let behind = MAX_CATCH_UP + Duration::from_secs(1);
+        let now = start + behind;
+        assert_eq!(
+            next_step(now, start, INTERVAL),
+            Step::Tick {
+                next_tick: now + INTERVAL,
+                skipped: behind.as_nanos() / INTERVAL.as_nanos()
+            }
+        );
+        // Only the one tick runs, the next is due an interval later again
+        assert_eq!(catch_up(now, start), (1, now + INTERVAL));
+    }
+}
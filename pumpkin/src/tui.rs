@@ -0,0 +1,293 @@
+//! A dashboard in the terminal, started with `--tui`. It shows the TPS, the players, the
+//! estimated memory and the chunk IO rates next to the log, with a box to type commands into.
+//!
+//! Logs are written into [`LogBuffer`] instead of the terminal while the dashboard runs.
+
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    sync::{Arc, LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_world::chunk::io::io_metrics::ChunkIoMetrics;
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, List, Paragraph},
+};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{
+    SHOULD_STOP, run_console_command,
+    server::{
+        Server,
+        memory::{MIB, WorldMemory},
+    },
+    stop_server,
+};
+
+/// How many log lines are kept for the dashboard
+const MAX_LOG_LINES: usize = 1000;
+/// How often the stats are gathered again
+const STATS_INTERVAL: Duration = Duration::from_secs(1);
+/// How long to wait for a key before drawing again
+const INPUT_POLL: Duration = Duration::from_millis(100);
+
+static ENABLED: LazyLock<bool> = LazyLock::new(|| std::env::args().any(|arg| arg == "--tui"));
+
+static LOG_BUFFER: LazyLock<Mutex<LogBuffer>> = LazyLock::new(|| Mutex::new(LogBuffer::default()));
+
+/// Whether the server was started with `--tui`
+#[must_use]
+pub fn enabled() -> bool {
+    *ENABLED
+}
+
+/// The latest log lines
+#[derive(Default)]
+pub struct LogBuffer {
+    lines: VecDeque<String>,
+    /// What was written of the line which isn't finished yet
+    partial: String,
+}
+
+impl LogBuffer {
+    fn push(&mut self, text: &str) {
+        self.partial.push_str(text);
+        while let Some(end) = self.partial.find('\n') {
+            let line = self.partial[..end].trim_end_matches('\r').to_string();
+            self.partial.drain(..=end);
+            if self.lines.len() == MAX_LOG_LINES {
+                self.lines.pop_front();
+            }
+            self.lines.push_back(line);
+        }
+    }
+}
+
+/// Where the logger writes to while the dashboard runs
+pub struct LogWriter;
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        LOG_BUFFER
+            .lock()
+            .unwrap()
+            .push(&String::from_utf8_lossy(buf));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// What the dashboard shows besides the log
+#[derive(Default, Clone)]
+struct Stats {
+    tps: f64,
+    millis_per_tick: f64,
+    players: Vec<String>,
+    memory_bytes: usize,
+    /// Chunk IO per second
+    io: ChunkIoMetrics,
+}
+
+/// Keeps the stats of the dashboard up to date, rates are measured between two updates
+async fn gather_stats(server: Arc<Server>, stats: Arc<Mutex<Stats>>) {
+    let mut last_time = Instant::now();
+    let mut last_ticks = server.tick_manager.tick_count();
+    let mut last_tick_time = server.tick_manager.total_tick_time();
+    let mut last_io = ChunkIoMetrics::default();
+
+    while !SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
+        tokio::time::sleep(STATS_INTERVAL).await;
+
+        let elapsed = last_time.elapsed().as_secs_f64();
+        let ticks = server.tick_manager.tick_count();
+        let tick_time = server.tick_manager.total_tick_time();
+        let tick_delta = ticks - last_ticks;
+
+        let mut memory_bytes = 0;
+        let mut io = ChunkIoMetrics::default();
+        for world in server.worlds.iter() {
+            memory_bytes += WorldMemory::of(world).await.total_bytes();
+            let metrics = world.level.chunk_io_metrics();
+            io.reads += metrics.reads;
+            io.writes += metrics.writes;
+            io.bytes_read += metrics.bytes_read;
+            io.bytes_written += metrics.bytes_written;
+        }
+        let per_second = |now: u64, before: u64| ((now - before) as f64 / elapsed) as u64;
+
+        let new_stats = Stats {
+            tps: tick_delta as f64 / elapsed,
+            millis_per_tick: (tick_time - last_tick_time).as_secs_f64() * 1000.0
+                / tick_delta.max(1) as f64,
+            players: server
+                .get_all_players()
+                .await
+                .iter()
+                .map(|player| player.gameprofile.name.clone())
+                .collect(),
+            memory_bytes,
+            io: ChunkIoMetrics {
+                reads: per_second(io.reads, last_io.reads),
+                writes: per_second(io.writes, last_io.writes),
+                bytes_read: per_second(io.bytes_read, last_io.bytes_read),
+                bytes_written: per_second(io.bytes_written, last_io.bytes_written),
+                ..Default::default()
+            },
+        };
+        *stats.lock().unwrap() = new_stats;
+
+        last_time = Instant::now();
+        last_ticks = ticks;
+        last_tick_time = tick_time;
+        last_io = io;
+    }
+}
+
+/// Starts the dashboard, which takes over the terminal until the server stops
+pub fn start(server: Arc<Server>) -> JoinHandle<()> {
+    let stats = Arc::new(Mutex::new(Stats::default()));
+    tokio::spawn(gather_stats(server.clone(), stats.clone()));
+
+    let (commands, mut received) = mpsc::unbounded_channel::<String>();
+    tokio::spawn(async move {
+        while let Some(line) = received.recv().await {
+            run_console_command(&server, line).await;
+        }
+    });
+
+    tokio::task::spawn_blocking(move || {
+        let mut terminal = ratatui::init();
+        if let Err(err) = run(&mut terminal, &stats, &commands) {
+            log::error!("The dashboard failed: {err}");
+        }
+        ratatui::restore();
+        log::debug!("Stopped the dashboard");
+    })
+}
+
+fn run(
+    terminal: &mut DefaultTerminal,
+    stats: &Mutex<Stats>,
+    commands: &mpsc::UnboundedSender<String>,
+) -> io::Result<()> {
+    let mut input = String::new();
+    while !SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
+        let stats = stats.lock().unwrap().clone();
+        terminal.draw(|frame| draw(frame, &stats, &input))?;
+
+        if !event::poll(INPUT_POLL)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => stop_server(),
+            KeyCode::Char(char) => input.push(char),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter if !input.is_empty() => {
+                let _ = commands.send(std::mem::take(&mut input));
+            }
+            KeyCode::Esc => input.clear(),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn draw(frame: &mut Frame, stats: &Stats, input: &str) {
+    let [header, body, input_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(3),
+    ])
+    .areas(frame.area());
+    let [log_area, players_area] =
+        Layout::horizontal([Constraint::Min(0), Constraint::Length(24)]).areas(body);
+
+    let tps_color = if stats.tps >= 19.0 {
+        Color::Green
+    } else if stats.tps >= 15.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+    let header_text = Line::from(vec![
+        ratatui::text::Span::styled(
+            format!("TPS {:.1} ({:.1} mspt)", stats.tps, stats.millis_per_tick),
+            Style::default().fg(tps_color),
+        ),
+        ratatui::text::Span::raw(format!(
+            " | Memory {} MiB | Chunk IO {} reads/s ({:.1} MiB/s), {} writes/s ({:.1} MiB/s)",
+            stats.memory_bytes / MIB,
+            stats.io.reads,
+            stats.io.bytes_read as f64 / MIB as f64,
+            stats.io.writes,
+            stats.io.bytes_written as f64 / MIB as f64,
+        )),
+    ]);
+    frame.render_widget(
+        Paragraph::new(header_text).block(Block::bordered().title("Pumpkin")),
+        header,
+    );
+
+    // Only the lines that fit are shown, the newest at the bottom
+    let visible = log_area.height.saturating_sub(2) as usize;
+    let lines: Vec<_> = {
+        let buffer = LOG_BUFFER.lock().unwrap();
+        let skip = buffer.lines.len().saturating_sub(visible);
+        buffer.lines.iter().skip(skip).cloned().collect()
+    };
+    frame.render_widget(
+        List::new(lines).block(Block::bordered().title("Log")),
+        log_area,
+    );
+
+    let players_title = format!(
+        "Players {}/{}",
+        stats.players.len(),
+        BASIC_CONFIG.max_players
+    );
+    frame.render_widget(
+        List::new(stats.players.clone()).block(Block::bordered().title(players_title)),
+        players_area,
+    );
+
+    frame.render_widget(
+        Paragraph::new(format!("/{input}")).block(Block::bordered().title("Command")),
+        input_area,
+    );
+    // After the slash and the border
+    frame.set_cursor_position((input_area.x + 2 + input.len() as u16, input_area.y + 1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogBuffer;
+
+    #[test]
+    fn splits_lines_written_in_parts() {
+        let mut buffer = LogBuffer::default();
+        buffer.push("first ");
+        buffer.push("line\nsecond\r\nthi");
+        assert_eq!(buffer.lines, ["first line", "second"]);
+        buffer.push("rd\n");
+        assert_eq!(buffer.lines.back().unwrap(), "third");
+        assert!(buffer.partial.is_empty());
+    }
+}
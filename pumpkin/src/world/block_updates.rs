@@ -0,0 +1,208 @@
+//! Neighbor updates are queued and run one after another instead of recursively, like in vanilla.
+//! Every update knows how deep in a cascade it is, so contraptions or bugs updating each other
+//! forever are cut off by a depth limit and a budget of updates per tick instead of hanging the
+//! server.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
+};
+
+use pumpkin_config::BlockUpdatesConfig;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::BlockDirection;
+
+/// A block being told that its neighbor changed
+pub struct NeighborUpdate {
+    /// The block being updated
+    pub position: BlockPos,
+    /// The block which changed
+    pub source: BlockPos,
+    /// The direction from the source to the updated block
+    pub direction: BlockDirection,
+    /// How many updates caused this one, `0` for an update caused by something else, like a
+    /// player placing a block
+    pub depth: u32,
+}
+
+/// Updates which were dropped, logged once the queue ran empty
+#[derive(Default)]
+pub struct DroppedUpdates {
+    /// Dropped for being deeper than `max_depth`
+    pub too_deep: u32,
+    /// Dropped because the budget of the tick ran out
+    pub over_budget: u32,
+    /// Where the first dropped update was
+    pub first: Option<BlockPos>,
+}
+
+impl DroppedUpdates {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.too_deep == 0 && self.over_budget == 0
+    }
+}
+
+/// The queue of neighbor updates of a world
+pub struct BlockUpdates {
+    max_depth: u32,
+    max_per_tick: u32,
+    queue: Mutex<VecDeque<NeighborUpdate>>,
+    /// Whether a task is running the queued updates, others only add to the queue then
+    running: AtomicBool,
+    /// The depth of the update being run, updates it causes are one deeper
+    current_depth: AtomicU32,
+    /// How many updates ran this tick
+    this_tick: AtomicU32,
+    dropped: Mutex<DroppedUpdates>,
+}
+
+impl BlockUpdates {
+    #[must_use]
+    pub fn new(config: &BlockUpdatesConfig) -> Self {
+        Self {
+            max_depth: config.max_depth,
+            max_per_tick: config.max_per_tick,
+            queue: Mutex::new(VecDeque::new()),
+            running: AtomicBool::new(false),
+            current_depth: AtomicU32::new(0),
+            this_tick: AtomicU32::new(0),
+            dropped: Mutex::new(DroppedUpdates::default()),
+        }
+    }
+
+    /// Queues updates for the neighbors of the block, except the one in `except`
+    pub fn queue_neighbors(&self, source: &BlockPos, except: Option<&BlockDirection>) {
+        let depth = if self.running.load(Ordering::Acquire) {
+            self.current_depth.load(Ordering::Relaxed) + 1
+        } else {
+            0
+        };
+        let mut queue = self.queue.lock().unwrap();
+        for direction in BlockDirection::update_order() {
+            if Some(&direction) == except {
+                continue;
+            }
+            queue.push_back(NeighborUpdate {
+                position: source.offset(direction.to_offset()),
+                source: *source,
+                direction,
+                depth,
+            });
+        }
+    }
+
+    /// Makes this task the one running the queue, returns false if another one already does
+    pub fn start(&self) -> bool {
+        self.running
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    /// Stops running the queue, returns true if updates were queued in the meantime, which the
+    /// caller should run by starting again
+    pub fn stop(&self) -> bool {
+        self.running.store(false, Ordering::Release);
+        !self.queue.lock().unwrap().is_empty()
+    }
+
+    /// The next update to run. Updates beyond the depth limit are dropped, and once the budget
+    /// of the tick ran out the whole queue is
+    pub fn next(&self) -> Option<NeighborUpdate> {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            let update = queue.pop_front()?;
+            if update.depth > self.max_depth {
+                self.drop_updates(&update.position, 1, 0);
+                continue;
+            }
+            if self.this_tick.fetch_add(1, Ordering::Relaxed) >= self.max_per_tick {
+                let dropped = queue.len() as u32 + 1;
+                queue.clear();
+                self.drop_updates(&update.position, 0, dropped);
+                return None;
+            }
+            self.current_depth.store(update.depth, Ordering::Relaxed);
+            return Some(update);
+        }
+    }
+
+    fn drop_updates(&self, position: &BlockPos, too_deep: u32, over_budget: u32) {
+        let mut dropped = self.dropped.lock().unwrap();
+        dropped.too_deep += too_deep;
+        dropped.over_budget += over_budget;
+        dropped.first.get_or_insert(*position);
+    }
+
+    /// The updates dropped since the last call
+    pub fn take_dropped(&self) -> DroppedUpdates {
+        std::mem::take(&mut *self.dropped.lock().unwrap())
+    }
+
+    /// Starts the budget of a new tick, returns how many updates ran in the last one
+    pub fn reset_tick(&self) -> u32 {
+        self.this_tick.swap(0, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_config::BlockUpdatesConfig;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::BlockUpdates;
+
+    fn origin() -> BlockPos {
+        BlockPos(Vector3::new(0, 64, 0))
+    }
+
+    #[test]
+    fn depth_limit() {
+        let updates = BlockUpdates::new(&BlockUpdatesConfig {
+            max_depth: 2,
+            max_per_tick: 1000,
+        });
+        updates.queue_neighbors(&origin(), None);
+        assert!(updates.start());
+        // Every update causes six more, until they are too deep
+        let mut ran = 0;
+        while let Some(update) = updates.next() {
+            ran += 1;
+            updates.queue_neighbors(&update.position, None);
+        }
+        assert!(!updates.stop());
+
+        assert_eq!(ran, 6 + 36 + 216);
+        let dropped = updates.take_dropped();
+        assert_eq!(dropped.too_deep, 1296);
+        assert_eq!(dropped.over_budget, 0);
+        assert!(updates.take_dropped().is_empty());
+    }
+
+    #[test]
+    fn tick_budget() {
+        let updates = BlockUpdates::new(&BlockUpdatesConfig {
+            max_depth: 512,
+            max_per_tick: 10,
+        });
+        updates.queue_neighbors(&origin(), None);
+        updates.queue_neighbors(&origin(), None);
+        assert!(updates.start());
+        let mut ran = 0;
+        while updates.next().is_some() {
+            ran += 1;
+        }
+        updates.stop();
+
+        assert_eq!(ran, 10);
+        let dropped = updates.take_dropped();
+        assert_eq!((dropped.too_deep, dropped.over_budget), (0, 2));
+        assert!(dropped.first == Some(origin().offset(Vector3::new(0, 0, -1))));
+        assert_eq!(updates.reset_tick(), 11);
+        updates.queue_neighbors(&origin(), None);
+        assert!(updates.next().is_some());
+    }
+}
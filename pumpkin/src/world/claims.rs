@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::{fs, io};
+
+use async_trait::async_trait;
+use pumpkin_config::advanced_config;
+use pumpkin_util::PermissionLvl;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+use crate::plugin::block::block_break::BlockBreakEvent;
+use crate::plugin::block::block_interact::BlockInteractEvent;
+use crate::plugin::block::block_place::BlockPlaceEvent;
+use crate::plugin::player::player_bucket_empty::PlayerBucketEmptyEvent;
+use crate::plugin::player::player_bucket_fill::PlayerBucketFillEvent;
+use crate::plugin::{EventHandler, EventPriority, PluginManager};
+
+/// The file in the `data` folder of a world the regions claimed in it are saved to
+pub const CLAIMS_FILE: &str = "claims.json";
+
+/// Something players who are not trusted in a region may or may not do in it
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimFlag {
+    /// Placing and breaking blocks, and using buckets
+    Build,
+    /// Using blocks, like doors, chests and buttons
+    Interact,
+    /// Players hurting each other, this one counts for trusted players as well
+    Pvp,
+}
+
+impl ClaimFlag {
+    pub const ALL: [Self; 3] = [Self::Build, Self::Interact, Self::Pvp];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Build => "build",
+            Self::Interact => "interact",
+            Self::Pvp => "pvp",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|flag| flag.name() == name)
+    }
+}
+
+/// A cuboid of blocks claimed by a player
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Region {
+    pub owner: Uuid,
+    /// The name of the owner when they claimed the region, to tell others whose it is
+    pub owner_name: String,
+    /// The players the owner trusts, they may do anything the owner can
+    pub members: BTreeMap<Uuid, String>,
+    pub min: [i32; 3],
+    pub max: [i32; 3],
+    /// What everyone else may do in the region, anything not set is denied
+    pub flags: BTreeMap<ClaimFlag, bool>,
+}
+
+impl Region {
+    /// A region from one corner to the other, both included
+    #[must_use]
+    pub fn new(owner: Uuid, owner_name: String, from: BlockPos, to: BlockPos) -> Self {
+        Self {
+            owner,
+            owner_name,
+            members: BTreeMap::new(),
+            min: [
+                from.0.x.min(to.0.x),
+                from.0.y.min(to.0.y),
+                from.0.z.min(to.0.z),
+            ],
+            max: [
+                from.0.x.max(to.0.x),
+                from.0.y.max(to.0.y),
+                from.0.z.max(to.0.z),
+            ],
+            flags: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, position: &BlockPos) -> bool {
+        let position = [position.0.x, position.0.y, position.0.z];
+        (0..3).all(|axis| (self.min[axis]..=self.max[axis]).contains(&position[axis]))
+    }
+
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..3).all(|axis| self.min[axis] <= other.max[axis] && other.min[axis] <= self.max[axis])
+    }
+
+    #[must_use]
+    pub fn volume(&self) -> u64 {
+        (0..3)
+            .map(|axis| (i64::from(self.max[axis]) - i64::from(self.min[axis]) + 1).unsigned_abs())
+            .product()
+    }
+
+    /// Whether the player is the owner or a member of the region
+    #[must_use]
+    pub fn is_trusted(&self, player: &Uuid) -> bool {
+        self.owner == *player || self.members.contains_key(player)
+    }
+
+    #[must_use]
+    pub fn flag(&self, flag: ClaimFlag) -> bool {
+        self.flags.get(&flag).copied().unwrap_or(false)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ClaimError {
+    /// There already is a region with the name
+    NameTaken,
+    /// The region would overlap the region with the given name
+    Overlaps(String),
+    /// The region has more blocks than the config allows
+    TooBig,
+    /// The player already owns as many regions as the config allows
+    TooMany,
+}
+
+/// The regions claimed in a world by their names
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Claims {
+    regions: BTreeMap<String, Region>,
+}
+
+impl Claims {
+    /// Reads the claims saved at `path`, there are none if the file doesn't exist
+    #[must_use]
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                log::error!("Failed to parse the claims at {}: {err}", path.display());
+                Self::default()
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                log::error!("Failed to read the claims at {}: {err}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Region> {
+        self.regions.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Region> {
+        self.regions.get_mut(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Region> {
+        self.regions.remove(name)
+    }
+
+    /// The region the position is in, regions never overlap so there is at most one
+    #[must_use]
+    pub fn region_at(&self, position: &BlockPos) -> Option<(&str, &Region)> {
+        self.regions
+            .iter()
+            .find(|(_, region)| region.contains(position))
+            .map(|(name, region)| (name.as_str(), region))
+    }
+
+    /// Adds the region if it neither overlaps another one nor goes over the limits of the config
+    pub fn claim(&mut self, name: String, region: Region) -> Result<(), ClaimError> {
+        let config = &advanced_config().claims;
+        if self.regions.contains_key(&name) {
+            return Err(ClaimError::NameTaken);
+        }
+        if config.max_volume != 0 && region.volume() > config.max_volume {
+            return Err(ClaimError::TooBig);
+        }
+        let owned = self
+            .regions
+            .values()
+            .filter(|other| other.owner == region.owner)
+            .count();
+        if config.max_claims_per_player != 0 && owned >= config.max_claims_per_player as usize {
+            return Err(ClaimError::TooMany);
+        }
+        if let Some((other, _)) = self
+            .regions
+            .iter()
+            .find(|(_, other)| other.intersects(&region))
+        {
+            return Err(ClaimError::Overlaps(other.clone()));
+        }
+        self.regions.insert(name, region);
+        Ok(())
+    }
+
+    /// Whether the player may do what the flag is about at the position
+    #[must_use]
+    pub fn allows(&self, position: &BlockPos, player: &Uuid, flag: ClaimFlag) -> bool {
+        self.region_at(position)
+            .is_none_or(|(_, region)| region.is_trusted(player) || region.flag(flag))
+    }
+
+    /// Whether players at the two positions may hurt each other, which they can't when either
+    /// stands in a region without pvp
+    #[must_use]
+    pub fn allows_pvp(&self, attacker: &BlockPos, victim: &BlockPos) -> bool {
+        [attacker, victim].into_iter().all(|position| {
+            self.region_at(position)
+                .is_none_or(|(_, region)| region.flag(ClaimFlag::Pvp))
+        })
+    }
+}
+
+/// Cancels the events of players doing what they are not allowed to in regions claimed by
+/// others. Operators may do anything anywhere
+pub struct ClaimProtection;
+
+impl ClaimProtection {
+    pub async fn register(manager: &PluginManager) {
+        let handler = Arc::new(Self);
+        manager
+            .register::<BlockBreakEvent, _>(handler.clone(), EventPriority::Highest, true)
+            .await;
+        manager
+            .register::<BlockPlaceEvent, _>(handler.clone(), EventPriority::Highest, true)
+            .await;
+        manager
+            .register::<BlockInteractEvent, _>(handler.clone(), EventPriority::Highest, true)
+            .await;
+        manager
+            .register::<PlayerBucketFillEvent, _>(handler.clone(), EventPriority::Highest, true)
+            .await;
+        manager
+            .register::<PlayerBucketEmptyEvent, _>(handler, EventPriority::Highest, true)
+            .await;
+    }
+}
+
+/// Whether the player may do what the flag is about at the position, telling them why not
+async fn check(player: &Player, position: &BlockPos, flag: ClaimFlag) -> bool {
+    if player.permission_lvl.load() >= PermissionLvl::Two {
+        return true;
+    }
+    let world = player.world().await;
+    let claims = world.claims.lock().await;
+    let Some((name, region)) = claims.region_at(position) else {
+        return true;
+    };
+    if region.is_trusted(&player.gameprofile.id) || region.flag(flag) {
+        return true;
+    }
+    let message = TextComponent::text(format!("{name} is claimed by {}", region.owner_name))
+        .color_named(NamedColor::Red);
+    drop(claims);
+    player.send_system_message_raw(&message, true).await;
+    false
+}
+
+#[async_trait]
+impl EventHandler<BlockBreakEvent> for ClaimProtection {
+    async fn handle_blocking(&self, event: &mut BlockBreakEvent) {
+        if let Some(player) = &event.player {
+            if !check(player, &event.position, ClaimFlag::Build).await {
+                event.cancelled = true;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler<BlockPlaceEvent> for ClaimProtection {
+    async fn handle_blocking(&self, event: &mut BlockPlaceEvent) {
+        if !check(&event.player, &event.position, ClaimFlag::Build).await {
+            event.cancelled = true;
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler<BlockInteractEvent> for ClaimProtection {
+    async fn handle_blocking(&self, event: &mut BlockInteractEvent) {
+        if !check(&event.player, &event.position, ClaimFlag::Interact).await {
+            event.cancelled = true;
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler<PlayerBucketFillEvent> for ClaimProtection {
+    async fn handle_blocking(&self, event: &mut PlayerBucketFillEvent) {
+        if !check(&event.player, &event.position, ClaimFlag::Build).await {
+            event.cancelled = true;
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler<PlayerBucketEmptyEvent> for ClaimProtection {
+    async fn handle_blocking(&self, event: &mut PlayerBucketEmptyEvent) {
+        if !check(&event.player, &event.position, ClaimFlag::Build).await {
+            event.cancelled = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::*;
+
+    fn pos(x: i32, y: i32, z: i32) -> BlockPos {
+        BlockPos(Vector3::new(x, y, z))
+    }
+
+    fn region(owner: Uuid, from: BlockPos, to: BlockPos) -> Region {
+        Region::new(owner, String::from("owner"), from, to)
+    }
+
+    #[test]
+    fn regions_may_not_overlap() {
+        let owner = Uuid::new_v4();
+        let mut claims = Claims::default();
+        claims
+            .claim("home".into(), region(owner, pos(0, 0, 0), pos(10, 10, 10)))
+            .unwrap();
+        assert_eq!(
+            claims.claim(
+                "base".into(),
+                region(owner, pos(10, 5, 10), pos(20, 20, 20))
+            ),
+            Err(ClaimError::Overlaps("home".into()))
+        );
+        assert_eq!(
+            claims.claim("home".into(), region(owner, pos(50, 0, 50), pos(60, 0, 60))),
+            Err(ClaimError::NameTaken)
+        );
+        assert!(
+            claims
+                .claim("base".into(), region(owner, pos(11, 0, 0), pos(20, 10, 10)))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn only_trusted_players_may_build() {
+        let owner = Uuid::new_v4();
+        let member = Uuid::new_v4();
+        let stranger = Uuid::new_v4();
+        let mut claims = Claims::default();
+        let mut home = region(owner, pos(-5, -64, -5), pos(5, 320, 5));
+        home.members.insert(member, String::from("member"));
+        home.flags.insert(ClaimFlag::Interact, true);
+        claims.claim("home".into(), home).unwrap();
+
+        let inside = pos(0, 70, 0);
+        assert!(claims.allows(&inside, &owner, ClaimFlag::Build));
+        assert!(claims.allows(&inside, &member, ClaimFlag::Build));
+        assert!(!claims.allows(&inside, &stranger, ClaimFlag::Build));
+        assert!(claims.allows(&inside, &stranger, ClaimFlag::Interact));
+        assert!(claims.allows(&pos(6, 70, 0), &stranger, ClaimFlag::Build));
+        assert!(!claims.allows_pvp(&pos(20, 70, 0), &inside));
+    }
+}
@@ -60,11 +60,12 @@ impl CustomBossbars {
         }
     }
 
+    /// The visible boss bars the player should see
     #[must_use]
     pub fn get_player_bars(&self, uuid: &Uuid) -> Option<Vec<&Bossbar>> {
         let mut player_bars: Vec<&Bossbar> = Vec::new();
         for bossbar in &self.custom_bossbars {
-            if bossbar.1.player.contains(uuid) {
+            if bossbar.1.visible && bossbar.1.player.contains(uuid) {
                 player_bars.push(&bossbar.1.bossbar_data);
             }
         }
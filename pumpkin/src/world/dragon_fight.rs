@@ -0,0 +1,328 @@
+//! The fight against the ender dragon around the exit portal of the End. It starts once a player
+//! comes close to the central island: an end crystal stands on every obsidian pillar, healing the
+//! dragon, and a boss bar shows its health. Killing the dragon opens the exit portal and an end
+//! gateway, and the first kill leaves a dragon egg on top of the portal.
+//!
+//! The dragon and the crystals aren't saved, if the server stops during a fight the dragon comes
+//! back with full health next time, on the crystals which weren't destroyed yet.
+
+use std::{collections::HashMap, f64::consts::PI, io, path::PathBuf, sync::Arc};
+
+use pumpkin_data::{block::Block, entity::EntityType};
+use pumpkin_util::{
+    math::{position::BlockPos, vector3::Vector3},
+    random::{RandomImpl, legacy_rand::LegacyRand},
+    text::TextComponent,
+};
+use pumpkin_world::{EndSpike, end_spikes};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use uuid::Uuid;
+
+use super::{
+    World,
+    bossbar::{Bossbar, BossbarColor, BossbarFlags},
+    end,
+};
+use crate::{
+    entity::{
+        end_crystal::EndCrystalEntity, ender_dragon::EnderDragonEntity,
+        experience_orb::ExperienceOrbEntity,
+    },
+    server::Server,
+};
+
+/// The file in the `data` folder of the End the state of the fight is saved to
+pub const DRAGON_FIGHT_FILE: &str = "dragon_fight.json";
+
+/// Players closer than this to the center of the End take part in the fight
+const FIGHT_RADIUS: f64 = 192.0;
+/// How often the players taking part are looked for
+const PLAYER_CHECK_INTERVAL: u64 = 20;
+/// Where the dragon appears
+const DRAGON_SPAWN: Vector3<f64> = Vector3::new(0.5, 128.0, 0.5);
+/// The experience the dragon drops, a lot more the first time
+const FIRST_KILL_EXPERIENCE: u32 = 12_000;
+const KILL_EXPERIENCE: u32 = 500;
+
+/// How far the end gateways are from the center of the End
+const GATEWAY_DISTANCE: f64 = 96.0;
+const GATEWAY_Y: i32 = 75;
+/// Every kill opens one more gateway, until there are this many
+const GATEWAY_COUNT: usize = 20;
+
+#[derive(Serialize, Deserialize, Default)]
+struct SavedFight {
+    dragon_killed: bool,
+    previously_killed: bool,
+    gateways_placed: usize,
+    /// The pillars whose crystal was destroyed during the current fight
+    destroyed_crystals: Vec<usize>,
+}
+
+pub struct DragonFight {
+    saved: SavedFight,
+    path: PathBuf,
+    spikes: Vec<EndSpike>,
+    gateways: Vec<BlockPos>,
+    dragon: Option<Arc<EnderDragonEntity>>,
+    /// The crystals of the fight by their entity, with the pillar they stand on
+    crystals: HashMap<Uuid, usize>,
+    bossbar: Option<Uuid>,
+    ticks: u64,
+}
+
+impl DragonFight {
+    /// The fight of the End saved at `path`, [`Self::load`] reads what was saved
+    #[must_use]
+    pub fn new(path: PathBuf, seed: u64) -> Self {
+        Self {
+            saved: SavedFight::default(),
+            path,
+            spikes: end_spikes(seed),
+            gateways: gateway_positions(seed),
+            dragon: None,
+            crystals: HashMap::new(),
+            bossbar: None,
+            ticks: 0,
+        }
+    }
+
+    /// Reads the saved state of the fight, it didn't start yet if the file doesn't exist
+    pub async fn load(&mut self) {
+        self.saved = match fs::read_to_string(&self.path).await {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                log::error!(
+                    "Failed to parse the dragon fight at {}: {err}",
+                    self.path.display()
+                );
+                SavedFight::default()
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => SavedFight::default(),
+            Err(err) => {
+                log::error!(
+                    "Failed to read the dragon fight at {}: {err}",
+                    self.path.display()
+                );
+                SavedFight::default()
+            }
+        };
+    }
+
+    /// Saves the fight right after it changed, as that doesn't happen often
+    async fn save(&self) {
+        let result = async {
+            if let Some(folder) = self.path.parent() {
+                fs::create_dir_all(folder).await?;
+            }
+            let content = serde_json::to_string_pretty(&self.saved)?;
+            fs::write(&self.path, content).await
+        }
+        .await;
+        if let Err(err) = result {
+            log::error!(
+                "Failed to save the dragon fight to {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+
+    /// Whether the dragon is dead, the exit portal is open then
+    #[must_use]
+    pub fn is_dragon_killed(&self) -> bool {
+        self.saved.dragon_killed
+    }
+
+    /// Where the gateways around the central island go, in the order they appear
+    #[must_use]
+    pub fn gateways(&self) -> &[BlockPos] {
+        &self.gateways
+    }
+
+    pub async fn tick(&mut self, world: &Arc<World>, server: &Server) {
+        self.ticks += 1;
+        if let (Some(dragon), Some(bossbar)) = (&self.dragon, self.bossbar) {
+            let progress = dragon.health_progress();
+            let mut boss_events = server.boss_events.lock().await;
+            #[expect(clippy::float_cmp)]
+            let changed = boss_events
+                .get(bossbar)
+                .is_some_and(|bossbar| bossbar.health != progress);
+            if changed {
+                boss_events.set_progress(server, bossbar, progress).await;
+            }
+        }
+        if self.ticks % PLAYER_CHECK_INTERVAL != 0 {
+            return;
+        }
+
+        let players: Vec<_> = world
+            .players
+            .read()
+            .await
+            .values()
+            .filter(|player| {
+                player.living_entity.health.load() > 0.0
+                    && player.living_entity.entity.pos.load().horizontal_length() <= FIGHT_RADIUS
+            })
+            .cloned()
+            .collect();
+        if !players.is_empty() && !self.saved.dragon_killed && self.dragon.is_none() {
+            self.start(world, server).await;
+        }
+
+        let Some(bossbar) = self.bossbar else {
+            return;
+        };
+        let mut boss_events = server.boss_events.lock().await;
+        let watching: Vec<_> = boss_events
+            .players(bossbar)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|uuid| !players.iter().any(|player| player.gameprofile.id == *uuid))
+            .collect();
+        for uuid in watching {
+            if let Some(player) = server.get_player_by_uuid(uuid).await {
+                boss_events.remove_player(bossbar, &player).await;
+            }
+        }
+        for player in &players {
+            boss_events.add_player(bossbar, player).await;
+        }
+    }
+
+    /// Spawns the dragon with the crystals which are still there and makes sure the closed exit
+    /// portal is there
+    async fn start(&mut self, world: &Arc<World>, server: &Server) {
+        let (origin, placed) = end::find_exit_portal(world).await;
+        if !placed {
+            end::place_exit_portal(world, origin, false).await;
+        }
+
+        for (index, spike) in self.spikes.iter().enumerate() {
+            if self.saved.destroyed_crystals.contains(&index) {
+                continue;
+            }
+            let entity = world.create_entity(spike.crystal_position(), EntityType::END_CRYSTAL);
+            self.crystals.insert(entity.entity_uuid, index);
+            world
+                .spawn_entity(Arc::new(EndCrystalEntity::new(entity)))
+                .await;
+        }
+
+        let perch = origin.to_f64().add_raw(0.0, 4.0, 0.0);
+        let entity = world.create_entity(DRAGON_SPAWN, EntityType::ENDER_DRAGON);
+        let dragon = Arc::new(EnderDragonEntity::new(entity, perch));
+        world.spawn_entity(dragon.clone()).await;
+        self.dragon = Some(dragon);
+
+        let mut bossbar = Bossbar::new(TextComponent::translate(
+            "entity.minecraft.ender_dragon",
+            [],
+        ));
+        bossbar.health = 1.0;
+        bossbar.color = BossbarColor::Pink;
+        bossbar.flags = BossbarFlags::DragonBar;
+        self.bossbar = Some(server.boss_events.lock().await.add(bossbar));
+    }
+
+    /// Forgets a destroyed crystal of the fight, it hurts the dragon if it was healing from it
+    pub async fn crystal_destroyed(&mut self, crystal: Uuid, position: Vector3<f64>) {
+        let Some(index) = self.crystals.remove(&crystal) else {
+            return;
+        };
+        self.saved.destroyed_crystals.push(index);
+        self.save().await;
+        if let Some(dragon) = &self.dragon {
+            dragon.crystal_destroyed(position).await;
+        }
+    }
+
+    /// Ends the fight: the exit portal opens, the next gateway appears and the dragon drops its
+    /// experience, with the dragon egg the first time
+    pub async fn dragon_died(
+        &mut self,
+        world: &Arc<World>,
+        server: &Server,
+        position: Vector3<f64>,
+    ) {
+        self.dragon = None;
+        self.crystals.clear();
+        if let Some(bossbar) = self.bossbar.take() {
+            server
+                .boss_events
+                .lock()
+                .await
+                .remove(server, bossbar)
+                .await;
+        }
+
+        let experience = if self.saved.previously_killed {
+            KILL_EXPERIENCE
+        } else {
+            FIRST_KILL_EXPERIENCE
+        };
+        ExperienceOrbEntity::spawn(world, position, experience).await;
+
+        let (origin, _) = end::find_exit_portal(world).await;
+        end::place_exit_portal(world, origin, true).await;
+        if !self.saved.previously_killed {
+            world
+                .set_block_state(
+                    &origin.offset(Vector3::new(0, 4, 0)),
+                    Block::DRAGON_EGG.default_state_id,
+                )
+                .await;
+        }
+        if let Some(gateway) = self.gateways.get(self.saved.gateways_placed) {
+            end::place_gateway(world, *gateway).await;
+            self.saved.gateways_placed += 1;
+        }
+
+        self.saved.dragon_killed = true;
+        self.saved.previously_killed = true;
+        self.saved.destroyed_crystals.clear();
+        self.save().await;
+    }
+}
+
+/// Where the end gateways around the central island go, in the order they appear
+#[must_use]
+pub fn gateway_positions(seed: u64) -> Vec<BlockPos> {
+    let mut positions: Vec<_> = (0..GATEWAY_COUNT)
+        .map(|i| {
+            let angle = 2.0 * (-PI + PI / 20.0 * i as f64);
+            BlockPos(Vector3::new(
+                (GATEWAY_DISTANCE * angle.cos()).floor() as i32,
+                GATEWAY_Y,
+                (GATEWAY_DISTANCE * angle.sin()).floor() as i32,
+            ))
+        })
+        .collect();
+    let mut random = LegacyRand::from_seed(seed);
+    for i in (2..=positions.len()).rev() {
+        let j = random.next_bounded_i32(i as i32) as usize;
+        positions.swap(i - 1, j);
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{GATEWAY_COUNT, GATEWAY_DISTANCE, gateway_positions};
+
+    #[test]
+    fn gateways_around_the_island() {
+        let gateways = gateway_positions(0);
+        assert_eq!(gateways.len(), GATEWAY_COUNT);
+        assert_eq!(gateways.iter().collect::<HashSet<_>>().len(), GATEWAY_COUNT);
+        for gateway in &gateways {
+            let distance = f64::from(gateway.0.x).hypot(f64::from(gateway.0.z));
+            assert!((distance - GATEWAY_DISTANCE).abs() < 2.0);
+        }
+        assert_eq!(gateways, gateway_positions(0));
+    }
+}
@@ -0,0 +1,287 @@
+//! The End: players stepping into an end portal arrive on an obsidian platform there, and leave
+//! again through the exit portal on the podium of the central island, seeing the credits the
+//! first time they do. The exit portal only opens once the dragon is killed, see
+//! `dragon_fight`, and every kill opens an end gateway leading to the outer islands.
+
+use std::sync::{Arc, atomic::Ordering};
+
+use pumpkin_data::block::{Block, BlockProperties, HorizontalFacing, WallTorchLikeProperties};
+use pumpkin_protocol::client::play::{CGameEvent, GameEvent};
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::dimension::Dimension;
+
+use super::World;
+use crate::entity::player::Player;
+use crate::server::Server;
+
+/// Where players arriving in the End stand, on top of the platform
+pub const ARRIVAL: Vector3<f64> = Vector3::new(100.5, 49.0, 0.5);
+/// Players arrive looking west, towards the central island
+pub const ARRIVAL_YAW: f32 = 90.0;
+
+/// The lowest the exit portal is placed, when the central island is missing
+const MIN_PODIUM_Y: i32 = 63;
+
+/// Gateways closer to the center than this lead to the outer islands, the others lead back
+const OUTER_GATEWAY_DISTANCE: f64 = 512.0;
+/// How far out the gateways around the central island lead
+const EXIT_DISTANCE: f64 = 1024.0;
+/// How far above the ground the gateway leading back is placed
+const EXIT_GATEWAY_HEIGHT: i32 = 10;
+/// Where the island is placed if a gateway leads into the void
+const EXIT_ISLAND_Y: i32 = 64;
+const EXIT_ISLAND_RADIUS: i32 = 3;
+
+/// Places the obsidian platform players arrive on, clearing the space above it
+pub async fn create_platform(world: &World) {
+    let center = BlockPos::floored(ARRIVAL.x, ARRIVAL.y, ARRIVAL.z);
+    for x in -2..=2 {
+        for z in -2..=2 {
+            for y in -1..=2 {
+                let block = if y == -1 { Block::OBSIDIAN } else { Block::AIR };
+                let position = center.offset(Vector3::new(x, y, z));
+                if world.get_block_state_id(&position).await.ok() != Some(block.default_state_id) {
+                    world
+                        .set_block_state(&position, block.default_state_id)
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+/// Where the podium of the exit portal is, or goes on top of the central island, and whether
+/// it's already there
+pub async fn find_exit_portal(world: &World) -> (BlockPos, bool) {
+    let top = world.get_top_block(Vector2::new(0, 0)).await;
+    // The pillar in the middle of the podium is the highest block, with the ring at its bottom
+    let origin = BlockPos(Vector3::new(0, top - 3, 0));
+    let is_bedrock = |position: BlockPos| async move {
+        world
+            .get_block(&position)
+            .await
+            .is_ok_and(|block| block.id == Block::BEDROCK.id)
+    };
+    if is_bedrock(origin).await
+        && is_bedrock(origin.offset(Vector3::new(0, 3, 0))).await
+        && is_bedrock(origin.offset(Vector3::new(3, 0, 0))).await
+    {
+        return (origin, true);
+    }
+    (
+        BlockPos(Vector3::new(0, (top + 1).max(MIN_PODIUM_Y), 0)),
+        false,
+    )
+}
+
+/// Places the podium of the exit portal like vanilla does, with portal blocks if it's `active`
+pub async fn place_exit_portal(world: &World, origin: BlockPos, active: bool) {
+    let close = |position: &BlockPos, distance: f64| {
+        let offset = position.0.sub(&origin.0);
+        f64::from(offset.x * offset.x + offset.y * offset.y + offset.z * offset.z)
+            < distance * distance
+    };
+    // Blocks further up or down than this are never close enough
+    for y in -1..=3 {
+        for x in -4..=4 {
+            for z in -4..=4 {
+                let position = origin.offset(Vector3::new(x, y, z));
+                let inner = close(&position, 2.5);
+                if !inner && !close(&position, 3.5) {
+                    continue;
+                }
+                let block = match y {
+                    ..0 if inner => Block::BEDROCK,
+                    ..0 => Block::END_STONE,
+                    0 if !inner => Block::BEDROCK,
+                    0 if active => Block::END_PORTAL,
+                    _ => Block::AIR,
+                };
+                world
+                    .set_block_state(&position, block.default_state_id)
+                    .await;
+            }
+        }
+    }
+
+    for y in 0..4 {
+        world
+            .set_block_state(
+                &origin.offset(Vector3::new(0, y, 0)),
+                Block::BEDROCK.default_state_id,
+            )
+            .await;
+    }
+    let torches = [
+        (HorizontalFacing::North, Vector3::new(0, 2, -1)),
+        (HorizontalFacing::South, Vector3::new(0, 2, 1)),
+        (HorizontalFacing::West, Vector3::new(-1, 2, 0)),
+        (HorizontalFacing::East, Vector3::new(1, 2, 0)),
+    ];
+    for (facing, offset) in torches {
+        let mut props = WallTorchLikeProperties::default(&Block::WALL_TORCH);
+        props.facing = facing;
+        world
+            .set_block_state(
+                &origin.offset(offset),
+                props.to_state_id(&Block::WALL_TORCH),
+            )
+            .await;
+    }
+}
+
+/// Moves a player who stepped into an end portal: into the End, or out of it
+pub async fn enter_portal(player: &Arc<Player>, server: &Server) {
+    // The player is watching the credits and leaves once they are done
+    if player.won_game.load(Ordering::Relaxed) {
+        return;
+    }
+    let world = player.world().await;
+    if matches!(world.dimension_type, DimensionType::TheEnd) {
+        player.won_game.store(true, Ordering::Relaxed);
+        // The client skips the credits and respawns right away for 0
+        let seen_credits = player.seen_credits.swap(true, Ordering::Relaxed);
+        player
+            .client
+            .send_packet(&CGameEvent::new(
+                GameEvent::WinGame,
+                if seen_credits { 0.0 } else { 1.0 },
+            ))
+            .await;
+        return;
+    }
+
+    let Some(end) = server.worlds.get(Dimension::End).cloned() else {
+        log::debug!(
+            "{} entered an end portal, but the End isn't loaded",
+            player.gameprofile.name
+        );
+        return;
+    };
+    create_platform(&end).await;
+    let (exit_portal, placed) = find_exit_portal(&end).await;
+    if !placed {
+        let open = match &end.dragon_fight {
+            Some(fight) => fight.lock().await.is_dragon_killed(),
+            None => true,
+        };
+        place_exit_portal(&end, exit_portal, open).await;
+    }
+    player
+        .teleport_world(end, Some(ARRIVAL), Some(ARRIVAL_YAW), Some(0.0))
+        .await;
+}
+
+/// Places an end gateway like vanilla does, with bedrock above and below it
+pub async fn place_gateway(world: &World, position: BlockPos) {
+    for x in -1..=1 {
+        for y in -2..=2 {
+            for z in -1..=1 {
+                let block = match (x, y, z) {
+                    (0, 0, 0) => Block::END_GATEWAY,
+                    (_, 0, _) => Block::AIR,
+                    (0, -2 | 2, 0) | (0, -1 | 1, _) | (_, -1 | 1, 0) => Block::BEDROCK,
+                    _ => Block::AIR,
+                };
+                world
+                    .set_block_state(
+                        &position.offset(Vector3::new(x, y, z)),
+                        block.default_state_id,
+                    )
+                    .await;
+            }
+        }
+    }
+}
+
+/// The ground below where a gateway leads to, placing a small island if there is none. A gateway
+/// placed there before is skipped
+async fn exit_ground(world: &World, x: i32, z: i32) -> i32 {
+    let top = world.get_top_block(Vector2::new(x, z)).await;
+    let block_at = |y: i32| async move {
+        world
+            .get_block(&BlockPos(Vector3::new(x, y, z)))
+            .await
+            .map_or(Block::AIR.id, |block| block.id)
+    };
+    if block_at(top).await == Block::BEDROCK.id && block_at(top - 2).await == Block::END_GATEWAY.id
+    {
+        return top - 2 - EXIT_GATEWAY_HEIGHT;
+    }
+    if top > 0 {
+        return top;
+    }
+
+    for dx in -EXIT_ISLAND_RADIUS..=EXIT_ISLAND_RADIUS {
+        for dz in -EXIT_ISLAND_RADIUS..=EXIT_ISLAND_RADIUS {
+            if dx * dx + dz * dz <= EXIT_ISLAND_RADIUS * EXIT_ISLAND_RADIUS {
+                let position = BlockPos(Vector3::new(x + dx, EXIT_ISLAND_Y, z + dz));
+                world
+                    .set_block_state(&position, Block::END_STONE.default_state_id)
+                    .await;
+            }
+        }
+    }
+    EXIT_ISLAND_Y
+}
+
+/// Moves a player who stepped into an end gateway: from the gateways around the central island
+/// out to the outer islands, where a gateway leading back is placed, or back again
+pub async fn enter_gateway(player: &Arc<Player>) {
+    let world = player.world().await;
+    let Some(fight) = &world.dragon_fight else {
+        return;
+    };
+    let gateway = player.living_entity.entity.block_pos.load().0;
+    let offset = Vector3::new(f64::from(gateway.x), 0.0, f64::from(gateway.z));
+    let distance = offset.horizontal_length();
+    if distance == 0.0 {
+        return;
+    }
+    let direction = offset.normalize();
+
+    let position = if distance < OUTER_GATEWAY_DISTANCE {
+        let target = direction * EXIT_DISTANCE;
+        let (x, z) = (target.x.floor() as i32, target.z.floor() as i32);
+        let ground = exit_ground(&world, x, z).await;
+        let exit = BlockPos(Vector3::new(x, ground + EXIT_GATEWAY_HEIGHT, z));
+        if world
+            .get_block(&exit)
+            .await
+            .is_ok_and(|block| block.id != Block::END_GATEWAY.id)
+        {
+            place_gateway(&world, exit).await;
+        }
+        BlockPos(Vector3::new(x, ground + 1, z)).to_f64()
+    } else {
+        // Onto the bedrock above the gateway around the central island facing this one
+        let fight = fight.lock().await;
+        let Some(closest) = fight.gateways().iter().min_by(|a, b| {
+            let distance = |gateway: &BlockPos| {
+                let other = Vector3::new(f64::from(gateway.0.x), 0.0, f64::from(gateway.0.z));
+                other.normalize().sub(&direction).length_squared()
+            };
+            distance(a).total_cmp(&distance(b))
+        }) else {
+            return;
+        };
+        closest.offset(Vector3::new(0, 3, 0)).to_f64()
+    };
+
+    let entity = &player.living_entity.entity;
+    player
+        .teleport(position, entity.yaw.load(), entity.pitch.load())
+        .await;
+}
+
+/// Brings a player who left the End through the exit portal back to the default world, once the
+/// credits are done. Returns false if the player didn't leave the End
+pub async fn leave_end(player: &Arc<Player>, server: &Server) -> bool {
+    if !player.won_game.swap(false, Ordering::Relaxed) {
+        return false;
+    }
+    let world = server.worlds.default_world().clone();
+    player.teleport_world(world, None, None, None).await;
+    true
+}
@@ -0,0 +1,73 @@
+use std::sync::{Arc, Mutex};
+
+use pumpkin_data::damage::DamageType;
+use pumpkin_util::math::position::BlockPos;
+
+use crate::entity::{EntityBase, EntityId};
+
+tokio::task_local! {
+    /// The place of the entity being ticked in the order of the tick, see `entity_shard`
+    static TICKING: usize;
+}
+
+/// Ticks the entity as the `index`th entity of the tick, so what it does to others is queued
+pub async fn scope<F: Future>(index: usize, tick: F) -> F::Output {
+    TICKING.scope(index, tick).await
+}
+
+/// The place of the entity being ticked on this task, `None` outside of entity ticks
+#[must_use]
+pub fn ticking_entity() -> Option<usize> {
+    TICKING.try_with(|index| *index).ok()
+}
+
+/// Something an entity does to other entities or the world while entities are ticked
+pub enum EntityEffect {
+    Damage {
+        target: Arc<dyn EntityBase>,
+        amount: f32,
+        damage_type: DamageType,
+    },
+    Spawn(Arc<dyn EntityBase>),
+    Remove {
+        uuid: uuid::Uuid,
+        entity_id: EntityId,
+    },
+    SetBlock {
+        position: BlockPos,
+        state_id: u16,
+    },
+}
+
+/// The effects queued while entities tick. Workers tick their shards at the same time, so the
+/// effects are applied afterwards in the order of the entities which caused them. That way the
+/// outcome doesn't depend on how many workers there are or which one was faster
+pub struct EffectQueue<E> {
+    effects: Mutex<Vec<(usize, E)>>,
+}
+
+impl<E> Default for EffectQueue<E> {
+    fn default() -> Self {
+        Self {
+            effects: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<E> EffectQueue<E> {
+    pub fn push(&self, index: usize, effect: E) {
+        self.effects
+            .lock()
+            .expect("Effect queue lock is poisoned")
+            .push((index, effect));
+    }
+
+    /// Takes the queued effects ordered by the entities which caused them. The effects of one
+    /// entity keep the order it caused them in
+    pub fn take(&self) -> Vec<E> {
+        let mut effects =
+            std::mem::take(&mut *self.effects.lock().expect("Effect queue lock is poisoned"));
+        effects.sort_by_key(|(index, _)| *index);
+        effects.into_iter().map(|(_, effect)| effect).collect()
+    }
+}
@@ -0,0 +1,177 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use pumpkin_util::math::{boundingbox::BoundingBox, vector2::Vector2};
+use uuid::Uuid;
+
+struct IndexInner<T> {
+    chunks: HashMap<Vector2<i32>, HashMap<Uuid, T>>,
+    positions: HashMap<Uuid, Vector2<i32>>,
+}
+
+/// A spatial index of entities by the chunk they are in.
+///
+/// Lookups only visit the chunks covering the queried area instead of every entity of the
+/// world. The chunk of an entity is not updated when it moves, call `update` for that; the world
+/// does this after every entity tick.
+pub struct EntityIndex<T> {
+    inner: RwLock<IndexInner<T>>,
+}
+
+impl<T> Default for EntityIndex<T> {
+    fn default() -> Self {
+        Self {
+            inner: RwLock::new(IndexInner {
+                chunks: HashMap::new(),
+                positions: HashMap::new(),
+            }),
+        }
+    }
+}
+
+impl<T: Clone> EntityIndex<T> {
+    pub fn insert(&self, uuid: Uuid, chunk: Vector2<i32>, entity: T) {
+        let mut inner = self.inner.write().unwrap();
+        if let Some(old_chunk) = inner.positions.insert(uuid, chunk) {
+            remove_from_chunk(&mut inner.chunks, old_chunk, uuid);
+        }
+        inner.chunks.entry(chunk).or_default().insert(uuid, entity);
+    }
+
+    pub fn remove(&self, uuid: &Uuid) -> Option<T> {
+        let mut inner = self.inner.write().unwrap();
+        let chunk = inner.positions.remove(uuid)?;
+        remove_from_chunk(&mut inner.chunks, chunk, *uuid)
+    }
+
+    /// Moves the entity to the given chunk, if it is indexed
+    pub fn update(&self, uuid: Uuid, chunk: Vector2<i32>) {
+        if self.inner.read().unwrap().positions.get(&uuid) == Some(&chunk) {
+            return;
+        }
+
+        let mut inner = self.inner.write().unwrap();
+        let Some(old_chunk) = inner.positions.get(&uuid).copied() else {
+            return;
+        };
+        if old_chunk == chunk {
+            return;
+        }
+        if let Some(entity) = remove_from_chunk(&mut inner.chunks, old_chunk, uuid) {
+            inner.chunks.entry(chunk).or_default().insert(uuid, entity);
+            inner.positions.insert(uuid, chunk);
+        }
+    }
+
+    /// All entities in the chunks from `min` to `max` (inclusive)
+    pub fn in_chunks(&self, min: Vector2<i32>, max: Vector2<i32>) -> Vec<T> {
+        let inner = self.inner.read().unwrap();
+        let area = i64::from(max.x - min.x + 1) * i64::from(max.z - min.z + 1);
+        // For huge areas it is cheaper to go over the occupied chunks
+        if area > inner.chunks.len() as i64 {
+            return inner
+                .chunks
+                .iter()
+                .filter(|(chunk, _)| {
+                    (min.x..=max.x).contains(&chunk.x) && (min.z..=max.z).contains(&chunk.z)
+                })
+                .flat_map(|(_, entities)| entities.values().cloned())
+                .collect();
+        }
+
+        let mut found = Vec::new();
+        for x in min.x..=max.x {
+            for z in min.z..=max.z {
+                if let Some(entities) = inner.chunks.get(&Vector2::new(x, z)) {
+                    found.extend(entities.values().cloned());
+                }
+            }
+        }
+        found
+    }
+
+    /// All entities in the chunks the bounding box touches
+    pub fn in_box_chunks(&self, aabb: &BoundingBox) -> Vec<T> {
+        let (min, max) = chunk_range(aabb);
+        self.in_chunks(min, max)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn remove_from_chunk<T>(
+    chunks: &mut HashMap<Vector2<i32>, HashMap<Uuid, T>>,
+    chunk: Vector2<i32>,
+    uuid: Uuid,
+) -> Option<T> {
+    let entities = chunks.get_mut(&chunk)?;
+    let entity = entities.remove(&uuid);
+    if entities.is_empty() {
+        chunks.remove(&chunk);
+    }
+    entity
+}
+
+/// The range of chunks a bounding box touches
+#[must_use]
+pub fn chunk_range(aabb: &BoundingBox) -> (Vector2<i32>, Vector2<i32>) {
+    let to_chunk = |coord: f64| (coord.floor() as i32) >> 4;
+    (
+        Vector2::new(to_chunk(aabb.min.x), to_chunk(aabb.min.z)),
+        Vector2::new(to_chunk(aabb.max.x), to_chunk(aabb.max.z)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::math::{boundingbox::BoundingBox, vector2::Vector2, vector3::Vector3};
+    use uuid::Uuid;
+
+    use super::EntityIndex;
+
+    #[test]
+    fn entities_follow_their_chunk() {
+        let index = EntityIndex::default();
+        let first = Uuid::from_u128(1);
+        let second = Uuid::from_u128(2);
+        index.insert(first, Vector2::new(0, 0), 1);
+        index.insert(second, Vector2::new(5, 5), 2);
+
+        assert_eq!(
+            index.in_chunks(Vector2::new(-1, -1), Vector2::new(1, 1)),
+            vec![1]
+        );
+
+        index.update(first, Vector2::new(10, 10));
+        assert!(
+            index
+                .in_chunks(Vector2::new(-1, -1), Vector2::new(1, 1))
+                .is_empty()
+        );
+        let mut found = index.in_chunks(Vector2::new(0, 0), Vector2::new(10, 10));
+        found.sort_unstable();
+        assert_eq!(found, vec![1, 2]);
+
+        assert_eq!(index.remove(&second), Some(2));
+        assert_eq!(index.remove(&second), None);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn box_query_covers_touched_chunks() {
+        let index = EntityIndex::default();
+        index.insert(Uuid::from_u128(1), Vector2::new(-1, 0), 1);
+        index.insert(Uuid::from_u128(2), Vector2::new(1, 0), 2);
+
+        let aabb = BoundingBox {
+            min: Vector3::new(-0.5, 0.0, 0.0),
+            max: Vector3::new(15.5, 2.0, 1.0),
+        };
+        assert_eq!(index.in_box_chunks(&aabb), vec![1]);
+    }
+}
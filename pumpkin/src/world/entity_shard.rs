@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+
+use pumpkin_util::math::vector2::Vector2;
+
+use super::entity_effects;
+
+/// Entities are sharded by regions of 32x32 chunks
+const SHARD_REGION_BITS: i32 = 5;
+
+/// The region shard a chunk belongs to
+#[must_use]
+pub fn shard_region(chunk: Vector2<i32>) -> (i32, i32) {
+    (chunk.x >> SHARD_REGION_BITS, chunk.z >> SHARD_REGION_BITS)
+}
+
+/// Groups entities by the region they are in.
+///
+/// The result only depends on the chunk and id of every entity, not on the order of `entities`:
+/// shards are sorted by region and the entities of every shard by their id. This keeps ticking
+/// deterministic, no matter how the entities are stored.
+#[must_use]
+pub fn shard_by_region<T>(
+    entities: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> (Vector2<i32>, i32),
+) -> Vec<Vec<T>> {
+    let mut shards: BTreeMap<(i32, i32), Vec<(i32, T)>> = BTreeMap::new();
+    for entity in entities {
+        let (chunk, id) = key(&entity);
+        shards
+            .entry(shard_region(chunk))
+            .or_default()
+            .push((id, entity));
+    }
+
+    shards
+        .into_values()
+        .map(|mut shard| {
+            shard.sort_by_key(|(id, _)| *id);
+            shard.into_iter().map(|(_, entity)| entity).collect()
+        })
+        .collect()
+}
+
+/// Splits the shards between `workers` workers, every worker ticks its shards one after another.
+///
+/// Shards are handed out round robin in their sorted order, so a shard always ends up with the
+/// same worker for the same set of shards.
+#[must_use]
+pub fn assign_workers<T>(shards: Vec<Vec<T>>, workers: usize) -> Vec<Vec<Vec<T>>> {
+    let workers = workers.clamp(1, shards.len().max(1));
+    let mut assigned: Vec<Vec<Vec<T>>> = (0..workers).map(|_| Vec::new()).collect();
+    for (i, shard) in shards.into_iter().enumerate() {
+        assigned[i % workers].push(shard);
+    }
+    assigned
+}
+
+/// Ticks the entities of the shards with `workers` workers.
+///
+/// Every entity is ticked as its place in the order of `shards`, which is what the effects it
+/// queues on others are ordered by, see `entity_effects`.
+pub async fn tick_shards<T, F, Fut>(shards: Vec<Vec<T>>, workers: usize, tick: F)
+where
+    T: Send + 'static,
+    F: Fn(T) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    let mut index = 0;
+    let shards: Vec<Vec<(usize, T)>> = shards
+        .into_iter()
+        .map(|shard| {
+            shard
+                .into_iter()
+                .map(|entity| {
+                    index += 1;
+                    (index - 1, entity)
+                })
+                .collect()
+        })
+        .collect();
+
+    if workers <= 1 || shards.len() <= 1 {
+        for (index, entity) in shards.into_iter().flatten() {
+            entity_effects::scope(index, tick(entity)).await;
+        }
+        return;
+    }
+
+    let tasks: Vec<_> = assign_workers(shards, workers)
+        .into_iter()
+        .map(|worker_shards| {
+            let tick = tick.clone();
+            tokio::spawn(async move {
+                for (index, entity) in worker_shards.into_iter().flatten() {
+                    entity_effects::scope(index, tick(entity)).await;
+                }
+            })
+        })
+        .collect();
+    for task in tasks {
+        if let Err(error) = task.await {
+            log::error!("Entity tick worker failed: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{assign_workers, shard_by_region, tick_shards};
+    use crate::world::entity_effects::{self, EffectQueue};
+
+    fn entities() -> Vec<(Vector2<i32>, i32)> {
+        vec![
+            (Vector2::new(0, 0), 7),
+            (Vector2::new(40, 3), 2),
+            (Vector2::new(31, 31), 3),
+            (Vector2::new(-1, 0), 9),
+            (Vector2::new(33, 0), 1),
+            (Vector2::new(1, 2), 4),
+        ]
+    }
+
+    fn ids(shards: &[Vec<(Vector2<i32>, i32)>]) -> Vec<Vec<i32>> {
+        shards
+            .iter()
+            .map(|shard| shard.iter().map(|(_, id)| *id).collect())
+            .collect()
+    }
+
+    #[test]
+    fn sharding_is_independent_of_input_order() {
+        let expected = shard_by_region(entities(), |entity| *entity);
+        assert_eq!(ids(&expected), vec![vec![9], vec![3, 4, 7], vec![1, 2]]);
+
+        let mut reversed = entities();
+        reversed.reverse();
+        assert_eq!(
+            ids(&shard_by_region(reversed, |entity| *entity)),
+            ids(&expected)
+        );
+
+        let mut rotated = entities();
+        rotated.rotate_left(2);
+        assert_eq!(
+            ids(&shard_by_region(rotated, |entity| *entity)),
+            ids(&expected)
+        );
+    }
+
+    #[test]
+    fn workers_get_the_same_shards() {
+        let shards = shard_by_region(entities(), |entity| *entity);
+        let assigned = assign_workers(shards, 2);
+        assert_eq!(assigned.len(), 2);
+        assert_eq!(ids(&assigned[0]), vec![vec![9], vec![1, 2]]);
+        assert_eq!(ids(&assigned[1]), vec![vec![3, 4, 7]]);
+
+        // More workers than shards and zero workers are clamped
+        let shards = shard_by_region(entities(), |entity| *entity);
+        assert_eq!(assign_workers(shards, 16).len(), 3);
+        let shards = shard_by_region(entities(), |entity| *entity);
+        assert_eq!(assign_workers(shards, 0).len(), 1);
+    }
+
+    enum Effect {
+        Hit { target: i32, amount: i32 },
+        Heal { target: i32 },
+        Spawn { chunk: Vector2<i32> },
+    }
+
+    /// Ticks a small world of entities which hit and heal each other across regions and spawn
+    /// new ones, returning the health of every entity at the end
+    async fn tick_world(workers: usize) -> Vec<(i32, i32)> {
+        let mut entities: BTreeMap<i32, (Vector2<i32>, i32)> = (0..64)
+            .map(|id| {
+                (
+                    id,
+                    (Vector2::new(id * 13 % 200 - 100, id * 7 % 150 - 75), 100),
+                )
+            })
+            .collect();
+        let effects = Arc::new(EffectQueue::default());
+        for _ in 0..10 {
+            let count = entities.len() as i32;
+            let snapshot: Vec<_> = entities
+                .iter()
+                .map(|(id, (chunk, health))| (*id, *chunk, *health))
+                .collect();
+            let shards = shard_by_region(snapshot, |(id, chunk, _)| (*chunk, *id));
+            let queue = effects.clone();
+            tick_shards(shards, workers, move |(id, chunk, health)| {
+                let queue = queue.clone();
+                async move {
+                    // Finish in a different order than the entities were handed out
+                    tokio::time::sleep(Duration::from_micros((id * 37 % 11) as u64 * 50)).await;
+                    let index = entity_effects::ticking_entity().unwrap();
+                    let target = (id * 5 + 1) % count;
+                    queue.push(
+                        index,
+                        Effect::Hit {
+                            target,
+                            amount: id % 5 + 1,
+                        },
+                    );
+                    if health % 3 == 0 {
+                        queue.push(index, Effect::Heal { target });
+                    }
+                    if id % 16 == 0 {
+                        queue.push(index, Effect::Spawn { chunk });
+                    }
+                }
+            })
+            .await;
+
+            // Applying them in another order changes the outcome
+            for effect in effects.take() {
+                match effect {
+                    Effect::Hit { target, amount } => {
+                        if let Some((_, health)) = entities.get_mut(&target) {
+                            *health = (*health - amount).max(1);
+                        }
+                    }
+                    Effect::Heal { target } => {
+                        if let Some((_, health)) = entities.get_mut(&target) {
+                            *health = (*health * 2).min(100);
+                        }
+                    }
+                    Effect::Spawn { chunk } => {
+                        let id = entities.len() as i32;
+                        entities.insert(id, (chunk, 50 + id % 7));
+                    }
+                }
+            }
+        }
+        entities
+            .into_iter()
+            .map(|(id, (_, health))| (id, health))
+            .collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn workers_tick_the_same_world() {
+        let ticked_alone = tick_world(1).await;
+        assert!(ticked_alone.len() > 64);
+        for workers in [2, 4, 8] {
+            assert_eq!(tick_world(workers).await, ticked_alone);
+        }
+    }
+
+    #[tokio::test]
+    async fn nothing_is_queued_outside_of_entity_ticks() {
+        assert_eq!(entity_effects::ticking_entity(), None);
+        let indices = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let shards = vec![vec!['a', 'b'], vec!['c']];
+        let seen = indices.clone();
+        tick_shards(shards, 1, move |_| {
+            let seen = seen.clone();
+            async move {
+                seen.lock()
+                    .unwrap()
+                    .push(entity_effects::ticking_entity().unwrap());
+            }
+        })
+        .await;
+        assert_eq!(*indices.lock().unwrap(), [0, 1, 2]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Keeps the entities of chunks in the `entities` region files like vanilla does. Entities are
+//! saved and removed once their chunk stops ticking and are spawned again when it starts again.
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_world::chunk::entities::ChunkEntityData;
+
+use crate::entity::mob;
+
+use super::World;
+
+/// How many ticks pass between checking which chunks were loaded or unloaded
+pub const SYNC_INTERVAL: i64 = 20;
+
+/// Saves the entities of the chunks which stopped ticking and spawns the saved entities of the
+/// chunks which started
+pub async fn sync(world: &Arc<World>) {
+    let ticking: HashSet<_> = world.level.ticking_chunks().into_iter().collect();
+    let mut entity_chunks = world.entity_chunks.lock().await;
+    save(world, &mut entity_chunks, &ticking, false).await;
+
+    let new_chunks: Vec<_> = ticking.difference(&entity_chunks).copied().collect();
+    entity_chunks.extend(new_chunks.iter().copied());
+    drop(entity_chunks);
+
+    for data in world.level.read_entities(&new_chunks).await {
+        for nbt in data.entities {
+            if let Some(entity) = mob::from_nbt(nbt, world).await {
+                world.spawn_entity(entity).await;
+            }
+        }
+    }
+}
+
+/// Saves the entities of every chunk, the ones in ticking chunks stay in the world
+pub async fn save_all(world: &World) {
+    let ticking: HashSet<_> = world.level.ticking_chunks().into_iter().collect();
+    let mut entity_chunks = world.entity_chunks.lock().await;
+    save(world, &mut entity_chunks, &ticking, true).await;
+}
+
+async fn save(
+    world: &World,
+    entity_chunks: &mut HashSet<Vector2<i32>>,
+    ticking: &HashSet<Vector2<i32>>,
+    all: bool,
+) {
+    // What was saved for these chunks was spawned, so it gets replaced, even if no entity is
+    // left in them
+    let mut chunks: HashMap<Vector2<i32>, Vec<NbtCompound>> = entity_chunks
+        .iter()
+        .filter(|chunk| all || !ticking.contains(chunk))
+        .map(|chunk| (*chunk, Vec::new()))
+        .collect();
+    entity_chunks.retain(|chunk| ticking.contains(chunk));
+    let replaced: HashSet<_> = chunks.keys().copied().collect();
+
+    let entities: Vec<_> = world.entities.read().await.values().cloned().collect();
+    let mut removed = Vec::new();
+    for entity in entities {
+        let chunk = entity.get_entity().chunk_pos.load();
+        let unloaded = !ticking.contains(&chunk);
+        if !unloaded && !replaced.contains(&chunk) {
+            continue;
+        }
+        let Some(nbt) = entity.to_nbt().await else {
+            continue;
+        };
+        chunks.entry(chunk).or_default().push(nbt);
+        if unloaded {
+            removed.push(entity);
+        }
+    }
+
+    // Entities which wandered into chunks that weren't loaded join what is saved there
+    let strays: Vec<_> = chunks
+        .keys()
+        .filter(|chunk| !replaced.contains(chunk))
+        .copied()
+        .collect();
+    for saved in world.level.read_entities(&strays).await {
+        if let Some(entities) = chunks.get_mut(&saved.position) {
+            entities.extend(saved.entities);
+        }
+    }
+
+    world
+        .level
+        .write_entities(
+            chunks
+                .into_iter()
+                .map(|(position, entities)| ChunkEntityData::new(position, entities))
+                .collect(),
+        )
+        .await;
+
+    for entity in removed {
+        world.remove_entity(entity.get_entity()).await;
+    }
+}
@@ -2,9 +2,14 @@ use std::sync::Arc;
 
 use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
 
-use crate::{block::drop_loot, server::Server};
+use crate::block::blocks::take_container_contents;
+use crate::{
+    block::{drop_loot, drop_stack},
+    server::Server,
+};
 
 use super::World;
+use super::loot::LootContextParameters;
 
 pub struct Explosion {
     power: f32,
@@ -84,7 +89,15 @@ impl Explosion {
             world.set_block_state(&pos, 0).await;
 
             if pumpkin_block.is_none_or(|s| s.should_drop_items_on_explosion()) {
-                drop_loot(world, &block, &pos, false, block_state.id).await;
+                let params = LootContextParameters {
+                    explosion_radius: Some(self.power),
+                    ..Default::default()
+                };
+                drop_loot(world, &block, &pos, false, block_state.id, params).await;
+            }
+            let contents = take_container_contents(&block, pos, server, world).await;
+            for stack in contents.into_iter().flatten() {
+                drop_stack(world, &pos, stack).await;
             }
             if let Some(pumpkin_block) = pumpkin_block {
                 pumpkin_block.explode(&block, world, pos).await;
@@ -0,0 +1,315 @@
+//! Game events are things happening in the world which listeners nearby can notice, like a sculk
+//! sensor picking up the vibration of a player walking by.
+//!
+//! Events are emitted with `World::emit_game_event` and handed to every registered listener in
+//! range, closest first.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    game_event::GameEvent,
+    tag::{RegistryKey, get_tag_values},
+};
+use pumpkin_protocol::client::play::PositionSource;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{block::registry::get_block_by_state_id, raycast::VoxelRay};
+use tokio::sync::RwLock;
+
+use super::World;
+use crate::entity::{Entity, EntityId};
+
+/// Identifies a registered listener, to unregister it again
+pub type ListenerId = u64;
+
+/// What caused a game event
+#[derive(Clone, Copy, Default)]
+pub struct GameEventContext {
+    /// The entity which caused the event, like the player who placed a block
+    pub source: Option<EntityId>,
+    /// Whether the source was sneaking, which keeps some events from being noticed
+    pub sneaking: bool,
+    /// The block state the event is about, like the placed block or the block stepped on
+    pub affected_state: Option<u16>,
+}
+
+impl GameEventContext {
+    #[must_use]
+    pub fn entity(entity: &Entity) -> Self {
+        Self {
+            source: Some(entity.entity_id),
+            sneaking: entity.sneaking.load(Ordering::Relaxed),
+            affected_state: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_state(mut self, state_id: u16) -> Self {
+        self.affected_state = Some(state_id);
+        self
+    }
+}
+
+#[async_trait]
+pub trait GameEventListener: Send + Sync {
+    /// Where the listener is, the range is measured from here
+    fn position(&self) -> Vector3<f64>;
+
+    /// How far away the listener notices events, in blocks
+    fn range(&self) -> u32;
+
+    /// Called for every event in range
+    async fn handle(
+        &self,
+        world: &Arc<World>,
+        event: GameEvent,
+        position: Vector3<f64>,
+        context: &GameEventContext,
+    );
+
+    /// Called every tick, like to let a vibration travel to the listener. The listener is
+    /// unregistered once this returns false
+    async fn tick(&self, _world: &Arc<World>) -> bool {
+        true
+    }
+
+    /// Where the debug renderer of the client shows the listener
+    fn position_source(&self) -> PositionSource {
+        let position = self.position();
+        PositionSource::Block(BlockPos::floored(position.x, position.y, position.z))
+    }
+}
+
+#[derive(Default)]
+struct Listeners {
+    listeners: HashMap<ListenerId, Arc<dyn GameEventListener>>,
+    /// Listeners belonging to a block, like a sculk sensor
+    blocks: HashMap<BlockPos, ListenerId>,
+}
+
+/// The game event listeners of a world
+#[derive(Default)]
+pub struct GameEventListeners {
+    next_id: AtomicU64,
+    inner: RwLock<Listeners>,
+}
+
+impl GameEventListeners {
+    pub async fn register(&self, listener: Arc<dyn GameEventListener>) -> ListenerId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.inner.write().await.listeners.insert(id, listener);
+        id
+    }
+
+    /// Returns whether there was a listener with the id
+    pub async fn unregister(&self, id: ListenerId) -> bool {
+        let mut inner = self.inner.write().await;
+        inner.blocks.retain(|_, listener| *listener != id);
+        inner.listeners.remove(&id).is_some()
+    }
+
+    /// Registers the listener of a block, replacing the one the block had before
+    pub async fn register_block(&self, position: BlockPos, listener: Arc<dyn GameEventListener>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut inner = self.inner.write().await;
+        if let Some(old) = inner.blocks.insert(position, id) {
+            inner.listeners.remove(&old);
+        }
+        inner.listeners.insert(id, listener);
+    }
+
+    pub async fn unregister_block(&self, position: &BlockPos) {
+        let mut inner = self.inner.write().await;
+        if let Some(id) = inner.blocks.remove(position) {
+            inner.listeners.remove(&id);
+        }
+    }
+
+    /// The listeners which have the position in range, closest first
+    pub async fn in_range(&self, position: Vector3<f64>) -> Vec<Arc<dyn GameEventListener>> {
+        let mut listeners: Vec<_> = self
+            .inner
+            .read()
+            .await
+            .listeners
+            .values()
+            .filter_map(|listener| {
+                let distance = listener.position().squared_distance_to_vec(position);
+                let range = f64::from(listener.range());
+                (distance <= range * range).then(|| (distance, listener.clone()))
+            })
+            .collect();
+        listeners.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        listeners
+            .into_iter()
+            .map(|(_, listener)| listener)
+            .collect()
+    }
+
+    pub(crate) async fn tick(&self, world: &Arc<World>) {
+        let listeners: Vec<_> = self
+            .inner
+            .read()
+            .await
+            .listeners
+            .iter()
+            .map(|(id, listener)| (*id, listener.clone()))
+            .collect();
+        for (id, listener) in listeners {
+            if !listener.tick(world).await {
+                self.unregister(id).await;
+            }
+        }
+    }
+}
+
+/// The middle of the block
+#[must_use]
+pub fn block_center(position: &BlockPos) -> Vector3<f64> {
+    Vector3::new(
+        f64::from(position.0.x) + 0.5,
+        f64::from(position.0.y) + 0.5,
+        f64::from(position.0.z) + 0.5,
+    )
+}
+
+/// The frequency of the vibration the event makes, from 1 to 15. Events without one, like
+/// `item_interact_start`, don't make vibrations
+#[must_use]
+pub const fn vibration_frequency(event: GameEvent) -> Option<u8> {
+    Some(match event {
+        GameEvent::Step | GameEvent::Swim | GameEvent::Flap | GameEvent::Resonate1 => 1,
+        GameEvent::ProjectileLand
+        | GameEvent::HitGround
+        | GameEvent::Splash
+        | GameEvent::Resonate2 => 2,
+        GameEvent::ItemInteractFinish
+        | GameEvent::ProjectileShoot
+        | GameEvent::InstrumentPlay
+        | GameEvent::Resonate3 => 3,
+        GameEvent::EntityAction
+        | GameEvent::ElytraGlide
+        | GameEvent::Unequip
+        | GameEvent::Resonate4 => 4,
+        GameEvent::EntityDismount | GameEvent::Equip | GameEvent::Resonate5 => 5,
+        GameEvent::EntityInteract
+        | GameEvent::Shear
+        | GameEvent::EntityMount
+        | GameEvent::Resonate6 => 6,
+        GameEvent::EntityDamage | GameEvent::Resonate7 => 7,
+        GameEvent::Drink | GameEvent::Eat | GameEvent::Resonate8 => 8,
+        GameEvent::ContainerClose
+        | GameEvent::BlockClose
+        | GameEvent::BlockDeactivate
+        | GameEvent::BlockDetach
+        | GameEvent::Resonate9 => 9,
+        GameEvent::ContainerOpen
+        | GameEvent::BlockOpen
+        | GameEvent::BlockActivate
+        | GameEvent::BlockAttach
+        | GameEvent::PrimeFuse
+        | GameEvent::NoteBlockPlay
+        | GameEvent::Resonate10 => 10,
+        GameEvent::BlockChange | GameEvent::Resonate11 => 11,
+        GameEvent::BlockDestroy | GameEvent::FluidPickup | GameEvent::Resonate12 => 12,
+        GameEvent::BlockPlace | GameEvent::FluidPlace | GameEvent::Resonate13 => 13,
+        GameEvent::EntityPlace
+        | GameEvent::LightningStrike
+        | GameEvent::Teleport
+        | GameEvent::Resonate14 => 14,
+        GameEvent::EntityDie | GameEvent::Explode | GameEvent::Resonate15 => 15,
+        _ => return None,
+    })
+}
+
+/// Whether a vibration listener ignores the event, like a player sneaking past or walking on wool
+#[must_use]
+pub fn is_dampened(event: GameEvent, context: &GameEventContext) -> bool {
+    let tagged = |registry, tag, name: &str| {
+        get_tag_values(registry, tag).is_some_and(|values| values.contains(&name))
+    };
+    if context.sneaking
+        && tagged(
+            RegistryKey::GameEvent,
+            "minecraft:ignore_vibrations_sneaking",
+            event.to_name(),
+        )
+    {
+        return true;
+    }
+    context
+        .affected_state
+        .and_then(get_block_by_state_id)
+        .is_some_and(|block| {
+            tagged(
+                RegistryKey::Block,
+                "minecraft:dampens_vibrations",
+                block.name,
+            )
+        })
+}
+
+/// Whether a block occluding vibrations, like wool, lies between the two positions. The blocks
+/// the positions are in don't count
+pub async fn is_occluded(world: &World, from: Vector3<f64>, to: Vector3<f64>) -> bool {
+    let Some(occluding) =
+        get_tag_values(RegistryKey::Block, "minecraft:occludes_vibration_signals")
+    else {
+        return false;
+    };
+    let from = BlockPos::floored(from.x, from.y, from.z);
+    let to = BlockPos::floored(to.x, to.y, to.z);
+    let start = block_center(&from);
+    let direction = block_center(&to).sub(&start);
+    let distance = direction.length();
+    if distance <= 0.0 {
+        return false;
+    }
+
+    for step in VoxelRay::new(start, direction, distance) {
+        let position = BlockPos(step.pos);
+        if position == from || position == to {
+            continue;
+        }
+        if world
+            .get_block(&position)
+            .await
+            .is_ok_and(|block| occluding.contains(&block.name))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::{block::Block, game_event::GameEvent};
+
+    use super::{GameEventContext, is_dampened, vibration_frequency};
+
+    #[test]
+    fn dampened_vibrations() {
+        assert_eq!(vibration_frequency(GameEvent::Step), Some(1));
+        assert_eq!(vibration_frequency(GameEvent::BlockPlace), Some(13));
+        assert_eq!(vibration_frequency(GameEvent::ItemInteractStart), None);
+
+        let sneaking = GameEventContext {
+            sneaking: true,
+            ..Default::default()
+        };
+        assert!(is_dampened(GameEvent::Step, &sneaking));
+        assert!(!is_dampened(GameEvent::BlockPlace, &sneaking));
+
+        let on_wool = GameEventContext::default().with_state(Block::WHITE_WOOL.default_state_id);
+        assert!(is_dampened(GameEvent::Step, &on_wool));
+        let on_stone = GameEventContext::default().with_state(Block::STONE.default_state_id);
+        assert!(!is_dampened(GameEvent::Step, &on_stone));
+    }
+}
@@ -0,0 +1,461 @@
+//! Rolls the loot tables of blocks and entities into the items they drop, following the vanilla
+//! rules for pools, weighted entries, conditions and functions.
+//!
+//! TODO: Items don't store their enchantments yet, so silk touch, fortune and looting never apply
+
+use pumpkin_data::item::Item;
+use pumpkin_data::tag::{RegistryKey, Tagable, get_tag_values};
+use pumpkin_util::loot_table::{
+    AlternativeEntry, BonusFormula, ItemEntry, LootCondition, LootFunction, LootFunctionTypes,
+    LootNumberProvider, LootPool, LootPoolEntry, LootPoolEntryTypes, LootTable, TagEntry,
+};
+use pumpkin_util::random::weighted;
+use pumpkin_world::item::ItemStack;
+use rand::Rng;
+
+/// What the loot is dropped for, which the conditions and functions of a table check
+#[derive(Default)]
+pub struct LootContextParameters<'a> {
+    /// The properties of the broken block
+    pub block_props: &'a [(&'a str, &'a str)],
+    /// The item the block was broken or the entity was killed with
+    pub tool: Option<&'a ItemStack>,
+    /// The power of the explosion the block was broken by
+    pub explosion_radius: Option<f32>,
+    pub killed_by_player: bool,
+}
+
+/// The level of the enchantment on the tool, 0 without one
+const fn enchantment_level(_tool: Option<&ItemStack>, _enchantment: &str) -> i32 {
+    // TODO: Use the enchantments of the tool once items store them
+    0
+}
+
+// The loot tables are defined in pumpkin_util, which knows nothing about items
+
+pub trait LootTableExt {
+    fn get_loot(&self, params: &LootContextParameters) -> Vec<ItemStack>;
+}
+
+impl LootTableExt for LootTable {
+    fn get_loot(&self, params: &LootContextParameters) -> Vec<ItemStack> {
+        let mut items = vec![];
+        for pool in self.pools.unwrap_or_default() {
+            items.extend(pool.get_loot(params));
+        }
+        let items = apply_functions(items, self.functions, params);
+
+        // Counts above the stack size are split up, like 20 lapis lazuli with fortune
+        let mut stacks = vec![];
+        for mut stack in items {
+            let max_stack_size = stack.item.components.max_stack_size.max(1);
+            while stack.item_count > max_stack_size {
+                stacks.push(ItemStack::new(max_stack_size, stack.item.clone()));
+                stack.item_count -= max_stack_size;
+            }
+            stacks.push(stack);
+        }
+        stacks
+    }
+}
+
+trait LootPoolExt {
+    fn get_loot(&self, params: &LootContextParameters) -> Vec<ItemStack>;
+}
+
+impl LootPoolExt for LootPool {
+    fn get_loot(&self, params: &LootContextParameters) -> Vec<ItemStack> {
+        if !test_conditions(self.conditions, params) {
+            return vec![];
+        }
+        // TODO: Add the bonus rolls times the luck of the player
+        let rolls = self.rolls.get_int();
+        let mut items = vec![];
+        for _ in 0..rolls {
+            let entries: Vec<&LootPoolEntry> = self
+                .entries
+                .iter()
+                .filter(|entry| entry.weight > 0 && test_conditions(entry.conditions, params))
+                .collect();
+            let total_weight = weighted::total_weight(&entries);
+            if total_weight <= 0 {
+                continue;
+            }
+            let roll = rand::thread_rng().gen_range(0..total_weight);
+            if let Some(entry) = weighted::get_at(&entries, roll) {
+                items.extend(entry.get_items(params));
+            }
+        }
+        apply_functions(items, self.functions, params)
+    }
+}
+
+trait LootPoolEntryExt {
+    fn get_items(&self, params: &LootContextParameters) -> Vec<ItemStack>;
+}
+
+impl LootPoolEntryExt for LootPoolEntry {
+    fn get_items(&self, params: &LootContextParameters) -> Vec<ItemStack> {
+        apply_functions(self.content.get_items(params), self.functions, params)
+    }
+}
+
+trait ItemEntryExt {
+    fn get_items(&self) -> Vec<ItemStack>;
+}
+
+impl ItemEntryExt for ItemEntry {
+    fn get_items(&self) -> Vec<ItemStack> {
+        let item = self.name.strip_prefix("minecraft:").unwrap_or(self.name);
+        Item::from_registry_key(item)
+            .map(|item| ItemStack::new(1, item))
+            .into_iter()
+            .collect()
+    }
+}
+
+trait TagEntryExt {
+    fn get_items(&self) -> Vec<ItemStack>;
+}
+
+impl TagEntryExt for TagEntry {
+    fn get_items(&self) -> Vec<ItemStack> {
+        let items: Vec<ItemStack> = get_tag_values(RegistryKey::Item, self.name)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|item| Item::from_registry_key(item))
+            .map(|item| ItemStack::new(1, item))
+            .collect();
+        if !self.expand || items.is_empty() {
+            return items;
+        }
+        let index = rand::thread_rng().gen_range(0..items.len());
+        vec![items[index].clone()]
+    }
+}
+
+trait AlternativeEntryExt {
+    fn get_items(&self, params: &LootContextParameters) -> Vec<ItemStack>;
+}
+
+impl AlternativeEntryExt for AlternativeEntry {
+    /// The items of the first child whose conditions pass
+    fn get_items(&self, params: &LootContextParameters) -> Vec<ItemStack> {
+        self.children
+            .iter()
+            .find(|child| test_conditions(child.conditions, params))
+            .map_or_else(Vec::new, |child| child.get_items(params))
+    }
+}
+
+trait LootPoolEntryTypesExt {
+    fn get_items(&self, params: &LootContextParameters) -> Vec<ItemStack>;
+}
+
+impl LootPoolEntryTypesExt for LootPoolEntryTypes {
+    fn get_items(&self, params: &LootContextParameters) -> Vec<ItemStack> {
+        match self {
+            Self::Item(item_entry) => item_entry.get_items(),
+            Self::Tag(tag_entry) => tag_entry.get_items(),
+            Self::Alternatives(alternative) => alternative.get_items(params),
+            // TODO: Tables referenced by others, like the wool of sheep, and the contents of
+            // blocks, like the sherds of decorated pots, aren't generated
+            Self::Empty | Self::LootTable | Self::Dynamic | Self::Sequence | Self::Group => vec![],
+        }
+    }
+}
+
+fn test_conditions(conditions: Option<&[LootCondition]>, params: &LootContextParameters) -> bool {
+    conditions
+        .unwrap_or_default()
+        .iter()
+        .all(|condition| condition.test(params))
+}
+
+trait LootConditionExt {
+    fn test(&self, params: &LootContextParameters) -> bool;
+}
+
+impl LootConditionExt for LootCondition {
+    fn test(&self, params: &LootContextParameters) -> bool {
+        match self {
+            Self::Inverted(term) => !term.test(params),
+            Self::AnyOf(terms) => terms.iter().any(|term| term.test(params)),
+            Self::AllOf(terms) => terms.iter().all(|term| term.test(params)),
+            Self::RandomChance { chance } => rand::random::<f32>() < *chance,
+            Self::RandomChanceWithEnchantedBonus {
+                enchantment,
+                unenchanted_chance,
+                base,
+                per_level_above_first,
+            } => {
+                let level = enchantment_level(params.tool, enchantment);
+                let chance = if level > 0 {
+                    base + per_level_above_first * (level - 1) as f32
+                } else {
+                    *unenchanted_chance
+                };
+                rand::random::<f32>() < chance
+            }
+            Self::KilledByPlayer => params.killed_by_player,
+            Self::BlockStateProperty { properties } => properties.iter().all(|(key, value)| {
+                params
+                    .block_props
+                    .iter()
+                    .any(|(k, v)| k == key && v == value)
+            }),
+            Self::MatchTool {
+                items,
+                enchantments,
+            } => {
+                let Some(tool) = params.tool else {
+                    return false;
+                };
+                let matches_item = items.is_none_or(|items| {
+                    if items.starts_with('#') {
+                        tool.item.is_tagged_with(items).unwrap_or_default()
+                    } else {
+                        items.strip_prefix("minecraft:").unwrap_or(items) == tool.item.registry_key
+                    }
+                });
+                matches_item
+                    && enchantments.iter().all(|(enchantment, min)| {
+                        enchantment_level(params.tool, enchantment) >= *min
+                    })
+            }
+            Self::TableBonus {
+                enchantment,
+                chances,
+            } => {
+                let level = enchantment_level(params.tool, enchantment).max(0) as usize;
+                chances
+                    .get(level.min(chances.len().saturating_sub(1)))
+                    .is_some_and(|chance| rand::random::<f32>() < *chance)
+            }
+            Self::SurvivesExplosion => params
+                .explosion_radius
+                .is_none_or(|radius| rand::random::<f32>() <= 1.0 / radius),
+            _ => false,
+        }
+    }
+}
+
+fn apply_functions(
+    items: Vec<ItemStack>,
+    functions: Option<&[LootFunction]>,
+    params: &LootContextParameters,
+) -> Vec<ItemStack> {
+    let functions = functions.unwrap_or_default();
+    items
+        .into_iter()
+        .filter_map(|mut stack| {
+            let mut count = i32::from(stack.item_count);
+            for function in functions {
+                if test_conditions(function.conditions, params) {
+                    count = function.content.apply(count, params);
+                }
+            }
+            stack.item_count = u8::try_from(count.max(0)).unwrap_or(u8::MAX);
+            (stack.item_count > 0).then_some(stack)
+        })
+        .collect()
+}
+
+trait LootFunctionTypesExt {
+    /// The new count of the item
+    fn apply(&self, count: i32, params: &LootContextParameters) -> i32;
+}
+
+impl LootFunctionTypesExt for LootFunctionTypes {
+    fn apply(&self, count: i32, params: &LootContextParameters) -> i32 {
+        match self {
+            Self::SetCount { count: set, add } => {
+                let set = set.get_int();
+                if *add { count + set } else { set }
+            }
+            Self::LimitCount { min, max } => {
+                let count = min.map_or(count, |min| count.max(min as i32));
+                max.map_or(count, |max| count.min(max as i32))
+            }
+            Self::ApplyBonus {
+                enchantment,
+                formula,
+            } => {
+                let level = enchantment_level(params.tool, enchantment);
+                formula.apply(count, level)
+            }
+            Self::EnchantedCountIncrease {
+                enchantment,
+                count: increase,
+                limit,
+            } => {
+                let level = enchantment_level(params.tool, enchantment);
+                if level <= 0 {
+                    return count;
+                }
+                let count = count + (increase.get_float() * level as f32).round() as i32;
+                if *limit > 0 { count.min(*limit) } else { count }
+            }
+            Self::ExplosionDecay => params.explosion_radius.map_or(count, |radius| {
+                (0..count)
+                    .filter(|_| rand::random::<f32>() <= 1.0 / radius)
+                    .count() as i32
+            }),
+            // TODO: Cooking the drops of burning mobs needs the smelting recipes
+            Self::FurnaceSmelt
+            | Self::CopyComponents
+            | Self::CopyState
+            | Self::SetPotion
+            | Self::SetOminousBottleAmplifier => count,
+        }
+    }
+}
+
+trait BonusFormulaExt {
+    fn apply(&self, count: i32, level: i32) -> i32;
+}
+
+impl BonusFormulaExt for BonusFormula {
+    fn apply(&self, count: i32, level: i32) -> i32 {
+        if level <= 0 {
+            return count;
+        }
+        let mut rng = rand::thread_rng();
+        match self {
+            Self::OreDrops => {
+                let bonus = (rng.gen_range(0..level + 2) - 1).max(0);
+                count * (bonus + 1)
+            }
+            Self::UniformBonusCount { bonus_multiplier } => {
+                count + rng.gen_range(0..=bonus_multiplier * level)
+            }
+            Self::BinomialWithBonusCount { extra, probability } => {
+                count
+                    + (0..level + extra)
+                        .filter(|_| rng.r#gen::<f32>() < *probability)
+                        .count() as i32
+            }
+        }
+    }
+}
+
+trait LootNumberProviderExt {
+    fn get_float(&self) -> f32;
+    fn get_int(&self) -> i32;
+}
+
+impl LootNumberProviderExt for LootNumberProvider {
+    fn get_float(&self) -> f32 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Uniform { min, max } if min < max => rand::thread_rng().gen_range(*min..*max),
+            Self::Uniform { min, .. } => *min,
+            Self::Binomial { .. } => self.get_int() as f32,
+        }
+    }
+
+    fn get_int(&self) -> i32 {
+        match self {
+            Self::Constant(value) => value.round() as i32,
+            Self::Uniform { min, max } => {
+                let (min, max) = (min.floor() as i32, max.floor() as i32);
+                if min < max {
+                    rand::thread_rng().gen_range(min..=max)
+                } else {
+                    min
+                }
+            }
+            Self::Binomial { n, p } => (0..n.floor() as i32)
+                .filter(|_| rand::random::<f32>() < *p)
+                .count() as i32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::block::Block;
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_data::item::Item;
+    use pumpkin_util::loot_table::{
+        ItemEntry, LootCondition, LootFunction, LootFunctionTypes, LootNumberProvider, LootPool,
+        LootPoolEntry, LootPoolEntryTypes, LootTable, LootTableType,
+    };
+
+    use super::{LootContextParameters, LootTableExt};
+
+    const fn entry(name: &'static str, weight: i32) -> LootPoolEntry {
+        LootPoolEntry {
+            content: LootPoolEntryTypes::Item(ItemEntry { name }),
+            conditions: None,
+            functions: None,
+            weight,
+        }
+    }
+
+    const fn table(pools: &'static [LootPool]) -> LootTable {
+        LootTable {
+            r#type: LootTableType::Chest,
+            random_sequence: None,
+            pools: Some(pools),
+            functions: None,
+        }
+    }
+
+    #[test]
+    fn one_weighted_entry_per_roll() {
+        const POOLS: &[LootPool] = &[LootPool {
+            entries: &[entry("minecraft:diamond", 1), entry("minecraft:dirt", 0)],
+            conditions: None,
+            functions: Some(&[LootFunction {
+                content: LootFunctionTypes::SetCount {
+                    count: LootNumberProvider::Uniform { min: 2.0, max: 4.0 },
+                    add: false,
+                },
+                conditions: None,
+            }]),
+            rolls: LootNumberProvider::Constant(3.0),
+            bonus_rolls: 0.0,
+        }];
+        let table = table(POOLS);
+        let loot = table.get_loot(&LootContextParameters::default());
+        assert_eq!(loot.len(), 3);
+        for stack in loot {
+            assert_eq!(stack.item.id, Item::DIAMOND.id);
+            assert!((2..=4).contains(&stack.item_count));
+        }
+    }
+
+    #[test]
+    fn conditions() {
+        const POOLS: &[LootPool] = &[LootPool {
+            entries: &[entry("minecraft:emerald", 1)],
+            conditions: Some(&[LootCondition::Inverted(&LootCondition::KilledByPlayer)]),
+            functions: None,
+            rolls: LootNumberProvider::Constant(1.0),
+            bonus_rolls: 0.0,
+        }];
+        let table = table(POOLS);
+        assert_eq!(table.get_loot(&LootContextParameters::default()).len(), 1);
+        let by_player = LootContextParameters {
+            killed_by_player: true,
+            ..Default::default()
+        };
+        assert!(table.get_loot(&by_player).is_empty());
+    }
+
+    #[test]
+    fn vanilla_tables() {
+        let stone = Block::STONE.loot_table.unwrap();
+        let loot = stone.get_loot(&LootContextParameters::default());
+        assert_eq!(loot.len(), 1);
+        assert_eq!(loot[0].item.id, Item::COBBLESTONE.id);
+
+        let zombie = EntityType::ZOMBIE.loot_table().unwrap();
+        for stack in zombie.get_loot(&LootContextParameters::default()) {
+            // The rare drops need a player to kill the zombie
+            assert_eq!(stack.item.id, Item::ROTTEN_FLESH.id);
+            assert!(stack.item_count <= 2);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! The worlds of the server, one for each dimension the config enables. The overworld is always
+//! loaded, players join and respawn there.
+
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_registry::DimensionType;
+use pumpkin_world::dimension::Dimension;
+
+use super::World;
+
+pub struct WorldManager {
+    worlds: BTreeMap<Dimension, Arc<World>>,
+}
+
+impl WorldManager {
+    /// Loads the overworld and the dimensions which are allowed from the world folder
+    #[must_use]
+    pub fn load(world_folder: &Path) -> Self {
+        let dimensions = [
+            (Dimension::OverWorld, DimensionType::Overworld, true),
+            (
+                Dimension::Nether,
+                DimensionType::TheNether,
+                BASIC_CONFIG.allow_nether,
+            ),
+            (
+                Dimension::End,
+                DimensionType::TheEnd,
+                BASIC_CONFIG.allow_end,
+            ),
+        ];
+        let worlds = dimensions
+            .into_iter()
+            .filter(|(_, _, allowed)| *allowed)
+            .map(|(dimension, dimension_type, _)| {
+                let level = dimension.into_level(world_folder.to_path_buf());
+                (dimension, Arc::new(World::load(level, dimension_type)))
+            })
+            .collect();
+        Self { worlds }
+    }
+
+    /// The overworld, where players join and respawn
+    #[must_use]
+    pub fn default_world(&self) -> &Arc<World> {
+        &self.worlds[&Dimension::OverWorld]
+    }
+
+    #[must_use]
+    pub fn get(&self, dimension: Dimension) -> Option<&Arc<World>> {
+        self.worlds.get(&dimension)
+    }
+
+    /// The world of the dimension type with the name, like `minecraft:the_nether`
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<&Arc<World>> {
+        self.iter()
+            .find(|world| world.dimension_type.name().to_string() == name)
+    }
+
+    /// All loaded worlds, the overworld first
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<World>> {
+        self.worlds.values()
+    }
+}
@@ -1,55 +1,84 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, atomic::Ordering},
 };
 
 pub mod chunker;
+pub mod entity_effects;
+pub mod entity_index;
+pub mod entity_shard;
 pub mod explosion;
+pub mod game_event;
+pub mod loot;
 pub mod time;
 
 use crate::{
-    PLUGIN_MANAGER, block,
+    PLUGIN_MANAGER,
+    block::{self, CampfireItems, tick_campfires},
     command::client_suggestions,
-    entity::{Entity, EntityBase, EntityId, player::Player},
+    entity::{Entity, EntityBase, EntityId, ids::ENTITY_IDS, player::Player},
     error::PumpkinError,
     plugin::{
         block::block_break::BlockBreakEvent,
         player::{player_join::PlayerJoinEvent, player_leave::PlayerLeaveEvent},
         world::{chunk_load::ChunkLoad, chunk_save::ChunkSave, chunk_send::ChunkSend},
     },
-    server::Server,
+    server::{Server, tick::TickPhase},
 };
+use block_updates::{BlockUpdates, NeighborUpdate};
 use border::Worldborder;
+use claims::Claims;
+use dragon_fight::DragonFight;
+use entity_effects::{EffectQueue, EntityEffect};
+use entity_index::EntityIndex;
 use explosion::Explosion;
-use pumpkin_config::BasicConfiguration;
+use game_event::{GameEventContext, GameEventListeners};
+use loot::LootContextParameters;
+use pumpkin_config::{BasicConfiguration, advanced_config};
 use pumpkin_data::{
+    chunk::Biome,
+    damage::DamageType,
     entity::{EntityStatus, EntityType},
     particle::Particle,
     sound::{Sound, SoundCategory},
+    tag::Tagable,
     world::WorldEvent,
 };
+use pumpkin_inventory::RecipeManager;
 use pumpkin_macros::send_cancellable;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_protocol::{
     ClientPacket,
     client::play::{
-        CEntityStatus, CGameEvent, CLogin, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo,
-        CSpawnEntity, GameEvent, PlayerAction,
+        CDebugGameEvent, CDebugGameEventListener, CEntityStatus, CGameEvent, CLogin,
+        CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo, CSpawnEntity, CUpdateRecipes,
+        GameEvent, PlayerAction,
     },
+    packet_encoder::EncodedPacket,
 };
 use pumpkin_protocol::{client::play::CLevelEvent, codec::identifier::Identifier};
 use pumpkin_protocol::{
     client::play::{
-        CBlockUpdate, CDisguisedChatMessage, CExplosion, CRespawn, CSetBlockDestroyStage,
-        CWorldEvent,
+        CBlockUpdate, CChunkBiomes, CDisguisedChatMessage, CExplosion, CRespawn,
+        CSetBlockDestroyStage, CWorldEvent,
     },
     codec::var_int::VarInt,
 };
 use pumpkin_registry::DimensionType;
+use pumpkin_util::math::boundingbox::BoundingBox;
 use pumpkin_util::math::vector2::Vector2;
-use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_util::math::{
+    position::{BlockPos, GlobalPos},
+    vector3::Vector3,
+};
 use pumpkin_util::text::{TextComponent, color::NamedColor};
+use pumpkin_util::{Difficulty, GameMode};
+use pumpkin_world::item::ItemStack;
 use pumpkin_world::level::Level;
 use pumpkin_world::level::SyncChunk;
+use pumpkin_world::raycast::{self, BlockHitResult, EntityHitResult, FluidHandling};
+use pumpkin_world::world_info::game_rules::{self, GameRule, GameRuleType, GameRules};
+use pumpkin_world::{WORLD_LOWEST_Y, WORLD_MAX_Y};
 use pumpkin_world::{block::BlockDirection, chunk::ChunkData};
 use pumpkin_world::{
     block::registry::{
@@ -57,21 +86,34 @@ use pumpkin_world::{
     },
     coordinates::ChunkRelativeBlockCoordinates,
 };
+use query::WorldQuery;
 use rand::{Rng, thread_rng};
-use scoreboard::Scoreboard;
 use thiserror::Error;
 use time::LevelTime;
 use tokio::sync::{Mutex, mpsc::UnboundedReceiver};
 use tokio::sync::{RwLock, mpsc};
 
+pub mod block_updates;
 pub mod border;
 pub mod bossbar;
+pub mod claims;
 pub mod custom_bossbar;
-pub mod scoreboard;
+pub mod dragon_fight;
+pub mod end;
+pub mod entity_storage;
+pub mod manager;
+pub mod nether_portal;
+pub mod query;
+pub mod seed;
+pub mod spawning;
 pub mod weather;
 
 use weather::Weather;
 
+/// How far (in blocks) beyond a queried box the entity index is searched, as an entity's bounding
+/// box can reach into the box while its position is in a neighbouring chunk
+const ENTITY_INDEX_MARGIN: f64 = 2.0;
+
 #[derive(Debug, Error)]
 pub enum GetBlockError {
     BlockOutOfWorldBounds,
@@ -115,8 +157,10 @@ pub struct World {
     /// A map of active entities within the world, keyed by their unique UUID.
     /// This does not include Players
     pub entities: Arc<RwLock<HashMap<uuid::Uuid, Arc<dyn EntityBase>>>>,
-    /// The world's scoreboard, used for tracking scores, objectives, and display information.
-    pub scoreboard: Mutex<Scoreboard>,
+    /// Spatial index of `entities` by chunk, see `entities_in_box`
+    pub entity_index: EntityIndex<Arc<dyn EntityBase>>,
+    /// Spatial index of `players` by chunk
+    pub player_index: EntityIndex<Arc<Player>>,
     /// The world's worldborder, defining the playable area and controlling its expansion or contraction.
     pub worldborder: Mutex<Worldborder>,
     /// The world's time, including counting ticks for weather, time cycles and statistics
@@ -125,26 +169,111 @@ pub struct World {
     pub dimension_type: DimensionType,
     /// The world's weather, including rain and thunder levels
     pub weather: Mutex<Weather>,
-    // TODO: entities
+    /// The game rules of the world, see `World::game_rule`
+    pub game_rules: std::sync::RwLock<GameRules>,
+    /// The discs inside of jukeboxes
+    // TODO: Save these with the chunk once there are block entities
+    pub jukebox_records: Mutex<HashMap<BlockPos, ItemStack>>,
+    /// The items cooking on campfires
+    // TODO: Save these with the chunk once there are block entities
+    pub(crate) campfire_items: Mutex<HashMap<BlockPos, CampfireItems>>,
+    /// How many players are looking into each ender chest, its lid stays open while there are any
+    pub ender_chest_viewers: Mutex<HashMap<BlockPos, u8>>,
+    /// The regions players claimed, only loaded when claims are enabled in the config
+    pub claims: Mutex<Claims>,
+    /// Everything listening for game events, like sculk sensors
+    // TODO: Register the listeners of loaded blocks once there are block entities
+    pub game_event_listeners: GameEventListeners,
+    /// The neighbor updates queued in this world, see `update_neighbors`
+    pub block_updates: BlockUpdates,
+    /// The chunks whose saved entities were spawned, see `entity_storage`
+    pub entity_chunks: Mutex<HashSet<Vector2<i32>>>,
+    /// The fight against the ender dragon, only the End has one
+    pub dragon_fight: Option<Mutex<DragonFight>>,
+    /// What entities did to others or the world while they were ticked, see `entity_effects`
+    pub entity_effects: EffectQueue<EntityEffect>,
 }
 
 impl World {
     #[must_use]
     pub fn load(level: Level, dimension_type: DimensionType) -> Self {
+        let claims = if advanced_config().claims.enabled {
+            Claims::load(&level.data_path(claims::CLAIMS_FILE))
+        } else {
+            Claims::default()
+        };
+        let level_time = LevelTime::from_level_info(&level.level_info);
+        let weather = Weather::from_level_info(&level.level_info);
+        let game_rules = level.level_info.game_rules.clone();
+        let dragon_fight = matches!(dimension_type, DimensionType::TheEnd).then(|| {
+            Mutex::new(DragonFight::new(
+                level.data_path(dragon_fight::DRAGON_FIGHT_FILE),
+                level.seed.0,
+            ))
+        });
         Self {
             level: Arc::new(level),
             players: Arc::new(RwLock::new(HashMap::new())),
             entities: Arc::new(RwLock::new(HashMap::new())),
-            scoreboard: Mutex::new(Scoreboard::new()),
+            entity_index: EntityIndex::default(),
+            player_index: EntityIndex::default(),
             worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 29_999_984.0, 0, 0, 0)),
-            level_time: Mutex::new(LevelTime::new()),
+            level_time: Mutex::new(level_time),
             dimension_type,
-            weather: Mutex::new(Weather::new()),
+            weather: Mutex::new(weather),
+            game_rules: std::sync::RwLock::new(game_rules),
+            jukebox_records: Mutex::new(HashMap::new()),
+            campfire_items: Mutex::new(HashMap::new()),
+            ender_chest_viewers: Mutex::new(HashMap::new()),
+            claims: Mutex::new(claims),
+            game_event_listeners: GameEventListeners::default(),
+            entity_chunks: Mutex::new(HashSet::new()),
+            block_updates: BlockUpdates::new(&advanced_config().block_updates),
+            dragon_fight,
+            entity_effects: EffectQueue::default(),
         }
     }
 
     pub async fn save(&self) {
-        self.level.save().await;
+        let mut level_info = self.level.level_info.clone();
+        self.level_time
+            .lock()
+            .await
+            .write_level_info(&mut level_info);
+        self.weather.lock().await.write_level_info(&mut level_info);
+        level_info.game_rules = self.game_rules.read().unwrap().clone();
+        entity_storage::save_all(self).await;
+        self.level.save(level_info).await;
+    }
+
+    /// The value the game rule has in this world
+    pub fn game_rule<T: GameRuleType>(&self, rule: GameRule<T>) -> T {
+        self.game_rules.read().unwrap().get(rule)
+    }
+
+    /// Saves the claims right after they changed, as that doesn't happen often
+    pub async fn save_claims(&self) {
+        let path = self.level.data_path(claims::CLAIMS_FILE);
+        if let Err(err) = self.claims.lock().await.save(&path) {
+            log::error!("Failed to save the claims to {}: {err}", path.display());
+        }
+    }
+
+    /// Read-only access to the loaded chunks which doesn't hold up the tick, see `WorldQuery`
+    #[must_use]
+    pub fn query(&self) -> WorldQuery {
+        WorldQuery::new(self.level.clone())
+    }
+
+    /// The difficulty of the world, like the chance of a villager being infected by a zombie
+    #[must_use]
+    pub fn difficulty(&self) -> Difficulty {
+        match self.level.level_info.difficulty {
+            0 => Difficulty::Peaceful,
+            1 => Difficulty::Easy,
+            3 => Difficulty::Hard,
+            _ => Difficulty::Normal,
+        }
     }
 
     pub async fn send_entity_status(&self, entity: &Entity, status: EntityStatus) {
@@ -162,9 +291,29 @@ impl World {
     where
         P: ClientPacket,
     {
+        self.broadcast_encoded(&EncodedPacket::new(packet)).await;
+    }
+
+    /// Like `broadcast_packet_all`, for packets which are also sent to other worlds
+    pub async fn broadcast_encoded(&self, packet: &EncodedPacket) {
+        let current_players = self.players.read().await;
+        for player in current_players.values() {
+            player.client.send_encoded(packet).await;
+        }
+    }
+
+    /// Broadcasts a weather packet to all players in the world, except those who have their own
+    /// weather set
+    pub async fn broadcast_weather_packet<P>(&self, packet: &P)
+    where
+        P: ClientPacket,
+    {
+        let packet = EncodedPacket::new(packet);
         let current_players = self.players.read().await;
         for player in current_players.values() {
-            player.client.send_packet(packet).await;
+            if player.client_weather().is_none() {
+                player.client.send_encoded(&packet).await;
+            }
         }
     }
 
@@ -193,9 +342,10 @@ impl World {
     where
         P: ClientPacket,
     {
+        let packet = EncodedPacket::new(packet);
         let current_players = self.players.read().await;
         for (_, player) in current_players.iter().filter(|c| !except.contains(c.0)) {
-            player.client.send_packet(packet).await;
+            player.client.send_encoded(&packet).await;
         }
     }
 
@@ -271,47 +421,187 @@ impl World {
         .await;
     }
 
-    pub async fn tick(&self, server: &Server) {
-        // world ticks
-        {
-            let mut level_time = self.level_time.lock().await;
-            level_time.tick_time();
-            if level_time.world_age % 20 == 0 {
-                level_time.send_time(self).await;
+    /// Lets the listeners in range know about the event, see `game_event`
+    pub async fn emit_game_event(
+        self: &Arc<Self>,
+        event: pumpkin_data::game_event::GameEvent,
+        position: Vector3<f64>,
+        context: GameEventContext,
+    ) {
+        let debug = advanced_config().logging.game_event_debug;
+        if debug {
+            self.broadcast_packet_all(&CDebugGameEvent::new(
+                Identifier::vanilla(event.to_name()),
+                position,
+            ))
+            .await;
+        }
+        for listener in self.game_event_listeners.in_range(position).await {
+            if debug {
+                self.broadcast_packet_all(&CDebugGameEventListener::new(
+                    listener.position_source(),
+                    VarInt(listener.range() as i32),
+                ))
+                .await;
             }
+            listener.handle(self, event, position, &context).await;
         }
+    }
 
-        {
-            let mut weather = self.weather.lock().await;
-            weather.tick_weather(self).await;
-        };
+    /// Runs one tick of this world, see `TickPhase` for the order everything runs in.
+    ///
+    /// When the game is frozen and `runs_normally` is false, only players are ticked and the
+    /// changes are sent out.
+    pub async fn tick(self: &Arc<Self>, server: &Arc<Server>, runs_normally: bool) {
+        self.block_updates.reset_tick();
+        for phase in TickPhase::ORDER {
+            if !runs_normally && !matches!(phase, TickPhase::Entities | TickPhase::NetworkFlush) {
+                continue;
+            }
+            self.tick_phase(phase, server, runs_normally).await;
+            server.tick_hooks.run(phase, self, server).await;
+        }
+    }
+
+    async fn tick_phase(
+        self: &Arc<Self>,
+        phase: TickPhase,
+        server: &Arc<Server>,
+        runs_normally: bool,
+    ) {
+        match phase {
+            TickPhase::Time => {
+                let mut level_time = self.level_time.lock().await;
+                level_time.tick_time(self.game_rule(game_rules::DO_DAYLIGHT_CYCLE));
+                if level_time.world_age % 20 == 0 {
+                    level_time.send_time(self).await;
+                }
+            }
+            TickPhase::Weather => {
+                let mut weather = self.weather.lock().await;
+                weather.tick_weather(self).await;
+            }
+            TickPhase::Entities => {
+                let world_age = self.level_time.lock().await.world_age;
+                if runs_normally && world_age % entity_storage::SYNC_INTERVAL == 0 {
+                    entity_storage::sync(self).await;
+                }
+                if runs_normally {
+                    spawning::tick(self, world_age).await;
+                }
+                self.tick_entities(server, runs_normally).await;
+                if let Some(fight) = self.dragon_fight.as_ref().filter(|_| runs_normally) {
+                    fight.lock().await.tick(self, server).await;
+                }
+            }
+            TickPhase::BlockEntities => {
+                tick_campfires(self).await;
+                self.game_event_listeners.tick(self).await;
+            }
+            // TODO: Scheduled ticks
+            TickPhase::ScheduledTicks => self.tick_random_blocks(server).await,
+            TickPhase::NetworkFlush => {
+                for player in self.players.read().await.values() {
+                    player.client.flush();
+                }
+            }
+        }
+    }
+
+    /// Gives a few random blocks of every 16x16x16 section of the chunks near players a tick,
+    /// which makes crops grow or farmland dry out
+    async fn tick_random_blocks(self: &Arc<Self>, server: &Server) {
+        let random_tick_speed = self.game_rule(game_rules::RANDOM_TICK_SPEED);
+        if random_tick_speed <= 0 {
+            return;
+        }
+        let mut ticked = Vec::new();
+        for chunk_pos in self.level.ticking_chunks() {
+            let Some(chunk) = self.level.get_loaded_chunk(&chunk_pos) else {
+                continue;
+            };
+            let chunk = chunk.read().await;
+            let mut rng = thread_rng();
+            for section_y in (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).step_by(16) {
+                for _ in 0..random_tick_speed {
+                    let relative = Vector3::new(
+                        rng.gen_range(0..16),
+                        section_y + rng.gen_range(0..16),
+                        rng.gen_range(0..16),
+                    );
+                    let Some(block) = chunk
+                        .subchunks
+                        .get_block(relative.into())
+                        .and_then(get_block_by_state_id)
+                    else {
+                        continue;
+                    };
+                    if server.block_registry.ticks_randomly(&block) {
+                        let position = BlockPos(Vector3::new(
+                            chunk_pos.x * 16 + relative.x,
+                            relative.y,
+                            chunk_pos.z * 16 + relative.z,
+                        ));
+                        ticked.push((block, position));
+                    }
+                }
+            }
+        }
 
+        for (block, position) in ticked {
+            server
+                .block_registry
+                .random_tick(&block, self, position)
+                .await;
+        }
+    }
+
+    async fn tick_entities(&self, server: &Arc<Server>, runs_normally: bool) {
         // player ticks
         for player in self.players.read().await.values() {
             player.tick(server).await;
+            self.player_index.update(
+                player.gameprofile.id,
+                player.living_entity.entity.chunk_pos.load(),
+            );
+        }
+
+        // Players keep ticking while the game is frozen
+        if !runs_normally {
+            return;
         }
 
         let entities_to_tick: Vec<_> = self.entities.read().await.values().cloned().collect();
+        let shards = entity_shard::shard_by_region(entities_to_tick, |entity| {
+            let entity = entity.get_entity();
+            (entity.chunk_pos.load(), entity.entity_id)
+        });
 
-        // entities tick
-        for entity in entities_to_tick {
-            entity.tick(server).await;
-            // this boolean thing prevents deadlocks, since we lock players we can't broadcast packets
-            let mut collied_player = None;
-            for player in self.players.read().await.values() {
-                if player
-                    .living_entity
-                    .entity
-                    .bounding_box
-                    .load()
-                    // This is vanilla, but TODO: change this when is in a Vehicle
-                    .expand(1.0, 0.5, 1.0)
-                    .intersects(&entity.get_entity().bounding_box.load())
-                {
-                    collied_player = Some(player.clone());
-                    break;
-                }
-            }
+        // entities tick, every shard is ticked by a single worker
+        let workers = advanced_config().entity.tick_workers;
+        let tick_server = server.clone();
+        entity_shard::tick_shards(shards.clone(), workers, move |entity| {
+            let server = tick_server.clone();
+            async move { entity.tick(&server).await }
+        })
+        .await;
+        // What entities did to others is applied in their order, no matter which worker was first
+        for effect in self.entity_effects.take() {
+            self.apply_entity_effect(effect).await;
+        }
+
+        // Interactions between entities can cross shards, so they run afterwards in a fixed order
+        for entity in shards.iter().flatten() {
+            let base = entity.get_entity();
+            self.entity_index
+                .update(base.entity_uuid, base.chunk_pos.load());
+
+            let bounding_box = base.bounding_box.load();
+            // This is vanilla, but TODO: change this when is in a Vehicle
+            let collied_player = self
+                .players_in_box(&bounding_box.expand(1.0, 0.5, 1.0))
+                .into_iter()
+                .next();
             if let Some(player) = collied_player {
                 entity.on_player_collision(player).await;
             }
@@ -340,8 +630,11 @@ impl World {
         player: Arc<Player>,
         server: &Server,
     ) {
-        let dimensions: Vec<Identifier> =
-            server.dimensions.iter().map(DimensionType::name).collect();
+        let dimensions: Vec<Identifier> = server
+            .worlds
+            .iter()
+            .map(|world| world.dimension_type.name())
+            .collect();
 
         // This code follows the vanilla packet order
         let entity_id = player.entity_id();
@@ -362,17 +655,17 @@ impl World {
                 base_config.max_players.into(),
                 base_config.view_distance.get().into(), //  TODO: view distance
                 base_config.simulation_distance.get().into(), // TODO: sim view dinstance
-                false,
-                true,
-                false,
+                self.game_rule(game_rules::REDUCED_DEBUG_INFO),
+                !self.game_rule(game_rules::DO_IMMEDIATE_RESPAWN),
+                self.game_rule(game_rules::DO_LIMITED_CRAFTING),
                 (self.dimension_type as u8).into(),
                 self.dimension_type.name(),
-                0, // seed
+                seed::client_hashed_seed(self.level.seed.0),
                 gamemode as u8,
                 base_config.default_gamemode as i8,
                 false,
                 false,
-                None,
+                player.last_death_location_for_client().await,
                 0.into(),
                 0.into(),
                 false,
@@ -381,6 +674,14 @@ impl World {
         // permissions, i. e. the commands a player may use
         player.send_permission_lvl_update().await;
         client_suggestions::send_c_commands_packet(&player, &server.command_dispatcher).await;
+        player.send_advancements(server).await;
+        player
+            .client
+            .send_packet(&CUpdateRecipes::new(
+                RecipeManager::property_sets(),
+                RecipeManager::stonecutter_recipes(),
+            ))
+            .await;
         // teleport
         let info = &self.level.level_info;
         let mut position = Vector3::new(f64::from(info.spawn_x), 120.0, f64::from(info.spawn_z));
@@ -424,7 +725,7 @@ impl World {
             let current_players = self.players.read().await;
             for (_, playerr) in current_players
                 .iter()
-                .filter(|(c, _)| **c != player.gameprofile.id)
+                .filter(|(c, p)| **c != player.gameprofile.id && player.can_see(p))
             {
                 let gameprofile = &playerr.gameprofile;
                 entries.push(pumpkin_protocol::client::play::Player {
@@ -435,13 +736,14 @@ impl World {
                             properties: &gameprofile.properties,
                         },
                         PlayerAction::UpdateListed(true),
+                        PlayerAction::UpdateDisplayName(playerr.tab_list_name()),
                     ],
                 });
             }
             log::debug!("Sending player info to {}", player.gameprofile.name);
             player
                 .client
-                .send_packet(&CPlayerInfoUpdate::new(0x01 | 0x08, &entries))
+                .send_packet(&CPlayerInfoUpdate::new(0x01 | 0x08 | 0x20, &entries))
                 .await;
         };
 
@@ -467,7 +769,13 @@ impl World {
         .await;
         // spawn players for our client
         let id = player.gameprofile.id;
-        for (_, existing_player) in self.players.read().await.iter().filter(|c| c.0 != &id) {
+        for (_, existing_player) in self
+            .players
+            .read()
+            .await
+            .iter()
+            .filter(|(c, p)| *c != &id && player.can_see(p))
+        {
             let entity = &existing_player.living_entity.entity;
             let pos = entity.pos.load();
             let gameprofile = &existing_player.gameprofile;
@@ -507,39 +815,42 @@ impl World {
         // Sends initial time
         player.send_time(self).await;
 
+        // Sends the tick rate, the client also freezes its own entities
+        player
+            .client
+            .send_packet(&server.tick_manager.state_packet())
+            .await;
+        player
+            .client
+            .send_packet(&server.tick_manager.step_packet())
+            .await;
+
         // Send initial weather state
         let weather = self.weather.lock().await;
-        if weather.raining {
-            player
-                .client
-                .send_packet(&CGameEvent::new(GameEvent::BeginRaining, 0.0))
-                .await;
-
-            // Calculate rain and thunder levels directly from public fields
-            let rain_level = weather.rain_level.clamp(0.0, 1.0);
-            let thunder_level = weather.thunder_level.clamp(0.0, 1.0);
+        player.send_weather(&weather).await;
+        drop(weather);
 
-            player
-                .client
-                .send_packet(&CGameEvent::new(GameEvent::RainLevelChange, rain_level))
-                .await;
-            player
-                .client
-                .send_packet(&CGameEvent::new(
-                    GameEvent::ThunderLevelChange,
-                    thunder_level,
-                ))
-                .await;
-        }
+        server
+            .scoreboard
+            .lock()
+            .await
+            .init_client(&player.client)
+            .await;
 
         // Spawn in initial chunks
         chunker::player_join(&player).await;
 
-        // if let Some(bossbars) = self..lock().await.get_player_bars(&player.gameprofile.id) {
-        //     for bossbar in bossbars {
-        //         player.send_bossbar(bossbar).await;
-        //     }
-        // }
+        if let Some(bossbars) = server
+            .bossbars
+            .lock()
+            .await
+            .get_player_bars(&player.gameprofile.id)
+        {
+            for bossbar in bossbars {
+                player.send_bossbar(bossbar).await;
+            }
+        }
+        server.boss_events.lock().await.init_player(&player).await;
 
         player.send_mobs(self).await;
     }
@@ -618,30 +929,34 @@ impl World {
         }
     }
 
-    pub async fn respawn_player(&self, player: &Arc<Player>, alive: bool) {
-        let last_pos = player.living_entity.last_pos.load();
-        let death_dimension = player.world().await.dimension_type.name();
-        let death_location = BlockPos(Vector3::new(
-            last_pos.x.round() as i32,
-            last_pos.y.round() as i32,
-            last_pos.z.round() as i32,
-        ));
+    pub async fn respawn_player(self: &Arc<Self>, player: &Arc<Player>, alive: bool) {
+        if !alive {
+            let position = player.living_entity.entity.pos.load();
+            *player.last_death_location.lock().await = Some(GlobalPos::new(
+                player.world().await.dimension_type.name().to_string(),
+                BlockPos::floored(position.x, position.y, position.z),
+            ));
+        }
+        let death_location = player.last_death_location_for_client().await;
 
         let data_kept = u8::from(alive);
 
-        // TODO: switch world in player entity to new world
+        let current = player.world().await;
+        if !Arc::ptr_eq(&current, self) {
+            player.move_to_world(&current, self).await;
+        }
 
         player
             .client
             .send_packet(&CRespawn::new(
                 (self.dimension_type as u8).into(),
                 self.dimension_type.name(),
-                0, // seed
+                seed::client_hashed_seed(self.level.seed.0),
                 player.gamemode.load() as u8,
                 player.gamemode.load() as i8,
                 false,
                 false,
-                Some((death_dimension, death_location)),
+                death_location,
                 0.into(),
                 0.into(),
                 data_kept,
@@ -706,6 +1021,7 @@ impl World {
         let new_spawn = chunks[0] == player.watched_section.load().center;
         let mut receiver = self.receive_chunks(chunks, new_spawn);
         let level = self.level.clone();
+        let dimension = self.dimension_type.dimension();
 
         // Only allow 128 chunk packets to be sent at a time to avoid overloading the client.
         // TODO: Bulk chunks?
@@ -718,7 +1034,7 @@ impl World {
                 if position == (0, 0).into() {
                     use pumpkin_protocol::client::play::CChunkData;
                     let binding = chunk.read().await;
-                    let packet = CChunkData(&binding);
+                    let packet = CChunkData::new(&binding, dimension.min_y(), dimension.height());
                     let mut test = bytes::BytesMut::new();
                     packet.write(&mut test);
                     let len = test.len();
@@ -786,28 +1102,41 @@ impl World {
             }
 
             #[cfg(debug_assertions)]
-            log::debug!("chunks queued after {}ms ", inst.elapsed().as_millis(),);
+            log::debug!("chunks queued after {}ms ", inst.elapsed().as_millis());
         });
     }
 
     /// Gets a Player by entity id
     pub async fn get_player_by_id(&self, id: EntityId) -> Option<Arc<Player>> {
-        for player in self.players.read().await.values() {
-            if player.entity_id() == id {
-                return Some(player.clone());
-            }
-        }
-        None
+        let uuid = ENTITY_IDS.get_uuid(id)?;
+        self.players
+            .read()
+            .await
+            .get(&uuid)
+            .filter(|player| player.entity_id() == id)
+            .cloned()
     }
 
-    /// Gets a Entity by entity id
+    /// Gets a Entity by entity id, or the entity a part with the id belongs to
     pub async fn get_entity_by_id(&self, id: EntityId) -> Option<Arc<dyn EntityBase>> {
-        for entity in self.entities.read().await.values() {
-            if entity.get_entity().entity_id == id {
-                return Some(entity.clone());
-            }
+        let uuid = ENTITY_IDS.get_uuid(id)?;
+        self.entities
+            .read()
+            .await
+            .get(&uuid)
+            .filter(|entity| {
+                let own_id = entity.get_entity().entity_id;
+                own_id == id || ENTITY_IDS.is_part_of(id, own_id)
+            })
+            .cloned()
+    }
+
+    /// Gets a Player or any other Entity by its UUID
+    pub async fn get_entity_by_uuid(&self, id: uuid::Uuid) -> Option<Arc<dyn EntityBase>> {
+        if let Some(player) = self.get_player_by_uuid(id).await {
+            return Some(player);
         }
-        None
+        self.entities.read().await.get(&id).cloned()
     }
 
     /// Gets a Player by username
@@ -869,27 +1198,125 @@ impl World {
     /// * `pos`: The middlepoint of the sphere
     /// * `radius`: The radius of the sphere. The higher the radius
     ///             the more area will be checked, in every direction.
-    pub async fn get_nearby_players(
+    pub fn get_nearby_players(
         &self,
         pos: Vector3<f64>,
         radius: f64,
     ) -> HashMap<uuid::Uuid, Arc<Player>> {
         let radius_squared = radius.powi(2);
 
-        self.players
-            .read()
-            .await
-            .iter()
-            .filter_map(|(id, player)| {
+        self.player_index
+            .in_box_chunks(&Self::range_box(pos, radius))
+            .into_iter()
+            .filter_map(|player| {
                 let player_pos = player.living_entity.entity.pos.load();
                 (player_pos.squared_distance_to_vec(pos) <= radius_squared)
-                    .then(|| (*id, player.clone()))
+                    .then(|| (player.gameprofile.id, player))
+            })
+            .collect()
+    }
+
+    /// The bounding box around a sphere
+    fn range_box(pos: Vector3<f64>, radius: f64) -> BoundingBox {
+        BoundingBox {
+            min: Vector3::new(pos.x - radius, pos.y - radius, pos.z - radius),
+            max: Vector3::new(pos.x + radius, pos.y + radius, pos.z + radius),
+        }
+    }
+
+    /// Grows a query box so that entities whose position is outside of it, but whose bounding box
+    /// reaches into it, are still found
+    fn index_query_box(aabb: &BoundingBox) -> BoundingBox {
+        aabb.expand(ENTITY_INDEX_MARGIN, 0.0, ENTITY_INDEX_MARGIN)
+    }
+
+    /// Gets the entities (without players) whose bounding box intersects the given box.
+    ///
+    /// Only the chunks around the box are searched, see `EntityIndex`
+    pub fn entities_in_box(&self, aabb: &BoundingBox) -> Vec<Arc<dyn EntityBase>> {
+        self.entity_index
+            .in_box_chunks(&Self::index_query_box(aabb))
+            .into_iter()
+            .filter(|entity| entity.get_entity().bounding_box.load().intersects(aabb))
+            .collect()
+    }
+
+    /// Gets the entities (without players) within `radius` blocks of `pos`
+    pub fn entities_in_range(&self, pos: Vector3<f64>, radius: f64) -> Vec<Arc<dyn EntityBase>> {
+        let radius_squared = radius.powi(2);
+        self.entity_index
+            .in_box_chunks(&Self::range_box(pos, radius))
+            .into_iter()
+            .filter(|entity| {
+                entity.get_entity().pos.load().squared_distance_to_vec(pos) <= radius_squared
+            })
+            .collect()
+    }
+
+    /// Gets the players whose bounding box intersects the given box
+    pub fn players_in_box(&self, aabb: &BoundingBox) -> Vec<Arc<Player>> {
+        self.player_index
+            .in_box_chunks(&Self::index_query_box(aabb))
+            .into_iter()
+            .filter(|player| {
+                player
+                    .living_entity
+                    .entity
+                    .bounding_box
+                    .load()
+                    .intersects(aabb)
             })
             .collect()
     }
 
+    /// Casts a ray through the loaded blocks of this world, see `Level::raycast_blocks`
+    pub async fn raycast_blocks(
+        &self,
+        origin: Vector3<f64>,
+        direction: Vector3<f64>,
+        max_distance: f64,
+        fluid_handling: FluidHandling,
+    ) -> Option<BlockHitResult> {
+        self.level
+            .raycast_blocks(origin, direction, max_distance, fluid_handling)
+            .await
+    }
+
+    /// Finds the closest entity (without players) hit by a ray within `max_distance`.
+    ///
+    /// Blocks are not taken into account, compare with `raycast_blocks` for that
+    pub fn raycast_entities(
+        &self,
+        origin: Vector3<f64>,
+        direction: Vector3<f64>,
+        max_distance: f64,
+    ) -> Option<EntityHitResult<Arc<dyn EntityBase>>> {
+        let length = direction.length();
+        if length <= 0.0 {
+            return None;
+        }
+        let end = origin.add(&(direction * (max_distance / length)));
+        let aabb = BoundingBox {
+            min: Vector3::new(
+                origin.x.min(end.x),
+                origin.y.min(end.y),
+                origin.z.min(end.z),
+            ),
+            max: Vector3::new(
+                origin.x.max(end.x),
+                origin.y.max(end.y),
+                origin.z.max(end.z),
+            ),
+        };
+        let candidates = self.entities_in_box(&aabb).into_iter().map(|entity| {
+            let aabb = entity.get_entity().bounding_box.load();
+            (entity, aabb)
+        });
+        raycast::raycast_entities(origin, direction, max_distance, candidates)
+    }
+
     pub async fn get_closest_player(&self, pos: Vector3<f64>, radius: f64) -> Option<Arc<Player>> {
-        let players = self.get_nearby_players(pos, radius).await;
+        let players = self.get_nearby_players(pos, radius);
         players
             .iter()
             .min_by(|a, b| {
@@ -923,6 +1350,11 @@ impl World {
     pub async fn add_player(&self, uuid: uuid::Uuid, player: Arc<Player>) {
         {
             let mut current_players = self.players.write().await;
+            self.player_index.insert(
+                uuid,
+                player.living_entity.entity.chunk_pos.load(),
+                player.clone(),
+            );
             current_players.insert(uuid, player.clone())
         };
 
@@ -941,7 +1373,8 @@ impl World {
                 .fire::<PlayerJoinEvent>(event)
                 .await;
 
-            if !event.cancelled {
+            // Vanished players join silently, plugins can vanish them in the join event
+            if !event.cancelled && !player.is_vanished() {
                 let current_players = current_players.clone();
                 let players = current_players.read().await;
                 for player in players.values() {
@@ -952,6 +1385,21 @@ impl World {
         });
     }
 
+    /// The players which count when checking how many players have to sleep to skip the night.
+    /// Spectators never count, AFK players only if configured
+    pub async fn players_counted_for_sleeping(&self) -> Vec<Arc<Player>> {
+        let ignore_afk = advanced_config().afk.ignore_for_sleeping;
+        self.players
+            .read()
+            .await
+            .values()
+            .filter(|player| {
+                player.gamemode.load() != GameMode::Spectator && !(ignore_afk && player.is_afk())
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Removes a player from the world and broadcasts a disconnect message if enabled.
     ///
     /// This function removes a player from the world based on their `Player` reference.
@@ -977,6 +1425,7 @@ impl World {
             .await
             .remove(&player.gameprofile.id)
             .unwrap();
+        self.player_index.remove(&player.gameprofile.id);
         let uuid = player.gameprofile.id;
         self.broadcast_packet_except(
             &[player.gameprofile.id],
@@ -1000,7 +1449,7 @@ impl World {
                 .fire::<PlayerLeaveEvent>(event)
                 .await;
 
-            if !event.cancelled {
+            if !event.cancelled && !player.is_vanished() {
                 let players = self.players.read().await;
                 for player in players.values() {
                     player.send_system_message(&event.leave_message).await;
@@ -1021,19 +1470,79 @@ impl World {
 
     /// Adds a entity to the world.
     pub async fn spawn_entity(&self, entity: Arc<dyn EntityBase>) {
+        if let Some(index) = entity_effects::ticking_entity() {
+            self.entity_effects.push(index, EntityEffect::Spawn(entity));
+            return;
+        }
         let base_entity = entity.get_entity();
         self.broadcast_packet_all(&base_entity.create_spawn_packet())
             .await;
         let mut current_living_entities = self.entities.write().await;
+        self.entity_index.insert(
+            base_entity.entity_uuid,
+            base_entity.chunk_pos.load(),
+            entity.clone(),
+        );
         current_living_entities.insert(base_entity.entity_uuid, entity);
     }
 
     pub async fn remove_entity(&self, entity: &Entity) {
-        self.entities.write().await.remove(&entity.entity_uuid);
-        self.broadcast_packet_all(&CRemoveEntities::new(&[entity.entity_id.into()]))
+        let (uuid, entity_id) = (entity.entity_uuid, entity.entity_id);
+        if let Some(index) = entity_effects::ticking_entity() {
+            self.entity_effects
+                .push(index, EntityEffect::Remove { uuid, entity_id });
+            return;
+        }
+        self.remove_entity_now(uuid, entity_id).await;
+    }
+
+    async fn remove_entity_now(&self, uuid: uuid::Uuid, entity_id: EntityId) {
+        self.entities.write().await.remove(&uuid);
+        self.entity_index.remove(&uuid);
+        self.broadcast_packet_all(&CRemoveEntities::new(&[entity_id.into()]))
             .await;
     }
 
+    /// Damages the entity, or queues the damage when an entity does it while entities tick
+    pub async fn damage_entity(
+        &self,
+        target: Arc<dyn EntityBase>,
+        amount: f32,
+        damage_type: DamageType,
+    ) {
+        if let Some(index) = entity_effects::ticking_entity() {
+            self.entity_effects.push(
+                index,
+                EntityEffect::Damage {
+                    target,
+                    amount,
+                    damage_type,
+                },
+            );
+            return;
+        }
+        target.damage(amount, damage_type).await;
+    }
+
+    async fn apply_entity_effect(&self, effect: EntityEffect) {
+        match effect {
+            EntityEffect::Damage {
+                target,
+                amount,
+                damage_type,
+            } => {
+                target.damage(amount, damage_type).await;
+            }
+            EntityEffect::Spawn(entity) => self.spawn_entity(entity).await,
+            EntityEffect::Remove { uuid, entity_id } => {
+                self.remove_entity_now(uuid, entity_id).await;
+            }
+            EntityEffect::SetBlock { position, state_id } => {
+                self.set_block_state(&position, state_id).await;
+            }
+        }
+    }
+
     pub async fn set_block_breaking(&self, from: &Entity, location: BlockPos, progress: i32) {
         self.broadcast_packet_except(
             &[from.entity_uuid],
@@ -1042,8 +1551,19 @@ impl World {
         .await;
     }
 
-    /// Sets a block
+    /// Sets a block. While entities tick, the block an entity sets is only set after all of them
+    /// ticked, the returned block is then the one there right now
     pub async fn set_block_state(&self, position: &BlockPos, block_state_id: u16) -> u16 {
+        if let Some(index) = entity_effects::ticking_entity() {
+            self.entity_effects.push(
+                index,
+                EntityEffect::SetBlock {
+                    position: *position,
+                    state_id: block_state_id,
+                },
+            );
+            return self.get_block_state_id(position).await.unwrap_or(0);
+        }
         let (chunk_coordinate, relative_coordinates) = position.chunk_and_chunk_relative_position();
 
         // Since we divide by 16 remnant can never exceed u8
@@ -1065,6 +1585,93 @@ impl World {
         replaced_block_state_id
     }
 
+    /// Gets the block entity of a block, like the items in a chest
+    pub async fn get_block_entity(&self, position: &BlockPos) -> Option<NbtCompound> {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk).await.0;
+        let chunk = chunk.read().await;
+        chunk.get_block_entity(position).cloned()
+    }
+
+    /// Sets the block entity of a block, `id` is its type like `minecraft:chest`
+    pub async fn set_block_entity(&self, id: &str, position: BlockPos, nbt: NbtCompound) {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk).await.0;
+        let mut chunk = chunk.write().await;
+        chunk.dirty = true;
+        chunk.set_block_entity(id, position, nbt);
+    }
+
+    /// Removes the block entity of a block, returning it. Setting a block leaves its block
+    /// entity alone, it has to be removed when the block is gone
+    pub async fn remove_block_entity(&self, position: &BlockPos) -> Option<NbtCompound> {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk).await.0;
+        let mut chunk = chunk.write().await;
+        let removed = chunk.remove_block_entity(position);
+        if removed.is_some() {
+            chunk.dirty = true;
+        }
+        removed
+    }
+
+    /// The brighter one of the sky light and the block light at a block. Sections without light
+    /// data count as lit by the sky, light isn't calculated for new chunks yet
+    pub async fn get_light(&self, position: &BlockPos) -> u8 {
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let relative = ChunkRelativeBlockCoordinates::from(relative);
+        let chunk = self.receive_chunk(chunk).await.0;
+        let chunk = chunk.read().await;
+        let sky = chunk.get_sky_light(relative).unwrap_or(15);
+        sky.max(chunk.get_block_light(relative).unwrap_or(0))
+    }
+
+    /// Gets the biome of the 4x4x4 cell the block is in
+    pub async fn get_biome(&self, position: &BlockPos) -> Biome {
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let relative = ChunkRelativeBlockCoordinates::from(relative);
+        let chunk = self.receive_chunk(chunk).await.0;
+        let chunk = chunk.read().await;
+        chunk.biomes.get_biome(relative)
+    }
+
+    /// Sets the biome of every 4x4x4 cell between the corners, which have to be aligned to the
+    /// cells. Only cells of the `filter` biome are set if given, returns how many were set
+    pub async fn fill_biome(
+        &self,
+        from: BlockPos,
+        to: BlockPos,
+        biome: Biome,
+        filter: Option<Biome>,
+    ) -> usize {
+        let mut changed = 0;
+        let mut changed_chunks = Vec::new();
+        for chunk_x in (from.0.x >> 4)..=(to.0.x >> 4) {
+            for chunk_z in (from.0.z >> 4)..=(to.0.z >> 4) {
+                let chunk_pos = Vector2::new(chunk_x, chunk_z);
+                let chunk = self.receive_chunk(chunk_pos).await.0;
+                let mut chunk = chunk.write().await;
+                let (set, chunk_changed) = fill_chunk_biomes(&mut chunk, from, to, biome, filter);
+                changed += set;
+                if chunk_changed {
+                    chunk.dirty = true;
+                    changed_chunks.push((chunk_pos, chunk.biomes.clone()));
+                }
+            }
+        }
+
+        if !changed_chunks.is_empty() {
+            let dimension = self.dimension_type.dimension();
+            self.broadcast_packet_all(&CChunkBiomes::new(
+                &changed_chunks,
+                dimension.min_y(),
+                dimension.height(),
+            ))
+            .await;
+        }
+        changed
+    }
+
     // Stream the chunks (don't collect them and then do stuff with them)
     /// Spawns a tokio task to stream chunks.
     /// Important: must be called from an async function (or changed to accept a tokio runtime
@@ -1103,16 +1710,32 @@ impl World {
             .expect("Channel closed for unknown reason")
     }
 
-    /// If server is sent, it will do a block update
+    /// If server is sent, it will do a block update.
+    ///
+    /// Returns whether the block was broken, which it is not when a plugin cancelled the
+    /// `BlockBreakEvent`
     pub async fn break_block(
         self: &Arc<Self>,
         position: &BlockPos,
         cause: Option<Arc<Player>>,
         drop: bool,
         server: Option<&Server>,
-    ) {
+    ) -> bool {
         let block = self.get_block(position).await.unwrap();
-        let event = BlockBreakEvent::new(cause.clone(), block.clone(), 0, false);
+        let state_id = self.get_block_state_id(position).await.unwrap_or_default();
+        let item = match &cause {
+            Some(player) => player.inventory().lock().await.held_item().cloned(),
+            None => None,
+        };
+        let event = BlockBreakEvent::new(
+            cause.clone(),
+            block.clone(),
+            state_id,
+            *position,
+            item,
+            0,
+            drop,
+        );
 
         let event = PLUGIN_MANAGER
             .lock()
@@ -1120,32 +1743,57 @@ impl World {
             .fire::<BlockBreakEvent>(event)
             .await;
 
-        if !event.cancelled {
-            let broken_block_state_id = self.set_block_state(position, 0).await;
+        if event.cancelled {
+            if let Some(player) = cause {
+                player.resync_block(position).await;
+            }
+            return false;
+        }
 
-            let particles_packet = CWorldEvent::new(
-                WorldEvent::BlockBroken as i32,
-                position,
-                broken_block_state_id.into(),
-                false,
-            );
+        let broken_block_state_id = self.set_block_state(position, 0).await;
+        let context = cause
+            .as_ref()
+            .map_or_else(GameEventContext::default, |player| {
+                GameEventContext::entity(&player.living_entity.entity)
+            });
+        self.emit_game_event(
+            pumpkin_data::game_event::GameEvent::BlockDestroy,
+            game_event::block_center(position),
+            context.with_state(broken_block_state_id),
+        )
+        .await;
 
-            if drop {
-                block::drop_loot(self, &block, position, true, broken_block_state_id).await;
-            }
+        let particles_packet = CWorldEvent::new(
+            WorldEvent::BlockBroken as i32,
+            position,
+            broken_block_state_id.into(),
+            false,
+        );
 
-            match cause {
-                Some(player) => {
-                    self.broadcast_packet_except(&[player.gameprofile.id], &particles_packet)
-                        .await;
-                }
-                None => self.broadcast_packet_all(&particles_packet).await,
-            }
+        // Shulker boxes drop themselves together with their items once broken
+        let keeps_items = block
+            .is_tagged_with("minecraft:shulker_boxes")
+            .unwrap_or_default();
+        if event.drop && !keeps_items {
+            let params = LootContextParameters {
+                tool: event.item.as_ref(),
+                ..Default::default()
+            };
+            block::drop_loot(self, &block, position, true, broken_block_state_id, params).await;
+        }
 
-            if let Some(server) = server {
-                self.update_neighbors(server, position, None).await;
+        match cause {
+            Some(player) => {
+                self.broadcast_packet_except(&[player.gameprofile.id], &particles_packet)
+                    .await;
             }
+            None => self.broadcast_packet_all(&particles_packet).await,
+        }
+
+        if let Some(server) = server {
+            self.update_neighbors(server, position, None).await;
         }
+        true
     }
 
     pub async fn get_block_state_id(&self, position: &BlockPos) -> Result<u16, GetBlockError> {
@@ -1188,35 +1836,96 @@ impl World {
         get_block_and_state_by_state_id(id).ok_or(GetBlockError::InvalidBlockId)
     }
 
-    /// Updates neighboring blocks of a block
+    /// Updates neighboring blocks of a block.
+    ///
+    /// The updates are queued, when this is called while updates are already running, like from
+    /// `on_neighbor_update`, they run after the current one instead of right away. See
+    /// `block_updates` for the limits of a cascade
     pub async fn update_neighbors(
         &self,
         server: &Server,
         block_pos: &BlockPos,
         except: Option<&BlockDirection>,
     ) {
-        for direction in BlockDirection::update_order() {
-            if Some(&direction) == except {
-                continue;
+        self.block_updates.queue_neighbors(block_pos, except);
+        while self.block_updates.start() {
+            while let Some(update) = self.block_updates.next() {
+                self.run_neighbor_update(server, &update).await;
+            }
+            let queued_meanwhile = self.block_updates.stop();
+
+            let dropped = self.block_updates.take_dropped();
+            if !dropped.is_empty() {
+                let first = dropped.first.map_or(Vector3::new(0, 0, 0), |pos| pos.0);
+                log::warn!(
+                    "Dropped neighbor updates starting at {} {} {}: {} went deeper than {}, {} were over the budget of {} per tick",
+                    first.x,
+                    first.y,
+                    first.z,
+                    dropped.too_deep,
+                    advanced_config().block_updates.max_depth,
+                    dropped.over_budget,
+                    advanced_config().block_updates.max_per_tick,
+                );
             }
-            let neighbor_pos = block_pos.offset(direction.to_offset());
-            let neighbor_block = self.get_block(&neighbor_pos).await;
-            if let Ok(neighbor_block) = neighbor_block {
-                if let Some(neighbor_pumpkin_block) =
-                    server.block_registry.get_pumpkin_block(&neighbor_block)
-                {
-                    neighbor_pumpkin_block
-                        .on_neighbor_update(
-                            server,
-                            self,
-                            &neighbor_block,
-                            &neighbor_pos,
-                            &direction,
-                            block_pos,
-                        )
-                        .await;
+            if !queued_meanwhile {
+                break;
+            }
+        }
+    }
+
+    async fn run_neighbor_update(&self, server: &Server, update: &NeighborUpdate) {
+        let Ok(neighbor_block) = self.get_block(&update.position).await else {
+            return;
+        };
+        if let Some(neighbor_pumpkin_block) =
+            server.block_registry.get_pumpkin_block(&neighbor_block)
+        {
+            neighbor_pumpkin_block
+                .on_neighbor_update(
+                    server,
+                    self,
+                    &neighbor_block,
+                    &update.position,
+                    &update.direction,
+                    &update.source,
+                )
+                .await;
+        }
+    }
+}
+
+/// Sets the biomes of the cells of the chunk which are between the corners, returns how many
+/// cells were set and whether any of them changed
+fn fill_chunk_biomes(
+    chunk: &mut ChunkData,
+    from: BlockPos,
+    to: BlockPos,
+    biome: Biome,
+    filter: Option<Biome>,
+) -> (usize, bool) {
+    let chunk_x = chunk.position.x * 16;
+    let chunk_z = chunk.position.z * 16;
+    let min_y = from.0.y.max(i32::from(WORLD_LOWEST_Y));
+    let max_y = to.0.y.min(i32::from(WORLD_MAX_Y) - 1);
+    let mut set = 0;
+    let mut changed = false;
+    for x in (chunk_x.max(from.0.x)..=(chunk_x + 15).min(to.0.x)).step_by(4) {
+        for z in (chunk_z.max(from.0.z)..=(chunk_z + 15).min(to.0.z)).step_by(4) {
+            for y in (min_y..=max_y).step_by(4) {
+                let relative = ChunkRelativeBlockCoordinates::from(Vector3::new(x & 15, y, z & 15));
+                let old = chunk.biomes.get_biome(relative);
+                if filter.is_some_and(|filter| filter != old) {
+                    continue;
+                }
+                // Like vanilla cells which already are the biome count as well
+                set += 1;
+                if old != biome {
+                    chunk.biomes.set_biome(relative, biome);
+                    changed = true;
                 }
             }
         }
     }
+    (set, changed)
 }
@@ -0,0 +1,430 @@
+//! Nether portals: obsidian frames lit with flint and steel fill up with portal blocks, and
+//! players standing in one long enough travel between the overworld and the Nether.
+//!
+//! A block in the Nether stands for eight in the overworld horizontally, so players arrive at
+//! the closest portal around the scaled position on the other side, or at a new one built there
+//! when there is none. Portals are found by their blocks, as there are no points of interest yet.
+
+use std::sync::{Arc, atomic::Ordering};
+
+use pumpkin_data::block::{Block, BlockProperties, HorizontalAxis, NetherPortalLikeProperties};
+use pumpkin_util::{
+    GameMode,
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+};
+use pumpkin_world::{chunk::ChunkData, dimension::Dimension, world_info::game_rules};
+
+use super::World;
+use crate::entity::player::Player;
+use crate::server::Server;
+
+/// The inside of a frame has to be at least this wide and high, and at most `MAX_SIZE`
+const MIN_WIDTH: i32 = 2;
+const MIN_HEIGHT: i32 = 3;
+const MAX_SIZE: i32 = 21;
+
+/// Ticks after traveling in which standing in a portal doesn't take the player back, it restarts
+/// while they are still standing in the portal they arrived in
+pub const COOLDOWN: u32 = 10;
+
+const UP: Vector3<i32> = Vector3::new(0, 1, 0);
+const DOWN: Vector3<i32> = Vector3::new(0, -1, 0);
+
+/// Where a new portal goes in the Nether when there is no room for it, vanilla builds it on a
+/// platform there
+const FALLBACK_NETHER_Y: i32 = 70;
+
+fn direction(axis: HorizontalAxis) -> Vector3<i32> {
+    match axis {
+        HorizontalAxis::X => Vector3::new(1, 0, 0),
+        HorizontalAxis::Z => Vector3::new(0, 0, 1),
+    }
+}
+
+fn portal_state(axis: HorizontalAxis) -> u16 {
+    NetherPortalLikeProperties { axis }.to_state_id(&Block::NETHER_PORTAL)
+}
+
+async fn is_block(world: &World, position: BlockPos, block: &Block) -> bool {
+    world
+        .get_block(&position)
+        .await
+        .is_ok_and(|found| found.id == block.id)
+}
+
+/// Whether a portal can fill the block, when lighting a frame
+async fn is_empty(world: &World, position: BlockPos) -> bool {
+    world.get_block(&position).await.is_ok_and(|block| {
+        [Block::AIR.id, Block::FIRE.id, Block::NETHER_PORTAL.id].contains(&block.id)
+    })
+}
+
+async fn on_obsidian(world: &World, position: BlockPos) -> bool {
+    is_block(world, position.offset(DOWN), &Block::OBSIDIAN).await
+}
+
+/// The inside of an obsidian frame
+pub struct PortalFrame {
+    /// The lowest inside block on the negative side
+    pub bottom_left: BlockPos,
+    pub axis: HorizontalAxis,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl PortalFrame {
+    /// The frame along the axis whose inside has `position`, if there is a complete one
+    pub async fn find(world: &World, position: BlockPos, axis: HorizontalAxis) -> Option<Self> {
+        let right = direction(axis);
+        if !is_empty(world, position).await {
+            return None;
+        }
+
+        let mut bottom = position;
+        for _ in 0..MAX_SIZE {
+            if !is_empty(world, bottom.offset(DOWN)).await {
+                break;
+            }
+            bottom = bottom.offset(DOWN);
+        }
+        if !on_obsidian(world, bottom).await {
+            return None;
+        }
+
+        let mut bottom_left = bottom;
+        for _ in 0..MAX_SIZE {
+            let next = bottom_left.offset(right * -1);
+            if !is_empty(world, next).await || !on_obsidian(world, next).await {
+                break;
+            }
+            bottom_left = next;
+        }
+        if !is_block(world, bottom_left.offset(right * -1), &Block::OBSIDIAN).await {
+            return None;
+        }
+
+        let mut width = 1;
+        loop {
+            let next = bottom_left.offset(right * width);
+            if is_block(world, next, &Block::OBSIDIAN).await {
+                break;
+            }
+            if width == MAX_SIZE || !is_empty(world, next).await || !on_obsidian(world, next).await
+            {
+                return None;
+            }
+            width += 1;
+        }
+        if width < MIN_WIDTH {
+            return None;
+        }
+
+        let mut height = 0;
+        loop {
+            let row = bottom_left.offset(UP * height);
+            let mut top = true;
+            let mut inside = true;
+            for i in 0..width {
+                let position = row.offset(right * i);
+                top &= is_block(world, position, &Block::OBSIDIAN).await;
+                inside &= is_empty(world, position).await;
+            }
+            if top {
+                break;
+            }
+            let sides = is_block(world, row.offset(right * -1), &Block::OBSIDIAN).await
+                && is_block(world, row.offset(right * width), &Block::OBSIDIAN).await;
+            if height == MAX_SIZE || !inside || !sides {
+                return None;
+            }
+            height += 1;
+        }
+        if height < MIN_HEIGHT {
+            return None;
+        }
+
+        Some(Self {
+            bottom_left,
+            axis,
+            width,
+            height,
+        })
+    }
+
+    pub fn inside(&self) -> impl Iterator<Item = BlockPos> + '_ {
+        let right = direction(self.axis);
+        (0..self.width).flat_map(move |i| {
+            (0..self.height).map(move |h| self.bottom_left.offset(right * i + UP * h))
+        })
+    }
+
+    pub async fn fill(&self, world: &World) {
+        let state = portal_state(self.axis);
+        for position in self.inside() {
+            world.set_block_state(&position, state).await;
+        }
+    }
+}
+
+/// Fills the frame around `position` with portal blocks, returns false if there is none
+pub async fn light(world: &World, position: BlockPos) -> bool {
+    for axis in [HorizontalAxis::X, HorizontalAxis::Z] {
+        if let Some(frame) = PortalFrame::find(world, position, axis).await {
+            frame.fill(world).await;
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether the portal block at the position is still held by its frame, a portal block breaks
+/// once a block next to it along its axis or above or below it is neither portal nor obsidian
+pub async fn is_supported(world: &World, position: BlockPos) -> bool {
+    let Ok(state) = world.get_block_state_id(&position).await else {
+        return false;
+    };
+    let axis = NetherPortalLikeProperties::from_state_id(state, &Block::NETHER_PORTAL).axis;
+    let right = direction(axis);
+    for offset in [right, right * -1, UP, DOWN] {
+        let neighbor = position.offset(offset);
+        if !is_block(world, neighbor, &Block::NETHER_PORTAL).await
+            && !is_block(world, neighbor, &Block::OBSIDIAN).await
+        {
+            return false;
+        }
+    }
+    true
+}
+
+/// How many chunks around the scaled position are searched for a portal, vanilla searches 128
+/// blocks in the overworld and 16 in the Nether
+const fn search_radius(dimension: Dimension) -> i32 {
+    match dimension {
+        Dimension::Nether => 1,
+        Dimension::OverWorld | Dimension::End => 8,
+    }
+}
+
+/// Updates `closest` with the portal blocks of the chunk which are closer to `around`
+fn closest_portal_in(chunk: &ChunkData, around: BlockPos, closest: &mut Option<BlockPos>) {
+    for axis in [HorizontalAxis::X, HorizontalAxis::Z] {
+        for position in chunk.subchunks.find_block(portal_state(axis)) {
+            let position = position.with_chunk_coordinates(chunk.position);
+            let position = BlockPos(Vector3::new(position.x, (*position.y).into(), position.z));
+            let distance = position.0.squared_distance_to_vec(around.0);
+            if closest.is_none_or(|closest| distance < closest.0.squared_distance_to_vec(around.0))
+            {
+                *closest = Some(position);
+            }
+        }
+    }
+}
+
+/// The squared horizontal distance from `around` to the closest block of the chunk, no portal in
+/// the chunk can be closer than this
+fn squared_chunk_distance(chunk: Vector2<i32>, around: BlockPos) -> i32 {
+    let axis_distance = |chunk: i32, block: i32| {
+        let min = chunk << 4;
+        (min - block).max(block - (min + 15)).max(0)
+    };
+    let x = axis_distance(chunk.x, around.0.x);
+    let z = axis_distance(chunk.z, around.0.z);
+    x * x + z * z
+}
+
+/// The bottom of the portal closest to `around`, in the chunks within the search radius.
+///
+/// The chunks which are loaded already are searched first. Only the other chunks which could
+/// still hold a closer portal are loaded afterwards, so mostly nothing is loaded when the player
+/// travels back and forth between two portals
+async fn find_portal(world: &World, around: BlockPos) -> Option<BlockPos> {
+    let radius = search_radius(world.level.dimension);
+    let center = around.chunk_and_chunk_relative_position().0;
+
+    let mut closest: Option<BlockPos> = None;
+    let mut unloaded = Vec::new();
+    for x in -radius..=radius {
+        for z in -radius..=radius {
+            let position = Vector2::new(center.x + x, center.z + z);
+            match world.level.get_loaded_chunk(&position) {
+                Some(chunk) => closest_portal_in(&*chunk.read().await, around, &mut closest),
+                None => unloaded.push(position),
+            }
+        }
+    }
+
+    if let Some(found) = closest {
+        let distance = found.0.squared_distance_to_vec(around.0);
+        unloaded.retain(|chunk| squared_chunk_distance(*chunk, around) < distance);
+    }
+    if !unloaded.is_empty() {
+        let mut receiver = world.receive_chunks(unloaded, false);
+        while let Some((chunk, _)) = receiver.recv().await {
+            closest_portal_in(&*chunk.read().await, around, &mut closest);
+        }
+    }
+
+    let mut bottom = closest?;
+    while is_block(world, bottom.offset(DOWN), &Block::NETHER_PORTAL).await {
+        bottom = bottom.offset(DOWN);
+    }
+    Some(bottom)
+}
+
+/// The height a new portal at the column goes, standing on the ground
+async fn portal_height(world: &World, column: Vector2<i32>) -> i32 {
+    if world.level.dimension != Dimension::Nether {
+        return world.get_top_block(column).await + 1;
+    }
+    // Below the bedrock roof, the first floor with room for the frame above it
+    for y in (1..=120).rev() {
+        let floor = BlockPos(Vector3::new(column.x, y - 1, column.z));
+        if world
+            .get_block_state(&floor)
+            .await
+            .is_ok_and(|state| state.air || state.is_liquid)
+        {
+            continue;
+        }
+        let mut room = true;
+        for h in 0..5 {
+            room &= world
+                .get_block_state(&floor.offset(UP * (h + 1)))
+                .await
+                .is_ok_and(|state| state.air);
+        }
+        if room {
+            return y;
+        }
+    }
+    FALLBACK_NETHER_Y
+}
+
+/// Builds the smallest portal, with an obsidian ledge on both sides to step out onto
+async fn build_portal(world: &World, bottom_left: BlockPos, axis: HorizontalAxis) {
+    let right = direction(axis);
+    let forward = direction(match axis {
+        HorizontalAxis::X => HorizontalAxis::Z,
+        HorizontalAxis::Z => HorizontalAxis::X,
+    });
+    let frame = PortalFrame {
+        bottom_left,
+        axis,
+        width: MIN_WIDTH,
+        height: MIN_HEIGHT,
+    };
+    for i in -1..=MIN_WIDTH {
+        for h in -1..=MIN_HEIGHT {
+            let position = bottom_left.offset(right * i + UP * h);
+            world
+                .set_block_state(&position, Block::OBSIDIAN.default_state_id)
+                .await;
+            for side in [forward, forward * -1] {
+                if (0..MIN_WIDTH).contains(&i) && h < MIN_HEIGHT {
+                    let state = if h == -1 {
+                        Block::OBSIDIAN.default_state_id
+                    } else {
+                        Block::AIR.default_state_id
+                    };
+                    world.set_block_state(&position.offset(side), state).await;
+                }
+            }
+        }
+    }
+    frame.fill(world).await;
+}
+
+/// Moves a player who stood in a nether portal long enough to the other side
+pub async fn travel(player: &Arc<Player>, server: &Server) {
+    let world = player.world().await;
+    let from = world.level.dimension;
+    let to = match from {
+        Dimension::OverWorld => Dimension::Nether,
+        Dimension::Nether => Dimension::OverWorld,
+        Dimension::End => return,
+    };
+    let Some(target) = server.worlds.get(to).cloned() else {
+        log::debug!(
+            "{} entered a nether portal, but the {to:?} isn't loaded",
+            player.gameprofile.name
+        );
+        return;
+    };
+
+    let position = player.living_entity.entity.pos.load();
+    let scaled = BlockPos::floored(
+        from.scale_to(position.x, to),
+        position.y,
+        from.scale_to(position.z, to),
+    );
+    let bottom = match find_portal(&target, scaled).await {
+        Some(bottom) => bottom,
+        None => new_portal(player, &world, &target, scaled).await,
+    };
+
+    // In the middle of the portal block, not on its corner
+    let arrival = Vector3::new(
+        f64::from(bottom.0.x) + 0.5,
+        f64::from(bottom.0.y),
+        f64::from(bottom.0.z) + 0.5,
+    );
+    let yaw = player.living_entity.entity.yaw.load();
+    let pitch = player.living_entity.entity.pitch.load();
+    player
+        .teleport_world(target, Some(arrival), Some(yaw), Some(pitch))
+        .await;
+}
+
+/// Builds the portal a player arrives at when there is none close to where they go yet
+async fn new_portal(player: &Player, from: &World, target: &World, scaled: BlockPos) -> BlockPos {
+    // The new portal faces the same way as the one the player came through
+    let axis = match from
+        .get_block_state_id(&player.living_entity.entity.block_pos.load())
+        .await
+    {
+        Ok(state) if state == portal_state(HorizontalAxis::Z) => HorizontalAxis::Z,
+        _ => HorizontalAxis::X,
+    };
+    let column = Vector2::new(scaled.0.x, scaled.0.z);
+    let bottom = BlockPos(Vector3::new(
+        column.x,
+        portal_height(target, column).await,
+        column.z,
+    ));
+    build_portal(target, bottom, axis).await;
+    bottom
+}
+
+/// Counts how long a player has been standing in a nether portal and takes them through once
+/// it's long enough, which is right away in creative
+pub async fn tick_player(player: &Arc<Player>, server: &Server, in_portal: bool) {
+    if !in_portal {
+        player.portal_ticks.store(0, Ordering::Relaxed);
+        let _ =
+            player
+                .portal_cooldown
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |ticks| {
+                    ticks.checked_sub(1)
+                });
+        return;
+    }
+    if player.portal_cooldown.load(Ordering::Relaxed) > 0 {
+        player.portal_cooldown.store(COOLDOWN, Ordering::Relaxed);
+        return;
+    }
+
+    let delay = {
+        let world = player.world().await;
+        if player.gamemode.load() == GameMode::Creative {
+            world.game_rule(game_rules::PLAYERS_NETHER_PORTAL_CREATIVE_DELAY)
+        } else {
+            world.game_rule(game_rules::PLAYERS_NETHER_PORTAL_DEFAULT_DELAY)
+        }
+    };
+    let ticks = player.portal_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+    if i64::from(ticks) >= i64::from(delay.max(1)) {
+        player.portal_ticks.store(0, Ordering::Relaxed);
+        player.portal_cooldown.store(COOLDOWN, Ordering::Relaxed);
+        travel(player, server).await;
+    }
+}
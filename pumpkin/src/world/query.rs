@@ -0,0 +1,169 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pumpkin_data::{block::Block, chunk::Biome};
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{
+    WORLD_LOWEST_Y, WORLD_MAX_Y, block::registry::get_block_by_state_id, chunk::ChunkData,
+    coordinates::ChunkRelativeBlockCoordinates, level::Level,
+};
+
+/// Read-only access to the blocks and biomes of a world, meant for plugins reading a lot of
+/// blocks from their own tasks.
+///
+/// A chunk is only locked for the moment a snapshot of it is taken, the first time the query
+/// reads from it, so scanning large areas doesn't hold up the world tick. The query keeps seeing
+/// the chunks as they were at that moment, a new query sees later changes.
+///
+/// Queries never load or generate chunks, reading from a chunk which is not loaded gives `None`.
+pub struct WorldQuery {
+    level: Arc<Level>,
+    chunks: HashMap<Vector2<i32>, Option<Arc<ChunkData>>>,
+}
+
+impl WorldQuery {
+    #[must_use]
+    pub fn new(level: Arc<Level>) -> Self {
+        Self {
+            level,
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// The snapshot of the chunk, taken the first time it is needed
+    async fn chunk(&mut self, position: Vector2<i32>) -> Option<Arc<ChunkData>> {
+        if let Some(chunk) = self.chunks.get(&position) {
+            return chunk.clone();
+        }
+        let snapshot = match self.level.get_loaded_chunk(&position) {
+            Some(chunk) => Some(Arc::new(chunk.read().await.snapshot())),
+            None => None,
+        };
+        self.chunks.insert(position, snapshot.clone());
+        snapshot
+    }
+
+    pub async fn get_block_state_id(&mut self, position: &BlockPos) -> Option<u16> {
+        if !in_height(position.0.y) {
+            return None;
+        }
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        self.chunk(chunk)
+            .await?
+            .get_block(ChunkRelativeBlockCoordinates::from(relative))
+    }
+
+    pub async fn get_block(&mut self, position: &BlockPos) -> Option<Block> {
+        let id = self.get_block_state_id(position).await?;
+        get_block_by_state_id(id)
+    }
+
+    pub async fn get_biome(&mut self, position: &BlockPos) -> Option<Biome> {
+        if !in_height(position.0.y) {
+            return None;
+        }
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = self.chunk(chunk).await?;
+        Some(
+            chunk
+                .biomes
+                .get_biome(ChunkRelativeBlockCoordinates::from(relative)),
+        )
+    }
+
+    /// Calls `visit` with the position and state id of every block in the box between the corners,
+    /// chunks which are not loaded are skipped
+    pub async fn scan<F: FnMut(BlockPos, u16) + Send>(
+        &mut self,
+        corner: BlockPos,
+        other_corner: BlockPos,
+        mut visit: F,
+    ) {
+        let from = BlockPos(Vector3::new(
+            corner.0.x.min(other_corner.0.x),
+            corner.0.y.min(other_corner.0.y),
+            corner.0.z.min(other_corner.0.z),
+        ));
+        let to = BlockPos(Vector3::new(
+            corner.0.x.max(other_corner.0.x),
+            corner.0.y.max(other_corner.0.y),
+            corner.0.z.max(other_corner.0.z),
+        ));
+        for chunk_x in (from.0.x >> 4)..=(to.0.x >> 4) {
+            for chunk_z in (from.0.z >> 4)..=(to.0.z >> 4) {
+                if let Some(chunk) = self.chunk(Vector2::new(chunk_x, chunk_z)).await {
+                    scan_chunk(&chunk, from, to, &mut visit);
+                }
+            }
+        }
+    }
+}
+
+fn in_height(y: i32) -> bool {
+    (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&y)
+}
+
+/// Visits the blocks of the chunk which are between the corners
+fn scan_chunk(
+    chunk: &ChunkData,
+    from: BlockPos,
+    to: BlockPos,
+    visit: &mut impl FnMut(BlockPos, u16),
+) {
+    let chunk_x = chunk.position.x * 16;
+    let chunk_z = chunk.position.z * 16;
+    let min_y = from.0.y.max(i32::from(WORLD_LOWEST_Y));
+    let max_y = to.0.y.min(i32::from(WORLD_MAX_Y) - 1);
+    for x in chunk_x.max(from.0.x)..=(chunk_x + 15).min(to.0.x) {
+        for z in chunk_z.max(from.0.z)..=(chunk_z + 15).min(to.0.z) {
+            for y in min_y..=max_y {
+                let relative = ChunkRelativeBlockCoordinates::from(Vector3::new(x & 15, y, z & 15));
+                if let Some(id) = chunk.get_block(relative) {
+                    visit(BlockPos(Vector3::new(x, y, z)), id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pumpkin_data::chunk::Biome;
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+    use pumpkin_world::chunk::{ChunkBiomes, ChunkData, ChunkHeightmaps, ChunkLight, Subchunks};
+
+    use super::scan_chunk;
+
+    #[test]
+    fn scans_only_the_part_of_the_box_in_the_chunk() {
+        let mut chunk = ChunkData {
+            subchunks: Subchunks::Single(0),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: ChunkBiomes::Single(Biome::Plains),
+            light: ChunkLight::default(),
+            block_entities: HashMap::new(),
+            position: Vector2::new(-1, 0),
+            dirty: false,
+        };
+        chunk.set_block(Vector3::new(15, 70, 2).into(), 1);
+
+        let mut visited = 0;
+        let mut found = Vec::new();
+        scan_chunk(
+            &chunk,
+            BlockPos(Vector3::new(-4, -100, 0)),
+            BlockPos(Vector3::new(3, 1000, 3)),
+            &mut |pos, id| {
+                visited += 1;
+                if id != 0 {
+                    found.push(pos);
+                }
+            },
+        );
+
+        // 4 by 4 blocks of the chunk over the whole height of the world
+        assert_eq!(visited, 4 * 4 * 384);
+        assert_eq!(found, [BlockPos(Vector3::new(-1, 70, 2))]);
+    }
+}
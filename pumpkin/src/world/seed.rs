@@ -0,0 +1,44 @@
+//! The seed clients get to see. While the seed is hidden, every client-facing seed is derived
+//! from the fake seed, so the real one can't be guessed from it.
+
+use std::sync::LazyLock;
+
+use pumpkin_config::advanced_config;
+use pumpkin_util::PermissionLvl;
+use sha2::{Digest, Sha256};
+
+/// Used when no fake seed is configured, so it stays the same until the server restarts
+static RANDOM_FAKE_SEED: LazyLock<i64> = LazyLock::new(rand::random);
+
+/// The seed shown to someone with the permission level
+#[must_use]
+pub fn visible_seed(seed: u64, level: PermissionLvl) -> i64 {
+    let config = &advanced_config().seed;
+    if config.hide && level < config.reveal_level {
+        config.fake_seed.unwrap_or(*RANDOM_FAKE_SEED)
+    } else {
+        seed as i64
+    }
+}
+
+/// The hashed seed sent on login and respawn, which the client uses for biome blending
+#[must_use]
+pub fn client_hashed_seed(seed: u64) -> i64 {
+    hash_seed(visible_seed(seed, PermissionLvl::Zero))
+}
+
+/// Hashes the seed like vanilla, with the first 8 bytes of the SHA-256 of it
+fn hash_seed(seed: i64) -> i64 {
+    let hash = Sha256::digest(seed.to_le_bytes());
+    i64::from_le_bytes(hash[..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hash_seed;
+
+    #[test]
+    fn vanilla_hash() {
+        assert_eq!(hash_seed(0), 8_794_265_229_978_523_055);
+    }
+}
@@ -0,0 +1,613 @@
+//! Natural mob spawning around players. Every spawn cycle each chunk in simulation distance of a
+//! player gets one try at spawning a group of the mobs its biome allows, until the mob cap of
+//! the category is reached.
+use std::{num::NonZeroI32, sync::Arc};
+
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_data::{
+    block::{Block, BlockState},
+    chunk::Biome,
+    entity::EntityType,
+    tag::Tagable,
+};
+use pumpkin_registry::DimensionType;
+use pumpkin_util::{
+    GameMode,
+    math::{vector2::Vector2, vector3::Vector3},
+};
+use pumpkin_world::{
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+    block::registry::{get_block_and_state_by_state_id, is_full_cube},
+    chunk::ChunkData,
+    world_info::game_rules,
+};
+use rand::{Rng, seq::SliceRandom, thread_rng};
+
+use crate::entity::mob;
+
+use super::World;
+
+/// Mobs don't spawn closer to a player than this
+const MIN_PLAYER_DISTANCE: f64 = 24.0;
+/// Monsters and bats which no player is this close to anymore despawn
+const DESPAWN_DISTANCE: f64 = 128.0;
+/// The mob caps are for this many chunks, the 17x17 chunks around a single player
+const CAP_CHUNKS: usize = 289;
+/// Bats only spawn below the sea level
+const SEA_LEVEL: i32 = 63;
+
+/// The groups mobs are counted in for the mob caps, each spawns with its own rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MobCategory {
+    /// Hostile mobs, they spawn in the dark
+    Monster,
+    /// Animals, they spawn on grass in the light
+    Creature,
+    /// Bats, they spawn in dark caves
+    Ambient,
+}
+
+impl MobCategory {
+    pub const ALL: [Self; 3] = [Self::Monster, Self::Creature, Self::Ambient];
+
+    /// How many mobs of the category may be in the world for every `CAP_CHUNKS` chunks players
+    /// are near
+    #[must_use]
+    pub const fn cap(self) -> usize {
+        match self {
+            Self::Monster => 70,
+            Self::Creature => 10,
+            Self::Ambient => 15,
+        }
+    }
+
+    /// How many ticks pass between two spawn cycles, animals mostly come with their chunk and
+    /// only rarely spawn later
+    #[must_use]
+    pub const fn interval(self) -> i64 {
+        match self {
+            Self::Monster | Self::Ambient => 1,
+            Self::Creature => 400,
+        }
+    }
+
+    /// The category the entity is counted in, `None` for entities which don't spawn naturally
+    #[must_use]
+    pub fn of(entity_type: EntityType) -> Option<Self> {
+        match entity_type {
+            EntityType::ZOMBIE
+            | EntityType::HUSK
+            | EntityType::ZOMBIE_VILLAGER
+            | EntityType::SKELETON
+            | EntityType::STRAY
+            | EntityType::SPIDER
+            | EntityType::CREEPER
+            | EntityType::SLIME
+            | EntityType::ENDERMAN
+            | EntityType::WITCH
+            | EntityType::ZOMBIFIED_PIGLIN
+            | EntityType::PIGLIN
+            | EntityType::HOGLIN
+            | EntityType::GHAST
+            | EntityType::MAGMA_CUBE => Some(Self::Monster),
+            EntityType::SHEEP
+            | EntityType::PIG
+            | EntityType::CHICKEN
+            | EntityType::COW
+            | EntityType::HORSE
+            | EntityType::DONKEY
+            | EntityType::MOOSHROOM
+            | EntityType::RABBIT
+            | EntityType::POLAR_BEAR => Some(Self::Creature),
+            EntityType::BAT => Some(Self::Ambient),
+            _ => None,
+        }
+    }
+
+    /// The mobs of the category which spawn in the biome
+    #[must_use]
+    pub const fn spawn_entries(self, biome: Biome) -> &'static [SpawnEntry] {
+        match self {
+            Self::Monster => monsters(biome),
+            Self::Creature => creatures(biome),
+            Self::Ambient => ambient(biome),
+        }
+    }
+}
+
+/// A mob a biome spawns, the chance it is picked is its weight compared to the others
+#[derive(Debug, Clone, Copy)]
+pub struct SpawnEntry {
+    pub entity_type: EntityType,
+    pub weight: u32,
+    pub min_group: u32,
+    pub max_group: u32,
+}
+
+const fn entry(entity_type: EntityType, weight: u32, min_group: u32, max_group: u32) -> SpawnEntry {
+    SpawnEntry {
+        entity_type,
+        weight,
+        min_group,
+        max_group,
+    }
+}
+
+const MONSTERS: &[SpawnEntry] = &[
+    entry(EntityType::SPIDER, 100, 4, 4),
+    entry(EntityType::ZOMBIE, 95, 4, 4),
+    entry(EntityType::ZOMBIE_VILLAGER, 5, 1, 1),
+    entry(EntityType::SKELETON, 100, 4, 4),
+    entry(EntityType::CREEPER, 100, 4, 4),
+    entry(EntityType::SLIME, 100, 4, 4),
+    entry(EntityType::ENDERMAN, 10, 1, 4),
+    entry(EntityType::WITCH, 5, 1, 1),
+];
+const DESERT_MONSTERS: &[SpawnEntry] = &[
+    entry(EntityType::SPIDER, 100, 4, 4),
+    entry(EntityType::ZOMBIE, 19, 4, 4),
+    entry(EntityType::HUSK, 80, 4, 4),
+    entry(EntityType::ZOMBIE_VILLAGER, 1, 1, 1),
+    entry(EntityType::SKELETON, 100, 4, 4),
+    entry(EntityType::CREEPER, 100, 4, 4),
+    entry(EntityType::SLIME, 100, 4, 4),
+    entry(EntityType::ENDERMAN, 10, 1, 4),
+    entry(EntityType::WITCH, 5, 1, 1),
+];
+const SNOWY_MONSTERS: &[SpawnEntry] = &[
+    entry(EntityType::SPIDER, 100, 4, 4),
+    entry(EntityType::ZOMBIE, 95, 4, 4),
+    entry(EntityType::ZOMBIE_VILLAGER, 5, 1, 1),
+    entry(EntityType::SKELETON, 20, 4, 4),
+    entry(EntityType::STRAY, 80, 4, 4),
+    entry(EntityType::CREEPER, 100, 4, 4),
+    entry(EntityType::SLIME, 100, 4, 4),
+    entry(EntityType::ENDERMAN, 10, 1, 4),
+    entry(EntityType::WITCH, 5, 1, 1),
+];
+const NETHER_WASTES_MONSTERS: &[SpawnEntry] = &[
+    entry(EntityType::GHAST, 50, 4, 4),
+    entry(EntityType::ZOMBIFIED_PIGLIN, 100, 4, 4),
+    entry(EntityType::MAGMA_CUBE, 2, 4, 4),
+    entry(EntityType::ENDERMAN, 1, 4, 4),
+    entry(EntityType::PIGLIN, 15, 4, 4),
+];
+const SOUL_SAND_VALLEY_MONSTERS: &[SpawnEntry] = &[
+    entry(EntityType::SKELETON, 20, 5, 5),
+    entry(EntityType::GHAST, 50, 4, 4),
+    entry(EntityType::ENDERMAN, 1, 4, 4),
+];
+const CRIMSON_FOREST_MONSTERS: &[SpawnEntry] = &[
+    entry(EntityType::ZOMBIFIED_PIGLIN, 1, 2, 4),
+    entry(EntityType::HOGLIN, 9, 3, 4),
+    entry(EntityType::PIGLIN, 5, 3, 4),
+];
+const WARPED_FOREST_MONSTERS: &[SpawnEntry] = &[entry(EntityType::ENDERMAN, 1, 4, 4)];
+const BASALT_DELTAS_MONSTERS: &[SpawnEntry] = &[
+    entry(EntityType::GHAST, 40, 1, 1),
+    entry(EntityType::MAGMA_CUBE, 100, 2, 5),
+];
+const END_MONSTERS: &[SpawnEntry] = &[entry(EntityType::ENDERMAN, 10, 4, 4)];
+
+const FARM_ANIMALS: &[SpawnEntry] = &[
+    entry(EntityType::SHEEP, 12, 4, 4),
+    entry(EntityType::PIG, 10, 4, 4),
+    entry(EntityType::CHICKEN, 10, 4, 4),
+    entry(EntityType::COW, 8, 4, 4),
+];
+const PLAINS_CREATURES: &[SpawnEntry] = &[
+    entry(EntityType::SHEEP, 12, 4, 4),
+    entry(EntityType::PIG, 10, 4, 4),
+    entry(EntityType::CHICKEN, 10, 4, 4),
+    entry(EntityType::COW, 8, 4, 4),
+    entry(EntityType::HORSE, 5, 2, 6),
+    entry(EntityType::DONKEY, 1, 1, 3),
+];
+const SNOWY_CREATURES: &[SpawnEntry] = &[
+    entry(EntityType::RABBIT, 10, 2, 3),
+    entry(EntityType::POLAR_BEAR, 1, 1, 2),
+];
+const DESERT_CREATURES: &[SpawnEntry] = &[entry(EntityType::RABBIT, 4, 2, 3)];
+const MOOSHROOMS: &[SpawnEntry] = &[entry(EntityType::MOOSHROOM, 8, 4, 8)];
+
+const BATS: &[SpawnEntry] = &[entry(EntityType::BAT, 10, 8, 8)];
+
+const fn monsters(biome: Biome) -> &'static [SpawnEntry] {
+    match biome {
+        Biome::MushroomFields | Biome::DeepDark | Biome::TheVoid => &[],
+        Biome::Desert => DESERT_MONSTERS,
+        Biome::SnowyPlains | Biome::IceSpikes => SNOWY_MONSTERS,
+        Biome::NetherWastes => NETHER_WASTES_MONSTERS,
+        Biome::SoulSandValley => SOUL_SAND_VALLEY_MONSTERS,
+        Biome::CrimsonForest => CRIMSON_FOREST_MONSTERS,
+        Biome::WarpedForest => WARPED_FOREST_MONSTERS,
+        Biome::BasaltDeltas => BASALT_DELTAS_MONSTERS,
+        Biome::TheEnd
+        | Biome::EndBarrens
+        | Biome::EndHighlands
+        | Biome::EndMidlands
+        | Biome::SmallEndIslands => END_MONSTERS,
+        _ => MONSTERS,
+    }
+}
+
+const fn creatures(biome: Biome) -> &'static [SpawnEntry] {
+    match biome {
+        Biome::Plains | Biome::SunflowerPlains => PLAINS_CREATURES,
+        Biome::SnowyPlains | Biome::IceSpikes => SNOWY_CREATURES,
+        Biome::Desert => DESERT_CREATURES,
+        Biome::MushroomFields => MOOSHROOMS,
+        Biome::Ocean
+        | Biome::DeepOcean
+        | Biome::ColdOcean
+        | Biome::DeepColdOcean
+        | Biome::FrozenOcean
+        | Biome::DeepFrozenOcean
+        | Biome::LukewarmOcean
+        | Biome::DeepLukewarmOcean
+        | Biome::WarmOcean
+        | Biome::River
+        | Biome::FrozenRiver
+        | Biome::Beach
+        | Biome::SnowyBeach
+        | Biome::StonyShore
+        | Biome::DripstoneCaves
+        | Biome::LushCaves
+        | Biome::DeepDark
+        | Biome::FrozenPeaks
+        | Biome::JaggedPeaks
+        | Biome::StonyPeaks
+        | Biome::SnowySlopes
+        | Biome::NetherWastes
+        | Biome::SoulSandValley
+        | Biome::CrimsonForest
+        | Biome::WarpedForest
+        | Biome::BasaltDeltas
+        | Biome::TheEnd
+        | Biome::EndBarrens
+        | Biome::EndHighlands
+        | Biome::EndMidlands
+        | Biome::SmallEndIslands
+        | Biome::TheVoid => &[],
+        _ => FARM_ANIMALS,
+    }
+}
+
+const fn ambient(biome: Biome) -> &'static [SpawnEntry] {
+    match biome {
+        Biome::MushroomFields
+        | Biome::NetherWastes
+        | Biome::SoulSandValley
+        | Biome::CrimsonForest
+        | Biome::WarpedForest
+        | Biome::BasaltDeltas
+        | Biome::TheEnd
+        | Biome::EndBarrens
+        | Biome::EndHighlands
+        | Biome::EndMidlands
+        | Biome::SmallEndIslands
+        | Biome::TheVoid => &[],
+        _ => BATS,
+    }
+}
+
+/// The block tag of the ground the animal spawns on
+fn creature_ground(entity_type: EntityType) -> &'static str {
+    match entity_type {
+        EntityType::MOOSHROOM => "minecraft:mooshrooms_spawnable_on",
+        EntityType::RABBIT => "minecraft:rabbits_spawnable_on",
+        _ => "minecraft:animals_spawnable_on",
+    }
+}
+
+/// Despawns the mobs players left behind and runs the spawn cycles due this tick
+pub async fn tick(world: &Arc<World>, world_age: i64) {
+    despawn(world).await;
+    if !world.game_rule(game_rules::DO_MOB_SPAWNING) {
+        return;
+    }
+    let chunks = spawn_chunks(world).await;
+    if chunks.is_empty() {
+        return;
+    }
+    let sky_darkness = world.level_time.lock().await.sky_darkness();
+    // Worlds without a sky have no sky light to darken
+    let sky_darkness = matches!(
+        world.dimension_type,
+        DimensionType::Overworld | DimensionType::OverworldCaves
+    )
+    .then_some(sky_darkness);
+
+    let mut counts = count_mobs(world).await;
+    for category in MobCategory::ALL {
+        if world_age % category.interval() != 0 {
+            continue;
+        }
+        let cap = category.cap() * chunks.len() / CAP_CHUNKS;
+        let count = &mut counts[category as usize];
+        for chunk in &chunks {
+            if *count >= cap {
+                break;
+            }
+            *count += spawn_group(world, category, *chunk, sky_darkness).await;
+        }
+    }
+}
+
+/// The loaded chunks in simulation distance of a player who isn't spectating
+async fn spawn_chunks(world: &World) -> Vec<Vector2<i32>> {
+    let distance = NonZeroI32::from(BASIC_CONFIG.simulation_distance).get();
+    let players: Vec<_> = world
+        .players
+        .read()
+        .await
+        .values()
+        .filter(|player| player.gamemode.load() != GameMode::Spectator)
+        .map(|player| player.living_entity.entity.chunk_pos.load())
+        .collect();
+    world
+        .level
+        .ticking_chunks()
+        .into_iter()
+        .filter(|chunk| {
+            players.iter().any(|player| {
+                (player.x - chunk.x).abs() <= distance && (player.z - chunk.z).abs() <= distance
+            })
+        })
+        .collect()
+}
+
+/// How many mobs of each category there are in the world, in the order of `MobCategory::ALL`
+async fn count_mobs(world: &World) -> [usize; MobCategory::ALL.len()] {
+    let mut counts = [0; MobCategory::ALL.len()];
+    for entity in world.entities.read().await.values() {
+        if let Some(category) = MobCategory::of(entity.get_entity().entity_type) {
+            counts[category as usize] += 1;
+        }
+    }
+    counts
+}
+
+/// Removes the monsters and bats no player is near anymore, animals stay
+async fn despawn(world: &World) {
+    let entities: Vec<_> = world.entities.read().await.values().cloned().collect();
+    for entity in entities {
+        let entity = entity.get_entity();
+        if !matches!(
+            MobCategory::of(entity.entity_type),
+            Some(MobCategory::Monster | MobCategory::Ambient)
+        ) {
+            continue;
+        }
+        if world
+            .get_nearby_players(entity.pos.load(), DESPAWN_DISTANCE)
+            .is_empty()
+        {
+            world.remove_entity(entity).await;
+        }
+    }
+}
+
+/// Tries to spawn a group of mobs of the category in the chunk, returns how many spawned
+async fn spawn_group(
+    world: &Arc<World>,
+    category: MobCategory,
+    chunk_pos: Vector2<i32>,
+    sky_darkness: Option<u8>,
+) -> usize {
+    let Some(chunk) = world.level.get_loaded_chunk(&chunk_pos) else {
+        return 0;
+    };
+    let chunk = chunk.read().await;
+    let Some((entity_type, spots)) = find_group(&chunk, category, sky_darkness, &mut thread_rng())
+    else {
+        return 0;
+    };
+    drop(chunk);
+
+    let mut spawned = 0;
+    for spot in spots {
+        let position = Vector3::new(
+            f64::from(chunk_pos.x * 16 + spot.x) + 0.5,
+            f64::from(spot.y),
+            f64::from(chunk_pos.z * 16 + spot.z) + 0.5,
+        );
+        if !world
+            .get_nearby_players(position, MIN_PLAYER_DISTANCE)
+            .is_empty()
+        {
+            continue;
+        }
+        world
+            .spawn_entity(mob::from_type(entity_type, position, world).await)
+            .await;
+        spawned += 1;
+    }
+    spawned
+}
+
+/// Picks a random spot in the chunk and the mob to spawn there, then the spots around it the
+/// members of the group spawn at, relative to the chunk. `sky_darkness` is `None` in worlds
+/// without a sky
+fn find_group(
+    chunk: &ChunkData,
+    category: MobCategory,
+    sky_darkness: Option<u8>,
+    rng: &mut impl Rng,
+) -> Option<(EntityType, Vec<Vector3<i32>>)> {
+    let mut x = rng.gen_range(0..16);
+    let mut z = rng.gen_range(0..16);
+    let lowest = i32::from(WORLD_LOWEST_Y) + 1;
+    // Spawning is tried up to the top block, mostly in caves below the surface
+    let top = (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y))
+        .rev()
+        .find(|y| block(chunk, Vector3::new(x, *y, z)).is_some_and(|(_, state)| !state.air))
+        .map_or(lowest, |y| {
+            (y + 1).clamp(lowest, i32::from(WORLD_MAX_Y) - 2)
+        });
+    let y = rng.gen_range(lowest..=top);
+
+    let biome = chunk.biomes.get_biome(Vector3::new(x, y, z).into());
+    let entry = category
+        .spawn_entries(biome)
+        .choose_weighted(rng, |entry| entry.weight)
+        .ok()?;
+    let group = rng.gen_range(entry.min_group..=entry.max_group);
+
+    let mut spots = Vec::new();
+    for _ in 0..group {
+        // Like vanilla every member wanders a bit further from the first spot
+        x += rng.gen_range(0..6) - rng.gen_range(0..6);
+        z += rng.gen_range(0..6) - rng.gen_range(0..6);
+        if !(0..16).contains(&x) || !(0..16).contains(&z) {
+            continue;
+        }
+        let spot = Vector3::new(x, y, z);
+        if can_spawn(chunk, category, entry.entity_type, spot, sky_darkness, rng) {
+            spots.push(spot);
+        }
+    }
+    Some((entry.entity_type, spots))
+}
+
+/// Whether the mob can spawn at the spot: it needs room to stand on solid ground and the right
+/// light for its category
+fn can_spawn(
+    chunk: &ChunkData,
+    category: MobCategory,
+    entity_type: EntityType,
+    spot: Vector3<i32>,
+    sky_darkness: Option<u8>,
+    rng: &mut impl Rng,
+) -> bool {
+    let is_free = |position| {
+        block(chunk, position)
+            .is_some_and(|(_, state)| state.collision_shapes.is_empty() && !state.is_liquid)
+    };
+    let below = Vector3::new(spot.x, spot.y - 1, spot.z);
+    let Some((ground, ground_state)) = block(chunk, below) else {
+        return false;
+    };
+    if !is_full_cube(ground_state.id)
+        || !is_free(spot)
+        || !is_free(Vector3::new(spot.x, spot.y + 1, spot.z))
+    {
+        return false;
+    }
+
+    let sky_light = chunk.get_sky_light(spot.into()).unwrap_or(15);
+    let block_light = chunk.get_block_light(spot.into()).unwrap_or(0);
+    let light = sky_darkness
+        .map_or(0, |darkness| sky_light.saturating_sub(darkness))
+        .max(block_light);
+    match category {
+        MobCategory::Monster => {
+            (sky_darkness.is_none() || block_light == 0) && light <= rng.gen_range(0..=7)
+        }
+        MobCategory::Creature => {
+            sky_light.max(block_light) > 8
+                && ground
+                    .is_tagged_with(creature_ground(entity_type))
+                    .unwrap_or_default()
+        }
+        MobCategory::Ambient => spot.y < SEA_LEVEL && light <= rng.gen_range(0..4),
+    }
+}
+
+fn block(chunk: &ChunkData, position: Vector3<i32>) -> Option<(Block, BlockState)> {
+    chunk
+        .subchunks
+        .get_block(position.into())
+        .and_then(get_block_and_state_by_state_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pumpkin_data::{chunk::Biome, entity::EntityType};
+    use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+    use pumpkin_world::{
+        block::registry::get_block,
+        chunk::{ChunkBiomes, ChunkData, ChunkHeightmaps, ChunkLight, Subchunks},
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::{MobCategory, can_spawn};
+
+    /// A chunk of air with a floor of the block at y 0
+    fn chunk_with_floor(floor: &str) -> ChunkData {
+        let mut chunk = ChunkData {
+            subchunks: Subchunks::Single(0),
+            heightmap: ChunkHeightmaps::default(),
+            biomes: ChunkBiomes::Single(Biome::Plains),
+            light: ChunkLight::default(),
+            block_entities: HashMap::new(),
+            position: Vector2::new(0, 0),
+            dirty: false,
+        };
+        let floor = get_block(floor).unwrap().default_state_id;
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.set_block(Vector3::new(x, 0, z).into(), floor);
+            }
+        }
+        chunk
+    }
+
+    #[test]
+    fn animals_spawn_on_grass_in_the_light() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let spot = Vector3::new(3, 1, 3);
+        let grass = chunk_with_floor("minecraft:grass_block");
+        let stone = chunk_with_floor("minecraft:stone");
+
+        let spawns = |chunk: &ChunkData, rng: &mut StdRng| {
+            can_spawn(
+                chunk,
+                MobCategory::Creature,
+                EntityType::COW,
+                spot,
+                Some(0),
+                rng,
+            )
+        };
+        assert!(spawns(&grass, &mut rng));
+        assert!(!spawns(&stone, &mut rng));
+        // Standing in the floor
+        assert!(!can_spawn(
+            &grass,
+            MobCategory::Creature,
+            EntityType::COW,
+            Vector3::new(3, 0, 3),
+            Some(0),
+            &mut rng
+        ));
+    }
+
+    #[test]
+    fn monsters_spawn_in_the_dark() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let spot = Vector3::new(3, 1, 3);
+        let mut chunk = chunk_with_floor("minecraft:stone");
+
+        let spawns = |chunk: &ChunkData, sky_darkness, rng: &mut StdRng| {
+            can_spawn(
+                chunk,
+                MobCategory::Monster,
+                EntityType::ZOMBIE,
+                spot,
+                sky_darkness,
+                rng,
+            )
+        };
+        // Full sky light during the day
+        assert!(!spawns(&chunk, Some(0), &mut rng));
+
+        chunk.set_sky_light(spot.into(), 0);
+        assert!(spawns(&chunk, Some(0), &mut rng));
+
+        chunk.set_block_light(spot.into(), 1);
+        assert!(!spawns(&chunk, Some(0), &mut rng));
+    }
+}
@@ -1,7 +1,29 @@
-use pumpkin_protocol::client::play::CUpdateTime;
+use std::f64::consts::PI;
+
+use pumpkin_world::world_info::LevelData;
 
 use super::World;
 
+/// A time of day only a single player sees, see `Player::set_client_time`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientTime {
+    /// Always shows this time of day, the sun doesn't move
+    Fixed(i64),
+    /// Shows the time of the world moved by this many ticks, the day keeps passing
+    Offset(i64),
+}
+
+impl ClientTime {
+    /// The time of day to show and whether it keeps increasing on the client
+    #[must_use]
+    pub const fn time_of_day(self, world_time: i64) -> (i64, bool) {
+        match self {
+            Self::Fixed(time) => (time, false),
+            Self::Offset(offset) => (world_time + offset, true),
+        }
+    }
+}
+
 pub struct LevelTime {
     pub world_age: i64,
     pub time_of_day: i64,
@@ -24,16 +46,36 @@ impl LevelTime {
         }
     }
 
-    pub fn tick_time(&mut self) {
+    /// The time saved in the level info
+    #[must_use]
+    pub const fn from_level_info(info: &LevelData) -> Self {
+        Self {
+            world_age: info.time,
+            time_of_day: info.day_time,
+            rain_time: 0,
+        }
+    }
+
+    /// Puts the time into the level info to be saved
+    pub const fn write_level_info(&self, info: &mut LevelData) {
+        info.time = self.world_age;
+        info.day_time = self.time_of_day;
+    }
+
+    /// Moves the time one tick forward, the time of day only when the daylight cycle is on
+    pub fn tick_time(&mut self, daylight_cycle: bool) {
         self.world_age += 1;
-        self.time_of_day += 1;
+        if daylight_cycle {
+            self.time_of_day += 1;
+        }
         self.rain_time += 1;
     }
 
+    /// Sends the time to every player in the world, players with their own time get theirs
     pub async fn send_time(&self, world: &World) {
-        world
-            .broadcast_packet_all(&CUpdateTime::new(self.world_age, self.time_of_day, true))
-            .await;
+        for player in world.players.read().await.values() {
+            player.send_level_time(self).await;
+        }
     }
 
     pub fn add_time(&mut self, time: i64) {
@@ -58,4 +100,37 @@ impl LevelTime {
     pub const fn query_day(&self) -> i64 {
         self.time_of_day / 24000
     }
+
+    /// How many levels the sky light is darkened by at this time of day, from 0 during the day
+    /// to 11 at night
+    #[must_use]
+    pub fn sky_darkness(&self) -> u8 {
+        // The angle of the sun, 0 at noon and 0.5 at midnight
+        let day = ((self.time_of_day % 24000) as f64 / 24000.0 - 0.25).rem_euclid(1.0);
+        let angle = (day * 2.0 + (0.5 - (day * PI).cos() / 2.0)) / 3.0;
+        let darkness = 1.0 - ((angle * 2.0 * PI).cos() * 2.0 + 0.5);
+        (darkness.clamp(0.0, 1.0) * 11.0) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientTime, LevelTime};
+
+    #[test]
+    fn sky_darkness() {
+        let time = |time_of_day| LevelTime {
+            time_of_day,
+            ..LevelTime::new()
+        };
+        assert_eq!(time(6000).sky_darkness(), 0);
+        assert_eq!(time(18000).sky_darkness(), 11);
+        assert_eq!(time(24000 + 18000).sky_darkness(), 11);
+    }
+
+    #[test]
+    fn client_time_of_day() {
+        assert_eq!(ClientTime::Fixed(6000).time_of_day(100), (6000, false));
+        assert_eq!(ClientTime::Offset(12000).time_of_day(100), (12100, true));
+    }
 }
@@ -1,5 +1,6 @@
 use super::World;
 use pumpkin_protocol::client::play::{CGameEvent, GameEvent};
+use pumpkin_world::world_info::{LevelData, game_rules};
 use rand::Rng;
 
 // Weather timing constants
@@ -14,6 +15,26 @@ const THUNDER_DURATION_MAX: i32 = 15_600;
 
 const WEATHER_TRANSITION_SPEED: f32 = 0.01;
 
+/// Weather only a single player sees, see `Player::set_client_weather`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientWeather {
+    Clear,
+    Rain,
+    Thunder,
+}
+
+impl ClientWeather {
+    /// Whether it rains, the rain level and the thunder level
+    #[must_use]
+    pub const fn levels(self) -> (bool, f32, f32) {
+        match self {
+            Self::Clear => (false, 0.0, 0.0),
+            Self::Rain => (true, 1.0, 0.0),
+            Self::Thunder => (true, 1.0, 1.0),
+        }
+    }
+}
+
 pub struct Weather {
     pub clear_weather_time: i32,
     pub raining: bool,
@@ -25,8 +46,6 @@ pub struct Weather {
     pub old_rain_level: f32,
     pub thunder_level: f32,
     pub old_thunder_level: f32,
-
-    pub weather_cycle_enabled: bool,
 }
 
 impl Default for Weather {
@@ -48,10 +67,36 @@ impl Weather {
             old_rain_level: 0.0,
             thunder_level: 0.0,
             old_thunder_level: 0.0,
-            weather_cycle_enabled: true,
         }
     }
 
+    /// The weather saved in the level info
+    #[must_use]
+    pub fn from_level_info(info: &LevelData) -> Self {
+        let rain_level = if info.raining { 1.0 } else { 0.0 };
+        let thunder_level = if info.thundering { 1.0 } else { 0.0 };
+        Self {
+            clear_weather_time: info.clear_weather_time,
+            raining: info.raining,
+            rain_time: info.rain_time,
+            thundering: info.thundering,
+            thunder_time: info.thunder_time,
+            rain_level,
+            old_rain_level: rain_level,
+            thunder_level,
+            old_thunder_level: thunder_level,
+        }
+    }
+
+    /// Puts the weather into the level info to be saved
+    pub const fn write_level_info(&self, info: &mut LevelData) {
+        info.clear_weather_time = self.clear_weather_time;
+        info.raining = self.raining;
+        info.rain_time = self.rain_time;
+        info.thundering = self.thundering;
+        info.thunder_time = self.thunder_time;
+    }
+
     pub async fn set_weather_parameters(
         &mut self,
         world: &World,
@@ -71,18 +116,18 @@ impl Weather {
         if was_raining != raining {
             if was_raining {
                 world
-                    .broadcast_packet_all(&CGameEvent::new(GameEvent::EndRaining, 0.0))
+                    .broadcast_weather_packet(&CGameEvent::new(GameEvent::EndRaining, 0.0))
                     .await;
             } else {
                 world
-                    .broadcast_packet_all(&CGameEvent::new(GameEvent::BeginRaining, 0.0))
+                    .broadcast_weather_packet(&CGameEvent::new(GameEvent::BeginRaining, 0.0))
                     .await;
             }
         }
     }
 
     pub async fn tick_weather(&mut self, world: &World) {
-        if !self.weather_cycle_enabled {
+        if world.game_rule(game_rules::DO_WEATHER_CYCLE) {
             self.advance_weather_cycle();
         }
 
@@ -105,7 +150,7 @@ impl Weather {
         // Broadcast level changes if needed
         if (self.old_rain_level - self.rain_level).abs() > f32::EPSILON {
             world
-                .broadcast_packet_all(&CGameEvent::new(
+                .broadcast_weather_packet(&CGameEvent::new(
                     GameEvent::RainLevelChange,
                     self.rain_level,
                 ))
@@ -114,7 +159,7 @@ impl Weather {
 
         if (self.old_thunder_level - self.thunder_level).abs() > f32::EPSILON {
             world
-                .broadcast_packet_all(&CGameEvent::new(
+                .broadcast_weather_packet(&CGameEvent::new(
                     GameEvent::ThunderLevelChange,
                     self.thunder_level,
                 ))
@@ -177,7 +222,6 @@ impl Clone for Weather {
             old_rain_level: self.old_rain_level,
             thunder_level: self.thunder_level,
             old_thunder_level: self.old_thunder_level,
-            weather_cycle_enabled: self.weather_cycle_enabled,
         }
     }
 }